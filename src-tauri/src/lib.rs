@@ -4,11 +4,18 @@ mod ytdlp;
 use specta_typescript::{BigIntExportBehavior, Typescript};
 use std::sync::Arc;
 use tauri::{Emitter, Manager};
+use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
+use tauri_plugin_store::StoreExt;
 use tauri_specta::{collect_commands, collect_events};
 
 pub mod modules {
+    pub mod data_dir;
+    pub mod legacy_data;
+    pub mod log_burst;
     pub mod log_commands;
     pub mod log_db;
+    pub mod log_summary;
+    pub mod log_tail;
     pub mod logger;
     pub mod types;
 }
@@ -22,48 +29,146 @@ pub fn run() {
     let builder = tauri_specta::Builder::<tauri::Wry>::new()
         .commands(collect_commands![
             ytdlp::commands::check_dependencies,
+            ytdlp::commands::refresh_binary_resolution,
             ytdlp::commands::update_ytdlp,
+            ytdlp::commands::cancel_update,
+            ytdlp::commands::rollback_ytdlp_update,
+            ytdlp::commands::list_ytdlp_backups,
+            ytdlp::commands::install_ffmpeg_version,
+            ytdlp::commands::rollback_ffmpeg,
+            ytdlp::commands::unpin_ffmpeg,
             ytdlp::commands::get_download_queue,
             ytdlp::commands::clear_completed,
+            ytdlp::commands::renumber_playlist_tasks,
             ytdlp::commands::retry_download,
+            ytdlp::commands::set_task_retry_policy,
+            ytdlp::commands::drain_and_exit,
+            ytdlp::commands::get_download_limits,
+            ytdlp::commands::get_bandwidth_usage,
             ytdlp::commands::get_settings,
+            ytdlp::commands::export_settings_redacted,
+            ytdlp::commands::check_host_policy,
+            ytdlp::commands::validate_settings,
             ytdlp::commands::update_settings,
+            ytdlp::commands::resolve_effective_options,
             ytdlp::commands::select_download_directory,
+            ytdlp::commands::get_recent_directories,
             ytdlp::commands::get_available_browsers,
+            ytdlp::commands::list_browser_profiles,
+            ytdlp::commands::get_default_browser,
+            ytdlp::commands::lint_output_template,
             ytdlp::commands::get_download_history,
             ytdlp::commands::check_duplicate,
+            ytdlp::commands::block_video,
+            ytdlp::commands::unblock_video,
+            ytdlp::commands::list_blocked,
             ytdlp::commands::delete_history_item,
+            ytdlp::commands::get_storage_by_directory,
+            ytdlp::commands::get_history_detail,
+            ytdlp::commands::get_throughput_history,
+            ytdlp::commands::bulk_tag,
+            ytdlp::commands::bulk_untag,
+            ytdlp::commands::toggle_favorite,
+            ytdlp::commands::preview_rename,
+            ytdlp::commands::apply_rename,
+            ytdlp::commands::export_playlist,
+            ytdlp::commands::find_similar,
+            ytdlp::commands::verify_history_item,
+            ytdlp::commands::rerun_postprocessing,
+            ytdlp::commands::backfill_durations,
+            ytdlp::commands::normalize_history,
+            ytdlp::commands::redownload_with_options,
             ytdlp::commands::get_active_downloads,
             ytdlp::commands::get_download_queue_paginated,
             ytdlp::commands::get_queue_summary,
+            ytdlp::commands::get_queue_grouped,
+            ytdlp::commands::watch_queue,
+            ytdlp::commands::get_total_speed,
+            ytdlp::commands::get_active_progress,
+            ytdlp::commands::get_stalled_tasks,
+            ytdlp::commands::set_focused_task,
+            ytdlp::commands::clear_focused_task,
+            ytdlp::commands::import_urls_from_file,
+            ytdlp::commands::estimate_download_size,
             ytdlp::metadata::validate_url,
             ytdlp::metadata::fetch_video_info,
+            ytdlp::metadata::format_recommendations,
             ytdlp::metadata::fetch_playlist_info,
             ytdlp::metadata::fetch_quick_metadata,
+            ytdlp::metadata::preview_output_filename,
             ytdlp::download::start_download,
             ytdlp::download::add_to_queue,
+            ytdlp::download::features_requiring_ffmpeg,
+            ytdlp::download::test_format_selection,
             ytdlp::download::cancel_download,
             ytdlp::download::cancel_all_downloads,
+            ytdlp::download::reset_manager_slots,
+            ytdlp::download::check_queue_stall,
+            ytdlp::download::get_next_start,
+            ytdlp::download::cancel_matching,
             ytdlp::download::pause_download,
             ytdlp::download::resume_download,
             ytdlp::commands::set_minimize_to_tray,
             ytdlp::commands::get_recent_logs,
             ytdlp::commands::get_cached_dep_status,
             ytdlp::commands::check_full_dependencies,
+            ytdlp::commands::get_binary_info,
+            ytdlp::commands::check_ytdlp_config_conflicts,
+            ytdlp::commands::health_check,
+            ytdlp::commands::diagnose_ytdlp_binary,
+            ytdlp::commands::test_network,
+            ytdlp::commands::get_network_report,
+            ytdlp::commands::get_app_info,
+            ytdlp::commands::diff_extractors,
+            ytdlp::commands::list_ytdlp_binaries,
+            ytdlp::commands::set_active_ytdlp,
+            ytdlp::commands::benchmark_ytdlp_spawn,
+            ytdlp::commands::watch_source,
+            ytdlp::commands::list_watched_sources,
+            ytdlp::commands::unwatch_source,
+            ytdlp::commands::save_preset,
+            ytdlp::commands::list_presets,
+            ytdlp::commands::delete_preset,
             ytdlp::commands::install_dependency,
             ytdlp::commands::install_all_dependencies,
             ytdlp::commands::check_dependency_update,
             ytdlp::commands::update_dependency,
             ytdlp::commands::delete_app_managed_dep,
             ytdlp::commands::reset_all_data,
+            ytdlp::commands::list_leftover_files,
+            ytdlp::commands::clean_leftover_files,
+            ytdlp::commands::get_cache_stats,
+            ytdlp::commands::clear_cache,
+            ytdlp::commands::prune_cache,
+            ytdlp::commands::schedule_idle_cleanup,
+            ytdlp::commands::export_full_backup,
+            ytdlp::commands::export_diagnostics,
+            ytdlp::commands::import_full_backup,
+            ytdlp::commands::check_app_data_writable,
+            ytdlp::commands::migrate_legacy_data,
+            ytdlp::commands::check_case_sensitivity,
+            ytdlp::commands::get_power_status,
+            ytdlp::commands::get_battery_state,
+            ytdlp::commands::get_cpu_load_state,
+            ytdlp::commands::list_orphaned_processes,
+            ytdlp::commands::reap_orphaned_processes,
+            ytdlp::commands::validate_cookies_file,
             modules::log_commands::get_logs,
+            modules::log_commands::search_logs,
             modules::log_commands::get_log_stats,
             modules::log_commands::clear_logs,
+            modules::log_commands::archive_logs,
+            modules::log_commands::export_task_report,
+            modules::log_commands::reclassify_logs,
+            modules::log_commands::summarize_errors,
+            modules::log_commands::get_log_histogram,
+            modules::log_commands::tail_logs,
         ])
         .events(collect_events![
             ytdlp::types::GlobalDownloadEvent,
             ytdlp::types::DepInstallEvent,
             ytdlp::types::NewLogEvent,
+            ytdlp::types::QueueDrainEvent,
         ]);
 
     #[cfg(debug_assertions)]
@@ -89,10 +194,54 @@ pub fn run() {
             app.handle()
                 .plugin(tauri_plugin_updater::Builder::new().build())?;
             builder.mount_events(app);
-            let app_data_dir = app
+            // settings.json always lives in the OS app data dir; the data dir override
+            // (logs, download DB, binaries) is read from it before anything is opened
+            let default_data_dir = app
                 .path()
                 .app_data_dir()
                 .expect("Failed to get app data directory");
+            // Bring data over from older layouts before anything reads or creates it
+            let started_at = chrono::Utc::now().timestamp();
+            let legacy = modules::legacy_data::run(
+                &default_data_dir,
+                &modules::legacy_data::candidates(app.handle()),
+            );
+            let settings =
+                ytdlp::settings::get_settings_from_path(&default_data_dir).unwrap_or_default();
+            let selection = modules::data_dir::select(
+                &default_data_dir,
+                settings.data_dir_override.as_deref(),
+                modules::data_dir::read_previous_dir(&default_data_dir).as_deref(),
+            );
+            if selection.settled {
+                if let Ok(store) = app.store("settings.json") {
+                    if store.delete(modules::data_dir::PREVIOUS_DIR_KEY) {
+                        let _ = store.save();
+                    }
+                }
+            }
+            let app_data_dir = selection.dir;
+            modules::data_dir::init(app_data_dir.clone());
+
+            // Fail early with the OS error instead of a confusing DB/binary error later
+            let writable = modules::data_dir::check_writable(&app_data_dir);
+            if !writable.writable {
+                let error = writable.error.unwrap_or_default();
+                eprintln!(
+                    "App data directory is not writable: {} ({})",
+                    writable.path, error
+                );
+                let handle = app.handle().clone();
+                app.dialog()
+                    .message(format!(
+                        "앱 데이터 폴더에 쓸 수 없습니다.\n\n{}\n{}\n\n폴더 권한을 확인하거나 다른 데이터 폴더를 선택한 후 다시 실행하세요.",
+                        writable.path, error
+                    ))
+                    .title("데이터 폴더 오류")
+                    .kind(MessageDialogKind::Error)
+                    .show(move |_| handle.exit(1));
+                return Ok(());
+            }
 
             modules::logger::init(app_data_dir.clone());
 
@@ -104,12 +253,25 @@ pub fn run() {
             modules::logger::init_app_handle(app.handle().clone());
             app.manage(log_db.clone());
 
-            // Cleanup old logs on startup (30 days, 50k max)
-            if let Err(e) = log_db.cleanup_old_logs(30, 50000) {
-                eprintln!("Failed to cleanup old logs: {}", e);
-            }
-
             modules::logger::info_cat("app", "Application started");
+            for note in &selection.notes {
+                modules::logger::warn_cat("app", note);
+            }
+            if let Some(error) = &legacy.error {
+                modules::logger::warn_cat(
+                    "app",
+                    &format!("Legacy data migration failed, will retry: {}", error),
+                );
+            } else if !legacy.migrated.is_empty() && legacy.ran_at >= started_at {
+                modules::logger::info_cat(
+                    "app",
+                    &format!(
+                        "Migrated legacy data from {}: {}",
+                        legacy.source.as_deref().unwrap_or_default(),
+                        legacy.migrated.join(", ")
+                    ),
+                );
+            }
 
             let db =
                 ytdlp::db::Database::new(&app_data_dir).expect("Failed to initialize database");
@@ -124,27 +286,64 @@ pub fn run() {
             }
             app.manage(Arc::new(db));
 
+            // yt-dlp/ffmpeg left running by a crashed session still hold file locks
+            if let Err(e) = ytdlp::orphans::find_orphans(app.handle(), true) {
+                modules::logger::warn_cat(
+                    "app",
+                    &format!("Failed to check for orphaned processes: {}", e),
+                );
+            }
+
             // Initialize DownloadManager with max_concurrent from settings
-            let settings =
-                ytdlp::settings::get_settings_from_path(&app_data_dir).unwrap_or_default();
             let download_manager = Arc::new(ytdlp::download::DownloadManager::new(
                 settings.max_concurrent,
             ));
+            download_manager.set_start_delay(settings.start_delay_secs.min(3600));
+            modules::log_burst::set_policy(settings.log_burst_policy);
             app.manage(download_manager);
+            ytdlp::download::limits::apply_settings(app.handle(), &settings);
+            ytdlp::download::limits::seed_daily(app.handle());
 
             // Setup system tray
             ytdlp::tray::setup_tray(&app.handle().clone()).expect("Failed to setup system tray");
 
+            let startup_network_check = settings.startup_network_check;
+
             // Process any pending downloads left from a previous session.
             // These are items that were 'pending' (not 'downloading') when the app closed,
             // so reset_stale_downloads() does not touch them.
             let handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
+                // Nothing is downloading yet, so leftover partials from the last session are stale
+                ytdlp::download::cleanup_stale_temp(&handle, &settings);
+                // Cache entries no queued download needs any more
+                if let Err(e) = ytdlp::cache::prune(&handle) {
+                    modules::logger::warn_cat("app", &format!("Failed to prune cache: {}", e));
+                }
                 // Small delay to let the app fully initialize before processing
                 tokio::time::sleep(std::time::Duration::from_millis(300)).await;
                 ytdlp::download::process_next_pending_public(handle);
             });
 
+            // Log expiry now; log trimming and VACUUM once the queue is idle
+            ytdlp::maintenance::spawn_maintenance(app.handle().clone());
+
+            // Restart the queue if pending tasks stop starting with slots free
+            ytdlp::download::watchdog::spawn_watchdog(app.handle().clone());
+
+            // Re-queue downloads interrupted by a system sleep
+            ytdlp::power::spawn_wake_watcher(app.handle().clone());
+            ytdlp::battery::spawn_watcher(app.handle().clone());
+            ytdlp::cpu_load::spawn_watcher(app.handle().clone());
+
+            // Resume polling watched channels/playlists for new uploads
+            ytdlp::watch::spawn_watcher(app.handle().clone());
+
+            // Check GitHub/extractor reachability without holding up startup
+            if startup_network_check {
+                ytdlp::network::spawn_startup_check(app.handle().clone());
+            }
+
             // Warmup yt-dlp in background to prime OS file cache (PyInstaller cold start mitigation)
             ytdlp::binary::warmup_ytdlp(app.handle().clone());
 
@@ -152,6 +351,15 @@ pub fn run() {
         })
         .invoke_handler(invoke_handler)
         .on_window_event(|window, event| {
+            // Window brought back by other means (dock icon, taskbar): lift the throttle
+            // try_state: setup bails out before managing state when the data dir is unwritable
+            if let tauri::WindowEvent::Focused(true) = event {
+                if let Some(manager) = window.app_handle().try_state::<DownloadManagerState>() {
+                    if manager.is_throttled() {
+                        ytdlp::tray::apply_background_throttle(window.app_handle(), false);
+                    }
+                }
+            }
             if let tauri::WindowEvent::CloseRequested { api, .. } = event {
                 let app = window.app_handle();
                 let setting = ytdlp::tray::get_minimize_to_tray_setting(app);
@@ -159,7 +367,7 @@ pub fn run() {
                     Some(true) => {
                         // Minimize to tray
                         api.prevent_close();
-                        let _ = window.hide();
+                        ytdlp::tray::hide_main_window(app);
                     }
                     Some(false) => {
                         // Let window close normally (cancel_all runs in RunEvent::Exit)
@@ -176,8 +384,9 @@ pub fn run() {
         .expect("error while building tauri application")
         .run(|app_handle, event| {
             if let tauri::RunEvent::Exit = event {
-                let manager = app_handle.state::<DownloadManagerState>();
-                manager.cancel_all();
+                if let Some(manager) = app_handle.try_state::<DownloadManagerState>() {
+                    manager.cancel_all();
+                }
             }
         });
 }
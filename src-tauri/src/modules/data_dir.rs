@@ -0,0 +1,386 @@
+use crate::modules::types::AppError;
+use crate::ytdlp::types::WritableCheck;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+/// Effective data dir for this process, fixed at startup.
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Settings store key recording the data dir in use when `dataDirOverride` last changed,
+/// so the next startup knows where to migrate from.
+pub const PREVIOUS_DIR_KEY: &str = "dataDirPrevious";
+
+/// Everything that lives in the data dir. settings.json and other stores stay in the
+/// OS app data dir, since that is where the override itself is read from.
+pub(super) const DATA_ITEMS: &[&str] = &[
+    "ytdlp.db",
+    "ytdlp.db-wal",
+    "ytdlp.db-shm",
+    "logs.db",
+    "logs.db-wal",
+    "logs.db-shm",
+    "log.txt",
+    "bin",
+];
+
+pub fn init(dir: PathBuf) {
+    let _ = DATA_DIR.set(dir);
+}
+
+/// Data dir in effect (override if applied at startup, else the OS app data dir).
+pub fn resolve(app: &AppHandle) -> Result<PathBuf, AppError> {
+    match DATA_DIR.get() {
+        Some(dir) => Ok(dir.clone()),
+        None => app
+            .path()
+            .app_data_dir()
+            .map_err(|e| AppError::Custom(format!("Failed to get app data dir: {}", e))),
+    }
+}
+
+/// Create, write, and delete a probe file in an existing `dir`. Unlike the
+/// read-only attribute this reflects ownership and ACLs.
+pub fn probe_writable(dir: &Path) -> std::io::Result<()> {
+    let probe = dir.join(format!(".write-test-{}", std::process::id()));
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&probe)?;
+    file.write_all(b"ok")?;
+    file.sync_all()?;
+    drop(file);
+    std::fs::remove_file(&probe)
+}
+
+/// Try to create, write, and delete a probe file in `dir` (creating `dir` if needed).
+/// This is the first thing that fails on a read-only or locked-down data dir, so the
+/// OS error is reported as-is instead of surfacing later as a DB or binary error.
+pub fn check_writable(dir: &Path) -> WritableCheck {
+    let result = std::fs::create_dir_all(dir).and_then(|()| probe_writable(dir));
+
+    let path = dir.to_string_lossy().to_string();
+    match result {
+        Ok(()) => WritableCheck {
+            path,
+            writable: true,
+            error: None,
+            error_kind: None,
+        },
+        Err(e) => WritableCheck {
+            path,
+            writable: false,
+            error: Some(e.to_string()),
+            error_kind: Some(format!("{:?}", e.kind())),
+        },
+    }
+}
+
+/// Remember the data dir in use now so the next startup migrates from it.
+/// If a switch is already pending, its source still holds the data, so keep it.
+pub fn record_pending_switch(app: &AppHandle) -> Result<(), AppError> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Custom(e.to_string()))?;
+    if store.get(PREVIOUS_DIR_KEY).is_some() {
+        return Ok(());
+    }
+    let current = resolve(app)?;
+    store.set(
+        PREVIOUS_DIR_KEY,
+        serde_json::Value::String(current.to_string_lossy().to_string()),
+    );
+    store.save().map_err(|e| AppError::Custom(e.to_string()))
+}
+
+pub(super) fn has_data(dir: &Path) -> bool {
+    dir.join("ytdlp.db").exists() || dir.join("logs.db").exists()
+}
+
+/// Refuse to switch to a dir (the override, or the OS app data dir when cleared)
+/// that already holds another copy of the app data: migration never overwrites,
+/// so starting there would leave the data in use now behind.
+pub fn check_switch_target(app: &AppHandle, override_dir: Option<&str>) -> Result<(), AppError> {
+    let target = match override_dir.filter(|d| !d.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => app
+            .path()
+            .app_data_dir()
+            .map_err(|e| AppError::Custom(format!("Failed to get app data dir: {}", e)))?,
+    };
+    if target != resolve(app)? && has_data(&target) {
+        return Err(AppError::FileError(format!(
+            "{} already contains app data; empty it or choose another directory",
+            target.display()
+        )));
+    }
+    Ok(())
+}
+
+pub(super) fn copy_recursive(from: &Path, to: &Path) -> std::io::Result<()> {
+    if from.is_dir() {
+        std::fs::create_dir_all(to)?;
+        for entry in std::fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        std::fs::copy(from, to).map(|_| ())
+    }
+}
+
+fn files_equal(a: &Path, b: &Path) -> std::io::Result<bool> {
+    if std::fs::metadata(a)?.len() != std::fs::metadata(b)?.len() {
+        return Ok(false);
+    }
+    let mut fa = std::io::BufReader::new(std::fs::File::open(a)?);
+    let mut fb = std::io::BufReader::new(std::fs::File::open(b)?);
+    let mut buf_a = [0u8; 64 * 1024];
+    let mut buf_b = [0u8; 64 * 1024];
+    loop {
+        let n = fa.read(&mut buf_a)?;
+        if n == 0 {
+            return Ok(true);
+        }
+        fb.read_exact(&mut buf_b[..n])?;
+        if buf_a[..n] != buf_b[..n] {
+            return Ok(false);
+        }
+    }
+}
+
+pub(super) fn verify_recursive(from: &Path, to: &Path) -> std::io::Result<bool> {
+    if from.is_dir() {
+        for entry in std::fs::read_dir(from)? {
+            let entry = entry?;
+            if !verify_recursive(&entry.path(), &to.join(entry.file_name()))? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    } else {
+        files_equal(from, to)
+    }
+}
+
+pub(super) fn remove_item(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
+
+/// Move data items from `from` to `to`: copy, verify byte-for-byte, then remove the
+/// originals. Runs before any database is opened. On copy/verify failure, everything
+/// copied is removed again and the originals are untouched.
+fn migrate(from: &Path, to: &Path) -> Result<(), String> {
+    let items: Vec<&str> = DATA_ITEMS
+        .iter()
+        .copied()
+        .filter(|item| from.join(item).exists())
+        .collect();
+
+    let copied = items.iter().try_for_each(|item| {
+        copy_recursive(&from.join(item), &to.join(item))
+            .map_err(|e| format!("copy {}: {}", item, e))?;
+        match verify_recursive(&from.join(item), &to.join(item)) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(format!("verify {}: contents differ", item)),
+            Err(e) => Err(format!("verify {}: {}", item, e)),
+        }
+    });
+
+    if let Err(e) = copied {
+        for item in &items {
+            let _ = remove_item(&to.join(item));
+        }
+        return Err(e);
+    }
+
+    // Swap: the copies are verified, so the originals can go
+    for item in &items {
+        let _ = remove_item(&from.join(item));
+    }
+    Ok(())
+}
+
+pub struct DataDirSelection {
+    pub dir: PathBuf,
+    /// Messages to log once the logger is up
+    pub notes: Vec<String>,
+    /// The configured dir is in use; no migration is left to retry
+    pub settled: bool,
+}
+
+/// Read the pending migration source straight from settings.json (the store isn't loaded yet).
+pub fn read_previous_dir(default_dir: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(default_dir.join("settings.json")).ok()?;
+    let value: serde_json::Value = serde_json::from_str(&content).ok()?;
+    value.get(PREVIOUS_DIR_KEY)?.as_str().map(String::from)
+}
+
+/// Pick the data dir at startup and migrate existing data when it changed.
+/// Falls back to the previous (or default) dir if the override is invalid or
+/// migration fails, so the app never starts against an empty copy by mistake.
+pub fn select(
+    default_dir: &Path,
+    override_dir: Option<&str>,
+    previous_dir: Option<&str>,
+) -> DataDirSelection {
+    let mut notes = Vec::new();
+    let previous = previous_dir.map(PathBuf::from);
+    let fallback = previous
+        .clone()
+        .filter(|p| has_data(p))
+        .unwrap_or_else(|| default_dir.to_path_buf());
+
+    let target = match override_dir.filter(|d| !d.is_empty()) {
+        Some(dir) => {
+            let problem = if Path::new(dir).is_absolute() {
+                check_writable(Path::new(dir)).error
+            } else {
+                Some("not an absolute path".to_string())
+            };
+            match problem {
+                None => PathBuf::from(dir),
+                Some(problem) => {
+                    notes.push(format!(
+                        "Data dir override {} is not usable ({}); using {}",
+                        dir,
+                        problem,
+                        fallback.display()
+                    ));
+                    return DataDirSelection {
+                        dir: fallback,
+                        notes,
+                        settled: false,
+                    };
+                }
+            }
+        }
+        None => default_dir.to_path_buf(),
+    };
+
+    if let Some(prev) = previous.filter(|p| *p != target) {
+        if has_data(&prev) && has_data(&target) {
+            // Either copy would be lost; leave both alone until one is cleared
+            notes.push(format!(
+                "{} already contains app data; not moving data from {}, still using it",
+                target.display(),
+                prev.display()
+            ));
+            return DataDirSelection {
+                dir: prev,
+                notes,
+                settled: false,
+            };
+        }
+        if has_data(&prev) {
+            match migrate(&prev, &target) {
+                Ok(()) => notes.push(format!(
+                    "Migrated app data from {} to {}",
+                    prev.display(),
+                    target.display()
+                )),
+                Err(e) => {
+                    notes.push(format!(
+                        "Failed to migrate app data to {} ({}); still using {}",
+                        target.display(),
+                        e,
+                        prev.display()
+                    ));
+                    return DataDirSelection {
+                        dir: prev,
+                        notes,
+                        settled: false,
+                    };
+                }
+            }
+        }
+    }
+
+    DataDirSelection {
+        dir: target,
+        notes,
+        settled: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_writable_creates_and_cleans_up() {
+        let dir = std::env::temp_dir().join(format!("ytdlp-writable-{}", std::process::id()));
+        let result = check_writable(&dir);
+        assert!(result.writable, "{:?}", result.error);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+        let _ = std::fs::remove_dir(&dir);
+    }
+
+    #[test]
+    fn select_migrates_from_previous_dir() {
+        let root = std::env::temp_dir().join(format!("ytdlp-datadir-{}", std::process::id()));
+        let old = root.join("old");
+        let new = root.join("new");
+        std::fs::create_dir_all(old.join("bin")).unwrap();
+        std::fs::write(old.join("ytdlp.db"), b"queue").unwrap();
+        std::fs::write(old.join("bin").join("yt-dlp"), b"binary").unwrap();
+
+        let selection = select(
+            &old,
+            Some(new.to_str().unwrap()),
+            Some(old.to_str().unwrap()),
+        );
+        assert_eq!(selection.dir, new);
+        assert!(selection.settled);
+        assert_eq!(selection.notes.len(), 1);
+        assert_eq!(std::fs::read(new.join("ytdlp.db")).unwrap(), b"queue");
+        assert_eq!(
+            std::fs::read(new.join("bin").join("yt-dlp")).unwrap(),
+            b"binary"
+        );
+        assert!(!old.join("ytdlp.db").exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn select_keeps_previous_dir_when_target_has_data() {
+        let root = std::env::temp_dir().join(format!("ytdlp-datadir-both-{}", std::process::id()));
+        let old = root.join("old");
+        let new = root.join("new");
+        std::fs::create_dir_all(&old).unwrap();
+        std::fs::create_dir_all(&new).unwrap();
+        std::fs::write(old.join("ytdlp.db"), b"current").unwrap();
+        std::fs::write(new.join("ytdlp.db"), b"other").unwrap();
+
+        let selection = select(
+            &old,
+            Some(new.to_str().unwrap()),
+            Some(old.to_str().unwrap()),
+        );
+        assert_eq!(selection.dir, old);
+        assert!(!selection.settled);
+        assert_eq!(selection.notes.len(), 1);
+        assert_eq!(std::fs::read(old.join("ytdlp.db")).unwrap(), b"current");
+        assert_eq!(std::fs::read(new.join("ytdlp.db")).unwrap(), b"other");
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn select_falls_back_on_relative_override() {
+        let default = std::env::temp_dir();
+        let selection = select(&default, Some("relative/dir"), None);
+        assert_eq!(selection.dir, default);
+        assert!(!selection.settled);
+        assert_eq!(selection.notes.len(), 1);
+    }
+}
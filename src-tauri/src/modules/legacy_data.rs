@@ -0,0 +1,151 @@
+//! One-time copy of app data from places earlier builds may have written to.
+//!
+//! Tauri 1 resolved the app dir to the OS config dir, which on Linux
+//! (`~/.config/<identifier>`) is not today's `app_data_dir`
+//! (`~/.local/share/<identifier>`); `app_local_data_dir` differs on Windows
+//! (`%LOCALAPPDATA%`). When the current dir isn't in use yet and one of those
+//! holds data, its databases, logs, binaries and settings are copied over and
+//! verified byte for byte. The originals stay where they are. The outcome is
+//! written to `legacy-migration.json` so the scan doesn't repeat.
+
+use super::data_dir::{self, DATA_ITEMS};
+use crate::ytdlp::types::LegacyMigration;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+const MARKER_FILE: &str = "legacy-migration.json";
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Data dirs of the current identifier other than `app_data_dir`.
+pub fn candidates(app: &AppHandle) -> Vec<PathBuf> {
+    let path = app.path();
+    [path.app_config_dir(), path.app_local_data_dir()]
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+fn in_use(dir: &Path) -> bool {
+    data_dir::has_data(dir) || dir.join(SETTINGS_FILE).exists()
+}
+
+/// Outcome recorded by an earlier run, if any.
+pub fn recorded(dir: &Path) -> Option<LegacyMigration> {
+    let content = std::fs::read_to_string(dir.join(MARKER_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn record(dir: &Path, result: &LegacyMigration) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let json = serde_json::to_string_pretty(result).map_err(std::io::Error::other)?;
+    std::fs::write(dir.join(MARKER_FILE), json)
+}
+
+/// Copy legacy data into `dir` (the OS app data dir) unless that was done
+/// before. Runs before settings are read or any database is opened. A failed
+/// copy is removed again and not recorded, so the next start retries it.
+pub fn run(dir: &Path, candidates: &[PathBuf]) -> LegacyMigration {
+    if let Some(done) = recorded(dir) {
+        return done;
+    }
+    let mut result = LegacyMigration {
+        source: None,
+        migrated: Vec::new(),
+        kept_current: false,
+        error: None,
+        ran_at: chrono::Utc::now().timestamp(),
+    };
+    let source = candidates.iter().find(|c| c.as_path() != dir && in_use(c));
+
+    if let Some(source) = source {
+        result.source = Some(source.to_string_lossy().to_string());
+        if in_use(dir) {
+            // Data written by this layout wins over anything older
+            result.kept_current = true;
+        } else {
+            let items: Vec<&str> = DATA_ITEMS
+                .iter()
+                .copied()
+                .chain([SETTINGS_FILE])
+                .filter(|item| source.join(item).exists())
+                .collect();
+            let copied = items.iter().try_for_each(|item| {
+                data_dir::copy_recursive(&source.join(item), &dir.join(item))
+                    .map_err(|e| format!("copy {}: {}", item, e))?;
+                match data_dir::verify_recursive(&source.join(item), &dir.join(item)) {
+                    Ok(true) => Ok(()),
+                    Ok(false) => Err(format!("verify {}: contents differ", item)),
+                    Err(e) => Err(format!("verify {}: {}", item, e)),
+                }
+            });
+            if let Err(e) = copied {
+                for item in &items {
+                    let _ = data_dir::remove_item(&dir.join(item));
+                }
+                result.error = Some(e);
+                return result;
+            }
+            result.migrated = items.iter().map(|i| i.to_string()).collect();
+        }
+    }
+
+    if let Err(e) = record(dir, &result) {
+        result.error = Some(format!("record migration: {}", e));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("ytdlp-legacy-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        root
+    }
+
+    #[test]
+    fn copies_legacy_data_once_and_keeps_the_originals() {
+        let root = temp_root("copy");
+        let old = root.join("config");
+        let new = root.join("data");
+        std::fs::create_dir_all(old.join("bin")).unwrap();
+        std::fs::write(old.join("ytdlp.db"), b"history").unwrap();
+        std::fs::write(old.join("settings.json"), b"{}").unwrap();
+        std::fs::write(old.join("bin").join("yt-dlp"), b"binary").unwrap();
+
+        let result = run(&new, &[new.clone(), old.clone()]);
+        assert_eq!(result.error, None);
+        assert_eq!(result.source.as_deref(), old.to_str());
+        assert_eq!(result.migrated, ["ytdlp.db", "bin", "settings.json"]);
+        assert_eq!(std::fs::read(new.join("ytdlp.db")).unwrap(), b"history");
+        assert!(old.join("ytdlp.db").exists());
+
+        // Recorded: a second start doesn't copy again
+        std::fs::write(old.join("logs.db"), b"logs").unwrap();
+        assert_eq!(run(&new, &[old.clone()]).migrated, result.migrated);
+        assert!(!new.join("logs.db").exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn current_data_wins_over_legacy_data() {
+        let root = temp_root("keep");
+        let old = root.join("config");
+        let new = root.join("data");
+        std::fs::create_dir_all(&old).unwrap();
+        std::fs::create_dir_all(&new).unwrap();
+        std::fs::write(old.join("ytdlp.db"), b"old").unwrap();
+        std::fs::write(new.join("ytdlp.db"), b"new").unwrap();
+
+        let result = run(&new, &[old.clone()]);
+        assert!(result.kept_current);
+        assert!(result.migrated.is_empty());
+        assert_eq!(std::fs::read(new.join("ytdlp.db")).unwrap(), b"new");
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,155 @@
+//! Coalescing of log bursts (`log_burst_policy`).
+//!
+//! A `--verbose` download can print thousands of lines a second, and every
+//! line costs a DB insert, a tail broadcast and a frontend event. Past
+//! `BURST_THRESHOLD` lines in a one-second window, DEBUG/INFO lines are
+//! sampled or dropped depending on the policy, and a single
+//! "… N similar lines …" entry per category records what was left out.
+//! WARN and ERROR are always written.
+//!
+//! The tradeoff: `Full` keeps every line but lets a burst slow the app and
+//! flood the log viewer; `Sampled` keeps 1 in `SAMPLE_EVERY` so the shape of
+//! the output survives; `Summarized` keeps only the counts. The summary is
+//! written with the next line logged after the window closes, so a burst at
+//! the very end of a session may go unsummarized.
+
+use crate::ytdlp::types::LogBurstPolicy;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+/// Lines per window before coalescing starts
+pub const BURST_THRESHOLD: u32 = 200;
+/// Window the threshold applies to
+const WINDOW: Duration = Duration::from_secs(1);
+/// `Sampled` keeps one line in this many past the threshold
+pub const SAMPLE_EVERY: u32 = 10;
+
+static POLICY: AtomicU8 = AtomicU8::new(policy_code(LogBurstPolicy::Summarized));
+static LIMITER: LazyLock<Mutex<BurstLimiter>> =
+    LazyLock::new(|| Mutex::new(BurstLimiter::new(Instant::now())));
+
+const fn policy_code(policy: LogBurstPolicy) -> u8 {
+    match policy {
+        LogBurstPolicy::Full => 0,
+        LogBurstPolicy::Sampled => 1,
+        LogBurstPolicy::Summarized => 2,
+    }
+}
+
+pub fn set_policy(policy: LogBurstPolicy) {
+    POLICY.store(policy_code(policy), Ordering::Relaxed);
+}
+
+fn policy() -> LogBurstPolicy {
+    match POLICY.load(Ordering::Relaxed) {
+        0 => LogBurstPolicy::Full,
+        1 => LogBurstPolicy::Sampled,
+        _ => LogBurstPolicy::Summarized,
+    }
+}
+
+/// Whether to write a line, plus per-category counts of lines left out of
+/// windows that have just closed (to be written as summaries first).
+pub fn admit(level: &str, category: &str) -> (bool, Vec<(String, u32)>) {
+    LIMITER.lock().unwrap_or_else(|e| e.into_inner()).admit(
+        policy(),
+        level,
+        category,
+        Instant::now(),
+    )
+}
+
+pub fn summary_message(count: u32) -> String {
+    format!("… {} similar lines suppressed during a log burst …", count)
+}
+
+struct BurstLimiter {
+    window_start: Instant,
+    count: u32,
+    suppressed: BTreeMap<String, u32>,
+}
+
+impl BurstLimiter {
+    fn new(now: Instant) -> Self {
+        Self {
+            window_start: now,
+            count: 0,
+            suppressed: BTreeMap::new(),
+        }
+    }
+
+    fn admit(
+        &mut self,
+        policy: LogBurstPolicy,
+        level: &str,
+        category: &str,
+        now: Instant,
+    ) -> (bool, Vec<(String, u32)>) {
+        let mut flushed = Vec::new();
+        if now.duration_since(self.window_start) >= WINDOW {
+            flushed = std::mem::take(&mut self.suppressed).into_iter().collect();
+            self.window_start = now;
+            self.count = 0;
+        }
+        self.count = self.count.saturating_add(1);
+
+        let important = matches!(level, "ERROR" | "WARN");
+        let keep = important
+            || self.count <= BURST_THRESHOLD
+            || match policy {
+                LogBurstPolicy::Full => true,
+                LogBurstPolicy::Sampled => {
+                    (self.count - BURST_THRESHOLD).is_multiple_of(SAMPLE_EVERY)
+                }
+                LogBurstPolicy::Summarized => false,
+            };
+        if !keep {
+            *self.suppressed.entry(category.to_string()).or_insert(0) += 1;
+        }
+        (keep, flushed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(policy: LogBurstPolicy, lines: u32, level: &str) -> (u32, BurstLimiter) {
+        let start = Instant::now();
+        let mut limiter = BurstLimiter::new(start);
+        let kept = (0..lines)
+            .filter(|_| limiter.admit(policy, level, "download", start).0)
+            .count() as u32;
+        (kept, limiter)
+    }
+
+    #[test]
+    fn full_keeps_everything() {
+        assert_eq!(run(LogBurstPolicy::Full, 1000, "INFO").0, 1000);
+    }
+
+    #[test]
+    fn summarized_drops_past_threshold_and_reports_counts() {
+        let (kept, mut limiter) = run(LogBurstPolicy::Summarized, 540, "INFO");
+        assert_eq!(kept, BURST_THRESHOLD);
+
+        let later = limiter.window_start + WINDOW;
+        let (keep, flushed) = limiter.admit(LogBurstPolicy::Summarized, "INFO", "app", later);
+        assert!(keep);
+        assert_eq!(flushed, vec![("download".to_string(), 340)]);
+    }
+
+    #[test]
+    fn sampled_keeps_one_in_n() {
+        let (kept, _) = run(LogBurstPolicy::Sampled, BURST_THRESHOLD + 100, "DEBUG");
+        assert_eq!(kept, BURST_THRESHOLD + 100 / SAMPLE_EVERY);
+    }
+
+    #[test]
+    fn warnings_and_errors_are_never_dropped() {
+        assert_eq!(run(LogBurstPolicy::Summarized, 1000, "WARN").0, 1000);
+        assert_eq!(run(LogBurstPolicy::Summarized, 1000, "ERROR").0, 1000);
+    }
+}
@@ -1,7 +1,20 @@
+use crate::modules::log_db::match_ranges;
+use crate::modules::log_tail::{self, CompiledLogFilter};
 use crate::modules::types::AppError;
-use crate::ytdlp::types::{LogQueryResult, LogStats};
+use crate::ytdlp::types::{
+    ErrorSummary, LogArchiveResult, LogBucket, LogEntry, LogQueryResult, LogSearchHit,
+    LogSearchResult, LogStats, LogTailFilter, ReclassifyResult, ReportFormat, TaskReportResult,
+};
+use tauri::ipc::Channel;
 use tauri::{AppHandle, Manager};
 
+/// The `log_max_page_size` setting, or the default when settings can't be read.
+fn max_page_size(app: &AppHandle) -> u32 {
+    crate::ytdlp::settings::get_settings(app)
+        .map(|s| s.log_max_page_size)
+        .unwrap_or_else(|_| crate::ytdlp::types::AppSettings::default().log_max_page_size)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn get_logs(
@@ -17,6 +30,7 @@ pub async fn get_logs(
     log_db.query_logs(
         page,
         page_size,
+        max_page_size(&app),
         level.as_deref(),
         category.as_deref(),
         search.as_deref(),
@@ -24,6 +38,63 @@ pub async fn get_logs(
     )
 }
 
+/// Same as `get_logs`, plus byte ranges of `search` matches in each entry's
+/// message and details so the log viewer can highlight them.
+#[tauri::command]
+#[specta::specta]
+pub async fn search_logs(
+    app: AppHandle,
+    page: u32,
+    page_size: u32,
+    level: Option<String>,
+    category: Option<String>,
+    search: Option<String>,
+    since: Option<i64>,
+) -> Result<LogSearchResult, AppError> {
+    let log_db = app.state::<crate::LogDbState>();
+    let result = log_db.query_logs(
+        page,
+        page_size,
+        max_page_size(&app),
+        level.as_deref(),
+        category.as_deref(),
+        search.as_deref(),
+        since,
+    )?;
+
+    let term = search.as_deref().unwrap_or("");
+    let items = result
+        .items
+        .into_iter()
+        .map(|entry| {
+            let (message_matches, details_matches) = if term.is_empty() {
+                (Vec::new(), Vec::new())
+            } else {
+                (
+                    match_ranges(&entry.message, term),
+                    entry
+                        .details
+                        .as_deref()
+                        .map(|d| match_ranges(d, term))
+                        .unwrap_or_default(),
+                )
+            };
+            LogSearchHit {
+                entry,
+                message_matches,
+                details_matches,
+            }
+        })
+        .collect();
+
+    Ok(LogSearchResult {
+        items,
+        total_count: result.total_count,
+        page: result.page,
+        page_size: result.page_size,
+    })
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn get_log_stats(app: AppHandle) -> Result<LogStats, AppError> {
@@ -37,3 +108,167 @@ pub async fn clear_logs(app: AppHandle, before_timestamp: Option<i64>) -> Result
     let log_db = app.state::<crate::LogDbState>();
     log_db.clear_logs(before_timestamp)
 }
+
+/// Move logs at or before `before_timestamp` into a gzip-compressed JSONL file.
+#[tauri::command]
+#[specta::specta]
+pub async fn archive_logs(
+    app: AppHandle,
+    path: String,
+    before_timestamp: i64,
+) -> Result<LogArchiveResult, AppError> {
+    let path = crate::ytdlp::security::sanitize_output_path(&path)?;
+    if std::path::Path::new(&path).exists() {
+        return Err(AppError::FileError(format!(
+            "Archive file already exists: {}",
+            path
+        )));
+    }
+    let log_db = app.state::<crate::LogDbState>();
+    let archived = log_db.archive_logs(std::path::Path::new(&path), before_timestamp)?;
+
+    crate::modules::logger::info_cat(
+        "app",
+        &format!("Archived {} log entries to {}", archived, path),
+    );
+    Ok(LogArchiveResult { archived, path })
+}
+
+/// Classify failed-download logs written before classification existed.
+/// Already classified rows are left alone, so it is safe to run repeatedly.
+#[tauri::command]
+#[specta::specta]
+pub async fn reclassify_logs(app: AppHandle) -> Result<ReclassifyResult, AppError> {
+    const BATCH_SIZE: u32 = 500;
+    let log_db = app.state::<crate::LogDbState>();
+    let by_kind = log_db.reclassify(BATCH_SIZE)?;
+    let classified = by_kind.iter().map(|c| c.count).sum();
+    crate::modules::logger::info_cat(
+        "app",
+        &format!("Reclassified {} download error log(s)", classified),
+    );
+    Ok(ReclassifyResult {
+        classified,
+        by_kind,
+    })
+}
+
+/// Digest of ERROR/WARN logs between `since` and `until` (ms timestamps,
+/// `until` defaults to now): counts per kind and category, top messages and
+/// the tasks involved.
+#[tauri::command]
+#[specta::specta]
+pub async fn summarize_errors(
+    app: AppHandle,
+    since: i64,
+    until: Option<i64>,
+) -> Result<ErrorSummary, AppError> {
+    let until = until.unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+    if since > until {
+        return Err(AppError::Custom(
+            "since must not be after until".to_string(),
+        ));
+    }
+    let log_db = app.state::<crate::LogDbState>();
+    log_db.summarize_errors(since, until)
+}
+
+/// Log counts per `bucket_secs` between `since` and `until` (ms timestamps,
+/// `until` defaults to now), split by level, for an activity chart. At most
+/// 500 buckets come back: longer ranges get wider buckets. `level` counts
+/// only that level.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_log_histogram(
+    app: AppHandle,
+    bucket_secs: u32,
+    since: i64,
+    until: Option<i64>,
+    level: Option<String>,
+) -> Result<Vec<LogBucket>, AppError> {
+    let until = until.unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+    if since < 0 || since > until {
+        return Err(AppError::Custom(
+            "since must be a timestamp not after until".to_string(),
+        ));
+    }
+    let bucket_ms = crate::modules::log_db::histogram_bucket_ms(bucket_secs, since, until);
+    let log_db = app.state::<crate::LogDbState>();
+    log_db.log_histogram(bucket_ms, since, until, level.as_deref())
+}
+
+/// Write one download's row, redacted command and tagged logs to `path` for a
+/// bug report. `format` defaults to JSON for a `.json` path, plain text otherwise.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_task_report(
+    app: AppHandle,
+    task_id: u64,
+    path: String,
+    format: Option<ReportFormat>,
+) -> Result<TaskReportResult, AppError> {
+    use crate::ytdlp::task_report;
+    const MAX_LOGS: u32 = 5000;
+
+    let path = crate::ytdlp::security::sanitize_output_path(&path)?;
+    let format = format.unwrap_or_else(|| {
+        if path.to_ascii_lowercase().ends_with(".json") {
+            ReportFormat::Json
+        } else {
+            ReportFormat::Text
+        }
+    });
+    let task = app
+        .state::<crate::DbState>()
+        .get_download(task_id)?
+        .ok_or_else(|| AppError::DownloadError(format!("Task {} not found", task_id)))?;
+    let logs = app
+        .state::<crate::LogDbState>()
+        .logs_mentioning(&[task_report::log_tag(task_id)], MAX_LOGS)?;
+    let log_count = logs.len() as u32;
+
+    let report = task_report::build(app.package_info().version.to_string(), task, logs);
+    let contents = task_report::render(&report, format)?;
+    tokio::fs::write(&path, contents)
+        .await
+        .map_err(|e| AppError::FileError(format!("Failed to write task report: {}", e)))?;
+
+    crate::modules::logger::info_cat(
+        "app",
+        &format!("Exported report for task {} to {}", task_id, path),
+    );
+    Ok(TaskReportResult { path, log_count })
+}
+
+/// Stream new log entries matching `filter` as they are written. The filter is
+/// compiled once and evaluated on this subscription's task, never in the logger.
+/// Entries a slow subscriber falls behind on are skipped.
+#[tauri::command]
+#[specta::specta]
+pub async fn tail_logs(
+    filter: Option<LogTailFilter>,
+    on_entry: Channel<LogEntry>,
+) -> Result<(), AppError> {
+    let filter = CompiledLogFilter::new(&filter.unwrap_or_default());
+    let mut rx = log_tail::subscribe();
+
+    tokio::spawn(async move {
+        use tokio::sync::broadcast::error::RecvError;
+        loop {
+            let entry = match rx.recv().await {
+                Ok(entry) => entry,
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            };
+            if !filter.matches(&entry) {
+                continue;
+            }
+            // Webview gone or channel dropped: stop forwarding
+            if on_entry.send(entry).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(())
+}
@@ -1,6 +1,11 @@
+use crate::modules::log_summary::{self, GroupRow, MessageRow};
 use crate::modules::types::AppError;
-use crate::ytdlp::types::{LogEntry, LogQueryResult, LogStats};
+use crate::ytdlp::error_kind::classify_download_error;
+use crate::ytdlp::types::{
+    ErrorKindCount, ErrorSummary, LogBucket, LogEntry, LogQueryResult, LogStats, MatchRange,
+};
 use rusqlite::{params, Connection};
+use std::io::Write;
 use std::path::Path;
 use std::sync::Mutex;
 
@@ -52,9 +57,36 @@ impl LogDatabase {
         )
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
+        // Download failure kind (`classify_download_error`), added after release
+        let has_classification = conn
+            .prepare("SELECT 1 FROM pragma_table_info('logs') WHERE name = 'classification'")
+            .and_then(|mut stmt| stmt.exists([]))
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        if !has_classification {
+            conn.execute_batch("ALTER TABLE logs ADD COLUMN classification TEXT;")
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
         Ok(())
     }
 
+    /// Kind of a failed download, for ERROR entries in the download category.
+    fn classification(
+        level: &str,
+        category: &str,
+        message: &str,
+        details: Option<&str>,
+    ) -> Option<&'static str> {
+        if level != "ERROR" || category != "download" {
+            return None;
+        }
+        let text = match details {
+            Some(d) => format!("{}\n{}", message, d),
+            None => message.to_string(),
+        };
+        Some(classify_download_error(&text).as_str())
+    }
+
     pub fn insert_log(
         &self,
         timestamp: i64,
@@ -63,26 +95,31 @@ impl LogDatabase {
         message: &str,
         details: Option<&str>,
     ) -> Result<i64, AppError> {
+        let classification = Self::classification(level, category, message, details);
         let conn = self.conn();
         conn.execute(
-            "INSERT INTO logs (timestamp, level, category, message, details) VALUES (?1, ?2, ?3, ?4, ?5)",
-            params![timestamp, level, category, message, details],
+            "INSERT INTO logs (timestamp, level, category, message, details, classification) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![timestamp, level, category, message, details, classification],
         )
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
         Ok(conn.last_insert_rowid())
     }
 
+    /// `max_page_size` is the `log_max_page_size` setting; pages never exceed
+    /// `PAGE_SIZE_CEILING` whatever it says.
+    #[allow(clippy::too_many_arguments)]
     pub fn query_logs(
         &self,
         page: u32,
         page_size: u32,
+        max_page_size: u32,
         level: Option<&str>,
         category: Option<&str>,
         search: Option<&str>,
         since: Option<i64>,
     ) -> Result<LogQueryResult, AppError> {
-        let page_size = page_size.clamp(1, 200);
+        let (page_size, max_page_size) = effective_page_size(page_size, max_page_size);
         let conn = self.conn();
 
         let mut conditions: Vec<String> = Vec::new();
@@ -171,9 +208,64 @@ impl LogDatabase {
             total_count,
             page,
             page_size,
+            max_page_size,
         })
     }
 
+    /// Entries whose message contains any of `needles`, oldest first.
+    pub fn logs_mentioning(
+        &self,
+        needles: &[String],
+        limit: u32,
+    ) -> Result<Vec<LogEntry>, AppError> {
+        if needles.is_empty() {
+            return Ok(Vec::new());
+        }
+        let conn = self.conn();
+        let conditions: Vec<String> = (1..=needles.len())
+            .map(|i| format!("message LIKE ?{} ESCAPE '\\'", i))
+            .collect();
+        let sql = format!(
+            "SELECT id, timestamp, level, category, message, details FROM (
+                SELECT * FROM logs WHERE {} ORDER BY timestamp DESC, id DESC LIMIT ?{}
+             ) ORDER BY timestamp, id",
+            conditions.join(" OR "),
+            needles.len() + 1
+        );
+        let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = needles
+            .iter()
+            .map(|n| {
+                let escaped = n
+                    .replace('\\', "\\\\")
+                    .replace('%', "\\%")
+                    .replace('_', "\\_");
+                Box::new(format!("%{}%", escaped)) as Box<dyn rusqlite::types::ToSql>
+            })
+            .collect();
+        param_values.push(Box::new(limit));
+        let refs: Vec<&dyn rusqlite::types::ToSql> =
+            param_values.iter().map(|p| p.as_ref()).collect();
+
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let items = stmt
+            .query_map(refs.as_slice(), |row| {
+                Ok(LogEntry {
+                    id: row.get(0)?,
+                    timestamp: row.get(1)?,
+                    level: row.get(2)?,
+                    category: row.get(3)?,
+                    message: row.get(4)?,
+                    details: row.get(5)?,
+                })
+            })
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(items)
+    }
+
     pub fn get_log_stats(&self) -> Result<LogStats, AppError> {
         let conn = self.conn();
 
@@ -213,6 +305,141 @@ impl LogDatabase {
         })
     }
 
+    /// ERROR/WARN entries between `since` and `until` (inclusive), grouped by
+    /// level, category and failure kind.
+    pub fn summarize_errors(&self, since: i64, until: i64) -> Result<ErrorSummary, AppError> {
+        // Distinct messages read back for merging; the rarest beyond this are dropped
+        const MESSAGE_ROWS: u32 = 5000;
+        let conn = self.conn();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT level, category, classification, COUNT(*), MIN(timestamp), MAX(timestamp)
+                 FROM logs
+                 WHERE level IN ('ERROR', 'WARN') AND timestamp BETWEEN ?1 AND ?2
+                 GROUP BY level, category, classification",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let groups = stmt
+            .query_map(params![since, until], |row| {
+                Ok(GroupRow {
+                    level: row.get(0)?,
+                    category: row.get(1)?,
+                    classification: row.get(2)?,
+                    count: row.get(3)?,
+                    first_at: row.get(4)?,
+                    last_at: row.get(5)?,
+                })
+            })
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT level, category, classification, message, COUNT(*) AS n
+                 FROM logs
+                 WHERE level IN ('ERROR', 'WARN') AND timestamp BETWEEN ?1 AND ?2
+                 GROUP BY level, category, classification, message
+                 ORDER BY n DESC
+                 LIMIT ?3",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let messages = stmt
+            .query_map(params![since, until, MESSAGE_ROWS], |row| {
+                Ok(MessageRow {
+                    level: row.get(0)?,
+                    category: row.get(1)?,
+                    classification: row.get(2)?,
+                    message: row.get(3)?,
+                    count: row.get(4)?,
+                })
+            })
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(log_summary::build(since, until, groups, messages))
+    }
+
+    /// Entries between `since` and `until` (inclusive) counted per time bucket
+    /// and level, oldest first. Buckets are `bucket_ms` wide and aligned to
+    /// multiples of it; empty ones are included. `level` counts only that level.
+    pub fn log_histogram(
+        &self,
+        bucket_ms: i64,
+        since: i64,
+        until: i64,
+        level: Option<&str>,
+    ) -> Result<Vec<LogBucket>, AppError> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare(
+                "SELECT (timestamp / ?1) * ?1 AS bucket, COUNT(*),
+                        SUM(level = 'ERROR'), SUM(level = 'WARN'), SUM(level = 'INFO'), SUM(level = 'DEBUG')
+                 FROM logs
+                 WHERE timestamp BETWEEN ?2 AND ?3 AND (?4 IS NULL OR level = ?4)
+                 GROUP BY bucket",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![bucket_ms, since, until, level], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    LogBucket {
+                        start: 0,
+                        end: 0,
+                        total: row.get(1)?,
+                        error: row.get(2)?,
+                        warn: row.get(3)?,
+                        info: row.get(4)?,
+                        debug: row.get(5)?,
+                    },
+                ))
+            })
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let first = since - since.rem_euclid(bucket_ms);
+        let count = (until - first) / bucket_ms + 1;
+        let mut buckets: Vec<LogBucket> = (0..count)
+            .map(|i| LogBucket {
+                start: first + i * bucket_ms,
+                end: first + (i + 1) * bucket_ms,
+                total: 0,
+                error: 0,
+                warn: 0,
+                info: 0,
+                debug: 0,
+            })
+            .collect();
+        for (start, counts) in rows {
+            if let Some(bucket) = buckets.get_mut(((start - first) / bucket_ms) as usize) {
+                *bucket = LogBucket {
+                    start: bucket.start,
+                    end: bucket.end,
+                    ..counts
+                };
+            }
+        }
+        Ok(buckets)
+    }
+
+    /// Move everything in the WAL into the main database file.
+    pub fn checkpoint(&self) -> Result<(), AppError> {
+        let conn = self.conn();
+        conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Run SQLite's quick integrity check. Returns "ok" when healthy.
+    pub fn quick_check(&self) -> Result<String, AppError> {
+        let conn = self.conn();
+        conn.query_row("PRAGMA quick_check", [], |row| row.get(0))
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
     pub fn clear_logs(&self, before_timestamp: Option<i64>) -> Result<u64, AppError> {
         let conn = self.conn();
 
@@ -227,6 +454,122 @@ impl LogDatabase {
         Ok(deleted as u64)
     }
 
+    /// Export logs at or before `before_timestamp` to a gzip-compressed JSONL file,
+    /// then delete them. Rows are only deleted once the archive is fully written,
+    /// and only the rows that went into it.
+    pub fn archive_logs(&self, path: &Path, before_timestamp: i64) -> Result<u64, AppError> {
+        // Read under the lock, then release it so logging isn't blocked while
+        // the archive is compressed and written
+        let entries = {
+            let conn = self.conn();
+            let mut stmt = conn
+                .prepare(
+                    "SELECT id, timestamp, level, category, message, details FROM logs
+                     WHERE timestamp <= ?1 ORDER BY timestamp ASC, id ASC",
+                )
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            let rows = stmt
+                .query_map(params![before_timestamp], |row| {
+                    Ok(LogEntry {
+                        id: row.get(0)?,
+                        timestamp: row.get(1)?,
+                        level: row.get(2)?,
+                        category: row.get(3)?,
+                        message: row.get(4)?,
+                        details: row.get(5)?,
+                    })
+                })
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            rows
+        };
+
+        if entries.is_empty() {
+            return Ok(0);
+        }
+
+        // Write to a temp file first so a partial archive never replaces the target
+        let temp_path = path.with_extension("tmp");
+        if let Err(e) = write_archive(&temp_path, &entries) {
+            let _ = std::fs::remove_file(&temp_path);
+            return Err(e);
+        }
+        std::fs::rename(&temp_path, path).map_err(|e| {
+            let _ = std::fs::remove_file(&temp_path);
+            AppError::FileError(format!("Failed to finalize log archive: {}", e))
+        })?;
+
+        // Ids are AUTOINCREMENT, so rows logged meanwhile all sit above max_id
+        let max_id = entries.iter().map(|e| e.id).max().unwrap_or(0);
+        let deleted = self
+            .conn()
+            .execute(
+                "DELETE FROM logs WHERE timestamp <= ?1 AND id <= ?2",
+                params![before_timestamp, max_id],
+            )
+            .map_err(|e| {
+                // Logs stay in the DB; drop the archive so a retry doesn't duplicate them
+                let _ = std::fs::remove_file(path);
+                AppError::DatabaseError(e.to_string())
+            })?;
+
+        Ok(deleted as u64)
+    }
+
+    /// Classify download ERROR entries that have no kind yet, `batch_size` rows per
+    /// transaction so logging isn't blocked for long. Returns the rows classified
+    /// per kind; rerunning only picks up rows added without a kind.
+    pub fn reclassify(&self, batch_size: u32) -> Result<Vec<ErrorKindCount>, AppError> {
+        let mut counts: Vec<ErrorKindCount> = Vec::new();
+        loop {
+            let mut conn = self.conn();
+            let tx = conn
+                .transaction()
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            let rows: Vec<(i64, String, Option<String>)> = {
+                let mut stmt = tx
+                    .prepare(
+                        "SELECT id, message, details FROM logs
+                         WHERE level = 'ERROR' AND category = 'download' AND classification IS NULL
+                         ORDER BY id LIMIT ?1",
+                    )
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                let rows = stmt
+                    .query_map([batch_size.max(1)], |row| {
+                        Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+                    })
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                rows
+            };
+            if rows.is_empty() {
+                break;
+            }
+            for (id, message, details) in &rows {
+                let text = match details {
+                    Some(d) => format!("{}\n{}", message, d),
+                    None => message.clone(),
+                };
+                let kind = classify_download_error(&text);
+                tx.execute(
+                    "UPDATE logs SET classification = ?1 WHERE id = ?2",
+                    params![kind.as_str(), id],
+                )
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                match counts.iter_mut().find(|c| c.kind == kind) {
+                    Some(c) => c.count += 1,
+                    None => counts.push(ErrorKindCount { kind, count: 1 }),
+                }
+            }
+            tx.commit()
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+        counts.sort_by_key(|c| std::cmp::Reverse(c.count));
+        Ok(counts)
+    }
+
     /// Delete all log data (used by factory reset).
     /// Uses the live connection instead of deleting the DB file.
     pub fn clear_all_data(&self) -> Result<(), AppError> {
@@ -238,33 +581,213 @@ impl LogDatabase {
         Ok(())
     }
 
-    pub fn cleanup_old_logs(&self, max_age_days: u32, max_entries: u64) -> Result<u64, AppError> {
-        let conn = self.conn();
-        let mut total_deleted = 0u64;
-
-        // Delete by age
+    /// Delete logs older than `max_age_days`. Uses the timestamp index, so it is
+    /// cheap enough to run while downloads are active.
+    pub fn delete_expired_logs(&self, max_age_days: u32) -> Result<u64, AppError> {
         let cutoff =
             chrono::Utc::now().timestamp_millis() - (max_age_days as i64 * 24 * 60 * 60 * 1000);
-        let deleted = conn
+        let deleted = self
+            .conn()
             .execute("DELETE FROM logs WHERE timestamp < ?1", params![cutoff])
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        total_deleted += deleted as u64;
+        Ok(deleted as u64)
+    }
 
-        // Delete excess entries (keep newest max_entries)
+    /// Keep only the newest `max_entries` logs. Can delete most of the table.
+    pub fn trim_logs(&self, max_entries: u64) -> Result<u64, AppError> {
+        let conn = self.conn();
         let count: u64 = conn
             .query_row("SELECT COUNT(*) FROM logs", [], |row| row.get(0))
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        if count <= max_entries {
+            return Ok(0);
+        }
+        let deleted = conn
+            .execute(
+                "DELETE FROM logs WHERE id NOT IN (SELECT id FROM logs ORDER BY timestamp DESC, id DESC LIMIT ?1)",
+                params![max_entries],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(deleted as u64)
+    }
+
+    /// Rebuild logs.db to return deleted pages to the OS. Returns bytes freed.
+    pub fn vacuum(&self) -> Result<u64, AppError> {
+        crate::ytdlp::db::vacuum(&self.conn())
+    }
+
+    /// Write a consistent copy of logs.db to `dest`, which must not exist.
+    pub fn snapshot_to(&self, dest: &Path) -> Result<(), AppError> {
+        self.conn()
+            .execute("VACUUM INTO ?1", [dest.to_string_lossy()])
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Add the logs of a backup's logs.db. `replace` drops the current logs first;
+    /// otherwise entries already present are skipped. Returns how many were added.
+    pub fn restore_from(&self, path: &Path, replace: bool) -> Result<u32, AppError> {
+        // Backups from before a column was added get it here
+        let backup = Connection::open(path).map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Self::create_tables(&backup)?;
+        drop(backup);
 
-        if count > max_entries {
-            let deleted = conn
+        let mut conn = self.conn();
+        conn.execute("ATTACH DATABASE ?1 AS bk", [path.to_string_lossy()])
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let result = (|| {
+            let tx = conn
+                .transaction()
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            if replace {
+                tx.execute("DELETE FROM main.logs", [])
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            }
+            let added = tx
                 .execute(
-                    "DELETE FROM logs WHERE id NOT IN (SELECT id FROM logs ORDER BY timestamp DESC, id DESC LIMIT ?1)",
-                    params![max_entries],
+                    "INSERT INTO main.logs (timestamp, level, category, message, details, classification)
+                     SELECT b.timestamp, b.level, b.category, b.message, b.details, b.classification
+                     FROM bk.logs b
+                     WHERE NOT EXISTS (SELECT 1 FROM main.logs l
+                         WHERE l.timestamp = b.timestamp AND l.category = b.category
+                           AND l.message = b.message)
+                     ORDER BY b.id",
+                    [],
                 )
                 .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-            total_deleted += deleted as u64;
-        }
+            tx.commit()
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            Ok(added as u32)
+        })();
+        let _ = conn.execute("DETACH DATABASE bk", []);
+        result
+    }
+}
+
+/// Byte ranges of non-overlapping matches of `needle` in `haystack`.
+/// Mirrors the `LIKE` search: literal text, ASCII case-insensitive.
+/// ASCII lowercasing keeps byte offsets unchanged, so ranges index the original string.
+pub fn match_ranges(haystack: &str, needle: &str) -> Vec<MatchRange> {
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let haystack = haystack.to_ascii_lowercase();
+    let needle = needle.to_ascii_lowercase();
+    haystack
+        .match_indices(&needle)
+        .map(|(start, m)| MatchRange {
+            start: start as u32,
+            end: (start + m.len()) as u32,
+        })
+        .collect()
+}
+
+/// Write entries as gzip-compressed JSON lines and flush to disk.
+fn write_archive(path: &Path, entries: &[LogEntry]) -> Result<(), AppError> {
+    let file = std::fs::File::create(path)
+        .map_err(|e| AppError::FileError(format!("Failed to create log archive: {}", e)))?;
+    let mut encoder = flate2::write::GzEncoder::new(
+        std::io::BufWriter::new(file),
+        flate2::Compression::default(),
+    );
+    for entry in entries {
+        serde_json::to_writer(&mut encoder, entry)
+            .map_err(|e| AppError::FileError(format!("Failed to write log archive: {}", e)))?;
+        encoder
+            .write_all(b"\n")
+            .map_err(|e| AppError::FileError(format!("Failed to write log archive: {}", e)))?;
+    }
+    let writer = encoder
+        .finish()
+        .map_err(|e| AppError::FileError(format!("Failed to write log archive: {}", e)))?;
+    let file = writer
+        .into_inner()
+        .map_err(|e| AppError::FileError(format!("Failed to write log archive: {}", e)))?;
+    file.sync_all()
+        .map_err(|e| AppError::FileError(format!("Failed to write log archive: {}", e)))
+}
+
+/// Most buckets `log_histogram` is asked for; longer ranges get wider buckets
+pub const MAX_HISTOGRAM_BUCKETS: i64 = 500;
+
+/// Bucket width in ms for `since..=until`: `bucket_secs`, widened to whole
+/// seconds when the range would need more than `MAX_HISTOGRAM_BUCKETS`.
+pub fn histogram_bucket_ms(bucket_secs: u32, since: i64, until: i64) -> i64 {
+    let requested = bucket_secs.max(1) as i64 * 1000;
+    // One bucket short of the cap leaves room for the range not starting on a boundary
+    let span = (until - since + 1).max(1);
+    let needed = (span + MAX_HISTOGRAM_BUCKETS - 2) / (MAX_HISTOGRAM_BUCKETS - 1);
+    requested.max((needed + 999) / 1000 * 1000)
+}
+
+/// Largest page `query_logs` returns, whatever `log_max_page_size` says
+pub const PAGE_SIZE_CEILING: u32 = 2000;
+
+/// Page size actually served and the limit it was clamped to.
+pub fn effective_page_size(requested: u32, configured_max: u32) -> (u32, u32) {
+    let max = configured_max.clamp(1, PAGE_SIZE_CEILING);
+    (requested.clamp(1, max), max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: u32, end: u32) -> MatchRange {
+        MatchRange { start, end }
+    }
+
+    #[test]
+    fn match_ranges_is_ascii_case_insensitive() {
+        assert_eq!(
+            match_ranges("Download failed: ERROR 403, error again", "error"),
+            vec![range(17, 22), range(28, 33)]
+        );
+    }
+
+    #[test]
+    fn match_ranges_treats_like_wildcards_literally() {
+        assert_eq!(match_ranges("100% done_ok", "%"), vec![range(3, 4)]);
+        assert!(match_ranges("abc", "a_c").is_empty());
+    }
+
+    #[test]
+    fn page_size_respects_configured_limit() {
+        assert_eq!(effective_page_size(50, 200), (50, 200));
+        assert_eq!(effective_page_size(200, 200), (200, 200));
+        assert_eq!(effective_page_size(500, 200), (200, 200));
+        assert_eq!(effective_page_size(0, 200), (1, 200));
+    }
+
+    #[test]
+    fn page_size_never_exceeds_ceiling() {
+        assert_eq!(
+            effective_page_size(PAGE_SIZE_CEILING, PAGE_SIZE_CEILING),
+            (PAGE_SIZE_CEILING, PAGE_SIZE_CEILING)
+        );
+        assert_eq!(
+            effective_page_size(u32::MAX, 50_000),
+            (PAGE_SIZE_CEILING, PAGE_SIZE_CEILING)
+        );
+        assert_eq!(effective_page_size(10, 0), (1, 1));
+    }
+
+    #[test]
+    fn histogram_buckets_widen_to_stay_under_the_cap() {
+        let day = 24 * 60 * 60 * 1000;
+        assert_eq!(histogram_bucket_ms(3600, 0, day), 3_600_000);
+        // A week of minutes is ~10k buckets; widened to whole seconds
+        let bucket = histogram_bucket_ms(60, 5, 7 * day);
+        assert_eq!(bucket % 1000, 0);
+        let first = 5 - 5 % bucket;
+        assert!((7 * day - first) / bucket < MAX_HISTOGRAM_BUCKETS);
+        assert_eq!(histogram_bucket_ms(0, 100, 100), 1000);
+    }
 
-        Ok(total_deleted)
+    #[test]
+    fn match_ranges_uses_byte_offsets() {
+        // "다운로드" is 12 bytes in UTF-8
+        assert_eq!(match_ranges("다운로드 OK", "ok"), vec![range(13, 15)]);
+        assert!(match_ranges("anything", "").is_empty());
     }
 }
@@ -0,0 +1,188 @@
+//! Digest of ERROR/WARN logs over a time window (`summarize_errors`).
+//!
+//! Counts and first/last times per group come straight from SQL. Messages are
+//! aggregated in SQL too, but executor lines carry a `[download:N]` tag that
+//! makes every task's copy distinct, so the tag is stripped and the counts are
+//! merged here, keeping the task ids aside.
+
+use crate::ytdlp::types::{DownloadErrorKind, ErrorGroup, ErrorSummary, MessageCount};
+
+/// Messages kept per group
+pub const TOP_MESSAGES: usize = 5;
+/// Task ids kept per group
+pub const MAX_TASK_IDS: usize = 100;
+
+/// Per-group aggregate from SQL.
+pub struct GroupRow {
+    pub level: String,
+    pub category: String,
+    pub classification: Option<String>,
+    pub count: u64,
+    pub first_at: i64,
+    pub last_at: i64,
+}
+
+/// Per-message aggregate from SQL.
+pub struct MessageRow {
+    pub level: String,
+    pub category: String,
+    pub classification: Option<String>,
+    pub message: String,
+    pub count: u64,
+}
+
+/// Split a leading `[download:N] ` tag off a log message.
+pub fn split_task_tag(message: &str) -> (Option<u64>, &str) {
+    let Some(rest) = message.strip_prefix("[download:") else {
+        return (None, message);
+    };
+    let Some((id, text)) = rest.split_once(']') else {
+        return (None, message);
+    };
+    match id.parse() {
+        Ok(id) => (Some(id), text.trim_start()),
+        Err(_) => (None, message),
+    }
+}
+
+pub fn build(
+    since: i64,
+    until: i64,
+    groups: Vec<GroupRow>,
+    messages: Vec<MessageRow>,
+) -> ErrorSummary {
+    let mut out: Vec<ErrorGroup> = groups
+        .iter()
+        .map(|g| ErrorGroup {
+            level: g.level.clone(),
+            category: g.category.clone(),
+            kind: g
+                .classification
+                .as_deref()
+                .and_then(DownloadErrorKind::parse),
+            count: g.count,
+            first_at: g.first_at,
+            last_at: g.last_at,
+            top_messages: Vec::new(),
+            task_ids: Vec::new(),
+        })
+        .collect();
+
+    for row in &messages {
+        let Some(i) = groups.iter().position(|g| {
+            g.level == row.level
+                && g.category == row.category
+                && g.classification == row.classification
+        }) else {
+            continue;
+        };
+        let group = &mut out[i];
+        let (task_id, text) = split_task_tag(&row.message);
+        if let Some(id) = task_id {
+            if let Err(pos) = group.task_ids.binary_search(&id) {
+                group.task_ids.insert(pos, id);
+            }
+        }
+        match group.top_messages.iter_mut().find(|m| m.message == text) {
+            Some(m) => m.count += row.count,
+            None => group.top_messages.push(MessageCount {
+                message: text.to_string(),
+                count: row.count,
+            }),
+        }
+    }
+
+    for group in &mut out {
+        group
+            .top_messages
+            .sort_by(|a, b| b.count.cmp(&a.count).then(a.message.cmp(&b.message)));
+        group.top_messages.truncate(TOP_MESSAGES);
+        group.task_ids.truncate(MAX_TASK_IDS);
+    }
+    out.sort_by_key(|g| std::cmp::Reverse(g.count));
+
+    ErrorSummary {
+        since,
+        until,
+        total: out.iter().map(|g| g.count).sum(),
+        first_at: out.iter().map(|g| g.first_at).min(),
+        last_at: out.iter().map(|g| g.last_at).max(),
+        groups: out,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(classification: Option<&str>, count: u64, first_at: i64, last_at: i64) -> GroupRow {
+        GroupRow {
+            level: "ERROR".into(),
+            category: "download".into(),
+            classification: classification.map(String::from),
+            count,
+            first_at,
+            last_at,
+        }
+    }
+
+    fn message(classification: Option<&str>, message: &str, count: u64) -> MessageRow {
+        MessageRow {
+            level: "ERROR".into(),
+            category: "download".into(),
+            classification: classification.map(String::from),
+            message: message.into(),
+            count,
+        }
+    }
+
+    #[test]
+    fn split_task_tag_reads_executor_prefix() {
+        assert_eq!(
+            split_task_tag("[download:42] timed out"),
+            (Some(42), "timed out")
+        );
+        assert_eq!(
+            split_task_tag("[download:x] timed out"),
+            (None, "[download:x] timed out")
+        );
+        assert_eq!(split_task_tag("timed out"), (None, "timed out"));
+    }
+
+    #[test]
+    fn merges_messages_that_differ_only_by_task() {
+        let summary = build(
+            0,
+            100,
+            vec![group(Some("network"), 3, 10, 30)],
+            vec![
+                message(Some("network"), "[download:7] connection reset", 1),
+                message(Some("network"), "[download:3] connection reset", 1),
+                message(Some("network"), "[download:7] timed out", 1),
+            ],
+        );
+        let g = &summary.groups[0];
+        assert_eq!(g.kind, Some(DownloadErrorKind::Network));
+        assert_eq!(g.task_ids, vec![3, 7]);
+        assert_eq!(g.top_messages[0].message, "connection reset");
+        assert_eq!(g.top_messages[0].count, 2);
+        assert_eq!(g.top_messages[1].count, 1);
+    }
+
+    #[test]
+    fn orders_groups_and_spans_window() {
+        let summary = build(
+            0,
+            100,
+            vec![
+                group(Some("geoBlocked"), 3, 50, 60),
+                group(Some("network"), 14, 20, 40),
+            ],
+            Vec::new(),
+        );
+        assert_eq!(summary.total, 17);
+        assert_eq!(summary.groups[0].kind, Some(DownloadErrorKind::Network));
+        assert_eq!(summary.first_at, Some(20));
+        assert_eq!(summary.last_at, Some(60));
+    }
+}
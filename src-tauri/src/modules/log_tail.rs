@@ -0,0 +1,111 @@
+use crate::ytdlp::types::{LogEntry, LogTailFilter};
+use std::sync::LazyLock;
+use tokio::sync::broadcast;
+
+/// Entries buffered per subscriber; a slower one skips ahead instead of holding up logging.
+const TAIL_CAPACITY: usize = 512;
+
+static TAIL: LazyLock<broadcast::Sender<LogEntry>> =
+    LazyLock::new(|| broadcast::channel(TAIL_CAPACITY).0);
+
+/// Hand a freshly stored entry to live subscribers. Never blocks; filtering happens
+/// on the subscriber side.
+pub fn publish(entry: &LogEntry) {
+    if TAIL.receiver_count() > 0 {
+        let _ = TAIL.send(entry.clone());
+    }
+}
+
+pub fn subscribe() -> broadcast::Receiver<LogEntry> {
+    TAIL.subscribe()
+}
+
+/// `LogTailFilter` normalized once per subscription: upper-cased levels, lower-cased
+/// search text. Empty fields match everything.
+pub struct CompiledLogFilter {
+    levels: Vec<String>,
+    category: Option<String>,
+    search: Option<String>,
+}
+
+impl CompiledLogFilter {
+    pub fn new(filter: &LogTailFilter) -> Self {
+        Self {
+            levels: filter
+                .levels
+                .iter()
+                .map(|l| l.trim().to_ascii_uppercase())
+                .filter(|l| !l.is_empty())
+                .collect(),
+            category: filter
+                .category
+                .as_deref()
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .map(String::from),
+            search: filter
+                .search
+                .as_deref()
+                .filter(|s| !s.is_empty())
+                .map(str::to_lowercase),
+        }
+    }
+
+    /// Case-insensitive substring search covers message and details, like the viewer.
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        if !self.levels.is_empty() && !self.levels.contains(&entry.level) {
+            return false;
+        }
+        if self.category.as_ref().is_some_and(|c| *c != entry.category) {
+            return false;
+        }
+        match &self.search {
+            Some(needle) => {
+                entry.message.to_lowercase().contains(needle)
+                    || entry
+                        .details
+                        .as_deref()
+                        .is_some_and(|d| d.to_lowercase().contains(needle))
+            }
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: &str, category: &str, message: &str) -> LogEntry {
+        LogEntry {
+            id: 1,
+            timestamp: 0,
+            level: level.to_string(),
+            category: category.to_string(),
+            message: message.to_string(),
+            details: Some("stderr: HTTP Error 403".to_string()),
+        }
+    }
+
+    #[test]
+    fn empty_filter_matches_everything() {
+        let filter = CompiledLogFilter::new(&LogTailFilter::default());
+        assert!(filter.matches(&entry("DEBUG", "app", "hello")));
+    }
+
+    #[test]
+    fn filter_combines_level_category_and_search() {
+        let filter = CompiledLogFilter::new(&LogTailFilter {
+            levels: vec!["error".to_string(), "WARN".to_string()],
+            category: Some("download".to_string()),
+            search: Some("http error".to_string()),
+        });
+        assert!(filter.matches(&entry("ERROR", "download", "failed")));
+        assert!(filter.matches(&entry("WARN", "download", "HTTP ERROR 429")));
+        assert!(!filter.matches(&entry("INFO", "download", "failed")));
+        assert!(!filter.matches(&entry("ERROR", "metadata", "failed")));
+        let mut no_details = entry("ERROR", "download", "failed");
+        no_details.details = None;
+        assert!(!filter.matches(&no_details));
+    }
+}
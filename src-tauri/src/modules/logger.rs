@@ -45,8 +45,24 @@ fn maybe_rotate(log_path: &PathBuf) {
     }
 }
 
-/// Core log function with category support
+/// Core log function with category support. Lines past a burst are coalesced
+/// according to `log_burst_policy` before reaching any sink.
 fn write_log_with_category(level: &str, category: &str, message: &str, details: Option<&str>) {
+    let (keep, suppressed) = super::log_burst::admit(level, category);
+    for (summary_category, count) in suppressed {
+        write_entry(
+            "INFO",
+            &summary_category,
+            &super::log_burst::summary_message(count),
+            None,
+        );
+    }
+    if keep {
+        write_entry(level, category, message, details);
+    }
+}
+
+fn write_entry(level: &str, category: &str, message: &str, details: Option<&str>) {
     let now = Local::now();
     let timestamp_millis = chrono::Utc::now().timestamp_millis();
 
@@ -81,23 +97,35 @@ fn write_log_with_category(level: &str, category: &str, message: &str, details:
     // 2. DB logging
     if let Some(db) = LOG_DB.get() {
         if let Ok(id) = db.insert_log(timestamp_millis, level, category, message, details) {
-            // 3. Event emission for live updates
+            let entry = crate::ytdlp::types::LogEntry {
+                id,
+                timestamp: timestamp_millis,
+                level: level.to_string(),
+                category: category.to_string(),
+                message: message.to_string(),
+                details: details.map(|s| s.to_string()),
+            };
+            // 3. Filtered live tails (`tail_logs`)
+            super::log_tail::publish(&entry);
+            // 4. Event emission for live updates
             if let Some(app) = APP_HANDLE.get() {
                 use tauri::Emitter;
-                let entry = crate::ytdlp::types::LogEntry {
-                    id,
-                    timestamp: timestamp_millis,
-                    level: level.to_string(),
-                    category: category.to_string(),
-                    message: message.to_string(),
-                    details: details.map(|s| s.to_string()),
-                };
                 let _ = app.emit("new-log-event", crate::ytdlp::types::NewLogEvent { entry });
             }
         }
     }
 }
 
+/// Checkpoint the log database so nothing is left only in its WAL; called
+/// before a planned exit.
+pub fn flush() {
+    if let Some(db) = LOG_DB.get() {
+        if let Err(e) = db.checkpoint() {
+            eprintln!("[Logger] Failed to flush log database: {}", e);
+        }
+    }
+}
+
 /// Write a log entry (backward compatible - uses "app" category)
 fn write_log(level: &str, message: &str) {
     write_log_with_category(level, "app", message, None);
@@ -0,0 +1,77 @@
+//! Version facts support needs to spot mismatches: the app, the Tauri runtime,
+//! the build target and the yt-dlp releases this app version was tested with.
+
+use super::binary;
+use super::types::AppInfo;
+use std::cmp::Ordering;
+use tauri::AppHandle;
+
+/// Oldest yt-dlp release this app version was tested against
+pub const YTDLP_TESTED_MIN: &str = "2025.01.15";
+/// Newest yt-dlp release this app version was tested against
+pub const YTDLP_TESTED_MAX: &str = "2025.09.26";
+
+/// Numeric parts of a yt-dlp version ("2025.01.15", nightly "2025.01.15.232712").
+fn version_parts(version: &str) -> Option<Vec<u32>> {
+    version
+        .trim()
+        .split('.')
+        .map(|p| p.parse().ok())
+        .collect::<Option<Vec<u32>>>()
+        .filter(|parts| parts.len() >= 3)
+}
+
+/// Where `version` falls relative to the tested range: `Less` when older,
+/// `Greater` when newer, `Equal` inside. None when it can't be parsed.
+pub fn tested_range_position(version: &str) -> Option<Ordering> {
+    let parts = version_parts(version)?;
+    let min = version_parts(YTDLP_TESTED_MIN)?;
+    let max = version_parts(YTDLP_TESTED_MAX)?;
+    // Compare on the release date only, so nightlies of the last tested release count
+    let date = &parts[..3];
+    Some(if date < &min[..] {
+        Ordering::Less
+    } else if date > &max[..] {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    })
+}
+
+pub async fn app_info(app: &AppHandle) -> AppInfo {
+    let ytdlp_version = binary::get_binary_info(app).await.version;
+    AppInfo {
+        app_version: app.package_info().version.to_string(),
+        tauri_version: tauri::VERSION.to_string(),
+        target: format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS),
+        debug_build: cfg!(debug_assertions),
+        ytdlp_tested_min: YTDLP_TESTED_MIN.to_string(),
+        ytdlp_tested_max: YTDLP_TESTED_MAX.to_string(),
+        ytdlp_in_tested_range: ytdlp_version
+            .as_deref()
+            .and_then(tested_range_position)
+            .map(|p| p == Ordering::Equal),
+        ytdlp_version,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_position_by_release_date() {
+        assert_eq!(tested_range_position("2024.12.23"), Some(Ordering::Less));
+        assert_eq!(
+            tested_range_position(YTDLP_TESTED_MIN),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            tested_range_position(&format!("{}.232712", YTDLP_TESTED_MAX)),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(tested_range_position("2099.01.01"), Some(Ordering::Greater));
+        assert_eq!(tested_range_position("unknown"), None);
+        assert_eq!(tested_range_position("2025.01"), None);
+    }
+}
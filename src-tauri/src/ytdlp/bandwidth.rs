@@ -0,0 +1,138 @@
+//! Data usage of completed downloads (`get_bandwidth_usage`) and the
+//! `monthly_data_cap` warning. Bytes are kept per local day, so the monthly
+//! total rolls over at local month boundaries without any reset step.
+
+use crate::modules::logger;
+use crate::modules::types::AppError;
+use crate::ytdlp::security;
+use crate::ytdlp::types::{BandwidthPeriod, BandwidthUsage};
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Share of the monthly cap at which the warning is raised
+pub const WARN_PERCENT: f64 = 90.0;
+
+/// Cap in bytes from the setting; None when unset, invalid or "0".
+pub fn cap_bytes(setting: Option<&str>) -> Option<u64> {
+    setting
+        .and_then(|s| security::sanitize_filesize(s).ok())
+        .and_then(|s| security::parse_filesize(&s))
+        .filter(|b| *b > 0)
+}
+
+fn cap_percent(month_bytes: u64, cap: Option<u64>) -> Option<f64> {
+    cap.map(|c| month_bytes as f64 * 100.0 / c as f64)
+}
+
+/// Start of the local month after `now`.
+pub fn next_month_start<Tz: TimeZone>(now: &DateTime<Tz>) -> i64 {
+    let date = now.date_naive();
+    let (year, month) = match date.month() {
+        12 => (date.year() + 1, 1),
+        m => (date.year(), m + 1),
+    };
+    // A DST change can skip midnight; fall back to "now" rather than guess
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .and_then(|t| t.and_local_timezone(now.timezone()).earliest())
+        .map_or(now.timestamp(), |t| t.timestamp())
+}
+
+/// Usage for `period`, with this month measured against the cap.
+pub fn usage(app: &AppHandle, period: BandwidthPeriod) -> Result<BandwidthUsage, AppError> {
+    let db = app.state::<crate::DbState>();
+    let now = chrono::Local::now();
+    let month = now.format("%Y-%m").to_string();
+    let month_bytes = db.bandwidth_bytes(&month)?;
+    let bytes = match period {
+        BandwidthPeriod::Today => db.bandwidth_bytes(&now.format("%Y-%m-%d").to_string())?,
+        BandwidthPeriod::Month => month_bytes,
+        BandwidthPeriod::AllTime => db.bandwidth_bytes("")?,
+    };
+    let cap = crate::ytdlp::settings::get_settings(app)
+        .ok()
+        .and_then(|s| cap_bytes(s.monthly_data_cap.as_deref()));
+    let percent = cap_percent(month_bytes, cap);
+    Ok(BandwidthUsage {
+        period,
+        bytes,
+        month_bytes,
+        monthly_cap: cap,
+        cap_percent: percent,
+        near_cap: percent.is_some_and(|p| p >= WARN_PERCENT),
+        month_resets_at: next_month_start(&now),
+    })
+}
+
+/// Add the size of a completed download to today's total and warn once when
+/// this month crosses the warning level or the cap itself.
+pub fn record(app: &AppHandle, bytes: u64) {
+    if bytes == 0 {
+        return;
+    }
+    let db = app.state::<crate::DbState>();
+    let now = chrono::Local::now();
+    if let Err(e) = db.add_bandwidth(&now.format("%Y-%m-%d").to_string(), bytes) {
+        logger::warn_cat("download", &format!("Failed to record data usage: {}", e));
+        return;
+    }
+
+    let Ok(usage) = usage(app, BandwidthPeriod::Month) else {
+        return;
+    };
+    let (Some(cap), Some(after)) = (usage.monthly_cap, usage.cap_percent) else {
+        return;
+    };
+    let before = cap_percent(usage.month_bytes.saturating_sub(bytes), Some(cap)).unwrap_or(0.0);
+    if crossed(before, after) {
+        logger::warn_cat(
+            "download",
+            &format!(
+                "Monthly data use at {:.0}% of the cap ({} of {} bytes)",
+                after, usage.month_bytes, cap
+            ),
+        );
+        let _ = app.emit("bandwidth-warning", &usage);
+    }
+}
+
+/// Whether going from `before` to `after` percent passes the warning level or 100%.
+fn crossed(before: f64, after: f64) -> bool {
+    [WARN_PERCENT, 100.0]
+        .iter()
+        .any(|level| before < *level && after >= *level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    #[test]
+    fn cap_parses_like_max_filesize() {
+        assert_eq!(cap_bytes(Some("50G")), Some(50 * 1024 * 1024 * 1024));
+        assert_eq!(cap_bytes(Some("0")), None);
+        assert_eq!(cap_bytes(Some("lots")), None);
+        assert_eq!(cap_bytes(None), None);
+    }
+
+    #[test]
+    fn warns_once_per_level() {
+        assert!(crossed(85.0, 91.0));
+        assert!(!crossed(91.0, 95.0));
+        assert!(crossed(95.0, 100.0));
+        assert!(crossed(50.0, 120.0));
+        assert!(!crossed(101.0, 130.0));
+    }
+
+    #[test]
+    fn month_resets_at_local_month_start() {
+        let tz = FixedOffset::east_opt(9 * 3600).unwrap();
+        let now = tz.with_ymd_and_hms(2026, 12, 31, 23, 30, 0).unwrap();
+        let reset = tz.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(next_month_start(&now), reset.timestamp());
+        let now = tz.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap();
+        let reset = tz.with_ymd_and_hms(2026, 11, 1, 0, 0, 0).unwrap();
+        assert_eq!(next_month_start(&now), reset.timestamp());
+    }
+}
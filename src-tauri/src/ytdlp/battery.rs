@@ -0,0 +1,268 @@
+//! `pauseBelowBatteryPercent`: hold new downloads while the machine runs on
+//! battery below a charge level.
+//!
+//! Best-effort and without extra bindings: the charge is read from
+//! `/sys/class/power_supply` on Linux, `pmset -g batt` on macOS and
+//! `GetSystemPowerStatus` on Windows. When none of these answers (desktops,
+//! VMs, locked-down systems) the state says so and nothing is paused.
+
+use crate::modules::logger;
+use crate::ytdlp::download::DownloadManager;
+use crate::ytdlp::types::BatteryState;
+use std::process::Command;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const TICK: Duration = Duration::from_secs(60);
+
+/// Charge and power source at one moment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Reading {
+    pub percent: u32,
+    pub on_battery: bool,
+}
+
+/// Whether starts should be held for `reading` under `threshold`.
+fn should_pause(reading: Option<Reading>, threshold: Option<u32>) -> bool {
+    match (reading, threshold) {
+        (Some(r), Some(t)) => r.on_battery && r.percent < t,
+        _ => false,
+    }
+}
+
+/// `-InternalBattery-0 (id=1234) 85%; discharging; 3:12 remaining present: true`
+fn parse_pmset(output: &str) -> Option<Reading> {
+    let line = output.lines().find(|l| l.contains("InternalBattery"))?;
+    let percent = line
+        .split_whitespace()
+        .find_map(|w| w.strip_suffix("%;"))?
+        .parse()
+        .ok()?;
+    let on_battery = output.contains("'Battery Power'");
+    Some(Reading {
+        percent,
+        on_battery,
+    })
+}
+
+/// `SYSTEM_POWER_STATUS` fields: AC line status (0 offline, 1 online, 255
+/// unknown), battery flag bits (8 charging, 128 no battery, 255 unknown) and
+/// percent (255 unknown). Anything but mains power or charging counts as on
+/// battery, which includes the low and critical states.
+fn parse_power_status(ac_line: u8, flag: u8, percent: u8) -> Option<Reading> {
+    if flag == 255 || flag & 128 != 0 || percent > 100 {
+        return None;
+    }
+    Some(Reading {
+        percent: percent.into(),
+        on_battery: ac_line != 1 && flag & 8 == 0,
+    })
+}
+
+/// Raw `GetSystemPowerStatus` fields (AC line, battery flag, percent).
+#[cfg(target_os = "windows")]
+fn system_power_status() -> Option<(u8, u8, u8)> {
+    // Laid out like SYSTEM_POWER_STATUS; the trailing fields are only there to be filled
+    #[repr(C)]
+    #[derive(Default)]
+    #[allow(dead_code)]
+    struct SystemPowerStatus {
+        ac_line_status: u8,
+        battery_flag: u8,
+        battery_life_percent: u8,
+        system_status_flag: u8,
+        battery_life_time: u32,
+        battery_full_life_time: u32,
+    }
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetSystemPowerStatus(status: *mut SystemPowerStatus) -> i32;
+    }
+    let mut status = SystemPowerStatus::default();
+    // SAFETY: `status` is a writable SYSTEM_POWER_STATUS for the call to fill
+    if unsafe { GetSystemPowerStatus(&mut status) } == 0 {
+        return None;
+    }
+    Some((
+        status.ac_line_status,
+        status.battery_flag,
+        status.battery_life_percent,
+    ))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn system_power_status() -> Option<(u8, u8, u8)> {
+    None
+}
+
+/// First battery under `/sys/class/power_supply`.
+fn read_sysfs() -> Option<Reading> {
+    let entries = std::fs::read_dir("/sys/class/power_supply").ok()?;
+    entries.flatten().find_map(|entry| {
+        let dir = entry.path();
+        let read = |name: &str| std::fs::read_to_string(dir.join(name)).ok();
+        if read("type")?.trim() != "Battery" {
+            return None;
+        }
+        Some(Reading {
+            percent: read("capacity")?.trim().parse().ok()?,
+            on_battery: read("status")?.trim() == "Discharging",
+        })
+    })
+}
+
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let mut cmd = Command::new(program);
+    cmd.args(args);
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+    let output = cmd.output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Current charge, or None when it can't be read. Blocking.
+pub fn read() -> Option<Reading> {
+    if cfg!(target_os = "windows") {
+        system_power_status()
+            .and_then(|(ac_line, flag, percent)| parse_power_status(ac_line, flag, percent))
+    } else if cfg!(target_os = "macos") {
+        run("pmset", &["-g", "batt"])
+            .as_deref()
+            .and_then(parse_pmset)
+    } else {
+        read_sysfs()
+    }
+}
+
+/// Battery reading together with the setting and whether starts are held.
+pub async fn state(app: &AppHandle) -> BatteryState {
+    let threshold = crate::ytdlp::settings::get_settings(app)
+        .ok()
+        .and_then(|s| s.pause_below_battery_percent);
+    let reading = tokio::task::spawn_blocking(read).await.ok().flatten();
+    BatteryState {
+        available: reading.is_some(),
+        percent: reading.map(|r| r.percent),
+        on_battery: reading.map(|r| r.on_battery),
+        threshold,
+        paused: app.state::<Arc<DownloadManager>>().battery_paused(),
+    }
+}
+
+/// Start the background battery check. Call once from setup.
+pub fn spawn_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            check(&app).await;
+            tokio::time::sleep(TICK).await;
+        }
+    });
+}
+
+/// Re-read the battery and hold or release new starts.
+pub async fn check(app: &AppHandle) {
+    let threshold = crate::ytdlp::settings::get_settings(app)
+        .ok()
+        .and_then(|s| s.pause_below_battery_percent);
+    // No need to poll the OS while the setting is off
+    let reading = match threshold {
+        Some(_) => tokio::task::spawn_blocking(read).await.ok().flatten(),
+        None => None,
+    };
+    let pause = should_pause(reading, threshold);
+
+    let manager = app.state::<Arc<DownloadManager>>();
+    if pause == manager.battery_paused() {
+        return;
+    }
+    manager.set_battery_paused(pause);
+    if pause {
+        logger::info_cat(
+            "download",
+            &format!(
+                "On battery at {}%; holding new downloads until charging or above {}%",
+                reading.map(|r| r.percent).unwrap_or_default(),
+                threshold.unwrap_or_default()
+            ),
+        );
+    } else {
+        logger::info_cat("download", "Battery hold lifted; resuming the queue");
+        crate::ytdlp::download::process_next_pending_public(app.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pmset_output() {
+        let output = "Now drawing from 'Battery Power'\n \
+            -InternalBattery-0 (id=4653155)\t42%; discharging; 2:31 remaining present: true\n";
+        assert_eq!(
+            parse_pmset(output),
+            Some(Reading {
+                percent: 42,
+                on_battery: true
+            })
+        );
+        let charging = "Now drawing from 'AC Power'\n \
+            -InternalBattery-0 (id=4653155)\t80%; charging; 0:40 remaining present: true\n";
+        assert_eq!(parse_pmset(charging).map(|r| r.on_battery), Some(false));
+        assert_eq!(parse_pmset("Now drawing from 'AC Power'\n"), None);
+    }
+
+    #[test]
+    fn parses_power_status() {
+        // Discharging from the high, low (2) and critical (4) bands
+        for flag in [1, 2, 4, 2 | 4] {
+            assert_eq!(
+                parse_power_status(0, flag, 9),
+                Some(Reading {
+                    percent: 9,
+                    on_battery: true
+                })
+            );
+        }
+        // AC line unknown and not charging still drains the battery
+        assert_eq!(
+            parse_power_status(255, 2, 9).map(|r| r.on_battery),
+            Some(true)
+        );
+        assert_eq!(
+            parse_power_status(1, 1, 100).map(|r| r.on_battery),
+            Some(false)
+        );
+        assert_eq!(
+            parse_power_status(255, 8, 40).map(|r| r.on_battery),
+            Some(false)
+        );
+        // No battery, or nothing known about it
+        assert_eq!(parse_power_status(1, 128, 255), None);
+        assert_eq!(parse_power_status(255, 255, 255), None);
+    }
+
+    #[test]
+    fn pauses_only_on_battery_below_threshold() {
+        let low = Some(Reading {
+            percent: 15,
+            on_battery: true,
+        });
+        assert!(should_pause(low, Some(20)));
+        assert!(!should_pause(low, Some(15)));
+        assert!(!should_pause(low, None));
+        assert!(!should_pause(None, Some(20)));
+        let plugged = Some(Reading {
+            percent: 15,
+            on_battery: false,
+        });
+        assert!(!should_pause(plugged, Some(20)));
+    }
+}
@@ -0,0 +1,141 @@
+use super::path::command_with_path_app;
+use super::resolve::{resolve_ytdlp_path_with_app, try_get_version};
+use crate::modules::logger;
+use crate::modules::types::AppError;
+use crate::ytdlp::types::{LatencyStats, SpawnBench};
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+/// Short, long-lived public video used for the `--simulate` timing
+const SIMULATE_URL: &str = "https://www.youtube.com/watch?v=jNQXAC9IVRw";
+
+/// Latest result, reported by the health check without re-running it
+static LAST_BENCH: LazyLock<Mutex<Option<SpawnBench>>> = LazyLock::new(|| Mutex::new(None));
+
+pub(crate) fn last_bench() -> Option<SpawnBench> {
+    LAST_BENCH.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn latency_stats(samples: &[Duration]) -> Option<LatencyStats> {
+    let min = samples.iter().min()?;
+    let max = samples.iter().max()?;
+    let total: Duration = samples.iter().sum();
+    Some(LatencyStats {
+        runs: samples.len() as u32,
+        min_ms: min.as_millis() as u64,
+        avg_ms: (total / samples.len() as u32).as_millis() as u64,
+        max_ms: max.as_millis() as u64,
+    })
+}
+
+/// Time one `--simulate` run (process spawn + Python import + extraction).
+async fn time_simulate(
+    app: &AppHandle,
+    ytdlp: &str,
+    ignore_config: bool,
+) -> Result<Duration, String> {
+    let mut cmd = command_with_path_app(ytdlp, app);
+    cmd.args(["--simulate", "--no-playlist", "--quiet", "--no-warnings"]);
+    if ignore_config {
+        cmd.arg("--ignore-config");
+    }
+    cmd.arg(SIMULATE_URL);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+
+    let start = Instant::now();
+    let output = tokio::time::timeout(Duration::from_secs(60), cmd.output())
+        .await
+        .map_err(|_| "timeout (60s)".to_string())?
+        .map_err(|e| format!("exec error: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.lines().last().unwrap_or("failed").trim().to_string());
+    }
+    Ok(start.elapsed())
+}
+
+/// Time `yt-dlp --version` and a `--simulate` run `runs` times each. The first
+/// `--version` sample includes any cold start (e.g. PyInstaller extraction).
+pub async fn benchmark_spawn(app: &AppHandle, runs: u32) -> Result<SpawnBench, AppError> {
+    let ytdlp = resolve_ytdlp_path_with_app(app).await?;
+    let ignore_config = crate::ytdlp::settings::get_settings(app)
+        .map(|s| s.ignore_config)
+        .unwrap_or(false);
+
+    let mut version = None;
+    let mut spawn_samples = Vec::new();
+    for _ in 0..runs {
+        let start = Instant::now();
+        let v = try_get_version(Path::new(&ytdlp))
+            .await
+            .map_err(AppError::BinaryNotFound)?;
+        spawn_samples.push(start.elapsed());
+        version = Some(v);
+    }
+
+    let mut simulate_samples = Vec::new();
+    let mut simulate_error = None;
+    for _ in 0..runs {
+        match time_simulate(app, &ytdlp, ignore_config).await {
+            Ok(elapsed) => simulate_samples.push(elapsed),
+            Err(e) => {
+                // Usually offline or rate-limited; the spawn numbers are still useful
+                simulate_error = Some(e);
+                break;
+            }
+        }
+    }
+
+    let bench = SpawnBench {
+        ytdlp_path: ytdlp,
+        version,
+        spawn: latency_stats(&spawn_samples),
+        simulate: latency_stats(&simulate_samples),
+        simulate_error,
+        measured_at: chrono::Utc::now().timestamp(),
+    };
+
+    let fmt = |s: &Option<LatencyStats>| {
+        s.as_ref()
+            .map(|s| format!("{}/{}/{}ms", s.min_ms, s.avg_ms, s.max_ms))
+            .unwrap_or_else(|| "n/a".to_string())
+    };
+    logger::info_cat(
+        "dependency",
+        &format!(
+            "yt-dlp spawn benchmark (min/avg/max): --version {}, --simulate {}",
+            fmt(&bench.spawn),
+            fmt(&bench.simulate)
+        ),
+    );
+
+    *LAST_BENCH.lock().unwrap_or_else(|e| e.into_inner()) = Some(bench.clone());
+    Ok(bench)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latency_stats_min_avg_max() {
+        let samples = [
+            Duration::from_millis(300),
+            Duration::from_millis(100),
+            Duration::from_millis(200),
+        ];
+        let stats = latency_stats(&samples).unwrap();
+        assert_eq!(
+            (stats.runs, stats.min_ms, stats.avg_ms, stats.max_ms),
+            (3, 100, 200, 300)
+        );
+        assert!(latency_stats(&[]).is_none());
+    }
+}
@@ -1,4 +1,4 @@
-use super::path::{app_bin_dir, command_with_path, is_external_mode};
+use super::path::{app_bin_dir, command_with_path, is_external_mode, prefers_system_binary};
 use super::resolve::{check_deno_version, check_ffmpeg, check_ytdlp, try_get_version};
 use crate::ytdlp::types::{DepInfo, DepSource, FullDependencyStatus};
 use std::sync::RwLock;
@@ -46,57 +46,75 @@ async fn quick_binary_exists(name: &str) -> bool {
     )
 }
 
-async fn check_dep_ytdlp(app: &AppHandle) -> DepInfo {
-    // Check app-managed first (only in external mode)
-    if is_external_mode(app) {
-        if let Some(bin_dir) = app_bin_dir(app) {
-            let bin_name = if cfg!(target_os = "windows") {
-                "yt-dlp.exe"
-            } else {
-                "yt-dlp"
-            };
-            let app_binary = bin_dir.join(bin_name);
-            if app_binary.exists() {
-                // Binary file exists in app bin dir — report as installed.
-                // Version check may fail on first run (PyInstaller extraction, Gatekeeper, etc.)
-                let version = try_get_version(&app_binary).await.ok();
-                return DepInfo {
-                    installed: true,
-                    version,
-                    source: DepSource::AppManaged,
-                    path: Some(app_binary.to_string_lossy().to_string()),
-                };
-            }
-        }
+async fn check_app_managed_ytdlp(app: &AppHandle) -> Option<DepInfo> {
+    // Only in external mode
+    if !is_external_mode(app) {
+        return None;
     }
+    let bin_name = if cfg!(target_os = "windows") {
+        "yt-dlp.exe"
+    } else {
+        "yt-dlp"
+    };
+    let app_binary = app_bin_dir(app)?.join(bin_name);
+    if !app_binary.exists() {
+        return None;
+    }
+    // Binary file exists in app bin dir — report as installed.
+    // Version check may fail on first run (PyInstaller extraction, Gatekeeper, etc.)
+    let version = try_get_version(&app_binary).await.ok();
+    Some(DepInfo {
+        installed: true,
+        version,
+        source: DepSource::AppManaged,
+        path: Some(app_binary.to_string_lossy().to_string()),
+    })
+}
 
+async fn check_system_ytdlp() -> Option<DepInfo> {
     // Quick existence check via which/where before spawning yt-dlp --version
     if !quick_binary_exists("yt-dlp").await {
-        return DepInfo {
-            installed: false,
-            version: None,
-            source: DepSource::NotFound,
-            path: None,
-        };
+        return None;
     }
 
-    // Check system PATH
     let (version, _debug) = check_ytdlp().await;
-    if let Some(ver) = version {
-        DepInfo {
+    version.map(|ver| DepInfo {
+        installed: true,
+        version: Some(ver),
+        source: DepSource::SystemPath,
+        path: None,
+    })
+}
+
+async fn check_dep_ytdlp(app: &AppHandle) -> DepInfo {
+    if let Some(path) = super::registry::active_registered_ytdlp(app) {
+        return DepInfo {
             installed: true,
-            version: Some(ver),
-            source: DepSource::SystemPath,
-            path: None,
+            version: try_get_version(&path).await.ok(),
+            source: DepSource::Registered,
+            path: Some(path.to_string_lossy().to_string()),
+        };
+    }
+
+    // Same order as resolve_ytdlp_path_with_app: app-managed first unless system is preferred
+    let found = if prefers_system_binary(app) {
+        match check_system_ytdlp().await {
+            Some(info) => Some(info),
+            None => check_app_managed_ytdlp(app).await,
         }
     } else {
-        DepInfo {
-            installed: false,
-            version: None,
-            source: DepSource::NotFound,
-            path: None,
+        match check_app_managed_ytdlp(app).await {
+            Some(info) => Some(info),
+            None => check_system_ytdlp().await,
         }
-    }
+    };
+
+    found.unwrap_or(DepInfo {
+        installed: false,
+        version: None,
+        source: DepSource::NotFound,
+        path: None,
+    })
 }
 
 async fn check_dep_ffmpeg(app: &AppHandle) -> DepInfo {
@@ -229,6 +247,7 @@ pub async fn check_full_dependencies(app: &AppHandle) -> FullDependencyStatus {
         ytdlp: ytdlp_info,
         ffmpeg: ffmpeg_info,
         deno: deno_info,
+        ffmpeg_pinned: crate::ytdlp::dep_ffmpeg::pinned_version(app),
     };
 
     // Store in memory cache
@@ -0,0 +1,156 @@
+//! Why an installed binary doesn't start.
+//!
+//! A download can pass its checksum and still leave a binary the OS refuses
+//! to run: Gatekeeper kills an unsigned file on macOS, a build for the wrong
+//! architecture fails with an exec-format error, a file on a `noexec` mount is
+//! not executable. The failure is classified from the exec error or stderr so
+//! the user gets a hint instead of "installed" with an unknown version.
+
+use super::resolve::{resolve_ytdlp_path_with_app, try_get_version, which_binary};
+use crate::modules::types::AppError;
+use crate::ytdlp::types::{BinaryDiagnosis, ExecFailureCause};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+/// Patterns per cause, matched case-insensitively; first match wins.
+const RULES: &[(ExecFailureCause, &[&str])] = &[
+    (
+        ExecFailureCause::Quarantine,
+        &[
+            "sigkill",
+            "signal: 9",
+            "quarantine",
+            "developer cannot be verified",
+            "not verified",
+            "code signature",
+        ],
+    ),
+    (
+        ExecFailureCause::ExecFormat,
+        &[
+            "exec format error",
+            "os error 8)",
+            "bad cpu type",
+            "not a valid win32 application",
+            "os error 193",
+            "cannot execute binary file",
+        ],
+    ),
+    (
+        ExecFailureCause::Permission,
+        &[
+            "permission denied",
+            "os error 13",
+            "access is denied",
+            "os error 5)",
+            "operation not permitted",
+        ],
+    ),
+    (
+        ExecFailureCause::MissingLibrary,
+        &[
+            "error while loading shared libraries",
+            "glibc_",
+            "library not loaded",
+            "cannot open shared object",
+            "vcruntime",
+            "failed to load python dll",
+        ],
+    ),
+    (ExecFailureCause::Timeout, &["timeout ("]),
+];
+
+/// Coarse cause of a failed `--version` run.
+pub fn classify(detail: &str) -> ExecFailureCause {
+    let lower = detail.to_lowercase();
+    RULES
+        .iter()
+        .find(|(_, patterns)| patterns.iter().any(|p| lower.contains(p)))
+        .map_or(ExecFailureCause::Other, |(cause, _)| *cause)
+}
+
+/// What the user can do about it.
+pub fn hint(cause: ExecFailureCause) -> &'static str {
+    match cause {
+        ExecFailureCause::Quarantine => {
+            "macOS blocked the binary. Allow it under System Settings → Privacy & Security, or run: xattr -d com.apple.quarantine <path>"
+        }
+        ExecFailureCause::ExecFormat => {
+            "The binary is built for a different CPU or OS. Install yt-dlp through your package manager, or use the system binary mode"
+        }
+        ExecFailureCause::Permission => {
+            "The binary is not allowed to run. Check that the app data folder is not on a noexec mount and that antivirus software has not blocked it"
+        }
+        ExecFailureCause::MissingLibrary => {
+            "A system library the binary needs is missing. Update the OS or install yt-dlp through your package manager"
+        }
+        ExecFailureCause::Timeout => {
+            "The binary did not answer in time. Antivirus scanning on first run can cause this; try again"
+        }
+        ExecFailureCause::Other => "The binary failed to start. See the error output for details",
+    }
+}
+
+/// Run `--version` and explain a failure.
+pub async fn diagnose(path: &Path) -> BinaryDiagnosis {
+    let (version, failure) = match try_get_version(path).await {
+        Ok(version) => (Some(version), None),
+        Err(detail) => (None, Some(detail)),
+    };
+    let cause = failure.as_deref().map(classify);
+    BinaryDiagnosis {
+        path: path.to_string_lossy().to_string(),
+        runs: version.is_some(),
+        version,
+        cause,
+        hint: cause.map(|c| hint(c).to_string()),
+        stderr: failure,
+    }
+}
+
+/// Diagnose the yt-dlp downloads would use.
+pub async fn diagnose_ytdlp(app: &AppHandle) -> Result<BinaryDiagnosis, AppError> {
+    let ytdlp_path = resolve_ytdlp_path_with_app(app).await?;
+    let path = PathBuf::from(&ytdlp_path);
+    let path = if path.is_absolute() {
+        path
+    } else {
+        which_binary(&ytdlp_path)
+            .await
+            .ok_or_else(|| AppError::BinaryNotFound(format!("Cannot locate {}", ytdlp_path)))?
+    };
+    Ok(diagnose(&path).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_common_exec_failures() {
+        assert_eq!(
+            classify("exit code=signal: 9 (SIGKILL), stderr="),
+            ExecFailureCause::Quarantine
+        );
+        assert_eq!(
+            classify("exec error: Exec format error (os error 8) (Uncategorized)"),
+            ExecFailureCause::ExecFormat
+        );
+        assert_eq!(
+            classify("exec error: Permission denied (os error 13) (PermissionDenied)"),
+            ExecFailureCause::Permission
+        );
+        assert_eq!(
+            classify("exit code=exit status: 127, stderr=yt-dlp: error while loading shared libraries: libz.so.1"),
+            ExecFailureCause::MissingLibrary
+        );
+        assert_eq!(
+            classify("timeout (10s) executing /bin/yt-dlp"),
+            ExecFailureCause::Timeout
+        );
+        assert_eq!(
+            classify("exit code=exit status: 2, stderr=boom"),
+            ExecFailureCause::Other
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use super::path::{app_bin_dir, command_with_path};
+use super::resolve::{app_managed_ytdlp, system_ytdlp, try_get_version};
+use crate::modules::types::AppError;
+use crate::ytdlp::types::ExtractorDiff;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// `--list-extractors` output per yt-dlp version. A version's list never changes,
+/// so entries are kept for the lifetime of the process.
+static EXTRACTOR_CACHE: std::sync::LazyLock<Mutex<HashMap<String, Vec<String>>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Every yt-dlp binary available locally: app-managed, system PATH, and any other
+/// `yt-dlp*` file kept in the app bin dir (e.g. a previous version).
+async fn local_binaries(app: &AppHandle) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    paths.extend(app_managed_ytdlp(app));
+    paths.extend(system_ytdlp().await);
+
+    if let Some(entries) = app_bin_dir(app).and_then(|d| std::fs::read_dir(d).ok()) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if path.is_file() && name.starts_with("yt-dlp") && !name.ends_with(".tmp") {
+                paths.push(path);
+            }
+        }
+    }
+
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+/// Find a local binary reporting the given version.
+fn find_binary_for_version(candidates: &[(PathBuf, String)], version: &str) -> Option<PathBuf> {
+    candidates
+        .iter()
+        .find(|(_, v)| v == version)
+        .map(|(p, _)| p.clone())
+}
+
+/// Run `--list-extractors` and return one extractor name per line.
+async fn list_extractors(binary_path: &Path) -> Result<Vec<String>, AppError> {
+    let mut cmd = command_with_path(binary_path.to_str().unwrap_or("yt-dlp"));
+    cmd.arg("--list-extractors");
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+
+    let output = tokio::time::timeout(Duration::from_secs(30), cmd.output())
+        .await
+        .map_err(|_| AppError::Custom("Timed out listing yt-dlp extractors".to_string()))?
+        .map_err(|e| AppError::Custom(format!("Failed to run yt-dlp: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(AppError::Custom(format!(
+            "yt-dlp --list-extractors failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect())
+}
+
+/// Cached extractor list for a version, running the binary on a miss.
+async fn extractors_for(version: &str, binary_path: &Path) -> Result<Vec<String>, AppError> {
+    if let Some(list) = EXTRACTOR_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(version)
+    {
+        return Ok(list.clone());
+    }
+
+    let list = list_extractors(binary_path).await?;
+    EXTRACTOR_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(version.to_string(), list.clone());
+    Ok(list)
+}
+
+/// Sorted (added, removed) going from list `a` to list `b`.
+fn diff_lists(a: &[String], b: &[String]) -> (Vec<String>, Vec<String>) {
+    let a: BTreeSet<&String> = a.iter().collect();
+    let b: BTreeSet<&String> = b.iter().collect();
+    let added = b.difference(&a).map(|s| s.to_string()).collect();
+    let removed = a.difference(&b).map(|s| s.to_string()).collect();
+    (added, removed)
+}
+
+/// Compare extractor support between two yt-dlp versions.
+/// Both versions must be present locally; nothing is downloaded.
+pub async fn diff_extractors(
+    app: &AppHandle,
+    version_a: &str,
+    version_b: &str,
+) -> Result<ExtractorDiff, AppError> {
+    let version_a = version_a.trim();
+    let version_b = version_b.trim();
+
+    let mut candidates = Vec::new();
+    for path in local_binaries(app).await {
+        if let Ok(version) = try_get_version(&path).await {
+            candidates.push((path, version));
+        }
+    }
+
+    let mut lists = Vec::with_capacity(2);
+    for version in [version_a, version_b] {
+        let path = find_binary_for_version(&candidates, version).ok_or_else(|| {
+            AppError::BinaryNotFound(format!("yt-dlp {} is not available locally", version))
+        })?;
+        lists.push(extractors_for(version, &path).await?);
+    }
+
+    let (added, removed) = diff_lists(&lists[0], &lists[1]);
+    Ok(ExtractorDiff {
+        version_a: version_a.to_string(),
+        version_b: version_b.to_string(),
+        added,
+        removed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(v: &[&str]) -> Vec<String> {
+        v.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn diff_lists_reports_added_and_removed() {
+        let a = names(&["youtube", "vimeo", "Dailymotion"]);
+        let b = names(&["youtube", "Dailymotion", "bilibili", "Afreeca"]);
+        let (added, removed) = diff_lists(&a, &b);
+        assert_eq!(added, names(&["Afreeca", "bilibili"]));
+        assert_eq!(removed, names(&["vimeo"]));
+    }
+
+    #[test]
+    fn diff_lists_identical_is_empty() {
+        let a = names(&["youtube"]);
+        let (added, removed) = diff_lists(&a, &a);
+        assert!(added.is_empty() && removed.is_empty());
+    }
+}
@@ -1,12 +1,26 @@
+mod bench;
 mod dep_check;
+mod diagnose;
+mod extractors;
 pub(crate) mod path;
+mod registry;
 pub(crate) mod resolve;
+mod update;
 
 // Re-export public API to preserve existing import paths
+pub use bench::benchmark_spawn;
+pub(crate) use bench::last_bench;
 pub use dep_check::{
     check_full_dependencies, get_cached_dep_status, invalidate_dep_cache, warmup_ytdlp,
 };
+pub use diagnose::{diagnose as diagnose_binary, diagnose_ytdlp};
+pub use extractors::diff_extractors;
 pub use path::command_with_path_app;
+pub use registry::{list_ytdlp_binaries, set_active_ytdlp};
 pub use resolve::{
-    check_dependencies, resolve_ffmpeg_path_with_app, resolve_ytdlp_path_with_app, update_ytdlp,
+    check_dependencies, get_binary_info, resolve_ffmpeg_path_with_app, resolve_ytdlp_path_with_app,
+};
+pub(crate) use update::rotate_backups;
+pub use update::{
+    cancel_update, list_backups, prune_ytdlp_backups, rollback_update, update_ytdlp, MAX_BACKUPS,
 };
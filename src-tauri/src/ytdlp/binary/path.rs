@@ -1,5 +1,5 @@
 use std::path::PathBuf;
-use tauri::{AppHandle, Manager};
+use tauri::AppHandle;
 use tauri_plugin_store::StoreExt;
 
 /// Platform-specific PATH separator.
@@ -100,9 +100,19 @@ pub(super) fn is_external_mode(app: &AppHandle) -> bool {
     get_dep_mode(app) == "external"
 }
 
+/// Check if system PATH yt-dlp should win over the app-managed copy. Defaults to false.
+pub(super) fn prefers_system_binary(app: &AppHandle) -> bool {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get("preferSystemBinary").and_then(|v| v.as_bool()))
+        .unwrap_or(false)
+}
+
 /// Get the app-managed bin directory path.
 pub(super) fn app_bin_dir(app: &AppHandle) -> Option<PathBuf> {
-    app.path().app_data_dir().ok().map(|d| d.join("bin"))
+    crate::modules::data_dir::resolve(app)
+        .ok()
+        .map(|d| d.join("bin"))
 }
 
 /// Build a PATH string that prepends app bin dir to the augmented PATH.
@@ -0,0 +1,83 @@
+use super::resolve::try_get_version;
+use crate::modules::logger;
+use crate::modules::types::AppError;
+use crate::ytdlp::types::{BinaryInfo, YtdlpBinary};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+/// The registered yt-dlp selected by `activeYtdlp`, if its label and file still exist.
+pub(super) fn active_registered_ytdlp(app: &AppHandle) -> Option<PathBuf> {
+    let settings = crate::ytdlp::settings::get_settings(app).ok()?;
+    let label = settings.active_ytdlp?;
+    let path = PathBuf::from(settings.ytdlp_binaries.get(&label)?);
+    if path.exists() {
+        Some(path)
+    } else {
+        logger::warn_cat(
+            "dependency",
+            &format!(
+                "Active yt-dlp '{}' is missing at {}, falling back to automatic resolution",
+                label,
+                path.display()
+            ),
+        );
+        None
+    }
+}
+
+/// Probe every registered yt-dlp binary for its version.
+pub async fn list_ytdlp_binaries(app: &AppHandle) -> Result<Vec<YtdlpBinary>, AppError> {
+    let settings = crate::ytdlp::settings::get_settings(app)?;
+    let mut binaries = Vec::with_capacity(settings.ytdlp_binaries.len());
+    for (label, path) in &settings.ytdlp_binaries {
+        let (version, error) = match try_get_version(Path::new(path)).await {
+            Ok(v) => (Some(v), None),
+            Err(e) => (None, Some(e)),
+        };
+        binaries.push(YtdlpBinary {
+            active: settings.active_ytdlp.as_deref() == Some(label.as_str()),
+            label: label.clone(),
+            path: path.clone(),
+            version,
+            error,
+        });
+    }
+    Ok(binaries)
+}
+
+/// Make a registered binary the one downloads use, or return to automatic
+/// resolution with `None`. The binary must run before it is activated.
+pub async fn set_active_ytdlp(
+    app: &AppHandle,
+    label: Option<String>,
+) -> Result<BinaryInfo, AppError> {
+    if let Some(ref label) = label {
+        let settings = crate::ytdlp::settings::get_settings(app)?;
+        let path = settings
+            .ytdlp_binaries
+            .get(label)
+            .ok_or_else(|| AppError::Custom(format!("yt-dlp '{}' is not registered", label)))?;
+        let version = try_get_version(Path::new(path))
+            .await
+            .map_err(|e| AppError::BinaryNotFound(format!("{} ({}): {}", label, path, e)))?;
+        logger::info_cat(
+            "dependency",
+            &format!("Switched yt-dlp to '{}' ({}, {})", label, path, version),
+        );
+    } else {
+        logger::info_cat("dependency", "yt-dlp back to automatic resolution");
+    }
+
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Custom(e.to_string()))?;
+    store.set(
+        "activeYtdlp",
+        serde_json::to_value(&label).map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+    store.save().map_err(|e| AppError::Custom(e.to_string()))?;
+
+    super::invalidate_dep_cache();
+    Ok(super::get_binary_info(app).await)
+}
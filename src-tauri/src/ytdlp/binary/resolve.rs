@@ -1,5 +1,6 @@
-use super::path::{app_bin_dir, command_with_path, is_external_mode};
+use super::path::{app_bin_dir, command_with_path, is_external_mode, prefers_system_binary};
 use crate::modules::types::AppError;
+use crate::ytdlp::types::{BinaryInfo, DepSource};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tauri::AppHandle;
@@ -179,7 +180,9 @@ pub async fn resolve_ffmpeg_path() -> Option<String> {
 }
 
 /// Get full dependency status
-pub async fn check_dependencies() -> super::super::types::DependencyStatus {
+pub async fn check_dependencies(
+    ffmpeg_pinned: Option<String>,
+) -> super::super::types::DependencyStatus {
     let (ytdlp_version, debug_lines) = check_ytdlp().await;
     let ffmpeg_version = check_ffmpeg().await;
 
@@ -194,29 +197,121 @@ pub async fn check_dependencies() -> super::super::types::DependencyStatus {
         ytdlp_version,
         ffmpeg_installed: ffmpeg_version.is_some(),
         ffmpeg_version,
+        ffmpeg_pinned,
         ytdlp_debug: debug_text,
     }
 }
 
+/// Locate a binary on the augmented PATH using `where` (Windows) or `which` (Unix).
+pub(super) async fn which_binary(name: &str) -> Option<PathBuf> {
+    let which_cmd = if cfg!(target_os = "windows") {
+        "where"
+    } else {
+        "which"
+    };
+    let mut cmd = command_with_path(which_cmd);
+    cmd.arg(name);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000);
+    }
+
+    let result = tokio::time::timeout(Duration::from_secs(5), cmd.output())
+        .await
+        .ok()?
+        .ok()?;
+    if !result.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8(result.stdout).ok()?;
+    let path = stdout.lines().next().unwrap_or("").trim();
+    if path.is_empty() {
+        None
+    } else {
+        Some(PathBuf::from(path))
+    }
+}
+
+/// App-managed yt-dlp binary, if external mode is on and the file exists.
+pub(super) fn app_managed_ytdlp(app: &AppHandle) -> Option<PathBuf> {
+    if !is_external_mode(app) {
+        return None;
+    }
+    let bin_name = if cfg!(target_os = "windows") {
+        "yt-dlp.exe"
+    } else {
+        "yt-dlp"
+    };
+    let app_binary = app_bin_dir(app)?.join(bin_name);
+    app_binary.exists().then_some(app_binary)
+}
+
+/// System PATH yt-dlp as an absolute path, so the app bin dir prepended to PATH
+/// by `command_with_path_app` can't shadow it.
+pub(super) async fn system_ytdlp() -> Option<PathBuf> {
+    if try_get_version(Path::new("yt-dlp")).await.is_err() {
+        return None;
+    }
+    Some(
+        which_binary("yt-dlp")
+            .await
+            .unwrap_or_else(|| PathBuf::from("yt-dlp")),
+    )
+}
+
+/// Pick the yt-dlp binary and report where it came from.
+/// An active registry entry (`activeYtdlp`) wins; otherwise the default order is
+/// app_data_dir/bin/ (if external mode) then system PATH, and
+/// `preferSystemBinary` flips it.
+async fn select_ytdlp(app: &AppHandle) -> Option<(PathBuf, DepSource)> {
+    if let Some(path) = super::registry::active_registered_ytdlp(app) {
+        return Some((path, DepSource::Registered));
+    }
+
+    if prefers_system_binary(app) {
+        if let Some(path) = system_ytdlp().await {
+            return Some((path, DepSource::SystemPath));
+        }
+        return app_managed_ytdlp(app).map(|p| (p, DepSource::AppManaged));
+    }
+
+    if let Some(path) = app_managed_ytdlp(app) {
+        return Some((path, DepSource::AppManaged));
+    }
+    if try_get_version(Path::new("yt-dlp")).await.is_ok() {
+        return Some((PathBuf::from("yt-dlp"), DepSource::SystemPath));
+    }
+    None
+}
+
 /// Resolve yt-dlp binary: app_data_dir/bin/ first (if external mode), then system PATH.
+/// The order is reversed when `preferSystemBinary` is set.
 pub async fn resolve_ytdlp_path_with_app(app: &AppHandle) -> Result<String, AppError> {
-    // 1. Check app-managed binary (only in external mode)
-    if is_external_mode(app) {
-        if let Some(bin_dir) = app_bin_dir(app) {
-            let bin_name = if cfg!(target_os = "windows") {
-                "yt-dlp.exe"
-            } else {
-                "yt-dlp"
-            };
-            let app_binary = bin_dir.join(bin_name);
-            if app_binary.exists() {
-                return Ok(app_binary.to_string_lossy().to_string());
-            }
-        }
+    match select_ytdlp(app).await {
+        Some((path, _)) => Ok(path.to_string_lossy().to_string()),
+        None => resolve_ytdlp_path().await,
     }
+}
 
-    // 2. Fallback to system PATH
-    resolve_ytdlp_path().await
+/// Describe the yt-dlp binary that downloads will use.
+pub async fn get_binary_info(app: &AppHandle) -> BinaryInfo {
+    let prefer_system = prefers_system_binary(app);
+    match select_ytdlp(app).await {
+        Some((path, source)) => BinaryInfo {
+            version: try_get_version(&path).await.ok(),
+            path: Some(path.to_string_lossy().to_string()),
+            source,
+            prefer_system,
+        },
+        None => BinaryInfo {
+            path: None,
+            version: None,
+            source: DepSource::NotFound,
+            prefer_system,
+        },
+    }
 }
 
 /// Resolve ffmpeg binary: app_data_dir/bin/ first (if external mode), then system PATH.
@@ -327,30 +422,3 @@ pub async fn check_deno_version(deno_path: &Path) -> Option<String> {
         None
     }
 }
-
-/// Update yt-dlp using --update flag
-pub async fn update_ytdlp() -> Result<String, AppError> {
-    let ytdlp_path = resolve_ytdlp_path().await?;
-
-    let mut cmd = command_with_path(&ytdlp_path);
-    cmd.arg("--update");
-
-    #[cfg(target_os = "windows")]
-    {
-        use std::os::windows::process::CommandExt;
-        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
-    }
-
-    let output = cmd
-        .output()
-        .await
-        .map_err(|e| AppError::Custom(format!("Failed to update yt-dlp: {}", e)))?;
-
-    if output.status.success() {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        Ok(stdout.trim().to_string())
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(AppError::Custom(format!("Update failed: {}", stderr)))
-    }
-}
@@ -0,0 +1,365 @@
+//! `yt-dlp --update` with a timeout, cancellation and rollback copies.
+//!
+//! yt-dlp replaces itself atomically, but a killed or misbehaving update can
+//! still leave a binary that no longer starts. The file is copied to
+//! `<name>.pre-update.1` before updating (older copies move up to `.2`, `.3`,
+//! ... up to `binary_backup_count`) and checked with `--version` afterwards;
+//! when it is broken the error points at `rollback_ytdlp_update`.
+
+use super::path::command_with_path;
+use super::resolve::{resolve_ytdlp_path, try_get_version, which_binary};
+use crate::modules::logger;
+use crate::modules::types::AppError;
+use crate::ytdlp::types::YtdlpBackup;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::sync::watch;
+
+const DEFAULT_TIMEOUT_SECS: u64 = 120;
+/// Most rollback copies `binary_backup_count` may ask for
+pub const MAX_BACKUPS: u32 = 10;
+
+/// Cancel signal of the update in progress, if any
+static RUNNING: LazyLock<Mutex<Option<watch::Sender<bool>>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Clears the running slot however the update ends.
+struct RunningGuard;
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        *RUNNING.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+/// Single rollback copy kept by versions before numbered backups.
+fn legacy_backup_path(binary: &Path) -> PathBuf {
+    let mut name = binary.file_name().unwrap_or_default().to_os_string();
+    name.push(".pre-update");
+    binary.with_file_name(name)
+}
+
+/// Rollback copy `index` (1 = newest) kept next to the binary.
+fn backup_path(binary: &Path, index: u32) -> PathBuf {
+    let mut name = legacy_backup_path(binary).into_os_string();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// Indexes of the rollback copies next to `binary`, ascending.
+fn backup_indexes(binary: &Path) -> Vec<u32> {
+    let prefix = format!(
+        "{}.",
+        legacy_backup_path(binary)
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+    );
+    let Some(dir) = binary.parent().and_then(|d| std::fs::read_dir(d).ok()) else {
+        return Vec::new();
+    };
+    let mut indexes: Vec<u32> = dir
+        .flatten()
+        .filter_map(|e| {
+            e.file_name()
+                .to_string_lossy()
+                .strip_prefix(&prefix)?
+                .parse()
+                .ok()
+        })
+        .filter(|&i| i > 0)
+        .collect();
+    indexes.sort_unstable();
+    indexes
+}
+
+/// Delete rollback copies past `keep` (and the pre-numbering copy, once it is numbered).
+pub(crate) fn prune_backups(binary: &Path, keep: u32) -> u32 {
+    let legacy = legacy_backup_path(binary);
+    if legacy.exists() {
+        let first = backup_path(binary, 1);
+        let _ = if first.exists() || keep == 0 {
+            std::fs::remove_file(&legacy)
+        } else {
+            std::fs::rename(&legacy, &first)
+        };
+    }
+    let mut removed = 0;
+    for index in backup_indexes(binary).into_iter().filter(|&i| i > keep) {
+        if std::fs::remove_file(backup_path(binary, index)).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+/// Make room for a new newest copy: drop the oldest beyond `keep` and move the
+/// rest up one index. Returns where the new copy goes, None when `keep` is 0.
+pub(crate) fn rotate_backups(binary: &Path, keep: u32) -> Option<PathBuf> {
+    let keep = keep.min(MAX_BACKUPS);
+    prune_backups(binary, keep.saturating_sub(1));
+    if keep == 0 {
+        return None;
+    }
+    for index in backup_indexes(binary).into_iter().rev() {
+        let _ = std::fs::rename(backup_path(binary, index), backup_path(binary, index + 1));
+    }
+    Some(backup_path(binary, 1))
+}
+
+/// Absolute path of the yt-dlp that `--update` rewrites.
+async fn ytdlp_file() -> Result<PathBuf, AppError> {
+    let ytdlp_path = resolve_ytdlp_path().await?;
+    let path = PathBuf::from(&ytdlp_path);
+    if path.is_absolute() {
+        return Ok(path);
+    }
+    which_binary(&ytdlp_path)
+        .await
+        .ok_or_else(|| AppError::BinaryNotFound(format!("Cannot locate {}", ytdlp_path)))
+}
+
+/// Update yt-dlp using --update. Killed after `timeout_secs` (default 2 minutes)
+/// or when `cancel_update` is called. Keeps up to `backup_count` older binaries.
+pub async fn update_ytdlp(
+    timeout_secs: Option<u64>,
+    backup_count: u32,
+) -> Result<String, AppError> {
+    let timeout = Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS).clamp(10, 1800));
+    let mut cancel_rx = {
+        let mut running = RUNNING.lock().unwrap_or_else(|e| e.into_inner());
+        if running.is_some() {
+            return Err(AppError::Custom(
+                "A yt-dlp update is already running".to_string(),
+            ));
+        }
+        let (tx, rx) = watch::channel(false);
+        *running = Some(tx);
+        rx
+    };
+    let _guard = RunningGuard;
+
+    let binary = ytdlp_file().await?;
+    let has_backup = match rotate_backups(&binary, backup_count) {
+        None => false,
+        Some(backup) => match tokio::fs::copy(&binary, &backup).await {
+            Ok(_) => true,
+            Err(e) => {
+                logger::warn_cat(
+                    "dependency",
+                    &format!(
+                        "Could not keep a rollback copy of {}: {}",
+                        binary.display(),
+                        e
+                    ),
+                );
+                false
+            }
+        },
+    };
+
+    let mut cmd = command_with_path(&binary.to_string_lossy());
+    cmd.arg("--update")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| AppError::Custom(format!("Failed to update yt-dlp: {}", e)))?;
+    let mut stdout = child.stdout.take();
+    let mut stderr = child.stderr.take();
+    let stdout_task = tokio::spawn(async move {
+        let mut buf = Vec::new();
+        if let Some(out) = stdout.as_mut() {
+            let _ = out.read_to_end(&mut buf).await;
+        }
+        buf
+    });
+    let stderr_task = tokio::spawn(async move {
+        let mut buf = Vec::new();
+        if let Some(err) = stderr.as_mut() {
+            let _ = err.read_to_end(&mut buf).await;
+        }
+        buf
+    });
+
+    let interrupted = tokio::select! {
+        status = child.wait() => {
+            status.map_err(|e| AppError::Custom(format!("Failed to update yt-dlp: {}", e)))?;
+            None
+        }
+        _ = tokio::time::sleep(timeout) => Some(format!("timed out after {}s", timeout.as_secs())),
+        _ = cancel_rx.changed() => Some("cancelled".to_string()),
+    };
+    if interrupted.is_some() {
+        let _ = child.kill().await;
+    }
+    let status = child.wait().await.ok();
+    let stdout = String::from_utf8_lossy(&stdout_task.await.unwrap_or_default()).to_string();
+    let stderr = String::from_utf8_lossy(&stderr_task.await.unwrap_or_default()).to_string();
+
+    // Whatever happened, the binary must still start
+    if let Err(reason) = try_get_version(&binary).await {
+        logger::error_cat(
+            "dependency",
+            &format!("yt-dlp is broken after update: {}", reason),
+        );
+        return Err(AppError::Custom(if has_backup {
+            format!(
+                "yt-dlp no longer runs after the update ({}). Roll back to the previous version.",
+                reason
+            )
+        } else {
+            format!("yt-dlp no longer runs after the update ({})", reason)
+        }));
+    }
+
+    if let Some(reason) = interrupted {
+        logger::warn_cat("dependency", &format!("yt-dlp update {}", reason));
+        return Err(AppError::Custom(format!(
+            "yt-dlp update {}; the existing version was kept",
+            reason
+        )));
+    }
+    if status.is_some_and(|s| s.success()) {
+        Ok(stdout.trim().to_string())
+    } else {
+        Err(AppError::Custom(format!("Update failed: {}", stderr)))
+    }
+}
+
+/// Stop a running update. Returns false when none is running.
+pub fn cancel_update() -> bool {
+    match RUNNING.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        Some(tx) => {
+            let _ = tx.send(true);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Put back rollback copy `index` (default 1, the one taken before the last
+/// update). Returns the restored version.
+pub async fn rollback_update(index: Option<u32>) -> Result<String, AppError> {
+    if RUNNING.lock().unwrap_or_else(|e| e.into_inner()).is_some() {
+        return Err(AppError::Custom(
+            "Cancel the running yt-dlp update first".to_string(),
+        ));
+    }
+    let binary = ytdlp_file().await?;
+    let index = index.unwrap_or(1);
+    let mut backup = backup_path(&binary, index);
+    if index == 1 && !backup.exists() {
+        backup = legacy_backup_path(&binary);
+    }
+    if !backup.exists() {
+        return Err(AppError::FileError(format!(
+            "No yt-dlp backup #{} to roll back to",
+            index
+        )));
+    }
+    tokio::fs::copy(&backup, &binary)
+        .await
+        .map_err(|e| AppError::FileError(format!("Rollback failed: {}", e)))?;
+    let version = try_get_version(&binary)
+        .await
+        .map_err(|e| AppError::Custom(format!("Rolled-back yt-dlp does not run: {}", e)))?;
+    logger::info_cat(
+        "dependency",
+        &format!("Rolled yt-dlp back to {} (backup #{})", version, index),
+    );
+    Ok(version)
+}
+
+/// Rollback copies of the active yt-dlp, newest first, with the version each reports.
+pub async fn list_backups() -> Result<Vec<YtdlpBackup>, AppError> {
+    let binary = ytdlp_file().await?;
+    let mut paths: Vec<(u32, PathBuf)> = backup_indexes(&binary)
+        .into_iter()
+        .map(|i| (i, backup_path(&binary, i)))
+        .collect();
+    let legacy = legacy_backup_path(&binary);
+    if paths.first().is_none_or(|(i, _)| *i != 1) && legacy.exists() {
+        paths.insert(0, (1, legacy));
+    }
+
+    let mut backups = Vec::with_capacity(paths.len());
+    for (index, path) in paths {
+        let Ok(meta) = tokio::fs::metadata(&path).await else {
+            continue;
+        };
+        backups.push(YtdlpBackup {
+            index,
+            version: try_get_version(&path).await.ok(),
+            size: meta.len(),
+            modified_at: meta
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs() as i64),
+            path: path.to_string_lossy().to_string(),
+        });
+    }
+    Ok(backups)
+}
+
+/// Drop rollback copies of the active yt-dlp past `keep`, after the setting is lowered.
+pub async fn prune_ytdlp_backups(keep: u32) -> Result<u32, AppError> {
+    let binary = ytdlp_file().await?;
+    Ok(prune_backups(&binary, keep.min(MAX_BACKUPS)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backup_sits_next_to_binary() {
+        assert_eq!(
+            backup_path(Path::new("/usr/local/bin/yt-dlp"), 1),
+            PathBuf::from("/usr/local/bin/yt-dlp.pre-update.1")
+        );
+        assert_eq!(
+            backup_path(Path::new("C:/tools/yt-dlp.exe"), 3),
+            PathBuf::from("C:/tools/yt-dlp.exe.pre-update.3")
+        );
+    }
+
+    #[test]
+    fn rotation_keeps_newest_copies() {
+        let dir = std::env::temp_dir().join(format!("ytdlp-rotate-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let binary = dir.join("yt-dlp");
+        std::fs::write(legacy_backup_path(&binary), "v1").unwrap();
+
+        for version in ["v2", "v3", "v4"] {
+            let slot = rotate_backups(&binary, 2).unwrap();
+            std::fs::write(slot, version).unwrap();
+        }
+        assert_eq!(backup_indexes(&binary), vec![1, 2]);
+        assert_eq!(
+            std::fs::read_to_string(backup_path(&binary, 1)).unwrap(),
+            "v4"
+        );
+        assert_eq!(
+            std::fs::read_to_string(backup_path(&binary, 2)).unwrap(),
+            "v3"
+        );
+        assert!(!legacy_backup_path(&binary).exists());
+
+        assert_eq!(prune_backups(&binary, 1), 1);
+        assert!(rotate_backups(&binary, 0).is_none());
+        assert!(backup_indexes(&binary).is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,157 @@
+//! Browser profiles for `--cookies-from-browser BROWSER:PROFILE`, found by
+//! scanning the browser's user data directory the way yt-dlp locates it.
+//!
+//! Chromium-based browsers keep one directory per profile ("Default",
+//! "Profile 2") holding a `Preferences` file; Firefox keeps one per profile
+//! holding `cookies.sqlite`. Safari has no profiles.
+
+use crate::modules::types::AppError;
+use crate::ytdlp::security::{self, CookieBrowserSpec};
+use std::path::{Path, PathBuf};
+
+/// Marker file that makes a subdirectory of the user data directory a profile.
+fn profile_marker(browser: &str) -> &'static str {
+    if browser == "firefox" {
+        "cookies.sqlite"
+    } else {
+        "Preferences"
+    }
+}
+
+fn env_dir(var: &str) -> Option<PathBuf> {
+    std::env::var_os(var)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Directory holding `browser`'s profiles on this OS, if it has one.
+fn user_data_dir(browser: &str) -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        let local = env_dir("LOCALAPPDATA");
+        let roaming = env_dir("APPDATA");
+        let (base, rel) = match browser {
+            "brave" => (local, r"BraveSoftware\Brave-Browser\User Data"),
+            "chrome" => (local, r"Google\Chrome\User Data"),
+            "chromium" => (local, r"Chromium\User Data"),
+            "edge" => (local, r"Microsoft\Edge\User Data"),
+            "opera" => (roaming, r"Opera Software\Opera Stable"),
+            "vivaldi" => (local, r"Vivaldi\User Data"),
+            "whale" => (local, r"Naver\Naver Whale\User Data"),
+            "firefox" => (roaming, r"Mozilla\Firefox\Profiles"),
+            _ => return None,
+        };
+        base.map(|b| b.join(rel))
+    } else if cfg!(target_os = "macos") {
+        let support = env_dir("HOME")?.join("Library/Application Support");
+        let rel = match browser {
+            "brave" => "BraveSoftware/Brave-Browser",
+            "chrome" => "Google/Chrome",
+            "chromium" => "Chromium",
+            "edge" => "Microsoft Edge",
+            "opera" => "com.operasoftware.Opera",
+            "vivaldi" => "Vivaldi",
+            "whale" => "Naver/Whale",
+            "firefox" => "Firefox/Profiles",
+            _ => return None,
+        };
+        Some(support.join(rel))
+    } else {
+        let home = env_dir("HOME")?;
+        if browser == "firefox" {
+            return Some(home.join(".mozilla/firefox"));
+        }
+        let config = env_dir("XDG_CONFIG_HOME").unwrap_or_else(|| home.join(".config"));
+        let rel = match browser {
+            "brave" => "BraveSoftware/Brave-Browser",
+            "chrome" => "google-chrome",
+            "chromium" => "chromium",
+            "edge" => "microsoft-edge",
+            "opera" => "opera",
+            "vivaldi" => "vivaldi",
+            "whale" => "naver-whale",
+            _ => return None,
+        };
+        Some(config.join(rel))
+    }
+}
+
+/// Names of the profile directories under `dir`, sorted.
+fn scan_profiles(dir: &Path, marker: &str) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut profiles: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.path().join(marker).is_file())
+        .filter_map(|e| e.file_name().into_string().ok())
+        .collect();
+    profiles.sort();
+    profiles
+}
+
+/// Profiles of `browser` on this machine; empty when it isn't installed or
+/// has no profiles (Safari).
+pub fn list(browser: &str) -> Result<Vec<String>, AppError> {
+    let spec = security::parse_cookie_browser(browser)?;
+    Ok(user_data_dir(&spec.browser)
+        .map(|dir| scan_profiles(&dir, profile_marker(&spec.browser)))
+        .unwrap_or_default())
+}
+
+/// Check that the profile named in `spec` exists, so a typo fails before
+/// yt-dlp is spawned. Specs without a profile always pass.
+pub fn check_profile(spec: &CookieBrowserSpec) -> Result<(), AppError> {
+    let Some(profile) = spec.profile.as_deref() else {
+        return Ok(());
+    };
+    // yt-dlp also takes a path to the profile directory
+    let path = Path::new(profile);
+    let found = if path.is_absolute() {
+        path.is_dir()
+    } else {
+        user_data_dir(&spec.browser)
+            .map(|dir| scan_profiles(&dir, profile_marker(&spec.browser)))
+            .is_some_and(|profiles| profiles.iter().any(|p| p == profile))
+    };
+    if found {
+        Ok(())
+    } else {
+        Err(AppError::Custom(format!(
+            "{} profile not found: '{}'",
+            spec.browser, profile
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_lists_only_directories_with_the_marker() {
+        let dir = std::env::temp_dir().join(format!("profiles-{}", std::process::id()));
+        for (name, marker) in [("Default", true), ("Profile 2", true), ("Crashpad", false)] {
+            std::fs::create_dir_all(dir.join(name)).unwrap();
+            if marker {
+                std::fs::write(dir.join(name).join("Preferences"), "{}").unwrap();
+            }
+        }
+        std::fs::write(dir.join("Local State"), "{}").unwrap();
+
+        assert_eq!(scan_profiles(&dir, "Preferences"), ["Default", "Profile 2"]);
+        assert!(scan_profiles(&dir.join("missing"), "Preferences").is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn spec_without_profile_or_with_existing_path_passes() {
+        let spec = security::parse_cookie_browser("chrome").unwrap();
+        assert!(check_profile(&spec).is_ok());
+
+        let tmp = std::env::temp_dir();
+        let spec = security::parse_cookie_browser(&format!("chrome:{}", tmp.display())).unwrap();
+        assert!(check_profile(&spec).is_ok());
+        let spec = security::parse_cookie_browser("chrome:/no/such/profile").unwrap();
+        assert!(check_profile(&spec).is_err());
+    }
+}
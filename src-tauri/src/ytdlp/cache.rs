@@ -0,0 +1,170 @@
+//! The app's working caches: partial downloads in the temp dir (`partial/`
+//! under the app cache dir unless the user chose another) and the codec
+//! sidecars yt-dlp prints for each running task (`codecs/<task id>.txt`).
+//!
+//! An entry is in use while a download can still pick it up: a codec sidecar
+//! while its task runs, a partial while a pending, downloading or paused task
+//! owns it. Clearing and pruning only ever remove entries nothing uses.
+
+use crate::modules::logger;
+use crate::modules::types::AppError;
+use crate::ytdlp::download::{self, DownloadManager};
+use crate::ytdlp::types::{CacheClearResult, CacheKind, CacheKindStats, CacheStats};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+const KINDS: [CacheKind; 2] = [CacheKind::Partial, CacheKind::Codecs];
+
+/// Folder of a kind, and whether every file in it is a cache entry. A temp
+/// dir the user chose may hold other files; only yt-dlp partials count there.
+fn kind_dir(app: &AppHandle, kind: CacheKind) -> Option<(PathBuf, bool)> {
+    match kind {
+        CacheKind::Partial => {
+            let settings = crate::ytdlp::settings::get_settings(app).ok()?;
+            download::partial_dir(app, &settings)
+        }
+        CacheKind::Codecs => download::codec_cache_dir(app).map(|dir| (dir, true)),
+    }
+}
+
+/// Task a codec sidecar belongs to: the part of the file name before the first dot.
+fn task_id_of(path: &Path) -> Option<u64> {
+    let name = path.file_name()?.to_str()?;
+    name.split('.').next()?.parse().ok()
+}
+
+/// Files of one cache kind with their sizes.
+fn entries(dir: &Path, owned: bool) -> Vec<(PathBuf, u64)> {
+    let Ok(read) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    read.flatten()
+        .filter(|e| owned || download::is_partial_file(&e.file_name().to_string_lossy()))
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            meta.is_file().then(|| (e.path(), meta.len()))
+        })
+        .collect()
+}
+
+/// What running and unfinished downloads still need.
+struct InUse {
+    running: HashSet<u64>,
+    partials: download::ActiveTasks,
+}
+
+impl InUse {
+    fn load(app: &AppHandle) -> Result<Self, AppError> {
+        Ok(Self {
+            running: app
+                .state::<Arc<DownloadManager>>()
+                .running_task_ids()
+                .into_iter()
+                .collect(),
+            partials: download::active_tasks(app)?,
+        })
+    }
+
+    fn uses(&self, kind: CacheKind, path: &Path) -> bool {
+        match kind {
+            CacheKind::Partial => path
+                .file_name()
+                .is_some_and(|n| self.partials.owns(&n.to_string_lossy())),
+            CacheKind::Codecs => is_live_sidecar(path, &self.running),
+        }
+    }
+}
+
+fn is_live_sidecar(path: &Path, running: &HashSet<u64>) -> bool {
+    task_id_of(path).is_some_and(|id| running.contains(&id))
+}
+
+pub fn stats(app: &AppHandle) -> Result<CacheStats, AppError> {
+    let in_use = InUse::load(app)?;
+    let kinds = KINDS
+        .into_iter()
+        .map(|kind| {
+            let files = kind_dir(app, kind)
+                .map(|(dir, owned)| entries(&dir, owned))
+                .unwrap_or_default();
+            CacheKindStats {
+                kind,
+                entries: files.len() as u32,
+                total_bytes: files.iter().map(|(_, size)| size).sum(),
+                orphaned: files.iter().filter(|(p, _)| !in_use.uses(kind, p)).count() as u32,
+            }
+        })
+        .collect();
+    Ok(CacheStats { kinds })
+}
+
+/// Delete the entries of `kind` (every kind when None) that nothing uses.
+fn remove_unused(app: &AppHandle, kind: Option<CacheKind>) -> Result<CacheClearResult, AppError> {
+    let in_use = InUse::load(app)?;
+    let mut result = CacheClearResult {
+        removed: 0,
+        freed_bytes: 0,
+    };
+    for kind in KINDS
+        .into_iter()
+        .filter(|k| kind.is_none_or(|only| only == *k))
+    {
+        let Some((dir, owned)) = kind_dir(app, kind) else {
+            continue;
+        };
+        for (path, size) in entries(&dir, owned) {
+            if in_use.uses(kind, &path) {
+                continue;
+            }
+            match std::fs::remove_file(&path) {
+                Ok(()) => {
+                    result.removed += 1;
+                    result.freed_bytes += size;
+                }
+                Err(e) => logger::warn_cat(
+                    "app",
+                    &format!("Failed to remove cache entry {}: {}", path.display(), e),
+                ),
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Empty one cache kind, keeping what running or unfinished downloads use.
+pub fn clear(app: &AppHandle, kind: Option<CacheKind>) -> Result<CacheClearResult, AppError> {
+    remove_unused(app, kind)
+}
+
+/// Remove entries of every kind that no download uses any more.
+pub fn prune(app: &AppHandle) -> Result<CacheClearResult, AppError> {
+    let result = remove_unused(app, None)?;
+    if result.removed > 0 {
+        logger::info_cat(
+            "app",
+            &format!(
+                "Pruned {} unused cache entries ({} bytes)",
+                result.removed, result.freed_bytes
+            ),
+        );
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codec_sidecars_are_keyed_by_task_id() {
+        assert_eq!(task_id_of(Path::new("/c/codecs/42.txt")), Some(42));
+        assert_eq!(task_id_of(Path::new("/c/codecs/notes.txt")), None);
+
+        let running: HashSet<u64> = [42].into();
+        assert!(is_live_sidecar(Path::new("/c/codecs/42.txt"), &running));
+        assert!(!is_live_sidecar(Path::new("/c/codecs/43.txt"), &running));
+        assert!(!is_live_sidecar(Path::new("/c/codecs/notes.txt"), &running));
+    }
+}
@@ -0,0 +1,123 @@
+//! `codecPreference` setting: preferred video codecs, tried in order. yt-dlp's
+//! `-S vcodec:X` takes a single codec as a limit and ignores repeats, so the
+//! preference becomes format filters instead: every video selector is tried
+//! with the first codec's filter, then the next, with the unfiltered selector
+//! as the last fallback (`bv*[vcodec~='^(av01)']+ba/bv*[vcodec~='^(avc1|…)']+ba/bv*+ba`).
+
+use crate::modules::types::AppError;
+
+/// Codec names yt-dlp uses, with common aliases and the prefixes of the
+/// `vcodec` strings sites report for them
+const KNOWN_CODECS: &[(&str, &[&str], &[&str])] = &[
+    ("av01", &["av1"], &["av01", "av1"]),
+    ("vp9.2", &[], &["vp09.02", "vp9.2"]),
+    ("vp9", &[], &["vp09", "vp9"]),
+    (
+        "h265",
+        &["hevc", "hvc1", "hev1"],
+        &["hvc1", "hev1", "h265", "hevc"],
+    ),
+    ("h264", &["avc", "avc1"], &["avc1", "avc3", "h264"]),
+    ("vp8", &[], &["vp8"]),
+    ("h263", &[], &["h263"]),
+    ("theora", &[], &["theora"]),
+];
+
+fn canonical(name: &str) -> Option<&'static str> {
+    KNOWN_CODECS
+        .iter()
+        .find(|(codec, aliases, _)| *codec == name || aliases.contains(&name))
+        .map(|(codec, _, _)| *codec)
+}
+
+/// Lowercase, map aliases to yt-dlp's names and drop duplicates. Unknown
+/// codecs are an error.
+pub fn sanitize(entries: &[String]) -> Result<Vec<String>, AppError> {
+    let mut out: Vec<String> = Vec::new();
+    for entry in entries {
+        let entry = entry.trim().to_lowercase();
+        if entry.is_empty() {
+            continue;
+        }
+        let codec = canonical(&entry).ok_or_else(|| {
+            AppError::Custom(format!(
+                "Unknown video codec: {} (known: {})",
+                entry,
+                KNOWN_CODECS
+                    .iter()
+                    .map(|(c, _, _)| *c)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })?;
+        if !out.iter().any(|c| c == codec) {
+            out.push(codec.to_string());
+        }
+    }
+    Ok(out)
+}
+
+fn prefixes(codec: &str) -> &'static [&'static str] {
+    KNOWN_CODECS
+        .iter()
+        .find(|(c, _, _)| *c == codec)
+        .map_or(&[], |(_, _, prefixes)| *prefixes)
+}
+
+/// `[vcodec~='^(…)']` filters for the preference, most preferred first.
+/// Entries that aren't known codecs are skipped.
+pub fn filters(preference: &[String]) -> Vec<String> {
+    preference
+        .iter()
+        .filter_map(|c| canonical(&c.to_lowercase()))
+        .map(|c| format!("[vcodec~='^({})']", prefixes(c).join("|")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn sanitize_maps_aliases_and_dedupes() {
+        assert_eq!(
+            sanitize(&strings(&[" AV1", "avc1", "av01", ""])).unwrap(),
+            strings(&["av01", "h264"])
+        );
+        assert!(sanitize(&strings(&["mpeg2"])).is_err());
+    }
+
+    #[test]
+    fn filters_list_codecs_in_order() {
+        assert_eq!(
+            filters(&strings(&["av01", "h264"])),
+            strings(&["[vcodec~='^(av01|av1)']", "[vcodec~='^(avc1|avc3|h264)']"])
+        );
+        assert!(filters(&[]).is_empty());
+    }
+
+    /// First format matching the first filter that matches anything, the way
+    /// yt-dlp walks `/` alternatives.
+    fn pick<'a>(preference: &[&str], available: &[&'a str]) -> Option<&'a str> {
+        preference.iter().find_map(|codec| {
+            let prefixes = prefixes(canonical(codec)?);
+            available
+                .iter()
+                .find(|vcodec| prefixes.iter().any(|p| vcodec.starts_with(p)))
+                .copied()
+        })
+    }
+
+    #[test]
+    fn every_preference_counts_not_just_the_first() {
+        let available = ["vp09.00.40.08", "avc1.64001F"];
+        // No AV1 here: the second choice wins over yt-dlp's default (VP9)
+        assert_eq!(pick(&["av01", "h264"], &available), Some("avc1.64001F"));
+        assert_eq!(pick(&["vp9", "h264"], &available), Some("vp09.00.40.08"));
+        assert_eq!(pick(&["h265", "av01"], &available), None);
+    }
+}
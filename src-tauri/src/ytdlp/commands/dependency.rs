@@ -1,19 +1,101 @@
 use crate::modules::logger;
 use crate::modules::types::AppError;
-use crate::ytdlp::binary;
 use crate::ytdlp::types::*;
+use crate::ytdlp::{binary, config_check};
 use tauri::AppHandle;
 
 #[tauri::command]
 #[specta::specta]
-pub async fn check_dependencies() -> Result<DependencyStatus, AppError> {
-    Ok(binary::check_dependencies().await)
+pub async fn check_dependencies(app: AppHandle) -> Result<DependencyStatus, AppError> {
+    let pinned = crate::ytdlp::dep_ffmpeg::pinned_version(&app);
+    Ok(binary::check_dependencies(pinned).await)
 }
 
+/// Drop the cached dependency status and resolve yt-dlp again, for binaries
+/// installed or removed outside the app (e.g. `brew install yt-dlp`).
 #[tauri::command]
 #[specta::specta]
-pub async fn update_ytdlp() -> Result<String, AppError> {
-    binary::update_ytdlp().await
+pub async fn refresh_binary_resolution(app: AppHandle) -> Result<DependencyStatus, AppError> {
+    binary::invalidate_dep_cache();
+    match binary::resolve_ytdlp_path_with_app(&app).await {
+        Ok(path) => logger::info_cat("dependency", &format!("Re-scan: yt-dlp at {}", path)),
+        Err(e) => logger::warn_cat("dependency", &format!("Re-scan: {}", e)),
+    }
+    let pinned = crate::ytdlp::dep_ffmpeg::pinned_version(&app);
+    Ok(binary::check_dependencies(pinned).await)
+}
+
+/// Run `yt-dlp --update`, killing it after `timeout_secs` (default 120).
+#[tauri::command]
+#[specta::specta]
+pub async fn update_ytdlp(app: AppHandle, timeout_secs: Option<u64>) -> Result<String, AppError> {
+    let backup_count = crate::ytdlp::settings::get_settings(&app)
+        .map(|s| s.binary_backup_count)
+        .unwrap_or(1);
+    binary::update_ytdlp(timeout_secs, backup_count).await
+}
+
+/// Abort a running `update_ytdlp`. Returns false when no update is running.
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_update() -> Result<bool, AppError> {
+    Ok(binary::cancel_update())
+}
+
+/// Restore a saved yt-dlp binary: `index` from `list_ytdlp_backups`, default 1
+/// (the one replaced last). Returns its version.
+#[tauri::command]
+#[specta::specta]
+pub async fn rollback_ytdlp_update(index: Option<u32>) -> Result<String, AppError> {
+    let result = binary::rollback_update(index).await;
+    binary::invalidate_dep_cache();
+    result
+}
+
+/// yt-dlp binaries kept for rollback, newest first.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_ytdlp_backups() -> Result<Vec<YtdlpBackup>, AppError> {
+    binary::list_backups().await
+}
+
+/// Install a specific ffmpeg build and keep it there: a release branch ("7.1")
+/// resolves to its newest dated BtbN release, which the pin then names
+/// ("7.1@autobuild-…"); a release tag on macOS. The binaries it replaces are
+/// kept for `rollback_ffmpeg`.
+#[tauri::command]
+#[specta::specta]
+pub async fn install_ffmpeg_version(app: AppHandle, version: String) -> Result<String, AppError> {
+    let result = crate::ytdlp::dep_ffmpeg::install_ffmpeg_version(&app, &version).await;
+    binary::invalidate_dep_cache();
+    if let Err(e) = &result {
+        crate::ytdlp::dep_download::emit_stage(
+            &app,
+            "ffmpeg",
+            DepInstallStage::Failed,
+            Some(&e.to_string()),
+        );
+    }
+    result
+}
+
+/// Stop following the pinned ffmpeg build; installs and updates get the latest
+/// one again. The installed binaries are left as they are.
+#[tauri::command]
+#[specta::specta]
+pub async fn unpin_ffmpeg(app: AppHandle) -> Result<(), AppError> {
+    let result = crate::ytdlp::dep_ffmpeg::unpin_ffmpeg(&app).await;
+    binary::invalidate_dep_cache();
+    result
+}
+
+/// Restore the ffmpeg replaced by the last `install_ffmpeg_version`. Returns its version.
+#[tauri::command]
+#[specta::specta]
+pub async fn rollback_ffmpeg(app: AppHandle) -> Result<String, AppError> {
+    let result = crate::ytdlp::dep_ffmpeg::rollback_ffmpeg(&app).await;
+    binary::invalidate_dep_cache();
+    result
 }
 
 #[tauri::command]
@@ -22,6 +104,82 @@ pub fn get_cached_dep_status(app: AppHandle) -> Result<Option<FullDependencyStat
     Ok(binary::get_cached_dep_status(&app))
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn get_binary_info(app: AppHandle) -> Result<BinaryInfo, AppError> {
+    Ok(binary::get_binary_info(&app).await)
+}
+
+/// Find yt-dlp config files that set options the app also manages.
+#[tauri::command]
+#[specta::specta]
+pub async fn check_ytdlp_config_conflicts(app: AppHandle) -> Result<Vec<ConfigConflict>, AppError> {
+    let conflicts = config_check::scan_conflicts(&app).await;
+
+    if !conflicts.is_empty() {
+        logger::warn_cat(
+            "dependency",
+            &format!(
+                "Found {} yt-dlp config option(s) overlapping app flags",
+                conflicts.len()
+            ),
+        );
+    }
+    Ok(conflicts)
+}
+
+/// App, Tauri and build versions plus the yt-dlp range this build was tested with.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_app_info(app: AppHandle) -> Result<AppInfo, AppError> {
+    Ok(crate::ytdlp::app_info::app_info(&app).await)
+}
+
+/// Run the active yt-dlp with `--version` and explain why it fails to start, if it does.
+#[tauri::command]
+#[specta::specta]
+pub async fn diagnose_ytdlp_binary(app: AppHandle) -> Result<BinaryDiagnosis, AppError> {
+    let diagnosis = binary::diagnose_ytdlp(&app).await?;
+    if let Some(cause) = diagnosis.cause {
+        logger::warn_cat(
+            "dependency",
+            &format!(
+                "yt-dlp at {} does not run ({:?}): {}",
+                diagnosis.path,
+                cause,
+                diagnosis.stderr.as_deref().unwrap_or("")
+            ),
+        );
+    }
+    Ok(diagnosis)
+}
+
+/// Check that GitHub (dependency downloads) and a sample extractor host answer.
+#[tauri::command]
+#[specta::specta]
+pub async fn test_network(app: AppHandle) -> Result<NetworkReport, AppError> {
+    crate::ytdlp::network::test_network(&app).await
+}
+
+/// Result of the last network check (e.g. the startup one), if any has finished.
+#[tauri::command]
+#[specta::specta]
+pub fn get_network_report() -> Result<Option<NetworkReport>, AppError> {
+    Ok(crate::ytdlp::network::last_report())
+}
+
+/// Aggregate app state (dependencies, databases, manager, disk, config) for triage.
+#[tauri::command]
+#[specta::specta]
+pub async fn health_check(app: AppHandle) -> Result<HealthReport, AppError> {
+    let report = crate::ytdlp::health::health_check(&app).await;
+    logger::info_cat(
+        "app",
+        &format!("Health check: overall={:?}", report.overall),
+    );
+    Ok(report)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn check_full_dependencies(
@@ -146,7 +304,7 @@ pub async fn check_dependency_update(
 ) -> Result<DepUpdateInfo, AppError> {
     let latest = match dep_name.as_str() {
         "yt-dlp" => crate::ytdlp::dep_ytdlp::get_latest_version().await?,
-        "ffmpeg" => crate::ytdlp::dep_ffmpeg::get_latest_version().await?,
+        "ffmpeg" => crate::ytdlp::dep_ffmpeg::get_latest_version(&app).await?,
         "deno" => crate::ytdlp::dep_deno::get_latest_version().await?,
         _ => {
             return Err(AppError::DependencyInstallError(format!(
@@ -166,8 +324,11 @@ pub async fn check_dependency_update(
         });
 
     // Compare versions: update is available if we can't determine current version
-    // or if the latest version string differs from the current one
+    // or if the latest version string differs from the current one. A pinned
+    // ffmpeg stays on its build until it is unpinned.
+    let pinned = dep_name == "ffmpeg" && crate::ytdlp::dep_ffmpeg::pinned_version(&app).is_some();
     let update_available = match &current_version {
+        _ if pinned => false,
         Some(current) => {
             // Normalize: strip leading 'v' for comparison
             let current_normalized = current.trim_start_matches('v');
@@ -245,3 +406,41 @@ pub async fn delete_app_managed_dep(app: AppHandle, dep_name: String) -> Result<
         Ok(format!("{}: deleted {}", dep_name, deleted.join(", ")))
     }
 }
+
+/// Compare extractor support between two locally available yt-dlp versions.
+#[tauri::command]
+#[specta::specta]
+pub async fn diff_extractors(
+    app: AppHandle,
+    version_a: String,
+    version_b: String,
+) -> Result<ExtractorDiff, AppError> {
+    binary::diff_extractors(&app, &version_a, &version_b).await
+}
+
+/// List the user-registered yt-dlp binaries with their probed versions.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_ytdlp_binaries(app: AppHandle) -> Result<Vec<YtdlpBinary>, AppError> {
+    binary::list_ytdlp_binaries(&app).await
+}
+
+/// Switch downloads to a registered yt-dlp (or back to automatic with `null`).
+#[tauri::command]
+#[specta::specta]
+pub async fn set_active_ytdlp(
+    app: AppHandle,
+    label: Option<String>,
+) -> Result<BinaryInfo, AppError> {
+    binary::set_active_ytdlp(&app, label).await
+}
+
+/// Time yt-dlp start-up (`--version`) and a `--simulate` run; `runs` defaults to 3 (max 10).
+#[tauri::command]
+#[specta::specta]
+pub async fn benchmark_ytdlp_spawn(
+    app: AppHandle,
+    runs: Option<u32>,
+) -> Result<SpawnBench, AppError> {
+    binary::benchmark_spawn(&app, runs.unwrap_or(3).clamp(1, 10)).await
+}
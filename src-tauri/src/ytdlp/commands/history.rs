@@ -1,7 +1,11 @@
+use crate::modules::logger;
 use crate::modules::types::AppError;
+use crate::ytdlp::existing_file;
 use crate::ytdlp::types::*;
+use tauri::ipc::Channel;
 use tauri::AppHandle;
 use tauri::Manager;
+use tauri_plugin_dialog::DialogExt;
 
 #[tauri::command]
 #[specta::specta]
@@ -10,20 +14,63 @@ pub async fn get_download_history(
     page: u32,
     page_size: u32,
     search: Option<String>,
+    favorites_only: Option<bool>,
+    sort_by: Option<HistorySortKey>,
+    sort_dir: Option<SortDir>,
 ) -> Result<HistoryResult, AppError> {
     let db = app.state::<crate::DbState>();
-    db.get_history(page, page_size, search.as_deref())
+    db.get_history(
+        page,
+        page_size,
+        search.as_deref(),
+        favorites_only.unwrap_or(false),
+        sort_by.unwrap_or_default(),
+        sort_dir.unwrap_or_default(),
+    )
 }
 
+/// Where `url` would be saved, if a file is already there: its path and a free
+/// name for saving alongside it (relative to the download folder). Playlists
+/// and failed lookups count as nothing found.
+async fn output_on_disk(app: &AppHandle, url: String) -> Option<(String, String)> {
+    let preview =
+        match crate::ytdlp::metadata::preview_output_filename(app.clone(), url, None).await {
+            Ok(preview) if !preview.playlist_example => preview,
+            Ok(_) => return None,
+            Err(e) => {
+                logger::debug_cat(
+                    "metadata",
+                    &format!("Output filename lookup for duplicate check failed: {}", e),
+                );
+                return None;
+            }
+        };
+    let found = existing_file::find_on_disk(std::path::Path::new(&preview.full_path))?;
+    let dir = found.parent()?;
+    let free = existing_file::free_name(&found.file_name()?.to_string_lossy(), |name| {
+        dir.join(name).exists()
+    })?;
+    let rename_to = std::path::Path::new(&preview.filename)
+        .with_file_name(free)
+        .to_string_lossy()
+        .to_string();
+    Some((found.to_string_lossy().to_string(), rename_to))
+}
+
+/// `url` adds a check for a file already at the would-be output path, which
+/// catches files kept after history was cleared. It costs a yt-dlp run, so
+/// batch adds leave it out.
 #[tauri::command]
 #[specta::specta]
 pub async fn check_duplicate(
     app: AppHandle,
     video_id: String,
+    url: Option<String>,
 ) -> Result<DuplicateCheckResult, AppError> {
     let db = app.state::<crate::DbState>();
     let history_item = db.check_duplicate(&video_id)?;
     let in_queue = db.check_duplicate_in_queue(&video_id)?;
+    let blocked = db.is_video_blocked(&video_id)?;
 
     let file_exists = if let Some(ref item) = history_item {
         match tokio::fs::metadata(&item.file_path).await {
@@ -37,17 +84,637 @@ pub async fn check_duplicate(
         false
     };
 
+    let on_disk = match url {
+        Some(url) if !in_queue => output_on_disk(&app, url).await,
+        _ => None,
+    };
+
     Ok(DuplicateCheckResult {
         in_history: history_item.is_some(),
         in_queue,
         history_item,
         file_exists,
+        file_on_disk: on_disk.is_some(),
+        rename_to: on_disk.as_ref().map(|(_, name)| name.clone()),
+        disk_path: on_disk.map(|(path, _)| path),
+        blocked,
     })
 }
 
+/// Never queue `video_id` again, from any source (manual adds, imports, watched
+/// channels). Returns false if it was already blocked.
+#[tauri::command]
+#[specta::specta]
+pub fn block_video(app: AppHandle, video_id: String) -> Result<bool, AppError> {
+    let video_id = video_id.trim();
+    if video_id.is_empty() {
+        return Err(AppError::Custom("Video id cannot be empty".to_string()));
+    }
+    let added = app.state::<crate::DbState>().block_video(video_id)?;
+    if added {
+        logger::info_cat("download", &format!("Blocked video {}", video_id));
+    }
+    Ok(added)
+}
+
+/// Take `video_id` off the blocklist. Returns false if it wasn't blocked.
+#[tauri::command]
+#[specta::specta]
+pub fn unblock_video(app: AppHandle, video_id: String) -> Result<bool, AppError> {
+    let removed = app
+        .state::<crate::DbState>()
+        .unblock_video(video_id.trim())?;
+    if removed {
+        logger::info_cat("download", &format!("Unblocked video {}", video_id.trim()));
+    }
+    Ok(removed)
+}
+
+/// Every blocked video id, most recently blocked first.
+#[tauri::command]
+#[specta::specta]
+pub fn list_blocked(app: AppHandle) -> Result<Vec<BlockedVideo>, AppError> {
+    app.state::<crate::DbState>().list_blocked_videos()
+}
+
+/// All columns of a history row plus its info-json sidecar (summarized) and the
+/// log lines of the download that produced it.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_history_detail(app: AppHandle, id: u64) -> Result<HistoryDetail, AppError> {
+    const MAX_LOGS: u32 = 200;
+
+    let (item, task_id) = {
+        let db = app.state::<crate::DbState>();
+        let item = db
+            .get_history_item(id)?
+            .ok_or_else(|| AppError::Custom(format!("History item {} not found", id)))?;
+        let task_id = db.task_id_for_history(&item.video_id, item.downloaded_at)?;
+        (item, task_id)
+    };
+
+    let sidecar = crate::ytdlp::normalize::info_json_path(&item.file_path);
+    let info_json = match tokio::fs::read_to_string(&sidecar).await {
+        Ok(text) => match serde_json::from_str::<serde_json::Value>(&text) {
+            Ok(info) => {
+                serde_json::to_string_pretty(&crate::ytdlp::normalize::summarize_info(&info)).ok()
+            }
+            Err(e) => {
+                logger::warn_cat(
+                    "app",
+                    &format!("Unreadable info-json {}: {}", sidecar.display(), e),
+                );
+                None
+            }
+        },
+        Err(_) => None,
+    };
+
+    let mut needles = Vec::new();
+    if let Some(task_id) = task_id {
+        needles.push(format!("[download:{}]", task_id));
+    }
+    if !item.video_id.is_empty() {
+        needles.push(item.video_id.clone());
+    }
+    let logs = app
+        .state::<crate::LogDbState>()
+        .logs_mentioning(&needles, MAX_LOGS)?;
+
+    Ok(HistoryDetail {
+        file_exists: std::path::Path::new(&item.file_path).exists(),
+        info_json_path: info_json
+            .is_some()
+            .then(|| sidecar.to_string_lossy().to_string()),
+        info_json,
+        task_id,
+        logs,
+        item,
+    })
+}
+
+/// Check a downloaded file with ffprobe now and store the outcome on its history row.
+#[tauri::command]
+#[specta::specta]
+pub async fn verify_history_item(app: AppHandle, id: u64) -> Result<HistoryItem, AppError> {
+    let item = app
+        .state::<crate::DbState>()
+        .get_history_item(id)?
+        .ok_or_else(|| AppError::Custom("History item not found".to_string()))?;
+    let verification = crate::ytdlp::verify::verify_file(
+        &app,
+        &item.video_url,
+        std::path::Path::new(&item.file_path),
+    )
+    .await
+    .ok_or_else(|| {
+        AppError::BinaryNotFound("ffprobe is required to verify downloads".to_string())
+    })?;
+    let db = app.state::<crate::DbState>();
+    db.set_history_verification(id, verification.status, verification.error.as_deref())?;
+    db.get_history_item(id)?
+        .ok_or_else(|| AppError::Custom("History item not found".to_string()))
+}
+
+/// Apply finishing steps (tags, cover art, subtitles, chapters) to a downloaded
+/// file without downloading it again, and store the result on its history row.
+#[tauri::command]
+#[specta::specta]
+pub async fn rerun_postprocessing(
+    app: AppHandle,
+    history_id: u64,
+    steps: PostprocessSteps,
+) -> Result<HistoryItem, AppError> {
+    let steps = crate::ytdlp::postprocess::sanitize(steps)?;
+    let item = app
+        .state::<crate::DbState>()
+        .get_history_item(history_id)?
+        .ok_or_else(|| AppError::Custom("History item not found".to_string()))?;
+    crate::ytdlp::postprocess::rerun(&app, &item, &steps).await?;
+
+    let path = std::path::Path::new(&item.file_path);
+    let file_size = tokio::fs::metadata(path).await.ok().map(|m| m.len());
+    let chapters_embedded = if steps.embed_chapters {
+        crate::ytdlp::probe::file_chapter_count(&app, path)
+            .await
+            .is_some_and(|n| n > 0)
+    } else {
+        item.chapters_embedded
+    };
+    let db = app.state::<crate::DbState>();
+    db.set_history_postprocessed(history_id, file_size, chapters_embedded)?;
+    logger::info_cat(
+        "download",
+        &format!("[history:{}] post-processing re-run finished", history_id),
+    );
+    db.get_history_item(history_id)?
+        .ok_or_else(|| AppError::Custom("History item not found".to_string()))
+}
+
+/// Space used by downloads in each output directory, largest first. Sizes come
+/// from history; `stat_files` measures files whose size was never recorded
+/// (slower on large libraries, off by default).
+#[tauri::command]
+#[specta::specta]
+pub async fn get_storage_by_directory(
+    app: AppHandle,
+    stat_files: Option<bool>,
+) -> Result<Vec<DirUsage>, AppError> {
+    let files = {
+        let db = app.state::<crate::DbState>();
+        db.history_file_sizes()?
+    };
+    let stat_files = stat_files.unwrap_or(false);
+    tokio::task::spawn_blocking(move || crate::ytdlp::disk::usage_by_directory(files, stat_files))
+        .await
+        .map_err(|e| AppError::Custom(format!("Storage scan failed: {}", e)))
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn delete_history_item(app: AppHandle, id: u64) -> Result<(), AppError> {
     let db = app.state::<crate::DbState>();
     db.delete_history(id)
 }
+
+/// Queue a fresh download of a history item with different options. The history
+/// row is kept and linked to the new task through `superseded_by` once it
+/// completes; with `replace_existing` the new download is written aside and
+/// replaces the old file only when it succeeds. Returns the new task id.
+#[tauri::command]
+#[specta::specta]
+pub async fn redownload_with_options(
+    app: AppHandle,
+    history_id: u64,
+    new_options: RedownloadOptions,
+) -> Result<u64, AppError> {
+    let item = app
+        .state::<crate::DbState>()
+        .get_history_item(history_id)?
+        .ok_or_else(|| AppError::Custom(format!("History item {} not found", history_id)))?;
+
+    let output_dir = new_options.output_dir.or_else(|| {
+        std::path::Path::new(&item.file_path)
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .map(|p| p.to_string_lossy().to_string())
+    });
+    let request = DownloadRequest {
+        video_url: item.video_url,
+        video_id: item.video_id,
+        title: item.title,
+        format_id: new_options.format_id.unwrap_or(item.format),
+        quality_label: new_options.quality_label.unwrap_or(item.quality_label),
+        output_dir,
+        cookie_browser: None,
+        playlist_id: None,
+        playlist_index: None,
+        credentials: None,
+        keep_separate_streams: new_options.keep_separate_streams,
+        max_filesize: None,
+        audio_lang: None,
+        embed_metadata: false,
+        embed_thumbnail: false,
+        preset: None,
+        live_from_start: false,
+        subtitles_only: false,
+        subtitle_langs: Vec::new(),
+        convert_subs: None,
+        tags: item.user_tags,
+        headers: None,
+        max_retries: None,
+        retry_backoff_secs: None,
+        on_existing_file: None,
+    };
+
+    let task_id = crate::ytdlp::download::enqueue(
+        &app,
+        request,
+        Some(crate::ytdlp::download::RedownloadLink {
+            history_id,
+            replace_existing: new_options.replace_existing,
+        }),
+    )
+    .await?;
+
+    logger::info_cat(
+        "download",
+        &format!(
+            "[download:{}] re-download of history item {} queued (replace={})",
+            task_id, history_id, new_options.replace_existing
+        ),
+    );
+    Ok(task_id)
+}
+
+/// Backfill uploader, container, tags and duration on history rows that predate
+/// those columns, one batch per call. Sources per row: an info-json sidecar, the
+/// file itself, then a yt-dlp probe of the URL. Progress goes out per row on
+/// `on_progress`. Pages like `backfill_durations`: each row is marked visited
+/// as it finishes, so an interrupted batch loses nothing; call again with
+/// `next_before_id` until it comes back None.
+#[tauri::command]
+#[specta::specta]
+pub async fn normalize_history(
+    app: AppHandle,
+    batch_size: Option<u32>,
+    before_id: Option<u64>,
+    on_progress: Channel<NormalizeProgress>,
+) -> Result<NormalizeSummary, AppError> {
+    let limit = batch_size.unwrap_or(20).clamp(1, 100);
+    let rows = app
+        .state::<crate::DbState>()
+        .history_to_normalize(before_id, limit)?;
+
+    let mut updated = 0;
+    for (processed, (id, file_path, video_url)) in rows.iter().enumerate() {
+        let fields = crate::ytdlp::normalize::row_fields(&app, file_path, video_url).await;
+        let changed = app
+            .state::<crate::DbState>()
+            .apply_normalized(*id, &fields)?;
+        if changed {
+            updated += 1;
+        }
+        // Dropped channel (page closed) just means nobody is watching
+        let _ = on_progress.send(NormalizeProgress {
+            history_id: *id,
+            processed: processed as u32 + 1,
+            batch_size: rows.len() as u32,
+            updated: changed,
+        });
+    }
+
+    logger::info_cat(
+        "download",
+        &format!("History normalize: {}/{} rows updated", updated, rows.len()),
+    );
+
+    let next_before_id = if rows.len() as u32 == limit {
+        rows.last().map(|(id, _, _)| *id)
+    } else {
+        None
+    };
+    Ok(NormalizeSummary {
+        scanned: rows.len() as u32,
+        updated,
+        next_before_id,
+    })
+}
+
+/// Probe durations for one bounded batch of history rows that predate the
+/// `duration_secs` column. The file on disk is probed with ffprobe, falling
+/// back to asking yt-dlp about the URL. Every row is marked probed, found or
+/// not, so rows that can't be probed are not tried again. Call again with
+/// `next_before_id` until it comes back None.
+#[tauri::command]
+#[specta::specta]
+pub async fn backfill_durations(
+    app: AppHandle,
+    batch_size: Option<u32>,
+    before_id: Option<u64>,
+) -> Result<DurationBackfill, AppError> {
+    let limit = batch_size.unwrap_or(20).clamp(1, 100);
+    let rows = app
+        .state::<crate::DbState>()
+        .history_missing_duration(before_id, limit)?;
+
+    let mut updated = 0;
+    for (id, file_path, video_url) in &rows {
+        let duration =
+            match crate::ytdlp::probe::file_duration(&app, std::path::Path::new(file_path)).await {
+                Some(secs) => Some(secs),
+                None => crate::ytdlp::probe::url_duration(&app, video_url).await,
+            };
+        app.state::<crate::DbState>()
+            .record_duration_probe(*id, duration)?;
+        if duration.is_some() {
+            updated += 1;
+        }
+    }
+
+    logger::info_cat(
+        "download",
+        &format!("Duration backfill: {}/{} rows updated", updated, rows.len()),
+    );
+
+    let next_before_id = if rows.len() as u32 == limit {
+        rows.last().map(|(id, _, _)| *id)
+    } else {
+        None
+    };
+    Ok(DurationBackfill {
+        scanned: rows.len() as u32,
+        updated,
+        next_before_id,
+    })
+}
+
+/// Completions per hour and average queue wait over the last `window_hours`
+/// (default 24, at most 90 days).
+#[tauri::command]
+#[specta::specta]
+pub async fn get_throughput_history(
+    app: AppHandle,
+    window_hours: Option<u32>,
+) -> Result<Throughput, AppError> {
+    use crate::ytdlp::throughput;
+
+    let window_hours = window_hours
+        .unwrap_or(throughput::DEFAULT_WINDOW_HOURS)
+        .clamp(1, throughput::MAX_WINDOW_HOURS);
+    let until = chrono::Utc::now().timestamp();
+    let rows = app
+        .state::<crate::DbState>()
+        .completion_timings(until - window_hours as i64 * 3600)?;
+    Ok(throughput::build(until, window_hours, &rows))
+}
+
+const MAX_TAG_LEN: usize = 64;
+
+pub(crate) fn check_tag(tag: &str) -> Result<&str, AppError> {
+    let tag = tag.trim();
+    if tag.is_empty() || tag.len() > MAX_TAG_LEN || tag.chars().any(char::is_control) {
+        return Err(AppError::Custom(format!(
+            "Tags must be 1-{} characters without control characters",
+            MAX_TAG_LEN
+        )));
+    }
+    Ok(tag)
+}
+
+/// Add user tag `tag` to every history item matching `search` (the Library search;
+/// None or empty means all). Returns how many items gained the tag.
+#[tauri::command]
+#[specta::specta]
+pub fn bulk_tag(app: AppHandle, search: Option<String>, tag: String) -> Result<u64, AppError> {
+    let tag = check_tag(&tag)?;
+    let search = search.as_deref().filter(|s| !s.is_empty());
+    let count = app.state::<crate::DbState>().bulk_tag(search, tag)?;
+    logger::info_cat(
+        "app",
+        &format!("Tagged {} history item(s) with \"{}\"", count, tag),
+    );
+    Ok(count)
+}
+
+/// Remove user tag `tag` from every history item matching `search`. Returns how many items lost it.
+#[tauri::command]
+#[specta::specta]
+pub fn bulk_untag(app: AppHandle, search: Option<String>, tag: String) -> Result<u64, AppError> {
+    let tag = check_tag(&tag)?;
+    let search = search.as_deref().filter(|s| !s.is_empty());
+    let count = app.state::<crate::DbState>().bulk_untag(search, tag)?;
+    logger::info_cat(
+        "app",
+        &format!("Removed tag \"{}\" from {} history item(s)", tag, count),
+    );
+    Ok(count)
+}
+
+/// Star or unstar a history item. Returns whether it is a favorite now.
+#[tauri::command]
+#[specta::specta]
+pub fn toggle_favorite(app: AppHandle, id: u64) -> Result<bool, AppError> {
+    app.state::<crate::DbState>()
+        .toggle_favorite(id)?
+        .ok_or_else(|| AppError::Custom(format!("History item {} not found", id)))
+}
+
+/// What renaming the current history files to `template` would do, without
+/// touching anything. Items whose file is missing are listed as such.
+#[tauri::command]
+#[specta::specta]
+pub async fn preview_rename(app: AppHandle, template: String) -> Result<Vec<RenamePlan>, AppError> {
+    let template = crate::ytdlp::security::sanitize_filename_template(&template)?;
+    crate::ytdlp::rename::check_template(&template)?;
+    let items = app.state::<crate::DbState>().current_history()?;
+    tokio::task::spawn_blocking(move || crate::ytdlp::rename::preview(&items, &template))
+        .await
+        .map_err(|e| AppError::Custom(e.to_string()))
+}
+
+/// Rename the files of `plans`, ready items of the latest `preview_rename`,
+/// and update their history rows. Nothing changes if any of them fails.
+/// Returns how many files were renamed.
+#[tauri::command]
+#[specta::specta]
+pub async fn apply_rename(app: AppHandle, plans: Vec<RenamePlan>) -> Result<u32, AppError> {
+    let db_app = app.clone();
+    let renamed = tokio::task::spawn_blocking(move || {
+        crate::ytdlp::rename::apply(&plans, |rows| {
+            db_app.state::<crate::DbState>().rename_history_paths(rows)
+        })
+    })
+    .await
+    .map_err(|e| AppError::Custom(e.to_string()))??;
+    logger::info_cat(
+        "app",
+        &format!("Renamed {} downloaded file(s) to a new template", renamed),
+    );
+    Ok(renamed)
+}
+
+const MAX_SIMILAR_RESULTS: usize = 20;
+
+/// History items that likely hold the same content as `video_id_or_url`, best
+/// match first. A history entry is looked up by id or URL; a URL not in history
+/// is probed with yt-dlp for its title and duration. Needs `similarity_fingerprints`.
+#[tauri::command]
+#[specta::specta]
+pub async fn find_similar(
+    app: AppHandle,
+    video_id_or_url: String,
+) -> Result<Vec<SimilarItem>, AppError> {
+    use crate::ytdlp::fingerprint;
+
+    if !crate::ytdlp::settings::get_settings(&app)?.similarity_fingerprints {
+        return Err(AppError::Custom(
+            "Similar-content search is off; enable it in settings".to_string(),
+        ));
+    }
+    let query = video_id_or_url.trim();
+    let is_url = query.starts_with("http://") || query.starts_with("https://");
+
+    let db = app.state::<crate::DbState>();
+    let filled = db.fill_fingerprints(fingerprint::title_key)?;
+    if filled > 0 {
+        logger::info_cat("app", &format!("Fingerprinted {} history item(s)", filled));
+    }
+
+    let known = if is_url {
+        db.history_by_url(query)?
+    } else {
+        db.check_duplicate(query)?
+    };
+    let (source_id, title, duration) = match known {
+        Some(item) => (Some(item.id), item.title, item.duration_secs),
+        None if is_url => {
+            let (title, duration) = crate::ytdlp::probe::url_title_and_duration(&app, query)
+                .await
+                .ok_or_else(|| {
+                    AppError::MetadataError("Could not read the title of this URL".to_string())
+                })?;
+            (None, title, duration)
+        }
+        None => {
+            return Err(AppError::Custom(format!(
+                "No history entry for video id {}",
+                query
+            )))
+        }
+    };
+    let key = fingerprint::title_key(&title);
+
+    let mut matches: Vec<(u64, f32, Option<u64>)> = db
+        .history_fingerprints()?
+        .into_iter()
+        .filter(|(id, _, _)| Some(*id) != source_id)
+        .filter_map(|(id, other, secs)| {
+            let confidence = fingerprint::score(&key, duration, &other, secs)?;
+            let diff = duration.zip(secs).map(|(a, b)| a.abs_diff(b));
+            (confidence >= fingerprint::MIN_CONFIDENCE).then_some((id, confidence, diff))
+        })
+        .collect();
+    matches.sort_by(|a, b| b.1.total_cmp(&a.1));
+    matches.truncate(MAX_SIMILAR_RESULTS);
+
+    let mut out = Vec::with_capacity(matches.len());
+    for (id, confidence, duration_diff_secs) in matches {
+        if let Some(item) = db.get_history_item(id)? {
+            out.push(SimilarItem {
+                item,
+                confidence,
+                duration_diff_secs,
+            });
+        }
+    }
+    Ok(out)
+}
+
+/// Write a playlist of the given history items, in that order, to `path` (a save
+/// dialog asks when None; None is returned if it's cancelled). Items whose file
+/// is missing are left out and listed. With `relative_paths` the entries point
+/// at the files relative to the playlist's folder, so the folder tree can move
+/// as a whole; files on another drive stay absolute.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_playlist(
+    app: AppHandle,
+    history_ids: Vec<u64>,
+    path: Option<String>,
+    format: PlaylistFormat,
+    relative_paths: bool,
+) -> Result<Option<PlaylistExport>, AppError> {
+    use crate::ytdlp::playlist_export::{self, PlaylistEntry};
+
+    let path = match path {
+        Some(path) => path,
+        None => {
+            let dialog_app = app.clone();
+            let ext = playlist_export::extension(format);
+            let picked = tokio::task::spawn_blocking(move || {
+                dialog_app
+                    .dialog()
+                    .file()
+                    .add_filter("Playlist", &[ext])
+                    .set_file_name(format!("playlist.{}", ext))
+                    .blocking_save_file()
+            })
+            .await
+            .map_err(|e| AppError::Custom(format!("Dialog task failed: {}", e)))?;
+            match picked {
+                Some(p) => p.to_string(),
+                None => return Ok(None),
+            }
+        }
+    };
+    let dest = std::path::PathBuf::from(crate::ytdlp::security::sanitize_output_path(&path)?);
+    let base = dest.parent().map(|p| p.to_path_buf());
+
+    let db = app.state::<crate::DbState>();
+    let mut entries = Vec::new();
+    let mut skipped = Vec::new();
+    for id in history_ids {
+        let item = match db.get_history_item(id)? {
+            Some(item) if std::path::Path::new(&item.file_path).is_file() => item,
+            other => {
+                skipped.push(PlaylistSkip {
+                    history_id: id,
+                    title: other.as_ref().map(|i| i.title.clone()),
+                    file_path: other.map(|i| i.file_path),
+                });
+                continue;
+            }
+        };
+        let file = std::path::Path::new(&item.file_path);
+        let entry_path = base
+            .as_deref()
+            .filter(|_| relative_paths)
+            .and_then(|base| playlist_export::relative_to(file, base))
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_else(|| item.file_path.clone());
+        entries.push(PlaylistEntry {
+            path: entry_path,
+            title: item.title,
+            duration_secs: item.duration_secs,
+        });
+    }
+
+    tokio::fs::write(&dest, playlist_export::render(&entries, format))
+        .await
+        .map_err(|e| AppError::FileError(format!("Failed to write {}: {}", path, e)))?;
+    logger::info_cat(
+        "download",
+        &format!(
+            "Exported playlist {} ({} entries, {} skipped)",
+            path,
+            entries.len(),
+            skipped.len()
+        ),
+    );
+    Ok(Some(PlaylistExport {
+        path,
+        entries: entries.len() as u32,
+        skipped,
+    }))
+}
@@ -2,6 +2,11 @@ use crate::modules::logger;
 use crate::modules::types::AppError;
 use crate::ytdlp::binary;
 use crate::ytdlp::download::DownloadManager;
+use crate::ytdlp::types::{
+    BackupImportMode, BatteryState, CacheClearResult, CacheKind, CacheStats, CleanupStatus,
+    CookiesValidation, CpuLoadState, FullBackupImport, FullBackupManifest, LeftoverCleanup,
+    LeftoverFile, LegacyMigration, OrphanProcess, PowerStatus, WritableCheck,
+};
 use std::sync::Arc;
 use tauri::AppHandle;
 use tauri::Manager;
@@ -19,9 +24,7 @@ pub fn set_minimize_to_tray(
     }
 
     if minimize {
-        if let Some(window) = app.get_webview_window("main") {
-            let _ = window.hide();
-        }
+        crate::ytdlp::tray::hide_main_window(&app);
     } else {
         let manager = app.state::<Arc<DownloadManager>>();
         manager.cancel_all();
@@ -49,6 +52,76 @@ pub fn get_recent_logs() -> String {
     logger::read_recent_logs(200)
 }
 
+/// yt-dlp partial files under `dir`, or under the download folders and temp dir.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_leftover_files(
+    app: AppHandle,
+    dir: Option<String>,
+) -> Result<Vec<LeftoverFile>, AppError> {
+    crate::ytdlp::download::list_leftovers(&app, dir.as_deref())
+}
+
+/// Delete partial files listed by `list_leftover_files`, identified by path.
+/// Pass the same `dir` the list was made with.
+#[tauri::command]
+#[specta::specta]
+pub async fn clean_leftover_files(
+    app: AppHandle,
+    dir: Option<String>,
+    paths: Vec<String>,
+) -> Result<LeftoverCleanup, AppError> {
+    let result = crate::ytdlp::download::clean_leftovers(&app, dir.as_deref(), &paths)?;
+    logger::info_cat(
+        "app",
+        &format!(
+            "Removed {} leftover partial file(s), {} bytes reclaimed, {} skipped",
+            result.removed,
+            result.reclaimed_bytes,
+            result.skipped.len()
+        ),
+    );
+    Ok(result)
+}
+
+/// Entry counts and sizes of the partial-download and codec caches.
+#[tauri::command]
+#[specta::specta]
+pub fn get_cache_stats(app: AppHandle) -> Result<CacheStats, AppError> {
+    crate::ytdlp::cache::stats(&app)
+}
+
+/// Empty one cache kind, or all of them when `kind` is None. Entries of
+/// running or unfinished downloads are kept.
+#[tauri::command]
+#[specta::specta]
+pub fn clear_cache(app: AppHandle, kind: Option<CacheKind>) -> Result<CacheClearResult, AppError> {
+    let result = crate::ytdlp::cache::clear(&app, kind)?;
+    logger::info_cat(
+        "app",
+        &format!(
+            "Cleared cache {:?}: {} entries, {} bytes",
+            kind, result.removed, result.freed_bytes
+        ),
+    );
+    Ok(result)
+}
+
+/// Remove cache entries no running or unfinished download uses.
+#[tauri::command]
+#[specta::specta]
+pub fn prune_cache(app: AppHandle) -> Result<CacheClearResult, AppError> {
+    crate::ytdlp::cache::prune(&app)
+}
+
+/// Run log trimming and VACUUM as soon as no download has been active for
+/// `cleanup_idle_secs`, instead of waiting for the daily pass.
+#[tauri::command]
+#[specta::specta]
+pub fn schedule_idle_cleanup(app: AppHandle) -> Result<CleanupStatus, AppError> {
+    Ok(crate::ytdlp::maintenance::schedule_heavy(&app))
+}
+
 /// Full factory reset: clear settings, databases (via live connections), binaries, and caches.
 ///
 /// Databases are cleared through their live connections rather than deleting files,
@@ -79,8 +152,8 @@ pub async fn reset_all_data(app: AppHandle) -> Result<Vec<String>, AppError> {
         Err(e) => results.push(format!("settings: error - {}", e)),
     }
 
-    // 3. Delete app-managed binaries (bin/ directory)
-    let bin_dir = app_data_dir.join("bin");
+    // 3. Delete app-managed binaries (bin/ directory, in the data dir override if set)
+    let bin_dir = crate::modules::data_dir::resolve(&app)?.join("bin");
     if bin_dir.exists() {
         match tokio::fs::remove_dir_all(&bin_dir).await {
             Ok(_) => results.push("bin/: deleted".to_string()),
@@ -121,3 +194,271 @@ pub async fn reset_all_data(app: AppHandle) -> Result<Vec<String>, AppError> {
 
     Ok(results)
 }
+
+/// Whether `dir` tells apart file names that differ only by case. When it
+/// doesn't, templates without a unique field like `%(id)s` can overwrite files.
+#[tauri::command]
+#[specta::specta]
+pub async fn check_case_sensitivity(dir: String) -> Result<bool, AppError> {
+    let path = std::path::PathBuf::from(&dir);
+    if !path.is_dir() {
+        return Err(AppError::FileError(format!("Not a directory: {}", dir)));
+    }
+    tokio::task::spawn_blocking(move || crate::ytdlp::disk::is_case_sensitive(&path))
+        .await
+        .map_err(|e| AppError::Custom(e.to_string()))?
+        .map_err(|e| AppError::FileError(format!("Failed to probe {}: {}", dir, e)))
+}
+
+/// Fresh scratch directory for a backup export/import under the app cache dir.
+fn backup_work_dir(app: &AppHandle) -> Result<std::path::PathBuf, AppError> {
+    let cache_dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| AppError::Custom(format!("Failed to get app cache dir: {}", e)))?;
+    crate::ytdlp::full_backup::work_dir(&cache_dir.join("backup"))
+}
+
+/// Write settings, queue, history, logs and the dependency list to one zip
+/// archive at `path`. With `redact_settings` secrets in the settings are masked
+/// (such an archive can't restore settings).
+#[tauri::command]
+#[specta::specta]
+pub async fn export_full_backup(
+    app: AppHandle,
+    path: String,
+    redact_settings: bool,
+) -> Result<FullBackupManifest, AppError> {
+    let dest = std::path::PathBuf::from(crate::ytdlp::security::sanitize_output_path(&path)?);
+    let mut settings = crate::ytdlp::settings::get_settings(&app)?;
+    if redact_settings {
+        settings = crate::ytdlp::settings::redacted(&settings);
+    }
+    let pinned = crate::ytdlp::dep_ffmpeg::pinned_version(&app);
+    let binaries = serde_json::json!({
+        "dependencies": binary::check_dependencies(pinned).await,
+        "ytdlpBackups": binary::list_backups().await.unwrap_or_default(),
+    });
+    let manifest = FullBackupManifest {
+        format_version: crate::ytdlp::full_backup::FORMAT_VERSION,
+        app_version: app.package_info().version.to_string(),
+        schema_version: crate::ytdlp::db::SCHEMA_VERSION,
+        created_at: chrono::Utc::now().timestamp(),
+        settings_redacted: redact_settings,
+    };
+
+    let db = app.state::<crate::DbState>().inner().clone();
+    let log_db = app.state::<crate::LogDbState>().inner().clone();
+    let result_manifest = manifest.clone();
+    let work = backup_work_dir(&app)?;
+    let blocking_work = work.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let parts = crate::ytdlp::full_backup::ExportParts {
+            manifest: &manifest,
+            settings: &settings,
+            binaries: &binaries,
+        };
+        crate::ytdlp::full_backup::export(&dest, &blocking_work, parts, &db, &log_db)
+    })
+    .await
+    .map_err(|e| AppError::Custom(e.to_string()));
+    let _ = std::fs::remove_dir_all(&work);
+    result??;
+
+    logger::info_cat(
+        "app",
+        &format!(
+            "Full backup written to {}{}",
+            path,
+            if redact_settings {
+                " (settings redacted)"
+            } else {
+                ""
+            }
+        ),
+    );
+    Ok(result_manifest)
+}
+
+/// Write a zip for bug reports to `path`: app info, a fresh health check, the
+/// redacted settings and the most recent log entries. No databases.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_diagnostics(app: AppHandle, path: String) -> Result<(), AppError> {
+    use crate::ytdlp::diagnostics;
+
+    let dest = std::path::PathBuf::from(crate::ytdlp::security::sanitize_output_path(&path)?);
+    let settings = crate::ytdlp::settings::redacted(&crate::ytdlp::settings::get_settings(&app)?);
+    let app_info = crate::ytdlp::app_info::app_info(&app).await;
+    let health = crate::ytdlp::health::health_check(&app).await;
+    let logs = app
+        .state::<crate::LogDbState>()
+        .query_logs(
+            0,
+            diagnostics::RECENT_LOGS,
+            diagnostics::RECENT_LOGS,
+            None,
+            None,
+            None,
+            None,
+        )
+        .map(|page| diagnostics::mask_logs(page.items))
+        .unwrap_or_default();
+
+    tokio::task::spawn_blocking(move || {
+        let parts = diagnostics::Parts {
+            app_info: &app_info,
+            health: &health,
+            settings: &settings,
+            logs: &logs,
+        };
+        diagnostics::write(&dest, parts)
+    })
+    .await
+    .map_err(|e| AppError::Custom(e.to_string()))??;
+
+    logger::info_cat("app", &format!("Diagnostics bundle written to {}", path));
+    Ok(())
+}
+
+/// Restore an `export_full_backup` archive. Older databases are migrated
+/// first. `Replace` needs an idle queue; `Merge` keeps local rows on conflict.
+/// Settings are restored only when asked and the archive has them unredacted.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_full_backup(
+    app: AppHandle,
+    path: String,
+    mode: BackupImportMode,
+    restore_settings: bool,
+) -> Result<FullBackupImport, AppError> {
+    let src = std::path::PathBuf::from(crate::ytdlp::security::sanitize_output_path(&path)?);
+    let manager = app.state::<Arc<DownloadManager>>();
+    if mode == BackupImportMode::Replace && manager.active_count() > 0 {
+        return Err(AppError::Custom(
+            "Stop running downloads before replacing all data with a backup".to_string(),
+        ));
+    }
+
+    let db = app.state::<crate::DbState>().inner().clone();
+    let log_db = app.state::<crate::LogDbState>().inner().clone();
+    let work = backup_work_dir(&app)?;
+    let blocking_work = work.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let extracted = crate::ytdlp::full_backup::read(&src, &blocking_work)?;
+        let from_version = crate::ytdlp::db::Database::upgrade_file(&extracted.main_db)?;
+        let mut counts = db.restore_from(&extracted.main_db, mode)?;
+        if let Some(logs) = &extracted.log_db {
+            counts.logs = log_db.restore_from(logs, mode == BackupImportMode::Replace)?;
+        }
+        Ok::<_, AppError>((extracted.manifest, extracted.settings, from_version, counts))
+    })
+    .await
+    .map_err(|e| AppError::Custom(e.to_string()));
+    let _ = std::fs::remove_dir_all(&work);
+    let (manifest, settings, from_version, counts) = result??;
+
+    logger::info_cat(
+        "app",
+        &format!(
+            "Imported backup from {} ({:?}, schema v{}): {} history, {} queued, {} logs",
+            manifest.app_version, mode, from_version, counts.history, counts.queue, counts.logs
+        ),
+    );
+
+    let mut settings_restored = false;
+    if restore_settings {
+        match settings {
+            Some(_) if manifest.settings_redacted => logger::warn_cat(
+                "app",
+                "Backup settings are redacted; current settings were kept",
+            ),
+            Some(value) => {
+                let settings = crate::ytdlp::settings::from_json(&value);
+                match super::settings_cmd::update_settings(app.clone(), settings) {
+                    Ok(()) => settings_restored = true,
+                    Err(e) => logger::warn_cat(
+                        "app",
+                        &format!("Backup settings were not restored: {}", e),
+                    ),
+                }
+            }
+            None => {}
+        }
+    }
+
+    crate::ytdlp::download::process_next_pending_public(app.clone());
+    Ok(FullBackupImport {
+        manifest,
+        counts,
+        settings_restored,
+    })
+}
+
+/// Check that the data dir (logs DB, download DB, binaries) can be written to.
+#[tauri::command]
+#[specta::specta]
+pub async fn check_app_data_writable(app: AppHandle) -> Result<WritableCheck, AppError> {
+    let data_dir = crate::modules::data_dir::resolve(&app)?;
+    Ok(crate::modules::data_dir::check_writable(&data_dir))
+}
+
+/// Copy data from a location earlier builds used into the current data dir.
+/// This runs once at startup; afterwards the recorded outcome is returned.
+#[tauri::command]
+#[specta::specta]
+pub async fn migrate_legacy_data(app: AppHandle) -> Result<LegacyMigration, AppError> {
+    let default_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Custom(e.to_string()))?;
+    let candidates = crate::modules::legacy_data::candidates(&app);
+    Ok(crate::modules::legacy_data::run(
+        &default_data_dir,
+        &candidates,
+    ))
+}
+
+/// Report how system sleep is handled and what happened on the last wake.
+#[tauri::command]
+#[specta::specta]
+pub fn get_power_status() -> Result<PowerStatus, AppError> {
+    Ok(crate::ytdlp::power::status())
+}
+
+/// Battery charge and whether new downloads are held for low battery.
+/// `available` is false when the charge can't be read on this system.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_battery_state(app: AppHandle) -> Result<BatteryState, AppError> {
+    Ok(crate::ytdlp::battery::state(&app).await)
+}
+
+/// Latest CPU usage sample and whether new downloads are held for system load.
+#[tauri::command]
+#[specta::specta]
+pub fn get_cpu_load_state(app: AppHandle) -> Result<CpuLoadState, AppError> {
+    Ok(crate::ytdlp::cpu_load::state(&app))
+}
+
+/// List yt-dlp/ffmpeg processes from the app's bin dir that no running app instance owns.
+#[tauri::command]
+#[specta::specta]
+pub fn list_orphaned_processes(app: AppHandle) -> Result<Vec<OrphanProcess>, AppError> {
+    crate::ytdlp::orphans::find_orphans(&app, false)
+}
+
+/// Terminate the processes reported by `list_orphaned_processes`.
+#[tauri::command]
+#[specta::specta]
+pub fn reap_orphaned_processes(app: AppHandle) -> Result<Vec<OrphanProcess>, AppError> {
+    crate::ytdlp::orphans::find_orphans(&app, true)
+}
+
+/// Check a Netscape-format cookies.txt: cookie and domain counts, expired
+/// entries and malformed lines.
+#[tauri::command]
+#[specta::specta]
+pub fn validate_cookies_file(path: String) -> Result<CookiesValidation, AppError> {
+    crate::ytdlp::cookies::validate_file(&path)
+}
@@ -1,12 +1,16 @@
 pub mod dependency;
 pub mod history;
 pub mod misc;
+pub mod presets;
 pub mod queue;
 pub mod settings_cmd;
+pub mod watch;
 
 // Re-export all items (includes __cmd__ and __specta__fn__ generated by proc macros)
 pub use dependency::*;
 pub use history::*;
 pub use misc::*;
+pub use presets::*;
 pub use queue::*;
 pub use settings_cmd::*;
+pub use watch::*;
@@ -0,0 +1,66 @@
+use crate::modules::logger;
+use crate::modules::types::AppError;
+use crate::ytdlp::types::*;
+use crate::ytdlp::{presets, security};
+use tauri::AppHandle;
+use tauri::Manager;
+
+/// Save `options` under `name`, replacing a saved preset of the same name.
+/// Built-in presets can't be overwritten.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_preset(
+    app: AppHandle,
+    name: String,
+    options: PresetOptions,
+) -> Result<DownloadPreset, AppError> {
+    let name = presets::sanitize_name(&name)?;
+    if presets::builtin(&name).is_some() {
+        return Err(AppError::Custom(format!(
+            "\"{}\" is a built-in preset",
+            name
+        )));
+    }
+    if let Some(dir) = &options.output_dir {
+        security::sanitize_output_path(dir)?;
+    }
+    if let Some(size) = &options.max_filesize {
+        security::sanitize_filesize(size)?;
+    }
+    if let Some(lang) = &options.audio_lang {
+        security::sanitize_language(lang)?;
+    }
+
+    let db = app.state::<crate::DbState>();
+    db.save_preset(&name, &options)?;
+    logger::info_cat("settings", &format!("Saved preset {}", name));
+    Ok(DownloadPreset {
+        name,
+        options,
+        builtin: false,
+    })
+}
+
+/// Built-in presets first, then saved ones by name.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_presets(app: AppHandle) -> Result<Vec<DownloadPreset>, AppError> {
+    let db = app.state::<crate::DbState>();
+    let mut all = presets::builtin_presets();
+    all.extend(db.list_presets()?);
+    Ok(all)
+}
+
+/// Delete a saved preset. Returns false when no such preset exists.
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_preset(app: AppHandle, name: String) -> Result<bool, AppError> {
+    if presets::builtin(name.trim()).is_some() {
+        return Err(AppError::Custom(format!(
+            "\"{}\" is a built-in preset",
+            name.trim()
+        )));
+    }
+    let db = app.state::<crate::DbState>();
+    db.delete_preset(name.trim())
+}
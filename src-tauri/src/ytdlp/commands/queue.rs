@@ -1,7 +1,10 @@
 use crate::modules::types::AppError;
-use crate::ytdlp::download::DownloadManager;
+use crate::ytdlp::download::{limits, retry, DownloadManager};
 use crate::ytdlp::types::*;
+use crate::ytdlp::url_import;
 use std::sync::Arc;
+use std::time::Duration;
+use tauri::ipc::Channel;
 use tauri::AppHandle;
 use tauri::Manager;
 
@@ -9,14 +12,76 @@ use tauri::Manager;
 #[specta::specta]
 pub async fn get_download_queue(app: AppHandle) -> Result<Vec<DownloadTaskInfo>, AppError> {
     let db = app.state::<crate::DbState>();
-    db.get_download_queue()
+    let mut tasks = db.get_download_queue()?;
+    let settings = crate::ytdlp::settings::get_settings(&app)?;
+    let favorites = db.favorite_video_ids()?;
+    for task in &mut tasks {
+        task.retry_policy = Some(retry::effective(task, &settings));
+        task.is_favorite = favorites.contains(&task.video_id);
+    }
+    Ok(tasks)
+}
+
+/// Stop starting downloads, wait up to `timeout_secs` (default one hour) for the
+/// running ones, then exit; whatever is still running then is cancelled.
+/// Progress is sent as `queue-drain-event`.
+#[tauri::command]
+#[specta::specta]
+pub async fn drain_and_exit(app: AppHandle, timeout_secs: Option<u32>) -> Result<(), AppError> {
+    crate::ytdlp::download::start_drain(&app, timeout_secs)
+}
+
+/// Override the automatic retry policy of a task that hasn't finished.
+/// None for a value goes back to the setting.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_task_retry_policy(
+    app: AppHandle,
+    task_id: u64,
+    max_retries: Option<u32>,
+    retry_backoff_secs: Option<u32>,
+) -> Result<(), AppError> {
+    crate::ytdlp::download::validate_retry_policy(max_retries, retry_backoff_secs)?;
+    let db = app.state::<crate::DbState>();
+    if !db.set_retry_policy(task_id, max_retries, retry_backoff_secs)? {
+        return Err(AppError::Custom(
+            "Task not found or already finished".to_string(),
+        ));
+    }
+    Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
 pub async fn clear_completed(app: AppHandle) -> Result<u32, AppError> {
     let db = app.state::<crate::DbState>();
-    db.clear_completed()
+    let credential_ids = db.get_finished_credential_ids()?;
+    let cleared = db.clear_completed()?;
+    for id in credential_ids {
+        crate::ytdlp::credentials::delete(id);
+    }
+    Ok(cleared)
+}
+
+/// Close the gaps left in a playlist's `%(playlist_index)s` numbering after
+/// cherry-picking: queued tasks get consecutive positions, downloaded ones keep theirs.
+#[tauri::command]
+#[specta::specta]
+pub async fn renumber_playlist_tasks(
+    app: AppHandle,
+    playlist_id: String,
+) -> Result<Vec<PlaylistIndexChange>, AppError> {
+    let db = app.state::<crate::DbState>();
+    let changes = db.renumber_playlist(&playlist_id)?;
+    crate::modules::logger::info_cat(
+        "download",
+        &format!(
+            "Renumbered {} task(s) of playlist {}",
+            changes.len(),
+            playlist_id
+        ),
+    );
+    Ok(changes)
 }
 
 #[tauri::command]
@@ -31,11 +96,15 @@ pub async fn retry_download(app: AppHandle, task_id: u64) -> Result<(), AppError
     // Reset the original task to pending (reuse existing DB row instead of
     // creating a duplicate via add_to_queue, which would leave a zombie pending row)
     db.update_download_status(task_id, &DownloadStatus::Pending, None)?;
+    // A manual retry gets a fresh set of automatic ones
+    db.reset_retry_count(task_id)?;
 
     // Try to acquire a slot and start the download immediately if possible
     let manager = app.state::<Arc<DownloadManager>>();
+    manager.publish(QueueEvent::task(QueueEventKind::Added, task_id));
     if manager.try_acquire() {
         db.update_download_status(task_id, &DownloadStatus::Downloading, None)?;
+        limits::note_start(&app, task_id);
         let app_clone = app.clone();
         let app_panic_guard = app.clone();
         tokio::spawn(async move {
@@ -81,3 +150,287 @@ pub async fn get_queue_summary(app: AppHandle) -> Result<QueueSummary, AppError>
     let db = app.state::<crate::DbState>();
     db.get_queue_summary(5)
 }
+
+/// Counts and the first few tasks for each status in one round trip.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_queue_grouped(
+    app: AppHandle,
+    sample_size: Option<u32>,
+) -> Result<QueueGrouped, AppError> {
+    let db = app.state::<crate::DbState>();
+    db.get_queue_grouped(sample_size.unwrap_or(5).clamp(1, 50))
+}
+
+/// Stream coarse queue state changes (add/start/milestone/complete/fail/cancel).
+/// Sends a full snapshot first, and again if the subscriber falls behind.
+#[tauri::command]
+#[specta::specta]
+pub async fn watch_queue(app: AppHandle, on_event: Channel<QueueEvent>) -> Result<(), AppError> {
+    // Subscribe before the snapshot so no transition between the two is missed
+    let mut rx = app.state::<Arc<DownloadManager>>().subscribe_queue();
+    let db = app.state::<crate::DbState>();
+    on_event
+        .send(QueueEvent::snapshot(db.get_download_queue()?))
+        .map_err(|e| AppError::Custom(e.to_string()))?;
+
+    tokio::spawn(async move {
+        use tokio::sync::broadcast::error::RecvError;
+        loop {
+            let event = match rx.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(_)) => {
+                    let db = app.state::<crate::DbState>();
+                    match db.get_download_queue() {
+                        Ok(tasks) => QueueEvent::snapshot(tasks),
+                        Err(_) => continue,
+                    }
+                }
+                Err(RecvError::Closed) => break,
+            };
+            // Webview gone or channel dropped: stop forwarding
+            if on_event.send(event).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Combined download speed of all running tasks, for a "total down" display.
+#[tauri::command]
+#[specta::specta]
+pub fn get_total_speed(app: AppHandle) -> Result<TotalSpeed, AppError> {
+    Ok(app.state::<Arc<DownloadManager>>().total_speed())
+}
+
+/// Latest progress of every running task in one call, for live tables that poll
+/// instead of following `download-event`.
+#[tauri::command]
+#[specta::specta]
+pub fn get_active_progress(app: AppHandle) -> Result<Vec<TaskProgress>, AppError> {
+    Ok(app.state::<Arc<DownloadManager>>().active_progress())
+}
+
+/// Running tasks whose download hasn't moved for `threshold_secs` or more, so
+/// they can be flagged before the download timeout kills them.
+#[tauri::command]
+#[specta::specta]
+pub fn get_stalled_tasks(app: AppHandle, threshold_secs: u64) -> Result<Vec<TaskStall>, AppError> {
+    Ok(app
+        .state::<Arc<DownloadManager>>()
+        .stalled_tasks(Duration::from_secs(threshold_secs)))
+}
+
+/// Probe the expected size of `format_id` for `url` and compare it with the size cap
+/// (`max_filesize` if given, else the setting) before queueing.
+#[tauri::command]
+#[specta::specta]
+pub async fn estimate_download_size(
+    app: AppHandle,
+    url: String,
+    format_id: String,
+    max_filesize: Option<String>,
+) -> Result<SizeEstimate, AppError> {
+    let url = crate::ytdlp::security::sanitize_url(&url)?;
+    let cap = match max_filesize {
+        Some(size) => Some(crate::ytdlp::security::sanitize_filesize(&size)?),
+        None => crate::ytdlp::settings::get_settings(&app)?.max_filesize,
+    };
+    let cap_bytes = cap
+        .as_deref()
+        .and_then(crate::ytdlp::security::parse_filesize)
+        .filter(|b| *b > 0);
+    let bytes = crate::ytdlp::probe::selected_filesize(&app, &url, &format_id).await;
+    Ok(SizeEstimate {
+        bytes,
+        cap_bytes,
+        exceeds_cap: matches!((bytes, cap_bytes), (Some(b), Some(c)) if b > c),
+    })
+}
+
+/// Queue every video URL in a text, CSV or JSON file (`format` is guessed from the
+/// extension when omitted). URLs already queued, already downloaded with the file
+/// still present, or repeated within the file are skipped. Downloads use
+/// `format_id`/`quality_label` when given, else the default quality setting.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_urls_from_file(
+    app: AppHandle,
+    path: String,
+    format: Option<UrlFileFormat>,
+    format_id: Option<String>,
+    quality_label: Option<String>,
+) -> Result<UrlImportResult, AppError> {
+    const MAX_IMPORT_BYTES: u64 = 10 * 1024 * 1024;
+
+    let size = tokio::fs::metadata(&path)
+        .await
+        .map_err(|e| AppError::FileError(format!("Cannot read {}: {}", path, e)))?
+        .len();
+    if size > MAX_IMPORT_BYTES {
+        return Err(AppError::FileError(format!(
+            "Import file is too large ({} bytes, max {})",
+            size, MAX_IMPORT_BYTES
+        )));
+    }
+    let bytes = tokio::fs::read(&path)
+        .await
+        .map_err(|e| AppError::FileError(format!("Cannot read {}: {}", path, e)))?;
+    let text = String::from_utf8_lossy(&bytes);
+    let format = format.unwrap_or_else(|| url_import::detect_format(&path));
+    let found = url_import::parse_urls(&text, format)?;
+
+    let settings = crate::ytdlp::settings::get_settings(&app)?;
+    let quality_label = quality_label.unwrap_or_else(|| settings.default_quality.clone());
+    let format_id =
+        format_id.unwrap_or_else(|| url_import::format_for_quality(&settings.default_quality));
+
+    let db = app.state::<crate::DbState>();
+    let mut seen = std::collections::HashSet::new();
+    let mut lines = Vec::with_capacity(found.len());
+    for item in found {
+        let validation =
+            crate::ytdlp::metadata::validate_url(app.clone(), item.url.clone(), Some(true))?;
+        let mut line = UrlImportLine {
+            line: item.line,
+            url: item.url,
+            status: UrlImportStatus::Invalid,
+            task_id: None,
+            message: None,
+        };
+        let normalized = match (
+            validation.valid,
+            validation.url_type,
+            validation.normalized_url,
+        ) {
+            (true, UrlType::Video, Some(normalized)) => normalized,
+            (true, _, _) => {
+                line.status = UrlImportStatus::Unsupported;
+                lines.push(line);
+                continue;
+            }
+            _ => {
+                lines.push(line);
+                continue;
+            }
+        };
+        let video_id = validation.video_id.unwrap_or_default();
+
+        if !seen.insert(normalized.clone()) {
+            line.status = UrlImportStatus::DuplicateInFile;
+        } else if !video_id.is_empty() && db.check_duplicate_in_queue(&video_id)? {
+            line.status = UrlImportStatus::AlreadyQueued;
+        } else if !video_id.is_empty()
+            && db
+                .check_duplicate(&video_id)?
+                .is_some_and(|h| std::path::Path::new(&h.file_path).exists())
+        {
+            line.status = UrlImportStatus::AlreadyDownloaded;
+        } else if !video_id.is_empty() && db.is_video_blocked(&video_id)? {
+            line.status = UrlImportStatus::Blocked;
+        } else {
+            let request = DownloadRequest {
+                video_url: normalized.clone(),
+                video_id,
+                title: normalized,
+                format_id: format_id.clone(),
+                quality_label: quality_label.clone(),
+                output_dir: None,
+                cookie_browser: None,
+                playlist_id: None,
+                playlist_index: None,
+                credentials: None,
+                keep_separate_streams: false,
+                max_filesize: None,
+                audio_lang: None,
+                embed_metadata: false,
+                embed_thumbnail: false,
+                preset: None,
+                live_from_start: false,
+                subtitles_only: false,
+                subtitle_langs: Vec::new(),
+                convert_subs: None,
+                tags: Vec::new(),
+                headers: None,
+                max_retries: None,
+                retry_backoff_secs: None,
+                on_existing_file: None,
+            };
+            match crate::ytdlp::download::commands::enqueue(&app, request, None).await {
+                Ok(task_id) => {
+                    line.status = UrlImportStatus::Queued;
+                    line.task_id = Some(task_id);
+                }
+                Err(e) => {
+                    line.status = UrlImportStatus::Failed;
+                    line.message = Some(e.to_string());
+                }
+            }
+        }
+        lines.push(line);
+    }
+
+    let count = |status: &[UrlImportStatus]| {
+        lines.iter().filter(|l| status.contains(&l.status)).count() as u32
+    };
+    let result = UrlImportResult {
+        queued: count(&[UrlImportStatus::Queued]),
+        skipped: count(&[
+            UrlImportStatus::AlreadyDownloaded,
+            UrlImportStatus::AlreadyQueued,
+            UrlImportStatus::DuplicateInFile,
+            UrlImportStatus::Blocked,
+        ]),
+        failed: count(&[
+            UrlImportStatus::Invalid,
+            UrlImportStatus::Unsupported,
+            UrlImportStatus::Failed,
+        ]),
+        lines,
+    };
+    crate::modules::logger::info_cat(
+        "download",
+        &format!(
+            "Imported URLs from {}: {} queued, {} skipped, {} failed",
+            path, result.queued, result.skipped, result.failed
+        ),
+    );
+    Ok(result)
+}
+
+/// Tasks started this session and today against their configured limits.
+#[tauri::command]
+#[specta::specta]
+pub fn get_download_limits(app: AppHandle) -> Result<DownloadLimitUsage, AppError> {
+    Ok(limits::usage(&app))
+}
+
+/// Bytes downloaded today, this month or in total, and this month's share of
+/// `monthly_data_cap`.
+#[tauri::command]
+#[specta::specta]
+pub fn get_bandwidth_usage(
+    app: AppHandle,
+    period: BandwidthPeriod,
+) -> Result<BandwidthUsage, AppError> {
+    crate::ytdlp::bandwidth::usage(&app, period)
+}
+
+/// Give `task_id` the full bandwidth: it runs uncapped while every other task
+/// is held to `throttleRateLimit`. Running tasks whose cap changes restart and
+/// resume. The focus is dropped when the task finishes.
+#[tauri::command]
+#[specta::specta]
+pub fn set_focused_task(app: AppHandle, task_id: u64) -> Result<(), AppError> {
+    crate::ytdlp::download::set_focused(&app, task_id)
+}
+
+/// Lift the focus set by `set_focused_task`; the other tasks go back to their normal rate.
+#[tauri::command]
+#[specta::specta]
+pub fn clear_focused_task(app: AppHandle) -> Result<(), AppError> {
+    crate::ytdlp::download::clear_focused(&app);
+    Ok(())
+}
@@ -2,6 +2,7 @@ use crate::modules::logger;
 use crate::modules::types::AppError;
 use crate::ytdlp::binary;
 use crate::ytdlp::download::DownloadManager;
+use crate::ytdlp::host_policy;
 use crate::ytdlp::security;
 use crate::ytdlp::types::*;
 use std::sync::Arc;
@@ -15,35 +16,186 @@ pub fn get_settings(app: AppHandle) -> Result<AppSettings, AppError> {
     crate::ytdlp::settings::get_settings(&app)
 }
 
+/// Current settings with secrets masked, for sharing in bug reports.
+#[tauri::command]
+#[specta::specta]
+pub fn export_settings_redacted(app: AppHandle) -> Result<AppSettings, AppError> {
+    Ok(crate::ytdlp::settings::redacted(
+        &crate::ytdlp::settings::get_settings(&app)?,
+    ))
+}
+
+/// Every problem with `settings`, keyed by settings field, without saving anything.
+#[tauri::command]
+#[specta::specta]
+pub fn validate_settings(settings: AppSettings) -> Result<Vec<SettingIssue>, AppError> {
+    Ok(crate::ytdlp::settings_check::validate(&settings))
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn update_settings(app: AppHandle, settings: AppSettings) -> Result<(), AppError> {
-    // Validate settings before saving
-    if !settings.download_path.is_empty() {
-        security::sanitize_output_path(&settings.download_path)?;
+    // Validate settings before saving; nothing is applied unless every field passes
+    let (warnings, errors): (Vec<_>, Vec<_>) = crate::ytdlp::settings_check::validate(&settings)
+        .into_iter()
+        .partition(|i| i.warning);
+    if !errors.is_empty() {
+        return Err(AppError::Custom(
+            errors
+                .iter()
+                .map(|i| format!("{}: {}", i.field, i.message))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ));
     }
-    security::sanitize_filename_template(&settings.filename_template)?;
-    if let Some(ref browser) = settings.cookie_browser {
-        security::sanitize_cookie_browser(browser)?;
+    for warning in &warnings {
+        logger::warn_cat(
+            "app",
+            &format!(
+                "Settings saved with a warning: {}: {}",
+                warning.field, warning.message
+            ),
+        );
+    }
+    if let Some(ref dir) = settings.data_dir_override {
+        if !dir.is_empty() {
+            let check = crate::modules::data_dir::check_writable(std::path::Path::new(dir));
+            if !check.writable {
+                return Err(AppError::FileError(format!(
+                    "Data directory is not writable: {}",
+                    check.error.unwrap_or_default()
+                )));
+            }
+        }
     }
 
     // Clamp max_concurrent to safe range
     let mut settings = settings;
     settings.max_concurrent = security::clamp_max_concurrent(settings.max_concurrent);
+    settings.throttle_max_concurrent =
+        security::clamp_max_concurrent(settings.throttle_max_concurrent);
+    // Cap the start delay at one hour
+    settings.start_delay_secs = settings.start_delay_secs.min(3600);
+    settings.max_retries = settings
+        .max_retries
+        .min(crate::ytdlp::download::retry::MAX_RETRIES_LIMIT);
+    settings.retry_backoff_secs = settings
+        .retry_backoff_secs
+        .clamp(1, crate::ytdlp::download::retry::MAX_BACKOFF_SECS);
+    settings.log_max_page_size = settings
+        .log_max_page_size
+        .clamp(1, crate::modules::log_db::PAGE_SIZE_CEILING);
+    // 0 means no limit
+    settings.session_download_limit = settings.session_download_limit.filter(|&n| n > 0);
+    settings.daily_download_limit = settings.daily_download_limit.filter(|&n| n > 0);
+    settings.binary_backup_count = settings.binary_backup_count.min(binary::MAX_BACKUPS);
+    settings.cleanup_idle_secs = settings
+        .cleanup_idle_secs
+        .min(crate::ytdlp::maintenance::MAX_IDLE_SECS);
+    settings.queue_watchdog_secs =
+        crate::ytdlp::download::watchdog::clamp_interval(settings.queue_watchdog_secs);
+    settings.default_headers = crate::ytdlp::http_headers::sanitize(&settings.default_headers)?;
+    settings.host_allowlist = host_policy::sanitize_list("allowlist", &settings.host_allowlist)?;
+    settings.host_denylist = host_policy::sanitize_list("denylist", &settings.host_denylist)?;
+    settings.codec_preference = crate::ytdlp::codec_pref::sanitize(&settings.codec_preference)?;
+    settings.pause_below_battery_percent = settings
+        .pause_below_battery_percent
+        .filter(|p| *p > 0)
+        .map(|p| p.min(100));
+    settings.pause_above_cpu_percent = settings
+        .pause_above_cpu_percent
+        .filter(|p| *p > 0)
+        .map(|p| p.min(100));
+    settings.cpu_sample_secs = settings
+        .cpu_sample_secs
+        .clamp(1, crate::ytdlp::cpu_load::MAX_SAMPLE_SECS);
+    settings.cpu_sustain_secs = settings
+        .cpu_sustain_secs
+        .min(crate::ytdlp::cpu_load::MAX_SUSTAIN_SECS);
 
-    // Check if dep_mode changed to invalidate cache
-    let old_dep_mode = crate::ytdlp::settings::get_settings(&app)
-        .map(|s| s.dep_mode)
-        .unwrap_or_default();
+    // Previous values, to apply what changed (binary cache, recode warning, data dir, ...)
+    let old = crate::ytdlp::settings::get_settings(&app).unwrap_or_default();
+
+    let non_empty = |d: &Option<String>| d.clone().filter(|d| !d.is_empty());
+    let data_dir_changed =
+        non_empty(&old.data_dir_override) != non_empty(&settings.data_dir_override);
+    if data_dir_changed {
+        crate::modules::data_dir::check_switch_target(&app, settings.data_dir_override.as_deref())?;
+    }
 
     crate::ytdlp::settings::update_settings(&app, &settings)?;
 
+    if settings.binary_backup_count < old.binary_backup_count {
+        let keep = settings.binary_backup_count;
+        tauri::async_runtime::spawn(async move {
+            match binary::prune_ytdlp_backups(keep).await {
+                Ok(n) if n > 0 => logger::info_cat(
+                    "dependency",
+                    &format!("Removed {} yt-dlp backup(s) beyond {}", n, keep),
+                ),
+                Ok(_) => {}
+                Err(e) => logger::warn_cat(
+                    "dependency",
+                    &format!("Failed to prune yt-dlp backups: {}", e),
+                ),
+            }
+        });
+    }
+
+    if settings.pause_below_battery_percent != old.pause_below_battery_percent {
+        // Apply a new threshold now instead of on the watcher's next tick
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move { crate::ytdlp::battery::check(&app).await });
+    }
+
+    if settings.pause_above_cpu_percent != old.pause_above_cpu_percent {
+        // Samples taken against the old threshold don't count toward the new one
+        crate::ytdlp::cpu_load::reset(&app);
+    }
+
+    if data_dir_changed {
+        // Applied on next start, which migrates data from the dir in use now
+        crate::modules::data_dir::record_pending_switch(&app)?;
+        logger::info_cat(
+            "settings",
+            "Data directory changed; data will be moved on next restart",
+        );
+    }
+
+    if old.recode_video.is_none() {
+        if let Some(ref format) = settings.recode_video {
+            logger::warn_cat(
+                "settings",
+                &format!(
+                    "Re-encoding to {} enabled: requires ffmpeg, is slow, and loses quality",
+                    format
+                ),
+            );
+        }
+    }
+
     // Sync max_concurrent to DownloadManager at runtime
     let manager = app.state::<Arc<DownloadManager>>();
     manager.set_max_concurrent(settings.max_concurrent);
+    manager.set_start_delay(settings.start_delay_secs);
+    let limited_before = manager.limit_reached().is_some();
+    crate::ytdlp::download::limits::apply_settings(&app, &settings);
+    if limited_before && manager.limit_reached().is_none() {
+        // A raised or removed limit frees the tasks it held back
+        crate::ytdlp::download::process_next_pending_public(app.clone());
+    }
+    crate::modules::log_burst::set_policy(settings.log_burst_policy);
+    if manager.is_throttled() {
+        // Re-apply throttle with the new values (or lift it if the option was turned off)
+        crate::ytdlp::tray::apply_background_throttle(&app, true);
+    }
 
-    // Invalidate dep cache when dep_mode changes
-    if old_dep_mode != settings.dep_mode {
+    // Invalidate dep cache when binary resolution changes
+    if old.dep_mode != settings.dep_mode
+        || old.prefer_system_binary != settings.prefer_system_binary
+        || old.ytdlp_binaries != settings.ytdlp_binaries
+        || old.active_ytdlp != settings.active_ytdlp
+    {
         binary::invalidate_dep_cache();
     }
 
@@ -52,15 +204,30 @@ pub fn update_settings(app: AppHandle, settings: AppSettings) -> Result<(), AppE
     Ok(())
 }
 
+/// Test `url` against the saved host allow/deny lists without queueing anything.
+#[tauri::command]
+#[specta::specta]
+pub fn check_host_policy(app: AppHandle, url: String) -> Result<(), AppError> {
+    let settings = crate::ytdlp::settings::get_settings(&app)?;
+    host_policy::check(&url, &settings.host_allowlist, &settings.host_denylist)?;
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn select_download_directory(app: AppHandle) -> Result<Option<String>, AppError> {
+    // Open the dialog at the most recently used directory
+    let initial_dir = crate::ytdlp::settings::get_recent_directories(&app)
+        .into_iter()
+        .next();
+
     // Use spawn_blocking to avoid blocking the async runtime
     let result = tokio::task::spawn_blocking(move || {
-        app.dialog()
-            .file()
-            .set_title("다운로드 폴더 선택")
-            .blocking_pick_folder()
+        let mut dialog = app.dialog().file().set_title("다운로드 폴더 선택");
+        if let Some(dir) = initial_dir {
+            dialog = dialog.set_directory(dir);
+        }
+        dialog.blocking_pick_folder()
     })
     .await
     .map_err(|e| AppError::Custom(format!("Dialog task failed: {}", e)))?;
@@ -68,6 +235,29 @@ pub async fn select_download_directory(app: AppHandle) -> Result<Option<String>,
     Ok(result.map(|p| p.to_string()))
 }
 
+/// Show which options a download of `url` would use and where each value came from.
+#[tauri::command]
+#[specta::specta]
+pub fn resolve_effective_options(
+    app: AppHandle,
+    url: String,
+    task_overrides: Option<TaskOverrides>,
+) -> Result<EffectiveOptions, AppError> {
+    let url = security::sanitize_url(&url)?;
+    let settings = crate::ytdlp::settings::get_settings(&app)?;
+    let overrides = task_overrides.unwrap_or_default();
+    Ok(EffectiveOptions {
+        url,
+        options: crate::ytdlp::options::resolve_options(&settings, &overrides),
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_recent_directories(app: AppHandle) -> Result<Vec<String>, AppError> {
+    Ok(crate::ytdlp::settings::get_recent_directories(&app))
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn get_available_browsers() -> Vec<String> {
@@ -144,3 +334,30 @@ pub fn get_available_browsers() -> Vec<String> {
 
     browsers
 }
+
+/// Profile names of `browser` for a `browser:profile` cookie source, e.g.
+/// ["Default", "Profile 2"]. Empty when the browser has none on this machine.
+#[tauri::command]
+#[specta::specta]
+pub fn list_browser_profiles(browser: String) -> Result<Vec<String>, AppError> {
+    crate::ytdlp::browser_profiles::list(&browser)
+}
+
+/// Check an output template for likely trouble (missing extension, fields that
+/// are often empty, Windows path length, separators) without downloading.
+/// `preview_output_filename` reports the same warnings next to a real name.
+#[tauri::command]
+#[specta::specta]
+pub async fn lint_output_template(template: String) -> Vec<TemplateWarning> {
+    crate::ytdlp::template_lint::lint(&template)
+}
+
+/// The system's default browser under a `get_available_browsers` name, or None.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_default_browser() -> Option<String> {
+    tauri::async_runtime::spawn_blocking(crate::ytdlp::default_browser::detect)
+        .await
+        .ok()
+        .flatten()
+}
@@ -0,0 +1,68 @@
+use crate::modules::logger;
+use crate::modules::types::AppError;
+use crate::ytdlp::types::*;
+use crate::ytdlp::watch;
+use tauri::AppHandle;
+use tauri::Manager;
+
+/// Watch a channel or playlist and queue its new uploads every `interval_secs`
+/// (default 1 hour, clamped to 5 minutes – 7 days). Watching an already watched
+/// URL updates its interval. The first check runs immediately and only records
+/// the entries that already exist.
+#[tauri::command]
+#[specta::specta]
+pub async fn watch_source(
+    app: AppHandle,
+    url: String,
+    interval_secs: Option<u64>,
+) -> Result<WatchedSource, AppError> {
+    let validation = crate::ytdlp::metadata::validate_url(app.clone(), url, Some(false))?;
+    // A single YouTube video has nothing to poll; other sites are left to yt-dlp
+    if validation.video_id.is_some() {
+        return Err(AppError::InvalidUrl(
+            "Only channels and playlists can be watched".to_string(),
+        ));
+    }
+    let url = match validation.normalized_url {
+        Some(normalized) if validation.valid => normalized,
+        _ => return Err(AppError::InvalidUrl("Invalid URL".to_string())),
+    };
+    let interval_secs = interval_secs
+        .unwrap_or(60 * 60)
+        .clamp(watch::MIN_INTERVAL_SECS, watch::MAX_INTERVAL_SECS);
+
+    let source = {
+        let db = app.state::<crate::DbState>();
+        db.upsert_watched_source(&url, interval_secs)?
+    };
+    logger::info_cat(
+        "download",
+        &format!("Watching {} every {}s", source.url, source.interval_secs),
+    );
+    if source.last_checked_at.is_none() {
+        watch::check_now(&app, &source).await?;
+    }
+
+    let db = app.state::<crate::DbState>();
+    db.get_watched_source(source.id)?
+        .ok_or_else(|| AppError::Custom("Watched source disappeared".to_string()))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_watched_sources(app: AppHandle) -> Result<Vec<WatchedSource>, AppError> {
+    let db = app.state::<crate::DbState>();
+    db.list_watched_sources()
+}
+
+/// Stop watching a source. Downloads it already queued are left alone.
+#[tauri::command]
+#[specta::specta]
+pub async fn unwatch_source(app: AppHandle, id: u64) -> Result<(), AppError> {
+    let db = app.state::<crate::DbState>();
+    if !db.delete_watched_source(id)? {
+        return Err(AppError::Custom(format!("Watched source {} not found", id)));
+    }
+    logger::info_cat("download", &format!("Stopped watching source {}", id));
+    Ok(())
+}
@@ -0,0 +1,271 @@
+use super::binary;
+use super::types::ConfigConflict;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+/// Options the app sets itself. A yt-dlp config file setting any of these can change
+/// what gets downloaded, where it goes, or break progress parsing.
+/// (long name, short alias, takes a value, reason)
+const MANAGED_OPTIONS: &[(&str, Option<&str>, bool, &str)] = &[
+    (
+        "--output",
+        Some("-o"),
+        true,
+        "Overrides the app's filename template",
+    ),
+    ("--paths", Some("-P"), true, "Changes where files are saved"),
+    (
+        "--format",
+        Some("-f"),
+        true,
+        "Overrides the selected quality",
+    ),
+    (
+        "--format-sort",
+        Some("-S"),
+        true,
+        "Changes which format is picked for a quality",
+    ),
+    (
+        "--yes-playlist",
+        None,
+        false,
+        "Downloads whole playlists instead of one video",
+    ),
+    (
+        "--quiet",
+        Some("-q"),
+        false,
+        "Suppresses output the app needs to track progress",
+    ),
+    ("--no-progress", None, false, "Suppresses progress output"),
+    (
+        "--progress-template",
+        None,
+        true,
+        "Breaks the app's progress parsing",
+    ),
+    (
+        "--no-newline",
+        None,
+        false,
+        "Breaks the app's progress parsing",
+    ),
+    (
+        "--extract-audio",
+        Some("-x"),
+        false,
+        "Converts every download to audio",
+    ),
+    (
+        "--merge-output-format",
+        None,
+        true,
+        "Changes the output container",
+    ),
+    ("--remux-video", None, true, "Changes the output container"),
+    ("--recode-video", None, true, "Re-encodes every download"),
+    ("--limit-rate", Some("-r"), true, "Limits download speed"),
+    (
+        "--cookies-from-browser",
+        None,
+        true,
+        "Overrides the app's cookie browser",
+    ),
+    (
+        "--ffmpeg-location",
+        None,
+        true,
+        "Overrides the ffmpeg the app resolved",
+    ),
+    (
+        "--force-overwrites",
+        None,
+        false,
+        "Overwrites existing files",
+    ),
+    ("--exec", None, true, "Runs a command after each download"),
+];
+
+/// Split a config line into arguments, honoring single/double quotes and `#` comments.
+fn split_args(line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut in_token = false;
+
+    for c in line.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None => match c {
+                '"' | '\'' => {
+                    quote = Some(c);
+                    in_token = true;
+                }
+                '#' if !in_token => break,
+                c if c.is_whitespace() => {
+                    if in_token {
+                        args.push(std::mem::take(&mut current));
+                        in_token = false;
+                    }
+                }
+                c => {
+                    current.push(c);
+                    in_token = true;
+                }
+            },
+        }
+    }
+    if in_token {
+        args.push(current);
+    }
+    args
+}
+
+/// Find managed options set in a yt-dlp config file's content.
+pub fn find_conflicts(content: &str, file: &str) -> Vec<ConfigConflict> {
+    let mut conflicts = Vec::new();
+
+    for (line_no, line) in content.lines().enumerate() {
+        let args = split_args(line);
+        let mut i = 0;
+        while i < args.len() {
+            let arg = &args[i];
+            // Support both "--opt value" and "--opt=value"
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((n, v)) if n.starts_with("--") => (n, Some(v.to_string())),
+                _ => (arg.as_str(), None),
+            };
+
+            if let Some((long, _, takes_value, reason)) = MANAGED_OPTIONS
+                .iter()
+                .find(|(long, short, _, _)| *long == name || *short == Some(name))
+            {
+                let value = if *takes_value {
+                    inline_value.or_else(|| {
+                        i += 1;
+                        args.get(i).cloned()
+                    })
+                } else {
+                    None
+                };
+                conflicts.push(ConfigConflict {
+                    file: file.to_string(),
+                    line: (line_no + 1) as u32,
+                    option: long.to_string(),
+                    value,
+                    reason: reason.to_string(),
+                });
+            }
+            i += 1;
+        }
+    }
+
+    conflicts
+}
+
+/// Config file locations yt-dlp reads, in its documented lookup order.
+/// `binary_dir` is the directory of the resolved yt-dlp binary (portable config).
+fn config_candidates(binary_dir: Option<&Path>) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+
+    if let Some(dir) = binary_dir {
+        paths.push(dir.join("yt-dlp.conf"));
+    }
+
+    let xdg = std::env::var("XDG_CONFIG_HOME")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from);
+    let home = std::env::var(if cfg!(target_os = "windows") {
+        "USERPROFILE"
+    } else {
+        "HOME"
+    })
+    .ok()
+    .map(PathBuf::from);
+
+    let config_home = xdg.or_else(|| home.as_ref().map(|h| h.join(".config")));
+    if let Some(config_home) = config_home {
+        paths.push(config_home.join("yt-dlp").join("config"));
+        paths.push(config_home.join("yt-dlp").join("config.txt"));
+        paths.push(config_home.join("yt-dlp.conf"));
+    }
+
+    if cfg!(target_os = "windows") {
+        if let Ok(appdata) = std::env::var("APPDATA") {
+            let appdata = PathBuf::from(appdata);
+            paths.push(appdata.join("yt-dlp").join("config"));
+            paths.push(appdata.join("yt-dlp").join("config.txt"));
+            paths.push(appdata.join("yt-dlp.conf"));
+        }
+    }
+
+    if let Some(home) = home {
+        paths.push(home.join("yt-dlp.conf"));
+        paths.push(home.join("yt-dlp.conf.txt"));
+        paths.push(home.join(".yt-dlp").join("config"));
+        paths.push(home.join(".yt-dlp").join("config.txt"));
+    }
+
+    if !cfg!(target_os = "windows") {
+        paths.push(PathBuf::from("/etc/yt-dlp.conf"));
+        paths.push(PathBuf::from("/etc/yt-dlp/config"));
+        paths.push(PathBuf::from("/etc/yt-dlp/config.txt"));
+    }
+
+    paths
+}
+
+/// Read every yt-dlp config file that exists and collect conflicting options.
+pub async fn scan_conflicts(app: &AppHandle) -> Vec<ConfigConflict> {
+    // Portable config lives next to the binary yt-dlp actually runs
+    let binary_dir = binary::resolve_ytdlp_path_with_app(app)
+        .await
+        .ok()
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .and_then(|p| p.parent().map(|d| d.to_path_buf()));
+
+    let mut conflicts = Vec::new();
+    for path in config_candidates(binary_dir.as_deref()) {
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        conflicts.extend(find_conflicts(&content, &path.to_string_lossy()));
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_args_handles_quotes_and_comments() {
+        assert_eq!(
+            split_args(r#"-o "~/My Videos/%(title)s.%(ext)s" # comment"#),
+            vec!["-o", "~/My Videos/%(title)s.%(ext)s"]
+        );
+        assert!(split_args("# only a comment").is_empty());
+        assert_eq!(split_args("--exec 'echo #1'"), vec!["--exec", "echo #1"]);
+    }
+
+    #[test]
+    fn finds_managed_options() {
+        let content = "# my config\n--no-mtime\n-f bestvideo\n--output=%(id)s.%(ext)s\n-q\n";
+        let conflicts = find_conflicts(content, "config");
+        let options: Vec<_> = conflicts.iter().map(|c| c.option.as_str()).collect();
+        assert_eq!(options, vec!["--format", "--output", "--quiet"]);
+        assert_eq!(conflicts[0].value.as_deref(), Some("bestvideo"));
+        assert_eq!(conflicts[0].line, 3);
+        assert_eq!(conflicts[1].value.as_deref(), Some("%(id)s.%(ext)s"));
+        assert_eq!(conflicts[2].value, None);
+    }
+
+    #[test]
+    fn ignores_unmanaged_options() {
+        assert!(find_conflicts("--no-mtime\n--embed-thumbnail\n", "config").is_empty());
+    }
+}
@@ -0,0 +1,78 @@
+//! Remux-vs-recode decision for `autoContainer`. Remuxing copies the streams into a new
+//! container (fast, lossless) but only works when the container can hold the codecs;
+//! otherwise ffmpeg has to re-encode.
+
+/// Codec family from a yt-dlp codec string (`avc1.64001F` → `avc1`, `vp09.00.40.08` → `vp9`).
+/// `none` and empty strings (no such stream) yield None.
+fn codec_family(codec: &str) -> Option<String> {
+    let family = codec.trim().split('.').next()?.to_ascii_lowercase();
+    match family.as_str() {
+        "" | "none" => None,
+        "vp09" => Some("vp9".to_string()),
+        "vp08" => Some("vp8".to_string()),
+        "h264" | "avc3" => Some("avc1".to_string()),
+        "h265" | "hevc" | "hev1" => Some("hvc1".to_string()),
+        "mp4a" | "aac" => Some("aac".to_string()),
+        "ec-3" | "eac3" => Some("ec-3".to_string()),
+        "ac3" => Some("ac-3".to_string()),
+        _ => Some(family),
+    }
+}
+
+/// Codecs each remux target can hold without re-encoding. Targets not listed here
+/// (avi, gif, audio formats) are always recoded.
+fn container_codecs(container: &str) -> Option<&'static [&'static str]> {
+    Some(match container {
+        "mp4" => &[
+            "avc1", "hvc1", "av01", "vp9", "aac", "mp3", "opus", "flac", "ac-3", "ec-3",
+        ],
+        "mov" => &["avc1", "hvc1", "aac", "mp3", "ac-3", "alac"],
+        "webm" => &["vp8", "vp9", "av01", "opus", "vorbis"],
+        "flv" => &["avc1", "aac", "mp3"],
+        _ => return None,
+    })
+}
+
+/// Whether streams with `vcodec`/`acodec` can be remuxed into `container`.
+/// Unknown codecs count as incompatible so the fallback is a (working) re-encode.
+pub fn can_remux(container: &str, vcodec: Option<&str>, acodec: Option<&str>) -> bool {
+    let container = container.to_ascii_lowercase();
+    let families: Vec<String> = [vcodec, acodec]
+        .into_iter()
+        .flatten()
+        .filter_map(codec_family)
+        .collect();
+    if families.is_empty() {
+        return false;
+    }
+    // Matroska holds practically anything
+    if container == "mkv" || container == "mka" {
+        return true;
+    }
+    match container_codecs(&container) {
+        Some(allowed) => families.iter().all(|f| allowed.contains(&f.as_str())),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remuxes_compatible_codecs() {
+        assert!(can_remux("mp4", Some("avc1.64001F"), Some("mp4a.40.2")));
+        assert!(can_remux("webm", Some("vp09.00.40.08"), Some("opus")));
+        assert!(can_remux("mkv", Some("vp09.00.40.08"), Some("opus")));
+        assert!(can_remux("MP4", Some("av01.0.08M.08"), Some("none")));
+    }
+
+    #[test]
+    fn recodes_incompatible_or_unknown_codecs() {
+        assert!(!can_remux("webm", Some("avc1.64001F"), Some("mp4a.40.2")));
+        assert!(!can_remux("mov", Some("vp09.00.40.08"), Some("opus")));
+        assert!(!can_remux("avi", Some("avc1.64001F"), Some("mp3")));
+        assert!(!can_remux("mp4", None, None));
+        assert!(!can_remux("mp4", Some("none"), Some("none")));
+    }
+}
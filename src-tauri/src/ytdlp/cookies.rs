@@ -0,0 +1,199 @@
+use crate::modules::types::AppError;
+use crate::ytdlp::types::{CookieDomain, CookieLineError, CookiesValidation, ExpiredCookie};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Larger files are not a browser export
+const MAX_COOKIES_FILE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Expired entries listed individually; the rest only count
+const MAX_EXPIRED_LISTED: usize = 100;
+
+const NETSCAPE_HEADERS: [&str; 2] = ["# Netscape HTTP Cookie File", "# HTTP Cookie File"];
+
+/// Parse a Netscape-format cookies file (the `--cookies` format) as of `now`.
+pub fn parse_cookies(path: &str, text: &str, now: i64) -> CookiesValidation {
+    let has_header = text
+        .lines()
+        .next()
+        .map(|l| {
+            NETSCAPE_HEADERS
+                .iter()
+                .any(|h| l.trim_start_matches('\u{feff}').starts_with(h))
+        })
+        .unwrap_or(false);
+
+    let mut total = 0u32;
+    let mut session = 0u32;
+    let mut expired = 0u32;
+    let mut domains: BTreeMap<String, (u32, u32)> = BTreeMap::new();
+    let mut expired_cookies = Vec::new();
+    let mut errors = Vec::new();
+
+    for (idx, raw) in text.lines().enumerate() {
+        let line_no = idx as u32 + 1;
+        // "#HttpOnly_" marks an HttpOnly cookie, not a comment
+        let line = raw.strip_prefix("#HttpOnly_").unwrap_or(raw);
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split('\t').collect();
+        // The value may be empty, which some exporters write without the last tab
+        if fields.len() != 7 && fields.len() != 6 {
+            errors.push(CookieLineError {
+                line: line_no,
+                message: format!("expected 7 tab-separated fields, found {}", fields.len()),
+            });
+            continue;
+        }
+        let (domain, subdomains, secure, expires, name) =
+            (fields[0], fields[1], fields[3], fields[4], fields[5]);
+
+        if domain.is_empty() {
+            errors.push(CookieLineError {
+                line: line_no,
+                message: "empty domain".to_string(),
+            });
+            continue;
+        }
+        if !is_flag(subdomains) || !is_flag(secure) {
+            errors.push(CookieLineError {
+                line: line_no,
+                message: "flag fields must be TRUE or FALSE".to_string(),
+            });
+            continue;
+        }
+        let expires: i64 = match expires.trim().parse() {
+            Ok(v) => v,
+            Err(_) => {
+                errors.push(CookieLineError {
+                    line: line_no,
+                    message: format!("invalid expiry timestamp '{}'", expires),
+                });
+                continue;
+            }
+        };
+
+        total += 1;
+        let entry = domains
+            .entry(domain.trim_start_matches('.').to_lowercase())
+            .or_default();
+        entry.0 += 1;
+        if expires == 0 {
+            session += 1;
+        } else if expires < now {
+            expired += 1;
+            entry.1 += 1;
+            if expired_cookies.len() < MAX_EXPIRED_LISTED {
+                expired_cookies.push(ExpiredCookie {
+                    domain: domain.to_string(),
+                    name: name.to_string(),
+                    expires_at: expires,
+                });
+            }
+        }
+    }
+
+    CookiesValidation {
+        path: path.to_string(),
+        valid: errors.is_empty() && total > 0,
+        has_header,
+        total,
+        session,
+        expired,
+        domains: domains
+            .into_iter()
+            .map(|(domain, (count, expired))| CookieDomain {
+                domain,
+                count,
+                expired,
+            })
+            .collect(),
+        expired_cookies,
+        errors,
+    }
+}
+
+fn is_flag(value: &str) -> bool {
+    value.eq_ignore_ascii_case("TRUE") || value.eq_ignore_ascii_case("FALSE")
+}
+
+/// Read and check a cookies file before it is handed to yt-dlp.
+pub fn validate_file(path: &str) -> Result<CookiesValidation, AppError> {
+    let p = Path::new(path.trim());
+    if !p.is_absolute() {
+        return Err(AppError::FileError(
+            "Cookies file path must be absolute".to_string(),
+        ));
+    }
+    let meta = std::fs::metadata(p)
+        .map_err(|e| AppError::FileError(format!("Cannot read cookies file: {}", e)))?;
+    if !meta.is_file() {
+        return Err(AppError::FileError(
+            "Cookies path is not a file".to_string(),
+        ));
+    }
+    if meta.len() > MAX_COOKIES_FILE_BYTES {
+        return Err(AppError::FileError(
+            "Cookies file is too large (over 10 MB)".to_string(),
+        ));
+    }
+    let bytes = std::fs::read(p)
+        .map_err(|e| AppError::FileError(format!("Cannot read cookies file: {}", e)))?;
+    let text = String::from_utf8_lossy(&bytes);
+    Ok(parse_cookies(
+        &p.to_string_lossy(),
+        &text,
+        chrono::Utc::now().timestamp(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    #[test]
+    fn counts_domains_sessions_and_expired() {
+        let text = "# Netscape HTTP Cookie File\n\
+            .youtube.com\tTRUE\t/\tTRUE\t1800000000\tSID\tabc\n\
+            #HttpOnly_.youtube.com\tTRUE\t/\tTRUE\t1600000000\tHSID\tdef\n\
+            www.example.com\tFALSE\t/\tFALSE\t0\tsession\t\n\
+            \n\
+            # a comment\n";
+        let v = parse_cookies("/c.txt", text, NOW);
+        assert!(v.valid);
+        assert!(v.has_header);
+        assert_eq!((v.total, v.session, v.expired), (3, 1, 1));
+        assert_eq!(v.domains.len(), 2);
+        assert_eq!(v.domains[1].domain, "youtube.com");
+        assert_eq!((v.domains[1].count, v.domains[1].expired), (2, 1));
+        assert_eq!(v.expired_cookies[0].name, "HSID");
+    }
+
+    #[test]
+    fn reports_malformed_lines_with_numbers() {
+        let text = "# Netscape HTTP Cookie File\n\
+            .youtube.com TRUE / TRUE 1800000000 SID abc\n\
+            .youtube.com\tYES\t/\tTRUE\t1800000000\tSID\tabc\n\
+            .youtube.com\tTRUE\t/\tTRUE\tsoon\tSID\tabc\n";
+        let v = parse_cookies("/c.txt", text, NOW);
+        assert!(!v.valid);
+        assert_eq!(v.total, 0);
+        let lines: Vec<u32> = v.errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn missing_header_is_flagged_but_not_an_error() {
+        let v = parse_cookies(
+            "/c.txt",
+            ".example.com\tTRUE\t/\tFALSE\t1800000000\ta\tb\n",
+            NOW,
+        );
+        assert!(v.valid);
+        assert!(!v.has_header);
+    }
+}
@@ -0,0 +1,172 @@
+//! `pauseAboveCpuPercent`: hold new downloads while the system is busy, for
+//! machines shared with games or renders.
+//!
+//! Total CPU usage is sampled every `cpuSampleSecs`. Starts are held once it
+//! has stayed above the threshold for `cpuSustainSecs` and released once it has
+//! stayed at or below it for as long, so short spikes don't flap the queue.
+//! Running downloads keep going. GPU load isn't read: there is no portable way
+//! to get it.
+
+use crate::modules::logger;
+use crate::ytdlp::download::DownloadManager;
+use crate::ytdlp::types::{AppSettings, CpuLoadState};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use sysinfo::System;
+use tauri::{AppHandle, Manager};
+
+pub const MAX_SAMPLE_SECS: u32 = 60;
+pub const MAX_SUSTAIN_SECS: u32 = 3600;
+/// How often the setting is looked at again while it is off
+const OFF_TICK: Duration = Duration::from_secs(10);
+
+/// Hold decision with the hysteresis that keeps it from flapping.
+#[derive(Default)]
+struct Tracker {
+    paused: bool,
+    /// When usage crossed to the other side of the threshold
+    crossed_at: Option<Instant>,
+    last_percent: Option<f32>,
+}
+
+impl Tracker {
+    /// Feed one sample; returns the new decision when it changes.
+    fn sample(
+        &mut self,
+        percent: f32,
+        threshold: u32,
+        sustain: Duration,
+        now: Instant,
+    ) -> Option<bool> {
+        self.last_percent = Some(percent);
+        let busy = percent > threshold as f32;
+        if busy == self.paused {
+            self.crossed_at = None;
+            return None;
+        }
+        let crossed_at = *self.crossed_at.get_or_insert(now);
+        if now.duration_since(crossed_at) < sustain {
+            return None;
+        }
+        self.paused = busy;
+        self.crossed_at = None;
+        Some(busy)
+    }
+}
+
+static TRACKER: LazyLock<Mutex<Tracker>> = LazyLock::new(|| Mutex::new(Tracker::default()));
+
+fn sample_interval(settings: &AppSettings) -> Duration {
+    Duration::from_secs(settings.cpu_sample_secs.clamp(1, MAX_SAMPLE_SECS) as u64)
+}
+
+fn sustain_window(settings: &AppSettings) -> Duration {
+    Duration::from_secs(settings.cpu_sustain_secs.min(MAX_SUSTAIN_SECS) as u64)
+}
+
+/// Start the background CPU sampler. Call once from setup.
+pub fn spawn_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut system = System::new();
+        loop {
+            let settings = crate::ytdlp::settings::get_settings(&app).unwrap_or_default();
+            let Some(threshold) = settings.pause_above_cpu_percent else {
+                reset(&app);
+                tokio::time::sleep(OFF_TICK).await;
+                continue;
+            };
+            // Usage is measured between two refreshes, so the first one only primes it
+            system.refresh_cpu_usage();
+            tokio::time::sleep(sample_interval(&settings)).await;
+            system.refresh_cpu_usage();
+            let percent = system.global_cpu_usage();
+
+            let flip = TRACKER.lock().unwrap_or_else(|e| e.into_inner()).sample(
+                percent,
+                threshold,
+                sustain_window(&settings),
+                Instant::now(),
+            );
+            if let Some(pause) = flip {
+                apply(&app, pause, percent, threshold);
+            }
+        }
+    });
+}
+
+fn apply(app: &AppHandle, pause: bool, percent: f32, threshold: u32) {
+    let manager = app.state::<Arc<DownloadManager>>();
+    manager.set_cpu_paused(pause);
+    if pause {
+        logger::info_cat(
+            "download",
+            &format!(
+                "CPU at {:.0}%, above {}%; holding new downloads until the system is less busy",
+                percent, threshold
+            ),
+        );
+    } else {
+        logger::info_cat("download", "CPU load hold lifted; resuming the queue");
+        crate::ytdlp::download::process_next_pending_public(app.clone());
+    }
+}
+
+/// Forget the samples so far and lift a hold, e.g. when the setting changes.
+pub fn reset(app: &AppHandle) {
+    *TRACKER.lock().unwrap_or_else(|e| e.into_inner()) = Tracker::default();
+    let manager = app.state::<Arc<DownloadManager>>();
+    if manager.cpu_paused() {
+        manager.set_cpu_paused(false);
+        logger::info_cat("download", "CPU load hold lifted; resuming the queue");
+        crate::ytdlp::download::process_next_pending_public(app.clone());
+    }
+}
+
+/// Latest sample together with the settings and whether starts are held.
+pub fn state(app: &AppHandle) -> CpuLoadState {
+    let settings = crate::ytdlp::settings::get_settings(app).unwrap_or_default();
+    let tracker = TRACKER.lock().unwrap_or_else(|e| e.into_inner());
+    CpuLoadState {
+        percent: tracker.last_percent,
+        threshold: settings.pause_above_cpu_percent,
+        sample_secs: sample_interval(&settings).as_secs() as u32,
+        sustain_secs: sustain_window(&settings).as_secs() as u32,
+        paused: app.state::<Arc<DownloadManager>>().cpu_paused(),
+        pending_secs: tracker.crossed_at.map(|at| at.elapsed().as_secs() as u32),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn holds_only_after_sustained_load_and_releases_the_same_way() {
+        let mut tracker = Tracker::default();
+        let start = Instant::now();
+        let sustain = Duration::from_secs(30);
+        let at = |secs| start + Duration::from_secs(secs);
+
+        assert_eq!(tracker.sample(95.0, 80, sustain, at(0)), None);
+        // A dip resets the window
+        assert_eq!(tracker.sample(40.0, 80, sustain, at(10)), None);
+        assert_eq!(tracker.sample(95.0, 80, sustain, at(20)), None);
+        assert_eq!(tracker.sample(90.0, 80, sustain, at(40)), None);
+        assert_eq!(tracker.sample(90.0, 80, sustain, at(50)), Some(true));
+        assert_eq!(tracker.sample(99.0, 80, sustain, at(60)), None);
+
+        // At the threshold counts as not busy
+        assert_eq!(tracker.sample(80.0, 80, sustain, at(70)), None);
+        assert_eq!(tracker.sample(85.0, 80, sustain, at(80)), None);
+        assert_eq!(tracker.sample(20.0, 80, sustain, at(90)), None);
+        assert_eq!(tracker.sample(20.0, 80, sustain, at(120)), Some(false));
+    }
+
+    #[test]
+    fn zero_sustain_flips_on_the_first_sample() {
+        let mut tracker = Tracker::default();
+        let now = Instant::now();
+        assert_eq!(tracker.sample(95.0, 80, Duration::ZERO, now), Some(true));
+        assert_eq!(tracker.sample(10.0, 80, Duration::ZERO, now), Some(false));
+    }
+}
@@ -0,0 +1,55 @@
+use super::types::TaskCredentials;
+use crate::modules::logger;
+use crate::modules::types::AppError;
+use keyring::Entry;
+
+/// Keychain service name for per-task credentials.
+const SERVICE: &str = "com.modern-ytdlp-gui.app";
+
+fn entry(task_id: u64) -> Result<Entry, AppError> {
+    Entry::new(SERVICE, &format!("task-{}", task_id))
+        .map_err(|e| AppError::Custom(format!("Keychain unavailable: {}", e)))
+}
+
+/// True if at least one credential field is set.
+pub fn has_any(creds: &TaskCredentials) -> bool {
+    [&creds.username, &creds.password, &creds.video_password]
+        .iter()
+        .any(|v| v.as_deref().is_some_and(|s| !s.is_empty()))
+}
+
+/// Save a task's credentials to the OS keychain (never the DB).
+pub fn store(task_id: u64, creds: &TaskCredentials) -> Result<(), AppError> {
+    let secret = serde_json::to_string(creds).map_err(|e| AppError::Custom(e.to_string()))?;
+    entry(task_id)?
+        .set_password(&secret)
+        .map_err(|e| AppError::Custom(format!("Failed to store credentials: {}", e)))
+}
+
+/// Load a task's credentials from the OS keychain.
+pub fn load(task_id: u64) -> Result<Option<TaskCredentials>, AppError> {
+    match entry(task_id)?.get_password() {
+        Ok(secret) => serde_json::from_str(&secret)
+            .map(Some)
+            .map_err(|e| AppError::Custom(format!("Corrupt stored credentials: {}", e))),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(AppError::Custom(format!(
+            "Failed to read credentials: {}",
+            e
+        ))),
+    }
+}
+
+/// Remove a task's credentials. Missing entries are not an error.
+pub fn delete(task_id: u64) {
+    let result = entry(task_id).and_then(|e| match e.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(AppError::Custom(e.to_string())),
+    });
+    if let Err(e) = result {
+        logger::warn_cat(
+            "download",
+            &format!("[download:{}] failed to delete credentials: {}", task_id, e),
+        );
+    }
+}
@@ -0,0 +1,239 @@
+//! Copying the database out to a full backup and back in (`export_full_backup`,
+//! `import_full_backup`).
+
+use super::{Database, SCHEMA_VERSION};
+use crate::modules::types::AppError;
+use crate::ytdlp::types::{BackupImportMode, BackupRestoreCounts};
+use rusqlite::Connection;
+use std::path::Path;
+
+/// Tables a full backup carries, parents before children
+const TABLES: &[&str] = &[
+    "downloads",
+    "history",
+    "watched_sources",
+    "watched_seen",
+    "presets",
+    "download_starts",
+    "bandwidth_usage",
+    "blocked_video_ids",
+];
+
+fn db_err(e: rusqlite::Error) -> AppError {
+    AppError::DatabaseError(e.to_string())
+}
+
+/// Column names of `schema.table`.
+fn columns(conn: &Connection, schema: &str, table: &str) -> Result<Vec<String>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!("PRAGMA {}.table_info({})", schema, table))
+        .map_err(db_err)?;
+    let names = stmt
+        .query_map([], |row| row.get::<_, String>(1))
+        .map_err(db_err)?;
+    names.collect::<Result<_, _>>().map_err(db_err)
+}
+
+/// Columns present in both copies of `table`, minus `skip`.
+fn shared_columns(conn: &Connection, table: &str, skip: &[&str]) -> Result<Vec<String>, AppError> {
+    let theirs = columns(conn, "bk", table)?;
+    Ok(columns(conn, "main", table)?
+        .into_iter()
+        .filter(|c| theirs.contains(c) && !skip.contains(&c.as_str()))
+        .collect())
+}
+
+impl Database {
+    /// Write a consistent copy of the database to `dest`, which must not exist.
+    pub fn snapshot_to(&self, dest: &Path) -> Result<(), AppError> {
+        self.conn()
+            .execute("VACUUM INTO ?1", [dest.to_string_lossy()])
+            .map_err(db_err)?;
+        Ok(())
+    }
+
+    /// Bring a database file taken from a backup up to the current schema.
+    /// Returns the version it had; refuses files from a newer app.
+    pub fn upgrade_file(path: &Path) -> Result<u32, AppError> {
+        let conn = Connection::open(path).map_err(db_err)?;
+        let check: String = conn
+            .query_row("PRAGMA quick_check", [], |row| row.get(0))
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Backup database is unreadable: {}", e))
+            })?;
+        if check != "ok" {
+            return Err(AppError::DatabaseError(format!(
+                "Backup database is damaged: {}",
+                check
+            )));
+        }
+        let version = Self::get_schema_version(&conn)?;
+        if version > SCHEMA_VERSION {
+            return Err(AppError::DatabaseError(format!(
+                "The backup was made by a newer version (schema {} > {})",
+                version, SCHEMA_VERSION
+            )));
+        }
+        Self::create_tables(&conn)?;
+        Self::run_migrations(&conn)?;
+        Ok(version)
+    }
+
+    /// Bring in the data of an upgraded backup database. `Replace` swaps every
+    /// table for the backup's; `Merge` adds only what isn't here yet and keeps
+    /// local rows on conflict. Tasks that were running come back as pending.
+    pub fn restore_from(
+        &self,
+        path: &Path,
+        mode: BackupImportMode,
+    ) -> Result<BackupRestoreCounts, AppError> {
+        let mut conn = self.conn();
+        conn.execute("ATTACH DATABASE ?1 AS bk", [path.to_string_lossy()])
+            .map_err(db_err)?;
+        let result = (|| {
+            let tx = conn.transaction().map_err(db_err)?;
+            let counts = match mode {
+                BackupImportMode::Replace => replace(&tx)?,
+                BackupImportMode::Merge => merge(&tx)?,
+            };
+            tx.commit().map_err(db_err)?;
+            Ok(counts)
+        })();
+        let _ = conn.execute("DETACH DATABASE bk", []);
+        result
+    }
+}
+
+fn replace(conn: &Connection) -> Result<BackupRestoreCounts, AppError> {
+    for table in TABLES.iter().rev() {
+        conn.execute(&format!("DELETE FROM main.{}", table), [])
+            .map_err(db_err)?;
+    }
+    let mut counts = BackupRestoreCounts::default();
+    for table in TABLES {
+        let cols = shared_columns(conn, table, &[])?.join(", ");
+        let n = conn
+            .execute(
+                &format!(
+                    "INSERT INTO main.{t} ({c}) SELECT {c} FROM bk.{t}",
+                    t = table,
+                    c = cols
+                ),
+                [],
+            )
+            .map_err(db_err)? as u32;
+        match *table {
+            "downloads" => counts.queue = n,
+            "history" => counts.history = n,
+            "watched_sources" => counts.watched_sources = n,
+            "presets" => counts.presets = n,
+            _ => {}
+        }
+    }
+    // No process is running for them here
+    conn.execute(
+        "UPDATE main.downloads SET status = 'pending' WHERE status = 'downloading'",
+        [],
+    )
+    .map_err(db_err)?;
+    // Keychain credentials are keyed by task id and don't travel with a
+    // backup; a restored id could otherwise pick up another task's login
+    conn.execute(
+        "UPDATE main.downloads SET has_credentials = 0 WHERE has_credentials = 1",
+        [],
+    )
+    .map_err(db_err)?;
+    Ok(counts)
+}
+
+fn merge(conn: &Connection) -> Result<BackupRestoreCounts, AppError> {
+    // History ids differ between installs, so links to superseding rows are dropped
+    let cols = shared_columns(conn, "history", &["id", "superseded_by"])?;
+    let select: Vec<String> = cols.iter().map(|c| format!("b.{}", c)).collect();
+    let history = conn
+        .execute(
+            &format!(
+                "INSERT INTO main.history ({}) SELECT {} FROM bk.history b
+                 WHERE NOT EXISTS (SELECT 1 FROM main.history h
+                     WHERE h.video_id = b.video_id AND h.file_path = b.file_path
+                       AND h.downloaded_at = b.downloaded_at)
+                 ORDER BY b.id",
+                cols.join(", "),
+                select.join(", ")
+            ),
+            [],
+        )
+        .map_err(db_err)? as u32;
+
+    // Only unfinished tasks; finished ones live on in history
+    let cols = shared_columns(conn, "downloads", &["id"])?;
+    let select: Vec<String> =
+        cols.iter()
+            .map(|c| match c.as_str() {
+                "status" => "CASE WHEN b.status = 'downloading' THEN 'pending' ELSE b.status END"
+                    .to_string(),
+                // Credentials stay in the keychain under the old task id
+                "has_credentials" => "0".to_string(),
+                _ => format!("b.{}", c),
+            })
+            .collect();
+    let queue = conn
+        .execute(
+            &format!(
+                "INSERT INTO main.downloads ({}) SELECT {} FROM bk.downloads b
+                 WHERE b.status IN ('pending', 'downloading', 'paused', 'failed')
+                   AND NOT EXISTS (SELECT 1 FROM main.downloads d
+                       WHERE d.video_id = b.video_id AND d.status IN ('pending', 'downloading', 'paused'))
+                 ORDER BY b.id",
+                cols.join(", "),
+                select.join(", ")
+            ),
+            [],
+        )
+        .map_err(db_err)? as u32;
+
+    let cols = shared_columns(conn, "watched_sources", &["id"])?.join(", ");
+    let watched_sources = conn
+        .execute(
+            &format!(
+                "INSERT OR IGNORE INTO main.watched_sources ({c}) SELECT {c} FROM bk.watched_sources",
+                c = cols
+            ),
+            [],
+        )
+        .map_err(db_err)? as u32;
+    conn.execute(
+        "INSERT OR IGNORE INTO main.watched_seen (source_id, video_id)
+         SELECT m.id, s.video_id FROM bk.watched_seen s
+         JOIN bk.watched_sources b ON b.id = s.source_id
+         JOIN main.watched_sources m ON m.url = b.url",
+        [],
+    )
+    .map_err(db_err)?;
+
+    let cols = shared_columns(conn, "presets", &[])?.join(", ");
+    let presets = conn
+        .execute(
+            &format!(
+                "INSERT OR IGNORE INTO main.presets ({c}) SELECT {c} FROM bk.presets",
+                c = cols
+            ),
+            [],
+        )
+        .map_err(db_err)? as u32;
+
+    conn.execute(
+        "INSERT OR IGNORE INTO main.blocked_video_ids (video_id, blocked_at)
+         SELECT video_id, blocked_at FROM bk.blocked_video_ids",
+        [],
+    )
+    .map_err(db_err)?;
+
+    Ok(BackupRestoreCounts {
+        history,
+        queue,
+        watched_sources,
+        presets,
+        logs: 0,
+    })
+}
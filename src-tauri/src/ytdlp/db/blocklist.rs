@@ -0,0 +1,65 @@
+use super::Database;
+use crate::modules::types::AppError;
+use crate::ytdlp::types::BlockedVideo;
+use rusqlite::{params, OptionalExtension};
+
+/// Video ids that are never queued (`block_video`).
+impl Database {
+    /// Add `video_id` to the blocklist. Returns false if it was already there.
+    pub fn block_video(&self, video_id: &str) -> Result<bool, AppError> {
+        let added = self
+            .conn()
+            .execute(
+                "INSERT OR IGNORE INTO blocked_video_ids (video_id, blocked_at) VALUES (?1, ?2)",
+                params![video_id, chrono::Utc::now().timestamp()],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(added > 0)
+    }
+
+    /// Remove `video_id` from the blocklist. Returns false if it wasn't there.
+    pub fn unblock_video(&self, video_id: &str) -> Result<bool, AppError> {
+        let removed = self
+            .conn()
+            .execute(
+                "DELETE FROM blocked_video_ids WHERE video_id = ?1",
+                [video_id],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(removed > 0)
+    }
+
+    pub fn is_video_blocked(&self, video_id: &str) -> Result<bool, AppError> {
+        let found: Option<i64> = self
+            .conn()
+            .query_row(
+                "SELECT 1 FROM blocked_video_ids WHERE video_id = ?1",
+                [video_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(found.is_some())
+    }
+
+    /// Newest first.
+    pub fn list_blocked_videos(&self) -> Result<Vec<BlockedVideo>, AppError> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare(
+                "SELECT video_id, blocked_at FROM blocked_video_ids
+                 ORDER BY blocked_at DESC, video_id",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(BlockedVideo {
+                    video_id: row.get(0)?,
+                    blocked_at: row.get(1)?,
+                })
+            })
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+}
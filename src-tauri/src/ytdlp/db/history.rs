@@ -1,15 +1,89 @@
 use super::Database;
 use crate::modules::types::AppError;
 use crate::ytdlp::types::*;
-use rusqlite::params;
+use rusqlite::{params, OptionalExtension};
+
+/// Tags are stored as a JSON array; '[]' when none are known.
+pub(super) fn tags_json(tags: &[String]) -> String {
+    serde_json::to_string(tags).unwrap_or_else(|_| "[]".to_string())
+}
+
+pub(super) fn map_history_row(row: &rusqlite::Row) -> rusqlite::Result<HistoryItem> {
+    Ok(HistoryItem {
+        id: row.get(0)?,
+        video_url: row.get(1)?,
+        video_id: row.get(2)?,
+        title: row.get(3)?,
+        quality_label: row.get(4)?,
+        format: row.get(5)?,
+        file_path: row.get(6)?,
+        file_size: row.get(7)?,
+        downloaded_at: row.get(8)?,
+        recode_target: row.get(9)?,
+        max_height: row.get(10)?,
+        duration_secs: row.get(11)?,
+        superseded_by: row.get(12)?,
+        chapters_embedded: row.get(13)?,
+        remux_target: row.get(14)?,
+        uploader: row.get(15)?,
+        container: row.get(16)?,
+        tags: row
+            .get::<_, Option<String>>(17)?
+            .and_then(|t| serde_json::from_str(&t).ok())
+            .unwrap_or_default(),
+        audio_lang: row.get(18)?,
+        verify_status: row.get(19)?,
+        verify_error: row.get(20)?,
+        destinations: row
+            .get::<_, Option<String>>(21)?
+            .and_then(|d| serde_json::from_str(&d).ok()),
+        preset: row.get(22)?,
+        vcodec: row.get(23)?,
+        is_favorite: row.get(24)?,
+        user_tags: serde_json::from_str(&row.get::<_, String>(25)?).unwrap_or_default(),
+    })
+}
+
+/// `get_history`'s search as a WHERE clause on `?1` and the bound pattern.
+/// No search matches every row (`?1` is then left unused).
+fn search_filter(search: Option<&str>) -> (&'static str, String) {
+    match search {
+        Some(s) => {
+            let escaped = s
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            ("WHERE title LIKE ?1 ESCAPE '\\'", format!("%{}%", escaped))
+        }
+        None => ("WHERE 1 = 1", String::new()),
+    }
+}
+
+/// ORDER BY for `get_history`. Column names come from this fixed mapping, never
+/// from the caller; the id breaks ties so pages don't overlap.
+fn order_clause(sort_by: HistorySortKey, sort_dir: SortDir) -> String {
+    let column = match sort_by {
+        HistorySortKey::Date => "downloaded_at",
+        HistorySortKey::Size => "file_size",
+        HistorySortKey::Duration => "duration_secs",
+        HistorySortKey::Title => "title COLLATE NOCASE",
+    };
+    let dir = match sort_dir {
+        SortDir::Asc => "ASC",
+        SortDir::Desc => "DESC",
+    };
+    format!("ORDER BY {} {}, id {}", column, dir, dir)
+}
+
+pub(super) const HISTORY_COLUMNS: &str = "id, video_url, video_id, title, quality_label, format, file_path, file_size, downloaded_at, recode_target, max_height, duration_secs, superseded_by, chapters_embedded, remux_target, uploader, container, tags, audio_lang, verify_status, verify_error, destinations, preset, vcodec, is_favorite, user_tags";
 
 impl Database {
     pub fn insert_history(&self, item: &HistoryItem) -> Result<u64, AppError> {
         let conn = self.conn();
 
         conn.execute(
-            "INSERT INTO history (video_url, video_id, title, quality_label, format, file_path, file_size, downloaded_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            "INSERT INTO history (video_url, video_id, title, quality_label, format, file_path, file_size, downloaded_at, recode_target, max_height, duration_secs, chapters_embedded, remux_target, uploader, container, tags, audio_lang, verify_status, verify_error, destinations, preset, vcodec, user_tags)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23)",
             params![
                 item.video_url,
                 item.video_id,
@@ -19,6 +93,23 @@ impl Database {
                 item.file_path,
                 item.file_size,
                 item.downloaded_at,
+                item.recode_target,
+                item.max_height,
+                item.duration_secs,
+                item.chapters_embedded,
+                item.remux_target,
+                item.uploader,
+                item.container,
+                tags_json(&item.tags),
+                item.audio_lang,
+                item.verify_status,
+                item.verify_error,
+                item.destinations
+                    .as_ref()
+                    .and_then(|d| serde_json::to_string(d).ok()),
+                item.preset,
+                item.vcodec,
+                tags_json(&item.user_tags),
             ],
         ).map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
@@ -30,18 +121,18 @@ impl Database {
         page: u32,
         page_size: u32,
         search: Option<&str>,
+        favorites_only: bool,
+        sort_by: HistorySortKey,
+        sort_dir: SortDir,
     ) -> Result<HistoryResult, AppError> {
         let page_size = page_size.clamp(1, 100);
         let conn = self.conn();
 
-        let (where_clause, search_param) = if let Some(s) = search {
-            let escaped = s
-                .replace('\\', "\\\\")
-                .replace('%', "\\%")
-                .replace('_', "\\_");
-            ("WHERE title LIKE ?1 ESCAPE '\\'", format!("%{}%", escaped))
+        let (where_clause, search_param) = search_filter(search);
+        let where_clause = if favorites_only {
+            format!("{} AND is_favorite = 1", where_clause)
         } else {
-            ("", String::new())
+            where_clause.to_string()
         };
 
         let total_count: u64 = if search.is_some() {
@@ -52,18 +143,24 @@ impl Database {
             )
             .map_err(|e| AppError::DatabaseError(e.to_string()))?
         } else {
-            conn.query_row("SELECT COUNT(*) FROM history", [], |row| row.get(0))
-                .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            conn.query_row(
+                &format!("SELECT COUNT(*) FROM history {}", where_clause),
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
         };
 
         let offset = page * page_size;
         let query = format!(
-            "SELECT id, video_url, video_id, title, quality_label, format, file_path, file_size, downloaded_at
+            "SELECT {}
              FROM history
              {}
-             ORDER BY downloaded_at DESC
+             {}
              LIMIT ?{} OFFSET ?{}",
+            HISTORY_COLUMNS,
             where_clause,
+            order_clause(sort_by, sort_dir),
             if search.is_some() { "2" } else { "1" },
             if search.is_some() { "3" } else { "2" }
         );
@@ -72,27 +169,13 @@ impl Database {
             .prepare(&query)
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        fn map_row(row: &rusqlite::Row) -> rusqlite::Result<HistoryItem> {
-            Ok(HistoryItem {
-                id: row.get(0)?,
-                video_url: row.get(1)?,
-                video_id: row.get(2)?,
-                title: row.get(3)?,
-                quality_label: row.get(4)?,
-                format: row.get(5)?,
-                file_path: row.get(6)?,
-                file_size: row.get(7)?,
-                downloaded_at: row.get(8)?,
-            })
-        }
-
         let items = if search.is_some() {
-            stmt.query_map(params![search_param, page_size, offset], map_row)
+            stmt.query_map(params![search_param, page_size, offset], map_history_row)
                 .map_err(|e| AppError::DatabaseError(e.to_string()))?
                 .collect::<Result<Vec<_>, _>>()
                 .map_err(|e| AppError::DatabaseError(e.to_string()))?
         } else {
-            stmt.query_map(params![page_size, offset], map_row)
+            stmt.query_map(params![page_size, offset], map_history_row)
                 .map_err(|e| AppError::DatabaseError(e.to_string()))?
                 .collect::<Result<Vec<_>, _>>()
                 .map_err(|e| AppError::DatabaseError(e.to_string()))?
@@ -103,9 +186,127 @@ impl Database {
             total_count,
             page,
             page_size,
+            sort_by,
+            sort_dir,
         })
     }
 
+    /// Add user tag `tag` to every row matching `search` (as in `get_history`)
+    /// that lacks it. Returns the number of rows changed.
+    pub fn bulk_tag(&self, search: Option<&str>, tag: &str) -> Result<u64, AppError> {
+        let (where_clause, search_param) = search_filter(search);
+        let changed = self
+            .conn()
+            .execute(
+                &format!(
+                    "UPDATE history SET user_tags = json_insert(user_tags, '$[#]', ?2)
+                     {} AND NOT EXISTS (SELECT 1 FROM json_each(history.user_tags) WHERE value = ?2)",
+                    where_clause
+                ),
+                params![search_param, tag],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(changed as u64)
+    }
+
+    /// Remove user tag `tag` from every row matching `search`. Returns the
+    /// number of rows changed.
+    pub fn bulk_untag(&self, search: Option<&str>, tag: &str) -> Result<u64, AppError> {
+        let (where_clause, search_param) = search_filter(search);
+        let changed = self
+            .conn()
+            .execute(
+                &format!(
+                    // json_group_array of no rows is '[]', never NULL
+                    "UPDATE history SET user_tags = (
+                         SELECT json_group_array(value) FROM json_each(history.user_tags) WHERE value != ?2)
+                     {} AND EXISTS (SELECT 1 FROM json_each(history.user_tags) WHERE value = ?2)",
+                    where_clause
+                ),
+                params![search_param, tag],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(changed as u64)
+    }
+
+    /// Flip the favorite flag of a history row. Returns the new value, or None
+    /// when the row doesn't exist.
+    pub fn toggle_favorite(&self, id: u64) -> Result<Option<bool>, AppError> {
+        let conn = self.conn();
+        let changed = conn
+            .execute(
+                "UPDATE history SET is_favorite = 1 - is_favorite WHERE id = ?1",
+                [id],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        if changed == 0 {
+            return Ok(None);
+        }
+        conn.query_row(
+            "SELECT is_favorite FROM history WHERE id = ?1",
+            [id],
+            |row| row.get(0),
+        )
+        .map(Some)
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Video ids with at least one favorite history row.
+    pub fn favorite_video_ids(&self) -> Result<std::collections::HashSet<String>, AppError> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare("SELECT DISTINCT video_id FROM history WHERE is_favorite = 1")
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let ids = stmt
+            .query_map([], |row| row.get(0))
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .collect::<Result<_, _>>()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(ids)
+    }
+
+    /// Current (not superseded) history rows, oldest first.
+    pub fn current_history(&self) -> Result<Vec<HistoryItem>, AppError> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM history WHERE superseded_by IS NULL ORDER BY id ASC",
+                HISTORY_COLUMNS
+            ))
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let items = stmt
+            .query_map([], map_history_row)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(items)
+    }
+
+    /// Point history rows at renamed files, as (id, old path, new path). All or
+    /// nothing: fails without changes when a row is gone or has moved meanwhile.
+    pub fn rename_history_paths(&self, rows: &[(u64, String, String)]) -> Result<(), AppError> {
+        let mut conn = self.conn();
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        for (id, from, to) in rows {
+            let changed = tx
+                .execute(
+                    "UPDATE history SET file_path = ?1 WHERE id = ?2 AND file_path = ?3",
+                    params![to, id, from],
+                )
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            if changed != 1 {
+                return Err(AppError::Custom(format!(
+                    "History item {} changed since the preview",
+                    id
+                )));
+            }
+        }
+        tx.commit()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
     pub fn check_duplicate_in_queue(&self, video_id: &str) -> Result<bool, AppError> {
         let conn = self.conn();
         let count: i64 = conn
@@ -118,29 +319,32 @@ impl Database {
         Ok(count > 0)
     }
 
+    pub fn get_history_item(&self, id: u64) -> Result<Option<HistoryItem>, AppError> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM history WHERE id = ?1",
+                HISTORY_COLUMNS
+            ))
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        match stmt.query_row([id], map_history_row) {
+            Ok(item) => Ok(Some(item)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(AppError::DatabaseError(e.to_string())),
+        }
+    }
+
     pub fn check_duplicate(&self, video_id: &str) -> Result<Option<HistoryItem>, AppError> {
         let conn = self.conn();
-        let mut stmt = conn.prepare(
-            "SELECT id, video_url, video_id, title, quality_label, format, file_path, file_size, downloaded_at
-             FROM history
-             WHERE video_id = ?1
-             ORDER BY downloaded_at DESC
-             LIMIT 1"
-        ).map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM history WHERE video_id = ?1 ORDER BY downloaded_at DESC LIMIT 1",
+                HISTORY_COLUMNS
+            ))
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        let result = stmt.query_row([video_id], |row| {
-            Ok(HistoryItem {
-                id: row.get(0)?,
-                video_url: row.get(1)?,
-                video_id: row.get(2)?,
-                title: row.get(3)?,
-                quality_label: row.get(4)?,
-                format: row.get(5)?,
-                file_path: row.get(6)?,
-                file_size: row.get(7)?,
-                downloaded_at: row.get(8)?,
-            })
-        });
+        let result = stmt.query_row([video_id], map_history_row);
 
         match result {
             Ok(item) => Ok(Some(item)),
@@ -149,6 +353,66 @@ impl Database {
         }
     }
 
+    /// Latest history row downloaded from `video_url`.
+    pub fn history_by_url(&self, video_url: &str) -> Result<Option<HistoryItem>, AppError> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM history WHERE video_url = ?1 ORDER BY downloaded_at DESC LIMIT 1",
+                HISTORY_COLUMNS
+            ))
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        match stmt.query_row([video_url], map_history_row) {
+            Ok(item) => Ok(Some(item)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(AppError::DatabaseError(e.to_string())),
+        }
+    }
+
+    /// Compute the fingerprint of every row that has none yet. Returns how many were filled.
+    pub fn fill_fingerprints(&self, key: impl Fn(&str) -> String) -> Result<u64, AppError> {
+        let mut conn = self.conn();
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let rows: Vec<(u64, String)> = {
+            let mut stmt = tx
+                .prepare("SELECT id, title FROM history WHERE fingerprint IS NULL")
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            let rows = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            rows.collect::<Result<_, _>>()
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        };
+        for (id, title) in &rows {
+            tx.execute(
+                "UPDATE history SET fingerprint = ?1 WHERE id = ?2",
+                params![key(title), id],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+        tx.commit()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(rows.len() as u64)
+    }
+
+    /// (id, fingerprint, duration_secs) of every fingerprinted row.
+    pub fn history_fingerprints(&self) -> Result<Vec<(u64, String, Option<u64>)>, AppError> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, fingerprint, duration_secs FROM history WHERE fingerprint IS NOT NULL",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
     pub fn delete_history(&self, id: u64) -> Result<(), AppError> {
         let conn = self.conn();
 
@@ -157,4 +421,214 @@ impl Database {
 
         Ok(())
     }
+
+    /// Rows still missing a duration that were never probed, as (id, file_path,
+    /// video_url), newest first, starting below `before_id`.
+    pub fn history_missing_duration(
+        &self,
+        before_id: Option<u64>,
+        limit: u32,
+    ) -> Result<Vec<(u64, String, String)>, AppError> {
+        self.history_batch(
+            "duration_secs IS NULL AND duration_probed_at IS NULL",
+            before_id,
+            limit,
+        )
+    }
+
+    /// Rows `normalize_history` has not visited yet, as (id, file_path,
+    /// video_url), newest first, starting below `before_id`.
+    pub fn history_to_normalize(
+        &self,
+        before_id: Option<u64>,
+        limit: u32,
+    ) -> Result<Vec<(u64, String, String)>, AppError> {
+        self.history_batch("normalized_at IS NULL", before_id, limit)
+    }
+
+    /// One batch of a backfill over rows matching `pending` (a fixed condition,
+    /// never caller input), paged by id like `backfill_durations`.
+    fn history_batch(
+        &self,
+        pending: &str,
+        before_id: Option<u64>,
+        limit: u32,
+    ) -> Result<Vec<(u64, String, String)>, AppError> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT id, file_path, video_url FROM history
+                 WHERE {} AND id < ?1
+                 ORDER BY id DESC LIMIT ?2",
+                pending
+            ))
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let before = before_id.map(|id| id as i64).unwrap_or(i64::MAX);
+        let rows = stmt
+            .query_map(params![before, limit], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(rows)
+    }
+
+    /// Timestamps of downloads completed at or after `since`. Extra stream files
+    /// share their task's row, so each (video_id, downloaded_at) counts once.
+    pub fn completion_timings(
+        &self,
+        since: i64,
+    ) -> Result<Vec<crate::ytdlp::throughput::TimingRow>, AppError> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare(
+                "SELECT downloaded_at, MAX(enqueued_at), MAX(started_at) FROM history
+                 WHERE downloaded_at >= ?1
+                 GROUP BY video_id, downloaded_at",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![since], |row| {
+                Ok(crate::ytdlp::throughput::TimingRow {
+                    downloaded_at: row.get(0)?,
+                    enqueued_at: row.get(1)?,
+                    started_at: row.get(2)?,
+                })
+            })
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(rows)
+    }
+
+    /// (file_path, file_size) of every download that wasn't replaced by a later one.
+    pub fn history_file_sizes(&self) -> Result<Vec<(String, Option<u64>)>, AppError> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare("SELECT file_path, file_size FROM history WHERE superseded_by IS NULL")
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(rows)
+    }
+
+    /// Fill only the columns that are still missing and mark the row visited;
+    /// its duration counts as probed too. Returns whether any column changed.
+    pub fn apply_normalized(
+        &self,
+        id: u64,
+        fields: &crate::ytdlp::normalize::RowFields,
+    ) -> Result<bool, AppError> {
+        let mut conn = self.conn();
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let tags = fields
+            .tags
+            .as_deref()
+            .filter(|t| !t.is_empty())
+            .map(tags_json);
+        let changed = tx
+            .execute(
+                "UPDATE history SET
+                    duration_secs = COALESCE(duration_secs, ?1),
+                    uploader = COALESCE(uploader, ?2),
+                    container = COALESCE(container, ?3),
+                    tags = CASE WHEN tags = '[]' AND ?4 IS NOT NULL THEN ?4 ELSE tags END
+                 WHERE id = ?5
+                   AND ((duration_secs IS NULL AND ?1 IS NOT NULL)
+                     OR (uploader IS NULL AND ?2 IS NOT NULL)
+                     OR (container IS NULL AND ?3 IS NOT NULL)
+                     OR (tags = '[]' AND ?4 IS NOT NULL))",
+                params![
+                    fields.duration_secs,
+                    fields.uploader,
+                    fields.container,
+                    tags,
+                    id
+                ],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let now = chrono::Utc::now().timestamp();
+        tx.execute(
+            "UPDATE history SET normalized_at = ?1,
+                 duration_probed_at = COALESCE(duration_probed_at, ?1)
+             WHERE id = ?2",
+            params![now, id],
+        )
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        tx.commit()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(changed > 0)
+    }
+
+    /// Queue task that completed into a history row: same video, same completion time.
+    pub fn task_id_for_history(
+        &self,
+        video_id: &str,
+        completed_at: i64,
+    ) -> Result<Option<u64>, AppError> {
+        let conn = self.conn();
+        conn.query_row(
+            "SELECT id FROM downloads WHERE video_id = ?1 AND completed_at = ?2 LIMIT 1",
+            params![video_id, completed_at],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    pub fn set_history_verification(
+        &self,
+        id: u64,
+        status: &str,
+        error: Option<&str>,
+    ) -> Result<(), AppError> {
+        let conn = self.conn();
+        conn.execute(
+            "UPDATE history SET verify_status = ?1, verify_error = ?2 WHERE id = ?3",
+            params![status, error, id],
+        )
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Store what re-running post-processing left on disk.
+    pub fn set_history_postprocessed(
+        &self,
+        id: u64,
+        file_size: Option<u64>,
+        chapters_embedded: bool,
+    ) -> Result<(), AppError> {
+        let conn = self.conn();
+        conn.execute(
+            "UPDATE history SET file_size = COALESCE(?1, file_size), chapters_embedded = ?2 WHERE id = ?3",
+            params![file_size, chapters_embedded, id],
+        )
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Store the outcome of probing a row's duration; None marks it probed
+    /// without one, so `history_missing_duration` skips it from then on.
+    pub fn record_duration_probe(
+        &self,
+        id: u64,
+        duration_secs: Option<u64>,
+    ) -> Result<(), AppError> {
+        let conn = self.conn();
+
+        conn.execute(
+            "UPDATE history SET duration_secs = COALESCE(?1, duration_secs), duration_probed_at = ?2
+             WHERE id = ?3",
+            params![duration_secs, chrono::Utc::now().timestamp(), id],
+        )
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
 }
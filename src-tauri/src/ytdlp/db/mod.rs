@@ -1,5 +1,10 @@
+mod backup;
+mod blocklist;
 mod history;
+mod presets;
 mod queue;
+mod usage;
+mod watch;
 
 use crate::modules::types::AppError;
 use rusqlite::Connection;
@@ -11,7 +16,7 @@ pub struct Database {
 }
 
 /// Current schema version. Increment when adding new migrations.
-const SCHEMA_VERSION: u32 = 3;
+pub const SCHEMA_VERSION: u32 = 38;
 
 impl Database {
     pub fn new(app_data_dir: &Path) -> Result<Self, AppError> {
@@ -87,6 +92,325 @@ impl Database {
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
         }
 
+        if current < 4 {
+            // v4: Track source playlist so queued playlist items can be cancelled together
+            conn.execute_batch("ALTER TABLE downloads ADD COLUMN playlist_id TEXT;")
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 5 {
+            // v5: Record the --recode-video target a download was converted to
+            conn.execute_batch("ALTER TABLE history ADD COLUMN recode_target TEXT;")
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 6 {
+            // v6: Flag tasks whose credentials live in the OS keychain
+            conn.execute_batch(
+                "ALTER TABLE downloads ADD COLUMN has_credentials INTEGER NOT NULL DEFAULT 0;",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 7 {
+            // v7: Keep best video and best audio as separate files instead of merging
+            conn.execute_batch(
+                "ALTER TABLE downloads ADD COLUMN keep_separate_streams INTEGER NOT NULL DEFAULT 0;",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 8 {
+            // v8: Record the global max height cap a download was limited to
+            conn.execute_batch("ALTER TABLE history ADD COLUMN max_height INTEGER;")
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 9 {
+            // v9: Media duration in seconds, filled at completion or by backfill
+            conn.execute_batch("ALTER TABLE history ADD COLUMN duration_secs INTEGER;")
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 10 {
+            // v10: Re-downloads from history — the new task points back at the row it
+            // redoes, and the history row records the task that superseded it
+            conn.execute_batch(
+                "ALTER TABLE history ADD COLUMN superseded_by INTEGER;
+                 ALTER TABLE downloads ADD COLUMN replaces_history_id INTEGER;
+                 ALTER TABLE downloads ADD COLUMN replace_existing INTEGER NOT NULL DEFAULT 0;",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 11 {
+            // v11: Whether chapter markers ended up embedded in the file
+            conn.execute_batch(
+                "ALTER TABLE history ADD COLUMN chapters_embedded INTEGER NOT NULL DEFAULT 0;",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 12 {
+            // v12: Container a download was losslessly remuxed into (`autoContainer`)
+            conn.execute_batch("ALTER TABLE history ADD COLUMN remux_target TEXT;")
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 13 {
+            // v13: Per-task --max-filesize override
+            conn.execute_batch("ALTER TABLE downloads ADD COLUMN max_filesize TEXT;")
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 14 {
+            // v14: Uploader, container and tags, backfilled for old rows by
+            // normalize_history (normalized_at marks rows it has visited)
+            conn.execute_batch(
+                "ALTER TABLE history ADD COLUMN uploader TEXT;
+                 ALTER TABLE history ADD COLUMN container TEXT;
+                 ALTER TABLE history ADD COLUMN tags TEXT;
+                 ALTER TABLE history ADD COLUMN normalized_at INTEGER;
+                 CREATE TABLE IF NOT EXISTS _history_normalize (last_id INTEGER NOT NULL);",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 15 {
+            // v15: Preferred audio language per task, and the one a download ended up with
+            conn.execute_batch(
+                "ALTER TABLE downloads ADD COLUMN audio_lang TEXT;
+                 ALTER TABLE history ADD COLUMN audio_lang TEXT;",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 16 {
+            // v16: Channels/playlists polled for new uploads, and the entries
+            // each one has already seen
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS watched_sources (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    url TEXT NOT NULL UNIQUE,
+                    title TEXT,
+                    interval_secs INTEGER NOT NULL,
+                    created_at INTEGER NOT NULL,
+                    last_checked_at INTEGER,
+                    last_new_count INTEGER NOT NULL DEFAULT 0,
+                    last_error TEXT
+                 );
+                 CREATE TABLE IF NOT EXISTS watched_seen (
+                    source_id INTEGER NOT NULL,
+                    video_id TEXT NOT NULL,
+                    PRIMARY KEY (source_id, video_id)
+                 );",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 17 {
+            // v17: ffprobe verification outcome, and whether a suspect file was re-downloaded
+            conn.execute_batch(
+                "ALTER TABLE history ADD COLUMN verify_status TEXT;
+                 ALTER TABLE history ADD COLUMN verify_error TEXT;
+                 ALTER TABLE downloads ADD COLUMN verify_retried INTEGER NOT NULL DEFAULT 0;",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 18 {
+            // v18: Per-type destination folders of a download (JSON)
+            conn.execute_batch("ALTER TABLE history ADD COLUMN destinations TEXT;")
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 19 {
+            // v19: Saved option presets, per-task embed flags, and the preset a download used
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS presets (
+                    name TEXT PRIMARY KEY,
+                    options TEXT NOT NULL,
+                    created_at INTEGER NOT NULL
+                 );
+                 ALTER TABLE downloads ADD COLUMN embed_metadata INTEGER NOT NULL DEFAULT 0;
+                 ALTER TABLE downloads ADD COLUMN embed_thumbnail INTEGER NOT NULL DEFAULT 0;
+                 ALTER TABLE downloads ADD COLUMN preset TEXT;
+                 ALTER TABLE history ADD COLUMN preset TEXT;",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 20 {
+            // v20: Record live streams from their start
+            conn.execute_batch(
+                "ALTER TABLE downloads ADD COLUMN live_from_start INTEGER NOT NULL DEFAULT 0;",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 21 {
+            // v21: Per-task HTTP headers (JSON)
+            conn.execute_batch("ALTER TABLE downloads ADD COLUMN http_headers TEXT;")
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 22 {
+            // v22: Position of tasks queued from a playlist
+            conn.execute_batch("ALTER TABLE downloads ADD COLUMN playlist_index INTEGER;")
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 23 {
+            // v23: Per-task retry overrides and automatic retry count
+            conn.execute_batch(
+                "ALTER TABLE downloads ADD COLUMN max_retries INTEGER;
+                 ALTER TABLE downloads ADD COLUMN retry_backoff_secs INTEGER;
+                 ALTER TABLE downloads ADD COLUMN retry_count INTEGER NOT NULL DEFAULT 0;",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 24 {
+            // v24: When a task first started, carried into history for wait-time stats
+            conn.execute_batch(
+                "ALTER TABLE downloads ADD COLUMN started_at INTEGER;
+                 ALTER TABLE history ADD COLUMN enqueued_at INTEGER;
+                 ALTER TABLE history ADD COLUMN started_at INTEGER;",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 25 {
+            // v25: First start of each task, counted against the daily download limit.
+            // Kept apart from downloads so clearing the queue doesn't reset the count.
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS download_starts (
+                    task_id INTEGER PRIMARY KEY,
+                    started_at INTEGER NOT NULL
+                 );
+                 CREATE INDEX IF NOT EXISTS idx_download_starts_started_at ON download_starts(started_at);",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 26 {
+            // v26: Normalized title for similar-content matching (see fingerprint.rs)
+            conn.execute_batch("ALTER TABLE history ADD COLUMN fingerprint TEXT;")
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 27 {
+            // v27: Video codec yt-dlp actually downloaded (to confirm codecPreference)
+            conn.execute_batch("ALTER TABLE history ADD COLUMN vcodec TEXT;")
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 28 {
+            // v28: Subtitle-only tasks (`--skip-download --write-subs`)
+            conn.execute_batch(
+                "ALTER TABLE downloads ADD COLUMN subtitles_only INTEGER NOT NULL DEFAULT 0;
+                 ALTER TABLE downloads ADD COLUMN subtitle_langs TEXT;
+                 ALTER TABLE downloads ADD COLUMN convert_subs TEXT;",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 29 {
+            // v29: Bytes downloaded per local day, for the monthly data cap
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS bandwidth_usage (
+                    day TEXT PRIMARY KEY,
+                    bytes INTEGER NOT NULL
+                 );",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 30 {
+            // v30: Video ids the user never wants queued, from any source
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS blocked_video_ids (
+                    video_id TEXT PRIMARY KEY,
+                    blocked_at INTEGER NOT NULL
+                 );",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 31 {
+            // v31: Favorite flag on history rows; the partial index keeps the
+            // favorites-only listing fast on large histories
+            conn.execute_batch(
+                "ALTER TABLE history ADD COLUMN is_favorite INTEGER NOT NULL DEFAULT 0;
+                 CREATE INDEX IF NOT EXISTS idx_history_favorites ON history(downloaded_at) WHERE is_favorite = 1;",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 32 {
+            // v32: Indexes for the sortable history columns (date has one since v2)
+            conn.execute_batch(
+                "CREATE INDEX IF NOT EXISTS idx_history_file_size ON history(file_size);
+                 CREATE INDEX IF NOT EXISTS idx_history_duration_secs ON history(duration_secs);
+                 CREATE INDEX IF NOT EXISTS idx_history_title ON history(title COLLATE NOCASE);",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 33 {
+            // v33: When a watched source's first listing was recorded. Seen rows
+            // can't tell, since that listing may have been empty
+            conn.execute_batch(
+                "ALTER TABLE watched_sources ADD COLUMN baseline_at INTEGER;
+                 UPDATE watched_sources SET baseline_at = COALESCE(last_checked_at, created_at)
+                 WHERE id IN (SELECT DISTINCT source_id FROM watched_seen);",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 34 {
+            // v34: `superseded_by` is set once the re-download completes, not when it
+            // is queued; unlink rows whose re-download hasn't finished
+            conn.execute_batch(
+                "UPDATE history SET superseded_by = NULL
+                 WHERE superseded_by IN (SELECT id FROM downloads WHERE status != 'completed');",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 35 {
+            // v35: Tags the user adds (`bulk_tag`), apart from the site's `tags`
+            conn.execute_batch(
+                "ALTER TABLE history ADD COLUMN user_tags TEXT NOT NULL DEFAULT '[]';",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 36 {
+            // v36: When `backfill_durations` last probed a row, so rows that can't be
+            // probed (file and URL gone) aren't retried on every visit
+            conn.execute_batch("ALTER TABLE history ADD COLUMN duration_probed_at INTEGER;")
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 37 {
+            // v37: normalize_history pages like backfill_durations (normalized_at marks
+            // visited rows), so its cursor table goes; no tags known is '[]', not NULL
+            conn.execute_batch(
+                "DROP TABLE IF EXISTS _history_normalize;
+                 UPDATE history SET tags = '[]' WHERE tags IS NULL;",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        if current < 38 {
+            // v38: User tags on queued tasks, carried over to the history row
+            conn.execute_batch(
+                "ALTER TABLE downloads ADD COLUMN user_tags TEXT NOT NULL DEFAULT '[]';",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
         if current < SCHEMA_VERSION {
             Self::set_schema_version(conn, SCHEMA_VERSION)?;
         }
@@ -99,18 +423,33 @@ impl Database {
         self.conn.lock().unwrap_or_else(|e| e.into_inner())
     }
 
-    /// Delete all data from downloads and history tables (used by factory reset).
+    /// Run SQLite's quick integrity check. Returns "ok" when healthy.
+    pub fn quick_check(&self) -> Result<String, AppError> {
+        let conn = self.conn();
+        conn.query_row("PRAGMA quick_check", [], |row| row.get(0))
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Delete all data from downloads, history and watched-source tables (used by factory reset).
     /// Uses the live connection instead of deleting DB files to avoid stale state.
     pub fn clear_all_data(&self) -> Result<(), AppError> {
         let conn = self.conn();
-        conn.execute_batch("DELETE FROM downloads; DELETE FROM history;")
-            .map_err(|e| AppError::DatabaseError(format!("Failed to clear database: {}", e)))?;
+        conn.execute_batch(
+            "DELETE FROM downloads; DELETE FROM history;
+             DELETE FROM watched_sources; DELETE FROM watched_seen;",
+        )
+        .map_err(|e| AppError::DatabaseError(format!("Failed to clear database: {}", e)))?;
         // Reclaim disk space
         conn.execute_batch("VACUUM;")
             .map_err(|e| AppError::DatabaseError(format!("Failed to vacuum database: {}", e)))?;
         Ok(())
     }
 
+    /// Rebuild the database file to return deleted pages to the OS. Returns bytes freed.
+    pub fn vacuum(&self) -> Result<u64, AppError> {
+        vacuum(&self.conn())
+    }
+
     fn create_tables(conn: &Connection) -> Result<(), AppError> {
         conn.execute(
             "CREATE TABLE IF NOT EXISTS downloads (
@@ -153,4 +492,20 @@ impl Database {
     }
 }
 
+/// VACUUM `conn` and report how much the file shrank.
+pub(crate) fn vacuum(conn: &Connection) -> Result<u64, AppError> {
+    let size = || -> Result<i64, AppError> {
+        conn.query_row(
+            "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+    };
+    let before = size()?;
+    conn.execute_batch("VACUUM;")
+        .map_err(|e| AppError::DatabaseError(format!("Failed to vacuum database: {}", e)))?;
+    Ok((before - size()?).max(0) as u64)
+}
+
 use rusqlite::OptionalExtension;
@@ -0,0 +1,68 @@
+use super::Database;
+use crate::modules::types::AppError;
+use crate::ytdlp::types::{DownloadPreset, PresetOptions};
+use rusqlite::{params, OptionalExtension};
+
+/// User presets; options are stored as JSON.
+impl Database {
+    pub fn save_preset(&self, name: &str, options: &PresetOptions) -> Result<(), AppError> {
+        let json = serde_json::to_string(options).map_err(|e| AppError::Custom(e.to_string()))?;
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO presets (name, options, created_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(name) DO UPDATE SET options = excluded.options",
+            params![name, json, chrono::Utc::now().timestamp()],
+        )
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    pub fn get_preset(&self, name: &str) -> Result<Option<PresetOptions>, AppError> {
+        let conn = self.conn();
+        let json: Option<String> = conn
+            .query_row(
+                "SELECT options FROM presets WHERE name = ?1",
+                [name],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        json.map(|j| serde_json::from_str(&j).map_err(|e| AppError::DatabaseError(e.to_string())))
+            .transpose()
+    }
+
+    pub fn list_presets(&self) -> Result<Vec<DownloadPreset>, AppError> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare("SELECT name, options FROM presets ORDER BY name")
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        // A row that no longer parses is skipped rather than failing the list
+        Ok(rows
+            .into_iter()
+            .filter_map(|(name, json)| {
+                serde_json::from_str(&json)
+                    .ok()
+                    .map(|options| DownloadPreset {
+                        name,
+                        options,
+                        builtin: false,
+                    })
+            })
+            .collect())
+    }
+
+    pub fn delete_preset(&self, name: &str) -> Result<bool, AppError> {
+        let conn = self.conn();
+        let deleted = conn
+            .execute("DELETE FROM presets WHERE name = ?1", [name])
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(deleted > 0)
+    }
+}
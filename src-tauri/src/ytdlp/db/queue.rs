@@ -1,5 +1,7 @@
+use super::history::tags_json;
 use super::Database;
 use crate::modules::types::AppError;
+use crate::ytdlp::credentials;
 use crate::ytdlp::types::*;
 use rusqlite::{params, OptionalExtension};
 
@@ -19,10 +21,49 @@ fn map_download_row(row: &rusqlite::Row) -> rusqlite::Result<DownloadTaskInfo> {
         error_message: row.get(11)?,
         created_at: row.get(12)?,
         completed_at: row.get(13)?,
+        playlist_id: row.get(14)?,
+        has_credentials: row.get(15)?,
+        keep_separate_streams: row.get(16)?,
+        replaces_history_id: row.get(17)?,
+        replace_existing: row.get(18)?,
+        max_filesize: row.get(19)?,
+        audio_lang: row.get(20)?,
+        embed_metadata: row.get(21)?,
+        embed_thumbnail: row.get(22)?,
+        preset: row.get(23)?,
+        live_from_start: row.get(24)?,
+        headers: row
+            .get::<_, Option<String>>(25)?
+            .and_then(|h| serde_json::from_str(&h).ok()),
+        playlist_index: row.get(26)?,
+        max_retries: row.get(27)?,
+        retry_backoff_secs: row.get(28)?,
+        retry_count: row.get(29)?,
+        subtitles_only: row.get(30)?,
+        subtitle_langs: row
+            .get::<_, Option<String>>(31)?
+            .map(|l| l.split(',').map(str::to_string).collect())
+            .unwrap_or_default(),
+        convert_subs: row.get(32)?,
+        user_tags: serde_json::from_str(&row.get::<_, String>(33)?).unwrap_or_default(),
+        retry_policy: None,
+        is_favorite: false,
     })
 }
 
-const DOWNLOAD_COLUMNS: &str = "id, video_url, video_id, title, format_id, quality_label, output_path, status, progress, speed, eta, error_message, created_at, completed_at";
+/// Per-task headers as stored; none or empty ones as NULL.
+fn headers_json(headers: Option<&RequestHeaders>) -> Option<String> {
+    headers
+        .filter(|h| **h != RequestHeaders::default())
+        .and_then(|h| serde_json::to_string(h).ok())
+}
+
+/// Subtitle languages as stored: comma-joined, none as NULL.
+fn subtitle_langs_csv(langs: &[String]) -> Option<String> {
+    (!langs.is_empty()).then(|| langs.join(","))
+}
+
+const DOWNLOAD_COLUMNS: &str = "id, video_url, video_id, title, format_id, quality_label, output_path, status, progress, speed, eta, error_message, created_at, completed_at, playlist_id, has_credentials, keep_separate_streams, replaces_history_id, replace_existing, max_filesize, audio_lang, embed_metadata, embed_thumbnail, preset, live_from_start, http_headers, playlist_index, max_retries, retry_backoff_secs, retry_count, subtitles_only, subtitle_langs, convert_subs, user_tags";
 
 impl Database {
     pub fn insert_download(
@@ -34,8 +75,8 @@ impl Database {
         let created_at = chrono::Utc::now().timestamp();
 
         conn.execute(
-            "INSERT INTO downloads (video_url, video_id, title, format_id, quality_label, output_path, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            "INSERT INTO downloads (video_url, video_id, title, format_id, quality_label, output_path, created_at, playlist_id, has_credentials, keep_separate_streams, max_filesize, audio_lang, embed_metadata, embed_thumbnail, preset, live_from_start, http_headers, playlist_index, max_retries, retry_backoff_secs, subtitles_only, subtitle_langs, convert_subs, user_tags)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24)",
             params![
                 req.video_url,
                 req.video_id,
@@ -44,12 +85,57 @@ impl Database {
                 req.quality_label,
                 output_path,
                 created_at,
+                req.playlist_id,
+                req.credentials.as_ref().is_some_and(credentials::has_any),
+                req.keep_separate_streams,
+                req.max_filesize,
+                req.audio_lang,
+                req.embed_metadata,
+                req.embed_thumbnail,
+                req.preset,
+                req.live_from_start,
+                headers_json(req.headers.as_ref()),
+                req.playlist_index,
+                req.max_retries,
+                req.retry_backoff_secs,
+                req.subtitles_only,
+                subtitle_langs_csv(&req.subtitle_langs),
+                req.convert_subs,
+                tags_json(&req.tags),
             ],
         ).map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
         Ok(conn.last_insert_rowid() as u64)
     }
 
+    /// Mark a queued task as a re-download of a history row. The row is linked to
+    /// the task (`superseded_by`) by `complete_and_record` once the task succeeds.
+    pub fn link_redownload(
+        &self,
+        task_id: u64,
+        history_id: u64,
+        replace_existing: bool,
+    ) -> Result<(), AppError> {
+        let conn = self.conn();
+        conn.execute(
+            "UPDATE downloads SET replaces_history_id = ?1, replace_existing = ?2 WHERE id = ?3",
+            params![history_id, replace_existing, task_id],
+        )
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Let the task write over a file already at its output path.
+    pub fn set_replace_existing(&self, task_id: u64) -> Result<(), AppError> {
+        let conn = self.conn();
+        conn.execute(
+            "UPDATE downloads SET replace_existing = 1 WHERE id = ?1",
+            params![task_id],
+        )
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
     /// Insert multiple downloads in a single transaction for batch/playlist operations.
     pub fn insert_downloads_batch(
         &self,
@@ -65,8 +151,8 @@ impl Database {
 
         for (req, output_path) in items {
             tx.execute(
-                "INSERT INTO downloads (video_url, video_id, title, format_id, quality_label, output_path, created_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                "INSERT INTO downloads (video_url, video_id, title, format_id, quality_label, output_path, created_at, playlist_id, has_credentials, keep_separate_streams, max_filesize, audio_lang, embed_metadata, embed_thumbnail, preset, live_from_start, http_headers, playlist_index, max_retries, retry_backoff_secs, subtitles_only, subtitle_langs, convert_subs, user_tags)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24)",
                 params![
                     req.video_url,
                     req.video_id,
@@ -75,6 +161,23 @@ impl Database {
                     req.quality_label,
                     output_path,
                     created_at,
+                    req.playlist_id,
+                    req.credentials.as_ref().is_some_and(credentials::has_any),
+                    req.keep_separate_streams,
+                    req.max_filesize,
+                    req.audio_lang,
+                    req.embed_metadata,
+                    req.embed_thumbnail,
+                    req.preset,
+                    req.live_from_start,
+                    headers_json(req.headers.as_ref()),
+                    req.playlist_index,
+                    req.max_retries,
+                    req.retry_backoff_secs,
+                    req.subtitles_only,
+                    subtitle_langs_csv(&req.subtitle_langs),
+                    req.convert_subs,
+                    tags_json(&req.tags),
                 ],
             )
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
@@ -95,9 +198,17 @@ impl Database {
     ) -> Result<(), AppError> {
         let conn = self.conn();
 
+        // started_at keeps the first start so retries don't hide the initial wait
         conn.execute(
-            "UPDATE downloads SET status = ?1, error_message = ?2 WHERE id = ?3",
-            params![status.to_string(), error_msg, id],
+            "UPDATE downloads SET status = ?1, error_message = ?2,
+                 started_at = CASE WHEN ?1 = 'downloading' THEN COALESCE(started_at, ?4) ELSE started_at END
+             WHERE id = ?3",
+            params![
+                status.to_string(),
+                error_msg,
+                id,
+                chrono::Utc::now().timestamp()
+            ],
         )
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
@@ -154,6 +265,22 @@ impl Database {
         Ok(tasks)
     }
 
+    /// Finished tasks (removable by clear_completed) that still hold credentials.
+    pub fn get_finished_credential_ids(&self) -> Result<Vec<u64>, AppError> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare(
+                "SELECT id FROM downloads WHERE has_credentials = 1 AND status IN ('completed', 'cancelled', 'failed')",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let ids = stmt
+            .query_map([], |row| row.get(0))
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .collect::<Result<Vec<u64>, _>>()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(ids)
+    }
+
     pub fn clear_completed(&self) -> Result<u32, AppError> {
         let conn = self.conn();
 
@@ -196,6 +323,92 @@ impl Database {
         Ok(())
     }
 
+    /// Claim the one automatic re-download of a suspect file. False if it was used already.
+    pub fn claim_verify_retry(&self, id: u64) -> Result<bool, AppError> {
+        let conn = self.conn();
+        let changed = conn
+            .execute(
+                "UPDATE downloads SET verify_retried = 1 WHERE id = ?1 AND verify_retried = 0",
+                [id],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(changed > 0)
+    }
+
+    /// Give a playlist's not-yet-downloaded tasks consecutive positions, after
+    /// the highest one already downloading or downloaded. Returns the changes.
+    pub fn renumber_playlist(
+        &self,
+        playlist_id: &str,
+    ) -> Result<Vec<PlaylistIndexChange>, AppError> {
+        let mut conn = self.conn();
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let rows: Vec<(u64, String, DownloadStatus, Option<u32>)> = {
+            let mut stmt = tx
+                .prepare("SELECT id, title, status, playlist_index FROM downloads WHERE playlist_id = ?1")
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            let rows = stmt
+                .query_map(params![playlist_id], |row| {
+                    Ok((
+                        row.get(0)?,
+                        row.get(1)?,
+                        DownloadStatus::parse(&row.get::<_, String>(2)?),
+                        row.get(3)?,
+                    ))
+                })
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            rows.collect::<Result<_, _>>()
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        };
+
+        // Files already (being) written keep their numbers; cancelled tasks take none
+        let first = rows
+            .iter()
+            .filter(|r| matches!(r.2, DownloadStatus::Downloading | DownloadStatus::Completed))
+            .filter_map(|r| r.3)
+            .max()
+            .map_or(1, |n| n + 1);
+        let movable: Vec<&(u64, String, DownloadStatus, Option<u32>)> = rows
+            .iter()
+            .filter(|r| {
+                matches!(
+                    r.2,
+                    DownloadStatus::Pending | DownloadStatus::Paused | DownloadStatus::Failed
+                )
+            })
+            .collect();
+        let order: Vec<(u64, Option<u32>)> = movable.iter().map(|r| (r.0, r.3)).collect();
+
+        let mut changes = Vec::new();
+        for (id, new_index) in crate::ytdlp::playlist_index::renumber(first, &order) {
+            let Some(row) = movable.iter().find(|r| r.0 == id) else {
+                continue;
+            };
+            // Guard against a task that started since it was read
+            let updated = tx
+                .execute(
+                    "UPDATE downloads SET playlist_index = ?1 WHERE id = ?2 AND status IN ('pending', 'paused', 'failed')",
+                    params![new_index, id],
+                )
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            if updated > 0 {
+                changes.push(PlaylistIndexChange {
+                    task_id: id,
+                    title: row.1.clone(),
+                    old_index: row.3,
+                    new_index,
+                });
+            }
+        }
+
+        tx.commit()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(changes)
+    }
+
     pub fn complete_and_record(
         &self,
         id: u64,
@@ -214,8 +427,8 @@ impl Database {
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
         tx.execute(
-            "INSERT INTO history (video_url, video_id, title, quality_label, format, file_path, file_size, downloaded_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            "INSERT INTO history (video_url, video_id, title, quality_label, format, file_path, file_size, downloaded_at, recode_target, max_height, duration_secs, chapters_embedded, remux_target, uploader, container, tags, audio_lang, verify_status, verify_error, destinations, preset, vcodec, user_tags)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23)",
             params![
                 history.video_url,
                 history.video_id,
@@ -225,9 +438,41 @@ impl Database {
                 history.file_path,
                 history.file_size,
                 history.downloaded_at,
+                history.recode_target,
+                history.max_height,
+                history.duration_secs,
+                history.chapters_embedded,
+                history.remux_target,
+                history.uploader,
+                history.container,
+                tags_json(&history.tags),
+                history.audio_lang,
+                history.verify_status,
+                history.verify_error,
+                history.destinations
+                    .as_ref()
+                    .and_then(|d| serde_json::to_string(d).ok()),
+                history.preset,
+                history.vcodec,
+                tags_json(&history.user_tags),
             ],
         )
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        tx.execute(
+            "UPDATE history SET
+                 enqueued_at = (SELECT created_at FROM downloads WHERE id = ?1),
+                 started_at = (SELECT started_at FROM downloads WHERE id = ?1)
+             WHERE id = ?2",
+            params![id, tx.last_insert_rowid()],
+        )
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        // A re-download supersedes the row it redid only once it has succeeded
+        tx.execute(
+            "UPDATE history SET superseded_by = ?1
+             WHERE id = (SELECT replaces_history_id FROM downloads WHERE id = ?1)",
+            params![id],
+        )
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
         tx.commit()
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
@@ -256,6 +501,90 @@ impl Database {
     /// Atomically claim the next pending download by setting its status to 'downloading'
     /// in a single SQL statement. Returns the claimed task or None if no pending tasks exist.
     /// This prevents the race condition where two concurrent callers could claim the same task.
+    /// Count one more automatic retry of a task. Returns the new count.
+    pub fn bump_retry_count(&self, id: u64) -> Result<u32, AppError> {
+        self.conn()
+            .query_row(
+                "UPDATE downloads SET retry_count = retry_count + 1 WHERE id = ?1 RETURNING retry_count",
+                params![id],
+                |row| row.get(0),
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    pub fn reset_retry_count(&self, id: u64) -> Result<(), AppError> {
+        self.conn()
+            .execute(
+                "UPDATE downloads SET retry_count = 0 WHERE id = ?1",
+                params![id],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Put a failed task back in the queue. False when it is no longer failed
+    /// (retried by hand, cancelled or removed meanwhile).
+    pub fn requeue_failed(&self, id: u64) -> Result<bool, AppError> {
+        let updated = self
+            .conn()
+            .execute(
+                "UPDATE downloads SET status = 'pending', error_message = NULL, progress = 0 WHERE id = ?1 AND status = 'failed'",
+                params![id],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(updated > 0)
+    }
+
+    /// Store retry overrides of a task that hasn't finished. False when it has.
+    pub fn set_retry_policy(
+        &self,
+        id: u64,
+        max_retries: Option<u32>,
+        retry_backoff_secs: Option<u32>,
+    ) -> Result<bool, AppError> {
+        let updated = self
+            .conn()
+            .execute(
+                "UPDATE downloads SET max_retries = ?1, retry_backoff_secs = ?2 WHERE id = ?3 AND status NOT IN ('completed', 'cancelled')",
+                params![max_retries, retry_backoff_secs, id],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(updated > 0)
+    }
+
+    /// Record the first start of a task. False when it started before (a retry).
+    pub fn record_start(&self, task_id: u64) -> Result<bool, AppError> {
+        let inserted = self
+            .conn()
+            .execute(
+                "INSERT OR IGNORE INTO download_starts (task_id, started_at) VALUES (?1, ?2)",
+                params![task_id, chrono::Utc::now().timestamp()],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(inserted > 0)
+    }
+
+    pub fn starts_since(&self, since: i64) -> Result<u32, AppError> {
+        self.conn()
+            .query_row(
+                "SELECT COUNT(*) FROM download_starts WHERE started_at >= ?1",
+                params![since],
+                |row| row.get(0),
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    pub fn prune_download_starts(&self, before: i64) -> Result<u64, AppError> {
+        let deleted = self
+            .conn()
+            .execute(
+                "DELETE FROM download_starts WHERE started_at < ?1",
+                params![before],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(deleted as u64)
+    }
+
     pub fn claim_next_pending(&self) -> Result<Option<DownloadTaskInfo>, AppError> {
         // Scope the MutexGuard so it is dropped before calling get_download(),
         // which also acquires the same Mutex. std::sync::Mutex is non-reentrant,
@@ -263,10 +592,10 @@ impl Database {
         let claimed_id: Option<u64> = {
             let conn = self.conn();
             conn.query_row(
-                "UPDATE downloads SET status = 'downloading'
+                "UPDATE downloads SET status = 'downloading', started_at = COALESCE(started_at, ?1)
                  WHERE id = (SELECT id FROM downloads WHERE status = 'pending' ORDER BY created_at ASC LIMIT 1)
                  RETURNING id",
-                [],
+                [chrono::Utc::now().timestamp()],
                 |row| row.get(0),
             )
             .optional()
@@ -291,6 +620,18 @@ impl Database {
         Ok(count)
     }
 
+    pub fn get_pending_count(&self) -> Result<u32, AppError> {
+        let conn = self.conn();
+        let count: u32 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM downloads WHERE status = 'pending'",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(count)
+    }
+
     pub fn get_cancellable_ids(&self) -> Result<Vec<u64>, AppError> {
         let conn = self.conn();
         let mut stmt = conn
@@ -306,6 +647,35 @@ impl Database {
         Ok(ids)
     }
 
+    /// Get pending/downloading tasks with the fields used by filtered cancel:
+    /// (id, video_url, playlist_id, user_tags).
+    #[allow(clippy::type_complexity)]
+    pub fn get_cancellable_tasks(
+        &self,
+    ) -> Result<Vec<(u64, String, Option<String>, Vec<String>)>, AppError> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, video_url, playlist_id, user_tags FROM downloads WHERE status IN ('downloading', 'pending')",
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let tasks = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    serde_json::from_str(&row.get::<_, String>(3)?).unwrap_or_default(),
+                ))
+            })
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(tasks)
+    }
+
     /// Reset downloads that were left in 'downloading' state from a previous session.
     /// Called on app startup to clean up stale state after unexpected shutdown.
     pub fn reset_stale_downloads(&self) -> Result<u32, AppError> {
@@ -484,6 +854,79 @@ impl Database {
         })
     }
 
+    /// Per-status counts plus up to `sample_limit` tasks per status, in two queries.
+    /// Active statuses sample oldest first (queue order); finished ones newest first.
+    pub fn get_queue_grouped(&self, sample_limit: u32) -> Result<QueueGrouped, AppError> {
+        let conn = self.conn();
+
+        let mut count_stmt = conn
+            .prepare("SELECT status, COUNT(*) FROM downloads GROUP BY status")
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let counts = count_stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, u64>(1)?))
+            })
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let mut sample_stmt = conn
+            .prepare(&format!(
+                "SELECT {cols} FROM (
+                    SELECT {cols}, ROW_NUMBER() OVER (
+                        PARTITION BY status
+                        ORDER BY CASE WHEN status IN ('pending', 'downloading', 'paused')
+                                      THEN created_at
+                                      ELSE -COALESCE(completed_at, created_at) END,
+                                 id
+                    ) AS rn
+                    FROM downloads
+                ) WHERE rn <= ?1 ORDER BY status, rn",
+                cols = DOWNLOAD_COLUMNS
+            ))
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let samples = sample_stmt
+            .query_map(params![sample_limit], map_download_row)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let statuses = [
+            DownloadStatus::Pending,
+            DownloadStatus::Downloading,
+            DownloadStatus::Paused,
+            DownloadStatus::Failed,
+            DownloadStatus::Completed,
+            DownloadStatus::Cancelled,
+        ];
+        let groups = statuses
+            .into_iter()
+            .map(|status| {
+                let key = status.to_string();
+                let count = counts
+                    .iter()
+                    .find(|(s, _)| *s == key)
+                    .map(|(_, c)| *c)
+                    .unwrap_or(0);
+                let items = samples
+                    .iter()
+                    .filter(|t| t.status.to_string() == key)
+                    .cloned()
+                    .collect();
+                QueueGroup {
+                    status,
+                    count,
+                    items,
+                }
+            })
+            .collect();
+
+        Ok(QueueGrouped {
+            groups,
+            total_count: counts.iter().map(|(_, c)| c).sum(),
+        })
+    }
+
     pub fn get_active_downloads(&self) -> Result<Vec<DownloadTaskInfo>, AppError> {
         let conn = self.conn();
         let mut stmt = conn
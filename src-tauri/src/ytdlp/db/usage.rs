@@ -0,0 +1,29 @@
+use super::Database;
+use crate::modules::types::AppError;
+use rusqlite::params;
+
+/// Bytes downloaded per local day ("YYYY-MM-DD"), for `get_bandwidth_usage`.
+impl Database {
+    pub fn add_bandwidth(&self, day: &str, bytes: u64) -> Result<(), AppError> {
+        self.conn()
+            .execute(
+                "INSERT INTO bandwidth_usage (day, bytes) VALUES (?1, ?2)
+                 ON CONFLICT(day) DO UPDATE SET bytes = bytes + excluded.bytes",
+                params![day, bytes],
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Total over the days starting with `prefix`: a day, a month ("YYYY-MM") or
+    /// everything ("").
+    pub fn bandwidth_bytes(&self, prefix: &str) -> Result<u64, AppError> {
+        self.conn()
+            .query_row(
+                "SELECT COALESCE(SUM(bytes), 0) FROM bandwidth_usage WHERE day LIKE ?1 || '%'",
+                params![prefix],
+                |row| row.get(0),
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+}
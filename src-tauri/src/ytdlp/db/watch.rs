@@ -0,0 +1,174 @@
+use super::Database;
+use crate::modules::types::AppError;
+use crate::ytdlp::types::WatchedSource;
+use rusqlite::{params, OptionalExtension};
+
+const WATCH_COLUMNS: &str =
+    "id, url, title, interval_secs, created_at, last_checked_at, last_new_count, last_error";
+
+fn map_watch_row(row: &rusqlite::Row) -> rusqlite::Result<WatchedSource> {
+    Ok(WatchedSource {
+        id: row.get(0)?,
+        url: row.get(1)?,
+        title: row.get(2)?,
+        interval_secs: row.get(3)?,
+        created_at: row.get(4)?,
+        last_checked_at: row.get(5)?,
+        last_new_count: row.get(6)?,
+        last_error: row.get(7)?,
+    })
+}
+
+impl Database {
+    /// Register a source, or update the interval of one already watched.
+    pub fn upsert_watched_source(
+        &self,
+        url: &str,
+        interval_secs: u64,
+    ) -> Result<WatchedSource, AppError> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO watched_sources (url, interval_secs, created_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(url) DO UPDATE SET interval_secs = excluded.interval_secs",
+            params![url, interval_secs, chrono::Utc::now().timestamp()],
+        )
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        conn.query_row(
+            &format!(
+                "SELECT {} FROM watched_sources WHERE url = ?1",
+                WATCH_COLUMNS
+            ),
+            [url],
+            map_watch_row,
+        )
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    pub fn get_watched_source(&self, id: u64) -> Result<Option<WatchedSource>, AppError> {
+        let conn = self.conn();
+        conn.query_row(
+            &format!(
+                "SELECT {} FROM watched_sources WHERE id = ?1",
+                WATCH_COLUMNS
+            ),
+            [id],
+            map_watch_row,
+        )
+        .optional()
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    pub fn list_watched_sources(&self) -> Result<Vec<WatchedSource>, AppError> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM watched_sources ORDER BY id",
+                WATCH_COLUMNS
+            ))
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let rows = stmt
+            .query_map([], map_watch_row)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Sources never checked, or whose interval has elapsed since the last check.
+    pub fn due_watched_sources(&self, now: i64) -> Result<Vec<WatchedSource>, AppError> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM watched_sources
+                 WHERE last_checked_at IS NULL OR last_checked_at + interval_secs <= ?1
+                 ORDER BY COALESCE(last_checked_at, 0), id",
+                WATCH_COLUMNS
+            ))
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let rows = stmt
+            .query_map([now], map_watch_row)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Remove a source and its seen entries. Returns false if it did not exist.
+    pub fn delete_watched_source(&self, id: u64) -> Result<bool, AppError> {
+        let conn = self.conn();
+        conn.execute("DELETE FROM watched_seen WHERE source_id = ?1", [id])
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let deleted = conn
+            .execute("DELETE FROM watched_sources WHERE id = ?1", [id])
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(deleted > 0)
+    }
+
+    pub fn has_watch_baseline(&self, source_id: u64) -> Result<bool, AppError> {
+        let conn = self.conn();
+        conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM watched_sources WHERE id = ?1 AND baseline_at IS NOT NULL)",
+            [source_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Mark the source's first listing as recorded, even when it had no entries.
+    pub fn set_watch_baseline(&self, source_id: u64) -> Result<(), AppError> {
+        let conn = self.conn();
+        conn.execute(
+            "UPDATE watched_sources SET baseline_at = ?1 WHERE id = ?2 AND baseline_at IS NULL",
+            params![chrono::Utc::now().timestamp(), source_id],
+        )
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Record entries as seen and return the ids that were not seen before.
+    pub fn mark_watch_seen(
+        &self,
+        source_id: u64,
+        video_ids: &[String],
+    ) -> Result<Vec<String>, AppError> {
+        let mut conn = self.conn();
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let mut fresh = Vec::new();
+        {
+            let mut stmt = tx
+                .prepare("INSERT OR IGNORE INTO watched_seen (source_id, video_id) VALUES (?1, ?2)")
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            for video_id in video_ids {
+                let inserted = stmt
+                    .execute(params![source_id, video_id])
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                if inserted > 0 {
+                    fresh.push(video_id.clone());
+                }
+            }
+        }
+        tx.commit()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(fresh)
+    }
+
+    /// Store the outcome of a check. A failed check keeps the previous title.
+    pub fn record_watch_check(
+        &self,
+        id: u64,
+        title: Option<&str>,
+        new_count: u32,
+        error: Option<&str>,
+    ) -> Result<(), AppError> {
+        let conn = self.conn();
+        conn.execute(
+            "UPDATE watched_sources
+             SET last_checked_at = ?1, title = COALESCE(?2, title), last_new_count = ?3,
+                 last_error = ?4
+             WHERE id = ?5",
+            params![chrono::Utc::now().timestamp(), title, new_count, error, id],
+        )
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+//! The system's default web browser, under the names `get_available_browsers`
+//! uses, so the cookies dropdown can suggest it and start out on it.
+//!
+//! Read from the https handler: the `UserChoice` ProgId in the registry on
+//! Windows, the LaunchServices handlers on macOS (Safari when none is set) and
+//! `xdg-settings` on Linux. Anything that can't be read or mapped gives None.
+
+use std::process::Command;
+use std::sync::OnceLock;
+
+/// Substrings of handler ids (lower-cased) and the browser name they map to.
+/// Chromium forks come before "chrome", which their ids often contain.
+const HANDLERS: &[(&str, &str)] = &[
+    ("edge", "edge"),
+    ("msedge", "edge"),
+    ("brave", "brave"),
+    ("chromium", "chromium"),
+    ("chrome", "chrome"),
+    ("firefox", "firefox"),
+    ("mozilla", "firefox"),
+    ("safari", "safari"),
+];
+
+/// Map a desktop file, bundle id or ProgId to a browser name.
+pub fn normalize(handler: &str) -> Option<&'static str> {
+    let lower = handler.trim().to_ascii_lowercase();
+    if lower.is_empty() {
+        return None;
+    }
+    HANDLERS
+        .iter()
+        .find(|(pattern, _)| lower.contains(pattern))
+        .map(|(_, name)| *name)
+}
+
+/// ProgId from `reg query ...\UserChoice /v ProgId` output.
+fn parse_reg_prog_id(output: &str) -> Option<&str> {
+    output.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        (parts.next() == Some("ProgId") && parts.next() == Some("REG_SZ"))
+            .then(|| parts.next())
+            .flatten()
+    })
+}
+
+/// Bundle id handling https from `defaults read ... LSHandlers` output.
+fn parse_launch_services(output: &str) -> Option<String> {
+    // One `{ ... }` dictionary per handler
+    output.split('}').find_map(|entry| {
+        if !entry.contains("LSHandlerURLScheme = https;") {
+            return None;
+        }
+        entry.lines().find_map(|line| {
+            line.trim()
+                .strip_prefix("LSHandlerRoleAll = ")
+                .map(|id| id.trim_end_matches(';').trim_matches('"').to_string())
+        })
+    })
+}
+
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let mut cmd = Command::new(program);
+    cmd.args(args);
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+    let output = cmd.output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Default browser, or None when it can't be determined.
+pub fn detect() -> Option<String> {
+    let handler = if cfg!(target_os = "windows") {
+        let output = run(
+            "reg",
+            &[
+                "query",
+                r"HKCU\Software\Microsoft\Windows\Shell\Associations\UrlAssociations\https\UserChoice",
+                "/v",
+                "ProgId",
+            ],
+        )?;
+        parse_reg_prog_id(&output)?.to_string()
+    } else if cfg!(target_os = "macos") {
+        run(
+            "defaults",
+            &[
+                "read",
+                "com.apple.LaunchServices/com.apple.launchservices.secure",
+                "LSHandlers",
+            ],
+        )
+        .and_then(|output| parse_launch_services(&output))
+        // Nothing registered means the system default
+        .unwrap_or_else(|| "com.apple.safari".to_string())
+    } else {
+        run("xdg-settings", &["get", "default-web-browser"])?
+    };
+    normalize(&handler).map(String::from)
+}
+
+/// The default browser when it's one `get_available_browsers` lists, detected
+/// once per run. Used as `cookie_browser` until the user picks one.
+pub fn initial_cookie_browser() -> Option<String> {
+    static INITIAL: OnceLock<Option<String>> = OnceLock::new();
+    INITIAL
+        .get_or_init(|| {
+            detect().filter(|browser| {
+                crate::ytdlp::commands::get_available_browsers().contains(browser)
+            })
+        })
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_platform_handler_ids() {
+        assert_eq!(normalize("google-chrome.desktop\n"), Some("chrome"));
+        assert_eq!(normalize("ChromeHTML"), Some("chrome"));
+        assert_eq!(normalize("com.google.chrome"), Some("chrome"));
+        assert_eq!(normalize("chromium-browser.desktop"), Some("chromium"));
+        assert_eq!(normalize("FirefoxURL-308046B0AF4A39CB"), Some("firefox"));
+        assert_eq!(normalize("org.mozilla.firefox"), Some("firefox"));
+        assert_eq!(normalize("MSEdgeHTM"), Some("edge"));
+        assert_eq!(normalize("com.microsoft.edgemac"), Some("edge"));
+        assert_eq!(normalize("BraveHTML"), Some("brave"));
+        assert_eq!(normalize("com.apple.Safari"), Some("safari"));
+        assert_eq!(normalize("org.gnome.Epiphany.desktop"), None);
+        assert_eq!(normalize(""), None);
+    }
+
+    #[test]
+    fn parses_registry_and_launch_services_output() {
+        let reg =
+            "\r\nHKEY_CURRENT_USER\\...\\UserChoice\r\n    ProgId    REG_SZ    MSEdgeHTM\r\n\r\n";
+        assert_eq!(parse_reg_prog_id(reg), Some("MSEdgeHTM"));
+
+        let ls = r#"(
+        {
+        LSHandlerContentType = "public.html";
+        LSHandlerRoleAll = "com.apple.safari";
+    },
+        {
+        LSHandlerPreferredVersions =         {
+            LSHandlerRoleAll = "-";
+        };
+        LSHandlerRoleAll = "org.mozilla.firefox";
+        LSHandlerURLScheme = https;
+    }
+)"#;
+        assert_eq!(
+            parse_launch_services(ls).as_deref(),
+            Some("org.mozilla.firefox")
+        );
+    }
+}
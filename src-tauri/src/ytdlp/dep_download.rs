@@ -3,13 +3,12 @@ use crate::modules::types::AppError;
 use futures_util::StreamExt;
 use sha2::{Digest, Sha256};
 use std::path::{Path, PathBuf};
-use tauri::{AppHandle, Emitter, Manager};
+use tauri::{AppHandle, Emitter};
 
-/// Ensure the `app_data_dir/bin/` directory exists and return its path.
+/// Ensure the `<data dir>/bin/` directory exists and return its path.
 pub fn ensure_bin_dir(app: &AppHandle) -> Result<PathBuf, AppError> {
-    let app_data = app.path().app_data_dir().map_err(|e| {
-        AppError::DependencyInstallError(format!("Failed to get app data dir: {}", e))
-    })?;
+    let app_data = crate::modules::data_dir::resolve(app)
+        .map_err(|e| AppError::DependencyInstallError(e.to_string()))?;
     let bin_dir = app_data.join("bin");
     std::fs::create_dir_all(&bin_dir).map_err(|e| {
         AppError::DependencyInstallError(format!("Failed to create bin dir: {}", e))
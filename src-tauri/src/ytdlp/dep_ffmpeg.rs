@@ -1,8 +1,19 @@
 use super::dep_download::*;
 use super::types::DepInstallStage;
+use crate::modules::logger;
 use crate::modules::types::AppError;
+use std::path::{Path, PathBuf};
 use tauri::AppHandle;
 
+/// Marker next to the binaries holding the pinned build ("7.1@autobuild-…", or a
+/// macOS release tag); absent when ffmpeg follows the latest build
+const PIN_FILE: &str = "ffmpeg.pinned";
+/// Dated BtbN releases; unlike "latest" their assets never change
+const BTBN_BUILD_PREFIX: &str = "autobuild-";
+const BTBN_RELEASES_API: &str = "https://api.github.com/repos/BtbN/FFmpeg-Builds/releases";
+/// Suffix of the copies `rollback_ffmpeg` restores
+const BACKUP_SUFFIX: &str = ".bak";
+
 /// Get ffmpeg download URL and archive format for the current platform.
 fn get_download_info() -> Result<(&'static str, ArchiveFormat), AppError> {
     if cfg!(target_os = "macos") {
@@ -53,10 +64,324 @@ fn get_binary_names() -> &'static [&'static str] {
     }
 }
 
-/// Install ffmpeg by downloading from GitHub.
+/// Install ffmpeg by downloading from GitHub: the pinned build when there is a
+/// pin (`unpin_ffmpeg` goes back to the latest), else the latest one.
 pub async fn install_ffmpeg(app: &AppHandle) -> Result<String, AppError> {
-    let bin_dir = ensure_bin_dir(app)?;
+    if let Some(pin) = pinned_version(app) {
+        logger::info_cat(
+            "dependency",
+            &format!("ffmpeg is pinned to {}; reinstalling that build", pin),
+        );
+        let (url, format, _) = get_versioned_download_info(&pin).await?;
+        return install_archive(app, &url, format).await;
+    }
     let (url, format) = get_download_info()?;
+    install_archive(app, url, format).await
+}
+
+/// Drop the pin so installs and updates follow the latest build again. The
+/// installed binaries stay until the next install.
+pub async fn unpin_ffmpeg(app: &AppHandle) -> Result<(), AppError> {
+    match tokio::fs::remove_file(ensure_bin_dir(app)?.join(PIN_FILE)).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(AppError::FileError(format!(
+            "Failed to remove ffmpeg pin: {}",
+            e
+        ))),
+        _ => {
+            logger::info_cat("dependency", "ffmpeg unpinned; following the latest build");
+            Ok(())
+        }
+    }
+}
+
+/// A pinned version as the mirror names it: a BtbN release branch ("7.1"), or a
+/// release tag of the macOS builds.
+fn validate_version(version: &str, release_branch: bool) -> Result<&str, AppError> {
+    let version = version.trim();
+    let valid = if release_branch {
+        let mut parts = version.split('.');
+        let numeric = |p: Option<&str>| {
+            p.is_some_and(|p| {
+                !p.is_empty() && p.len() <= 3 && p.chars().all(|c| c.is_ascii_digit())
+            })
+        };
+        numeric(parts.next()) && numeric(parts.next()) && parts.next().is_none()
+    } else {
+        (1..=32).contains(&version.len())
+            && version
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+    };
+    if valid {
+        Ok(version)
+    } else {
+        Err(AppError::DependencyInstallError(format!(
+            "Invalid ffmpeg version: {} (expected {})",
+            version,
+            if release_branch {
+                "a release branch like 7.1"
+            } else {
+                "a release tag"
+            }
+        )))
+    }
+}
+
+/// A dated BtbN release tag ("autobuild-2024-10-31-12-55").
+fn validate_build_tag(tag: &str) -> Result<&str, AppError> {
+    let valid = tag.strip_prefix(BTBN_BUILD_PREFIX).is_some_and(|stamp| {
+        (10..=24).contains(&stamp.len()) && stamp.chars().all(|c| c.is_ascii_digit() || c == '-')
+    });
+    if valid {
+        Ok(tag)
+    } else {
+        Err(AppError::DependencyInstallError(format!(
+            "Invalid ffmpeg build: {} (expected {}<date>)",
+            tag, BTBN_BUILD_PREFIX
+        )))
+    }
+}
+
+/// Split a BtbN pin into its release branch and, once resolved, its dated release.
+fn parse_pin(pin: &str) -> (&str, Option<&str>) {
+    match pin.trim().split_once('@') {
+        Some((branch, tag)) => (branch, Some(tag)),
+        None => (pin.trim(), None),
+    }
+}
+
+/// BtbN build target and archive of the current platform.
+fn btbn_target() -> (&'static str, &'static str, ArchiveFormat) {
+    if cfg!(target_os = "windows") {
+        ("win64", "zip", ArchiveFormat::Zip)
+    } else if cfg!(target_arch = "aarch64") {
+        ("linuxarm64", "tar.xz", ArchiveFormat::TarXz)
+    } else {
+        ("linux64", "tar.xz", ArchiveFormat::TarXz)
+    }
+}
+
+/// Download URL of the release branch `branch` build for `target` in a BtbN
+/// release (GitHub API JSON). Assets are named
+/// `ffmpeg-n7.1-<n>-g<hash>-win64-gpl-7.1.zip`; the branch closes the name.
+fn btbn_asset(
+    release: &serde_json::Value,
+    branch: &str,
+    target: &str,
+    ext: &str,
+) -> Option<String> {
+    let suffix = format!("-{}-gpl-{}.{}", target, branch, ext);
+    release["assets"].as_array()?.iter().find_map(|asset| {
+        let name = asset["name"].as_str()?;
+        (name.starts_with("ffmpeg-n") && name.ends_with(&suffix))
+            .then(|| asset["browser_download_url"].as_str().map(String::from))
+            .flatten()
+    })
+}
+
+async fn github_json(url: &str) -> Result<serde_json::Value, AppError> {
+    let resp = reqwest::Client::new()
+        .get(url)
+        .header("User-Agent", "modern-ytdlp-gui")
+        .send()
+        .await
+        .map_err(|e| AppError::NetworkError(format!("Failed to reach the ffmpeg mirror: {}", e)))?;
+    if !resp.status().is_success() {
+        return Err(AppError::NetworkError(format!(
+            "ffmpeg mirror answered HTTP {}",
+            resp.status()
+        )));
+    }
+    resp.json()
+        .await
+        .map_err(|e| AppError::NetworkError(format!("Failed to parse response: {}", e)))
+}
+
+/// Resolve a BtbN pin to the asset URL of one dated release. A bare branch
+/// resolves to the newest dated release that built it; the returned pin names
+/// that release so later installs get the same files.
+async fn resolve_btbn(pin: &str) -> Result<(String, ArchiveFormat, String), AppError> {
+    let (branch, tag) = parse_pin(pin);
+    let branch = validate_version(branch, true)?;
+    let (target, ext, format) = btbn_target();
+    let found = match tag {
+        Some(tag) => {
+            let tag = validate_build_tag(tag)?;
+            let release = github_json(&format!("{}/tags/{}", BTBN_RELEASES_API, tag)).await?;
+            btbn_asset(&release, branch, target, ext).map(|url| (url, tag.to_string()))
+        }
+        None => {
+            let releases = github_json(&format!("{}?per_page=30", BTBN_RELEASES_API)).await?;
+            releases
+                .as_array()
+                .into_iter()
+                .flatten()
+                .find_map(|release| {
+                    let tag = release["tag_name"].as_str()?;
+                    validate_build_tag(tag).ok()?;
+                    btbn_asset(release, branch, target, ext).map(|url| (url, tag.to_string()))
+                })
+        }
+    };
+    let (url, tag) = found.ok_or_else(|| {
+        AppError::DependencyInstallError(format!(
+            "ffmpeg {} is not available for this platform{}",
+            branch,
+            tag.map(|t| format!(" in {}", t)).unwrap_or_default()
+        ))
+    })?;
+    Ok((url, format, format!("{}@{}", branch, tag)))
+}
+
+/// Download URL of a specific ffmpeg build for the current platform, with the
+/// pin that names it exactly.
+async fn get_versioned_download_info(
+    version: &str,
+) -> Result<(String, ArchiveFormat, String), AppError> {
+    if cfg!(target_os = "macos") {
+        let tag = validate_version(version, false)?;
+        let arch = if cfg!(target_arch = "aarch64") {
+            "arm64"
+        } else {
+            "x64"
+        };
+        let url = format!(
+            "https://github.com/vanloctech/ffmpeg-macos/releases/download/{}/ffmpeg-macos-{}.tar.gz",
+            tag, arch
+        );
+        ensure_available(&url, tag).await?;
+        Ok((url, ArchiveFormat::TarGz, tag.to_string()))
+    } else {
+        resolve_btbn(version).await
+    }
+}
+
+/// Fail early when the mirror has no build of the requested version.
+async fn ensure_available(url: &str, version: &str) -> Result<(), AppError> {
+    let resp = reqwest::Client::new()
+        .head(url)
+        .header("User-Agent", "modern-ytdlp-gui")
+        .send()
+        .await
+        .map_err(|e| AppError::NetworkError(format!("Failed to reach the ffmpeg mirror: {}", e)))?;
+    match resp.status() {
+        s if s.is_success() => Ok(()),
+        reqwest::StatusCode::NOT_FOUND => Err(AppError::DependencyInstallError(format!(
+            "ffmpeg {} is not available for this platform",
+            version
+        ))),
+        s => Err(AppError::NetworkError(format!(
+            "ffmpeg mirror answered HTTP {}",
+            s
+        ))),
+    }
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(BACKUP_SUFFIX);
+    path.with_file_name(name)
+}
+
+/// The ffmpeg/ffprobe binaries and pin marker of the app bin dir.
+fn managed_files(bin_dir: &Path) -> Vec<PathBuf> {
+    get_binary_names()
+        .iter()
+        .map(|name| bin_dir.join(name))
+        .chain(std::iter::once(bin_dir.join(PIN_FILE)))
+        .collect()
+}
+
+/// Pinned ffmpeg version of the app-managed install, if any.
+pub fn pinned_version(app: &AppHandle) -> Option<String> {
+    let bin_dir = crate::modules::data_dir::resolve(app).ok()?.join("bin");
+    std::fs::read_to_string(bin_dir.join(PIN_FILE))
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Install a specific ffmpeg build and pin it, keeping the current binaries as
+/// `.bak` copies for `rollback_ffmpeg`.
+pub async fn install_ffmpeg_version(app: &AppHandle, version: &str) -> Result<String, AppError> {
+    let (url, format, pin) = get_versioned_download_info(version).await?;
+    let version = pin.as_str();
+
+    let bin_dir = ensure_bin_dir(app)?;
+    for file in managed_files(&bin_dir) {
+        let backup = backup_path(&file);
+        let result = if file.exists() {
+            tokio::fs::copy(&file, &backup).await.map(|_| ())
+        } else {
+            // No stale copy from an older install may survive a rollback
+            match tokio::fs::remove_file(&backup).await {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            }
+        };
+        if let Err(e) = result {
+            return Err(AppError::DependencyInstallError(format!(
+                "Could not keep a rollback copy of {}: {}",
+                file.display(),
+                e
+            )));
+        }
+    }
+
+    let installed = install_archive(app, &url, format).await?;
+    if installed == "unknown" {
+        return Err(AppError::DependencyInstallError(format!(
+            "ffmpeg {} was installed but does not run. Roll back to the previous version.",
+            version
+        )));
+    }
+    tokio::fs::write(bin_dir.join(PIN_FILE), version)
+        .await
+        .map_err(|e| AppError::FileError(format!("Failed to record ffmpeg pin: {}", e)))?;
+    logger::info_cat(
+        "dependency",
+        &format!("Pinned ffmpeg to {} ({})", version, installed),
+    );
+    Ok(installed)
+}
+
+/// Put back the binaries saved by the last `install_ffmpeg_version`. Returns
+/// the restored version.
+pub async fn rollback_ffmpeg(app: &AppHandle) -> Result<String, AppError> {
+    let bin_dir = ensure_bin_dir(app)?;
+    let ffmpeg = bin_dir.join(get_binary_names()[0]);
+    if !backup_path(&ffmpeg).exists() {
+        return Err(AppError::FileError(
+            "No previous ffmpeg version to roll back to".to_string(),
+        ));
+    }
+    for file in managed_files(&bin_dir) {
+        let backup = backup_path(&file);
+        let result = if backup.exists() {
+            tokio::fs::rename(&backup, &file).await
+        } else {
+            match tokio::fs::remove_file(&file).await {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            }
+        };
+        result.map_err(|e| AppError::FileError(format!("Rollback failed: {}", e)))?;
+    }
+    let version = get_binary_version(&ffmpeg, "-version")
+        .await
+        .ok_or_else(|| AppError::Custom("Rolled-back ffmpeg does not run".to_string()))?;
+    logger::info_cat("dependency", &format!("Rolled ffmpeg back to {}", version));
+    Ok(version)
+}
+
+/// Download and extract an ffmpeg archive into the app bin dir. Returns the
+/// installed version, or "unknown" when it doesn't run.
+async fn install_archive(
+    app: &AppHandle,
+    url: &str,
+    format: ArchiveFormat,
+) -> Result<String, AppError> {
+    let bin_dir = ensure_bin_dir(app)?;
     let binary_names = get_binary_names();
 
     let temp_archive = format!(
@@ -129,8 +454,11 @@ pub async fn install_ffmpeg(app: &AppHandle) -> Result<String, AppError> {
     Ok(version)
 }
 
-/// Get the latest ffmpeg version info.
-pub async fn get_latest_version() -> Result<String, AppError> {
+/// Get the latest ffmpeg version info; the pin itself when ffmpeg is pinned.
+pub async fn get_latest_version(app: &AppHandle) -> Result<String, AppError> {
+    if let Some(pin) = pinned_version(app) {
+        return Ok(pin);
+    }
     // BtbN builds use rolling "latest" tag, so we just return a placeholder.
     // For vanloctech/ffmpeg-macos, check the latest release.
     if cfg!(target_os = "macos") {
@@ -158,3 +486,57 @@ pub async fn get_latest_version() -> Result<String, AppError> {
         Ok("latest".to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn release_branch_versions() {
+        assert_eq!(validate_version(" 7.1 ", true).unwrap(), "7.1");
+        assert!(validate_version("7", true).is_err());
+        assert!(validate_version("7.1.1", true).is_err());
+        assert!(validate_version("latest", true).is_err());
+    }
+
+    #[test]
+    fn release_tags_reject_path_characters() {
+        assert!(validate_version("v7.1-2", false).is_ok());
+        assert!(validate_version("../latest", false).is_err());
+        assert!(validate_version("", false).is_err());
+    }
+
+    #[test]
+    fn build_tags_and_pins() {
+        assert!(validate_build_tag("autobuild-2024-10-31-12-55").is_ok());
+        assert!(validate_build_tag("latest").is_err());
+        assert!(validate_build_tag("autobuild-../x").is_err());
+        assert_eq!(parse_pin("7.1"), ("7.1", None));
+        assert_eq!(
+            parse_pin("7.1@autobuild-2024-10-31-12-55"),
+            ("7.1", Some("autobuild-2024-10-31-12-55"))
+        );
+    }
+
+    #[test]
+    fn btbn_asset_matches_branch_and_target() {
+        let release = serde_json::json!({
+            "tag_name": "autobuild-2024-10-31-12-55",
+            "assets": [
+                {"name": "ffmpeg-n7.1-22-g0e8c5b2-win64-gpl-shared-7.1.zip", "browser_download_url": "shared"},
+                {"name": "ffmpeg-n7.1-22-g0e8c5b2-win64-gpl-7.1.zip", "browser_download_url": "win"},
+                {"name": "ffmpeg-n7.1-22-g0e8c5b2-linux64-gpl-7.1.tar.xz", "browser_download_url": "linux"},
+                {"name": "ffmpeg-master-latest-win64-gpl.zip", "browser_download_url": "master"}
+            ]
+        });
+        assert_eq!(
+            btbn_asset(&release, "7.1", "win64", "zip").as_deref(),
+            Some("win")
+        );
+        assert_eq!(
+            btbn_asset(&release, "7.1", "linux64", "tar.xz").as_deref(),
+            Some("linux")
+        );
+        assert_eq!(btbn_asset(&release, "7.0", "win64", "zip"), None);
+    }
+}
@@ -73,8 +73,21 @@ pub async fn install_ytdlp(app: &AppHandle) -> Result<String, AppError> {
     set_executable(&temp_path)?;
     remove_quarantine(&temp_path)?;
 
-    // Finalize
+    // Finalize, keeping the binary it replaces for rollback
     let final_path = bin_dir.join(get_binary_name());
+    if final_path.exists() {
+        let keep = crate::ytdlp::settings::get_settings(app)
+            .map(|s| s.binary_backup_count)
+            .unwrap_or(1);
+        if let Some(backup) = crate::ytdlp::binary::rotate_backups(&final_path, keep) {
+            if let Err(e) = std::fs::copy(&final_path, &backup) {
+                crate::modules::logger::warn_cat(
+                    "dependency",
+                    &format!("Could not keep a rollback copy of yt-dlp: {}", e),
+                );
+            }
+        }
+    }
     finalize_binary(&temp_path, &final_path)?;
 
     // Verify installation
@@ -84,9 +97,18 @@ pub async fn install_ytdlp(app: &AppHandle) -> Result<String, AppError> {
         DepInstallStage::Completing,
         Some("Verifying installation..."),
     );
-    let version = get_binary_version(&final_path, "--version")
-        .await
-        .unwrap_or_else(|| "unknown".to_string());
+    // The checksum can pass and the OS still refuse to run the file
+    let diagnosis = crate::ytdlp::binary::diagnose_binary(&final_path).await;
+    let Some(version) = diagnosis.version else {
+        let message = format!(
+            "yt-dlp was installed but does not run. {}\n\n{}",
+            diagnosis.hint.unwrap_or_default(),
+            diagnosis.stderr.unwrap_or_default()
+        );
+        crate::modules::logger::error_cat("dependency", &message);
+        emit_stage(app, "yt-dlp", DepInstallStage::Failed, Some(&message));
+        return Err(AppError::DependencyInstallError(message));
+    };
 
     emit_stage(
         app,
@@ -0,0 +1,68 @@
+//! Diagnostics bundle for bug reports (`export_diagnostics`).
+//!
+//! A zip holding `app_info.json`, `health.json` (a fresh `health_check`),
+//! `settings.json` (always `settings::redacted`) and `logs.json` (the most
+//! recent log entries with the home directory masked). Unlike a full backup
+//! it holds no databases and is never read back by the app.
+
+use super::security::sanitize_error_message;
+use super::types::{AppInfo, AppSettings, HealthReport, LogEntry};
+use crate::modules::types::AppError;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Number of most recent log entries included.
+pub const RECENT_LOGS: u32 = 500;
+
+fn file_err(e: impl std::fmt::Display) -> AppError {
+    AppError::FileError(e.to_string())
+}
+
+/// Everything that goes into a bundle.
+pub struct Parts<'a> {
+    pub app_info: &'a AppInfo,
+    pub health: &'a HealthReport,
+    /// Already redacted
+    pub settings: &'a AppSettings,
+    pub logs: &'a [LogEntry],
+}
+
+/// Log entries with paths under the home directory shown as `~`.
+pub fn mask_logs(logs: Vec<LogEntry>) -> Vec<LogEntry> {
+    logs.into_iter()
+        .map(|entry| LogEntry {
+            message: sanitize_error_message(&entry.message),
+            details: entry.details.as_deref().map(sanitize_error_message),
+            ..entry
+        })
+        .collect()
+}
+
+/// Write the bundle to `dest` (replaced only once complete). Blocking.
+pub fn write(dest: &Path, parts: Parts<'_>) -> Result<(), AppError> {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".partial");
+    let partial = dest.with_file_name(name);
+    let result = (|| {
+        let mut zip =
+            zip::ZipWriter::new(BufWriter::new(File::create(&partial).map_err(file_err)?));
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+        for (name, value) in [
+            ("app_info.json", serde_json::to_vec_pretty(parts.app_info)),
+            ("health.json", serde_json::to_vec_pretty(parts.health)),
+            ("settings.json", serde_json::to_vec_pretty(parts.settings)),
+            ("logs.json", serde_json::to_vec_pretty(parts.logs)),
+        ] {
+            zip.start_file(name, options).map_err(file_err)?;
+            zip.write_all(&value.map_err(file_err)?).map_err(file_err)?;
+        }
+        zip.finish().map_err(file_err)?.flush().map_err(file_err)?;
+        std::fs::rename(&partial, dest).map_err(file_err)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&partial);
+    }
+    result
+}
@@ -0,0 +1,169 @@
+use crate::ytdlp::types::DirUsage;
+use std::collections::HashMap;
+use std::path::Path;
+use sysinfo::Disks;
+
+/// Free bytes on the volume containing `path` (or its nearest existing ancestor).
+/// Returns None if no mounted disk matches.
+pub fn available_space(path: &Path) -> Option<u64> {
+    let existing = path.ancestors().find(|p| p.exists())?;
+    let target = existing
+        .canonicalize()
+        .unwrap_or_else(|_| existing.to_path_buf());
+
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|d| target.starts_with(d.mount_point()))
+        // Most specific mount point wins (e.g. /home over /)
+        .max_by_key(|d| d.mount_point().as_os_str().len())
+        .map(|d| d.available_space())
+}
+
+/// Whether `dir` keeps names that differ only by case apart. macOS and Windows
+/// volumes usually don't, so `Title.mp4` and `title.mp4` land on one file.
+/// Creates two probe files that differ by case and removes them again.
+pub fn is_case_sensitive(dir: &Path) -> std::io::Result<bool> {
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    let lower = dir.join(format!(".case-probe-{}-{}.tmp", std::process::id(), stamp));
+    let upper = dir.join(format!(".CASE-PROBE-{}-{}.TMP", std::process::id(), stamp));
+
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&lower)?;
+    let result = match std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&upper)
+    {
+        Ok(_) => {
+            // Both names were free, so both must still be there as separate files
+            let sensitive = lower.is_file() && upper.is_file();
+            let _ = std::fs::remove_file(&upper);
+            Ok(sensitive)
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    };
+    let _ = std::fs::remove_file(&lower);
+    result
+}
+
+/// Whether `a` and `b` name the same existing file, e.g. names that differ only
+/// by case on a case-insensitive volume.
+pub fn same_file(a: &Path, b: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        match (std::fs::metadata(a), std::fs::metadata(b)) {
+            (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+            _ => false,
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        // Windows: canonical paths carry the name as stored on disk
+        match (a.canonicalize(), b.canonicalize()) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// Total downloaded files per parent directory, largest first. With
+/// `stat_missing`, files without a recorded size are measured on disk.
+pub fn usage_by_directory(files: Vec<(String, Option<u64>)>, stat_missing: bool) -> Vec<DirUsage> {
+    let mut usage = group_usage(files, |path| {
+        if stat_missing {
+            std::fs::metadata(path).ok().map(|m| m.len())
+        } else {
+            None
+        }
+    });
+    for dir in &mut usage {
+        dir.missing = !Path::new(&dir.path).is_dir();
+    }
+    usage
+}
+
+fn group_usage(
+    files: Vec<(String, Option<u64>)>,
+    size_of: impl Fn(&Path) -> Option<u64>,
+) -> Vec<DirUsage> {
+    let mut dirs: HashMap<String, DirUsage> = HashMap::new();
+    for (file, size) in files {
+        let path = Path::new(&file);
+        let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) else {
+            continue;
+        };
+        let dir = parent.to_string_lossy().to_string();
+        let entry = dirs.entry(dir.clone()).or_insert_with(|| DirUsage {
+            path: dir,
+            file_count: 0,
+            total_bytes: 0,
+            unknown_size_count: 0,
+            missing: false,
+        });
+        entry.file_count += 1;
+        match size.or_else(|| size_of(path)) {
+            Some(bytes) => entry.total_bytes += bytes,
+            None => entry.unknown_size_count += 1,
+        }
+    }
+    let mut usage: Vec<DirUsage> = dirs.into_values().collect();
+    usage.sort_by(|a, b| {
+        b.total_bytes
+            .cmp(&a.total_bytes)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    usage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn case_probe_leaves_no_files() {
+        let dir = std::env::temp_dir().join(format!("case-probe-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        assert!(is_case_sensitive(&dir).is_ok());
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+        std::fs::remove_dir(&dir).unwrap();
+    }
+
+    #[test]
+    fn groups_by_parent_directory() {
+        let files = vec![
+            ("/videos/a.mp4".to_string(), Some(100)),
+            ("/videos/b.mp4".to_string(), None),
+            ("/music/c.mp3".to_string(), Some(500)),
+            ("orphan.mp4".to_string(), Some(1)),
+        ];
+        let usage = group_usage(files, |p| (p == Path::new("/videos/b.mp4")).then_some(50));
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage[0].path, "/music");
+        assert_eq!(usage[0].total_bytes, 500);
+        assert_eq!(usage[1].path, "/videos");
+        assert_eq!(usage[1].file_count, 2);
+        assert_eq!(usage[1].total_bytes, 150);
+        assert_eq!(usage[1].unknown_size_count, 0);
+    }
+
+    #[test]
+    fn counts_unknown_sizes_without_stat() {
+        let files = vec![
+            ("/videos/a.mp4".to_string(), Some(100)),
+            ("/videos/b.mp4".to_string(), None),
+        ];
+        let usage = group_usage(files, |_| None);
+        assert_eq!(usage[0].file_count, 2);
+        assert_eq!(usage[0].total_bytes, 100);
+        assert_eq!(usage[0].unknown_size_count, 1);
+    }
+}
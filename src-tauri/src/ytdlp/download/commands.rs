@@ -1,31 +1,193 @@
 use super::executor::{execute_download, process_next_pending};
 use super::manager::DownloadManager;
+use super::next_start;
+use super::retry;
 use crate::modules::logger;
 use crate::modules::types::AppError;
 use crate::ytdlp::types::*;
-use crate::ytdlp::{security, settings};
+use crate::ytdlp::{
+    credentials, existing_file, host_policy, http_headers, output_paths, presets, security,
+    settings,
+};
 use std::sync::Arc;
 use tauri::{AppHandle, Manager};
 
 #[tauri::command]
 #[specta::specta]
 pub async fn add_to_queue(app: AppHandle, request: DownloadRequest) -> Result<u64, AppError> {
+    enqueue(&app, request, None).await
+}
+
+/// Features of `options` (a request as it would be queued, preset applied) that
+/// need ffmpeg, so the UI can warn before queueing when ffmpeg is missing.
+#[tauri::command]
+#[specta::specta]
+pub fn features_requiring_ffmpeg(
+    app: AppHandle,
+    options: DownloadRequest,
+) -> Result<Vec<String>, AppError> {
+    let request = expand_preset(&app, options)?;
+    let settings = settings::get_settings(&app)?;
+    Ok(crate::ytdlp::ffmpeg_features::required(&request, &settings))
+}
+
+/// What yt-dlp would download for `options` under the current settings
+/// (`maxHeight`, codec preference, audio language), without downloading.
+#[tauri::command]
+#[specta::specta]
+pub async fn test_format_selection(
+    app: AppHandle,
+    options: DownloadRequest,
+) -> Result<SelectedFormat, AppError> {
+    let request = expand_preset(&app, options)?;
+    let settings = settings::get_settings(&app)?;
+    crate::ytdlp::format_test::run(&app, &settings, &request).await
+}
+
+/// Re-download link for `enqueue`: the history row being redone and whether
+/// its file should be replaced.
+pub(crate) struct RedownloadLink {
+    pub history_id: u64,
+    pub replace_existing: bool,
+}
+
+/// Fill the request from its named preset, built-in or saved. Explicit fields win.
+fn expand_preset(app: &AppHandle, request: DownloadRequest) -> Result<DownloadRequest, AppError> {
+    let Some(name) = request.preset.clone() else {
+        return Ok(request);
+    };
+    let options = match presets::builtin(&name) {
+        Some(preset) => preset.options,
+        None => app
+            .state::<crate::DbState>()
+            .get_preset(&name)?
+            .ok_or_else(|| AppError::Custom(format!("Unknown preset: {}", name)))?,
+    };
+    Ok(presets::apply(request, &options))
+}
+
+/// Reject retry overrides outside the allowed ranges.
+pub(crate) fn validate_retry_policy(
+    max_retries: Option<u32>,
+    retry_backoff_secs: Option<u32>,
+) -> Result<(), AppError> {
+    if max_retries.is_some_and(|n| n > retry::MAX_RETRIES_LIMIT) {
+        return Err(AppError::Custom(format!(
+            "At most {} automatic retries are allowed",
+            retry::MAX_RETRIES_LIMIT
+        )));
+    }
+    if retry_backoff_secs.is_some_and(|s| s == 0 || s > retry::MAX_BACKOFF_SECS) {
+        return Err(AppError::Custom(format!(
+            "Retry backoff must be between 1 and {} seconds",
+            retry::MAX_BACKOFF_SECS
+        )));
+    }
+    Ok(())
+}
+
+/// Queue a download and start it if a slot is free. Shared by `add_to_queue`
+/// and history re-downloads, neither of which is blocked by duplicate checks.
+pub(crate) async fn enqueue(
+    app: &AppHandle,
+    request: DownloadRequest,
+    link: Option<RedownloadLink>,
+) -> Result<u64, AppError> {
+    let mut request = expand_preset(app, request)?;
+
     // Validate URL
     security::sanitize_url(&request.video_url)?;
 
+    // Blocked ids are never queued; redoing a history row is an explicit choice
+    if link.is_none()
+        && !request.video_id.is_empty()
+        && app
+            .state::<crate::DbState>()
+            .is_video_blocked(&request.video_id)?
+    {
+        logger::info_cat(
+            "download",
+            &format!("Skipped blocked video {}", request.video_id),
+        );
+        return Err(AppError::Custom(format!(
+            "Video {} is on the blocklist",
+            request.video_id
+        )));
+    }
+
     // Get settings for download path and filename template
-    let settings = settings::get_settings(&app)?;
+    let settings = settings::get_settings(app)?;
+
+    if let Err(rejection) = host_policy::check(
+        &request.video_url,
+        &settings.host_allowlist,
+        &settings.host_denylist,
+    ) {
+        let err = AppError::from(rejection);
+        logger::warn_cat("download", &format!("Rejected download: {}", err));
+        return Err(err);
+    }
 
     // Determine output directory and validate path
+    // A folder picked for this download wins over the per-type folders
     let output_dir = request
         .output_dir
         .as_deref()
-        .unwrap_or(&settings.download_path);
+        .unwrap_or_else(|| output_paths::main_dir(&settings, &request.format_id));
     security::sanitize_output_path(output_dir)?;
+    if let Some(size) = &request.max_filesize {
+        security::sanitize_filesize(size)?;
+    }
+    if let Some(lang) = &request.audio_lang {
+        security::sanitize_language(lang)?;
+    }
+    if request.subtitles_only {
+        request.subtitle_langs = super::subtitles::sanitize_langs(&request.subtitle_langs)?;
+        request.convert_subs = request
+            .convert_subs
+            .as_deref()
+            .map(super::subtitles::sanitize_format)
+            .transpose()?;
+    }
+    validate_retry_policy(request.max_retries, request.retry_backoff_secs)?;
+    let mut tags: Vec<String> = Vec::with_capacity(request.tags.len());
+    for tag in &request.tags {
+        let tag = crate::ytdlp::commands::history::check_tag(tag)?;
+        if !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    request.tags = tags;
+    if let Some(headers) = &request.headers {
+        request.headers = Some(http_headers::sanitize(headers)?);
+    }
+
+    // Re-encoding targets a single merged file; an audio-only stream can't be recoded to video
+    if request.keep_separate_streams && settings.recode_video.is_some() {
+        return Err(AppError::Custom(
+            "재인코딩이 켜져 있으면 비디오/오디오를 분리해서 받을 수 없습니다. 설정에서 재인코딩을 끄세요."
+                .to_string(),
+        ));
+    }
+    crate::ytdlp::ffmpeg_features::check_embeds(&(&request).into(), &settings)
+        .map_err(AppError::Custom)?;
+
+    if let Err(e) = settings::record_recent_directory(app, output_dir) {
+        logger::warn_cat(
+            "settings",
+            &format!("Failed to record recent directory: {}", e),
+        );
+    }
 
     // Build output template using OS-native path separators
+    let filename_template = match &request.on_existing_file {
+        Some(ExistingFileAction::Rename { filename }) => {
+            security::sanitize_filename_template(&existing_file::literal_template(filename))?
+        }
+        _ => settings.filename_template.clone(),
+    };
     let output_template = std::path::Path::new(output_dir)
-        .join(&settings.filename_template)
+        .join(filename_template)
         .to_string_lossy()
         .to_string();
 
@@ -35,12 +197,41 @@ pub async fn add_to_queue(app: AppHandle, request: DownloadRequest) -> Result<u6
     // Insert download record into DB with pending status
     let task_id = db_state.insert_download(&request, &output_template)?;
 
+    // Link before the task can start so the executor sees it
+    if let Some(link) = link {
+        db_state.link_redownload(task_id, link.history_id, link.replace_existing)?;
+    }
+    if matches!(
+        request.on_existing_file,
+        Some(ExistingFileAction::Overwrite)
+    ) {
+        db_state.set_replace_existing(task_id)?;
+    }
+
+    // Credentials go to the OS keychain; the DB row only flags that they exist
+    if let Some(creds) = request
+        .credentials
+        .as_ref()
+        .filter(|c| credentials::has_any(c))
+    {
+        if let Err(e) = credentials::store(task_id, creds) {
+            let _ = db_state.update_download_status(
+                task_id,
+                &DownloadStatus::Failed,
+                Some("인증 정보를 안전하게 저장할 수 없습니다."),
+            );
+            return Err(e);
+        }
+    }
+
     // Try to acquire a download slot
     let manager = app.state::<Arc<DownloadManager>>();
+    manager.publish(QueueEvent::task(QueueEventKind::Added, task_id));
     if manager.try_acquire() {
         // Immediately start download - ensure release() on DB update failure
         match db_state.update_download_status(task_id, &DownloadStatus::Downloading, None) {
             Ok(()) => {
+                super::limits::note_start(app, task_id);
                 let app_clone = app.clone();
                 let app_panic_guard = app.clone();
                 tokio::spawn(async move {
@@ -90,10 +281,8 @@ pub async fn start_download(app: AppHandle, request: DownloadRequest) -> Result<
     add_to_queue(app, request).await
 }
 
-// Proper cancel implementation that kills the actual yt-dlp process
-#[tauri::command]
-#[specta::specta]
-pub async fn cancel_download(app: AppHandle, task_id: u64) -> Result<(), AppError> {
+/// Cancel a single task. Returns true if the task was still active and got cancelled.
+fn cancel_task(app: &AppHandle, task_id: u64) -> Result<bool, AppError> {
     let db_state = app.state::<crate::DbState>();
 
     // Only cancel if task is still in a cancellable state (pending/downloading).
@@ -105,8 +294,17 @@ pub async fn cancel_download(app: AppHandle, task_id: u64) -> Result<(), AppErro
         // Send cancel signal to kill the actual yt-dlp process (no-op if not running)
         let manager = app.state::<Arc<DownloadManager>>();
         manager.send_cancel(task_id);
+        manager.publish(QueueEvent::task(QueueEventKind::Cancelled, task_id));
     }
 
+    Ok(was_cancelled)
+}
+
+// Proper cancel implementation that kills the actual yt-dlp process
+#[tauri::command]
+#[specta::specta]
+pub async fn cancel_download(app: AppHandle, task_id: u64) -> Result<(), AppError> {
+    cancel_task(&app, task_id)?;
     Ok(())
 }
 
@@ -122,6 +320,7 @@ pub async fn cancel_all_downloads(app: AppHandle) -> Result<u32, AppError> {
     for id in ids {
         if db_state.cancel_if_active(id).unwrap_or(false) {
             manager.send_cancel(id);
+            manager.publish(QueueEvent::task(QueueEventKind::Cancelled, id));
             cancelled += 1;
         }
     }
@@ -135,6 +334,136 @@ pub async fn cancel_all_downloads(app: AppHandle) -> Result<u32, AppError> {
     Ok(cancelled)
 }
 
+/// When the next pending task should start, or None when nothing is pending.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_next_start(app: AppHandle) -> Result<Option<NextStart>, AppError> {
+    let db_state = app.state::<crate::DbState>();
+    let manager = app.state::<Arc<DownloadManager>>();
+
+    let Some(next) = db_state.get_next_pending()? else {
+        return Ok(None);
+    };
+    let running_etas: Vec<Option<u64>> = db_state
+        .get_active_downloads()?
+        .iter()
+        .filter(|t| matches!(t.status, DownloadStatus::Downloading))
+        .map(|t| t.eta.as_deref().and_then(next_start::parse_eta))
+        .collect();
+    let now = chrono::Local::now();
+    let secs_to_midnight = (super::limits::next_day_start(&now) - now.timestamp()).max(0) as u64;
+    let estimate = next_start::estimate(
+        next_start::hold(&manager, secs_to_midnight),
+        manager.free_slots(),
+        manager.start_delay_remaining(),
+        &running_etas,
+    );
+
+    Ok(Some(NextStart {
+        task_id: next.id,
+        title: next.title,
+        reason: estimate.reason,
+        eta_secs: estimate.eta_secs,
+        starts_at: estimate
+            .eta_secs
+            .map(|secs| chrono::Utc::now().timestamp() + secs as i64),
+        confidence: estimate.confidence,
+    }))
+}
+
+/// Recount running tasks from the DB and correct the manager's slot counter,
+/// for when a leaked slot keeps queued downloads from starting.
+#[tauri::command]
+#[specta::specta]
+pub async fn reset_manager_slots(app: AppHandle) -> Result<SlotReset, AppError> {
+    let db_state = app.state::<crate::DbState>();
+    let manager = app.state::<Arc<DownloadManager>>();
+
+    let actual = db_state.get_active_count()?;
+    let previous = manager.sync_active_count(actual);
+    let corrected = previous != actual;
+    if corrected {
+        logger::warn_cat(
+            "download",
+            &format!(
+                "Slot counter out of sync: {} counted, {} downloading; corrected",
+                previous, actual
+            ),
+        );
+        // Freed slots can start waiting tasks right away
+        process_next_pending(app.clone());
+    } else {
+        logger::info_cat(
+            "download",
+            &format!("Slot counter in sync ({} downloading)", actual),
+        );
+    }
+
+    Ok(SlotReset {
+        previous,
+        actual,
+        max_concurrent: manager.max_concurrent(),
+        corrected,
+    })
+}
+
+/// Run the queue watchdog's check now: restart the queue if pending tasks are
+/// waiting with free slots and nothing running.
+#[tauri::command]
+#[specta::specta]
+pub async fn check_queue_stall(app: AppHandle) -> Result<QueueStallCheck, AppError> {
+    super::watchdog::check(&app, true)
+}
+
+/// Cancel only the active tasks matching `filter` (e.g. one playlist or tag), leaving the rest queued.
+#[tauri::command]
+#[specta::specta]
+pub async fn cancel_matching(
+    app: AppHandle,
+    filter: CancelFilter,
+) -> Result<CancelMatchResult, AppError> {
+    if filter.host.is_none() && filter.playlist_id.is_none() && filter.tag.is_none() {
+        return Err(AppError::DownloadError(
+            "Cancel filter must set a host, playlist id or tag".to_string(),
+        ));
+    }
+
+    let db_state = app.state::<crate::DbState>();
+
+    let mut task_ids = Vec::new();
+    for (id, video_url, playlist_id, user_tags) in db_state.get_cancellable_tasks()? {
+        let host_ok = filter
+            .host
+            .as_deref()
+            .is_none_or(|h| security::url_matches_host(&video_url, h));
+        let playlist_ok = filter
+            .playlist_id
+            .as_deref()
+            .is_none_or(|p| playlist_id.as_deref() == Some(p));
+        let tag_ok = filter
+            .tag
+            .as_deref()
+            .is_none_or(|tag| user_tags.iter().any(|t| t == tag.trim()));
+        if host_ok && playlist_ok && tag_ok && cancel_task(&app, id).unwrap_or(false) {
+            task_ids.push(id);
+        }
+    }
+
+    // No active_count sync here: unlike cancel_all, other tasks keep running.
+    // Each cancelled process releases its own slot and pulls the next pending task.
+    if !task_ids.is_empty() {
+        logger::info_cat(
+            "download",
+            &format!("Cancelled {} task(s) matching filter", task_ids.len()),
+        );
+    }
+
+    Ok(CancelMatchResult {
+        count: task_ids.len() as u32,
+        task_ids,
+    })
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn pause_download(_app: AppHandle, _task_id: u64) -> Result<(), AppError> {
@@ -0,0 +1,86 @@
+//! "Quit after downloads finish": stop new starts, wait for the running
+//! downloads, then exit. Past the timeout the rest are cancelled like a
+//! normal quit.
+
+use super::manager::DownloadManager;
+use crate::modules::logger;
+use crate::modules::types::AppError;
+use crate::ytdlp::types::{DrainPhase, QueueDrainEvent};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Wait used when the caller doesn't give one
+pub const DEFAULT_TIMEOUT_SECS: u32 = 3600;
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Time cancelled tasks get to mark themselves before the process exits
+const CANCEL_GRACE: Duration = Duration::from_secs(2);
+
+static DRAINING: AtomicBool = AtomicBool::new(false);
+
+/// Start draining in the background; progress goes out as `queue-drain-event`.
+pub fn start_drain(app: &AppHandle, timeout_secs: Option<u32>) -> Result<(), AppError> {
+    if DRAINING.swap(true, Ordering::SeqCst) {
+        return Err(AppError::Custom(
+            "Already waiting for downloads to finish".to_string(),
+        ));
+    }
+    let timeout_secs = timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS).max(1);
+    app.state::<Arc<DownloadManager>>().set_starts_paused(true);
+    logger::info_cat(
+        "download",
+        &format!(
+            "Quit requested after downloads finish (timeout {}s)",
+            timeout_secs
+        ),
+    );
+    tauri::async_runtime::spawn(drain(app.clone(), timeout_secs));
+    Ok(())
+}
+
+async fn drain(app: AppHandle, timeout_secs: u32) {
+    let started = Instant::now();
+    let timeout = Duration::from_secs(timeout_secs as u64);
+    let manager = app.state::<Arc<DownloadManager>>();
+
+    loop {
+        let active = manager.active_count();
+        let elapsed = started.elapsed();
+        if active == 0 {
+            emit(&app, DrainPhase::Exiting, 0, elapsed, timeout_secs);
+            break;
+        }
+        if elapsed >= timeout {
+            logger::warn_cat(
+                "download",
+                &format!(
+                    "Drain timed out after {}s, cancelling {} download(s)",
+                    timeout_secs, active
+                ),
+            );
+            emit(&app, DrainPhase::Cancelling, active, elapsed, timeout_secs);
+            manager.cancel_all();
+            tokio::time::sleep(CANCEL_GRACE).await;
+            break;
+        }
+        emit(&app, DrainPhase::Waiting, active, elapsed, timeout_secs);
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+
+    logger::info_cat("app", "Exiting after queue drain");
+    logger::flush();
+    app.exit(0);
+}
+
+fn emit(app: &AppHandle, phase: DrainPhase, active: u32, elapsed: Duration, timeout_secs: u32) {
+    let _ = app.emit(
+        "queue-drain-event",
+        QueueDrainEvent {
+            phase,
+            active,
+            elapsed_secs: elapsed.as_secs() as u32,
+            timeout_secs,
+        },
+    );
+}
@@ -1,7 +1,15 @@
+use super::focus;
 use super::manager::DownloadManager;
+use super::retry;
+use super::subtitles;
+use super::temp;
 use crate::modules::logger;
 use crate::ytdlp::types::*;
-use crate::ytdlp::{binary, progress, security, settings};
+use crate::ytdlp::{
+    binary, browser_profiles, container, credentials, ffmpeg_features, http_headers, output_paths,
+    playlist_index, progress, security, settings,
+};
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
 use std::time::Duration;
@@ -49,6 +57,10 @@ async fn kill_process_tree(child: &mut tokio::process::Child) {
 /// Sanitizes the error message to remove sensitive system paths before sending to UI.
 fn emit_download_error(app: &AppHandle, task_id: u64, message: String) {
     let sanitized = security::sanitize_error_message(&message);
+    app.state::<Arc<DownloadManager>>().publish(QueueEvent {
+        message: Some(sanitized.clone()),
+        ..QueueEvent::task(QueueEventKind::Failed, task_id)
+    });
     let _ = app.emit(
         "download-event",
         GlobalDownloadEvent {
@@ -60,6 +72,8 @@ fn emit_download_error(app: &AppHandle, task_id: u64, message: String) {
             file_path: None,
             file_size: None,
             message: Some(sanitized),
+            downloaded_bytes: None,
+            elapsed_secs: None,
         },
     );
 }
@@ -78,9 +92,26 @@ fn handle_download_failure(
     emit_download_error(app, task_id, error_msg.to_string());
     manager.unregister_cancel(task_id);
     manager.release();
+    focus::task_ended(app, task_id);
     process_next_pending(app.clone());
 }
 
+/// Human-readable size in binary units ("1.50 GiB"), matching yt-dlp's size syntax.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.2} {}", value, UNITS[unit])
+    }
+}
+
 pub(super) fn append_limited(buffer: &mut String, line: &str, max_bytes: usize) {
     if !buffer.is_empty() {
         buffer.push('\n');
@@ -109,11 +140,14 @@ pub async fn execute_download_public(app: AppHandle, task_id: u64) {
     execute_download(app, task_id).await;
 }
 
+/// Output file path, stream paths, size-limit line and subtitle paths read from stdout
+type StdoutSummary = (Option<String>, Vec<String>, Option<String>, Vec<String>);
+
 pub(super) async fn execute_download(app: AppHandle, task_id: u64) {
     let db_state = app.state::<crate::DbState>();
     let manager = app.state::<Arc<DownloadManager>>();
 
-    let task = match db_state.get_download(task_id) {
+    let mut task = match db_state.get_download(task_id) {
         Ok(Some(t)) => t,
         _ => {
             logger::error_cat(
@@ -133,6 +167,9 @@ pub(super) async fn execute_download(app: AppHandle, task_id: u64) {
         return;
     }
 
+    // Single videos queued from a playlist: yt-dlp can't fill %(playlist_index)s itself
+    task.output_path = playlist_index::apply(&task.output_path, task.playlist_index);
+
     let ytdlp_path = match binary::resolve_ytdlp_path_with_app(&app).await {
         Ok(p) => p,
         Err(_e) => {
@@ -175,23 +212,125 @@ pub(super) async fn execute_download(app: AppHandle, task_id: u64) {
             file_path: None,
             file_size: None,
             message: None,
+            downloaded_bytes: None,
+            elapsed_secs: None,
         },
     );
+    manager.publish(QueueEvent::task(QueueEventKind::Started, task_id));
 
     // Register cancel receiver before spawning process
     let mut cancel_rx = manager.register_cancel(task_id);
+    manager.set_stage(task_id, TaskStage::Starting);
 
     // Build yt-dlp args in a Vec for logging before passing to Command
     let mut args: Vec<String> = Vec::new();
-    args.extend(["--format".to_string(), task.format_id.clone()]);
-    args.extend(["--output".to_string(), task.output_path.clone()]);
+    // Subtitle-only tasks skip everything that concerns the media file itself
+    let media = !task.subtitles_only;
+    // Separate streams: "," downloads each format on its own instead of merging with "+"
+    let separate_streams = task.keep_separate_streams && media;
+    let output_path = if separate_streams {
+        separate_stream_template(&task.output_path)
+    } else {
+        task.output_path.clone()
+    };
+    // Preferred audio language; yt-dlp falls back to the unfiltered selector
+    let audio_lang = task
+        .audio_lang
+        .as_deref()
+        .filter(|_| media)
+        .and_then(|l| security::sanitize_language(l).ok());
+    let selector = build_selector(
+        settings,
+        &task.format_id,
+        separate_streams,
+        audio_lang.as_deref(),
+    );
+    if let (true, Some(lang)) = (selector.audio_lang_ignored, audio_lang.as_deref()) {
+        logger::warn_cat(
+            "download",
+            &format!(
+                "[download:{}] format '{}' has no generic audio selector; ignoring audio language {}",
+                task_id, task.format_id, lang
+            ),
+        );
+    }
+    let max_height_applied = selector.max_height;
+    let format = selector.format;
+    // ffmpeg postprocessing, decided the same way `features_requiring_ffmpeg` reports it
+    let postprocess = ffmpeg_features::plan(&(&task).into(), &settings);
+    let subtitle_format = postprocess.convert_subs.clone();
+    if media {
+        args.extend(["--format".to_string(), format.clone()]);
+    } else {
+        args.extend(subtitles::args(
+            &task.subtitle_langs,
+            subtitle_format.as_deref(),
+        ));
+    }
+
+    // Temp dir and per-type side-file folders: yt-dlp ignores `--paths` for an absolute
+    // --output, so split the stored output path into a home dir and a relative template.
+    let side_paths = output_paths::side_paths(&settings);
+    let (home, template) = split_output_template(&output_path);
+    let temp_dir = temp::temp_dir_for(&app, &settings);
+    // A replacing download is written next to the files it replaces and swapped in
+    // only once it succeeds, so a failed or cancelled run leaves them intact
+    let staging = (task.replace_existing && media).then(|| staging_dir(&home, task_id));
+    if temp_dir.is_none() && side_paths.is_empty() && staging.is_none() {
+        args.extend(["--output".to_string(), output_path]);
+    } else {
+        let written_home = staging.as_ref().unwrap_or(&home);
+        args.extend([
+            "--paths".to_string(),
+            format!("home:{}", written_home.to_string_lossy()),
+        ]);
+        if let Some(temp_dir) = &temp_dir {
+            if let Err(e) = std::fs::create_dir_all(temp_dir) {
+                logger::warn_cat(
+                    "download",
+                    &format!(
+                        "[download:{}] failed to create temp dir {}: {}",
+                        task_id,
+                        temp_dir.display(),
+                        e
+                    ),
+                );
+            }
+            if temp::is_same_volume(temp_dir, &home) == Some(false) {
+                logger::info_cat(
+                    "download",
+                    &format!(
+                        "[download:{}] temp dir is on a different volume; final move will copy",
+                        task_id
+                    ),
+                );
+            }
+            args.extend([
+                "--paths".to_string(),
+                format!("temp:{}", temp_dir.to_string_lossy()),
+            ]);
+        }
+        for (kind, dir) in &side_paths {
+            args.extend(["--paths".to_string(), format!("{}:{}", kind, dir)]);
+        }
+        args.extend(["--output".to_string(), template]);
+    }
     args.extend([
         "--progress-template".to_string(),
         progress::progress_template(),
     ]);
     args.push("--newline".to_string());
     args.push("--no-playlist".to_string());
-    args.push("--no-overwrites".to_string());
+    if settings.ignore_config {
+        args.push("--ignore-config".to_string());
+    }
+    // Subtitles are fetched again: an existing one would be skipped unreported
+    if task.subtitles_only {
+        args.push("--force-overwrites".to_string());
+    } else {
+        // Pick up .part files left by an interrupted run (e.g. re-queued after sleep)
+        args.extend(["--no-overwrites".to_string(), "--continue".to_string()]);
+    }
 
     // Force UTF-8 encoding inside yt-dlp (fixes cp949 crash on Korean Windows)
     args.push("--encoding".to_string());
@@ -204,13 +343,201 @@ pub(super) async fn execute_download(app: AppHandle, task_id: u64) {
     }
 
     // Pass ffmpeg location explicitly if available
-    if let Some(ffmpeg_path) = binary::resolve_ffmpeg_path_with_app(&app).await {
-        args.extend(["--ffmpeg-location".to_string(), ffmpeg_path]);
+    let ffmpeg_location = binary::resolve_ffmpeg_path_with_app(&app).await;
+    if let Some(ffmpeg_path) = &ffmpeg_location {
+        args.extend(["--ffmpeg-location".to_string(), ffmpeg_path.clone()]);
+    }
+
+    // Re-encode after download (requires ffmpeg)
+    let recode_target = postprocess.recode.clone();
+    let requested_recode = settings
+        .recode_video
+        .as_deref()
+        .filter(|f| security::sanitize_recode_format(f).is_ok());
+    if let (Some(format), None, true) = (requested_recode, &recode_target, media) {
+        // Separate streams or an audio-only format: recoding would fail on the audio stream
+        logger::info_cat(
+            "download",
+            &format!(
+                "[download:{}] skipping recode to {}: no merged video to recode",
+                task_id, format
+            ),
+        );
+    }
+    // autoContainer: remux (stream copy) when the selected codecs fit the target container,
+    // re-encode only when they don't
+    let remux_target = match &recode_target {
+        Some(target) if settings.auto_container => {
+            match crate::ytdlp::probe::selected_codecs(&app, &task.video_url, &format).await {
+                Some((vcodec, acodec))
+                    if container::can_remux(target, vcodec.as_deref(), acodec.as_deref()) =>
+                {
+                    logger::info_cat(
+                        "download",
+                        &format!(
+                            "[download:{}] {}/{} fit {}; remuxing (lossless, no re-encode)",
+                            task_id,
+                            vcodec.as_deref().unwrap_or("none"),
+                            acodec.as_deref().unwrap_or("none"),
+                            target
+                        ),
+                    );
+                    Some(target.clone())
+                }
+                Some((vcodec, acodec)) => {
+                    logger::info_cat(
+                        "download",
+                        &format!(
+                            "[download:{}] {}/{} can't be remuxed into {}; re-encoding (slower, lossy)",
+                            task_id,
+                            vcodec.as_deref().unwrap_or("none"),
+                            acodec.as_deref().unwrap_or("none"),
+                            target
+                        ),
+                    );
+                    None
+                }
+                None => {
+                    logger::warn_cat(
+                        "download",
+                        &format!(
+                            "[download:{}] couldn't determine codecs; re-encoding to {}",
+                            task_id, target
+                        ),
+                    );
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+    let recode_target = recode_target.filter(|_| remux_target.is_none());
+    if let Some(format) = &remux_target {
+        args.extend(["--remux-video".to_string(), format.clone()]);
+    }
+    if let Some(format) = &recode_target {
+        if ffmpeg_location.is_none() {
+            logger::warn_cat(
+                "download",
+                &format!(
+                    "[download:{}] recode to {} requested but ffmpeg location is unknown",
+                    task_id, format
+                ),
+            );
+        }
+        args.extend(["--recode-video".to_string(), format.clone()]);
+        if let Some(ffmpeg_args) = settings
+            .recode_ffmpeg_args
+            .as_deref()
+            .and_then(|a| security::sanitize_ffmpeg_args(a).ok())
+        {
+            args.extend([
+                "--postprocessor-args".to_string(),
+                format!("VideoConvertor:{}", ffmpeg_args),
+            ]);
+        }
+    }
+
+    // Size cap: task override > setting; "0" disables it
+    let max_filesize = task
+        .max_filesize
+        .as_deref()
+        .or(settings.max_filesize.as_deref())
+        .filter(|_| media)
+        .and_then(|s| security::sanitize_filesize(s).ok())
+        .filter(|s| security::parse_filesize(s) != Some(0));
+    if let Some(cap) = &max_filesize {
+        args.extend(["--max-filesize".to_string(), cap.clone()]);
+    }
+
+    // Source URL and metadata as extended file attributes (filesystem permitting)
+    if settings.write_xattrs && media {
+        args.push("--xattrs".to_string());
+    }
+
+    // Chapters. Embedding is an ffmpeg postprocessor; when the source has no chapters,
+    // SponsorBlock segments marked via the user's yt-dlp config (`--sponsorblock-mark`)
+    // are embedded as chapters instead.
+    let embed_chapters = postprocess.embed_chapters
+        && (ffmpeg_location.is_some() || binary::resolve::check_ffmpeg().await.is_some());
+    if postprocess.embed_chapters && !embed_chapters {
+        logger::warn_cat(
+            "download",
+            &format!(
+                "[download:{}] skipping --embed-chapters: ffmpeg not found",
+                task_id
+            ),
+        );
+    }
+    if embed_chapters {
+        args.push("--embed-chapters".to_string());
+    }
+    // Codec yt-dlp ends up downloading, for history (print-to-file appends, so start empty)
+    let codec_sidecar = codec_sidecar_path(&app, task_id).filter(|_| media);
+    if let Some(path) = &codec_sidecar {
+        let _ = std::fs::remove_file(path);
+        args.extend([
+            "--print-to-file".to_string(),
+            "after_video:%(vcodec)s".to_string(),
+            path.to_string_lossy().replace('%', "%%"),
+        ]);
+    }
+    if settings.write_chapters && media {
+        // Once per video (not per stream), as JSON; "NA" when the video has no chapters,
+        // in which case the sidecar is removed once the download finishes
+        args.extend([
+            "--print-to-file".to_string(),
+            "after_video:%(chapters)j".to_string(),
+            chapters_sidecar_template(&task.output_path),
+        ]);
+    }
+
+    // Record a live stream from its beginning (also covers premieres that are still airing)
+    if task.live_from_start && media {
+        args.push("--live-from-start".to_string());
+    }
+
+    // Site-specific headers: the settings defaults with this task's overrides
+    let headers = http_headers::merge(&settings.default_headers, task.headers.as_ref());
+    args.extend(http_headers::to_args(&headers));
+
+    // Tags and cover art from the task's preset; both are ffmpeg postprocessors
+    if postprocess.embed_metadata || postprocess.embed_thumbnail {
+        if ffmpeg_location.is_some() || binary::resolve::check_ffmpeg().await.is_some() {
+            if postprocess.embed_metadata {
+                args.push("--embed-metadata".to_string());
+            }
+            if postprocess.embed_thumbnail {
+                args.push("--embed-thumbnail".to_string());
+            }
+        } else {
+            logger::warn_cat(
+                "download",
+                &format!(
+                    "[download:{}] skipping metadata/thumbnail embedding: ffmpeg not found",
+                    task_id
+                ),
+            );
+        }
+    }
+
+    // Background throttle / foreground focus: limit bandwidth for tasks started while
+    // hidden to tray, or for every task but the focused one
+    if let Some(rate) =
+        focus::rate_limit_for(&manager, settings.throttle_rate_limit.as_deref(), task_id)
+    {
+        args.extend(["--limit-rate".to_string(), rate]);
+        manager.mark_rate_capped(task_id);
     }
 
     // Add cookie browser from settings if available (validated)
     if let Some(browser) = &settings.cookie_browser {
-        if security::sanitize_cookie_browser(browser).is_ok() {
+        if let Ok(spec) = security::parse_cookie_browser(browser) {
+            // A missing profile would only fail inside yt-dlp with a less clear error
+            if let Err(e) = browser_profiles::check_profile(&spec) {
+                handle_download_failure(&app, task_id, &e.to_string(), &db_state, &manager);
+                return;
+            }
             args.extend(["--cookies-from-browser".to_string(), browser.clone()]);
         } else {
             logger::warn_cat(
@@ -223,13 +550,97 @@ pub(super) async fn execute_download(app: AppHandle, task_id: u64) {
         }
     }
 
+    // Per-task credentials from the OS keychain
+    if task.has_credentials {
+        match credentials::load(task_id) {
+            Ok(Some(creds)) => {
+                let fields = [
+                    ("--username", creds.username),
+                    ("--password", creds.password),
+                    ("--video-password", creds.video_password),
+                ];
+                for (flag, value) in fields {
+                    if let Some(value) = value.filter(|v| !v.is_empty()) {
+                        args.extend([flag.to_string(), value]);
+                    }
+                }
+            }
+            Ok(None) => {
+                handle_download_failure(
+                    &app,
+                    task_id,
+                    "저장된 인증 정보를 찾을 수 없습니다. 다시 입력한 후 대기열에 추가하세요.",
+                    &db_state,
+                    &manager,
+                );
+                return;
+            }
+            Err(e) => {
+                handle_download_failure(&app, task_id, &e.to_string(), &db_state, &manager);
+                return;
+            }
+        }
+    }
+
+    // Refuse early when the expected size is already over the cap, instead of
+    // letting yt-dlp start (and for merged formats, possibly finish one stream)
+    if let Some(cap) = &max_filesize {
+        let cap_bytes = security::parse_filesize(cap).unwrap_or(u64::MAX);
+        if let Some(size) =
+            crate::ytdlp::probe::selected_filesize(&app, &task.video_url, &format).await
+        {
+            if size > cap_bytes {
+                handle_download_failure(
+                    &app,
+                    task_id,
+                    &format!(
+                        "예상 파일 크기({})가 최대 크기 제한({})을 초과합니다. 작업 또는 설정에서 제한을 늘리세요.",
+                        format_bytes(size),
+                        cap
+                    ),
+                    &db_state,
+                    &manager,
+                );
+                return;
+            }
+        }
+    }
+
+    // Check which audio track the selector resolves to, so a missing language is
+    // reported instead of silently downloading the default track
+    let mut recorded_lang = audio_lang.clone();
+    if let Some(lang) = &audio_lang {
+        if let Some(actual) =
+            crate::ytdlp::probe::selected_language(&app, &task.video_url, &format).await
+        {
+            if !actual
+                .to_ascii_lowercase()
+                .starts_with(&lang.to_ascii_lowercase())
+            {
+                logger::warn_cat(
+                    "download",
+                    &format!(
+                        "[download:{}] no '{}' audio track available; falling back to '{}'",
+                        task_id, lang, actual
+                    ),
+                );
+            }
+            recorded_lang = Some(actual);
+        }
+    }
+
     // Add video URL
     args.push(task.video_url.clone());
 
-    // Log the full command before spawning
+    // Log the full command before spawning (credential values redacted)
     logger::info_cat(
         "download",
-        &format!("[download:{}] spawning: {} {:?}", task_id, ytdlp_path, args),
+        &format!(
+            "[download:{}] spawning: {} {:?}",
+            task_id,
+            ytdlp_path,
+            security::redact_args(&args)
+        ),
     );
 
     // Build command with augmented PATH including app bin dir
@@ -261,6 +672,7 @@ pub(super) async fn execute_download(app: AppHandle, task_id: u64) {
         None => {
             manager.unregister_cancel(task_id);
             manager.release();
+            focus::task_ended(&app, task_id);
             process_next_pending(app);
             return;
         }
@@ -271,6 +683,7 @@ pub(super) async fn execute_download(app: AppHandle, task_id: u64) {
         None => {
             manager.unregister_cancel(task_id);
             manager.release();
+            focus::task_ended(&app, task_id);
             process_next_pending(app);
             return;
         }
@@ -278,89 +691,183 @@ pub(super) async fn execute_download(app: AppHandle, task_id: u64) {
 
     // Clone necessary data for the async task
     let db_state_clone = db_state.inner().clone();
+    let manager_clone = manager.inner().clone();
     let app_clone = app.clone();
 
     // Save JoinHandle for stdout reader task
-    // Returns the actual output file path parsed from yt-dlp stdout
-    let stdout_handle: tokio::task::JoinHandle<Option<String>> = tokio::spawn(async move {
-        let mut reader = BufReader::new(stdout);
-        let mut buf = Vec::new();
-        let mut last_progress_percent: Option<f32> = None;
-        let mut last_progress_update = tokio::time::Instant::now() - Duration::from_secs(1);
-        let mut actual_file_path: Option<String> = None;
+    // Returns the actual output file path parsed from yt-dlp stdout, every
+    // downloaded file's final path (more than one for separate streams), and the
+    // "File is larger than max-filesize" line if yt-dlp skipped the download,
+    // and the subtitle files written by a subtitle-only task
+    let stdout_handle: tokio::task::JoinHandle<StdoutSummary> = {
+        let subtitle_format = subtitle_format.clone();
+        tokio::spawn(async move {
+            let mut reader = BufReader::new(stdout);
+            let mut buf = Vec::new();
+            let mut last_progress_percent: Option<f32> = None;
+            let mut last_progress_update = tokio::time::Instant::now() - Duration::from_secs(1);
+            let mut actual_file_path: Option<String> = None;
+            let mut stream_paths: Vec<String> = Vec::new();
+            let mut size_limit_line: Option<String> = None;
+            let mut subtitle_paths: Vec<String> = Vec::new();
+            let mut last_milestone = 0u32;
 
-        loop {
-            buf.clear();
-            match reader.read_until(b'\n', &mut buf).await {
-                Ok(0) => break, // EOF
-                Ok(_) => {}
-                Err(_) => continue, // non-fatal read error, keep going
-            }
-            let line = String::from_utf8_lossy(&buf).trim_end().to_string();
-            // Capture actual file path from yt-dlp output lines:
-            // "[download] Destination: /path/to/file.mp4"
-            // "[Merger] Merging formats into "/path/to/file.mkv""
-            // "[ExtractAudio] Destination: /path/to/file.mp3"
-            if let Some(path) = line.strip_prefix("[Merger] Merging formats into \"") {
-                if let Some(path) = path.strip_suffix('"') {
-                    actual_file_path = Some(path.to_string());
+            loop {
+                buf.clear();
+                match reader.read_until(b'\n', &mut buf).await {
+                    Ok(0) => break, // EOF
+                    Ok(_) => {}
+                    Err(_) => continue, // non-fatal read error, keep going
+                }
+                let line = String::from_utf8_lossy(&buf).trim_end().to_string();
+                // Capture actual file path from yt-dlp output lines:
+                // "[download] Destination: /path/to/file.mp4"
+                // "[Merger] Merging formats into "/path/to/file.mkv""
+                // "[ExtractAudio] Destination: /path/to/file.mp3"
+                // "[VideoConvertor] ...; Destination: /path/to/file.mp4" (recode)
+                // "[VideoRemuxer] ...; Destination: /path/to/file.mp4" (autoContainer remux)
+                // "[MoveFiles] Moving file "/tmp/file.mp4" to "/path/to/file.mp4"" (temp dir mode)
+                if let Some(rest) = line.strip_prefix("[MoveFiles] Moving file \"") {
+                    if let Some((src, dest)) = rest.split_once("\" to \"") {
+                        if let Some(dest) = dest.strip_suffix('"') {
+                            match stream_paths.iter_mut().find(|p| p.as_str() == src) {
+                                Some(p) => *p = dest.to_string(),
+                                None => stream_paths.push(dest.to_string()),
+                            }
+                            if let Some(p) = subtitle_paths.iter_mut().find(|p| p.as_str() == src) {
+                                *p = dest.to_string();
+                            }
+                            actual_file_path = Some(dest.to_string());
+                        }
+                    }
+                } else if let Some(path) = line.strip_prefix("[Merger] Merging formats into \"") {
+                    if let Some(path) = path.strip_suffix('"') {
+                        actual_file_path = Some(path.to_string());
+                    }
+                } else if let Some(path) = line.strip_prefix("[download] Destination: ") {
+                    stream_paths.push(path.trim().to_string());
+                    actual_file_path = Some(path.trim().to_string());
+                } else if let Some(path) = line.strip_prefix("[ExtractAudio] Destination: ") {
+                    actual_file_path = Some(path.trim().to_string());
+                } else if let Some(rest) = line.strip_prefix("[VideoConvertor] ") {
+                    // "[VideoConvertor] Converting video from webm to mp4; Destination: /path/to/file.mp4"
+                    if let Some((_, path)) = rest.split_once("Destination: ") {
+                        actual_file_path = Some(path.trim().to_string());
+                    }
+                } else if let Some(rest) = line.strip_prefix("[VideoRemuxer] ") {
+                    // "[VideoRemuxer] Remuxing video from webm to mkv; Destination: /path/to/file.mkv"
+                    if let Some((_, path)) = rest.split_once("Destination: ") {
+                        actual_file_path = Some(path.trim().to_string());
+                    }
+                } else if let Some(path) =
+                    subtitles::written_path(&line, subtitle_format.as_deref())
+                {
+                    subtitle_paths.push(path);
+                } else if line.contains("File is larger than max-filesize") {
+                    // "[download] File is larger than max-filesize (X bytes > Y bytes). Aborting."
+                    // yt-dlp exits 0 here without writing a file
+                    size_limit_line = Some(line.clone());
+                }
+                if progress::is_postprocess_line(&line) {
+                    manager_clone.set_stage(task_id, TaskStage::Processing);
                 }
-            } else if let Some(path) = line
-                .strip_prefix("[download] Destination: ")
-                .or_else(|| line.strip_prefix("[ExtractAudio] Destination: "))
-            {
-                actual_file_path = Some(path.trim().to_string());
-            }
 
-            if let Some(progress_info) = progress::parse_progress_line(&line) {
-                let now = tokio::time::Instant::now();
-                let should_update = match last_progress_percent {
-                    None => true,
-                    Some(prev) => {
-                        (progress_info.percent - prev).abs() >= 0.2
-                            || now.duration_since(last_progress_update)
-                                >= Duration::from_millis(500)
-                            || progress_info.percent >= 100.0
+                if let Some(progress_info) = progress::parse_progress_line(&line) {
+                    if let Some(bps) = progress_info.speed_bytes {
+                        manager_clone.record_speed(task_id, bps);
                     }
-                };
 
-                if !should_update {
-                    continue;
-                }
+                    // Coarse milestone for watch_queue subscribers
+                    let milestone = (progress_info.percent as u32 / 25) * 25;
+                    if !progress_info.indeterminate && milestone > last_milestone && milestone < 100
+                    {
+                        last_milestone = milestone;
+                        manager_clone.publish(QueueEvent {
+                            percent: Some(milestone),
+                            ..QueueEvent::task(QueueEventKind::Progress, task_id)
+                        });
+                    }
+
+                    let now = tokio::time::Instant::now();
+                    let should_update = match last_progress_percent {
+                        // A growing live download has no percent to compare
+                        Some(_) if progress_info.indeterminate => {
+                            now.duration_since(last_progress_update) >= Duration::from_millis(500)
+                        }
+                        None => true,
+                        Some(prev) => {
+                            (progress_info.percent - prev).abs() >= 0.2
+                                || now.duration_since(last_progress_update)
+                                    >= Duration::from_millis(500)
+                                || progress_info.percent >= 100.0
+                        }
+                    };
+
+                    if !should_update {
+                        continue;
+                    }
 
-                let speed = progress_info.speed.as_deref().unwrap_or("...").to_string();
-                let eta = progress_info.eta.as_deref().unwrap_or("...").to_string();
+                    let speed = progress_info.speed.as_deref().unwrap_or("...").to_string();
+                    let eta = progress_info.eta.as_deref().unwrap_or("...").to_string();
 
-                // Send global progress event
-                let _ = app_clone.emit(
-                    "download-event",
-                    GlobalDownloadEvent {
+                    // Send global progress event
+                    let _ = app_clone.emit(
+                        "download-event",
+                        GlobalDownloadEvent {
+                            task_id,
+                            // Live downloads grow without a known total: no percent, report bytes instead
+                            event_type: if progress_info.indeterminate {
+                                "liveProgress".to_string()
+                            } else {
+                                "progress".to_string()
+                            },
+                            percent: (!progress_info.indeterminate)
+                                .then_some(progress_info.percent),
+                            speed: Some(speed.clone()),
+                            eta: Some(eta.clone()),
+                            file_path: None,
+                            file_size: None,
+                            message: None,
+                            downloaded_bytes: progress_info.downloaded_bytes,
+                            elapsed_secs: progress_info.elapsed_secs,
+                        },
+                    );
+
+                    manager_clone.record_progress(TaskProgress {
                         task_id,
-                        event_type: "progress".to_string(),
-                        percent: Some(progress_info.percent),
-                        speed: Some(speed.clone()),
-                        eta: Some(eta.clone()),
-                        file_path: None,
-                        file_size: None,
-                        message: None,
-                    },
-                );
+                        stage: if progress_info.indeterminate {
+                            TaskStage::Live
+                        } else {
+                            TaskStage::Downloading
+                        },
+                        percent: (!progress_info.indeterminate).then_some(progress_info.percent),
+                        speed: progress_info.speed.clone(),
+                        eta: progress_info.eta.clone(),
+                        downloaded_bytes: progress_info.downloaded_bytes,
+                        updated_at: chrono::Utc::now().timestamp(),
+                    });
 
-                // Update DB progress
-                let _ = db_state_clone.update_download_progress(
-                    task_id,
-                    progress_info.percent,
-                    Some(&speed),
-                    Some(&eta),
-                );
+                    // Update DB progress
+                    let _ = db_state_clone.update_download_progress(
+                        task_id,
+                        progress_info.percent,
+                        Some(&speed),
+                        Some(&eta),
+                    );
 
-                last_progress_percent = Some(progress_info.percent);
-                last_progress_update = now;
+                    last_progress_percent = Some(progress_info.percent);
+                    last_progress_update = now;
+                }
             }
-        }
 
-        actual_file_path
-    });
+            (
+                actual_file_path,
+                stream_paths,
+                size_limit_line,
+                subtitle_paths,
+            )
+        })
+    };
 
     // Collect stderr for error messages (byte-level reader for non-UTF-8 resilience)
     let stderr_handle = tokio::spawn(async move {
@@ -409,6 +916,7 @@ pub(super) async fn execute_download(app: AppHandle, task_id: u64) {
             emit_download_error(&app, task_id, error_msg.to_string());
             manager.unregister_cancel(task_id);
             manager.release();
+            focus::task_ended(&app, task_id);
             process_next_pending(app);
             return;
         }
@@ -417,6 +925,19 @@ pub(super) async fn execute_download(app: AppHandle, task_id: u64) {
             kill_process_tree(&mut child).await;
             let _ = stdout_handle.await;
             let _ = stderr_handle.await;
+            if manager.take_requeue(task_id) {
+                // Stopped for recovery (e.g. after system sleep): back to pending, resumes via --continue
+                let _ = db_state.update_download_status(task_id, &DownloadStatus::Pending, None);
+                logger::info_cat(
+                    "download",
+                    &format!("[download:{}] stopped and re-queued", task_id),
+                );
+                manager.publish(QueueEvent::task(QueueEventKind::Added, task_id));
+                manager.unregister_cancel(task_id);
+                manager.release();
+                process_next_pending(app);
+                return;
+            }
             let _ = db_state.update_download_status(task_id, &DownloadStatus::Cancelled, None);
             let _ = app.emit(
                 "download-event",
@@ -429,17 +950,21 @@ pub(super) async fn execute_download(app: AppHandle, task_id: u64) {
                     file_path: None,
                     file_size: None,
                     message: Some("다운로드가 취소되었습니다.".to_string()),
+                    downloaded_bytes: None,
+                    elapsed_secs: None,
                 },
             );
             manager.unregister_cancel(task_id);
             manager.release();
+            focus::task_ended(&app, task_id);
             process_next_pending(app);
             return;
         }
     };
 
     // Await both stdout and stderr handles before checking result
-    let actual_file_path = stdout_handle.await.ok().flatten();
+    let (actual_file_path, stream_paths, size_limit_line, subtitle_paths) =
+        stdout_handle.await.unwrap_or_default();
     let stderr_output = stderr_handle.await.unwrap_or_default();
 
     // Log process exit for debugging
@@ -458,15 +983,124 @@ pub(super) async fn execute_download(app: AppHandle, task_id: u64) {
         );
     }
 
-    if status.success() {
-        // Use the actual file path parsed from yt-dlp stdout, falling back to the template path
-        let file_path = actual_file_path.unwrap_or_else(|| task.output_path.clone());
+    // xattrs are best-effort: a filesystem without support must not fail a finished download
+    let xattr_only_failure = settings.write_xattrs
+        && !status.success()
+        && only_xattr_errors(&stderr_output)
+        && actual_file_path
+            .as_deref()
+            .is_some_and(|p| std::path::Path::new(p).exists());
+    if settings.write_xattrs && stderr_output.lines().any(is_xattr_message) {
+        logger::warn_cat(
+            "download",
+            &format!(
+                "[download:{}] could not write extended attributes (unsupported filesystem or missing xattr tool); file kept",
+                task_id
+            ),
+        );
+    }
+
+    if task.subtitles_only && status.success() {
+        subtitles::finish(&app, &task, subtitle_paths).await;
+    } else if (status.success() || xattr_only_failure) && size_limit_line.is_none() {
+        // Use the actual file path parsed from yt-dlp stdout, falling back to the template path.
+        // Separate streams produce one file per format; the first (video) is the primary one.
+        let separate_paths = if task.keep_separate_streams {
+            stream_paths
+        } else {
+            Vec::new()
+        };
+        let file_path = separate_paths
+            .first()
+            .cloned()
+            .or(actual_file_path)
+            .unwrap_or_else(|| task.output_path.clone());
         let file_size = tokio::fs::metadata(&file_path)
             .await
             .ok()
             .map(|m| m.len())
             .unwrap_or(0);
 
+        let duration_secs =
+            crate::ytdlp::probe::file_duration(&app, std::path::Path::new(&file_path)).await;
+        // Only counts if the file actually carries chapters (the source may have none)
+        let chapters_embedded = embed_chapters
+            && crate::ytdlp::probe::file_chapter_count(&app, std::path::Path::new(&file_path))
+                .await
+                .is_some_and(|n| n > 0);
+
+        let verification = if settings.verify_downloads {
+            crate::ytdlp::verify::verify_file(&app, &task.video_url, Path::new(&file_path)).await
+        } else {
+            None
+        };
+        // A suspect file gets one fresh download before it is recorded
+        let suspect = verification
+            .as_ref()
+            .is_some_and(|v| v.status == crate::ytdlp::verify::SUSPECT);
+        if suspect
+            && settings.retry_suspect_downloads
+            && db_state.claim_verify_retry(task_id).unwrap_or(false)
+        {
+            for path in std::iter::once(&file_path).chain(separate_paths.iter().skip(1)) {
+                let _ = tokio::fs::remove_file(path).await;
+            }
+            let _ = db_state.update_download_status(task_id, &DownloadStatus::Pending, None);
+            logger::warn_cat(
+                "download",
+                &format!(
+                    "[download:{}] verification failed, re-queued once: {}",
+                    task_id,
+                    verification
+                        .as_ref()
+                        .and_then(|v| v.error.as_deref())
+                        .unwrap_or("")
+                ),
+            );
+            manager.publish(QueueEvent::task(QueueEventKind::Added, task_id));
+            manager.unregister_cancel(task_id);
+            manager.release();
+            process_next_pending(app);
+            return;
+        }
+
+        // Replace the earlier files with the verified download
+        let (file_path, separate_paths) = match &staging {
+            Some(staging) => match swap_in(staging, &home) {
+                Ok(()) => (
+                    unstage(&file_path, staging, &home),
+                    separate_paths
+                        .iter()
+                        .map(|p| unstage(p, staging, &home))
+                        .collect(),
+                ),
+                Err(e) => {
+                    let error_msg = format!(
+                        "다운로드한 파일로 기존 파일을 교체하지 못했습니다: {} ({})",
+                        e,
+                        staging.display()
+                    );
+                    handle_download_failure(&app, task_id, &error_msg, &db_state, &manager);
+                    return;
+                }
+            },
+            None => (file_path, separate_paths),
+        };
+        if settings.write_chapters && media {
+            let sidecar = Path::new(&file_path).with_extension("chapters.json");
+            if let Ok(text) = tokio::fs::read_to_string(&sidecar).await {
+                match chapters_json(&text) {
+                    // print-to-file appends, so a retried download can leave older lines
+                    Some(json) => {
+                        let _ = tokio::fs::write(&sidecar, format!("{}\n", json)).await;
+                    }
+                    None => {
+                        let _ = tokio::fs::remove_file(&sidecar).await;
+                    }
+                }
+            }
+        }
+
         // Mark as completed and insert history in a single transaction
         let completed_at = chrono::Utc::now().timestamp();
         let history_item = HistoryItem {
@@ -475,11 +1109,42 @@ pub(super) async fn execute_download(app: AppHandle, task_id: u64) {
             video_id: task.video_id.clone(),
             title: task.title.clone(),
             quality_label: task.quality_label.clone(),
-            format: task.format_id.clone(),
+            format: if task.keep_separate_streams {
+                "bv,ba".to_string()
+            } else {
+                task.format_id.clone()
+            },
             file_path: file_path.clone(),
             file_size: Some(file_size),
             downloaded_at: completed_at,
+            recode_target: recode_target.clone(),
+            max_height: max_height_applied,
+            duration_secs,
+            superseded_by: None,
+            chapters_embedded,
+            remux_target: remux_target.clone(),
+            uploader: None,
+            container: crate::ytdlp::normalize::container_from_path(&file_path),
+            tags: Vec::new(),
+            audio_lang: recorded_lang.clone(),
+            verify_status: verification.as_ref().map(|v| v.status.to_string()),
+            verify_error: verification.and_then(|v| v.error),
+            destinations: Some(output_paths::destinations(
+                &settings,
+                &format,
+                &home.to_string_lossy(),
+            )),
+            preset: task.preset.clone(),
+            vcodec: codec_sidecar
+                .as_deref()
+                .and_then(|p| std::fs::read_to_string(p).ok())
+                .and_then(|text| parse_vcodec(&text)),
+            is_favorite: false,
+            user_tags: task.user_tags.clone(),
         };
+        if let Some(path) = &codec_sidecar {
+            let _ = std::fs::remove_file(path);
+        }
 
         if let Err(e) = db_state.complete_and_record(task_id, completed_at, &history_item) {
             logger::error_cat(
@@ -493,6 +1158,62 @@ pub(super) async fn execute_download(app: AppHandle, task_id: u64) {
             let _ = db_state.mark_completed(task_id, completed_at);
         }
 
+        // Record the remaining stream files (e.g. audio) as their own history entries
+        let mut total_bytes = file_size;
+        for path in separate_paths.iter().skip(1) {
+            let size = tokio::fs::metadata(path).await.ok().map(|m| m.len());
+            total_bytes += size.unwrap_or(0);
+            let item = HistoryItem {
+                file_path: path.clone(),
+                file_size: size,
+                ..history_item.clone()
+            };
+            if let Err(e) = db_state.insert_history(&item) {
+                logger::error_cat(
+                    "download",
+                    &format!(
+                        "[download:{}] failed to record stream {}: {}",
+                        task_id, path, e
+                    ),
+                );
+            }
+        }
+
+        crate::ytdlp::bandwidth::record(&app, total_bytes);
+
+        // Re-download that replaces an earlier file saved under a different name
+        if task.replace_existing {
+            if let Some(old) = task
+                .replaces_history_id
+                .and_then(|id| db_state.get_history_item(id).ok().flatten())
+            {
+                let old_path = std::path::Path::new(&old.file_path);
+                if old_path != std::path::Path::new(&file_path) && old_path.exists() {
+                    match tokio::fs::remove_file(old_path).await {
+                        Ok(()) => logger::info_cat(
+                            "download",
+                            &format!(
+                                "[download:{}] removed replaced file {}",
+                                task_id, old.file_path
+                            ),
+                        ),
+                        Err(e) => logger::warn_cat(
+                            "download",
+                            &format!(
+                                "[download:{}] failed to remove replaced file {}: {}",
+                                task_id, old.file_path, e
+                            ),
+                        ),
+                    }
+                }
+            }
+        }
+
+        // Credentials are no longer needed once the file is on disk
+        if task.has_credentials {
+            credentials::delete(task_id);
+        }
+
         logger::info_cat(
             "download",
             &format!(
@@ -513,16 +1234,39 @@ pub(super) async fn execute_download(app: AppHandle, task_id: u64) {
                 file_path: Some(file_path),
                 file_size: Some(file_size),
                 message: None,
+                downloaded_bytes: None,
+                elapsed_secs: None,
             },
         );
+        manager.publish(QueueEvent::task(QueueEventKind::Completed, task_id));
     } else {
         // Download failed
-        let error_message = if let Some(code) = status.code() {
+        let error_message = if let Some(line) = &size_limit_line {
+            format!(
+                "파일이 최대 크기 제한({})보다 커서 다운로드하지 않았습니다. 작업 또는 설정에서 제한을 늘리세요.\n\n[yt-dlp]: {}",
+                max_filesize.as_deref().unwrap_or("?"),
+                line
+            )
+        } else if let Some(code) = status.code() {
             match code {
                 1 => {
-                    if stderr_output.contains("Could not copy") && stderr_output.contains("cookie")
+                    if is_auth_failure(&stderr_output) {
+                        "인증에 실패했습니다. 사용자 이름, 비밀번호 또는 동영상 암호를 확인하세요."
+                            .to_string()
+                    } else if stderr_output.contains("Could not copy")
+                        && stderr_output.contains("cookie")
                     {
                         "브라우저 쿠키에 접근할 수 없습니다. 브라우저를 완전히 종료하거나, Firefox 쿠키를 사용하세요.".to_string()
+                    } else if http_headers::is_forbidden(&stderr_output) {
+                        let hint = if headers.referer.is_none() {
+                            "이 사이트는 Referer 헤더가 필요할 수 있습니다. 작업 또는 설정에서 Referer(동영상이 삽입된 페이지 주소)를 지정하세요."
+                        } else {
+                            "지정한 Referer/User-Agent 헤더가 거부되었을 수 있습니다. 헤더 설정을 확인하세요."
+                        };
+                        format!(
+                            "서버가 요청을 거부했습니다(HTTP 403). {}\n\n[stderr]: {}",
+                            hint, stderr_output
+                        )
                     } else if stderr_output.is_empty() {
                         "다운로드 중 오류가 발생했습니다.".to_string()
                     } else {
@@ -585,14 +1329,61 @@ pub(super) async fn execute_download(app: AppHandle, task_id: u64) {
             Some(&sanitized_error),
         );
         emit_download_error(&app, task_id, sanitized_error);
+
+        // Transient failures go back in the queue after a growing wait
+        let policy = retry::effective(&task, &settings);
+        let kind = crate::ytdlp::error_kind::classify_download_error(&error_message);
+        if retry::is_retryable(kind) && task.retry_count < policy.max_retries {
+            schedule_retry(&app, task_id, policy.backoff_secs);
+        }
     }
 
     // Release the download slot and process next pending
     manager.unregister_cancel(task_id);
     manager.release();
+    focus::task_ended(&app, task_id);
     process_next_pending(app);
 }
 
+/// Re-queue a failed task after its backoff, unless it was retried, cancelled
+/// or removed by hand in the meantime.
+fn schedule_retry(app: &AppHandle, task_id: u64, backoff_secs: u32) {
+    let db_state = app.state::<crate::DbState>();
+    let attempt = match db_state.bump_retry_count(task_id) {
+        Ok(n) => n,
+        Err(e) => {
+            logger::warn_cat(
+                "download",
+                &format!("[download:{}] could not schedule retry: {}", task_id, e),
+            );
+            return;
+        }
+    };
+    let wait = retry::delay(backoff_secs, attempt);
+    logger::info_cat(
+        "download",
+        &format!(
+            "[download:{}] retry {} in {}s",
+            task_id,
+            attempt,
+            wait.as_secs()
+        ),
+    );
+    let app = app.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(wait).await;
+        let requeued = app
+            .state::<crate::DbState>()
+            .requeue_failed(task_id)
+            .unwrap_or(false);
+        if requeued {
+            app.state::<Arc<DownloadManager>>()
+                .publish(QueueEvent::task(QueueEventKind::Added, task_id));
+            process_next_pending(app);
+        }
+    });
+}
+
 /// Public wrapper for process_next_pending (used by retry_download in commands.rs)
 pub fn process_next_pending_public(app: AppHandle) {
     process_next_pending(app);
@@ -604,12 +1395,30 @@ pub(super) fn process_next_pending(app: AppHandle) {
 
     // Try to start pending tasks while slots are available
     while manager.try_acquire() {
+        // Honor the start delay; retry once it has elapsed
+        let previous_start = match manager.try_mark_start() {
+            Ok(previous) => previous,
+            Err(remaining) => {
+                manager.release();
+                if manager.claim_wake() {
+                    let app_wake = app.clone();
+                    tokio::spawn(async move {
+                        tokio::time::sleep(remaining).await;
+                        app_wake.state::<Arc<DownloadManager>>().clear_wake();
+                        process_next_pending(app_wake);
+                    });
+                }
+                break;
+            }
+        };
+
         // Use claim_next_pending for atomic dequeue (prevents double-dispatch race condition)
         match db_state.claim_next_pending() {
             Ok(Some(task)) => {
                 let app_clone = app.clone();
                 let app_panic_guard = app.clone();
                 let task_id = task.id;
+                super::limits::note_start(&app, task_id);
                 tokio::spawn(async move {
                     let result = tokio::spawn(async move {
                         execute_download(app_clone, task_id).await;
@@ -628,6 +1437,7 @@ pub(super) fn process_next_pending(app: AppHandle) {
             }
             _ => {
                 // No more pending tasks, release the slot
+                manager.undo_start(previous_start);
                 manager.release();
                 break;
             }
@@ -635,10 +1445,520 @@ pub(super) fn process_next_pending(app: AppHandle) {
     }
 }
 
+/// Detect yt-dlp stderr reporting rejected login or video password.
+fn is_auth_failure(stderr: &str) -> bool {
+    const PATTERNS: &[&str] = &[
+        "incorrect username or password",
+        "invalid username or password",
+        "wrong password",
+        "password is incorrect",
+        "unable to log in",
+        "login failed",
+        "this video is protected by a password",
+        "use --video-password",
+        "use --username and --password",
+    ];
+    let lower = stderr.to_lowercase();
+    PATTERNS.iter().any(|p| lower.contains(p))
+}
+
+/// Whether a yt-dlp output line is about writing extended attributes.
+fn is_xattr_message(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    (lower.starts_with("warning:") || lower.starts_with("error:"))
+        && (lower.contains("xattr") || lower.contains("extended attributes"))
+}
+
+/// True when stderr has errors and every one of them is an xattr write failure.
+fn only_xattr_errors(stderr: &str) -> bool {
+    let mut errors = stderr
+        .lines()
+        .filter(|l| l.starts_with("ERROR:"))
+        .peekable();
+    errors.peek().is_some() && errors.all(is_xattr_message)
+}
+
+/// Generic selectors that pick a video stream (alone or combined with audio)
+const VIDEO_SELECTORS: &[&str] = &[
+    "bestvideo*",
+    "bestvideo",
+    "best*",
+    "best",
+    "bv*",
+    "bv",
+    "b*",
+    "b",
+];
+
+/// Add a `[height<=N]` filter to every video selector in a generic format string.
+/// Returns None when the format is explicit: it already filters on height, or it
+/// names specific format ids (e.g. "137+140") instead of best/worst selectors.
+fn apply_max_height(format: &str, max_height: u32) -> Option<String> {
+    if format.contains("height") {
+        return None;
+    }
+    add_selector_filter(
+        format,
+        VIDEO_SELECTORS,
+        &format!("[height<={}]", max_height),
+    )
+}
+
+/// Prefer audio in `lang` (`[language^=en]` also matches "en-US"), keeping the
+/// unfiltered selector as a fallback for videos without that track. Each stream of
+/// a separate-streams format ("bv,ba") gets its own fallback. Returns None when
+/// the format already filters on language or has no generic audio selector.
+fn apply_audio_lang(format: &str, lang: &str) -> Option<String> {
+    if format.contains("language") {
+        return None;
+    }
+    // Combined formats carry the audio track too
+    const AUDIO_SELECTORS: &[&str] = &["bestaudio", "ba", "best*", "best", "b*", "b"];
+    let filter = format!("[language^={}]", lang);
+    let mut applied = false;
+    let parts: Vec<String> = split_top_level(format, ',')
+        .into_iter()
+        .map(
+            |part| match add_selector_filter(part, AUDIO_SELECTORS, &filter) {
+                Some(filtered) => {
+                    applied = true;
+                    format!("{}/{}", filtered, part)
+                }
+                None => part.to_string(),
+            },
+        )
+        .collect();
+    applied.then(|| parts.join(","))
+}
+
+/// Try each preferred codec in order (`codec_pref::filters`) on every video
+/// selector, falling back to the selector as given. Each stream of a
+/// separate-streams format gets its own fallback chain. Returns None when the
+/// format already filters on vcodec or has no generic video selector.
+fn apply_codec_preference(format: &str, preference: &[String]) -> Option<String> {
+    let filters = crate::ytdlp::codec_pref::filters(preference);
+    if filters.is_empty() || format.contains("vcodec") {
+        return None;
+    }
+    let mut applied = false;
+    let parts: Vec<String> = split_top_level(format, ',')
+        .into_iter()
+        .map(|part| {
+            let mut chain: Vec<String> = filters
+                .iter()
+                .filter_map(|f| add_selector_filter(part, VIDEO_SELECTORS, f))
+                .collect();
+            if chain.is_empty() {
+                return part.to_string();
+            }
+            applied = true;
+            chain.push(part.to_string());
+            chain.join("/")
+        })
+        .collect();
+    applied.then(|| parts.join(","))
+}
+
+/// Split `format` on `sep` outside of `[...]` filters and `(...)` groups.
+fn split_top_level(format: &str, sep: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0u32;
+    let mut start = 0;
+    for (i, c) in format.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth = depth.saturating_sub(1),
+            c if c == sep && depth == 0 => {
+                parts.push(&format[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&format[start..]);
+    parts
+}
+
+/// Insert `filter` right after every selector in `format` named in `selectors`.
+/// Returns None when no selector matched.
+fn add_selector_filter(format: &str, selectors: &[&str], filter: &str) -> Option<String> {
+    let mut applied = false;
+    let mut out = String::with_capacity(format.len() + filter.len() * 2);
+    let mut name = String::new();
+    let mut depth = 0u32;
+
+    // Walk selectors separated by / + , ( ) and tag matching ones right after their name
+    for c in format.chars() {
+        match c {
+            '[' => {
+                if depth == 0 && selectors.contains(&name.as_str()) {
+                    name.push_str(filter);
+                    applied = true;
+                }
+                depth += 1;
+                name.push(c);
+            }
+            ']' => {
+                depth = depth.saturating_sub(1);
+                name.push(c);
+            }
+            '/' | '+' | ',' | '(' | ')' if depth == 0 => {
+                if selectors.contains(&name.as_str()) {
+                    name.push_str(filter);
+                    applied = true;
+                }
+                out.push_str(&std::mem::take(&mut name));
+                out.push(c);
+            }
+            _ => name.push(c),
+        }
+    }
+    if selectors.contains(&name.as_str()) {
+        name.push_str(filter);
+        applied = true;
+    }
+    out.push_str(&name);
+
+    applied.then_some(out)
+}
+
+/// A download's `--format` and what went into it.
+struct Selector {
+    format: String,
+    /// The `max_height` cap, when the format didn't already limit height
+    max_height: Option<u32>,
+    /// An audio language was asked for but the format has no generic audio selector
+    audio_lang_ignored: bool,
+}
+
+/// Apply separate streams, the `max_height` cap, the audio language and the
+/// codec preference to `format_id`, in that order.
+fn build_selector(
+    settings: &AppSettings,
+    format_id: &str,
+    separate_streams: bool,
+    audio_lang: Option<&str>,
+) -> Selector {
+    let format = if separate_streams {
+        "bv,ba".to_string()
+    } else {
+        format_id.to_string()
+    };
+    // Global resolution cap; a format that already limits height is left alone
+    let capped = settings
+        .max_height
+        .filter(|h| security::sanitize_max_height(*h).is_ok())
+        .and_then(|h| apply_max_height(&format, h).map(|f| (f, h)));
+    let max_height = capped.as_ref().map(|(_, h)| *h);
+    let format = capped.map(|(f, _)| f).unwrap_or(format);
+    let lang_format = audio_lang.and_then(|lang| apply_audio_lang(&format, lang));
+    let audio_lang_ignored = audio_lang.is_some() && lang_format.is_none();
+    let format = lang_format.unwrap_or(format);
+    // Preferred codecs, each tried before the next
+    let format = apply_codec_preference(&format, &settings.codec_preference).unwrap_or(format);
+    Selector {
+        format,
+        max_height,
+        audio_lang_ignored,
+    }
+}
+
+/// The `--format` a download of `request` runs with, built the same way
+/// `execute_download` builds it.
+pub fn effective_selector(settings: &AppSettings, request: &DownloadRequest) -> String {
+    let audio_lang = request
+        .audio_lang
+        .as_deref()
+        .filter(|_| !request.subtitles_only)
+        .and_then(|l| security::sanitize_language(l).ok());
+    build_selector(
+        settings,
+        &request.format_id,
+        request.keep_separate_streams && !request.subtitles_only,
+        audio_lang.as_deref(),
+    )
+    .format
+}
+
+/// Folder a replacing download of `task_id` is written to before it is swapped in.
+fn staging_dir(home: &Path, task_id: u64) -> PathBuf {
+    home.join(format!(".replace-{}", task_id))
+}
+
+/// Move everything under `staging` to the same place under `home`, over the files
+/// there, then remove `staging`.
+fn swap_in(staging: &Path, home: &Path) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(staging)? {
+        let entry = entry?;
+        let target = home.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            std::fs::create_dir_all(&target)?;
+            swap_in(&entry.path(), &target)?;
+        } else {
+            std::fs::rename(entry.path(), &target)?;
+        }
+    }
+    std::fs::remove_dir(staging)
+}
+
+/// Where `path`, written under `staging`, ends up after `swap_in`.
+fn unstage(path: &str, staging: &Path, home: &Path) -> String {
+    match Path::new(path).strip_prefix(staging) {
+        Ok(rest) => home.join(rest).to_string_lossy().to_string(),
+        Err(_) => path.to_string(),
+    }
+}
+
+/// Give each stream its own filename when downloading formats separately,
+/// matching yt-dlp's `.f<format_id>` naming for unmerged formats.
+fn separate_stream_template(output_path: &str) -> String {
+    if output_path.contains("%(format_id)s") {
+        return output_path.to_string();
+    }
+    match output_path.strip_suffix(".%(ext)s") {
+        Some(stem) => format!("{}.f%(format_id)s.%(ext)s", stem),
+        None => format!("{}.f%(format_id)s", output_path),
+    }
+}
+
+/// `<app cache>/codecs`, where the per-task codec sidecars go.
+pub fn codec_cache_dir(app: &AppHandle) -> Option<PathBuf> {
+    app.path().app_cache_dir().ok().map(|d| d.join("codecs"))
+}
+
+/// `<app cache>/codecs/<task id>.txt`, for `--print-to-file`. None when the
+/// folder can't be created.
+fn codec_sidecar_path(app: &AppHandle, task_id: u64) -> Option<PathBuf> {
+    let dir = codec_cache_dir(app)?;
+    std::fs::create_dir_all(&dir).ok()?;
+    Some(dir.join(format!("{}.txt", task_id)))
+}
+
+/// Last codec written to the sidecar; None for audio-only or unknown.
+fn parse_vcodec(text: &str) -> Option<String> {
+    text.lines()
+        .map(str::trim)
+        .rfind(|l| !l.is_empty())
+        .filter(|l| *l != "none" && *l != "NA")
+        .map(String::from)
+}
+
+/// `<name>.chapters.json` next to the output, for `--print-to-file`.
+fn chapters_sidecar_template(output_path: &str) -> String {
+    let stem = output_path.strip_suffix(".%(ext)s").unwrap_or(output_path);
+    format!("{}.chapters.json", stem)
+}
+
+/// Last chapter list written to the sidecar; None when the video has no chapters.
+fn chapters_json(text: &str) -> Option<&str> {
+    text.lines()
+        .map(str::trim)
+        .rfind(|l| !l.is_empty())
+        .filter(|l| {
+            serde_json::from_str::<serde_json::Value>(l)
+                .is_ok_and(|v| v.as_array().is_some_and(|a| !a.is_empty()))
+        })
+}
+
+/// Split an absolute output path into the directory part and the yt-dlp template part,
+/// which starts at the first component containing a `%(...)` field.
+pub(super) fn split_output_template(output_path: &str) -> (PathBuf, String) {
+    let path = Path::new(output_path);
+    let mut home = PathBuf::new();
+    let mut rest = PathBuf::new();
+    for component in path.components() {
+        let part = component.as_os_str();
+        if rest.as_os_str().is_empty() && !part.to_string_lossy().contains("%(") {
+            home.push(part);
+        } else {
+            rest.push(part);
+        }
+    }
+    // No template field at all: treat the last component as the filename
+    if rest.as_os_str().is_empty() {
+        if let Some(name) = path.file_name() {
+            rest.push(name);
+            home.pop();
+        }
+    }
+    (home, rest.to_string_lossy().to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn swap_in_replaces_originals_and_maps_paths() {
+        let home = std::env::temp_dir().join(format!("swap-in-test-{}", std::process::id()));
+        let staging = staging_dir(&home, 7);
+        std::fs::create_dir_all(staging.join("sub")).unwrap();
+        std::fs::write(home.join("video.mp4"), "old").unwrap();
+        std::fs::write(staging.join("video.mp4"), "new").unwrap();
+        std::fs::write(staging.join("sub").join("audio.m4a"), "a").unwrap();
+
+        let staged = staging.join("video.mp4").to_string_lossy().to_string();
+        swap_in(&staging, &home).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(home.join("video.mp4")).unwrap(),
+            "new"
+        );
+        assert!(home.join("sub").join("audio.m4a").exists());
+        assert!(!staging.exists());
+        assert_eq!(
+            unstage(&staged, &staging, &home),
+            home.join("video.mp4").to_string_lossy()
+        );
+        let _ = std::fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn split_output_template_separates_dir_and_template() {
+        let (home, template) = split_output_template("/downloads/videos/%(title)s.%(ext)s");
+        assert_eq!(home, PathBuf::from("/downloads/videos"));
+        assert_eq!(template, "%(title)s.%(ext)s");
+
+        let (home, template) = split_output_template("/downloads/%(uploader)s/%(title)s.%(ext)s");
+        assert_eq!(home, PathBuf::from("/downloads"));
+        assert_eq!(
+            PathBuf::from(template),
+            PathBuf::from("%(uploader)s").join("%(title)s.%(ext)s")
+        );
+
+        let (home, template) = split_output_template("/downloads/video.mp4");
+        assert_eq!(home, PathBuf::from("/downloads"));
+        assert_eq!(template, "video.mp4");
+    }
+
+    #[test]
+    fn apply_max_height_caps_generic_selectors() {
+        assert_eq!(
+            apply_max_height("bestvideo+bestaudio/best", 1080).as_deref(),
+            Some("bestvideo[height<=1080]+bestaudio/best[height<=1080]")
+        );
+        assert_eq!(
+            apply_max_height(
+                "bestvideo[ext=mp4]+bestaudio[ext=m4a]/best[ext=mp4]/best",
+                720
+            )
+            .as_deref(),
+            Some("bestvideo[height<=720][ext=mp4]+bestaudio[ext=m4a]/best[height<=720][ext=mp4]/best[height<=720]")
+        );
+        assert_eq!(
+            apply_max_height("bv,ba", 480).as_deref(),
+            Some("bv[height<=480],ba")
+        );
+    }
+
+    #[test]
+    fn apply_max_height_leaves_explicit_formats() {
+        assert_eq!(
+            apply_max_height("bestvideo[height<=720]+bestaudio", 1080),
+            None
+        );
+        assert_eq!(apply_max_height("137+140", 1080), None);
+        assert_eq!(apply_max_height("bestaudio", 1080), None);
+    }
+
+    #[test]
+    fn apply_audio_lang_prefers_language_with_fallback() {
+        assert_eq!(
+            apply_audio_lang("bestvideo[height<=1080]+bestaudio/best[height<=1080]", "en")
+                .as_deref(),
+            Some("bestvideo[height<=1080]+bestaudio[language^=en]/best[language^=en][height<=1080]/bestvideo[height<=1080]+bestaudio/best[height<=1080]")
+        );
+        assert_eq!(
+            apply_audio_lang("bv,ba", "ja").as_deref(),
+            Some("bv,ba[language^=ja]/ba")
+        );
+        assert_eq!(apply_audio_lang("137+140", "en"), None);
+        assert_eq!(apply_audio_lang("ba[language=de]", "en"), None);
+    }
+
+    #[test]
+    fn apply_codec_preference_tries_codecs_in_order() {
+        let preference = vec!["av01".to_string(), "h264".to_string()];
+        assert_eq!(
+            apply_codec_preference("bv*+ba/b", &preference).as_deref(),
+            Some("bv*[vcodec~='^(av01|av1)']+ba/b[vcodec~='^(av01|av1)']/bv*[vcodec~='^(avc1|avc3|h264)']+ba/b[vcodec~='^(avc1|avc3|h264)']/bv*+ba/b")
+        );
+        assert_eq!(
+            apply_codec_preference("bv,ba", &preference[1..]).as_deref(),
+            Some("bv[vcodec~='^(avc1|avc3|h264)']/bv,ba")
+        );
+        assert_eq!(apply_codec_preference("137+140", &preference), None);
+        assert_eq!(apply_codec_preference("bv[vcodec^=vp9]", &preference), None);
+        assert_eq!(apply_codec_preference("bv*+ba", &[]), None);
+    }
+
+    #[test]
+    fn format_bytes_uses_binary_units() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.00 GiB");
+    }
+
+    #[test]
+    fn separate_stream_template_adds_format_id() {
+        assert_eq!(
+            separate_stream_template("/downloads/%(title)s.%(ext)s"),
+            "/downloads/%(title)s.f%(format_id)s.%(ext)s"
+        );
+        assert_eq!(
+            separate_stream_template("/downloads/%(title)s-%(format_id)s.%(ext)s"),
+            "/downloads/%(title)s-%(format_id)s.%(ext)s"
+        );
+    }
+
+    #[test]
+    fn chapters_sidecar_replaces_extension() {
+        assert_eq!(
+            chapters_sidecar_template("/downloads/%(title)s.%(ext)s"),
+            "/downloads/%(title)s.chapters.json"
+        );
+        assert_eq!(
+            chapters_sidecar_template("/downloads/video"),
+            "/downloads/video.chapters.json"
+        );
+    }
+
+    #[test]
+    fn chapters_json_skips_videos_without_chapters() {
+        assert_eq!(chapters_json("NA\n"), None);
+        assert_eq!(chapters_json("[]\n"), None);
+        assert_eq!(chapters_json(""), None);
+        let chapters = r#"[{"start_time": 0.0, "end_time": 60.0, "title": "Intro"}]"#;
+        assert_eq!(
+            chapters_json(&format!("NA\n{}\n", chapters)),
+            Some(chapters)
+        );
+    }
+
+    #[test]
+    fn parse_vcodec_takes_last_video_codec() {
+        assert_eq!(
+            parse_vcodec("vp9\navc1.640028\n").as_deref(),
+            Some("avc1.640028")
+        );
+        assert_eq!(parse_vcodec("none\n"), None);
+        assert_eq!(parse_vcodec(""), None);
+    }
+
+    #[test]
+    fn xattr_errors_are_recognised() {
+        let stderr = "WARNING: [youtube] some unrelated warning\n\
+            ERROR: Postprocessing: This filesystem doesn't support extended attributes. You need to use NTFS";
+        assert!(only_xattr_errors(stderr));
+        assert!(!only_xattr_errors(
+            "ERROR: Postprocessing: This filesystem doesn't support extended attributes\n\
+             ERROR: [youtube] abc: Video unavailable"
+        ));
+        assert!(!only_xattr_errors("WARNING: xattr limit exceeded"));
+        assert!(is_xattr_message(
+            "WARNING: Couldn't find a tool to set the xattrs. Install either the python 'xattr' module, or the 'xattr' binary"
+        ));
+    }
+
     #[test]
     fn append_limited_keeps_recent_tail() {
         let mut output = String::new();
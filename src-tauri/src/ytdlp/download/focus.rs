@@ -0,0 +1,88 @@
+//! Foreground priority: the task the user is watching downloads uncapped while
+//! every other running task is held to `throttle_rate_limit`. yt-dlp can't
+//! change `--limit-rate` on a running process, so tasks whose cap changes are
+//! stopped and re-queued; they pick up where they left off via `--continue`.
+
+use super::manager::DownloadManager;
+use crate::modules::logger;
+use crate::modules::types::AppError;
+use crate::ytdlp::security;
+use crate::ytdlp::types::DownloadStatus;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+/// Rate to start `task_id` with, if it should be capped right now.
+pub(super) fn rate_limit_for(
+    manager: &DownloadManager,
+    rate: Option<&str>,
+    task_id: u64,
+) -> Option<String> {
+    let rate = rate.filter(|r| security::sanitize_rate_limit(r).is_ok())?;
+    manager.should_rate_limit(task_id).then(|| rate.to_string())
+}
+
+pub fn set_focused(app: &AppHandle, task_id: u64) -> Result<(), AppError> {
+    let task = app
+        .state::<crate::DbState>()
+        .get_download(task_id)?
+        .ok_or_else(|| AppError::Custom("Task not found".to_string()))?;
+    if !matches!(
+        task.status,
+        DownloadStatus::Pending | DownloadStatus::Downloading
+    ) {
+        return Err(AppError::Custom(
+            "Only a queued or running task can be focused".to_string(),
+        ));
+    }
+    app.state::<Arc<DownloadManager>>()
+        .set_focused_task(Some(task_id));
+    logger::info_cat(
+        "download",
+        &format!(
+            "[download:{}] focused; other downloads are rate-limited",
+            task_id
+        ),
+    );
+    respawn_mismatched(app);
+    Ok(())
+}
+
+pub fn clear_focused(app: &AppHandle) {
+    let manager = app.state::<Arc<DownloadManager>>();
+    if manager.focused_task().is_none() {
+        return;
+    }
+    manager.set_focused_task(None);
+    logger::info_cat("download", "Download focus cleared");
+    respawn_mismatched(app);
+}
+
+/// Called when a task leaves the queue for good; a focus on it goes with it.
+pub(super) fn task_ended(app: &AppHandle, task_id: u64) {
+    if app.state::<Arc<DownloadManager>>().clear_focus_if(task_id) {
+        logger::info_cat(
+            "download",
+            &format!("[download:{}] focus cleared: task finished", task_id),
+        );
+        respawn_mismatched(app);
+    }
+}
+
+/// Restart running tasks whose `--limit-rate` no longer matches the focus.
+fn respawn_mismatched(app: &AppHandle) {
+    let can_cap = crate::ytdlp::settings::get_settings(app)
+        .ok()
+        .and_then(|s| s.throttle_rate_limit)
+        .is_some_and(|r| security::sanitize_rate_limit(&r).is_ok());
+    let manager = app.state::<Arc<DownloadManager>>();
+    for task_id in manager.rate_limit_mismatches(can_cap) {
+        logger::info_cat(
+            "download",
+            &format!(
+                "[download:{}] restarting to apply the new rate limit",
+                task_id
+            ),
+        );
+        manager.send_requeue(task_id);
+    }
+}
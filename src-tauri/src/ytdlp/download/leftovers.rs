@@ -0,0 +1,263 @@
+//! yt-dlp partials (`.part`, `.ytdl`, fragments) left behind by failed or
+//! cancelled downloads in the download folders.
+//!
+//! Folders searched are the download folders from settings, the temp dir and
+//! the output folder of every task in the queue, since tasks can pick their own.
+//!
+//! Partials don't record which task wrote them, so a file counts as in use
+//! when its name carries the video id or starts with the title of a task that
+//! is still pending, downloading or paused, or that failed in a way a retry
+//! may fix. Such files are listed but never removed, since yt-dlp resumes from them.
+
+use super::executor::split_output_template;
+use super::retry;
+use super::temp::{is_partial_file, temp_dir_for};
+use crate::modules::types::AppError;
+use crate::ytdlp::error_kind::classify_download_error;
+use crate::ytdlp::types::{DownloadStatus, DownloadTaskInfo, LeftoverCleanup, LeftoverFile};
+use crate::ytdlp::{security, settings};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+/// Folder levels below a root that are searched (templates may add uploader/date folders)
+const MAX_DEPTH: usize = 4;
+
+/// Characters of a task title compared against file names
+const TITLE_PREFIX_LEN: usize = 16;
+
+/// Lower-cased letters and digits only, so yt-dlp's filename sanitizing doesn't matter.
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Video ids and normalized title prefixes of the tasks a partial may belong to.
+pub struct ActiveTasks(Vec<(String, String)>);
+
+impl ActiveTasks {
+    pub fn owns(&self, file_name: &str) -> bool {
+        let name = normalize(file_name);
+        self.0.iter().any(|(video_id, title)| {
+            (!video_id.is_empty() && file_name.contains(video_id.as_str()))
+                || (!title.is_empty() && name.starts_with(title.as_str()))
+        })
+    }
+}
+
+/// Whether yt-dlp may still resume from the task's partials.
+fn may_resume(task: &DownloadTaskInfo) -> bool {
+    match task.status {
+        DownloadStatus::Pending | DownloadStatus::Downloading | DownloadStatus::Paused => true,
+        DownloadStatus::Failed => task
+            .error_message
+            .as_deref()
+            .is_some_and(|e| retry::is_retryable(classify_download_error(e))),
+        _ => false,
+    }
+}
+
+fn owners(tasks: &[DownloadTaskInfo]) -> ActiveTasks {
+    ActiveTasks(
+        tasks
+            .iter()
+            .filter(|t| may_resume(t))
+            .map(|t| {
+                let title: String = normalize(&t.title).chars().take(TITLE_PREFIX_LEN).collect();
+                (t.video_id.clone(), title)
+            })
+            .collect(),
+    )
+}
+
+pub fn active_tasks(app: &AppHandle) -> Result<ActiveTasks, AppError> {
+    let tasks = app.state::<crate::DbState>().get_download_queue()?;
+    Ok(owners(&tasks))
+}
+
+/// Download folders (including per-type ones), the temp dir and every task's
+/// output folder, or just `dir`.
+fn roots(
+    app: &AppHandle,
+    dir: Option<&str>,
+    tasks: &[DownloadTaskInfo],
+) -> Result<Vec<PathBuf>, AppError> {
+    if let Some(dir) = dir {
+        return Ok(vec![PathBuf::from(security::sanitize_output_path(dir)?)]);
+    }
+    let settings = settings::get_settings(app)?;
+    let paths = &settings.output_paths_by_type;
+    let mut roots: Vec<PathBuf> = [
+        Some(settings.download_path.as_str()),
+        paths.video.as_deref(),
+        paths.audio.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|d| !d.is_empty())
+    .map(PathBuf::from)
+    .collect();
+    roots.extend(temp_dir_for(app, &settings));
+    roots.extend(
+        tasks
+            .iter()
+            .map(|t| split_output_template(&t.output_path).0)
+            .filter(|home| home.is_absolute()),
+    );
+    roots.sort();
+    roots.dedup();
+    Ok(roots)
+}
+
+fn collect(dir: &Path, depth: usize, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        if file_type.is_dir() && depth < MAX_DEPTH {
+            collect(&path, depth + 1, out);
+        } else if file_type.is_file() && is_partial_file(&entry.file_name().to_string_lossy()) {
+            out.push(path);
+        }
+    }
+}
+
+/// Partials under `dir`, or under every download folder and the temp dir.
+pub fn list_leftovers(app: &AppHandle, dir: Option<&str>) -> Result<Vec<LeftoverFile>, AppError> {
+    let tasks = app.state::<crate::DbState>().get_download_queue()?;
+    let active = owners(&tasks);
+    let mut paths = Vec::new();
+    for root in roots(app, dir, &tasks)? {
+        collect(&root, 0, &mut paths);
+    }
+    paths.sort();
+    paths.dedup();
+
+    let mut files: Vec<LeftoverFile> = paths
+        .into_iter()
+        .filter_map(|path| {
+            let meta = std::fs::metadata(&path).ok()?;
+            let name = path.file_name()?.to_string_lossy().to_string();
+            Some(LeftoverFile {
+                in_use: active.owns(&name),
+                path: path.to_string_lossy().to_string(),
+                size: meta.len(),
+                modified_at: meta
+                    .modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|d| d.as_secs() as i64),
+            })
+        })
+        .collect();
+    files.sort_by_key(|f| std::cmp::Reverse(f.size));
+    Ok(files)
+}
+
+/// Delete the given partials, found by `list_leftovers` with the same `dir`.
+/// Paths that aren't partials under those folders or that belong to an
+/// unfinished task are skipped.
+pub fn clean_leftovers(
+    app: &AppHandle,
+    dir: Option<&str>,
+    paths: &[String],
+) -> Result<LeftoverCleanup, AppError> {
+    let tasks = app.state::<crate::DbState>().get_download_queue()?;
+    Ok(remove_partials(
+        paths,
+        &roots(app, dir, &tasks)?,
+        &owners(&tasks),
+    ))
+}
+
+fn remove_partials(paths: &[String], roots: &[PathBuf], active: &ActiveTasks) -> LeftoverCleanup {
+    let mut result = LeftoverCleanup {
+        removed: 0,
+        reclaimed_bytes: 0,
+        skipped: Vec::new(),
+    };
+    for raw in paths {
+        let path = Path::new(raw);
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let allowed = is_partial_file(&name)
+            && !active.owns(&name)
+            && roots.iter().any(|root| path.starts_with(root))
+            && !path
+                .components()
+                .any(|c| c == std::path::Component::ParentDir);
+        let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        if allowed && std::fs::remove_file(path).is_ok() {
+            result.removed += 1;
+            result.reclaimed_bytes += size;
+        } else {
+            result.skipped.push(raw.clone());
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partials_of_unfinished_tasks_are_in_use() {
+        let active = ActiveTasks(vec![
+            (
+                "dQw4w9WgXcQ".to_string(),
+                normalize("Never Gonna Give You Up"),
+            ),
+            (String::new(), normalize("Ünïcode: Ti/tle")),
+        ]);
+        assert!(active.owns("Song [dQw4w9WgXcQ].mp4.part"));
+        assert!(active.owns("Never Gonna Give You Up (Official Video).f137.mp4.part-Frag3"));
+        assert!(active.owns("Ünïcode： Ti⧸tle.webm.ytdl"));
+        assert!(!active.owns("Something else.mp4.part"));
+    }
+
+    #[test]
+    fn cleans_partials_listed_from_an_explicit_dir() {
+        let dir = std::env::temp_dir().join(format!("ytdlp-leftovers-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let stale = dir.join("Old video [abc].mp4.part");
+        let owned = dir.join("Queued [dQw4w9WgXcQ].mp4.part");
+        let other = dir.join("notes.txt");
+        for path in [&stale, &owned, &other] {
+            std::fs::write(path, b"data").unwrap();
+        }
+        let active = ActiveTasks(vec![("dQw4w9WgXcQ".to_string(), String::new())]);
+
+        let mut found = Vec::new();
+        collect(&dir, 0, &mut found);
+        found.push(other.clone());
+        let paths: Vec<String> = found
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+        let result = remove_partials(&paths, std::slice::from_ref(&dir), &active);
+
+        assert_eq!(result.removed, 1);
+        assert_eq!(result.reclaimed_bytes, 4);
+        assert!(!stale.exists());
+        assert!(owned.exists() && other.exists());
+        // Outside the listed dir nothing is removed
+        std::fs::write(&stale, b"data").unwrap();
+        let elsewhere = remove_partials(
+            &[stale.to_string_lossy().to_string()],
+            &[dir.join("sub")],
+            &active,
+        );
+        assert_eq!(elsewhere.removed, 0);
+        assert!(stale.exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,135 @@
+//! Caps on task starts per app run and per local day (`session_download_limit`,
+//! `daily_download_limit`). A reached limit leaves the manager with no free
+//! slots, so every start path holds off and tasks stay pending. Only a task's
+//! first start counts; retries don't.
+
+use super::executor::process_next_pending_public;
+use super::manager::DownloadManager;
+use crate::modules::logger;
+use crate::ytdlp::types::{AppSettings, DownloadLimitUsage};
+use chrono::{DateTime, TimeZone};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+/// Start of the day containing `now`, in `now`'s time zone.
+pub fn day_start<Tz: TimeZone>(now: &DateTime<Tz>) -> i64 {
+    midnight(now, now.date_naive())
+}
+
+/// Start of the day after `now`, when the daily count resets.
+pub fn next_day_start<Tz: TimeZone>(now: &DateTime<Tz>) -> i64 {
+    let tomorrow = now.date_naive().succ_opt().unwrap_or(now.date_naive());
+    midnight(now, tomorrow)
+}
+
+fn midnight<Tz: TimeZone>(now: &DateTime<Tz>, date: chrono::NaiveDate) -> i64 {
+    // A DST change can skip midnight; fall back to "now" rather than guess
+    date.and_hms_opt(0, 0, 0)
+        .and_then(|t| t.and_local_timezone(now.timezone()).earliest())
+        .map_or(now.timestamp(), |t| t.timestamp())
+}
+
+pub fn apply_settings(app: &AppHandle, settings: &AppSettings) {
+    app.state::<Arc<DownloadManager>>().set_download_limits(
+        settings.session_download_limit,
+        settings.daily_download_limit,
+    );
+}
+
+/// Load today's starts from earlier runs so a restart doesn't reset the daily count.
+pub fn seed_daily(app: &AppHandle) {
+    let since = day_start(&chrono::Local::now());
+    let count = match app.state::<crate::DbState>().starts_since(since) {
+        Ok(n) => n,
+        Err(e) => {
+            logger::warn_cat(
+                "download",
+                &format!("Failed to count today's starts: {}", e),
+            );
+            return;
+        }
+    };
+    let manager = app.state::<Arc<DownloadManager>>();
+    manager.seed_daily_starts(count);
+    if manager.limit_reached().is_some() {
+        wake_at_midnight(app);
+    }
+}
+
+/// Count the start of `task_id` and log when it uses up a limit.
+pub(crate) fn note_start(app: &AppHandle, task_id: u64) {
+    match app.state::<crate::DbState>().record_start(task_id) {
+        Ok(false) => return,
+        Ok(true) => {}
+        // Count it anyway: over-counting only delays a start, under-counting breaks the cap
+        Err(e) => logger::warn_cat(
+            "download",
+            &format!("[download:{}] failed to record start: {}", task_id, e),
+        ),
+    }
+
+    let manager = app.state::<Arc<DownloadManager>>();
+    manager.count_start();
+    let (session, daily) = manager.start_counts();
+    let (session_limit, daily_limit) = manager.download_limits();
+    if let Some(limit) = session_limit.filter(|&limit| limit == session) {
+        logger::info_cat(
+            "download",
+            &format!(
+                "Session download limit reached ({}/{}); pending tasks wait until it is raised or the app restarts",
+                session, limit
+            ),
+        );
+    }
+    if let Some(limit) = daily_limit.filter(|&limit| limit == daily) {
+        logger::info_cat(
+            "download",
+            &format!(
+                "Daily download limit reached ({}/{}); pending tasks resume after midnight",
+                daily, limit
+            ),
+        );
+        wake_at_midnight(app);
+    }
+}
+
+pub fn usage(app: &AppHandle) -> DownloadLimitUsage {
+    let manager = app.state::<Arc<DownloadManager>>();
+    let (session_used, daily_used) = manager.start_counts();
+    let (session_limit, daily_limit) = manager.download_limits();
+    DownloadLimitUsage {
+        session_used,
+        session_limit,
+        daily_used,
+        daily_limit,
+        reached: manager.limit_reached(),
+        daily_resets_at: next_day_start(&chrono::Local::now()),
+    }
+}
+
+/// Nothing else starts the queue when the day rolls over.
+fn wake_at_midnight(app: &AppHandle) {
+    let now = chrono::Local::now();
+    let wait = (next_day_start(&now) - now.timestamp()).max(0) as u64 + 1;
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(wait)).await;
+        process_next_pending_public(app);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    #[test]
+    fn day_bounds_follow_the_local_zone() {
+        let kst = FixedOffset::east_opt(9 * 3600).unwrap();
+        // 2024-03-10 01:30 in UTC+9 is still 2024-03-09 in UTC
+        let now = kst.with_ymd_and_hms(2024, 3, 10, 1, 30, 0).unwrap();
+        let start = kst.with_ymd_and_hms(2024, 3, 10, 0, 0, 0).unwrap();
+        assert_eq!(day_start(&now), start.timestamp());
+        assert_eq!(next_day_start(&now), start.timestamp() + 86_400);
+    }
+}
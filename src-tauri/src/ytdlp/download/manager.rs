@@ -1,12 +1,56 @@
-use std::collections::HashMap;
-use std::sync::atomic::{AtomicU32, Ordering};
+use crate::ytdlp::types::{
+    DownloadLimitKind, QueueEvent, TaskProgress, TaskStage, TaskStall, TotalSpeed,
+};
+use chrono::NaiveDate;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Mutex;
-use tokio::sync::watch;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, watch};
+
+/// Speed readings older than this count as 0 (stalled or between fragments)
+const SPEED_STALE_AFTER: Duration = Duration::from_secs(5);
+
+/// Buffered queue events per subscriber before it is considered lagging
+const QUEUE_EVENT_CAPACITY: usize = 256;
 
 pub struct DownloadManager {
     active_count: AtomicU32,
     max_concurrent: AtomicU32,
+    /// Background throttle: when set, concurrency is capped at throttle_limit
+    throttled: AtomicBool,
+    throttle_limit: AtomicU32,
+    /// No new task starts while set (queue draining before exit)
+    starts_paused: AtomicBool,
+    /// No new task starts while set (on battery below `pauseBelowBatteryPercent`)
+    battery_paused: AtomicBool,
+    /// No new task starts while set (CPU above `pauseAboveCpuPercent` for a while)
+    cpu_paused: AtomicBool,
+    /// Tasks allowed to start per app run / per local day (0 = unlimited)
+    session_limit: AtomicU32,
+    daily_limit: AtomicU32,
+    session_starts: AtomicU32,
+    /// Starts counted toward the daily limit, and the local day they belong to
+    daily_starts: Mutex<(NaiveDate, u32)>,
+    /// Minimum gap between consecutive task starts (0 = none)
+    start_delay_secs: AtomicU32,
+    last_start: Mutex<Option<Instant>>,
+    /// Set while a delayed `process_next_pending` wake-up is pending
+    wake_scheduled: AtomicBool,
     cancel_senders: Mutex<HashMap<u64, watch::Sender<bool>>>,
+    /// Running tasks whose cancel signal means "stop and put back as pending"
+    requeue_ids: Mutex<HashSet<u64>>,
+    queue_events: broadcast::Sender<QueueEvent>,
+    /// Latest speed (bytes/sec) per running task and when it was reported
+    speeds: Mutex<HashMap<u64, (u64, Instant)>>,
+    /// Latest progress snapshot per running task
+    progress: Mutex<HashMap<u64, TaskProgress>>,
+    /// When each running task's bytes or percent last changed
+    last_advance: Mutex<HashMap<u64, Instant>>,
+    /// Task the user is watching: uncapped while every other task is rate-limited
+    focused_task: Mutex<Option<u64>>,
+    /// Running tasks whose process was started with `--limit-rate`
+    rate_capped: Mutex<HashSet<u64>>,
 }
 
 impl DownloadManager {
@@ -14,7 +58,26 @@ impl DownloadManager {
         Self {
             active_count: AtomicU32::new(0),
             max_concurrent: AtomicU32::new(max_concurrent.clamp(1, 20)),
+            throttled: AtomicBool::new(false),
+            throttle_limit: AtomicU32::new(1),
+            starts_paused: AtomicBool::new(false),
+            battery_paused: AtomicBool::new(false),
+            cpu_paused: AtomicBool::new(false),
+            session_limit: AtomicU32::new(0),
+            daily_limit: AtomicU32::new(0),
+            session_starts: AtomicU32::new(0),
+            daily_starts: Mutex::new((chrono::Local::now().date_naive(), 0)),
+            start_delay_secs: AtomicU32::new(0),
+            last_start: Mutex::new(None),
+            wake_scheduled: AtomicBool::new(false),
             cancel_senders: Mutex::new(HashMap::new()),
+            requeue_ids: Mutex::new(HashSet::new()),
+            queue_events: broadcast::channel(QUEUE_EVENT_CAPACITY).0,
+            speeds: Mutex::new(HashMap::new()),
+            progress: Mutex::new(HashMap::new()),
+            last_advance: Mutex::new(HashMap::new()),
+            focused_task: Mutex::new(None),
+            rate_capped: Mutex::new(HashSet::new()),
         }
     }
 
@@ -32,11 +95,216 @@ impl DownloadManager {
             .store(val.clamp(1, 20), Ordering::SeqCst);
     }
 
+    /// Enable background throttling with the given concurrency cap, or disable it with None.
+    /// Running tasks are never interrupted; the cap only applies to new acquisitions.
+    pub fn set_throttle(&self, limit: Option<u32>) {
+        match limit {
+            Some(n) => {
+                self.throttle_limit.store(n.clamp(1, 20), Ordering::SeqCst);
+                self.throttled.store(true, Ordering::SeqCst);
+            }
+            None => self.throttled.store(false, Ordering::SeqCst),
+        }
+    }
+
+    pub fn is_throttled(&self) -> bool {
+        self.throttled.load(Ordering::SeqCst)
+    }
+
+    pub fn set_focused_task(&self, task_id: Option<u64>) {
+        *self.focused_task.lock().unwrap_or_else(|e| e.into_inner()) = task_id;
+    }
+
+    pub fn focused_task(&self) -> Option<u64> {
+        *self.focused_task.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Drop the focus if it is on `task_id`. Returns whether it was.
+    pub(super) fn clear_focus_if(&self, task_id: u64) -> bool {
+        let mut focused = self.focused_task.lock().unwrap_or_else(|e| e.into_inner());
+        if *focused == Some(task_id) {
+            *focused = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Whether `task_id` should run rate-limited: every task but the focused one
+    /// while a task has focus, otherwise only under the background throttle.
+    pub fn should_rate_limit(&self, task_id: u64) -> bool {
+        match self.focused_task() {
+            Some(focused) => focused != task_id,
+            None => self.is_throttled(),
+        }
+    }
+
+    pub(super) fn mark_rate_capped(&self, task_id: u64) {
+        self.rate_capped
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(task_id);
+    }
+
+    /// Running tasks whose process was started with the wrong cap for the
+    /// current focus, ordered by task id. `can_cap` is false when there is no
+    /// valid rate to cap with, so no task needs one.
+    pub fn rate_limit_mismatches(&self, can_cap: bool) -> Vec<u64> {
+        let mut running = self.running_task_ids();
+        running.sort_unstable();
+        let capped = self.rate_capped.lock().unwrap_or_else(|e| e.into_inner());
+        running
+            .into_iter()
+            .filter(|id| (can_cap && self.should_rate_limit(*id)) != capped.contains(id))
+            .collect()
+    }
+
+    /// Stop (or resume) starting new tasks. Running ones are left alone.
+    pub fn set_starts_paused(&self, paused: bool) {
+        self.starts_paused.store(paused, Ordering::SeqCst);
+    }
+
+    pub fn starts_paused(&self) -> bool {
+        self.starts_paused.load(Ordering::SeqCst)
+    }
+
+    /// Hold (or release) new starts for low battery. Kept apart from
+    /// `starts_paused` so it can't undo a drain before exit.
+    pub fn set_battery_paused(&self, paused: bool) {
+        self.battery_paused.store(paused, Ordering::SeqCst);
+    }
+
+    pub fn battery_paused(&self) -> bool {
+        self.battery_paused.load(Ordering::SeqCst)
+    }
+
+    /// Hold (or release) new starts while the system is busy.
+    pub fn set_cpu_paused(&self, paused: bool) {
+        self.cpu_paused.store(paused, Ordering::SeqCst);
+    }
+
+    pub fn cpu_paused(&self) -> bool {
+        self.cpu_paused.load(Ordering::SeqCst)
+    }
+
+    /// Set the session/daily start limits; None lifts a limit.
+    pub fn set_download_limits(&self, session: Option<u32>, daily: Option<u32>) {
+        self.session_limit
+            .store(session.unwrap_or(0), Ordering::SeqCst);
+        self.daily_limit.store(daily.unwrap_or(0), Ordering::SeqCst);
+    }
+
+    /// Starts already made today, e.g. by an earlier run of the app.
+    pub fn seed_daily_starts(&self, count: u32) {
+        *self.daily_starts.lock().unwrap_or_else(|e| e.into_inner()) =
+            (chrono::Local::now().date_naive(), count);
+    }
+
+    /// Count a task's first start toward both limits.
+    pub fn count_start(&self) {
+        self.session_starts.fetch_add(1, Ordering::SeqCst);
+        let today = chrono::Local::now().date_naive();
+        let mut daily = self.daily_starts.lock().unwrap_or_else(|e| e.into_inner());
+        if daily.0 != today {
+            *daily = (today, 0);
+        }
+        daily.1 += 1;
+    }
+
+    /// (session starts, starts today). The daily count resets at local midnight.
+    pub fn start_counts(&self) -> (u32, u32) {
+        let today = chrono::Local::now().date_naive();
+        let daily = self.daily_starts.lock().unwrap_or_else(|e| e.into_inner());
+        let daily_count = if daily.0 == today { daily.1 } else { 0 };
+        (self.session_starts.load(Ordering::SeqCst), daily_count)
+    }
+
+    pub fn download_limits(&self) -> (Option<u32>, Option<u32>) {
+        let limit = |a: &AtomicU32| Some(a.load(Ordering::SeqCst)).filter(|&n| n > 0);
+        (limit(&self.session_limit), limit(&self.daily_limit))
+    }
+
+    /// The limit that currently keeps new tasks from starting, if any.
+    pub fn limit_reached(&self) -> Option<DownloadLimitKind> {
+        let (session, daily) = self.start_counts();
+        let (session_limit, daily_limit) = self.download_limits();
+        if session_limit.is_some_and(|n| session >= n) {
+            Some(DownloadLimitKind::Session)
+        } else if daily_limit.is_some_and(|n| daily >= n) {
+            Some(DownloadLimitKind::Daily)
+        } else {
+            None
+        }
+    }
+
+    /// Concurrency limit currently in effect (throttle cap applied, 0 while starts
+    /// are paused or a download limit is reached).
+    fn effective_max(&self) -> u32 {
+        let max = self.max_concurrent.load(Ordering::SeqCst);
+        if self.starts_paused()
+            || self.battery_paused()
+            || self.cpu_paused()
+            || self.limit_reached().is_some()
+        {
+            0
+        } else if self.is_throttled() {
+            max.min(self.throttle_limit.load(Ordering::SeqCst))
+        } else {
+            max
+        }
+    }
+
+    pub fn set_start_delay(&self, secs: u32) {
+        self.start_delay_secs.store(secs, Ordering::SeqCst);
+    }
+
+    /// Record a task start now, or return the time left until the start delay allows one.
+    /// On success returns the previous start time so it can be restored with `undo_start`
+    /// if there turns out to be nothing to start.
+    pub(super) fn try_mark_start(&self) -> Result<Option<Instant>, Duration> {
+        let delay = Duration::from_secs(self.start_delay_secs.load(Ordering::SeqCst) as u64);
+        let mut last = self.last_start.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(prev) = *last {
+            let elapsed = prev.elapsed();
+            if elapsed < delay {
+                return Err(delay - elapsed);
+            }
+        }
+        Ok(last.replace(Instant::now()))
+    }
+
+    /// Time until the start delay allows another start, if it is still running.
+    pub fn start_delay_remaining(&self) -> Option<Duration> {
+        let delay = Duration::from_secs(self.start_delay_secs.load(Ordering::SeqCst) as u64);
+        let last = *self.last_start.lock().unwrap_or_else(|e| e.into_inner());
+        last.and_then(|prev| delay.checked_sub(prev.elapsed()))
+            .filter(|left| !left.is_zero())
+    }
+
+    /// Slots a new task could take right now (throttle cap applied).
+    pub fn free_slots(&self) -> u32 {
+        self.effective_max()
+            .saturating_sub(self.active_count.load(Ordering::SeqCst))
+    }
+
+    pub(super) fn undo_start(&self, previous: Option<Instant>) {
+        *self.last_start.lock().unwrap_or_else(|e| e.into_inner()) = previous;
+    }
+
+    /// Claim the single delayed wake-up slot. Returns false if one is already pending.
+    pub(super) fn claim_wake(&self) -> bool {
+        !self.wake_scheduled.swap(true, Ordering::SeqCst)
+    }
+
+    pub(super) fn clear_wake(&self) {
+        self.wake_scheduled.store(false, Ordering::SeqCst);
+    }
+
     // CAS loop to fix TOCTOU race condition
     pub fn try_acquire(&self) -> bool {
         loop {
             let current = self.active_count.load(Ordering::SeqCst);
-            if current >= self.max_concurrent.load(Ordering::SeqCst) {
+            if current >= self.effective_max() {
                 return false;
             }
             if self
@@ -57,11 +325,113 @@ impl DownloadManager {
             });
     }
 
-    /// Synchronize active_count with the actual DB state.
-    /// Used after cancel_all to correct any drift between the atomic counter
-    /// and the real number of downloading tasks.
-    pub fn sync_active_count(&self, count: u32) {
-        self.active_count.store(count, Ordering::SeqCst);
+    /// Synchronize active_count with the actual DB state and return the count
+    /// it held before. Used after cancel_all and by slot recovery to correct
+    /// any drift between the atomic counter and the real number of downloading tasks.
+    pub fn sync_active_count(&self, count: u32) -> u32 {
+        self.active_count.swap(count, Ordering::SeqCst)
+    }
+
+    /// Publish a queue state change to `watch_queue` subscribers (no-op without any).
+    pub fn publish(&self, event: QueueEvent) {
+        let _ = self.queue_events.send(event);
+    }
+
+    pub fn subscribe_queue(&self) -> broadcast::Receiver<QueueEvent> {
+        self.queue_events.subscribe()
+    }
+
+    pub(super) fn record_speed(&self, task_id: u64, bytes_per_sec: u64) {
+        let mut speeds = self.speeds.lock().unwrap_or_else(|e| e.into_inner());
+        speeds.insert(task_id, (bytes_per_sec, Instant::now()));
+    }
+
+    /// Aggregate download speed across running tasks, ignoring stale readings.
+    pub fn total_speed(&self) -> TotalSpeed {
+        let speeds = self.speeds.lock().unwrap_or_else(|e| e.into_inner());
+        let fresh = speeds
+            .values()
+            .filter(|(_, at)| at.elapsed() < SPEED_STALE_AFTER);
+        let (bytes_per_sec, reporting_tasks) =
+            fresh.fold((0u64, 0u32), |(sum, n), (bps, _)| (sum + bps, n + 1));
+        TotalSpeed {
+            bytes_per_sec,
+            reporting_tasks,
+        }
+    }
+
+    pub(super) fn record_progress(&self, progress: TaskProgress) {
+        let mut all = self.progress.lock().unwrap_or_else(|e| e.into_inner());
+        let advanced = all.get(&progress.task_id).is_none_or(|prev| {
+            prev.downloaded_bytes != progress.downloaded_bytes || prev.percent != progress.percent
+        });
+        if advanced {
+            self.last_advance
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(progress.task_id, Instant::now());
+        }
+        all.insert(progress.task_id, progress);
+    }
+
+    /// Move a running task to `stage`, keeping its last reading.
+    pub(super) fn set_stage(&self, task_id: u64, stage: TaskStage) {
+        let mut progress = self.progress.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = progress.entry(task_id).or_insert_with(|| TaskProgress {
+            task_id,
+            stage,
+            percent: None,
+            speed: None,
+            eta: None,
+            downloaded_bytes: None,
+            updated_at: 0,
+        });
+        entry.stage = stage;
+        entry.updated_at = chrono::Utc::now().timestamp();
+        self.last_advance
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(task_id)
+            .or_insert_with(Instant::now);
+    }
+
+    /// Progress of every task with a running process, ordered by task id.
+    pub fn active_progress(&self) -> Vec<TaskProgress> {
+        let running: HashSet<u64> = self.running_task_ids().into_iter().collect();
+        let mut progress = self.progress.lock().unwrap_or_else(|e| e.into_inner());
+        // Backstop for an exit path that missed unregister_cancel
+        progress.retain(|id, _| running.contains(id));
+        let mut out: Vec<TaskProgress> = progress.values().cloned().collect();
+        out.sort_by_key(|p| p.task_id);
+        out
+    }
+
+    /// Running tasks that haven't advanced for at least `threshold`, longest
+    /// stalled first. Post-processing is skipped: it reports no progress.
+    pub fn stalled_tasks(&self, threshold: Duration) -> Vec<TaskStall> {
+        let progress = self.active_progress();
+        // Locked after `progress` is released, in the order record_progress uses
+        let last_advance = self.last_advance.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out: Vec<TaskStall> = progress
+            .into_iter()
+            .filter(|p| p.stage != TaskStage::Processing)
+            .filter_map(|p| {
+                let stalled = last_advance.get(&p.task_id)?.elapsed();
+                (stalled >= threshold).then_some(TaskStall {
+                    task_id: p.task_id,
+                    stage: p.stage,
+                    stalled_secs: stalled.as_secs(),
+                    percent: p.percent,
+                    downloaded_bytes: p.downloaded_bytes,
+                })
+            })
+            .collect();
+        out.sort_by(|a, b| {
+            b.stalled_secs
+                .cmp(&a.stalled_secs)
+                .then(a.task_id.cmp(&b.task_id))
+        });
+        out
     }
 
     // Cancel support methods
@@ -85,12 +455,56 @@ impl DownloadManager {
         }
     }
 
+    /// Ids of tasks with a running yt-dlp process.
+    pub fn running_task_ids(&self) -> Vec<u64> {
+        self.cancel_senders
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .keys()
+            .copied()
+            .collect()
+    }
+
+    /// Stop a running task and have it re-queued as pending instead of cancelled.
+    pub fn send_requeue(&self, task_id: u64) {
+        self.requeue_ids
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(task_id);
+        self.send_cancel(task_id);
+    }
+
+    /// Whether the cancel signal for `task_id` came from `send_requeue`.
+    pub(super) fn take_requeue(&self, task_id: u64) -> bool {
+        self.requeue_ids
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&task_id)
+    }
+
+    /// Called on every task exit path; also drops the task's speed and progress readings.
     pub(super) fn unregister_cancel(&self, task_id: u64) {
         let mut senders = self
             .cancel_senders
             .lock()
             .unwrap_or_else(|e| e.into_inner());
         senders.remove(&task_id);
+        self.speeds
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&task_id);
+        self.progress
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&task_id);
+        self.last_advance
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&task_id);
+        self.rate_capped
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&task_id);
     }
 
     /// 앱 종료 시 모든 활성 다운로드 취소. 동기적으로 cancel signal만 전송.
@@ -104,3 +518,112 @@ impl DownloadManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sync_recovers_leaked_slots() {
+        let manager = DownloadManager::new(2);
+        assert!(manager.try_acquire());
+        assert!(manager.try_acquire());
+        // Both tasks ended on a path that never called release()
+        assert!(!manager.try_acquire());
+
+        assert_eq!(manager.sync_active_count(0), 2);
+        assert_eq!(manager.active_count(), 0);
+        assert!(manager.try_acquire());
+        assert!(manager.try_acquire());
+        assert!(!manager.try_acquire());
+    }
+
+    #[test]
+    fn paused_starts_block_acquire() {
+        let manager = DownloadManager::new(2);
+        manager.set_starts_paused(true);
+        assert!(!manager.try_acquire());
+        assert_eq!(manager.free_slots(), 0);
+        manager.set_starts_paused(false);
+        assert!(manager.try_acquire());
+    }
+
+    #[test]
+    fn download_limits_block_acquire() {
+        let manager = DownloadManager::new(3);
+        manager.set_download_limits(Some(2), None);
+        manager.count_start();
+        assert!(manager.try_acquire());
+        manager.count_start();
+        assert_eq!(manager.limit_reached(), Some(DownloadLimitKind::Session));
+        assert!(!manager.try_acquire());
+
+        manager.set_download_limits(None, Some(5));
+        manager.seed_daily_starts(5);
+        assert_eq!(manager.limit_reached(), Some(DownloadLimitKind::Daily));
+        manager.set_download_limits(None, None);
+        assert!(manager.try_acquire());
+    }
+
+    #[test]
+    fn focus_caps_every_other_running_task() {
+        let manager = DownloadManager::new(3);
+        let _rx1 = manager.register_cancel(1);
+        let _rx2 = manager.register_cancel(2);
+        let _rx3 = manager.register_cancel(3);
+        assert!(manager.rate_limit_mismatches(true).is_empty());
+
+        manager.set_focused_task(Some(2));
+        assert_eq!(manager.rate_limit_mismatches(true), [1, 3]);
+        assert!(manager.rate_limit_mismatches(false).is_empty());
+        manager.mark_rate_capped(1);
+        manager.mark_rate_capped(3);
+        assert!(manager.rate_limit_mismatches(true).is_empty());
+
+        // The focused task finishing lifts the caps again
+        assert!(manager.clear_focus_if(2));
+        assert!(!manager.clear_focus_if(2));
+        manager.unregister_cancel(2);
+        assert_eq!(manager.rate_limit_mismatches(true), [1, 3]);
+    }
+
+    #[test]
+    fn progress_only_lists_running_tasks() {
+        let manager = DownloadManager::new(2);
+        let _rx = manager.register_cancel(1);
+        manager.set_stage(1, TaskStage::Starting);
+        manager.set_stage(2, TaskStage::Processing);
+        let progress = manager.active_progress();
+        assert_eq!(progress.len(), 1);
+        assert_eq!(progress[0].stage, TaskStage::Starting);
+
+        manager.unregister_cancel(1);
+        assert!(manager.active_progress().is_empty());
+    }
+
+    #[test]
+    fn stalls_skip_post_processing_and_reset_on_progress() {
+        let manager = DownloadManager::new(2);
+        let _rx1 = manager.register_cancel(1);
+        let _rx2 = manager.register_cancel(2);
+        manager.set_stage(1, TaskStage::Starting);
+        manager.set_stage(2, TaskStage::Processing);
+        let stalled = manager.stalled_tasks(Duration::ZERO);
+        assert_eq!(stalled.len(), 1);
+        assert_eq!(stalled[0].task_id, 1);
+
+        let reading = |bytes| TaskProgress {
+            task_id: 1,
+            stage: TaskStage::Downloading,
+            percent: Some(10.0),
+            speed: None,
+            eta: None,
+            downloaded_bytes: Some(bytes),
+            updated_at: 0,
+        };
+        manager.record_progress(reading(100));
+        assert!(manager.stalled_tasks(Duration::from_secs(60)).is_empty());
+        manager.record_progress(reading(100));
+        assert_eq!(manager.stalled_tasks(Duration::ZERO).len(), 1);
+    }
+}
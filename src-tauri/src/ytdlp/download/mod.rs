@@ -1,9 +1,24 @@
 pub mod commands;
+mod drain;
 mod executor;
+mod focus;
+mod leftovers;
+pub mod limits;
 mod manager;
+mod next_start;
+pub mod retry;
+pub mod subtitles;
+mod temp;
+pub mod watchdog;
 
 // Re-export all items from commands (includes __cmd__ and __specta__fn__ generated by proc macros)
 pub use commands::*;
+pub use drain::start_drain;
+pub use executor::codec_cache_dir;
+pub use executor::effective_selector;
 pub use executor::execute_download_public;
 pub use executor::process_next_pending_public;
+pub use focus::{clear_focused, set_focused};
+pub use leftovers::{active_tasks, clean_leftovers, list_leftovers, ActiveTasks};
 pub use manager::DownloadManager;
+pub use temp::{cleanup_stale_temp, is_partial_file, partial_dir};
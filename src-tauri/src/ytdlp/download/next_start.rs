@@ -0,0 +1,199 @@
+//! When the next pending task will begin (`get_next_start`).
+//!
+//! There is no fixed schedule: a task starts once a slot is free (the
+//! background throttle may lower the cap) and the start delay since the last
+//! start has passed. With every slot busy the wait is the shortest ETA among
+//! running tasks, which is only as good as yt-dlp's estimate and is missing
+//! for live streams and extractors that don't report one.
+//!
+//! Holds come first: while new starts are paused (by hand, a drain, low
+//! battery or a busy CPU) or a download limit is reached, no slot opening
+//! up will start the task.
+
+use super::DownloadManager;
+use crate::ytdlp::types::{DownloadLimitKind, NextStartConfidence, NextStartReason};
+use std::time::Duration;
+
+/// What keeps the queue from starting anything, free slot or not.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Hold {
+    Paused,
+    Battery,
+    Cpu,
+    SessionLimit,
+    /// Seconds until the daily count resets at local midnight
+    DailyLimit(u64),
+}
+
+/// The hold in effect, checked in the order the manager applies them.
+pub(super) fn hold(manager: &DownloadManager, secs_to_midnight: u64) -> Option<Hold> {
+    if manager.starts_paused() {
+        Some(Hold::Paused)
+    } else if manager.battery_paused() {
+        Some(Hold::Battery)
+    } else if manager.cpu_paused() {
+        Some(Hold::Cpu)
+    } else {
+        match manager.limit_reached()? {
+            DownloadLimitKind::Session => Some(Hold::SessionLimit),
+            DownloadLimitKind::Daily => Some(Hold::DailyLimit(secs_to_midnight)),
+        }
+    }
+}
+
+pub(super) struct Estimate {
+    pub reason: NextStartReason,
+    pub eta_secs: Option<u64>,
+    pub confidence: NextStartConfidence,
+}
+
+/// yt-dlp ETA text ("05:12", "1:02:03") in seconds.
+pub(super) fn parse_eta(eta: &str) -> Option<u64> {
+    let parts: Vec<&str> = eta.trim().split(':').collect();
+    if parts.len() < 2 || parts.len() > 3 {
+        return None;
+    }
+    parts
+        .iter()
+        .try_fold(0u64, |acc, p| p.parse::<u64>().ok().map(|n| acc * 60 + n))
+}
+
+/// `hold`: what keeps new starts back, if anything; `free_slots`: slots open
+/// right now; `delay_left`: start delay still to run; `running_etas`: ETA of
+/// each running task, None where unknown.
+pub(super) fn estimate(
+    hold: Option<Hold>,
+    free_slots: u32,
+    delay_left: Option<Duration>,
+    running_etas: &[Option<u64>],
+) -> Estimate {
+    if let Some(hold) = hold {
+        let (reason, eta_secs) = match hold {
+            Hold::Paused => (NextStartReason::Paused, None),
+            Hold::Battery => (NextStartReason::BatteryLow, None),
+            Hold::Cpu => (NextStartReason::CpuBusy, None),
+            Hold::SessionLimit => (NextStartReason::SessionLimit, None),
+            Hold::DailyLimit(secs) => (NextStartReason::DailyLimit, Some(secs)),
+        };
+        return Estimate {
+            reason,
+            eta_secs,
+            // Only the midnight reset is on a clock; the rest wait on the user or the system
+            confidence: if eta_secs.is_some() {
+                NextStartConfidence::Medium
+            } else {
+                NextStartConfidence::Low
+            },
+        };
+    }
+    let delay_secs = delay_left.map(|d| d.as_secs_f64().ceil() as u64);
+    if free_slots > 0 {
+        return match delay_secs {
+            Some(secs) if secs > 0 => Estimate {
+                reason: NextStartReason::StartDelay,
+                eta_secs: Some(secs),
+                confidence: NextStartConfidence::High,
+            },
+            _ => Estimate {
+                reason: NextStartReason::Ready,
+                eta_secs: Some(0),
+                confidence: NextStartConfidence::High,
+            },
+        };
+    }
+
+    let known: Vec<u64> = running_etas.iter().flatten().copied().collect();
+    let confidence = if !known.is_empty() && known.len() == running_etas.len() {
+        NextStartConfidence::Medium
+    } else {
+        NextStartConfidence::Low
+    };
+    Estimate {
+        reason: NextStartReason::WaitingForSlot,
+        // The delay runs from the last start, so it overlaps the wait for a slot
+        eta_secs: known
+            .iter()
+            .min()
+            .map(|&eta| eta.max(delay_secs.unwrap_or(0))),
+        confidence,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_yt_dlp_etas() {
+        assert_eq!(parse_eta("05:12"), Some(312));
+        assert_eq!(parse_eta("1:02:03"), Some(3723));
+        assert_eq!(parse_eta("..."), None);
+        assert_eq!(parse_eta("Unknown"), None);
+        assert_eq!(parse_eta("12"), None);
+    }
+
+    #[test]
+    fn free_slot_waits_only_for_start_delay() {
+        let ready = estimate(None, 1, None, &[]);
+        assert_eq!(ready.reason, NextStartReason::Ready);
+        assert_eq!(ready.eta_secs, Some(0));
+
+        let delayed = estimate(None, 2, Some(Duration::from_millis(4200)), &[Some(60)]);
+        assert_eq!(delayed.reason, NextStartReason::StartDelay);
+        assert_eq!(delayed.eta_secs, Some(5));
+        assert_eq!(delayed.confidence, NextStartConfidence::High);
+    }
+
+    #[test]
+    fn full_slots_wait_for_first_finisher() {
+        let e = estimate(None, 0, None, &[Some(300), Some(90)]);
+        assert_eq!(e.reason, NextStartReason::WaitingForSlot);
+        assert_eq!(e.eta_secs, Some(90));
+        assert_eq!(e.confidence, NextStartConfidence::Medium);
+
+        let delayed = estimate(None, 0, Some(Duration::from_secs(120)), &[Some(90)]);
+        assert_eq!(delayed.eta_secs, Some(120));
+    }
+
+    #[test]
+    fn holds_win_over_free_slots() {
+        for (hold, reason) in [
+            (Hold::Paused, NextStartReason::Paused),
+            (Hold::Battery, NextStartReason::BatteryLow),
+            (Hold::Cpu, NextStartReason::CpuBusy),
+            (Hold::SessionLimit, NextStartReason::SessionLimit),
+        ] {
+            let e = estimate(Some(hold), 2, None, &[Some(30)]);
+            assert_eq!(e.reason, reason);
+            assert_eq!(e.eta_secs, None);
+            assert_eq!(e.confidence, NextStartConfidence::Low);
+        }
+        let daily = estimate(Some(Hold::DailyLimit(3600)), 2, None, &[]);
+        assert_eq!(daily.reason, NextStartReason::DailyLimit);
+        assert_eq!(daily.eta_secs, Some(3600));
+    }
+
+    #[test]
+    fn manager_flags_become_holds() {
+        let manager = DownloadManager::new(2);
+        assert_eq!(hold(&manager, 60), None);
+        manager.set_download_limits(None, Some(1));
+        manager.count_start();
+        assert_eq!(hold(&manager, 60), Some(Hold::DailyLimit(60)));
+        manager.set_cpu_paused(true);
+        assert_eq!(hold(&manager, 60), Some(Hold::Cpu));
+        manager.set_starts_paused(true);
+        assert_eq!(hold(&manager, 60), Some(Hold::Paused));
+    }
+
+    #[test]
+    fn unknown_etas_lower_confidence() {
+        let partial = estimate(None, 0, None, &[None, Some(40)]);
+        assert_eq!(partial.eta_secs, Some(40));
+        assert_eq!(partial.confidence, NextStartConfidence::Low);
+
+        let unknown = estimate(None, 0, None, &[None]);
+        assert_eq!(unknown.eta_secs, None);
+        assert_eq!(unknown.confidence, NextStartConfidence::Low);
+    }
+}
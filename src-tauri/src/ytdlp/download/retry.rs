@@ -0,0 +1,60 @@
+//! Automatic retries of downloads that failed for a transient reason.
+//!
+//! The `max_retries` / `retry_backoff_secs` settings are the defaults and a
+//! task may override either. Waits double with every attempt. Failures a
+//! retry can't fix (login, removed video, size cap, full disk...) are never
+//! retried.
+
+use crate::ytdlp::types::{AppSettings, DownloadErrorKind, DownloadTaskInfo, RetryPolicy};
+use std::time::Duration;
+
+pub const MAX_RETRIES_LIMIT: u32 = 10;
+pub const MAX_BACKOFF_SECS: u32 = 3600;
+
+/// The task's overrides on top of the settings.
+pub fn effective(task: &DownloadTaskInfo, settings: &AppSettings) -> RetryPolicy {
+    RetryPolicy {
+        max_retries: task.max_retries.unwrap_or(settings.max_retries),
+        backoff_secs: task
+            .retry_backoff_secs
+            .unwrap_or(settings.retry_backoff_secs),
+        overridden: task.max_retries.is_some() || task.retry_backoff_secs.is_some(),
+    }
+}
+
+/// Failures that may go away on their own.
+pub fn is_retryable(kind: DownloadErrorKind) -> bool {
+    matches!(
+        kind,
+        DownloadErrorKind::Network | DownloadErrorKind::RateLimited | DownloadErrorKind::Unknown
+    )
+}
+
+/// Wait before retry number `attempt` (1-based): the backoff, doubled per earlier attempt.
+pub fn delay(backoff_secs: u32, attempt: u32) -> Duration {
+    let factor = 1u64 << attempt.saturating_sub(1).min(16);
+    Duration::from_secs((backoff_secs as u64 * factor).min(MAX_BACKOFF_SECS as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_and_is_capped() {
+        assert_eq!(delay(30, 1), Duration::from_secs(30));
+        assert_eq!(delay(30, 2), Duration::from_secs(60));
+        assert_eq!(delay(30, 3), Duration::from_secs(120));
+        assert_eq!(delay(30, 40), Duration::from_secs(MAX_BACKOFF_SECS as u64));
+        assert_eq!(delay(0, 3), Duration::ZERO);
+    }
+
+    #[test]
+    fn only_transient_failures_are_retried() {
+        assert!(is_retryable(DownloadErrorKind::Network));
+        assert!(is_retryable(DownloadErrorKind::RateLimited));
+        assert!(!is_retryable(DownloadErrorKind::Auth));
+        assert!(!is_retryable(DownloadErrorKind::SizeLimit));
+        assert!(!is_retryable(DownloadErrorKind::Unavailable));
+    }
+}
@@ -0,0 +1,257 @@
+//! Subtitle-only tasks (`DownloadRequest.subtitles_only`): yt-dlp runs with
+//! `--skip-download --write-subs`, and every subtitle file it writes becomes its
+//! own history entry. A video without subtitles in the requested languages
+//! completes with a note rather than failing.
+
+use super::manager::DownloadManager;
+use crate::modules::logger;
+use crate::modules::types::AppError;
+use crate::ytdlp::credentials;
+use crate::ytdlp::security;
+use crate::ytdlp::types::*;
+use std::path::Path;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Targets yt-dlp's `--convert-subs` accepts
+pub const SUBTITLE_FORMATS: &[&str] = &["srt", "vtt", "ass", "lrc"];
+
+/// Completion note for a task that found nothing to write
+pub const NO_SUBTITLES: &str = "No subtitles available for the requested languages";
+
+/// Trim and check the requested languages. Each is "all" or a language code,
+/// optionally with ".*" to match its regional variants ("en.*" → en-US, en-GB).
+pub fn sanitize_langs(langs: &[String]) -> Result<Vec<String>, AppError> {
+    let mut out: Vec<String> = Vec::new();
+    for lang in langs {
+        let lang = lang.trim();
+        if lang.is_empty() {
+            continue;
+        }
+        let lang = if lang.eq_ignore_ascii_case("all") {
+            "all".to_string()
+        } else {
+            match lang.strip_suffix(".*") {
+                Some(code) => format!("{}.*", security::sanitize_language(code)?),
+                None => security::sanitize_language(lang)?,
+            }
+        };
+        if !out.contains(&lang) {
+            out.push(lang);
+        }
+    }
+    if out.is_empty() {
+        return Err(AppError::Custom(
+            "Subtitles-only downloads need at least one subtitle language".to_string(),
+        ));
+    }
+    Ok(out)
+}
+
+/// Lowercase `format` and check it is a `--convert-subs` target.
+pub fn sanitize_format(format: &str) -> Result<String, AppError> {
+    let format = format.trim().to_lowercase();
+    if !SUBTITLE_FORMATS.contains(&format.as_str()) {
+        return Err(AppError::Custom(format!(
+            "Unsupported subtitle format: {} (supported: {})",
+            format,
+            SUBTITLE_FORMATS.join(", ")
+        )));
+    }
+    Ok(format)
+}
+
+/// yt-dlp arguments that fetch only the subtitles.
+pub fn args(langs: &[String], convert: Option<&str>) -> Vec<String> {
+    let mut args = vec![
+        "--skip-download".to_string(),
+        "--write-subs".to_string(),
+        "--sub-langs".to_string(),
+        langs.join(","),
+    ];
+    if let Some(format) = convert {
+        args.extend(["--convert-subs".to_string(), format.to_string()]);
+    }
+    args
+}
+
+/// Path from `[info] Writing video subtitles to: PATH`, with the extension it
+/// ends up with after `--convert-subs`.
+pub fn written_path(line: &str, convert: Option<&str>) -> Option<String> {
+    let path = line
+        .strip_prefix("[info] Writing video subtitles to: ")?
+        .trim();
+    Some(match convert {
+        Some(format) => Path::new(path)
+            .with_extension(format)
+            .to_string_lossy()
+            .to_string(),
+        None => path.to_string(),
+    })
+}
+
+/// Language part of `Title.en.srt`.
+fn file_lang(path: &str) -> Option<String> {
+    let stem = Path::new(path).file_stem()?;
+    Path::new(stem)
+        .extension()
+        .map(|l| l.to_string_lossy().to_string())
+}
+
+/// Record a finished subtitle-only task: one history entry per file, or a
+/// completed task with `NO_SUBTITLES` when yt-dlp wrote none.
+pub(super) async fn finish(app: &AppHandle, task: &DownloadTaskInfo, paths: Vec<String>) {
+    let db_state = app.state::<crate::DbState>();
+    let manager = app.state::<Arc<DownloadManager>>();
+    let task_id = task.id;
+    let completed_at = chrono::Utc::now().timestamp();
+
+    let mut recorded = false;
+    let mut total_size = 0u64;
+    for path in &paths {
+        let size = tokio::fs::metadata(path).await.ok().map(|m| m.len());
+        total_size += size.unwrap_or(0);
+        let item = HistoryItem {
+            id: 0,
+            video_url: task.video_url.clone(),
+            video_id: task.video_id.clone(),
+            title: task.title.clone(),
+            quality_label: match file_lang(path) {
+                Some(lang) => format!("Subtitles ({})", lang),
+                None => "Subtitles".to_string(),
+            },
+            format: task.format_id.clone(),
+            file_path: path.clone(),
+            file_size: size,
+            downloaded_at: completed_at,
+            recode_target: None,
+            max_height: None,
+            duration_secs: None,
+            superseded_by: None,
+            chapters_embedded: false,
+            remux_target: None,
+            uploader: None,
+            container: crate::ytdlp::normalize::container_from_path(path),
+            tags: Vec::new(),
+            audio_lang: None,
+            verify_status: None,
+            verify_error: None,
+            destinations: None,
+            preset: task.preset.clone(),
+            vcodec: None,
+            is_favorite: false,
+            user_tags: task.user_tags.clone(),
+        };
+        // The first file completes the task; the others are added alongside it
+        let result = if recorded {
+            db_state.insert_history(&item).map(|_| ())
+        } else {
+            db_state.complete_and_record(task_id, completed_at, &item)
+        };
+        match result {
+            Ok(()) => recorded = true,
+            Err(e) => logger::error_cat(
+                "download",
+                &format!(
+                    "[download:{}] failed to record subtitle {}: {}",
+                    task_id, path, e
+                ),
+            ),
+        }
+    }
+
+    crate::ytdlp::bandwidth::record(app, total_size);
+
+    let message = if paths.is_empty() {
+        let _ = db_state.update_download_status(
+            task_id,
+            &DownloadStatus::Completed,
+            Some(NO_SUBTITLES),
+        );
+        let _ = db_state.mark_completed(task_id, completed_at);
+        Some(NO_SUBTITLES.to_string())
+    } else {
+        if !recorded {
+            let _ = db_state.mark_completed(task_id, completed_at);
+        }
+        None
+    };
+
+    if task.has_credentials {
+        credentials::delete(task_id);
+    }
+
+    logger::info_cat(
+        "download",
+        &format!(
+            "[download:{}] subtitles only: {} file(s) written",
+            task_id,
+            paths.len()
+        ),
+    );
+
+    let _ = app.emit(
+        "download-event",
+        GlobalDownloadEvent {
+            task_id,
+            event_type: "completed".to_string(),
+            percent: Some(100.0),
+            speed: None,
+            eta: None,
+            file_path: paths.first().cloned(),
+            file_size: Some(total_size),
+            message,
+            downloaded_bytes: None,
+            elapsed_secs: None,
+        },
+    );
+    manager.publish(QueueEvent::task(QueueEventKind::Completed, task_id));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn sanitize_langs_requires_one_language() {
+        assert_eq!(
+            sanitize_langs(&strings(&[" en ", "ALL", "ja.*", "en"])).unwrap(),
+            strings(&["en", "all", "ja.*"])
+        );
+        assert!(sanitize_langs(&strings(&["", " "])).is_err());
+        assert!(sanitize_langs(&strings(&["en,ja"])).is_err());
+        assert!(sanitize_langs(&strings(&["-live_chat"])).is_err());
+        assert!(sanitize_format(" SRT").is_ok());
+        assert!(sanitize_format("json3").is_err());
+    }
+
+    #[test]
+    fn builds_args_and_final_paths() {
+        assert_eq!(
+            args(&strings(&["en", "ko"]), Some("srt")),
+            strings(&[
+                "--skip-download",
+                "--write-subs",
+                "--sub-langs",
+                "en,ko",
+                "--convert-subs",
+                "srt"
+            ])
+        );
+        let line = "[info] Writing video subtitles to: /dl/Clip [abc].en.vtt";
+        assert_eq!(
+            written_path(line, Some("srt")).as_deref(),
+            Some("/dl/Clip [abc].en.srt")
+        );
+        assert_eq!(
+            written_path(line, None).as_deref(),
+            Some("/dl/Clip [abc].en.vtt")
+        );
+        assert_eq!(written_path("[info] There are no subtitles", None), None);
+        assert_eq!(file_lang("/dl/Clip.pt-BR.srt").as_deref(), Some("pt-BR"));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::modules::logger;
+use crate::ytdlp::types::AppSettings;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+/// Default temp location for partial downloads when no custom temp dir is set.
+fn default_temp_dir(app: &AppHandle) -> Option<PathBuf> {
+    app.path().app_cache_dir().ok().map(|d| d.join("partial"))
+}
+
+/// The temp dir partials go to when `use_temp_dir` is on, and whether the app
+/// owns it (the default dir) rather than the user having chosen it.
+pub fn partial_dir(app: &AppHandle, settings: &AppSettings) -> Option<(PathBuf, bool)> {
+    match settings.temp_dir.as_deref().filter(|d| !d.is_empty()) {
+        Some(dir) => Some((PathBuf::from(dir), false)),
+        None => default_temp_dir(app).map(|dir| (dir, true)),
+    }
+}
+
+/// Resolve the temp dir for partial files, or None when `use_temp_dir` is off.
+pub(super) fn temp_dir_for(app: &AppHandle, settings: &AppSettings) -> Option<PathBuf> {
+    if !settings.use_temp_dir {
+        return None;
+    }
+    partial_dir(app, settings).map(|(dir, _)| dir)
+}
+
+/// Walk up to the nearest existing ancestor (the output dir may not exist yet).
+fn existing_ancestor(path: &Path) -> Option<&Path> {
+    path.ancestors().find(|p| p.exists())
+}
+
+/// Check if two paths live on the same volume, so the final move is a cheap rename.
+/// Returns None when it can't be determined.
+pub(super) fn is_same_volume(a: &Path, b: &Path) -> Option<bool> {
+    let a = existing_ancestor(a)?;
+    let b = existing_ancestor(b)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let dev_a = std::fs::metadata(a).ok()?.dev();
+        let dev_b = std::fs::metadata(b).ok()?.dev();
+        Some(dev_a == dev_b)
+    }
+
+    #[cfg(not(unix))]
+    {
+        // Windows: compare drive/UNC prefixes
+        let prefix = |p: &Path| {
+            p.components()
+                .next()
+                .map(|c| c.as_os_str().to_string_lossy().to_lowercase())
+        };
+        Some(prefix(a)? == prefix(b)?)
+    }
+}
+
+/// yt-dlp intermediate files: `.part`, `.part-Frag12`, `.ytdl`, `.temp.mp4`
+pub fn is_partial_file(name: &str) -> bool {
+    name.ends_with(".part")
+        || name.ends_with(".ytdl")
+        || name.contains(".part-Frag")
+        || name.contains(".temp.")
+}
+
+/// Remove partial files left in the temp dir by a previous session.
+/// Runs on startup before any download is spawned. Files a resumable task may
+/// continue from (`leftovers::active_tasks`: queued, re-queued after sleep, or
+/// failed retryably, e.g. by the app closing) are kept. Otherwise the app-owned
+/// default dir is emptied fully; a user-chosen dir only loses yt-dlp partials.
+/// Nothing is touched while `use_temp_dir` is off.
+pub fn cleanup_stale_temp(app: &AppHandle, settings: &AppSettings) {
+    if !settings.use_temp_dir {
+        return;
+    }
+    let Some((dir, owned)) = partial_dir(app, settings) else {
+        return;
+    };
+    let active = match super::leftovers::active_tasks(app) {
+        Ok(active) => active,
+        Err(e) => {
+            // Without the queue there's no telling what is resumable, so keep everything
+            logger::warn_cat(
+                "app",
+                &format!("Skipped temp cleanup; can't read the queue: {}", e),
+            );
+            return;
+        }
+    };
+
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    let mut removed = 0u32;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        if (owned || is_partial_file(&name))
+            && !active.owns(&name)
+            && std::fs::remove_file(&path).is_ok()
+        {
+            removed += 1;
+        }
+    }
+
+    if removed > 0 {
+        logger::info_cat(
+            "app",
+            &format!(
+                "Removed {} stale temp file(s) from {}",
+                removed,
+                dir.display()
+            ),
+        );
+    }
+}
@@ -0,0 +1,133 @@
+//! Queue watchdog: looks for pending tasks that sit with free slots while
+//! nothing is running, the state a broken start chain leaves behind, and
+//! restarts the queue when it finds one.
+//!
+//! Each check is two COUNT queries. Pauses, download limits and the start
+//! delay keep holding the queue: they zero the free slots or are honored by
+//! `process_next_pending` itself.
+
+use super::executor::process_next_pending;
+use super::manager::DownloadManager;
+use crate::modules::logger;
+use crate::modules::types::AppError;
+use crate::ytdlp::types::QueueStallCheck;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+pub const MIN_INTERVAL_SECS: u32 = 5;
+pub const MAX_INTERVAL_SECS: u32 = 3600;
+/// How often a disabled watchdog looks at the setting again
+const DISABLED_POLL: Duration = Duration::from_secs(60);
+
+/// Set when the last periodic check saw a stall; the next one acts on it.
+static SUSPECT: AtomicBool = AtomicBool::new(false);
+
+/// 0 turns the watchdog off; other values are kept within range.
+pub fn clamp_interval(secs: u32) -> u32 {
+    if secs == 0 {
+        0
+    } else {
+        secs.clamp(MIN_INTERVAL_SECS, MAX_INTERVAL_SECS)
+    }
+}
+
+fn interval_secs(app: &AppHandle) -> u32 {
+    crate::ytdlp::settings::get_settings(app)
+        .map(|s| clamp_interval(s.queue_watchdog_secs))
+        .unwrap_or(0)
+}
+
+/// Check the queue every `queue_watchdog_secs`.
+pub fn spawn_watchdog(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let secs = interval_secs(&app);
+            if secs == 0 {
+                SUSPECT.store(false, Ordering::SeqCst);
+                tokio::time::sleep(DISABLED_POLL).await;
+                continue;
+            }
+            tokio::time::sleep(Duration::from_secs(secs as u64)).await;
+            if let Err(e) = check(&app, false) {
+                logger::warn_cat("download", &format!("Queue watchdog check failed: {}", e));
+            }
+        }
+    });
+}
+
+fn is_stalled(pending: u32, running: u32, manager: &DownloadManager) -> bool {
+    // A leaked slot counts as taken even though nothing runs in it
+    pending > 0 && running == 0 && (manager.free_slots() > 0 || manager.active_count() > 0)
+}
+
+/// Look for a stalled queue and restart it. Periodic checks only act when two
+/// in a row see the stall, so a task caught between claiming a slot and being
+/// marked downloading isn't mistaken for one; `immediate` acts right away.
+pub fn check(app: &AppHandle, immediate: bool) -> Result<QueueStallCheck, AppError> {
+    let db = app.state::<crate::DbState>();
+    let manager = app.state::<Arc<DownloadManager>>();
+
+    let pending = db.get_pending_count()?;
+    let running = db.get_active_count()?;
+    let stalled = is_stalled(pending, running, &manager);
+    let confirmed = stalled && (immediate || SUSPECT.swap(true, Ordering::SeqCst));
+    if !stalled {
+        SUSPECT.store(false, Ordering::SeqCst);
+    }
+    if !confirmed {
+        return Ok(QueueStallCheck {
+            pending,
+            running,
+            stalled,
+            started: 0,
+        });
+    }
+    SUSPECT.store(false, Ordering::SeqCst);
+
+    let leaked = manager.sync_active_count(0);
+    process_next_pending(app.clone());
+    let started = db.get_active_count()?;
+    if started > 0 || leaked > 0 {
+        logger::warn_cat(
+            "download",
+            &format!(
+                "Queue watchdog: {} pending task(s) weren't starting with nothing running \
+                 ({} slot(s) leaked); restarted the queue, {} started",
+                pending, leaked, started
+            ),
+        );
+    }
+    Ok(QueueStallCheck {
+        pending,
+        running,
+        stalled,
+        started,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stall_needs_pending_work_and_nothing_running() {
+        let manager = DownloadManager::new(2);
+        assert!(is_stalled(3, 0, &manager));
+        assert!(!is_stalled(0, 0, &manager));
+        assert!(!is_stalled(3, 1, &manager));
+
+        // Paused queues hold their tasks on purpose
+        manager.set_starts_paused(true);
+        assert!(!is_stalled(3, 0, &manager));
+    }
+
+    #[test]
+    fn interval_is_off_or_within_range() {
+        assert_eq!(clamp_interval(0), 0);
+        assert_eq!(clamp_interval(1), MIN_INTERVAL_SECS);
+        assert_eq!(clamp_interval(30), 30);
+        assert_eq!(clamp_interval(100_000), MAX_INTERVAL_SECS);
+    }
+}
@@ -0,0 +1,189 @@
+//! Coarse cause of a failed download, read from its error text.
+//!
+//! Matches both the app's own messages (see the executor's failure branch)
+//! and the yt-dlp stderr appended to them. The first matching rule wins, so
+//! more specific causes come first.
+
+use super::types::DownloadErrorKind;
+
+const RULES: &[(DownloadErrorKind, &[&str])] = &[
+    (
+        DownloadErrorKind::SizeLimit,
+        &["최대 크기 제한", "larger than max-filesize"],
+    ),
+    (
+        DownloadErrorKind::Auth,
+        &[
+            "인증에 실패",
+            "incorrect username or password",
+            "invalid username or password",
+            "wrong password",
+            "password is incorrect",
+            "unable to log in",
+            "login failed",
+            "protected by a password",
+            "use --video-password",
+            "use --username and --password",
+        ],
+    ),
+    (
+        DownloadErrorKind::Cookies,
+        &[
+            "브라우저 쿠키",
+            "could not copy",
+            "failed to decrypt",
+            "cookies database",
+        ],
+    ),
+    (
+        DownloadErrorKind::AgeRestricted,
+        &[
+            "sign in to confirm your age",
+            "age-restricted",
+            "age restricted",
+        ],
+    ),
+    (
+        DownloadErrorKind::GeoBlocked,
+        &[
+            "not available in your country",
+            "geo restriction",
+            "geo-restricted",
+            "from your location",
+        ],
+    ),
+    (
+        DownloadErrorKind::RateLimited,
+        &[
+            "http error 429",
+            "too many requests",
+            "rate-limited",
+            "confirm you're not a bot",
+            "confirm you’re not a bot",
+        ],
+    ),
+    (
+        DownloadErrorKind::Unavailable,
+        &[
+            "video unavailable",
+            "private video",
+            "this video has been removed",
+            "account associated with this video has been terminated",
+            "http error 404",
+            "http error 410",
+            "members-only",
+            "join this channel",
+        ],
+    ),
+    (
+        DownloadErrorKind::FormatUnavailable,
+        &[
+            "requested format is not available",
+            "no video formats found",
+        ],
+    ),
+    (
+        DownloadErrorKind::DiskFull,
+        &[
+            "no space left on device",
+            "errno 28",
+            "disk is full",
+            "not enough space",
+        ],
+    ),
+    (
+        DownloadErrorKind::Encoding,
+        &["인코딩 오류", "unicodeencodeerror", "cp949", "cp932"],
+    ),
+    (
+        DownloadErrorKind::Postprocess,
+        &["postprocessing:", "ffmpeg", "ffprobe", "conversion failed"],
+    ),
+    (
+        DownloadErrorKind::Network,
+        &[
+            "네트워크 연결",
+            "unable to download webpage",
+            "connection reset",
+            "connection refused",
+            "timed out",
+            "name or service not known",
+            "getaddrinfo failed",
+            "temporary failure in name resolution",
+            "ssl:",
+            "http error 5",
+        ],
+    ),
+];
+
+pub fn classify_download_error(text: &str) -> DownloadErrorKind {
+    let lower = text.to_lowercase();
+    RULES
+        .iter()
+        .find(|(_, patterns)| patterns.iter().any(|p| lower.contains(p)))
+        .map(|(kind, _)| *kind)
+        .unwrap_or(DownloadErrorKind::Unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_app_messages() {
+        assert_eq!(
+            classify_download_error(
+                "인증에 실패했습니다. 사용자 이름, 비밀번호 또는 동영상 암호를 확인하세요."
+            ),
+            DownloadErrorKind::Auth
+        );
+        assert_eq!(
+            classify_download_error("네트워크 연결 문제입니다. 인터넷 연결을 확인하세요."),
+            DownloadErrorKind::Network
+        );
+    }
+
+    #[test]
+    fn classifies_yt_dlp_stderr() {
+        assert_eq!(
+            classify_download_error(
+                "ERROR: [youtube] abc: Private video. Sign in if you've been granted access"
+            ),
+            DownloadErrorKind::Unavailable
+        );
+        assert_eq!(
+            classify_download_error(
+                "ERROR: unable to download video data: HTTP Error 429: Too Many Requests"
+            ),
+            DownloadErrorKind::RateLimited
+        );
+        assert_eq!(
+            classify_download_error("ERROR: [youtube] abc: Requested format is not available"),
+            DownloadErrorKind::FormatUnavailable
+        );
+        assert_eq!(
+            classify_download_error("ERROR: Postprocessing: Conversion failed!"),
+            DownloadErrorKind::Postprocess
+        );
+        assert_eq!(
+            classify_download_error("something odd happened"),
+            DownloadErrorKind::Unknown
+        );
+    }
+
+    #[test]
+    fn specific_causes_win() {
+        // The size-limit rule is checked before everything else
+        assert_eq!(
+            classify_download_error(
+                "[download] File is larger than max-filesize (5 bytes > 1 bytes). Aborting."
+            ),
+            DownloadErrorKind::SizeLimit
+        );
+        // Age gate text also contains "unable to download webpage" noise
+        assert_eq!(
+            classify_download_error("unable to download webpage; Sign in to confirm your age"),
+            DownloadErrorKind::AgeRestricted
+        );
+    }
+}
@@ -0,0 +1,67 @@
+//! Files already on disk where a new download would land.
+//!
+//! The DB duplicate check only knows what history remembers; after history is
+//! cleared the files stay. `check_duplicate` resolves the would-be filename
+//! and looks for it here. yt-dlp may settle on another container after
+//! merging, so a file with the same stem and a media extension counts too.
+
+use std::path::{Path, PathBuf};
+
+const MEDIA_EXTENSIONS: &[&str] = &[
+    "mp4", "mkv", "webm", "mov", "avi", "flv", "m4a", "mp3", "opus", "ogg", "flac", "wav", "aac",
+];
+
+/// Highest " (n)" tried before giving up on a free name
+const MAX_RENAME_ATTEMPTS: u32 = 999;
+
+/// The file at `path`, or a sibling with the same stem and a media extension.
+pub fn find_on_disk(path: &Path) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path.to_path_buf());
+    }
+    let stem = path.file_stem()?;
+    let dir = path.parent()?;
+    MEDIA_EXTENSIONS
+        .iter()
+        .map(|ext| dir.join(format!("{}.{}", stem.to_string_lossy(), ext)))
+        .find(|candidate| candidate.is_file())
+}
+
+/// File name `name` with " (n)" before its extension, for the lowest n where
+/// `exists` is false.
+pub fn free_name(name: &str, exists: impl Fn(&str) -> bool) -> Option<String> {
+    let path = Path::new(name);
+    let stem = path.file_stem()?.to_string_lossy();
+    let ext = path.extension().map(|e| e.to_string_lossy());
+    (1..=MAX_RENAME_ATTEMPTS)
+        .map(|n| match &ext {
+            Some(ext) => format!("{} ({}).{}", stem, n, ext),
+            None => format!("{} ({})", stem, n),
+        })
+        .find(|candidate| !exists(candidate))
+}
+
+/// A literal filename as an output template: `%` would start a field.
+pub fn literal_template(name: &str) -> String {
+    name.replace('%', "%%")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_name_skips_taken_numbers() {
+        let taken = ["clip (1).mp4", "clip (2).mp4"];
+        assert_eq!(
+            free_name("clip.mp4", |c| taken.contains(&c)).as_deref(),
+            Some("clip (3).mp4")
+        );
+        assert_eq!(free_name("clip", |_| false).as_deref(), Some("clip (1)"));
+    }
+
+    #[test]
+    fn literal_template_escapes_fields() {
+        assert_eq!(literal_template("100% done.mp4"), "100%% done.mp4");
+    }
+}
@@ -0,0 +1,277 @@
+//! Which parts of a download need ffmpeg, in one place: `plan` decides the
+//! postprocessing `execute_download` asks yt-dlp for, and `required` lists the
+//! same steps so the UI can warn before queueing when ffmpeg is missing instead
+//! of the task failing or silently skipping a step.
+
+use crate::ytdlp::download::subtitles;
+use crate::ytdlp::types::{AppSettings, DownloadRequest, DownloadTaskInfo};
+use crate::ytdlp::{output_paths, security};
+
+/// Merging separate video and audio streams into one file
+pub const MERGE: &str = "merge";
+/// `recodeVideo`, including the lossless remux of `autoContainer`
+pub const RECODE: &str = "recode";
+pub const EMBED_METADATA: &str = "embedMetadata";
+pub const EMBED_THUMBNAIL: &str = "embedThumbnail";
+pub const EMBED_CHAPTERS: &str = "embedChapters";
+/// `--convert-subs` on a subtitle-only task
+pub const CONVERT_SUBS: &str = "convertSubs";
+
+/// Recode targets yt-dlp can embed a thumbnail into (alac lands in .m4a, vorbis in .ogg).
+const THUMBNAIL_CONTAINERS: &[&str] = &[
+    "mp3", "mkv", "mka", "ogg", "vorbis", "opus", "flac", "m4a", "alac", "mp4", "mov",
+];
+
+/// The options of a request or queued task that decide its postprocessing.
+pub struct Job<'a> {
+    pub format_id: &'a str,
+    pub keep_separate_streams: bool,
+    pub subtitles_only: bool,
+    pub convert_subs: Option<&'a str>,
+    pub embed_metadata: bool,
+    pub embed_thumbnail: bool,
+}
+
+impl<'a> From<&'a DownloadRequest> for Job<'a> {
+    fn from(r: &'a DownloadRequest) -> Self {
+        Job {
+            format_id: &r.format_id,
+            keep_separate_streams: r.keep_separate_streams,
+            subtitles_only: r.subtitles_only,
+            convert_subs: r.convert_subs.as_deref(),
+            embed_metadata: r.embed_metadata,
+            embed_thumbnail: r.embed_thumbnail,
+        }
+    }
+}
+
+impl<'a> From<&'a DownloadTaskInfo> for Job<'a> {
+    fn from(t: &'a DownloadTaskInfo) -> Self {
+        Job {
+            format_id: &t.format_id,
+            keep_separate_streams: t.keep_separate_streams,
+            subtitles_only: t.subtitles_only,
+            convert_subs: t.convert_subs.as_deref(),
+            embed_metadata: t.embed_metadata,
+            embed_thumbnail: t.embed_thumbnail,
+        }
+    }
+}
+
+/// ffmpeg postprocessing of one download.
+#[derive(Debug, Default, PartialEq)]
+pub struct Plan {
+    pub merge: bool,
+    /// `--recode-video` target (or remux target under `autoContainer`)
+    pub recode: Option<String>,
+    pub embed_chapters: bool,
+    pub embed_metadata: bool,
+    pub embed_thumbnail: bool,
+    /// `--convert-subs` target of a subtitle-only task
+    pub convert_subs: Option<String>,
+}
+
+impl Plan {
+    /// Feature names of the steps, in the order they run.
+    pub fn features(&self) -> Vec<String> {
+        [
+            (self.merge, MERGE),
+            (self.recode.is_some(), RECODE),
+            (self.embed_chapters, EMBED_CHAPTERS),
+            (self.embed_metadata, EMBED_METADATA),
+            (self.embed_thumbnail, EMBED_THUMBNAIL),
+            (self.convert_subs.is_some(), CONVERT_SUBS),
+        ]
+        .into_iter()
+        .filter(|(on, _)| *on)
+        .map(|(_, name)| name.to_string())
+        .collect()
+    }
+}
+
+/// The first choice of a format selector merges streams ("bv+ba/b").
+fn merges(format: &str) -> bool {
+    format
+        .split('/')
+        .next()
+        .is_some_and(|first| first.contains('+'))
+}
+
+/// Postprocessing `job` gets under `settings`.
+pub fn plan(job: &Job, settings: &AppSettings) -> Plan {
+    if job.subtitles_only {
+        return Plan {
+            convert_subs: job
+                .convert_subs
+                .and_then(|f| subtitles::sanitize_format(f).ok()),
+            ..Plan::default()
+        };
+    }
+
+    let separate = job.keep_separate_streams;
+    Plan {
+        merge: !separate && merges(job.format_id),
+        // Recoding is skipped for separate streams, and an audio-only file has no video to recode
+        recode: settings
+            .recode_video
+            .as_deref()
+            .and_then(|f| security::sanitize_recode_format(f).ok())
+            .filter(|_| !separate && !output_paths::is_audio_only(job.format_id)),
+        embed_chapters: settings.embed_chapters,
+        embed_metadata: job.embed_metadata,
+        embed_thumbnail: job.embed_thumbnail,
+        convert_subs: None,
+    }
+}
+
+/// Reject embed options `job` can't honour under `settings` before it is queued,
+/// instead of letting yt-dlp fail (or skip them) in postprocessing.
+pub fn check_embeds(job: &Job, settings: &AppSettings) -> Result<(), String> {
+    if job.subtitles_only && (job.embed_metadata || job.embed_thumbnail) {
+        return Err(
+            "Subtitle-only downloads have no media file to embed metadata or a thumbnail into"
+                .to_string(),
+        );
+    }
+    // The video-only stream is often WebM, which has no cover art
+    if job.keep_separate_streams && job.embed_thumbnail {
+        return Err(
+            "A thumbnail can't be embedded when video and audio are kept separate".to_string(),
+        );
+    }
+    if let Some(container) = plan(job, settings).recode {
+        if job.embed_thumbnail && !THUMBNAIL_CONTAINERS.contains(&container.as_str()) {
+            return Err(format!(
+                "{} can't hold an embedded thumbnail; turn off thumbnail embedding or re-encoding",
+                container
+            ));
+        }
+        if job.embed_metadata && container == "gif" {
+            return Err(
+                "gif can't hold embedded metadata; turn off metadata embedding or re-encoding"
+                    .to_string(),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// ffmpeg-dependent features `request` would use under `settings`, in the
+/// order they run. Empty when it needs no ffmpeg at all.
+pub fn required(request: &DownloadRequest, settings: &AppSettings) -> Vec<String> {
+    plan(&request.into(), settings).features()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(format_id: &str) -> DownloadRequest {
+        DownloadRequest {
+            video_url: "https://www.youtube.com/watch?v=abc".to_string(),
+            video_id: "abc".to_string(),
+            title: "t".to_string(),
+            format_id: format_id.to_string(),
+            quality_label: "Best".to_string(),
+            output_dir: None,
+            cookie_browser: None,
+            playlist_id: None,
+            playlist_index: None,
+            credentials: None,
+            keep_separate_streams: false,
+            max_filesize: None,
+            audio_lang: None,
+            embed_metadata: false,
+            embed_thumbnail: false,
+            preset: None,
+            live_from_start: false,
+            subtitles_only: false,
+            subtitle_langs: Vec::new(),
+            convert_subs: None,
+            tags: Vec::new(),
+            headers: None,
+            max_retries: None,
+            retry_backoff_secs: None,
+            on_existing_file: None,
+        }
+    }
+
+    #[test]
+    fn single_file_without_extras_needs_nothing() {
+        let settings = AppSettings {
+            embed_chapters: false,
+            ..AppSettings::default()
+        };
+        assert!(required(&request("best"), &settings).is_empty());
+        assert!(required(&request("bestaudio/best"), &settings).is_empty());
+    }
+
+    #[test]
+    fn lists_merge_recode_and_embeds() {
+        let settings = AppSettings {
+            recode_video: Some("mp4".to_string()),
+            embed_chapters: true,
+            ..AppSettings::default()
+        };
+        let mut r = request("bestvideo+bestaudio/best");
+        r.embed_thumbnail = true;
+        assert_eq!(
+            required(&r, &settings),
+            [MERGE, RECODE, EMBED_CHAPTERS, EMBED_THUMBNAIL]
+        );
+        // Separate streams are neither merged nor recoded
+        r.keep_separate_streams = true;
+        assert_eq!(required(&r, &settings), [EMBED_CHAPTERS, EMBED_THUMBNAIL]);
+
+        let mut subs = request("best");
+        subs.subtitles_only = true;
+        assert!(required(&subs, &settings).is_empty());
+        subs.convert_subs = Some("srt".to_string());
+        assert_eq!(required(&subs, &settings), [CONVERT_SUBS]);
+    }
+
+    #[test]
+    fn audio_only_formats_are_not_recoded() {
+        let settings = AppSettings {
+            recode_video: Some("mp4".to_string()),
+            embed_chapters: false,
+            ..AppSettings::default()
+        };
+        let audio = plan(&(&request("bestaudio/best")).into(), &settings);
+        assert_eq!(audio.recode, None);
+        assert!(audio.features().is_empty());
+        let video = plan(&(&request("bv*+ba/b")).into(), &settings);
+        assert_eq!(video.recode.as_deref(), Some("mp4"));
+    }
+
+    #[test]
+    fn rejects_embeds_the_output_cannot_hold() {
+        let settings = AppSettings::default();
+        let mut r = request("bv*+ba/b");
+        r.embed_thumbnail = true;
+        r.embed_metadata = true;
+        assert!(check_embeds(&(&r).into(), &settings).is_ok());
+
+        let mut subs = r.clone();
+        subs.subtitles_only = true;
+        assert!(check_embeds(&(&subs).into(), &settings).is_err());
+
+        let mut separate = r.clone();
+        separate.keep_separate_streams = true;
+        assert!(check_embeds(&(&separate).into(), &settings).is_err());
+        separate.embed_thumbnail = false;
+        assert!(check_embeds(&(&separate).into(), &settings).is_ok());
+
+        let webm = AppSettings {
+            recode_video: Some("webm".to_string()),
+            ..AppSettings::default()
+        };
+        assert!(check_embeds(&(&r).into(), &webm).is_err());
+        r.embed_thumbnail = false;
+        assert!(check_embeds(&(&r).into(), &webm).is_ok());
+        // Audio-only downloads aren't recoded, so the target doesn't apply
+        let mut audio = request("bestaudio/best");
+        audio.embed_thumbnail = true;
+        assert!(check_embeds(&(&audio).into(), &webm).is_ok());
+    }
+}
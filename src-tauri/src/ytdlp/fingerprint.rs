@@ -0,0 +1,112 @@
+//! Heuristic "same content" matching across re-downloads and re-encodes.
+//!
+//! A history row's fingerprint is its title reduced to a sorted set of words,
+//! with quality and release-type noise ("official", "4k", "lyrics", ...)
+//! dropped. Two rows look alike when their durations agree within a tolerance
+//! and their word sets overlap; the score is a confidence, not a verdict.
+
+use std::collections::BTreeSet;
+
+/// Matches below this confidence are not reported
+pub const MIN_CONFIDENCE: f32 = 0.5;
+
+/// Words that describe a release or its quality rather than its content
+const NOISE_WORDS: &[&str] = &[
+    "official",
+    "video",
+    "audio",
+    "lyrics",
+    "lyric",
+    "mv",
+    "hd",
+    "hq",
+    "4k",
+    "8k",
+    "1080p",
+    "720p",
+    "2160p",
+    "60fps",
+    "remastered",
+    "visualizer",
+    "full",
+];
+
+/// Normalized title: lower-case words without punctuation or noise, sorted and deduplicated.
+pub fn title_key(title: &str) -> String {
+    let lower = title.to_lowercase();
+    let words: BTreeSet<&str> = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty() && !NOISE_WORDS.contains(w))
+        .collect();
+    words.into_iter().collect::<Vec<_>>().join(" ")
+}
+
+/// How far apart two durations may be and still count as the same content:
+/// 3% of the longer one, at least 5 seconds (trimmed intros, re-muxed streams).
+fn duration_tolerance(a: u64, b: u64) -> u64 {
+    (a.max(b) * 3 / 100).max(5)
+}
+
+/// Confidence in [0, 1] that two rows hold the same content, or None when they
+/// clearly don't (durations out of tolerance, no words in common).
+pub fn score(a_key: &str, a_secs: Option<u64>, b_key: &str, b_secs: Option<u64>) -> Option<f32> {
+    let a: BTreeSet<&str> = a_key.split(' ').filter(|w| !w.is_empty()).collect();
+    let b: BTreeSet<&str> = b_key.split(' ').filter(|w| !w.is_empty()).collect();
+    let union = a.union(&b).count();
+    if union == 0 {
+        return None;
+    }
+    let title = a.intersection(&b).count() as f32 / union as f32;
+    if title == 0.0 {
+        return None;
+    }
+    let confidence = match (a_secs, b_secs) {
+        (Some(x), Some(y)) => {
+            let tolerance = duration_tolerance(x, y);
+            let diff = x.abs_diff(y);
+            if diff > tolerance {
+                return None;
+            }
+            // Anything inside the tolerance keeps at least half the duration weight
+            let duration = 1.0 - 0.5 * diff as f32 / tolerance as f32;
+            0.5 * duration + 0.5 * title
+        }
+        // Title alone is weaker evidence
+        _ => 0.6 * title,
+    };
+    Some(confidence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn title_key_drops_noise_and_order() {
+        assert_eq!(
+            title_key("Rick Astley - Never Gonna Give You Up (Official Video) [4K]"),
+            title_key("never gonna give you up | RICK ASTLEY")
+        );
+        assert_eq!(title_key("Official Video"), "");
+    }
+
+    #[test]
+    fn score_needs_close_durations() {
+        let a = title_key("Song Title - Artist");
+        let b = title_key("Artist - Song Title (Lyrics)");
+        let same = score(&a, Some(213), &b, Some(215)).unwrap();
+        assert!(same > 0.9, "{}", same);
+        assert_eq!(score(&a, Some(213), &b, Some(300)), None);
+        // Unknown duration caps the confidence
+        let untimed = score(&a, None, &b, Some(215)).unwrap();
+        assert!((MIN_CONFIDENCE..0.7).contains(&untimed));
+    }
+
+    #[test]
+    fn score_rejects_unrelated_titles() {
+        let a = title_key("Cooking pasta at home");
+        let b = title_key("Formula 1 highlights");
+        assert_eq!(score(&a, Some(600), &b, Some(600)), None);
+        assert_eq!(score("", Some(10), "", Some(10)), None);
+    }
+}
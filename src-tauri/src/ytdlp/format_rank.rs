@@ -0,0 +1,298 @@
+//! Quality-vs-size guidance for `format_recommendations`: ranks a video's
+//! formats by how much picture each byte buys and picks a recommended and a
+//! smallest acceptable option, with reasons the UI shows as tooltips.
+//!
+//! Quality is the frame height, raised a fifth for high frame rates. Video-only
+//! formats are counted merged with the best audio, as `bv+ba` downloads them.
+//! Formats without a known size can't be compared and are left out.
+
+use crate::ytdlp::types::{FormatInfo, FormatRec, SizeAccuracy};
+
+/// Highest resolution recommended; above it size grows fast for a difference
+/// most screens don't show
+const RECOMMENDED_MAX_HEIGHT: u32 = 1080;
+/// Lowest resolution still considered watchable
+const ACCEPTABLE_MIN_HEIGHT: u32 = 480;
+const HIGH_FPS_BONUS: f64 = 1.2;
+const MIB: f64 = 1024.0 * 1024.0;
+
+struct Candidate<'a> {
+    video: &'a FormatInfo,
+    audio: Option<&'a FormatInfo>,
+    height: u32,
+    size: u64,
+    accuracy: SizeAccuracy,
+    score: f64,
+}
+
+impl Candidate<'_> {
+    fn high_fps(&self) -> bool {
+        self.video.fps.is_some_and(|fps| fps > 30.0)
+    }
+
+    fn efficiency(&self) -> f64 {
+        self.score / (self.size as f64 / MIB).max(f64::EPSILON)
+    }
+
+    fn format_id(&self) -> String {
+        match self.audio {
+            Some(audio) => format!("{}+{}", self.video.format_id, audio.format_id),
+            None => self.video.format_id.clone(),
+        }
+    }
+
+    /// Another candidate looks at least as good for fewer bytes.
+    fn dominated_by<'b>(&self, all: &'b [Candidate]) -> Option<&'b Candidate<'b>> {
+        all.iter()
+            .filter(|o| o.score >= self.score && o.size < self.size)
+            .min_by_key(|o| o.size)
+    }
+}
+
+fn label(c: &Candidate) -> String {
+    if c.high_fps() {
+        format!("{}p{}", c.height, c.video.fps.unwrap_or(0.0).round())
+    } else {
+        format!("{}p", c.height)
+    }
+}
+
+/// The audio-only format `bv+ba` would pick: the largest one with a known size.
+fn best_audio(formats: &[FormatInfo]) -> Option<&FormatInfo> {
+    formats
+        .iter()
+        .filter(|f| f.has_audio && !f.has_video && f.size_bytes.is_some())
+        .max_by_key(|f| f.size_bytes)
+}
+
+fn candidates(formats: &[FormatInfo]) -> Vec<Candidate<'_>> {
+    let audio = best_audio(formats);
+    formats
+        .iter()
+        .filter(|f| f.has_video)
+        .filter_map(|video| {
+            let height = video.height.filter(|h| *h > 0)?;
+            let audio = if video.has_audio { None } else { audio };
+            if !video.has_audio && audio.is_none() {
+                return None;
+            }
+            let size = video.size_bytes? + audio.and_then(|a| a.size_bytes).unwrap_or(0);
+            let exact = video.size_accuracy == SizeAccuracy::Exact
+                && audio.is_none_or(|a| a.size_accuracy == SizeAccuracy::Exact);
+            let fps_factor = if video.fps.is_some_and(|fps| fps > 30.0) {
+                HIGH_FPS_BONUS
+            } else {
+                1.0
+            };
+            Some(Candidate {
+                video,
+                audio,
+                height,
+                size,
+                accuracy: if exact {
+                    SizeAccuracy::Exact
+                } else {
+                    SizeAccuracy::Approx
+                },
+                score: height as f64 * fps_factor,
+            })
+        })
+        .collect()
+}
+
+/// Formats best value first: undominated watchable ones by quality per byte,
+/// then undominated low resolutions, then formats something else beats.
+pub fn rank(formats: &[FormatInfo]) -> Vec<FormatRec> {
+    let all = candidates(formats);
+    let recommended = all
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.height <= RECOMMENDED_MAX_HEIGHT && c.dominated_by(&all).is_none())
+        .max_by(|(_, a), (_, b)| a.score.total_cmp(&b.score).then(b.size.cmp(&a.size)))
+        .or_else(|| all.iter().enumerate().min_by_key(|(_, c)| c.size))
+        .map(|(i, _)| i);
+    let smallest_acceptable = all
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.height >= ACCEPTABLE_MIN_HEIGHT)
+        .min_by_key(|(_, c)| c.size)
+        .map(|(i, _)| i);
+
+    let mut recs: Vec<(u8, f64, FormatRec)> = all
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let dominated = c.dominated_by(&all);
+            let mut reasons = Vec::new();
+            if recommended == Some(i) {
+                reasons.push(if c.height <= RECOMMENDED_MAX_HEIGHT {
+                    format!(
+                        "Highest quality up to {}p without a smaller equivalent",
+                        RECOMMENDED_MAX_HEIGHT
+                    )
+                } else {
+                    "Smallest download available".to_string()
+                });
+            }
+            if smallest_acceptable == Some(i) {
+                reasons.push(format!(
+                    "Smallest download at {}p or better",
+                    ACCEPTABLE_MIN_HEIGHT
+                ));
+            }
+            if let Some(better) = dominated {
+                reasons.push(format!(
+                    "{} ({}) looks as good or better and is smaller",
+                    label(better),
+                    better.format_id()
+                ));
+            }
+            if c.height > RECOMMENDED_MAX_HEIGHT {
+                reasons.push(
+                    "Above 1080p: much larger for a difference most screens won't show".to_string(),
+                );
+            } else if c.height < ACCEPTABLE_MIN_HEIGHT {
+                reasons.push(format!(
+                    "Below {}p: small, but visibly soft on most screens",
+                    ACCEPTABLE_MIN_HEIGHT
+                ));
+            }
+            if c.high_fps() {
+                reasons.push("High frame rate: smoother motion, larger file".to_string());
+            }
+            if let Some(audio) = c.audio {
+                reasons.push(format!("Merged with audio format {}", audio.format_id));
+            }
+            if c.accuracy != SizeAccuracy::Exact {
+                reasons.push("Size is an estimate".to_string());
+            }
+
+            let tier = match (dominated.is_some(), c.height >= ACCEPTABLE_MIN_HEIGHT) {
+                (false, true) => 0,
+                (false, false) => 1,
+                (true, _) => 2,
+            };
+            let rec = FormatRec {
+                format_id: c.format_id(),
+                label: label(c),
+                ext: c.video.ext.clone(),
+                height: c.height,
+                fps: c.video.fps,
+                vcodec: c.video.vcodec.clone(),
+                size_bytes: c.size,
+                size_accuracy: c.accuracy,
+                quality_per_mib: (c.efficiency() * 100.0).round() / 100.0,
+                recommended: recommended == Some(i),
+                smallest_acceptable: smallest_acceptable == Some(i),
+                reasons,
+            };
+            (tier, c.efficiency(), rec)
+        })
+        .collect();
+    recs.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.total_cmp(&a.1)));
+    recs.into_iter().map(|(_, _, rec)| rec).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn video(id: &str, height: u32, fps: f64, mib: u64) -> FormatInfo {
+        FormatInfo {
+            format_id: id.to_string(),
+            ext: "mp4".to_string(),
+            resolution: None,
+            quality_label: None,
+            filesize: Some(mib << 20),
+            vcodec: Some("avc1".to_string()),
+            acodec: Some("none".to_string()),
+            has_video: true,
+            has_audio: false,
+            language: None,
+            size_bytes: Some(mib << 20),
+            size_accuracy: SizeAccuracy::Exact,
+            height: Some(height),
+            fps: Some(fps),
+        }
+    }
+
+    fn audio(id: &str, mib: u64) -> FormatInfo {
+        FormatInfo {
+            vcodec: Some("none".to_string()),
+            acodec: Some("mp4a".to_string()),
+            has_video: false,
+            has_audio: true,
+            height: None,
+            fps: None,
+            ..video(id, 0, 0.0, mib)
+        }
+    }
+
+    fn ids(recs: &[FormatRec]) -> Vec<&str> {
+        recs.iter().map(|r| r.format_id.as_str()).collect()
+    }
+
+    #[test]
+    fn recommends_1080p_and_flags_smallest_watchable() {
+        let formats = vec![
+            audio("140", 4),
+            audio("139", 2),
+            video("160", 144, 30.0, 2),
+            video("135", 480, 30.0, 10),
+            video("136", 720, 30.0, 20),
+            video("137", 1080, 30.0, 40),
+            video("401", 2160, 30.0, 200),
+        ];
+        let recs = rank(&formats);
+        let recommended: Vec<_> = recs.iter().filter(|r| r.recommended).collect();
+        assert_eq!(recommended.len(), 1);
+        assert_eq!(recommended[0].format_id, "137+140");
+        assert_eq!(recommended[0].size_bytes, 44 << 20);
+
+        let smallest = recs.iter().find(|r| r.smallest_acceptable).unwrap();
+        assert_eq!(smallest.format_id, "135+140");
+        // Watchable first by quality per byte, then low resolutions
+        assert_eq!(
+            ids(&recs),
+            ["135+140", "136+140", "137+140", "401+140", "160+140"]
+        );
+        assert!(recs[3].reasons[0].starts_with("Above 1080p"));
+    }
+
+    #[test]
+    fn larger_formats_of_equal_quality_are_dominated() {
+        let mut vp9 = video("248", 1080, 30.0, 30);
+        vp9.vcodec = Some("vp9".to_string());
+        let mut combined = video("22", 720, 30.0, 25);
+        combined.has_audio = true;
+        combined.acodec = Some("mp4a".to_string());
+        let formats = vec![
+            audio("140", 4),
+            video("137", 1080, 30.0, 40),
+            vp9,
+            combined,
+            video("299", 1080, 60.0, 60),
+        ];
+        let recs = rank(&formats);
+        assert_eq!(ids(&recs).last(), Some(&"137+140"));
+        let dominated = recs.last().unwrap();
+        assert!(!dominated.recommended);
+        assert!(dominated.reasons[0].contains("248+140"));
+        // 60 fps scores higher, so it's the recommendation despite its size
+        assert!(recs
+            .iter()
+            .any(|r| r.format_id == "299+140" && r.recommended));
+        // Combined formats carry their own audio
+        assert!(recs.iter().any(|r| r.format_id == "22"));
+    }
+
+    #[test]
+    fn formats_without_size_or_audio_are_skipped() {
+        let mut unknown = video("137", 1080, 30.0, 0);
+        unknown.size_bytes = None;
+        unknown.size_accuracy = SizeAccuracy::Unknown;
+        assert!(rank(&[audio("140", 4), unknown]).is_empty());
+        // A video-only stream has no audio to merge with
+        assert!(rank(&[video("136", 720, 30.0, 20)]).is_empty());
+    }
+}
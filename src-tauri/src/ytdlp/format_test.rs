@@ -0,0 +1,158 @@
+//! `test_format_selection`: ask yt-dlp which format a request would download,
+//! with the same selector the download uses, without downloading anything.
+//!
+//! Each probe is a network round trip, so results are kept for a few minutes
+//! and only a couple of probes run at once.
+
+use crate::modules::types::AppError;
+use crate::ytdlp::types::{AppSettings, DownloadRequest, SelectedFormat};
+use crate::ytdlp::{download, probe, security};
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tokio::sync::Semaphore;
+
+const CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+const CACHE_CAPACITY: usize = 32;
+
+static CACHE: LazyLock<Mutex<ProbeCache>> = LazyLock::new(|| Mutex::new(ProbeCache::default()));
+static PROBES: Semaphore = Semaphore::const_new(2);
+
+/// Everything that changes what yt-dlp picks.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    url: String,
+    selector: String,
+    cookie_browser: Option<String>,
+}
+
+#[derive(Default)]
+struct ProbeCache {
+    entries: HashMap<CacheKey, (Instant, SelectedFormat)>,
+}
+
+impl ProbeCache {
+    fn get(&mut self, key: &CacheKey, now: Instant) -> Option<SelectedFormat> {
+        self.entries
+            .retain(|_, (at, _)| now.duration_since(*at) < CACHE_TTL);
+        self.entries.get(key).map(|(_, format)| format.clone())
+    }
+
+    /// Store a result, dropping the oldest entry when full.
+    fn insert(&mut self, key: CacheKey, format: SelectedFormat, now: Instant) {
+        if self.entries.len() >= CACHE_CAPACITY && !self.entries.contains_key(&key) {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (at, _))| *at)
+                .map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(key, (now, format));
+    }
+}
+
+fn cache_key(settings: &AppSettings, request: &DownloadRequest) -> CacheKey {
+    CacheKey {
+        url: request.video_url.trim().to_string(),
+        selector: download::effective_selector(settings, request),
+        cookie_browser: settings.cookie_browser.clone(),
+    }
+}
+
+pub async fn run(
+    app: &AppHandle,
+    settings: &AppSettings,
+    request: &DownloadRequest,
+) -> Result<SelectedFormat, AppError> {
+    security::sanitize_url(&request.video_url)?;
+    if request.subtitles_only {
+        return Err(AppError::Custom(
+            "Subtitle-only downloads don't select a media format".to_string(),
+        ));
+    }
+    let key = cache_key(settings, request);
+    if let Some(found) = CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&key, Instant::now())
+    {
+        return Ok(SelectedFormat {
+            cached: true,
+            ..found
+        });
+    }
+
+    let _permit = PROBES
+        .acquire()
+        .await
+        .map_err(|e| AppError::Custom(e.to_string()))?;
+    let streams = probe::selected_format(app, &key.url, &key.selector)
+        .await
+        .ok_or_else(|| {
+            AppError::Custom(format!(
+                "yt-dlp found no format matching '{}'",
+                key.selector
+            ))
+        })?;
+    let selected = SelectedFormat {
+        selector: key.selector.clone(),
+        streams,
+        cached: false,
+    };
+    CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(key, selected.clone(), Instant::now());
+    Ok(selected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ytdlp::types::SelectedStream;
+
+    fn key(url: &str) -> CacheKey {
+        CacheKey {
+            url: url.to_string(),
+            selector: "bv*+ba/b".to_string(),
+            cookie_browser: None,
+        }
+    }
+
+    fn selected(id: &str) -> SelectedFormat {
+        SelectedFormat {
+            selector: "bv*+ba/b".to_string(),
+            streams: vec![SelectedStream {
+                format_id: id.to_string(),
+                resolution: Some("1920x1080".to_string()),
+                vcodec: Some("avc1.640028".to_string()),
+                acodec: Some("mp4a.40.2".to_string()),
+            }],
+            cached: false,
+        }
+    }
+
+    #[test]
+    fn cache_expires_and_evicts_the_oldest_entry() {
+        let mut cache = ProbeCache::default();
+        let start = Instant::now();
+        cache.insert(key("a"), selected("137+140"), start);
+        assert_eq!(
+            cache.get(&key("a"), start + Duration::from_secs(60)),
+            Some(selected("137+140"))
+        );
+        assert_eq!(cache.get(&key("a"), start + CACHE_TTL), None);
+
+        for i in 0..=CACHE_CAPACITY {
+            let at = start + Duration::from_secs(i as u64);
+            cache.insert(key(&i.to_string()), selected("18"), at);
+        }
+        assert_eq!(cache.entries.len(), CACHE_CAPACITY);
+        assert_eq!(cache.get(&key("0"), start), None);
+        assert!(cache.get(&key("1"), start).is_some());
+    }
+}
@@ -0,0 +1,212 @@
+//! Whole-app backup archive (`export_full_backup`, `import_full_backup`).
+//!
+//! A zip holding `manifest.json`, `settings.json`, `binaries.json` (which
+//! yt-dlp/ffmpeg/deno were in use; informational, binaries are per machine)
+//! and `VACUUM INTO` snapshots of ytdlp.db and logs.db. Database files are
+//! copied through the zip streams in chunks, never loaded whole. Credentials
+//! stay in the OS keychain and are not part of a backup.
+
+use super::db::Database;
+use super::types::{AppSettings, FullBackupManifest};
+use crate::modules::log_db::LogDatabase;
+use crate::modules::types::AppError;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Bumped when the archive layout changes incompatibly
+pub const FORMAT_VERSION: u32 = 1;
+
+const MANIFEST: &str = "manifest.json";
+const SETTINGS: &str = "settings.json";
+const BINARIES: &str = "binaries.json";
+const MAIN_DB: &str = "ytdlp.db";
+const LOG_DB: &str = "logs.db";
+
+fn file_err(e: impl std::fmt::Display) -> AppError {
+    AppError::FileError(e.to_string())
+}
+
+/// Create a fresh scratch directory under `base` (the app cache dir) for
+/// snapshots and extracted files. Never derived from the archive path, so
+/// removing it afterwards can't touch anything of the user's.
+pub fn work_dir(base: &Path) -> Result<PathBuf, AppError> {
+    static NEXT: AtomicU32 = AtomicU32::new(0);
+    std::fs::create_dir_all(base).map_err(file_err)?;
+    let dir = base.join(format!(
+        "backup-{}-{}-{}",
+        std::process::id(),
+        chrono::Utc::now().timestamp_millis(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ));
+    // create_dir (not _all) fails rather than reuse an existing directory
+    std::fs::create_dir(&dir).map_err(file_err)?;
+    Ok(dir)
+}
+
+/// Refuse archives this version can't read.
+pub fn check_manifest(manifest: &FullBackupManifest) -> Result<(), AppError> {
+    if manifest.format_version > FORMAT_VERSION {
+        return Err(AppError::Custom(format!(
+            "This backup was made by a newer app ({}); update to import it",
+            manifest.app_version
+        )));
+    }
+    Ok(())
+}
+
+/// Everything that goes into an archive besides the databases.
+pub struct ExportParts<'a> {
+    pub manifest: &'a FullBackupManifest,
+    pub settings: &'a AppSettings,
+    pub binaries: &'a serde_json::Value,
+}
+
+/// Write the archive to `dest` (replaced only once complete), staging
+/// snapshots in `work` (see `work_dir`; the caller removes it). Blocking.
+pub fn export(
+    dest: &Path,
+    work: &Path,
+    parts: ExportParts<'_>,
+    db: &Database,
+    log_db: &LogDatabase,
+) -> Result<(), AppError> {
+    let main_snapshot = work.join(MAIN_DB);
+    let log_snapshot = work.join(LOG_DB);
+    db.snapshot_to(&main_snapshot)?;
+    log_db.snapshot_to(&log_snapshot)?;
+
+    let partial = work.join("backup.zip");
+    let mut zip = zip::ZipWriter::new(BufWriter::new(File::create(&partial).map_err(file_err)?));
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .large_file(true);
+
+    for (name, value) in [
+        (MANIFEST, serde_json::to_vec_pretty(parts.manifest)),
+        (SETTINGS, serde_json::to_vec_pretty(parts.settings)),
+        (BINARIES, serde_json::to_vec_pretty(parts.binaries)),
+    ] {
+        zip.start_file(name, options).map_err(file_err)?;
+        zip.write_all(&value.map_err(file_err)?).map_err(file_err)?;
+    }
+    for (name, path) in [(MAIN_DB, &main_snapshot), (LOG_DB, &log_snapshot)] {
+        zip.start_file(name, options).map_err(file_err)?;
+        let mut src = BufReader::new(File::open(path).map_err(file_err)?);
+        std::io::copy(&mut src, &mut zip).map_err(file_err)?;
+    }
+    zip.finish().map_err(file_err)?.flush().map_err(file_err)?;
+    // The cache dir may be on another volume than `dest`
+    if std::fs::rename(&partial, dest).is_err() {
+        let mut name = dest.file_name().unwrap_or_default().to_os_string();
+        name.push(".partial");
+        let staged = dest.with_file_name(name);
+        let moved = std::fs::copy(&partial, &staged)
+            .and_then(|_| std::fs::rename(&staged, dest))
+            .map_err(file_err);
+        if moved.is_err() {
+            let _ = std::fs::remove_file(&staged);
+        }
+        moved?;
+    }
+    Ok(())
+}
+
+/// Contents of an archive, with the databases extracted into `work`.
+pub struct Extracted {
+    pub manifest: FullBackupManifest,
+    /// Parse with `settings::from_json`; older archives lack newer keys
+    pub settings: Option<serde_json::Value>,
+    pub main_db: PathBuf,
+    /// None for archives without logs
+    pub log_db: Option<PathBuf>,
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(
+    zip: &mut zip::ZipArchive<BufReader<File>>,
+    name: &str,
+) -> Result<Option<T>, AppError> {
+    let entry = match zip.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(file_err(e)),
+    };
+    serde_json::from_reader(entry)
+        .map(Some)
+        .map_err(|e| AppError::Custom(format!("Invalid {} in backup: {}", name, e)))
+}
+
+fn extract(
+    zip: &mut zip::ZipArchive<BufReader<File>>,
+    name: &str,
+    dest: &Path,
+) -> Result<bool, AppError> {
+    let mut entry = match zip.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(false),
+        Err(e) => return Err(file_err(e)),
+    };
+    let mut out = BufWriter::new(File::create(dest).map_err(file_err)?);
+    std::io::copy(&mut entry, &mut out).map_err(file_err)?;
+    out.flush().map_err(file_err)?;
+    Ok(true)
+}
+
+/// Open the archive at `src` and extract its databases into `work`. Blocking.
+pub fn read(src: &Path, work: &Path) -> Result<Extracted, AppError> {
+    let mut zip = zip::ZipArchive::new(BufReader::new(File::open(src).map_err(file_err)?))
+        .map_err(|e| AppError::FileError(format!("Not a backup archive: {}", e)))?;
+    let manifest: FullBackupManifest = read_json(&mut zip, MANIFEST)?
+        .ok_or_else(|| AppError::FileError("Not a backup archive: no manifest".to_string()))?;
+    check_manifest(&manifest)?;
+    let settings = read_json(&mut zip, SETTINGS)?;
+
+    std::fs::create_dir_all(work).map_err(file_err)?;
+    let main_db = work.join(MAIN_DB);
+    if !extract(&mut zip, MAIN_DB, &main_db)? {
+        return Err(AppError::FileError(
+            "Backup archive has no database".to_string(),
+        ));
+    }
+    let log_path = work.join(LOG_DB);
+    let log_db = extract(&mut zip, LOG_DB, &log_path)?.then_some(log_path);
+    Ok(Extracted {
+        manifest,
+        settings,
+        main_db,
+        log_db,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(format_version: u32) -> FullBackupManifest {
+        FullBackupManifest {
+            format_version,
+            app_version: "9.9.9".to_string(),
+            schema_version: 1,
+            created_at: 0,
+            settings_redacted: false,
+        }
+    }
+
+    #[test]
+    fn newer_archive_format_is_refused() {
+        assert!(check_manifest(&manifest(FORMAT_VERSION)).is_ok());
+        assert!(check_manifest(&manifest(FORMAT_VERSION + 1)).is_err());
+    }
+
+    #[test]
+    fn work_dirs_are_fresh_and_distinct() {
+        let base = std::env::temp_dir().join(format!("full-backup-test-{}", std::process::id()));
+        let a = work_dir(&base).unwrap();
+        let b = work_dir(&base).unwrap();
+        assert_ne!(a, b);
+        assert!(a.is_dir() && b.is_dir());
+        assert!(a.starts_with(&base) && b.starts_with(&base));
+        let _ = std::fs::remove_dir_all(&base);
+    }
+}
@@ -0,0 +1,314 @@
+use super::types::{HealthCheck, HealthReport, HealthStatus};
+use super::{app_info, binary, config_check, disk, settings};
+use std::future::Future;
+use tauri::{AppHandle, Manager};
+
+/// Warn when the download volume has less than this much free space.
+const LOW_DISK_WARN_BYTES: u64 = 1024 * 1024 * 1024;
+/// Error when the download volume has less than this much free space.
+const LOW_DISK_ERROR_BYTES: u64 = 100 * 1024 * 1024;
+/// Warn when logs.db grows beyond this size.
+const LOG_DB_WARN_BYTES: u64 = 200 * 1024 * 1024;
+/// Warn when starting yt-dlp (`--version`) takes longer than this on average.
+const SLOW_SPAWN_WARN_MS: u64 = 3000;
+
+fn check(name: &str, status: HealthStatus, message: impl Into<String>) -> HealthCheck {
+    HealthCheck {
+        name: name.to_string(),
+        status,
+        message: message.into(),
+    }
+}
+
+/// Run a sub-check on its own task so a panic only fails that check.
+async fn run_isolated<F>(name: &str, fut: F) -> HealthCheck
+where
+    F: Future<Output = HealthCheck> + Send + 'static,
+{
+    match tokio::spawn(fut).await {
+        Ok(result) => result,
+        Err(e) => check(name, HealthStatus::Error, format!("check crashed: {}", e)),
+    }
+}
+
+async fn check_dependencies(app: AppHandle) -> HealthCheck {
+    let status = binary::check_full_dependencies(&app).await;
+    if !status.ytdlp.installed {
+        return check(
+            "dependencies",
+            HealthStatus::Error,
+            "yt-dlp is not installed",
+        );
+    }
+    let mut missing = Vec::new();
+    if !status.ffmpeg.installed {
+        missing.push("ffmpeg");
+    }
+    if !status.deno.installed {
+        missing.push("deno");
+    }
+    if missing.is_empty() {
+        check(
+            "dependencies",
+            HealthStatus::Ok,
+            "yt-dlp, ffmpeg and deno installed",
+        )
+    } else {
+        check(
+            "dependencies",
+            HealthStatus::Warn,
+            format!("missing: {}", missing.join(", ")),
+        )
+    }
+}
+
+async fn check_binary(app: AppHandle) -> HealthCheck {
+    let info = binary::get_binary_info(&app).await;
+    match (info.path, info.version) {
+        (Some(path), Some(version)) => check(
+            "binary",
+            HealthStatus::Ok,
+            format!("{} ({:?}) {}", path, info.source, version),
+        ),
+        (Some(path), None) => check(
+            "binary",
+            HealthStatus::Warn,
+            format!("{} found but --version failed", path),
+        ),
+        (None, _) => check("binary", HealthStatus::Error, "no yt-dlp binary resolved"),
+    }
+}
+
+async fn check_logs_db(app: AppHandle) -> HealthCheck {
+    let log_db = app.state::<crate::LogDbState>();
+    let integrity = match log_db.quick_check() {
+        Ok(result) => result,
+        Err(e) => return check("logsDb", HealthStatus::Error, e.to_string()),
+    };
+    if integrity != "ok" {
+        return check(
+            "logsDb",
+            HealthStatus::Error,
+            format!("integrity check failed: {}", integrity),
+        );
+    }
+
+    let size = crate::modules::data_dir::resolve(&app)
+        .ok()
+        .and_then(|d| std::fs::metadata(d.join("logs.db")).ok())
+        .map(|m| m.len())
+        .unwrap_or(0);
+    let size_mb = size as f64 / (1024.0 * 1024.0);
+    if size > LOG_DB_WARN_BYTES {
+        check(
+            "logsDb",
+            HealthStatus::Warn,
+            format!("{:.1} MB, consider clearing old logs", size_mb),
+        )
+    } else {
+        check("logsDb", HealthStatus::Ok, format!("{:.1} MB", size_mb))
+    }
+}
+
+async fn check_download_db(app: AppHandle) -> HealthCheck {
+    let db = app.state::<crate::DbState>();
+    match db.quick_check() {
+        Ok(result) if result == "ok" => match db.get_active_count() {
+            Ok(active) => check(
+                "downloadDb",
+                HealthStatus::Ok,
+                format!("reachable, {} downloading", active),
+            ),
+            Err(e) => check("downloadDb", HealthStatus::Error, e.to_string()),
+        },
+        Ok(result) => check(
+            "downloadDb",
+            HealthStatus::Error,
+            format!("integrity check failed: {}", result),
+        ),
+        Err(e) => check("downloadDb", HealthStatus::Error, e.to_string()),
+    }
+}
+
+async fn check_manager(app: AppHandle) -> HealthCheck {
+    let manager = app.state::<crate::DownloadManagerState>();
+    let mut message = format!(
+        "{}/{} slots in use",
+        manager.active_count(),
+        manager.max_concurrent()
+    );
+    if manager.is_throttled() {
+        message.push_str(", background throttle active");
+    }
+    // Slot counter ahead of the DB means a leaked slot that blocks the queue
+    let db = app.state::<crate::DbState>();
+    match db.get_active_count() {
+        Ok(db_active) if manager.active_count() > db_active => check(
+            "manager",
+            HealthStatus::Warn,
+            format!("{} but only {} downloading in DB", message, db_active),
+        ),
+        _ => check("manager", HealthStatus::Ok, message),
+    }
+}
+
+async fn check_disk_space(app: AppHandle) -> HealthCheck {
+    let settings = match settings::get_settings(&app) {
+        Ok(s) => s,
+        Err(e) => return check("diskSpace", HealthStatus::Error, e.to_string()),
+    };
+    let download_path = if settings.download_path.is_empty() {
+        settings::default_download_path()
+    } else {
+        settings.download_path
+    };
+
+    match disk::available_space(std::path::Path::new(&download_path)) {
+        Some(free) => {
+            let free_gb = free as f64 / (1024.0 * 1024.0 * 1024.0);
+            let message = format!("{:.1} GB free at {}", free_gb, download_path);
+            let status = if free < LOW_DISK_ERROR_BYTES {
+                HealthStatus::Error
+            } else if free < LOW_DISK_WARN_BYTES {
+                HealthStatus::Warn
+            } else {
+                HealthStatus::Ok
+            };
+            check("diskSpace", status, message)
+        }
+        None => check(
+            "diskSpace",
+            HealthStatus::Warn,
+            format!("could not determine free space for {}", download_path),
+        ),
+    }
+}
+
+async fn check_config_conflicts(app: AppHandle) -> HealthCheck {
+    let conflicts = config_check::scan_conflicts(&app).await;
+    let ignored = settings::get_settings(&app)
+        .map(|s| s.ignore_config)
+        .unwrap_or(false);
+    if conflicts.is_empty() {
+        check(
+            "configConflicts",
+            HealthStatus::Ok,
+            "no conflicting yt-dlp config",
+        )
+    } else if ignored {
+        check(
+            "configConflicts",
+            HealthStatus::Ok,
+            format!(
+                "{} conflicting option(s), ignored via --ignore-config",
+                conflicts.len()
+            ),
+        )
+    } else {
+        let options: Vec<_> = conflicts.iter().map(|c| c.option.as_str()).collect();
+        check(
+            "configConflicts",
+            HealthStatus::Warn,
+            format!("yt-dlp config sets {}", options.join(", ")),
+        )
+    }
+}
+
+/// Report the last `benchmark_ytdlp_spawn` result; the benchmark itself is too
+/// slow (and needs network) to run as part of every health check.
+async fn check_spawn_latency() -> HealthCheck {
+    let Some(bench) = binary::last_bench() else {
+        return check(
+            "spawnLatency",
+            HealthStatus::Ok,
+            "not measured yet (run the yt-dlp spawn benchmark)",
+        );
+    };
+    let Some(spawn) = bench.spawn else {
+        return check("spawnLatency", HealthStatus::Warn, "no successful runs");
+    };
+    let simulate = match (&bench.simulate, &bench.simulate_error) {
+        (Some(s), _) => format!("--simulate avg {}ms", s.avg_ms),
+        (None, Some(e)) => format!("--simulate failed: {}", e),
+        (None, None) => "--simulate not run".to_string(),
+    };
+    let message = format!(
+        "--version avg {}ms (min {}ms, max {}ms); {}",
+        spawn.avg_ms, spawn.min_ms, spawn.max_ms, simulate
+    );
+    let status = if spawn.avg_ms > SLOW_SPAWN_WARN_MS {
+        HealthStatus::Warn
+    } else {
+        HealthStatus::Ok
+    };
+    check("spawnLatency", status, message)
+}
+
+async fn check_ytdlp_compat(app: AppHandle) -> HealthCheck {
+    let info = app_info::app_info(&app).await;
+    let range = format!(
+        "tested {} – {}",
+        info.ytdlp_tested_min, info.ytdlp_tested_max
+    );
+    let Some(version) = info.ytdlp_version else {
+        return check(
+            "ytdlpCompat",
+            HealthStatus::Warn,
+            format!("yt-dlp version unknown ({})", range),
+        );
+    };
+    match app_info::tested_range_position(&version) {
+        Some(std::cmp::Ordering::Equal) => check(
+            "ytdlpCompat",
+            HealthStatus::Ok,
+            format!("yt-dlp {} ({})", version, range),
+        ),
+        Some(std::cmp::Ordering::Less) => check(
+            "ytdlpCompat",
+            HealthStatus::Warn,
+            format!("yt-dlp {} is older than {}; update it", version, range),
+        ),
+        Some(std::cmp::Ordering::Greater) => check(
+            "ytdlpCompat",
+            HealthStatus::Ok,
+            format!(
+                "yt-dlp {} is newer than this app version was {}",
+                version, range
+            ),
+        ),
+        None => check(
+            "ytdlpCompat",
+            HealthStatus::Warn,
+            format!("unrecognised yt-dlp version {} ({})", version, range),
+        ),
+    }
+}
+
+/// Run every sub-check and aggregate them. The overall status is the worst sub-check.
+pub async fn health_check(app: &AppHandle) -> HealthReport {
+    let checks = vec![
+        run_isolated("dependencies", check_dependencies(app.clone())).await,
+        run_isolated("binary", check_binary(app.clone())).await,
+        run_isolated("ytdlpCompat", check_ytdlp_compat(app.clone())).await,
+        run_isolated("logsDb", check_logs_db(app.clone())).await,
+        run_isolated("downloadDb", check_download_db(app.clone())).await,
+        run_isolated("manager", check_manager(app.clone())).await,
+        run_isolated("diskSpace", check_disk_space(app.clone())).await,
+        run_isolated("configConflicts", check_config_conflicts(app.clone())).await,
+        run_isolated("spawnLatency", check_spawn_latency()).await,
+    ];
+
+    let overall = if checks.iter().any(|c| c.status == HealthStatus::Error) {
+        HealthStatus::Error
+    } else if checks.iter().any(|c| c.status == HealthStatus::Warn) {
+        HealthStatus::Warn
+    } else {
+        HealthStatus::Ok
+    };
+
+    HealthReport {
+        overall,
+        checks,
+        generated_at: chrono::Utc::now().timestamp(),
+    }
+}
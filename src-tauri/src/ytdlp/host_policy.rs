@@ -0,0 +1,163 @@
+//! Site restrictions for controlled installs (`host_allowlist`, `host_denylist`).
+//!
+//! Entries are host names: `example.com` matches only that host, and
+//! `*.example.com` matches example.com and any subdomain. The denylist wins;
+//! a non-empty allowlist rejects every host it doesn't match.
+
+use crate::modules::types::AppError;
+
+/// Entries allowed per list
+const MAX_ENTRIES: usize = 500;
+
+/// Why a URL's host was refused.
+#[derive(Debug, PartialEq)]
+pub enum Rejection {
+    Denied { host: String, rule: String },
+    NotAllowed { host: String },
+}
+
+impl From<Rejection> for AppError {
+    fn from(r: Rejection) -> Self {
+        match r {
+            Rejection::Denied { host, rule } => AppError::InvalidUrl(format!(
+                "Downloads from {} are blocked by the host denylist ({})",
+                host, rule
+            )),
+            Rejection::NotAllowed { host } => {
+                AppError::InvalidUrl(format!("{} is not in the host allowlist", host))
+            }
+        }
+    }
+}
+
+/// Whether `host` (lower-case, no trailing dot) matches the list entry `rule`.
+fn matches(host: &str, rule: &str) -> bool {
+    match rule.strip_prefix("*.") {
+        Some(domain) => host == domain || host.ends_with(&format!(".{}", domain)),
+        None => host == rule,
+    }
+}
+
+/// Check `url` against the lists. URLs without a host pass; `sanitize_url` rejects them.
+pub fn check(url: &str, allowlist: &[String], denylist: &[String]) -> Result<(), Rejection> {
+    if allowlist.is_empty() && denylist.is_empty() {
+        return Ok(());
+    }
+    let Some(host) = reqwest::Url::parse(url.trim())
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.trim_end_matches('.').to_string()))
+    else {
+        return Ok(());
+    };
+    if let Some(rule) = denylist.iter().find(|rule| matches(&host, rule)) {
+        return Err(Rejection::Denied {
+            host,
+            rule: rule.clone(),
+        });
+    }
+    if !allowlist.is_empty() && !allowlist.iter().any(|rule| matches(&host, rule)) {
+        return Err(Rejection::NotAllowed { host });
+    }
+    Ok(())
+}
+
+/// Normalize a list from settings: entries trimmed and lower-cased, a pasted
+/// URL reduced to its host, blanks and duplicates dropped.
+pub fn sanitize_list(what: &str, entries: &[String]) -> Result<Vec<String>, AppError> {
+    let mut out: Vec<String> = Vec::new();
+    for entry in entries {
+        let entry = entry.trim().trim_end_matches('.').to_lowercase();
+        if entry.is_empty() {
+            continue;
+        }
+        let (wildcard, name) = match entry.strip_prefix("*.") {
+            Some(name) => (true, name.to_string()),
+            None => (false, entry.clone()),
+        };
+        // Let a pasted URL stand for its host
+        let name = match reqwest::Url::parse(&name) {
+            Ok(u) if u.has_host() => u.host_str().unwrap_or_default().to_string(),
+            _ => name,
+        };
+        // Round-trip through the URL parser so IDNs and IPs come out in canonical form
+        let host = (!name.contains(['/', '?', '#', '@']))
+            .then(|| reqwest::Url::parse(&format!("https://{}/", name)).ok())
+            .flatten()
+            .and_then(|u| u.host_str().map(String::from))
+            .filter(|h| {
+                h.starts_with('[')
+                    || h.chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+            })
+            .ok_or_else(|| AppError::Custom(format!("Invalid host in {}: {}", what, entry)))?;
+        let rule = if wildcard {
+            format!("*.{}", host)
+        } else {
+            host
+        };
+        if !out.contains(&rule) {
+            out.push(rule);
+        }
+    }
+    if out.len() > MAX_ENTRIES {
+        return Err(AppError::Custom(format!(
+            "The {} holds at most {} entries",
+            what, MAX_ENTRIES
+        )));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn wildcard_covers_subdomains_and_apex() {
+        let allow = list(&["*.youtube.com", "vimeo.com"]);
+        assert!(check("https://www.youtube.com/watch?v=x", &allow, &[]).is_ok());
+        assert!(check("https://youtube.com/watch?v=x", &allow, &[]).is_ok());
+        assert!(check("https://vimeo.com/1", &allow, &[]).is_ok());
+        assert_eq!(
+            check("https://player.vimeo.com/1", &allow, &[]),
+            Err(Rejection::NotAllowed {
+                host: "player.vimeo.com".into()
+            })
+        );
+        assert!(check("https://notyoutube.com/x", &allow, &[]).is_err());
+    }
+
+    #[test]
+    fn denylist_wins_over_allowlist() {
+        let allow = list(&["*.example.com"]);
+        let deny = list(&["*.bad.example.com"]);
+        assert!(check("https://ok.example.com/", &allow, &deny).is_ok());
+        assert!(matches!(
+            check("https://x.bad.example.com/", &allow, &deny),
+            Err(Rejection::Denied { .. })
+        ));
+        assert!(check("https://EXAMPLE.com./", &[], &list(&["example.com"])).is_err());
+    }
+
+    #[test]
+    fn sanitize_normalizes_entries() {
+        let out = sanitize_list(
+            "allowlist",
+            &list(&[
+                " YouTube.com ",
+                "",
+                "*.Vimeo.com",
+                "https://www.twitch.tv/x",
+                "youtube.com",
+            ]),
+        )
+        .unwrap();
+        assert_eq!(out, list(&["youtube.com", "*.vimeo.com", "www.twitch.tv"]));
+        assert!(sanitize_list("allowlist", &list(&["exa mple.com"])).is_err());
+        assert!(sanitize_list("allowlist", &list(&["foo*.com"])).is_err());
+    }
+}
@@ -0,0 +1,233 @@
+//! Extra HTTP headers sent by yt-dlp (`--user-agent`, `--referer`, `--add-header`).
+//!
+//! Settings hold the defaults and a task may override them: its User-Agent and
+//! referer replace the defaults, and its headers replace default headers of
+//! the same name. Values of auth-bearing headers are masked wherever command
+//! lines are logged.
+
+use super::types::RequestHeaders;
+use crate::modules::types::AppError;
+
+/// Extra headers allowed per task or in settings
+const MAX_HEADERS: usize = 20;
+
+const MAX_VALUE_LEN: usize = 4096;
+
+/// Header names (lower-case) whose values are secrets
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "x-api-key",
+    "x-auth-token",
+    "x-csrf-token",
+];
+
+/// True for headers that carry credentials or session tokens.
+pub fn is_sensitive(name: &str) -> bool {
+    let name = name.trim().to_ascii_lowercase();
+    SENSITIVE_HEADERS.contains(&name.as_str()) || name.contains("token") || name.contains("secret")
+}
+
+/// `Name: value` with the value masked when the header is sensitive.
+pub fn redact_header(header: &str) -> String {
+    match header.split_once(':') {
+        Some((name, _)) if is_sensitive(name) => format!("{}:***", name),
+        _ => header.to_string(),
+    }
+}
+
+/// RFC 9110 token characters
+fn is_token(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c))
+}
+
+fn check_value(what: &str, value: &str) -> Result<(), AppError> {
+    if value.len() > MAX_VALUE_LEN || value.chars().any(|c| c.is_control()) {
+        return Err(AppError::Custom(format!(
+            "Invalid {} (control characters or over {} bytes)",
+            what, MAX_VALUE_LEN
+        )));
+    }
+    Ok(())
+}
+
+/// Validate and normalize headers: values trimmed, empty entries dropped,
+/// each extra header rewritten as `Name:value`.
+pub fn sanitize(headers: &RequestHeaders) -> Result<RequestHeaders, AppError> {
+    let user_agent = headers
+        .user_agent
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty());
+    if let Some(ua) = user_agent {
+        check_value("User-Agent", ua)?;
+    }
+    let referer = headers
+        .referer
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty());
+    if let Some(referer) = referer {
+        check_value("referer", referer)?;
+        let valid =
+            reqwest::Url::parse(referer).is_ok_and(|u| matches!(u.scheme(), "http" | "https"));
+        if !valid {
+            return Err(AppError::InvalidUrl(format!(
+                "Referer must be an http(s) URL: {}",
+                referer
+            )));
+        }
+    }
+
+    let mut extra = Vec::new();
+    for header in headers
+        .headers
+        .iter()
+        .map(|h| h.trim())
+        .filter(|h| !h.is_empty())
+    {
+        let Some((name, value)) = header.split_once(':') else {
+            return Err(AppError::Custom(format!(
+                "Header must look like 'Name: value': {}",
+                redact_header(header)
+            )));
+        };
+        let (name, value) = (name.trim(), value.trim());
+        if !is_token(name) {
+            return Err(AppError::Custom(format!("Invalid header name: {:?}", name)));
+        }
+        check_value(&format!("value for header {}", name), value)?;
+        extra.push(format!("{}:{}", name, value));
+    }
+    if extra.len() > MAX_HEADERS {
+        return Err(AppError::Custom(format!(
+            "At most {} extra headers are allowed",
+            MAX_HEADERS
+        )));
+    }
+
+    Ok(RequestHeaders {
+        user_agent: user_agent.map(String::from),
+        referer: referer.map(String::from),
+        headers: extra,
+    })
+}
+
+fn header_name(header: &str) -> String {
+    header
+        .split_once(':')
+        .map_or(header, |(name, _)| name)
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Defaults from settings with the task's values on top.
+pub fn merge(defaults: &RequestHeaders, task: Option<&RequestHeaders>) -> RequestHeaders {
+    let Some(task) = task else {
+        return defaults.clone();
+    };
+    let overridden: Vec<String> = task.headers.iter().map(|h| header_name(h)).collect();
+    let mut headers: Vec<String> = defaults
+        .headers
+        .iter()
+        .filter(|h| !overridden.contains(&header_name(h)))
+        .cloned()
+        .collect();
+    headers.extend(task.headers.iter().cloned());
+    RequestHeaders {
+        user_agent: task
+            .user_agent
+            .clone()
+            .or_else(|| defaults.user_agent.clone()),
+        referer: task.referer.clone().or_else(|| defaults.referer.clone()),
+        headers,
+    }
+}
+
+/// yt-dlp arguments for the headers.
+pub fn to_args(headers: &RequestHeaders) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(ua) = &headers.user_agent {
+        args.extend(["--user-agent".to_string(), ua.clone()]);
+    }
+    if let Some(referer) = &headers.referer {
+        args.extend(["--referer".to_string(), referer.clone()]);
+    }
+    for header in &headers.headers {
+        args.extend(["--add-header".to_string(), header.clone()]);
+    }
+    args
+}
+
+/// The server refused the request outright, which on many sites means a
+/// missing referer or a rejected User-Agent.
+pub fn is_forbidden(stderr: &str) -> bool {
+    stderr.contains("HTTP Error 403") || stderr.contains("403: Forbidden")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(ua: Option<&str>, referer: Option<&str>, extra: &[&str]) -> RequestHeaders {
+        RequestHeaders {
+            user_agent: ua.map(String::from),
+            referer: referer.map(String::from),
+            headers: extra.iter().map(|h| h.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn sanitize_normalizes_and_rejects_bad_headers() {
+        let clean = sanitize(&headers(
+            Some("  Mozilla/5.0 "),
+            Some(""),
+            &["X-Forwarded-For:  1.2.3.4", "  "],
+        ))
+        .unwrap();
+        assert_eq!(
+            clean,
+            headers(Some("Mozilla/5.0"), None, &["X-Forwarded-For:1.2.3.4"])
+        );
+
+        assert!(sanitize(&headers(None, None, &["No colon"])).is_err());
+        assert!(sanitize(&headers(None, None, &["Bad Name: x"])).is_err());
+        assert!(sanitize(&headers(None, None, &["X-Test: a\r\nInjected: b"])).is_err());
+        assert!(sanitize(&headers(Some("a\nb"), None, &[])).is_err());
+        assert!(sanitize(&headers(None, Some("javascript:alert(1)"), &[])).is_err());
+        assert!(sanitize(&headers(None, Some("https://example.com/"), &[])).is_ok());
+    }
+
+    #[test]
+    fn task_values_override_defaults() {
+        let defaults = headers(
+            Some("Default UA"),
+            Some("https://a.example/"),
+            &["Accept-Language:en", "X-Keep:1"],
+        );
+        let task = headers(None, Some("https://b.example/"), &["accept-language:ko"]);
+        assert_eq!(
+            merge(&defaults, Some(&task)),
+            headers(
+                Some("Default UA"),
+                Some("https://b.example/"),
+                &["X-Keep:1", "accept-language:ko"]
+            )
+        );
+        assert_eq!(merge(&defaults, None), defaults);
+    }
+
+    #[test]
+    fn auth_headers_are_masked() {
+        assert_eq!(
+            redact_header("Authorization:Bearer abc"),
+            "Authorization:***"
+        );
+        assert_eq!(redact_header("X-Session-Token:abc"), "X-Session-Token:***");
+        assert_eq!(redact_header("Accept-Language:en"), "Accept-Language:en");
+    }
+}
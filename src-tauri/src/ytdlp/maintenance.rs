@@ -0,0 +1,193 @@
+//! Periodic log/DB cleanup that stays out of the way of downloads.
+//!
+//! Dropping logs past their age is an indexed delete and runs whenever it is
+//! due. Trimming logs to the entry cap and VACUUM rewrite large parts of the
+//! database files and hold their locks while doing it, so they wait until no
+//! download has been running for `cleanup_idle_secs`.
+
+use crate::modules::logger;
+use crate::modules::types::AppError;
+use crate::ytdlp::types::CleanupStatus;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+pub const LOG_MAX_AGE_DAYS: u32 = 30;
+pub const LOG_MAX_ENTRIES: u64 = 50_000;
+/// Longest idle period the setting accepts
+pub const MAX_IDLE_SECS: u32 = 86_400;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const LIGHT_INTERVAL_SECS: i64 = 3600;
+/// Start records older than this can't fall on the current local day
+const START_RECORD_KEEP_SECS: i64 = 2 * 86_400;
+/// Heavy cleanup repeats daily unless scheduled sooner
+const HEAVY_INTERVAL_SECS: i64 = 86_400;
+
+#[derive(Default)]
+struct State {
+    /// Heavy cleanup runs once the queue is idle at or after this time
+    heavy_due_at: i64,
+    idle_since: Option<i64>,
+    deferred_since: Option<i64>,
+    last_light_at: Option<i64>,
+    last_heavy_at: Option<i64>,
+}
+
+static STATE: LazyLock<Mutex<State>> = LazyLock::new(|| Mutex::new(State::default()));
+
+#[derive(Debug, PartialEq)]
+enum Heavy {
+    NotDue,
+    Defer,
+    Run,
+}
+
+fn decide(now: i64, due_at: i64, idle_since: Option<i64>, idle_secs: u32) -> Heavy {
+    if now < due_at {
+        return Heavy::NotDue;
+    }
+    match idle_since {
+        Some(since) if now - since >= idle_secs as i64 => Heavy::Run,
+        _ => Heavy::Defer,
+    }
+}
+
+/// Check every minute for cleanup work. The first heavy pass is due at startup.
+pub fn spawn_maintenance(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tick(&app).await;
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+/// Make heavy cleanup due now; it still waits for the queue to go idle.
+pub fn schedule_heavy(app: &AppHandle) -> CleanupStatus {
+    let now = chrono::Utc::now().timestamp();
+    STATE.lock().unwrap_or_else(|e| e.into_inner()).heavy_due_at = now;
+    logger::info_cat("app", "DB cleanup scheduled for the next idle period");
+    status(app)
+}
+
+pub fn status(app: &AppHandle) -> CleanupStatus {
+    let now = chrono::Utc::now().timestamp();
+    let state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+    CleanupStatus {
+        due: now >= state.heavy_due_at,
+        idle_secs_required: idle_secs(app),
+        active_downloads: app.state::<crate::DownloadManagerState>().active_count(),
+        deferred_since: state.deferred_since,
+        last_run_at: state.last_heavy_at,
+    }
+}
+
+fn idle_secs(app: &AppHandle) -> u32 {
+    crate::ytdlp::settings::get_settings(app)
+        .map(|s| s.cleanup_idle_secs)
+        .unwrap_or(300)
+        .min(MAX_IDLE_SECS)
+}
+
+async fn tick(app: &AppHandle) {
+    let now = chrono::Utc::now().timestamp();
+    let active = app.state::<crate::DownloadManagerState>().active_count();
+    let idle_secs = idle_secs(app);
+
+    let (light_due, heavy) = {
+        let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+        state.idle_since = match active {
+            0 => Some(state.idle_since.unwrap_or(now)),
+            _ => None,
+        };
+        let light_due = state
+            .last_light_at
+            .is_none_or(|at| now - at >= LIGHT_INTERVAL_SECS);
+        if light_due {
+            state.last_light_at = Some(now);
+        }
+        let heavy = decide(now, state.heavy_due_at, state.idle_since, idle_secs);
+        // Only active downloads count as a deferral; a queue that just went idle is simply waiting
+        if heavy == Heavy::Defer && active > 0 && state.deferred_since.is_none() {
+            state.deferred_since = Some(now);
+            logger::info_cat(
+                "app",
+                &format!(
+                    "DB cleanup deferred: {} download(s) active, runs after {}s idle",
+                    active, idle_secs
+                ),
+            );
+        }
+        (light_due, heavy)
+    };
+
+    if light_due {
+        // Only today's starts count toward the daily download limit
+        if let Err(e) = app
+            .state::<crate::DbState>()
+            .prune_download_starts(now - START_RECORD_KEEP_SECS)
+        {
+            logger::warn_cat("app", &format!("Failed to prune start records: {}", e));
+        }
+        let log_db = app.state::<crate::LogDbState>().inner().clone();
+        match tokio::task::spawn_blocking(move || log_db.delete_expired_logs(LOG_MAX_AGE_DAYS))
+            .await
+        {
+            Ok(Ok(n)) if n > 0 => logger::info_cat(
+                "app",
+                &format!("Deleted {} logs past {} days", n, LOG_MAX_AGE_DAYS),
+            ),
+            Ok(Err(e)) => logger::warn_cat("app", &format!("Failed to delete old logs: {}", e)),
+            _ => {}
+        }
+    }
+
+    if heavy != Heavy::Run {
+        return;
+    }
+    let log_db = app.state::<crate::LogDbState>().inner().clone();
+    let db = app.state::<crate::DbState>().inner().clone();
+    let result = tokio::task::spawn_blocking(move || -> Result<_, AppError> {
+        let trimmed = log_db.trim_logs(LOG_MAX_ENTRIES)?;
+        let freed = log_db.vacuum()? + db.vacuum()?;
+        Ok((trimmed, freed))
+    })
+    .await
+    .unwrap_or_else(|e| Err(AppError::Custom(e.to_string())));
+
+    let deferred_since = {
+        let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+        state.heavy_due_at = now + HEAVY_INTERVAL_SECS;
+        state.last_heavy_at = Some(now);
+        state.deferred_since.take()
+    };
+    match result {
+        Ok((trimmed, freed)) => logger::info_cat(
+            "app",
+            &format!(
+                "DB cleanup ran{}: trimmed {} logs, freed {} bytes",
+                deferred_since
+                    .map(|at| format!(" after waiting {}s", now - at))
+                    .unwrap_or_default(),
+                trimmed,
+                freed
+            ),
+        ),
+        Err(e) => logger::warn_cat("app", &format!("DB cleanup failed: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heavy_waits_for_idle_period() {
+        assert_eq!(decide(100, 200, Some(0), 60), Heavy::NotDue);
+        assert_eq!(decide(100, 50, None, 60), Heavy::Defer);
+        assert_eq!(decide(100, 50, Some(70), 60), Heavy::Defer);
+        assert_eq!(decide(100, 50, Some(40), 60), Heavy::Run);
+        assert_eq!(decide(100, 50, Some(100), 0), Heavy::Run);
+    }
+}
@@ -23,6 +23,9 @@ pub async fn fetch_video_info(app: AppHandle, url: String) -> Result<VideoInfo,
     let mut cmd = binary::command_with_path_app(&ytdlp_path, &app);
     cmd.arg("--dump-json").arg("--no-playlist");
     cmd.arg("--encoding").arg("UTF-8");
+    if settings.ignore_config {
+        cmd.arg("--ignore-config");
+    }
     if let Some(browser) = &settings.cookie_browser {
         if security::sanitize_cookie_browser(browser).is_ok() {
             cmd.arg("--cookies-from-browser").arg(browser);
@@ -76,6 +79,7 @@ pub async fn fetch_video_info(app: AppHandle, url: String) -> Result<VideoInfo,
     let thumbnail = json["thumbnail"].as_str().unwrap_or("").to_string();
 
     let duration = json["duration"].as_u64().unwrap_or(0);
+    let duration_secs = json["duration"].as_f64();
 
     let upload_date = json["upload_date"].as_str().unwrap_or("").to_string();
 
@@ -94,6 +98,11 @@ pub async fn fetch_video_info(app: AppHandle, url: String) -> Result<VideoInfo,
     let webpage_url = json["webpage_url"].as_str().unwrap_or(&url).to_string();
 
     let filesize_approx = json["filesize_approx"].as_u64();
+    let live_status = json["live_status"].as_str().map(|s| s.to_string());
+    let live_event = matches!(
+        live_status.as_deref(),
+        Some("is_live" | "is_upcoming" | "post_live")
+    ) || json["is_live"].as_bool() == Some(true);
 
     // Extract formats
     let formats = json["formats"]
@@ -106,11 +115,18 @@ pub async fn fetch_video_info(app: AppHandle, url: String) -> Result<VideoInfo,
             let resolution = format["resolution"].as_str().map(|s| s.to_string());
             let quality_label = format["format_note"].as_str().map(|s| s.to_string());
             let filesize = format["filesize"].as_u64();
+            let (size_bytes, size_accuracy) = format_size(format, duration_secs);
             let vcodec = format["vcodec"].as_str().map(|s| s.to_string());
             let acodec = format["acodec"].as_str().map(|s| s.to_string());
+            let height = format["height"].as_u64().map(|h| h as u32);
+            let fps = format["fps"].as_f64();
 
             let has_video = vcodec.as_deref() != Some("none");
             let has_audio = acodec.as_deref() != Some("none");
+            let language = format["language"]
+                .as_str()
+                .filter(|l| !l.is_empty() && *l != "none")
+                .map(|s| s.to_string());
 
             Some(FormatInfo {
                 format_id,
@@ -122,9 +138,22 @@ pub async fn fetch_video_info(app: AppHandle, url: String) -> Result<VideoInfo,
                 acodec,
                 has_video,
                 has_audio,
+                language,
+                size_bytes,
+                size_accuracy,
+                height,
+                fps,
             })
         })
+        .collect::<Vec<FormatInfo>>();
+
+    let mut audio_languages: Vec<String> = formats
+        .iter()
+        .filter(|f| f.has_audio)
+        .filter_map(|f| f.language.clone())
         .collect();
+    audio_languages.sort();
+    audio_languages.dedup();
 
     logger::info_cat(
         "metadata",
@@ -142,9 +171,48 @@ pub async fn fetch_video_info(app: AppHandle, url: String) -> Result<VideoInfo,
         channel_url,
         formats,
         filesize_approx,
+        audio_languages,
+        live_status,
+        live_event,
     })
 }
 
+/// `url`'s formats ranked by quality per byte, with a recommended and a
+/// smallest acceptable option flagged (see `format_rank`).
+#[tauri::command]
+#[specta::specta]
+pub async fn format_recommendations(
+    app: AppHandle,
+    url: String,
+) -> Result<Vec<FormatRec>, AppError> {
+    let info = fetch_video_info(app, url).await?;
+    Ok(crate::ytdlp::format_rank::rank(&info.formats))
+}
+
+/// Size of one format from the `--dump-json` entry: exact, yt-dlp's
+/// `filesize_approx`, or the total bitrate (kbit/s) over `duration` seconds.
+/// Needs no extra probe; the dump is already a `--simulate` run.
+fn format_size(format: &serde_json::Value, duration: Option<f64>) -> (Option<u64>, SizeAccuracy) {
+    if let Some(size) = format["filesize"].as_u64() {
+        return (Some(size), SizeAccuracy::Exact);
+    }
+    if let Some(size) = format["filesize_approx"].as_u64() {
+        return (Some(size), SizeAccuracy::Approx);
+    }
+    let kbps = format["tbr"].as_f64().or_else(|| {
+        let vbr = format["vbr"].as_f64();
+        let abr = format["abr"].as_f64();
+        (vbr.is_some() || abr.is_some()).then(|| vbr.unwrap_or(0.0) + abr.unwrap_or(0.0))
+    });
+    match (kbps, duration) {
+        (Some(kbps), Some(secs)) if kbps > 0.0 && secs > 0.0 => (
+            Some((kbps * 1000.0 / 8.0 * secs).round() as u64),
+            SizeAccuracy::Approx,
+        ),
+        _ => (None, SizeAccuracy::Unknown),
+    }
+}
+
 /// Fetch playlist metadata and entries using yt-dlp --flat-playlist
 #[tauri::command]
 #[specta::specta]
@@ -166,6 +234,9 @@ pub async fn fetch_playlist_info(
     let mut cmd = binary::command_with_path_app(&ytdlp_path, &app);
     cmd.arg("--flat-playlist").arg("--dump-json");
     cmd.arg("--encoding").arg("UTF-8");
+    if settings.ignore_config {
+        cmd.arg("--ignore-config");
+    }
     // Server-side pagination: yt-dlp -I START:END (1-indexed)
     // page_size >= 99999 means "Download All", so skip -I
     if page_size < 99999 {
@@ -401,3 +472,155 @@ pub async fn fetch_quick_metadata(url: String) -> Result<QuickMetadata, AppError
         thumbnail,
     })
 }
+
+/// The line where yt-dlp rejects an output template, without its "error:" prefix.
+fn template_error(stderr: &str) -> Option<String> {
+    stderr
+        .lines()
+        .map(str::trim)
+        .find(|l| {
+            let lower = l.to_lowercase();
+            lower.contains("error") && lower.contains("template")
+        })
+        .map(|l| match l.to_lowercase().rfind("error:") {
+            Some(i) => l[i + "error:".len()..].trim().to_string(),
+            None => l.to_string(),
+        })
+}
+
+/// Resolve the filename `template` (default: the filename template setting)
+/// produces for `url`, without downloading. For a playlist or channel the
+/// first entry stands in for the rest.
+#[tauri::command]
+#[specta::specta]
+pub async fn preview_output_filename(
+    app: AppHandle,
+    url: String,
+    template: Option<String>,
+) -> Result<FilenamePreview, AppError> {
+    let url = security::sanitize_url(&url)?;
+    let settings = crate::ytdlp::settings::get_settings(&app)?;
+    let template = security::sanitize_filename_template(
+        template.as_deref().unwrap_or(&settings.filename_template),
+    )?;
+    let is_playlist = matches!(
+        super::validate_url(app.clone(), url.clone(), None).map(|v| v.url_type),
+        Ok(UrlType::Playlist | UrlType::Channel)
+    );
+    let ytdlp_path = binary::resolve_ytdlp_path_with_app(&app).await?;
+
+    let mut cmd = binary::command_with_path_app(&ytdlp_path, &app);
+    cmd.args(["--simulate", "--print", "filename", "-o", &template]);
+    cmd.arg("--encoding").arg("UTF-8");
+    if is_playlist {
+        cmd.arg("--playlist-items").arg("1");
+    } else {
+        cmd.arg("--no-playlist");
+    }
+    if settings.ignore_config {
+        cmd.arg("--ignore-config");
+    }
+    if let Some(browser) = &settings.cookie_browser {
+        if security::sanitize_cookie_browser(browser).is_ok() {
+            cmd.arg("--cookies-from-browser").arg(browser);
+        }
+    }
+    cmd.arg(&url);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+
+    let output = tokio::time::timeout(METADATA_TIMEOUT, cmd.output())
+        .await
+        .map_err(|_| {
+            AppError::MetadataError(
+                "메타데이터 요청 시간이 초과되었습니다. 네트워크 연결을 확인하세요.".to_string(),
+            )
+        })?
+        .map_err(|e| AppError::MetadataError(format!("Failed to execute yt-dlp: {}", e)))?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    if let Some(reason) = template_error(&stderr) {
+        return Err(AppError::Custom(format!(
+            "Invalid filename template: {}",
+            reason
+        )));
+    }
+    if !output.status.success() {
+        logger::error_cat(
+            "metadata",
+            &format!(
+                "preview_output_filename failed: {}",
+                security::sanitize_error_message(&stderr)
+            ),
+        );
+        return Err(map_stderr_error(&stderr));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let filename = stdout
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .ok_or_else(|| AppError::MetadataError("yt-dlp printed no filename".to_string()))?
+        .to_string();
+    // Same folder choice as enqueue for a download without an explicit folder
+    let output_dir = crate::ytdlp::output_paths::main_dir(&settings, "");
+    let full_path = std::path::Path::new(output_dir)
+        .join(&filename)
+        .to_string_lossy()
+        .to_string();
+
+    Ok(FilenamePreview {
+        filename,
+        full_path,
+        playlist_example: is_playlist,
+        warnings: crate::ytdlp::template_lint::lint(&template),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template_error_strips_prefix() {
+        let stderr = "Usage: yt-dlp [OPTIONS] URL\n\nyt-dlp: error: invalid output template: unsupported format character 'q'";
+        assert_eq!(
+            template_error(stderr).as_deref(),
+            Some("invalid output template: unsupported format character 'q'")
+        );
+        assert_eq!(
+            template_error("ERROR: [youtube] x: Video unavailable"),
+            None
+        );
+    }
+
+    #[test]
+    fn format_size_prefers_exact_then_approx_then_bitrate() {
+        let exact = serde_json::json!({"filesize": 1000, "filesize_approx": 900, "tbr": 128.0});
+        assert_eq!(
+            format_size(&exact, Some(60.0)),
+            (Some(1000), SizeAccuracy::Exact)
+        );
+        let approx = serde_json::json!({"filesize_approx": 900, "tbr": 128.0});
+        assert_eq!(
+            format_size(&approx, Some(60.0)),
+            (Some(900), SizeAccuracy::Approx)
+        );
+        // 128 kbit/s for a minute
+        let bitrate = serde_json::json!({"abr": 128.0});
+        assert_eq!(
+            format_size(&bitrate, Some(60.0)),
+            (Some(960_000), SizeAccuracy::Approx)
+        );
+        assert_eq!(format_size(&bitrate, None), (None, SizeAccuracy::Unknown));
+        assert_eq!(
+            format_size(&serde_json::json!({}), Some(60.0)),
+            (None, SizeAccuracy::Unknown)
+        );
+    }
+}
@@ -2,6 +2,7 @@ use crate::modules::types::AppError;
 use crate::ytdlp::types::*;
 use once_cell::sync::Lazy;
 use regex::Regex;
+use tauri::AppHandle;
 
 // Regex patterns for YouTube URL validation
 pub(super) static VIDEO_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
@@ -26,20 +27,53 @@ static CHANNEL_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
     ]
 });
 
-/// Validate if a URL is a valid YouTube URL
+static LIST_ID_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[a-zA-Z0-9_-]+$").unwrap());
+
+/// Look up a query parameter by name (first occurrence, fragment ignored).
+pub(super) fn query_param<'a>(url: &'a str, key: &str) -> Option<&'a str> {
+    let query = url.split_once('?')?.1;
+    let query = query.split('#').next().unwrap_or(query);
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v)
+        .filter(|v| !v.is_empty())
+}
+
+/// Validate if a URL is a valid YouTube URL.
+///
+/// A watch URL that also carries `list=` is treated as a single video when
+/// `single_video` (or the `single_video_default` setting when omitted) is on,
+/// and as the playlist otherwise. Either way the list id is returned so the
+/// caller can switch for this paste.
 #[tauri::command]
 #[specta::specta]
-pub fn validate_url(url: String) -> Result<UrlValidation, AppError> {
+pub fn validate_url(
+    app: AppHandle,
+    url: String,
+    single_video: Option<bool>,
+) -> Result<UrlValidation, AppError> {
+    let prefer_single = single_video.unwrap_or_else(|| {
+        crate::ytdlp::settings::get_settings(&app)
+            .map(|s| s.single_video_default)
+            .unwrap_or(true)
+    });
+    Ok(classify_url(&url, prefer_single))
+}
+
+fn classify_url(url: &str, prefer_single: bool) -> UrlValidation {
     // Basic security validation (scheme, SSRF protection)
-    let url = match crate::ytdlp::security::sanitize_url(&url) {
+    let url = match crate::ytdlp::security::sanitize_url(url) {
         Ok(u) => u,
         Err(_) => {
-            return Ok(UrlValidation {
+            return UrlValidation {
                 valid: false,
                 url_type: UrlType::Unknown,
                 normalized_url: None,
                 video_id: None,
-            });
+                playlist_id: None,
+            };
         }
     };
     let url = url.trim();
@@ -48,13 +82,29 @@ pub fn validate_url(url: String) -> Result<UrlValidation, AppError> {
     for pattern in VIDEO_PATTERNS.iter() {
         if let Some(captures) = pattern.captures(url) {
             let video_id = captures.get(1).unwrap().as_str();
+            let list_id = query_param(url, "list").filter(|l| LIST_ID_PATTERN.is_match(l));
+            if let (Some(list_id), false) = (list_id, prefer_single) {
+                return UrlValidation {
+                    valid: true,
+                    url_type: UrlType::Playlist,
+                    normalized_url: Some(format!(
+                        "https://www.youtube.com/playlist?list={}",
+                        list_id
+                    )),
+                    video_id: Some(video_id.to_string()),
+                    playlist_id: Some(list_id.to_string()),
+                };
+            }
+            // The normalized URL drops `list=`, and yt-dlp runs with
+            // `--no-playlist`, so only this video is fetched.
             let normalized = format!("https://www.youtube.com/watch?v={}", video_id);
-            return Ok(UrlValidation {
+            return UrlValidation {
                 valid: true,
                 url_type: UrlType::Video,
                 normalized_url: Some(normalized),
                 video_id: Some(video_id.to_string()),
-            });
+                playlist_id: list_id.map(String::from),
+            };
         }
     }
 
@@ -62,23 +112,25 @@ pub fn validate_url(url: String) -> Result<UrlValidation, AppError> {
     if let Some(captures) = PLAYLIST_PATTERN.captures(url) {
         let playlist_id = captures.get(1).unwrap().as_str();
         let normalized = format!("https://www.youtube.com/playlist?list={}", playlist_id);
-        return Ok(UrlValidation {
+        return UrlValidation {
             valid: true,
             url_type: UrlType::Playlist,
             normalized_url: Some(normalized),
             video_id: None,
-        });
+            playlist_id: Some(playlist_id.to_string()),
+        };
     }
 
     // Check for channel URLs
     for pattern in CHANNEL_PATTERNS.iter() {
         if pattern.is_match(url) {
-            return Ok(UrlValidation {
+            return UrlValidation {
                 valid: true,
                 url_type: UrlType::Channel,
                 normalized_url: Some(url.to_string()),
                 video_id: None,
-            });
+                playlist_id: None,
+            };
         }
     }
 
@@ -87,18 +139,62 @@ pub fn validate_url(url: String) -> Result<UrlValidation, AppError> {
     // so here we just verify the scheme and let yt-dlp decide if it works.
     let lower = url.to_lowercase();
     if lower.starts_with("http://") || lower.starts_with("https://") {
-        return Ok(UrlValidation {
+        return UrlValidation {
             valid: true,
             url_type: UrlType::Video,
             normalized_url: Some(url.to_string()),
             video_id: None,
-        });
+            playlist_id: None,
+        };
     }
 
-    Ok(UrlValidation {
+    UrlValidation {
         valid: false,
         url_type: UrlType::Unknown,
         normalized_url: None,
         video_id: None,
-    })
+        playlist_id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_param_reads_list_id() {
+        let url = "https://www.youtube.com/watch?v=dQw4w9WgXcQ&list=PL123_abc&index=2#t=5";
+        assert_eq!(query_param(url, "list"), Some("PL123_abc"));
+        assert_eq!(query_param(url, "index"), Some("2"));
+        assert_eq!(query_param(url, "missing"), None);
+        assert_eq!(query_param("https://youtu.be/dQw4w9WgXcQ", "list"), None);
+    }
+
+    #[test]
+    fn watch_url_with_list_follows_preference() {
+        let url = "https://www.youtube.com/watch?v=dQw4w9WgXcQ&list=PL123_abc";
+
+        let single = classify_url(url, true);
+        assert!(matches!(single.url_type, UrlType::Video));
+        assert_eq!(
+            single.normalized_url.as_deref(),
+            Some("https://www.youtube.com/watch?v=dQw4w9WgXcQ")
+        );
+        assert_eq!(single.playlist_id.as_deref(), Some("PL123_abc"));
+
+        let whole = classify_url(url, false);
+        assert!(matches!(whole.url_type, UrlType::Playlist));
+        assert_eq!(
+            whole.normalized_url.as_deref(),
+            Some("https://www.youtube.com/playlist?list=PL123_abc")
+        );
+        assert_eq!(whole.video_id.as_deref(), Some("dQw4w9WgXcQ"));
+    }
+
+    #[test]
+    fn plain_watch_url_is_unaffected() {
+        let v = classify_url("https://www.youtube.com/watch?v=dQw4w9WgXcQ", false);
+        assert!(matches!(v.url_type, UrlType::Video));
+        assert!(v.playlist_id.is_none());
+    }
 }
@@ -1,14 +1,58 @@
+pub mod app_info;
+pub mod bandwidth;
+pub mod battery;
 pub mod binary;
+pub mod browser_profiles;
+pub mod cache;
+pub mod codec_pref;
 pub mod commands;
+pub mod config_check;
+pub mod container;
+pub mod cookies;
+pub mod cpu_load;
+pub mod credentials;
 pub mod db;
+pub mod default_browser;
 pub mod dep_deno;
 pub mod dep_download;
 pub mod dep_ffmpeg;
 pub mod dep_ytdlp;
+pub mod diagnostics;
+pub mod disk;
 pub mod download;
+pub mod error_kind;
+pub mod existing_file;
+pub mod ffmpeg_features;
+pub mod fingerprint;
+pub mod format_rank;
+pub mod format_test;
+pub mod full_backup;
+pub mod health;
+pub mod host_policy;
+pub mod http_headers;
+pub mod maintenance;
 pub mod metadata;
+pub mod network;
+pub mod normalize;
+pub mod options;
+pub mod orphans;
+pub mod output_paths;
+pub mod playlist_export;
+pub mod playlist_index;
+pub mod postprocess;
+pub mod power;
+pub mod presets;
+pub mod probe;
 pub mod progress;
+pub mod rename;
 pub mod security;
 pub mod settings;
+pub mod settings_check;
+pub mod task_report;
+pub mod template_lint;
+pub mod throughput;
 pub mod tray;
 pub mod types;
+pub mod url_import;
+pub mod verify;
+pub mod watch;
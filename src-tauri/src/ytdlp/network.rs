@@ -0,0 +1,111 @@
+//! Reachability of the hosts the app depends on.
+//!
+//! Dependency installs fetch from GitHub and downloads talk to the extractor
+//! sites, so a firewall or proxy blocking either makes "install" or
+//! "download" silently do nothing. `test_network` probes one host of each;
+//! with `startup_network_check` on, setup runs the same probe in the
+//! background and the layout shows a banner when something is unreachable.
+
+use super::types::{NetworkCheck, NetworkReport};
+use crate::modules::logger;
+use crate::modules::types::AppError;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+const TIMEOUT: Duration = Duration::from_secs(8);
+
+/// Name and URL of each probed host
+const TARGETS: &[(&str, &str)] = &[
+    ("github", "https://github.com"),
+    ("githubApi", "https://api.github.com"),
+    ("youtube", "https://www.youtube.com"),
+];
+
+/// Result of the last run, for a window that missed the event
+static LAST: LazyLock<Mutex<Option<NetworkReport>>> = LazyLock::new(|| Mutex::new(None));
+
+/// HTTP client for the probes: short timeout, identifies the app.
+pub fn client() -> Result<reqwest::Client, AppError> {
+    reqwest::Client::builder()
+        .timeout(TIMEOUT)
+        .user_agent("modern-ytdlp-gui")
+        .build()
+        .map_err(|e| AppError::NetworkError(format!("HTTP client error: {}", e)))
+}
+
+async fn probe(client: &reqwest::Client, name: &str, url: &str) -> NetworkCheck {
+    let started = Instant::now();
+    // Any HTTP answer, even an error status, means the host is reachable
+    let result = client.head(url).send().await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+    match result {
+        Ok(resp) => NetworkCheck {
+            name: name.to_string(),
+            url: url.to_string(),
+            reachable: true,
+            status: Some(resp.status().as_u16()),
+            latency_ms: Some(latency_ms),
+            error: None,
+        },
+        Err(e) => NetworkCheck {
+            name: name.to_string(),
+            url: url.to_string(),
+            reachable: false,
+            status: None,
+            latency_ms: None,
+            error: Some(if e.is_timeout() {
+                format!("timed out after {}s", TIMEOUT.as_secs())
+            } else {
+                e.to_string()
+            }),
+        },
+    }
+}
+
+/// Probe every host concurrently, log and remember the result, and emit `network-check`.
+pub async fn test_network(app: &AppHandle) -> Result<NetworkReport, AppError> {
+    let client = client()?;
+    let checks =
+        futures_util::future::join_all(TARGETS.iter().map(|(name, url)| probe(&client, name, url)))
+            .await;
+    let report = NetworkReport {
+        all_reachable: checks.iter().all(|c| c.reachable),
+        checks,
+        checked_at: chrono::Utc::now().timestamp(),
+    };
+
+    for check in &report.checks {
+        match &check.error {
+            None => logger::info_cat(
+                "app",
+                &format!(
+                    "Network check: {} reachable ({} ms)",
+                    check.url,
+                    check.latency_ms.unwrap_or(0)
+                ),
+            ),
+            Some(e) => logger::warn_cat(
+                "app",
+                &format!("Network check: {} unreachable: {}", check.url, e),
+            ),
+        }
+    }
+    *LAST.lock().unwrap_or_else(|e| e.into_inner()) = Some(report.clone());
+    let _ = app.emit("network-check", &report);
+    Ok(report)
+}
+
+/// Result of the last check, if one has finished.
+pub fn last_report() -> Option<NetworkReport> {
+    LAST.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Run the check in the background; never delays startup.
+pub fn spawn_startup_check(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = test_network(&app).await {
+            logger::warn_cat("app", &format!("Startup network check failed: {}", e));
+        }
+    });
+}
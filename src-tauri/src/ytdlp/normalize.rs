@@ -0,0 +1,269 @@
+use crate::ytdlp::{binary, probe, security};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// Fields `normalize_history` can backfill for one history row.
+#[derive(Debug, Default, PartialEq)]
+pub struct RowFields {
+    pub duration_secs: Option<u64>,
+    pub uploader: Option<String>,
+    pub container: Option<String>,
+    pub tags: Option<Vec<String>>,
+}
+
+impl RowFields {
+    /// Fill whatever is still missing from `other`.
+    fn or(self, other: RowFields) -> RowFields {
+        RowFields {
+            duration_secs: self.duration_secs.or(other.duration_secs),
+            uploader: self.uploader.or(other.uploader),
+            container: self.container.or(other.container),
+            tags: self.tags.or(other.tags),
+        }
+    }
+
+    fn complete(&self) -> bool {
+        self.duration_secs.is_some() && self.uploader.is_some() && self.tags.is_some()
+    }
+}
+
+/// Container of a downloaded file, from its extension.
+pub fn container_from_path(path: &str) -> Option<String> {
+    let ext = Path::new(path)
+        .extension()?
+        .to_string_lossy()
+        .to_ascii_lowercase();
+    (!ext.is_empty() && ext != "part").then_some(ext)
+}
+
+/// Read the fields out of a yt-dlp info-json document.
+fn fields_from_info(info: &serde_json::Value) -> RowFields {
+    let text = |key: &str| {
+        info.get(key)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+    };
+    RowFields {
+        duration_secs: info
+            .get("duration")
+            .and_then(|v| v.as_f64())
+            .filter(|d| d.is_finite() && *d >= 0.0)
+            .map(|d| d.round() as u64),
+        uploader: text("uploader").or_else(|| text("channel")),
+        container: text("ext"),
+        tags: info.get("tags").and_then(|v| v.as_array()).map(|tags| {
+            tags.iter()
+                .filter_map(|t| t.as_str().map(String::from))
+                .collect()
+        }),
+    }
+}
+
+/// Sidecar yt-dlp writes with `--write-info-json`: `<stem>.info.json` next to the file.
+pub(crate) fn info_json_path(file_path: &str) -> PathBuf {
+    Path::new(file_path).with_extension("info.json")
+}
+
+/// Limits for `summarize_info`: info-json files carry every format, thumbnail
+/// and caption track and easily reach megabytes.
+const SUMMARY_MAX_ITEMS: usize = 10;
+const SUMMARY_MAX_KEYS: usize = 50;
+const SUMMARY_MAX_STRING: usize = 2000;
+const SUMMARY_MAX_DEPTH: usize = 6;
+
+/// Shrink an info-json for display: long arrays keep their first items plus a
+/// count of the rest, wide objects keep their first keys, long strings are cut.
+pub fn summarize_info(value: &serde_json::Value) -> serde_json::Value {
+    summarize_at(value, 0)
+}
+
+fn summarize_at(value: &serde_json::Value, depth: usize) -> serde_json::Value {
+    use serde_json::Value;
+    match value {
+        Value::Array(items) if depth >= SUMMARY_MAX_DEPTH => {
+            Value::String(format!("[{} items]", items.len()))
+        }
+        Value::Object(map) if depth >= SUMMARY_MAX_DEPTH => {
+            Value::String(format!("{{{} keys}}", map.len()))
+        }
+        Value::Array(items) => {
+            let mut out: Vec<Value> = items
+                .iter()
+                .take(SUMMARY_MAX_ITEMS)
+                .map(|v| summarize_at(v, depth + 1))
+                .collect();
+            if items.len() > SUMMARY_MAX_ITEMS {
+                out.push(Value::String(format!(
+                    "… {} more",
+                    items.len() - SUMMARY_MAX_ITEMS
+                )));
+            }
+            Value::Array(out)
+        }
+        Value::Object(map) => {
+            let mut out: serde_json::Map<String, Value> = map
+                .iter()
+                .take(SUMMARY_MAX_KEYS)
+                .map(|(k, v)| (k.clone(), summarize_at(v, depth + 1)))
+                .collect();
+            if map.len() > SUMMARY_MAX_KEYS {
+                out.insert(
+                    "…".to_string(),
+                    Value::String(format!("{} more keys", map.len() - SUMMARY_MAX_KEYS)),
+                );
+            }
+            Value::Object(out)
+        }
+        Value::String(s) if s.chars().count() > SUMMARY_MAX_STRING => Value::String(format!(
+            "{}…",
+            s.chars().take(SUMMARY_MAX_STRING).collect::<String>()
+        )),
+        other => other.clone(),
+    }
+}
+
+/// Backfill a row from, in order: an info-json sidecar, the file itself (extension
+/// and ffprobe), and finally a yt-dlp probe of the original URL. The URL is only
+/// asked when the local sources leave something missing.
+pub async fn row_fields(app: &AppHandle, file_path: &str, video_url: &str) -> RowFields {
+    let mut fields = RowFields::default();
+
+    if let Ok(text) = tokio::fs::read_to_string(info_json_path(file_path)).await {
+        if let Ok(info) = serde_json::from_str::<serde_json::Value>(&text) {
+            fields = fields_from_info(&info);
+        }
+    }
+
+    // The file on disk wins over info-json for the container (it may have been recoded)
+    if let Some(container) =
+        container_from_path(file_path).filter(|_| Path::new(file_path).exists())
+    {
+        fields.container = Some(container);
+    }
+    if fields.duration_secs.is_none() {
+        fields.duration_secs = probe::file_duration(app, Path::new(file_path)).await;
+    }
+
+    if !fields.complete() {
+        if let Some(remote) = url_fields(app, video_url).await {
+            fields = fields.or(remote);
+        }
+    }
+    fields
+}
+
+/// Ask yt-dlp for duration, uploader and tags without downloading (network probe).
+async fn url_fields(app: &AppHandle, url: &str) -> Option<RowFields> {
+    let url = security::sanitize_url(url).ok()?;
+    let ytdlp_path = binary::resolve_ytdlp_path_with_app(app).await.ok()?;
+    let settings = crate::ytdlp::settings::get_settings(app).unwrap_or_default();
+
+    let mut cmd = binary::command_with_path_app(&ytdlp_path, app);
+    cmd.args(["--dump-json", "--skip-download", "--no-playlist"]);
+    cmd.args(["--encoding", "UTF-8"]);
+    if settings.ignore_config {
+        cmd.arg("--ignore-config");
+    }
+    if let Some(browser) = &settings.cookie_browser {
+        if security::sanitize_cookie_browser(browser).is_ok() {
+            cmd.arg("--cookies-from-browser").arg(browser);
+        }
+    }
+    cmd.arg(&url);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+
+    let output = tokio::time::timeout(Duration::from_secs(60), cmd.output())
+        .await
+        .ok()?
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let info: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    // The remote format's ext is not necessarily what ended up on disk
+    Some(RowFields {
+        container: None,
+        ..fields_from_info(&info)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_info_json_fields() {
+        let info = serde_json::json!({
+            "duration": 212.4,
+            "uploader": "  ",
+            "channel": "Some Channel",
+            "ext": "webm",
+            "tags": ["music", 3, "live"],
+        });
+        assert_eq!(
+            fields_from_info(&info),
+            RowFields {
+                duration_secs: Some(212),
+                uploader: Some("Some Channel".to_string()),
+                container: Some("webm".to_string()),
+                tags: Some(vec!["music".to_string(), "live".to_string()]),
+            }
+        );
+        assert_eq!(
+            fields_from_info(&serde_json::json!({})),
+            RowFields::default()
+        );
+    }
+
+    #[test]
+    fn summarizes_large_info_json() {
+        let formats: Vec<_> = (0..25).map(|i| serde_json::json!({ "id": i })).collect();
+        let info = serde_json::json!({
+            "title": "x",
+            "formats": formats,
+            "description": "d".repeat(5000),
+        });
+        let summary = summarize_info(&info);
+        let formats = summary["formats"].as_array().unwrap();
+        assert_eq!(formats.len(), SUMMARY_MAX_ITEMS + 1);
+        assert_eq!(formats[0]["id"], 0);
+        assert_eq!(formats.last().unwrap(), "… 15 more");
+        assert_eq!(
+            summary["description"].as_str().unwrap().chars().count(),
+            SUMMARY_MAX_STRING + 1
+        );
+        assert_eq!(summary["title"], "x");
+
+        let mut deep = serde_json::json!([1]);
+        for _ in 0..SUMMARY_MAX_DEPTH {
+            deep = serde_json::json!({ "a": deep });
+        }
+        let mut cursor = &summarize_info(&deep);
+        for _ in 0..SUMMARY_MAX_DEPTH {
+            cursor = &cursor["a"];
+        }
+        assert_eq!(cursor, "[1 items]");
+    }
+
+    #[test]
+    fn container_comes_from_extension() {
+        assert_eq!(
+            container_from_path("/videos/Title [abc].MKV"),
+            Some("mkv".to_string())
+        );
+        assert_eq!(container_from_path("/videos/file.mp4.part"), None);
+        assert_eq!(container_from_path("/videos/noext"), None);
+        assert_eq!(
+            info_json_path("/videos/a.b.mp4"),
+            PathBuf::from("/videos/a.b.info.json")
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use super::settings::default_download_path;
+use super::types::{AppSettings, EffectiveOption, OptionSource, TaskOverrides};
+
+/// Pick a value by precedence: task override > global setting (if changed from default) > default.
+fn resolve_value(
+    key: &str,
+    task: Option<String>,
+    global: Option<String>,
+    default: Option<String>,
+) -> EffectiveOption {
+    let (value, source) = if task.is_some() {
+        (task, OptionSource::Task)
+    } else if global != default {
+        (global, OptionSource::Settings)
+    } else {
+        (default, OptionSource::Default)
+    };
+    EffectiveOption {
+        key: key.to_string(),
+        value,
+        source,
+    }
+}
+
+fn bool_value(v: bool) -> Option<String> {
+    Some(v.to_string())
+}
+
+/// Merge global settings and per-task overrides into the option set a download will use.
+pub fn resolve_options(settings: &AppSettings, overrides: &TaskOverrides) -> Vec<EffectiveOption> {
+    let defaults = AppSettings::default();
+
+    vec![
+        resolve_value(
+            "quality",
+            overrides.quality.clone(),
+            Some(settings.default_quality.clone()),
+            Some(defaults.default_quality),
+        ),
+        resolve_value(
+            "outputDir",
+            overrides.output_dir.clone(),
+            Some(settings.download_path.clone()).filter(|p| !p.is_empty()),
+            Some(default_download_path()),
+        ),
+        resolve_value(
+            "filenameTemplate",
+            overrides.filename_template.clone(),
+            Some(settings.filename_template.clone()),
+            Some(defaults.filename_template),
+        ),
+        resolve_value(
+            "cookieBrowser",
+            overrides.cookie_browser.clone(),
+            settings.cookie_browser.clone(),
+            defaults.cookie_browser,
+        ),
+        resolve_value(
+            "maxHeight",
+            None,
+            settings.max_height.map(|h| h.to_string()),
+            defaults.max_height.map(|h| h.to_string()),
+        ),
+        resolve_value(
+            "maxFilesize",
+            overrides.max_filesize.clone(),
+            settings.max_filesize.clone(),
+            defaults.max_filesize,
+        ),
+        resolve_value(
+            "embedChapters",
+            None,
+            bool_value(settings.embed_chapters),
+            bool_value(defaults.embed_chapters),
+        ),
+        resolve_value(
+            "writeChapters",
+            None,
+            bool_value(settings.write_chapters),
+            bool_value(defaults.write_chapters),
+        ),
+        resolve_value(
+            "autoContainer",
+            None,
+            bool_value(settings.auto_container),
+            bool_value(defaults.auto_container),
+        ),
+        resolve_value(
+            "writeXattrs",
+            None,
+            bool_value(settings.write_xattrs),
+            bool_value(defaults.write_xattrs),
+        ),
+        resolve_value(
+            "useTempDir",
+            None,
+            bool_value(settings.use_temp_dir),
+            bool_value(defaults.use_temp_dir),
+        ),
+        resolve_value(
+            "preferSystemBinary",
+            None,
+            bool_value(settings.prefer_system_binary),
+            bool_value(defaults.prefer_system_binary),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find<'a>(options: &'a [EffectiveOption], key: &str) -> &'a EffectiveOption {
+        options.iter().find(|o| o.key == key).expect("missing key")
+    }
+
+    #[test]
+    fn defaults_when_nothing_set() {
+        let options = resolve_options(&AppSettings::default(), &TaskOverrides::default());
+        let quality = find(&options, "quality");
+        assert_eq!(quality.value.as_deref(), Some("1080p"));
+        assert_eq!(quality.source, OptionSource::Default);
+        assert_eq!(find(&options, "cookieBrowser").value, None);
+    }
+
+    #[test]
+    fn settings_override_defaults() {
+        let settings = AppSettings {
+            default_quality: "720p".to_string(),
+            cookie_browser: Some("firefox".to_string()),
+            ..AppSettings::default()
+        };
+        let options = resolve_options(&settings, &TaskOverrides::default());
+        let quality = find(&options, "quality");
+        assert_eq!(quality.value.as_deref(), Some("720p"));
+        assert_eq!(quality.source, OptionSource::Settings);
+        assert_eq!(
+            find(&options, "cookieBrowser").source,
+            OptionSource::Settings
+        );
+    }
+
+    #[test]
+    fn task_overrides_win_over_settings() {
+        let settings = AppSettings {
+            default_quality: "720p".to_string(),
+            filename_template: "%(id)s.%(ext)s".to_string(),
+            ..AppSettings::default()
+        };
+        let overrides = TaskOverrides {
+            quality: Some("480p".to_string()),
+            ..TaskOverrides::default()
+        };
+        let options = resolve_options(&settings, &overrides);
+
+        let quality = find(&options, "quality");
+        assert_eq!(quality.value.as_deref(), Some("480p"));
+        assert_eq!(quality.source, OptionSource::Task);
+
+        // Not overridden: falls through to settings
+        let template = find(&options, "filenameTemplate");
+        assert_eq!(template.value.as_deref(), Some("%(id)s.%(ext)s"));
+        assert_eq!(template.source, OptionSource::Settings);
+    }
+
+    #[test]
+    fn task_override_equal_to_default_is_still_task() {
+        let overrides = TaskOverrides {
+            quality: Some("1080p".to_string()),
+            ..TaskOverrides::default()
+        };
+        let options = resolve_options(&AppSettings::default(), &overrides);
+        assert_eq!(find(&options, "quality").source, OptionSource::Task);
+    }
+}
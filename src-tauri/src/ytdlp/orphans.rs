@@ -0,0 +1,127 @@
+use crate::modules::logger;
+use crate::modules::types::AppError;
+use crate::ytdlp::types::OrphanProcess;
+use std::path::{Path, PathBuf};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+use tauri::AppHandle;
+
+/// Parent links followed when checking whether a process is our own descendant
+const MAX_ANCESTRY_DEPTH: usize = 16;
+
+/// yt-dlp / ffmpeg / ffprobe executables that live in the app's bin dir.
+/// Anything outside it (the user's own installs) is never a candidate.
+fn is_managed_binary(exe: &Path, bin_dir: &Path) -> bool {
+    if !exe.starts_with(bin_dir) {
+        return false;
+    }
+    let stem = exe
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    stem.starts_with("yt-dlp") || stem == "ffmpeg" || stem == "ffprobe"
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Whether `pid` descends from a running instance of this app (this one or
+/// another window of the same executable), i.e. belongs to a live download.
+fn has_live_owner(sys: &System, pid: Pid, own_exe: Option<&Path>) -> bool {
+    let own = Pid::from_u32(std::process::id());
+    let mut current = sys.process(pid).and_then(|p| p.parent());
+    for _ in 0..MAX_ANCESTRY_DEPTH {
+        let Some(p) = current else { return false };
+        if p == own {
+            return true;
+        }
+        let Some(process) = sys.process(p) else {
+            return false;
+        };
+        if own_exe.is_some() && process.exe().map(canonical).as_deref() == own_exe {
+            return true;
+        }
+        current = process.parent();
+    }
+    false
+}
+
+/// Find app-managed yt-dlp/ffmpeg processes no running app instance owns,
+/// e.g. left behind by a crash. With `kill`, terminate them as well.
+pub fn find_orphans(app: &AppHandle, kill: bool) -> Result<Vec<OrphanProcess>, AppError> {
+    let bin_dir = canonical(&crate::modules::data_dir::resolve(app)?.join("bin"));
+    if !bin_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing().with_exe(UpdateKind::Always),
+    );
+    let own_exe = std::env::current_exe().ok().map(|p| canonical(&p));
+
+    let mut orphans = Vec::new();
+    for (pid, process) in sys.processes() {
+        let Some(exe) = process.exe() else { continue };
+        let exe = canonical(exe);
+        if !is_managed_binary(&exe, &bin_dir) || has_live_owner(&sys, *pid, own_exe.as_deref()) {
+            continue;
+        }
+        let killed = kill && process.kill();
+        orphans.push(OrphanProcess {
+            pid: pid.as_u32(),
+            name: process.name().to_string_lossy().to_string(),
+            exe: exe.to_string_lossy().to_string(),
+            killed,
+        });
+    }
+
+    if kill {
+        for orphan in &orphans {
+            let msg = format!(
+                "Orphaned process pid={} {} ({})",
+                orphan.pid,
+                if orphan.killed {
+                    "terminated"
+                } else {
+                    "could not be terminated"
+                },
+                orphan.exe
+            );
+            if orphan.killed {
+                logger::info_cat("app", &msg);
+            } else {
+                logger::warn_cat("app", &msg);
+            }
+        }
+    }
+    Ok(orphans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_managed_binaries_inside_bin_dir_match() {
+        let bin = Path::new("/data/app/bin");
+        assert!(is_managed_binary(Path::new("/data/app/bin/yt-dlp"), bin));
+        assert!(is_managed_binary(
+            Path::new("/data/app/bin/yt-dlp_macos"),
+            bin
+        ));
+        assert!(is_managed_binary(
+            Path::new("/data/app/bin/ffmpeg.exe"),
+            bin
+        ));
+        assert!(is_managed_binary(Path::new("/data/app/bin/ffprobe"), bin));
+        assert!(!is_managed_binary(Path::new("/usr/bin/yt-dlp"), bin));
+        assert!(!is_managed_binary(Path::new("/data/app/bin/deno"), bin));
+        assert!(!is_managed_binary(
+            Path::new("/data/app/binaries/ffmpeg"),
+            bin
+        ));
+    }
+}
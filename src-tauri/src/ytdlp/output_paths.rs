@@ -0,0 +1,104 @@
+//! Per-file-type download folders (`output_paths_by_type`).
+//!
+//! yt-dlp's `--paths` only knows output *file* types (subtitle, thumbnail, …),
+//! not video vs audio. So the main file's folder is chosen when a task is
+//! queued, from whether its format selects audio only, and subtitle/thumbnail
+//! folders are passed to yt-dlp as `--paths TYPE:DIR`.
+
+use crate::ytdlp::security;
+use crate::ytdlp::types::{AppSettings, OutputPathsByType};
+
+/// yt-dlp `--paths` types that can get their own folder
+const SIDE_TYPES: [&str; 2] = ["subtitle", "thumbnail"];
+
+/// Whether a format selector picks an audio-only stream (judged by its first choice).
+pub fn is_audio_only(format: &str) -> bool {
+    let first = format.split('/').next().unwrap_or("").trim();
+    if first.contains('+') || first.contains(',') {
+        return false;
+    }
+    let selector = first.split('[').next().unwrap_or("");
+    matches!(selector, "bestaudio" | "ba" | "worstaudio" | "wa")
+}
+
+fn configured(dir: &Option<String>) -> Option<&str> {
+    dir.as_deref()
+        .map(str::trim)
+        .filter(|d| !d.is_empty() && security::sanitize_output_path(d).is_ok())
+}
+
+/// Folder for a task's main file: the audio or video folder when set, else the download path.
+pub fn main_dir<'a>(settings: &'a AppSettings, format: &str) -> &'a str {
+    let paths = &settings.output_paths_by_type;
+    let dir = if is_audio_only(format) {
+        &paths.audio
+    } else {
+        &paths.video
+    };
+    configured(dir).unwrap_or(&settings.download_path)
+}
+
+/// `--paths` values for the side-file types that have their own folder.
+pub fn side_paths(settings: &AppSettings) -> Vec<(&'static str, String)> {
+    let paths = &settings.output_paths_by_type;
+    SIDE_TYPES
+        .iter()
+        .zip([&paths.subtitle, &paths.thumbnail])
+        .filter_map(|(kind, dir)| configured(dir).map(|d| (*kind, d.to_string())))
+        .collect()
+}
+
+/// Where each kind of file of a download ended up: the main file's folder for
+/// its own kind, side files in their folder or next to the main file.
+pub fn destinations(settings: &AppSettings, format: &str, main_dir: &str) -> OutputPathsByType {
+    let paths = &settings.output_paths_by_type;
+    let side = |dir: &Option<String>| Some(configured(dir).unwrap_or(main_dir).to_string());
+    let audio_only = is_audio_only(format);
+    OutputPathsByType {
+        video: (!audio_only).then(|| main_dir.to_string()),
+        audio: audio_only.then(|| main_dir.to_string()),
+        subtitle: side(&paths.subtitle),
+        thumbnail: side(&paths.thumbnail),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(paths: OutputPathsByType) -> AppSettings {
+        AppSettings {
+            download_path: "/downloads".to_string(),
+            output_paths_by_type: paths,
+            ..AppSettings::default()
+        }
+    }
+
+    #[test]
+    fn detects_audio_only_selectors() {
+        assert!(is_audio_only("bestaudio/best"));
+        assert!(is_audio_only("ba[language^=en]/ba"));
+        assert!(!is_audio_only("bestvideo+bestaudio/best"));
+        assert!(!is_audio_only("bv,ba"));
+        assert!(!is_audio_only("best"));
+        assert!(!is_audio_only("137"));
+    }
+
+    #[test]
+    fn unset_types_fall_back_to_download_path() {
+        let s = settings(OutputPathsByType {
+            audio: Some("/music".to_string()),
+            subtitle: Some("/subs".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(main_dir(&s, "bestaudio/best"), "/music");
+        assert_eq!(main_dir(&s, "bestvideo+bestaudio/best"), "/downloads");
+        assert_eq!(side_paths(&s), vec![("subtitle", "/subs".to_string())]);
+
+        let d = destinations(&s, "bestaudio/best", "/music");
+        assert_eq!(d.audio.as_deref(), Some("/music"));
+        assert_eq!(d.video, None);
+        assert_eq!(d.subtitle.as_deref(), Some("/subs"));
+        assert_eq!(d.thumbnail.as_deref(), Some("/music"));
+    }
+}
@@ -0,0 +1,128 @@
+//! Playlist files (M3U/M3U8/PLS) pointing at downloaded files, for
+//! `export_playlist`. Entries carry the history title and duration so players
+//! can list them without probing every file.
+
+use crate::ytdlp::types::PlaylistFormat;
+use std::path::{Component, Path, PathBuf};
+
+pub struct PlaylistEntry {
+    pub path: String,
+    pub title: String,
+    pub duration_secs: Option<u64>,
+}
+
+pub fn extension(format: PlaylistFormat) -> &'static str {
+    match format {
+        PlaylistFormat::M3u => "m3u",
+        PlaylistFormat::M3u8 => "m3u8",
+        PlaylistFormat::Pls => "pls",
+    }
+}
+
+/// `path` relative to `base` (the playlist's folder), or None when there is no
+/// relative route, e.g. another drive on Windows.
+pub fn relative_to(path: &Path, base: &Path) -> Option<PathBuf> {
+    let path: Vec<Component> = path.components().collect();
+    let base: Vec<Component> = base.components().collect();
+    // Different roots (or prefixes) can't be bridged with ".."
+    if path.first() != base.first() {
+        return None;
+    }
+    let common = path.iter().zip(&base).take_while(|(a, b)| a == b).count();
+    let mut rel = PathBuf::new();
+    for _ in common..base.len() {
+        rel.push("..");
+    }
+    for part in &path[common..] {
+        rel.push(part.as_os_str());
+    }
+    Some(rel)
+}
+
+/// Titles go on a single line in every format.
+fn one_line(title: &str) -> String {
+    title
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect()
+}
+
+pub fn render(entries: &[PlaylistEntry], format: PlaylistFormat) -> String {
+    // -1 is the "unknown length" both formats use
+    let length = |e: &PlaylistEntry| e.duration_secs.map_or(-1, |d| d as i64);
+    let mut out = String::new();
+    match format {
+        PlaylistFormat::M3u | PlaylistFormat::M3u8 => {
+            out.push_str("#EXTM3U\n");
+            for e in entries {
+                out.push_str(&format!(
+                    "#EXTINF:{},{}\n{}\n",
+                    length(e),
+                    one_line(&e.title),
+                    e.path
+                ));
+            }
+        }
+        PlaylistFormat::Pls => {
+            out.push_str("[playlist]\n");
+            for (i, e) in entries.iter().enumerate() {
+                let n = i + 1;
+                out.push_str(&format!(
+                    "File{n}={}\nTitle{n}={}\nLength{n}={}\n",
+                    e.path,
+                    one_line(&e.title),
+                    length(e)
+                ));
+            }
+            out.push_str(&format!("NumberOfEntries={}\nVersion=2\n", entries.len()));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<PlaylistEntry> {
+        vec![
+            PlaylistEntry {
+                path: "music/a.mp3".to_string(),
+                title: "First\nSong".to_string(),
+                duration_secs: Some(215),
+            },
+            PlaylistEntry {
+                path: "b.m4a".to_string(),
+                title: "Second".to_string(),
+                duration_secs: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn renders_extended_m3u_and_pls() {
+        assert_eq!(
+            render(&entries(), PlaylistFormat::M3u8),
+            "#EXTM3U\n#EXTINF:215,First Song\nmusic/a.mp3\n#EXTINF:-1,Second\nb.m4a\n"
+        );
+        assert_eq!(
+            render(&entries(), PlaylistFormat::Pls),
+            "[playlist]\nFile1=music/a.mp3\nTitle1=First Song\nLength1=215\n\
+             File2=b.m4a\nTitle2=Second\nLength2=-1\nNumberOfEntries=2\nVersion=2\n"
+        );
+    }
+
+    #[test]
+    fn relative_paths_climb_out_of_the_playlist_folder() {
+        let base = Path::new("/home/me/Playlists");
+        assert_eq!(
+            relative_to(Path::new("/home/me/Playlists/a.mp3"), base),
+            Some(PathBuf::from("a.mp3"))
+        );
+        assert_eq!(
+            relative_to(Path::new("/home/me/Music/x/a.mp3"), base),
+            Some(PathBuf::from("../Music/x/a.mp3"))
+        );
+        assert_eq!(relative_to(Path::new("a.mp3"), base), None);
+    }
+}
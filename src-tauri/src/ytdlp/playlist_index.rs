@@ -0,0 +1,65 @@
+//! Playlist positions of tasks queued from a playlist.
+//!
+//! Playlist entries are queued as single videos, so yt-dlp never sees the
+//! playlist and `%(playlist_index)s` would come out as "NA". The task keeps
+//! its position instead and the executor writes it into the output template.
+//! After cherry-picking, `renumber_playlist_tasks` closes the gaps.
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// `%(playlist_index)s`, `%(playlist_index)d` and zero-padded `%(playlist_index)03d`
+static INDEX_FIELD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"%\(playlist_index\)(0?)(\d*)([sd])").unwrap());
+
+/// Fill the playlist index fields of an output template. Left alone without an index.
+pub fn apply(template: &str, index: Option<u32>) -> String {
+    let Some(index) = index else {
+        return template.to_string();
+    };
+    INDEX_FIELD
+        .replace_all(template, |caps: &regex::Captures| {
+            let width: usize = caps[2].parse().unwrap_or(0);
+            if &caps[1] == "0" {
+                format!("{:0width$}", index, width = width)
+            } else {
+                format!("{:width$}", index, width = width)
+            }
+        })
+        .into_owned()
+}
+
+/// Sequential positions from `first` for tasks in their current order
+/// (by index, unnumbered last, then by id). Returns `(task_id, new_index)`.
+pub fn renumber(first: u32, tasks: &[(u64, Option<u32>)]) -> Vec<(u64, u32)> {
+    let mut ordered = tasks.to_vec();
+    ordered.sort_by_key(|&(id, index)| (index.is_none(), index, id));
+    ordered
+        .into_iter()
+        .zip(first..)
+        .map(|((id, _), index)| (id, index))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_index_fields() {
+        let template = "/dl/%(playlist_index)03d - %(title)s [%(playlist_index)s].%(ext)s";
+        assert_eq!(apply(template, Some(7)), "/dl/007 - %(title)s [7].%(ext)s");
+        assert_eq!(apply(template, None), template);
+        assert_eq!(apply("%(title)s.%(ext)s", Some(3)), "%(title)s.%(ext)s");
+    }
+
+    #[test]
+    fn renumber_closes_gaps_in_order() {
+        let tasks = [(10, Some(9)), (11, None), (12, Some(2)), (13, Some(5))];
+        assert_eq!(
+            renumber(3, &tasks),
+            vec![(12, 3), (13, 4), (10, 5), (11, 6)]
+        );
+        assert!(renumber(1, &[]).is_empty());
+    }
+}
@@ -0,0 +1,247 @@
+//! `rerun_postprocessing`: apply the finishing steps (tags, cover art, subtitles,
+//! chapters) to a file that is already on disk, e.g. when ffmpeg was missing at
+//! download time.
+//!
+//! yt-dlp is pointed at the existing file as a literal output path, so it reports
+//! it as already downloaded and runs only its post-processors. Should it start
+//! fetching the media anyway, it is stopped rather than left to re-download.
+
+use crate::modules::logger;
+use crate::modules::types::AppError;
+use crate::ytdlp::types::{AppSettings, HistoryItem, PostprocessSteps};
+use crate::ytdlp::{binary, download, existing_file, http_headers, security};
+use std::path::Path;
+use std::process::Stdio;
+use tauri::AppHandle;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+
+/// Check the requested steps; subtitle languages are normalized in place.
+pub fn sanitize(mut steps: PostprocessSteps) -> Result<PostprocessSteps, AppError> {
+    if !(steps.embed_metadata || steps.embed_thumbnail || steps.embed_subs || steps.embed_chapters)
+    {
+        return Err(AppError::Custom(
+            "Choose at least one post-processing step".to_string(),
+        ));
+    }
+    if steps.embed_subs {
+        steps.subtitle_langs = download::subtitles::sanitize_langs(&steps.subtitle_langs)?;
+    }
+    Ok(steps)
+}
+
+/// yt-dlp arguments that re-run `steps` on `file_path` (URL last).
+fn args(
+    item: &HistoryItem,
+    steps: &PostprocessSteps,
+    settings: &AppSettings,
+    ffmpeg_location: &str,
+) -> Vec<String> {
+    let mut args = vec![
+        // A single-file format: merged formats would make yt-dlp look for a
+        // differently named output and fetch the streams again
+        "--format".to_string(),
+        "b/bv/ba".to_string(),
+        "--output".to_string(),
+        existing_file::literal_template(&item.file_path),
+        "--no-overwrites".to_string(),
+        "--no-playlist".to_string(),
+        "--newline".to_string(),
+        "--encoding".to_string(),
+        "UTF-8".to_string(),
+        "--ffmpeg-location".to_string(),
+        ffmpeg_location.to_string(),
+    ];
+    if settings.ignore_config {
+        args.push("--ignore-config".to_string());
+    }
+    if steps.embed_metadata {
+        args.push("--embed-metadata".to_string());
+    }
+    if steps.embed_thumbnail {
+        args.push("--embed-thumbnail".to_string());
+    }
+    if steps.embed_subs {
+        args.extend([
+            "--embed-subs".to_string(),
+            "--sub-langs".to_string(),
+            steps.subtitle_langs.join(","),
+        ]);
+    }
+    if steps.embed_chapters {
+        args.push("--embed-chapters".to_string());
+    }
+    args.extend(http_headers::to_args(&http_headers::merge(
+        &settings.default_headers,
+        None,
+    )));
+    if let Some(browser) = settings
+        .cookie_browser
+        .as_ref()
+        .filter(|b| security::sanitize_cookie_browser(b).is_ok())
+    {
+        args.extend(["--cookies-from-browser".to_string(), browser.clone()]);
+    }
+    args.push(item.video_url.clone());
+    args
+}
+
+/// `[download] /path/file.mp4 has already been downloaded`
+fn reused_existing(line: &str) -> bool {
+    line.starts_with("[download] ") && line.ends_with(" has already been downloaded")
+}
+
+/// yt-dlp is about to write the media file itself (side files have other names).
+fn downloads_media(line: &str, file_path: &str) -> bool {
+    line.strip_prefix("[download] Destination: ")
+        .is_some_and(|dest| Path::new(dest.trim()) == Path::new(file_path))
+}
+
+/// Run the post-processors of `steps` over the file of `item`.
+pub async fn rerun(
+    app: &AppHandle,
+    item: &HistoryItem,
+    steps: &PostprocessSteps,
+) -> Result<(), AppError> {
+    if !Path::new(&item.file_path).is_file() {
+        return Err(AppError::FileError(format!(
+            "File not found: {}",
+            item.file_path
+        )));
+    }
+    let ffmpeg_location = binary::resolve_ffmpeg_path_with_app(app)
+        .await
+        .ok_or_else(|| {
+            AppError::BinaryNotFound("ffmpeg is required to re-run post-processing".to_string())
+        })?;
+    security::sanitize_url(&item.video_url)?;
+    let ytdlp_path = binary::resolve_ytdlp_path_with_app(app).await?;
+    let settings = crate::ytdlp::settings::get_settings(app)?;
+    let args = args(item, steps, &settings, &ffmpeg_location);
+
+    logger::info_cat(
+        "download",
+        &format!(
+            "[history:{}] re-running post-processing: {} {:?}",
+            item.id,
+            ytdlp_path,
+            security::redact_args(&args)
+        ),
+    );
+
+    let mut cmd = binary::command_with_path_app(&ytdlp_path, app);
+    cmd.args(&args);
+    cmd.stdin(Stdio::null());
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| AppError::Custom(format!("Failed to spawn yt-dlp: {}", e)))?;
+
+    let stderr = child.stderr.take();
+    let stderr_handle = tokio::spawn(async move {
+        let mut buf = Vec::new();
+        if let Some(mut stderr) = stderr {
+            let _ = stderr.read_to_end(&mut buf).await;
+        }
+        String::from_utf8_lossy(&buf).to_string()
+    });
+
+    let mut reused = false;
+    if let Some(stdout) = child.stdout.take() {
+        let mut reader = BufReader::new(stdout);
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            match reader.read_until(b'\n', &mut buf).await {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
+            let line = String::from_utf8_lossy(&buf).trim_end().to_string();
+            if reused_existing(&line) {
+                reused = true;
+            } else if downloads_media(&line, &item.file_path) {
+                let _ = child.kill().await;
+                return Err(AppError::Custom(
+                    "yt-dlp started downloading the video again, so it was stopped. \
+                     Use a re-download instead."
+                        .to_string(),
+                ));
+            }
+        }
+    }
+
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| AppError::Custom(format!("yt-dlp did not finish: {}", e)))?;
+    let stderr_output = stderr_handle.await.unwrap_or_default();
+    if !status.success() {
+        let last_line = stderr_output
+            .lines()
+            .rev()
+            .find(|l| !l.trim().is_empty())
+            .unwrap_or("Post-processing failed");
+        return Err(AppError::Custom(security::sanitize_error_message(
+            last_line,
+        )));
+    }
+    if !reused {
+        return Err(AppError::Custom(
+            "yt-dlp did not pick up the existing file; nothing was changed".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn steps() -> PostprocessSteps {
+        PostprocessSteps {
+            embed_metadata: false,
+            embed_thumbnail: false,
+            embed_subs: false,
+            embed_chapters: false,
+            subtitle_langs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn sanitize_needs_a_step_and_subtitle_languages() {
+        assert!(sanitize(steps()).is_err());
+        assert!(sanitize(PostprocessSteps {
+            embed_subs: true,
+            ..steps()
+        })
+        .is_err());
+        let ok = sanitize(PostprocessSteps {
+            embed_subs: true,
+            subtitle_langs: vec![" en ".to_string()],
+            ..steps()
+        })
+        .unwrap();
+        assert_eq!(ok.subtitle_langs, vec!["en".to_string()]);
+    }
+
+    #[test]
+    fn recognizes_reuse_and_media_download() {
+        assert!(reused_existing(
+            "[download] /dl/Clip 100%.mp4 has already been downloaded"
+        ));
+        assert!(!reused_existing("[download] Destination: /dl/Clip.mp4"));
+        assert!(downloads_media(
+            "[download] Destination: /dl/Clip.mp4",
+            "/dl/Clip.mp4"
+        ));
+        assert!(!downloads_media(
+            "[download] Destination: /dl/Clip.en.vtt",
+            "/dl/Clip.mp4"
+        ));
+    }
+}
@@ -0,0 +1,108 @@
+//! Best-effort handling of system sleep.
+//!
+//! There is no portable "about to sleep" notification without per-platform
+//! bindings, so downloads are not paused ahead of a sleep. Instead a watcher
+//! notices the wake-up: it ticks on a short timer and, when far more wall-clock
+//! time passed between ticks than the timer allows, the process was suspended.
+//! Downloads that were running across the gap are stopped and re-queued so
+//! yt-dlp restarts them with `--continue` on a fresh connection.
+//!
+//! A large manual clock change looks the same as a sleep and triggers the same
+//! (harmless) recovery.
+
+use crate::modules::logger;
+use crate::ytdlp::download::DownloadManager;
+use crate::ytdlp::types::PowerStatus;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Manager};
+
+const TICK: Duration = Duration::from_secs(5);
+
+/// Wall-clock time beyond `TICK` that counts as a sleep
+const SLEEP_THRESHOLD: Duration = Duration::from_secs(60);
+
+static STATUS: LazyLock<Mutex<PowerStatus>> = LazyLock::new(|| {
+    Mutex::new(PowerStatus {
+        wake_detection: "clock-gap".to_string(),
+        last_wake_at: None,
+        last_sleep_secs: None,
+        requeued_on_last_wake: 0,
+    })
+});
+
+/// How long the system was suspended, if the time between two ticks says so.
+fn sleep_gap(wall_elapsed: Duration) -> Option<Duration> {
+    let gap = wall_elapsed.checked_sub(TICK)?;
+    (gap >= SLEEP_THRESHOLD).then_some(gap)
+}
+
+pub fn status() -> PowerStatus {
+    STATUS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Start the background wake watcher. Call once from setup.
+pub fn spawn_wake_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut last = SystemTime::now();
+        loop {
+            tokio::time::sleep(TICK).await;
+            let now = SystemTime::now();
+            let elapsed = now.duration_since(last).unwrap_or_default();
+            last = now;
+            if let Some(gap) = sleep_gap(elapsed) {
+                on_wake(&app, gap);
+            }
+        }
+    });
+}
+
+fn on_wake(app: &AppHandle, gap: Duration) {
+    let requeue = crate::ytdlp::settings::get_settings(app)
+        .map(|s| s.requeue_after_sleep)
+        .unwrap_or(true);
+
+    let manager = app.state::<Arc<DownloadManager>>();
+    let running = manager.running_task_ids();
+    if requeue {
+        for &task_id in &running {
+            manager.send_requeue(task_id);
+        }
+    }
+    let requeued = if requeue { running.len() as u32 } else { 0 };
+
+    logger::info_cat(
+        "app",
+        &format!(
+            "System resumed after ~{}s; {} running download(s), {} re-queued",
+            gap.as_secs(),
+            running.len(),
+            requeued
+        ),
+    );
+
+    let mut status = STATUS.lock().unwrap_or_else(|e| e.into_inner());
+    status.last_wake_at = Some(chrono::Utc::now().timestamp());
+    status.last_sleep_secs = Some(gap.as_secs());
+    status.requeued_on_last_wake = requeued;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sleep_gap_ignores_normal_and_late_ticks() {
+        assert_eq!(sleep_gap(TICK), None);
+        assert_eq!(sleep_gap(TICK + Duration::from_secs(20)), None);
+        assert_eq!(sleep_gap(Duration::from_secs(1)), None);
+    }
+
+    #[test]
+    fn sleep_gap_reports_suspended_time() {
+        assert_eq!(
+            sleep_gap(TICK + Duration::from_secs(600)),
+            Some(Duration::from_secs(600))
+        );
+    }
+}
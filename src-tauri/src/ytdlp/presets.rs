@@ -0,0 +1,160 @@
+//! Named bundles of download options (`save_preset`, `DownloadRequest.preset`).
+//!
+//! A preset only fills what the request leaves open: empty format/quality,
+//! unset optional fields and flags that are off. Anything the caller set
+//! explicitly wins. Built-in presets live here; user presets in the DB.
+
+use crate::modules::types::AppError;
+use crate::ytdlp::types::{DownloadPreset, DownloadRequest, PresetOptions};
+
+pub const MAX_NAME_LEN: usize = 64;
+
+pub fn builtin_presets() -> Vec<DownloadPreset> {
+    vec![
+        DownloadPreset {
+            name: "podcast".to_string(),
+            options: PresetOptions {
+                format_id: Some("bestaudio/best".to_string()),
+                quality_label: Some("audio".to_string()),
+                embed_metadata: Some(true),
+                embed_thumbnail: Some(true),
+                ..Default::default()
+            },
+            builtin: true,
+        },
+        DownloadPreset {
+            name: "archive".to_string(),
+            options: PresetOptions {
+                format_id: Some("bestvideo+bestaudio/best".to_string()),
+                quality_label: Some("best".to_string()),
+                embed_metadata: Some(true),
+                embed_thumbnail: Some(true),
+                ..Default::default()
+            },
+            builtin: true,
+        },
+        DownloadPreset {
+            name: "mobile-720p".to_string(),
+            options: PresetOptions {
+                format_id: Some(
+                    "bestvideo[height<=720][ext=mp4]+bestaudio[ext=m4a]/best[height<=720][ext=mp4]/best[height<=720]"
+                        .to_string(),
+                ),
+                quality_label: Some("720p".to_string()),
+                ..Default::default()
+            },
+            builtin: true,
+        },
+    ]
+}
+
+pub fn builtin(name: &str) -> Option<DownloadPreset> {
+    builtin_presets().into_iter().find(|p| p.name == name)
+}
+
+pub fn sanitize_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN || name.chars().any(char::is_control)
+    {
+        return Err(AppError::Custom(format!(
+            "Preset name must be 1-{} printable characters",
+            MAX_NAME_LEN
+        )));
+    }
+    Ok(name.to_string())
+}
+
+/// Fill the fields `request` leaves open from `options`.
+pub fn apply(mut request: DownloadRequest, options: &PresetOptions) -> DownloadRequest {
+    let fill = |field: &mut String, value: &Option<String>| {
+        if field.trim().is_empty() {
+            if let Some(v) = value {
+                *field = v.clone();
+            }
+        }
+    };
+    fill(&mut request.format_id, &options.format_id);
+    fill(&mut request.quality_label, &options.quality_label);
+    request.output_dir = request.output_dir.or_else(|| options.output_dir.clone());
+    request.max_filesize = request
+        .max_filesize
+        .or_else(|| options.max_filesize.clone());
+    request.audio_lang = request.audio_lang.or_else(|| options.audio_lang.clone());
+    request.keep_separate_streams |= options.keep_separate_streams.unwrap_or(false);
+    request.embed_metadata |= options.embed_metadata.unwrap_or(false);
+    request.embed_thumbnail |= options.embed_thumbnail.unwrap_or(false);
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> DownloadRequest {
+        DownloadRequest {
+            video_url: "https://www.youtube.com/watch?v=jNQXAC9IVRw".to_string(),
+            video_id: "jNQXAC9IVRw".to_string(),
+            title: "t".to_string(),
+            format_id: String::new(),
+            quality_label: String::new(),
+            output_dir: None,
+            cookie_browser: None,
+            playlist_id: None,
+            playlist_index: None,
+            credentials: None,
+            keep_separate_streams: false,
+            max_filesize: None,
+            audio_lang: None,
+            embed_metadata: false,
+            embed_thumbnail: false,
+            preset: Some("podcast".to_string()),
+            live_from_start: false,
+            subtitles_only: false,
+            subtitle_langs: Vec::new(),
+            convert_subs: None,
+            tags: Vec::new(),
+            headers: None,
+            max_retries: None,
+            retry_backoff_secs: None,
+            on_existing_file: None,
+        }
+    }
+
+    #[test]
+    fn preset_fills_open_fields() {
+        let podcast = builtin("podcast").unwrap();
+        let r = apply(request(), &podcast.options);
+        assert_eq!(r.format_id, "bestaudio/best");
+        assert_eq!(r.quality_label, "audio");
+        assert!(r.embed_metadata && r.embed_thumbnail);
+    }
+
+    #[test]
+    fn explicit_fields_win() {
+        let options = PresetOptions {
+            format_id: Some("bestaudio/best".to_string()),
+            max_filesize: Some("1G".to_string()),
+            audio_lang: Some("en".to_string()),
+            ..Default::default()
+        };
+        let r = apply(
+            DownloadRequest {
+                format_id: "137+140".to_string(),
+                audio_lang: Some("ja".to_string()),
+                ..request()
+            },
+            &options,
+        );
+        assert_eq!(r.format_id, "137+140");
+        assert_eq!(r.audio_lang.as_deref(), Some("ja"));
+        assert_eq!(r.max_filesize.as_deref(), Some("1G"));
+    }
+
+    #[test]
+    fn names_are_trimmed_and_bounded() {
+        assert_eq!(sanitize_name("  talks ").unwrap(), "talks");
+        assert!(sanitize_name("   ").is_err());
+        assert!(sanitize_name(&"x".repeat(MAX_NAME_LEN + 1)).is_err());
+        assert!(sanitize_name("a\nb").is_err());
+    }
+}
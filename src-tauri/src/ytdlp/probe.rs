@@ -0,0 +1,292 @@
+use crate::ytdlp::types::SelectedStream;
+use crate::ytdlp::{binary, security};
+use std::path::Path;
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// Parse a duration in (possibly fractional) seconds, as printed by
+/// ffprobe or `yt-dlp --print duration`, rounded to whole seconds.
+pub fn parse_seconds(output: &str) -> Option<u64> {
+    let secs: f64 = output.lines().next()?.trim().parse().ok()?;
+    (secs.is_finite() && secs >= 0.0).then(|| secs.round() as u64)
+}
+
+/// Read the duration of a media file with ffprobe (next to the resolved ffmpeg).
+pub async fn file_duration(app: &AppHandle, path: &Path) -> Option<u64> {
+    let output = ffprobe(app, path, &["-show_entries", "format=duration"]).await?;
+    parse_seconds(&output)
+}
+
+/// Number of chapters in a media file, via ffprobe.
+pub async fn file_chapter_count(app: &AppHandle, path: &Path) -> Option<u32> {
+    let output = ffprobe(app, path, &["-show_entries", "chapter=id"]).await?;
+    Some(output.lines().filter(|l| !l.trim().is_empty()).count() as u32)
+}
+
+/// Outcome of checking a finished file with ffprobe.
+pub enum MediaProbe {
+    /// ffprobe could not be run (not installed or timed out)
+    Unavailable,
+    /// ffprobe could not read the file; holds its error output
+    Invalid(String),
+    /// A readable media container, with its duration when it reports one
+    Valid(Option<u64>),
+}
+
+/// Check that a file is a readable media container and read its duration.
+pub async fn probe_media(app: &AppHandle, path: &Path) -> MediaProbe {
+    if !path.exists() {
+        return MediaProbe::Invalid("File not found".to_string());
+    }
+    let Some(output) = ffprobe_output(app, path, &["-show_entries", "format=duration"]).await
+    else {
+        return MediaProbe::Unavailable;
+    };
+    // -v error: anything on stderr is a real decode/container error
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if !output.status.success() || !stderr.is_empty() {
+        return MediaProbe::Invalid(if stderr.is_empty() {
+            format!("ffprobe exited with {}", output.status)
+        } else {
+            stderr
+        });
+    }
+    MediaProbe::Valid(parse_seconds(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Run ffprobe with `entries` and return its bare (key-less) stdout.
+async fn ffprobe(app: &AppHandle, path: &Path, entries: &[&str]) -> Option<String> {
+    if !path.exists() {
+        return None;
+    }
+    let output = ffprobe_output(app, path, entries).await?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Spawn ffprobe; None when it can't be run or doesn't finish in time.
+async fn ffprobe_output(
+    app: &AppHandle,
+    path: &Path,
+    entries: &[&str],
+) -> Option<std::process::Output> {
+    let bin_name = if cfg!(target_os = "windows") {
+        "ffprobe.exe"
+    } else {
+        "ffprobe"
+    };
+    let program = match binary::resolve_ffmpeg_path_with_app(app).await {
+        Some(dir) => Path::new(&dir).join(bin_name).to_string_lossy().to_string(),
+        None => bin_name.to_string(),
+    };
+
+    let mut cmd = binary::command_with_path_app(&program, app);
+    cmd.args(["-v", "error"])
+        .args(entries)
+        .args(["-of", "default=noprint_wrappers=1:nokey=1"])
+        .arg(path);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+
+    tokio::time::timeout(Duration::from_secs(10), cmd.output())
+        .await
+        .ok()?
+        .ok()
+}
+
+/// Ask yt-dlp for a video's duration without downloading it (network probe).
+pub async fn url_duration(app: &AppHandle, url: &str) -> Option<u64> {
+    let url = security::sanitize_url(url).ok()?;
+    let ytdlp_path = binary::resolve_ytdlp_path_with_app(app).await.ok()?;
+    let settings = crate::ytdlp::settings::get_settings(app).unwrap_or_default();
+
+    let mut cmd = binary::command_with_path_app(&ytdlp_path, app);
+    cmd.args(["--print", "duration", "--skip-download", "--no-playlist"]);
+    if settings.ignore_config {
+        cmd.arg("--ignore-config");
+    }
+    cmd.arg(&url);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+
+    let output = tokio::time::timeout(Duration::from_secs(30), cmd.output())
+        .await
+        .ok()?
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_seconds(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Title and duration of `url` as yt-dlp reports them (network probe).
+pub async fn url_title_and_duration(app: &AppHandle, url: &str) -> Option<(String, Option<u64>)> {
+    let stdout = print_for_format(app, url, "bv*+ba/b", &["duration", "title"]).await?;
+    let mut lines = stdout.lines();
+    let duration = lines.next().and_then(parse_seconds);
+    let title = lines.next()?.trim();
+    (!title.is_empty() && title != "NA").then(|| (title.to_string(), duration))
+}
+
+/// Video and audio codec of what `format` selects for `url`, as yt-dlp would pick it
+/// (network probe). Either side is None/`"none"` when that stream is absent.
+pub async fn selected_codecs(
+    app: &AppHandle,
+    url: &str,
+    format: &str,
+) -> Option<(Option<String>, Option<String>)> {
+    let stdout = print_for_format(app, url, format, &["vcodec", "acodec"]).await?;
+    let mut lines = stdout.lines().map(|l| {
+        let l = l.trim();
+        (!l.is_empty() && l != "NA").then(|| l.to_string())
+    });
+    Some((lines.next().flatten(), lines.next().flatten()))
+}
+
+/// Format id, resolution, video and audio codec of every format `format` selects
+/// for `url` (network probe). None when yt-dlp finds no matching format.
+pub async fn selected_format(
+    app: &AppHandle,
+    url: &str,
+    format: &str,
+) -> Option<Vec<SelectedStream>> {
+    let stdout = print_for_format(app, url, format, &SELECTED_FIELDS).await?;
+    let streams = parse_selected_streams(&stdout);
+    (!streams.is_empty()).then_some(streams)
+}
+
+const SELECTED_FIELDS: [&str; 4] = ["format_id", "resolution", "vcodec", "acodec"];
+
+/// Split `--print` output of `SELECTED_FIELDS` into one entry per downloaded
+/// format; yt-dlp prints the whole set again for each one.
+fn parse_selected_streams(stdout: &str) -> Vec<SelectedStream> {
+    let lines: Vec<Option<String>> = stdout
+        .lines()
+        .map(|l| {
+            let l = l.trim();
+            (!l.is_empty() && l != "NA").then(|| l.to_string())
+        })
+        .collect();
+    lines
+        .chunks_exact(SELECTED_FIELDS.len())
+        .filter_map(|fields| {
+            Some(SelectedStream {
+                format_id: fields[0].clone()?,
+                resolution: fields[1].clone(),
+                vcodec: fields[2].clone(),
+                acodec: fields[3].clone(),
+            })
+        })
+        .collect()
+}
+
+/// Expected size in bytes of what `format` selects for `url` (exact when the site
+/// reports it, else yt-dlp's estimate). None when neither is known.
+pub async fn selected_filesize(app: &AppHandle, url: &str, format: &str) -> Option<u64> {
+    let stdout = print_for_format(app, url, format, &["%(filesize,filesize_approx)s"]).await?;
+    stdout.lines().next()?.trim().parse().ok()
+}
+
+/// Audio language of what `format` selects for `url` (the audio stream of a merged
+/// format). None when the site doesn't tag languages.
+pub async fn selected_language(app: &AppHandle, url: &str, format: &str) -> Option<String> {
+    let stdout = print_for_format(
+        app,
+        url,
+        format,
+        &["%(requested_formats.1.language,language)s"],
+    )
+    .await?;
+    let lang = stdout.lines().next()?.trim();
+    (!lang.is_empty() && lang != "NA" && lang != "none").then(|| lang.to_string())
+}
+
+/// Run `yt-dlp --format <format> --print <t>... --skip-download` and return stdout,
+/// one line per template.
+async fn print_for_format(
+    app: &AppHandle,
+    url: &str,
+    format: &str,
+    templates: &[&str],
+) -> Option<String> {
+    let url = security::sanitize_url(url).ok()?;
+    let ytdlp_path = binary::resolve_ytdlp_path_with_app(app).await.ok()?;
+    let settings = crate::ytdlp::settings::get_settings(app).unwrap_or_default();
+
+    let mut cmd = binary::command_with_path_app(&ytdlp_path, app);
+    cmd.args(["--format", format]);
+    for template in templates {
+        cmd.args(["--print", template]);
+    }
+    cmd.args(["--skip-download", "--no-playlist"]);
+    if settings.ignore_config {
+        cmd.arg("--ignore-config");
+    }
+    if let Some(browser) = &settings.cookie_browser {
+        if security::sanitize_cookie_browser(browser).is_ok() {
+            cmd.arg("--cookies-from-browser").arg(browser);
+        }
+    }
+    cmd.arg(&url);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+
+    let output = tokio::time::timeout(Duration::from_secs(60), cmd.output())
+        .await
+        .ok()?
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_seconds_rounds_fractional_output() {
+        assert_eq!(parse_seconds("754.320000\n"), Some(754));
+        assert_eq!(parse_seconds("12.5"), Some(13));
+        assert_eq!(parse_seconds("61"), Some(61));
+    }
+
+    #[test]
+    fn parse_seconds_rejects_missing_values() {
+        assert_eq!(parse_seconds("N/A"), None);
+        assert_eq!(parse_seconds("NA\n"), None);
+        assert_eq!(parse_seconds(""), None);
+        assert_eq!(parse_seconds("-3"), None);
+    }
+
+    #[test]
+    fn selected_streams_split_per_format() {
+        let streams = parse_selected_streams(
+            "137\n1920x1080\navc1.640028\nnone\n140\naudio only\nnone\nmp4a.40.2\n",
+        );
+        assert_eq!(streams.len(), 2);
+        assert_eq!(streams[0].format_id, "137");
+        assert_eq!(streams[0].acodec.as_deref(), Some("none"));
+        assert_eq!(streams[1].format_id, "140");
+        assert_eq!(streams[1].resolution.as_deref(), Some("audio only"));
+
+        let merged = parse_selected_streams("137+140\n1920x1080\nNA\nmp4a.40.2\n");
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].vcodec, None);
+    }
+}
@@ -2,21 +2,36 @@ use super::types::ProgressInfo;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
-static PROGRESS_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^([0-9.]+)%\|([^|]*)\|([^|]*)$").expect("Invalid regex"));
+static PROGRESS_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:([0-9.]+)|N/A|Unknown) ?%?\|([^|]*)\|([^|]*)(?:\|([^|]*)\|([^|]*))?$")
+        .expect("Invalid regex")
+});
 
 /// Parse a single progress line from yt-dlp stderr
-/// Input format (from --progress-template): "download:XX.X%|2.5MiB/s|00:01:30" or similar
+/// Input format (from --progress-template): "download:XX.X%|2.5MiB/s|00:01:30|1048576|12.5" or similar.
+/// Without a total size (live streams) the percent is "N/A"; such lines are
+/// `indeterminate` and only useful when the downloaded byte count is known.
 pub fn parse_progress_line(line: &str) -> Option<ProgressInfo> {
     let line = line.trim();
     let line = line.strip_prefix("download:").unwrap_or(line).trim();
 
     if let Some(captures) = PROGRESS_RE.captures(line) {
-        let percent_str = captures.get(1)?.as_str();
+        let percent = match captures.get(1) {
+            Some(p) => Some(p.as_str().parse::<f32>().ok()?),
+            None => None,
+        };
         let speed_str = captures.get(2)?.as_str().trim();
         let eta_str = captures.get(3)?.as_str().trim();
-
-        let percent = percent_str.parse::<f32>().ok()?;
+        let downloaded_bytes = captures
+            .get(4)
+            .and_then(|m| m.as_str().trim().parse::<u64>().ok());
+        let elapsed_secs = captures
+            .get(5)
+            .and_then(|m| m.as_str().trim().parse::<f64>().ok())
+            .map(|s| s as u64);
+        if percent.is_none() && downloaded_bytes.is_none() {
+            return None;
+        }
 
         let speed = if speed_str.is_empty() || speed_str == "N/A" || speed_str == "Unknown" {
             None
@@ -31,18 +46,66 @@ pub fn parse_progress_line(line: &str) -> Option<ProgressInfo> {
         };
 
         return Some(ProgressInfo {
-            percent,
+            percent: percent.unwrap_or(0.0),
+            indeterminate: percent.is_none(),
+            speed_bytes: speed.as_deref().and_then(parse_speed_bytes),
             speed,
             eta,
+            downloaded_bytes,
+            elapsed_secs,
         });
     }
 
     None
 }
 
+/// Convert a yt-dlp speed string ("2.5MiB/s", "812.3KiB/s", "1.2MB/s") to bytes per second.
+pub fn parse_speed_bytes(speed: &str) -> Option<u64> {
+    let value = speed.trim().strip_suffix("/s")?;
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier: f64 = match unit.trim() {
+        "B" => 1.0,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        "KB" | "kB" => 1000.0,
+        "MB" => 1000.0 * 1000.0,
+        "GB" => 1000.0 * 1000.0 * 1000.0,
+        _ => return None,
+    };
+    Some((number * multiplier) as u64)
+}
+
+/// yt-dlp post-processor tags that run after the download finishes
+const POSTPROCESS_TAGS: &[&str] = &[
+    "[Merger]",
+    "[ExtractAudio]",
+    "[VideoConvertor]",
+    "[VideoRemuxer]",
+    "[EmbedSubtitle]",
+    "[EmbedThumbnail]",
+    "[Metadata]",
+    "[SponsorBlock]",
+    "[ModifyChapters]",
+    "[SplitChapters]",
+    "[ThumbnailsConvertor]",
+    "[SubtitlesConvertor]",
+    "[MoveFiles]",
+];
+
+/// Whether `line` is output from a post-processing step (merge, convert, embed, fixup).
+pub fn is_postprocess_line(line: &str) -> bool {
+    line.starts_with("[Fixup") || POSTPROCESS_TAGS.iter().any(|tag| line.starts_with(tag))
+}
+
 /// Build the --progress-template argument string
 pub fn progress_template() -> String {
-    "download:%(progress._percent_str)s|%(progress._speed_str)s|%(progress._eta_str)s".to_string()
+    "download:%(progress._percent_str)s|%(progress._speed_str)s|%(progress._eta_str)s|%(progress.downloaded_bytes)s|%(progress.elapsed)s"
+        .to_string()
 }
 
 #[cfg(test)]
@@ -113,6 +176,39 @@ mod tests {
         assert_eq!(info.eta, None);
     }
 
+    #[test]
+    fn test_parse_byte_count_and_elapsed() {
+        let line = "download: 45.2%|2.5MiB/s|00:01:30|1048576|12.75";
+        let info = parse_progress_line(line).unwrap();
+        assert_eq!(info.percent, 45.2);
+        assert!(!info.indeterminate);
+        assert_eq!(info.downloaded_bytes, Some(1_048_576));
+        assert_eq!(info.elapsed_secs, Some(12));
+    }
+
+    #[test]
+    fn test_parse_live_without_total() {
+        let line = "download:  N/A%|1.2MiB/s|Unknown|73400320|61.0";
+        let info = parse_progress_line(line).unwrap();
+        assert!(info.indeterminate);
+        assert_eq!(info.percent, 0.0);
+        assert_eq!(info.downloaded_bytes, Some(73_400_320));
+        assert_eq!(info.eta, None);
+
+        // Nothing to show without a percent or a byte count
+        assert!(parse_progress_line("download:Unknown %|N/A|N/A|NA|NA").is_none());
+    }
+
+    #[test]
+    fn test_parse_speed_bytes() {
+        assert_eq!(parse_speed_bytes("2.5MiB/s"), Some(2_621_440));
+        assert_eq!(parse_speed_bytes("512.0KiB/s"), Some(524_288));
+        assert_eq!(parse_speed_bytes("100B/s"), Some(100));
+        assert_eq!(parse_speed_bytes("1.5MB/s"), Some(1_500_000));
+        assert_eq!(parse_speed_bytes("Unknown"), None);
+        assert_eq!(parse_speed_bytes("3.0XiB/s"), None);
+    }
+
     #[test]
     fn test_parse_invalid_line() {
         let line = "Some other output from yt-dlp";
@@ -125,5 +221,19 @@ mod tests {
         assert!(template.contains("progress._percent_str"));
         assert!(template.contains("progress._speed_str"));
         assert!(template.contains("progress._eta_str"));
+        assert!(template.contains("progress.downloaded_bytes"));
+        assert!(template.contains("progress.elapsed"));
+    }
+
+    #[test]
+    fn test_postprocess_lines() {
+        assert!(is_postprocess_line(
+            "[Merger] Merging formats into \"a.mp4\""
+        ));
+        assert!(is_postprocess_line(
+            "[FixupM3u8] Fixing MPEG-TS in MP4 container"
+        ));
+        assert!(!is_postprocess_line("[download] Destination: a.mp4"));
+        assert!(!is_postprocess_line("[youtube] abc: Downloading webpage"));
     }
 }
@@ -0,0 +1,509 @@
+//! Rename downloaded files to match a new output template
+//! (`preview_rename` / `apply_rename`).
+//!
+//! Names are filled in locally from the info-json sidecar yt-dlp wrote next to
+//! the file, or from the history row when there is none, the way yt-dlp would
+//! fill them. Files stay in their folder, so templates with folders are refused.
+//! Sidecars named after the file (info-json, chapters, thumbnail, subtitles)
+//! are renamed with it. Only plans from the latest preview are applied, and
+//! either every file in a batch is renamed with its history row updated, or
+//! none is.
+
+use crate::modules::types::AppError;
+use crate::ytdlp::disk;
+use crate::ytdlp::normalize::info_json_path;
+use crate::ytdlp::types::{HistoryItem, RenamePlan, RenameStatus};
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+/// How long a preview can be applied
+const PREVIEW_TTL: Duration = Duration::from_secs(10 * 60);
+/// What yt-dlp writes for a field the video doesn't have
+const NA_PLACEHOLDER: &str = "NA";
+
+/// Extensions of sidecars yt-dlp names after the media file: thumbnails,
+/// subtitles (`<name>.<lang>.<ext>`) and the description
+const SIDECAR_EXTS: &[&str] = &[
+    "jpg",
+    "jpeg",
+    "png",
+    "webp",
+    "srt",
+    "vtt",
+    "ass",
+    "lrc",
+    "ttml",
+    "srv1",
+    "srv2",
+    "srv3",
+    "json3",
+    "description",
+];
+
+/// `%%` or a `%(spec)` field with its conversion
+static FIELD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"%%|%\(([^)]*)\)(0?\d*)(?:\.(\d+))?([a-zA-Z])").unwrap());
+
+struct Preview {
+    at: Instant,
+    plans: Vec<RenamePlan>,
+}
+
+static LAST_PREVIEW: Mutex<Option<Preview>> = Mutex::new(None);
+
+/// Refuse templates that would move files into other folders.
+pub fn check_template(template: &str) -> Result<(), AppError> {
+    let literal = FIELD.replace_all(template, "");
+    if literal.contains('/') || literal.contains('\\') {
+        return Err(AppError::Custom(
+            "Renaming keeps files in their folder; use a template without folders".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// A field value as yt-dlp puts it in a file name: characters Windows doesn't
+/// allow become their full-width look-alikes, control characters are dropped.
+fn sanitize_value(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_control())
+        .map(|c| match c {
+            '/' => '\u{29F8}',
+            '\\' => '\u{29F9}',
+            '"' | '*' | ':' | '<' | '>' | '?' | '|' => {
+                char::from_u32(c as u32 + 0xFEE0).unwrap_or('_')
+            }
+            c => c,
+        })
+        .collect()
+}
+
+/// Fields for a row without an info-json sidecar.
+fn history_fields(item: &HistoryItem) -> Map<String, Value> {
+    let mut fields = Map::new();
+    fields.insert("id".into(), item.video_id.clone().into());
+    fields.insert("title".into(), item.title.clone().into());
+    fields.insert("fulltitle".into(), item.title.clone().into());
+    fields.insert("webpage_url".into(), item.video_url.clone().into());
+    if let Some(uploader) = &item.uploader {
+        fields.insert("uploader".into(), uploader.clone().into());
+        fields.insert("channel".into(), uploader.clone().into());
+    }
+    if let Some(duration) = item.duration_secs {
+        fields.insert("duration".into(), duration.into());
+    }
+    fields
+}
+
+/// Fill `template` from `fields`. Missing fields become "NA" when `complete`
+/// (the full info dict, as yt-dlp sees it) and are an error otherwise, since
+/// the history row only knows a few of them.
+fn render(template: &str, fields: &Map<String, Value>, complete: bool) -> Result<String, String> {
+    let mut out = String::new();
+    let mut last = 0;
+    for caps in FIELD.captures_iter(template) {
+        let whole = caps.get(0).unwrap();
+        out.push_str(&template[last..whole.start()]);
+        last = whole.end();
+        let Some(spec) = caps.get(1) else {
+            out.push('%');
+            continue;
+        };
+        let (names, default) = match spec.as_str().split_once('|') {
+            Some((names, default)) => (names, Some(default)),
+            None => (spec.as_str(), None),
+        };
+        if names
+            .chars()
+            .any(|c| !(c.is_ascii_alphanumeric() || c == '_' || c == ','))
+        {
+            return Err(format!(
+                "%({}) uses template features renaming doesn't support",
+                spec.as_str()
+            ));
+        }
+        let value = names
+            .split(',')
+            .find_map(|name| fields.get(name).filter(|v| !v.is_null()));
+        let text = match (value, caps.get(4).map(|c| c.as_str())) {
+            (Some(value), Some("d")) => {
+                let n = value
+                    .as_i64()
+                    .or_else(|| value.as_f64().map(|f| f as i64))
+                    .ok_or_else(|| format!("%({}) is not a number", names))?;
+                let width: usize = caps[2].parse().unwrap_or(0);
+                format!("{:0width$}", n, width = width)
+            }
+            (Some(value), Some("s")) => {
+                let text = match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                match caps.get(3).and_then(|p| p.as_str().parse::<usize>().ok()) {
+                    Some(precision) => text.chars().take(precision).collect(),
+                    None => text,
+                }
+            }
+            (Some(_), conversion) => {
+                return Err(format!(
+                    "%({}){} uses a conversion renaming doesn't support",
+                    names,
+                    conversion.unwrap_or_default()
+                ))
+            }
+            (None, _) => match default {
+                Some(default) => default.to_string(),
+                None if complete => NA_PLACEHOLDER.to_string(),
+                None => {
+                    return Err(format!(
+                        "%({}) isn't known without the info-json sidecar",
+                        names
+                    ))
+                }
+            },
+        };
+        out.push_str(&sanitize_value(&text));
+    }
+    out.push_str(&template[last..]);
+    let name = out.trim().trim_end_matches('.').to_string();
+    if name.is_empty() {
+        return Err("The template gives an empty name".to_string());
+    }
+    Ok(name)
+}
+
+/// Plan for one history row. `exists` tells whether a path is taken on disk,
+/// `same_file` whether two paths are one file.
+fn plan_item(
+    item: &HistoryItem,
+    info: Option<Map<String, Value>>,
+    template: &str,
+    exists: impl Fn(&Path) -> bool,
+    same_file: impl Fn(&Path, &Path) -> bool,
+) -> RenamePlan {
+    let from = Path::new(&item.file_path);
+    let mut plan = RenamePlan {
+        history_id: item.id,
+        title: item.title.clone(),
+        from: item.file_path.clone(),
+        to: None,
+        status: RenameStatus::Ready,
+        detail: None,
+    };
+    if !exists(from) {
+        plan.status = RenameStatus::Missing;
+        return plan;
+    }
+    let complete = info.is_some();
+    let mut fields = info.unwrap_or_else(|| history_fields(item));
+    // The file's container can differ from what the info dict says after a merge or remux
+    if let Some(ext) = from.extension() {
+        fields.insert("ext".into(), ext.to_string_lossy().to_string().into());
+    }
+    let name = match render(template, &fields, complete) {
+        Ok(name) => name,
+        Err(e) => {
+            plan.status = RenameStatus::Unresolved;
+            plan.detail = Some(e);
+            return plan;
+        }
+    };
+    let to = from.with_file_name(&name);
+    let to_str = to.to_string_lossy().to_string();
+    if to == from {
+        plan.status = RenameStatus::Unchanged;
+    } else if exists(&to) && !same_file(from, &to) {
+        // A case-only change is the same file on a case-insensitive volume
+        plan.status = RenameStatus::Collision;
+        plan.detail = Some("A file with this name already exists".to_string());
+    }
+    plan.to = Some(to_str);
+    plan
+}
+
+/// Flag ready plans that would land on the same path. `case_sensitive` tells
+/// whether a folder keeps names that differ only by case apart.
+fn mark_shared_targets(plans: &mut [RenamePlan], mut case_sensitive: impl FnMut(&Path) -> bool) {
+    let mut key = |to: &str| {
+        let dir = Path::new(to).parent().unwrap_or(Path::new(""));
+        if case_sensitive(dir) {
+            to.to_string()
+        } else {
+            to.to_lowercase()
+        }
+    };
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut keys = Vec::with_capacity(plans.len());
+    for plan in plans.iter() {
+        let k = plan
+            .to
+            .as_deref()
+            .filter(|_| plan.status == RenameStatus::Ready)
+            .map(&mut key);
+        if let Some(k) = &k {
+            *seen.entry(k.clone()).or_default() += 1;
+        }
+        keys.push(k);
+    }
+    for (plan, k) in plans.iter_mut().zip(keys) {
+        let shared = k.is_some_and(|k| seen.get(&k).copied().unwrap_or(0) > 1);
+        if shared {
+            plan.status = RenameStatus::Collision;
+            plan.detail = Some("Another file would get the same name".to_string());
+        }
+    }
+}
+
+/// Suffix of a sidecar of `stem` ("info.json", "en.srt", "jpg"), or None when
+/// `name` isn't one.
+fn sidecar_suffix<'a>(name: &'a str, stem: &str) -> Option<&'a str> {
+    let rest = name.strip_prefix(stem)?.strip_prefix('.')?;
+    if rest == "info.json" || rest == "chapters.json" {
+        return Some(rest);
+    }
+    let (lang, ext) = match rest.rsplit_once('.') {
+        Some((lang, ext)) => (Some(lang), ext),
+        None => (None, rest),
+    };
+    let lang_ok = lang.is_none_or(|l| {
+        !l.is_empty()
+            && l.chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    });
+    (lang_ok && SIDECAR_EXTS.contains(&ext.to_ascii_lowercase().as_str())).then_some(rest)
+}
+
+/// Sidecars next to `media`, each with the path it gets when `media` becomes `to`.
+fn sidecars(media: &Path, to: &Path) -> Vec<(PathBuf, PathBuf)> {
+    let (Some(dir), Some(stem), Some(to_stem)) = (
+        media.parent(),
+        media.file_stem().and_then(|s| s.to_str()),
+        to.file_stem().and_then(|s| s.to_str()),
+    ) else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let suffix = sidecar_suffix(&name, stem)?;
+            let target = to.with_file_name(format!("{}.{}", to_stem, suffix));
+            (entry.path() != media).then(|| (entry.path(), target))
+        })
+        .collect()
+}
+
+fn read_info(file_path: &str) -> Option<Map<String, Value>> {
+    let text = std::fs::read_to_string(info_json_path(file_path)).ok()?;
+    match serde_json::from_str::<Value>(&text).ok()? {
+        Value::Object(map) => Some(map),
+        _ => None,
+    }
+}
+
+/// Plans for `items` under `template`, remembered for `apply`.
+pub fn preview(items: &[HistoryItem], template: &str) -> Vec<RenamePlan> {
+    let mut plans: Vec<RenamePlan> = items
+        .iter()
+        .map(|item| {
+            let info = read_info(&item.file_path);
+            plan_item(item, info, template, |p| p.exists(), disk::same_file)
+        })
+        .collect();
+    let mut case_sensitive: HashMap<PathBuf, bool> = HashMap::new();
+    mark_shared_targets(&mut plans, |dir| {
+        // Unknown counts as case-insensitive, the stricter of the two
+        *case_sensitive
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| disk::is_case_sensitive(dir).unwrap_or(false))
+    });
+    *LAST_PREVIEW.lock().unwrap_or_else(|e| e.into_inner()) = Some(Preview {
+        at: Instant::now(),
+        plans: plans.clone(),
+    });
+    plans
+}
+
+/// Check that every plan is a ready plan of the latest preview, then rename
+/// the files and their sidecars. `update_db` records the new paths; if
+/// anything fails, the files already moved are moved back.
+pub fn apply(
+    plans: &[RenamePlan],
+    update_db: impl FnOnce(&[(u64, String, String)]) -> Result<(), AppError>,
+) -> Result<u32, AppError> {
+    {
+        let last = LAST_PREVIEW.lock().unwrap_or_else(|e| e.into_inner());
+        let previewed = match last.as_ref() {
+            Some(preview) if preview.at.elapsed() < PREVIEW_TTL => &preview.plans,
+            _ => {
+                return Err(AppError::Custom(
+                    "The rename preview has expired; preview again".to_string(),
+                ))
+            }
+        };
+        for plan in plans {
+            if plan.status != RenameStatus::Ready || !previewed.contains(plan) {
+                return Err(AppError::Custom(format!(
+                    "\"{}\" isn't a ready plan from the latest preview; preview again",
+                    plan.title
+                )));
+            }
+        }
+    }
+
+    let mut moved: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let mut rows = Vec::new();
+    let result = plans.iter().try_for_each(|plan| {
+        let from = PathBuf::from(&plan.from);
+        let to = PathBuf::from(plan.to.as_deref().unwrap_or_default());
+        if !from.is_file() {
+            return Err(format!("{} is gone", plan.from));
+        }
+        if to.exists() && !disk::same_file(&from, &to) {
+            return Err(format!("{} already exists", to.display()));
+        }
+        let sidecars = sidecars(&from, &to);
+        std::fs::rename(&from, &to).map_err(|e| format!("rename {}: {}", plan.from, e))?;
+        moved.push((from.clone(), to.clone()));
+
+        for (sidecar, sidecar_to) in sidecars {
+            // Never write over another file's sidecar
+            if sidecar_to.exists() && !disk::same_file(&sidecar, &sidecar_to) {
+                continue;
+            }
+            std::fs::rename(&sidecar, &sidecar_to)
+                .map_err(|e| format!("rename {}: {}", sidecar.display(), e))?;
+            moved.push((sidecar, sidecar_to));
+        }
+        rows.push((
+            plan.history_id,
+            plan.from.clone(),
+            to.to_string_lossy().to_string(),
+        ));
+        Ok(())
+    });
+    let result = result
+        .map_err(AppError::Custom)
+        .and_then(|()| update_db(&rows));
+
+    if let Err(e) = result {
+        for (from, to) in moved.iter().rev() {
+            let _ = std::fs::rename(to, from);
+        }
+        return Err(e);
+    }
+    *LAST_PREVIEW.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    Ok(rows.len() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(json: Value) -> Map<String, Value> {
+        match json {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn renders_like_ytdlp() {
+        let info = fields(serde_json::json!({
+            "title": "A/B: the \"cut\"?",
+            "id": "abc",
+            "playlist_index": 7,
+            "ext": "mp4",
+        }));
+        assert_eq!(
+            render("%(title)s [%(id)s].%(ext)s", &info, true).unwrap(),
+            "A\u{29F8}B： the ＂cut＂？ [abc].mp4"
+        );
+        assert_eq!(
+            render("%(playlist_index)03d - %(title).3s 100%%", &info, true).unwrap(),
+            "007 - A\u{29F8}B 100%"
+        );
+        assert_eq!(
+            render("%(artist,uploader|Unknown)s", &info, true).unwrap(),
+            "Unknown"
+        );
+        assert_eq!(render("%(artist)s", &info, true).unwrap(), "NA");
+        // Without the info dict a missing field can't be told apart from an unset one
+        assert!(render("%(artist)s", &info, false).is_err());
+        assert!(render("%(upload_date>%Y)s", &info, true).is_err());
+    }
+
+    #[test]
+    fn folders_are_refused() {
+        assert!(check_template("%(title)s.%(ext)s").is_ok());
+        assert!(check_template("%(uploader)s/%(title)s.%(ext)s").is_err());
+    }
+
+    #[test]
+    fn shared_targets_are_collisions() {
+        let plan = |id, to: &str| RenamePlan {
+            history_id: id,
+            title: String::new(),
+            from: format!("/d/{}.mp4", id),
+            to: Some(to.to_string()),
+            status: RenameStatus::Ready,
+            detail: None,
+        };
+        let mut plans = vec![
+            plan(1, "/d/Same.mp4"),
+            plan(2, "/d/same.mp4"),
+            plan(3, "/d/x.mp4"),
+        ];
+        let mut sensitive = plans.clone();
+        mark_shared_targets(&mut plans, |_| false);
+        let statuses: Vec<_> = plans.iter().map(|p| p.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                RenameStatus::Collision,
+                RenameStatus::Collision,
+                RenameStatus::Ready
+            ]
+        );
+        // Names that differ by case are different files on a case-sensitive volume
+        mark_shared_targets(&mut sensitive, |_| true);
+        assert!(sensitive.iter().all(|p| p.status == RenameStatus::Ready));
+    }
+
+    #[test]
+    fn sidecars_follow_the_media_name() {
+        assert_eq!(sidecar_suffix("Song.info.json", "Song"), Some("info.json"));
+        assert_eq!(sidecar_suffix("Song.en-US.vtt", "Song"), Some("en-US.vtt"));
+        assert_eq!(sidecar_suffix("Song.webp", "Song"), Some("webp"));
+        assert_eq!(
+            sidecar_suffix("Song.chapters.json", "Song"),
+            Some("chapters.json")
+        );
+        // Another video whose title starts the same way
+        assert_eq!(sidecar_suffix("Song. Live.mp4", "Song"), None);
+        assert_eq!(sidecar_suffix("Songs.jpg", "Song"), None);
+    }
+
+    #[test]
+    fn case_only_change_of_the_same_file_is_not_a_collision() {
+        let item: HistoryItem = serde_json::from_value(serde_json::json!({
+            "id": 1, "videoUrl": "u", "videoId": "abc", "title": "Song",
+            "qualityLabel": "", "format": "", "filePath": "/d/song.mp4",
+            "downloadedAt": 0, "chaptersEmbedded": false, "tags": [], "isFavorite": false,
+            "userTags": []
+        }))
+        .unwrap();
+        let plan = plan_item(&item, None, "%(title)s.%(ext)s", |_| true, |_, _| true);
+        assert_eq!(plan.status, RenameStatus::Ready);
+        let plan = plan_item(&item, None, "%(title)s.%(ext)s", |_| true, |_, _| false);
+        assert_eq!(plan.status, RenameStatus::Collision);
+    }
+}
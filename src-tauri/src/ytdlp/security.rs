@@ -19,6 +19,27 @@ const VALID_COOKIE_BROWSERS: &[&str] = &[
     "brave", "chrome", "chromium", "edge", "firefox", "opera", "safari", "vivaldi", "whale",
 ];
 
+/// Linux keyrings yt-dlp can decrypt Chromium cookies with (`chrome+kwallet`)
+const VALID_COOKIE_KEYRINGS: &[&str] = &[
+    "basictext",
+    "gnomekeyring",
+    "kwallet",
+    "kwallet5",
+    "kwallet6",
+];
+
+/// Target formats accepted by yt-dlp's --recode-video
+const VALID_RECODE_FORMATS: &[&str] = &[
+    "avi", "flv", "gif", "mkv", "mov", "mp4", "webm", "aac", "aiff", "alac", "flac", "m4a", "mka",
+    "mp3", "ogg", "opus", "vorbis", "wav",
+];
+
+/// Heights accepted for the global resolution cap
+const VALID_MAX_HEIGHTS: &[u32] = &[144, 240, 360, 480, 720, 1080, 1440, 2160, 4320];
+
+/// Maximum length of custom ffmpeg postprocessor args
+const MAX_FFMPEG_ARGS_LENGTH: usize = 512;
+
 /// Dangerous yt-dlp output template patterns that could cause path traversal or abuse
 const DANGEROUS_TEMPLATE_PATTERNS: &[&str] = &[
     "..",   // path traversal
@@ -108,6 +129,21 @@ fn extract_host(url: &str) -> Option<String> {
     }
 }
 
+/// Check whether a URL's host equals `host` or is a subdomain of it (case-insensitive).
+pub fn url_matches_host(url: &str, host: &str) -> bool {
+    let want = host.trim().trim_start_matches("www.").to_lowercase();
+    if want.is_empty() {
+        return false;
+    }
+    match extract_host(url) {
+        Some(actual) => {
+            let actual = actual.to_lowercase();
+            actual == want || actual.ends_with(&format!(".{}", want))
+        }
+        None => false,
+    }
+}
+
 /// Check if a hostname is a potential SSRF target (local/private network).
 fn is_ssrf_target(host: &str) -> bool {
     // Check common local hostnames
@@ -228,29 +264,218 @@ pub fn sanitize_filename_template(template: &str) -> Result<String, AppError> {
     Ok(template.to_string())
 }
 
-/// Validate the cookie browser name against known yt-dlp supported browsers.
-pub fn sanitize_cookie_browser(browser: &str) -> Result<String, AppError> {
-    let browser = browser.trim().to_lowercase();
+/// Validate a BCP 47-style language code as yt-dlp reports it ("en", "pt-BR", "zh-Hant").
+pub fn sanitize_language(lang: &str) -> Result<String, AppError> {
+    let lang = lang.trim();
+    let mut parts = lang.split('-');
+    let primary = parts.next().unwrap_or("");
+    let valid = (2..=3).contains(&primary.len())
+        && primary.chars().all(|c| c.is_ascii_alphabetic())
+        && parts
+            .all(|p| (1..=8).contains(&p.len()) && p.chars().all(|c| c.is_ascii_alphanumeric()))
+        && lang.len() <= 35;
+    if !valid {
+        return Err(AppError::Custom(format!(
+            "Invalid language code: '{}'. Use a code such as en, ko or pt-BR",
+            lang
+        )));
+    }
+    Ok(lang.to_string())
+}
 
-    if browser.is_empty() {
+/// A `--cookies-from-browser` value split into its parts:
+/// `BROWSER[+KEYRING][:PROFILE][::CONTAINER]` (the container is Firefox's).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CookieBrowserSpec {
+    pub browser: String,
+    pub keyring: Option<String>,
+    /// Profile directory name ("Profile 2") or an absolute path to one
+    pub profile: Option<String>,
+    pub container: Option<String>,
+}
+
+/// Parse and validate a cookie browser spec such as "chrome", "chrome:Profile 2"
+/// or "chromium+kwallet:Default". The browser and keyring must be ones yt-dlp knows.
+pub fn parse_cookie_browser(spec: &str) -> Result<CookieBrowserSpec, AppError> {
+    let spec = spec.trim();
+    if spec.is_empty() {
         return Err(AppError::Custom(
             "Cookie browser name cannot be empty".to_string(),
         ));
     }
+    if spec.chars().any(char::is_control) {
+        return Err(AppError::Custom(
+            "Cookie browser contains control characters".to_string(),
+        ));
+    }
 
-    // yt-dlp accepts browser names, optionally with profile: "chrome:Profile 1"
-    // Extract just the browser name (before the colon)
-    let browser_name = browser.split(':').next().unwrap_or(&browser);
+    // The profile may itself contain ':' (a Windows path), so split off the container first
+    let (rest, container) = match spec.split_once("::") {
+        Some((rest, container)) => (rest, Some(container.trim())),
+        None => (spec, None),
+    };
+    let (name, profile) = match rest.split_once(':') {
+        Some((name, profile)) => (name, Some(profile.trim())),
+        None => (rest, None),
+    };
+    let (browser, keyring) = match name.split_once('+') {
+        Some((browser, keyring)) => (browser, Some(keyring.trim().to_lowercase())),
+        None => (name, None),
+    };
+    let browser = browser.trim().to_lowercase();
 
-    if !VALID_COOKIE_BROWSERS.contains(&browser_name) {
+    if !VALID_COOKIE_BROWSERS.contains(&browser.as_str()) {
         return Err(AppError::Custom(format!(
             "Unsupported cookie browser: '{}'. Supported: {}",
-            browser_name,
+            browser,
             VALID_COOKIE_BROWSERS.join(", ")
         )));
     }
+    if let Some(keyring) = &keyring {
+        if !VALID_COOKIE_KEYRINGS.contains(&keyring.as_str()) {
+            return Err(AppError::Custom(format!(
+                "Unsupported cookie keyring: '{}'. Supported: {}",
+                keyring,
+                VALID_COOKIE_KEYRINGS.join(", ")
+            )));
+        }
+    }
+    if profile.is_some_and(str::is_empty) || container.is_some_and(str::is_empty) {
+        return Err(AppError::Custom(format!(
+            "Empty profile or container in cookie browser: '{}'",
+            spec
+        )));
+    }
+
+    Ok(CookieBrowserSpec {
+        browser,
+        keyring,
+        profile: profile.map(String::from),
+        container: container.map(String::from),
+    })
+}
 
-    Ok(browser.to_string())
+/// Validate the cookie browser against known yt-dlp supported browsers and keyrings.
+/// Returns the spec with the browser and keyring lower-cased; the profile keeps its case.
+pub fn sanitize_cookie_browser(browser: &str) -> Result<String, AppError> {
+    let spec = parse_cookie_browser(browser)?;
+    let mut out = spec.browser;
+    if let Some(keyring) = spec.keyring {
+        out.push('+');
+        out.push_str(&keyring);
+    }
+    if let Some(profile) = spec.profile {
+        out.push(':');
+        out.push_str(&profile);
+    }
+    if let Some(container) = spec.container {
+        out.push_str("::");
+        out.push_str(&container);
+    }
+    Ok(out)
+}
+
+/// Validate a yt-dlp rate limit value such as "500K", "2M" or "1.5M".
+pub fn sanitize_rate_limit(rate: &str) -> Result<String, AppError> {
+    let rate = rate.trim();
+    let digits = rate.trim_end_matches(['K', 'M', 'G', 'k', 'm', 'g']);
+    let suffix_len = rate.len() - digits.len();
+    let valid = suffix_len <= 1
+        && !digits.is_empty()
+        && digits
+            .parse::<f64>()
+            .is_ok_and(|n| n > 0.0 && n.is_finite());
+    if !valid {
+        return Err(AppError::Custom(format!(
+            "Invalid rate limit: '{}'. Use a number with optional K/M/G suffix (e.g. 2M)",
+            rate
+        )));
+    }
+    Ok(rate.to_string())
+}
+
+/// Parse a yt-dlp size such as "500M", "2G" or "1.5g" into bytes (binary multiples,
+/// like yt-dlp's `--max-filesize`). A bare number is bytes.
+pub fn parse_filesize(size: &str) -> Option<u64> {
+    let size = size.trim();
+    let digits = size.trim_end_matches(['K', 'M', 'G', 'T', 'k', 'm', 'g', 't']);
+    if size.len() - digits.len() > 1 || digits.is_empty() {
+        return None;
+    }
+    let exponent = match size[digits.len()..].to_ascii_uppercase().as_str() {
+        "" => 0,
+        "K" => 1,
+        "M" => 2,
+        "G" => 3,
+        _ => 4,
+    };
+    let n = digits
+        .parse::<f64>()
+        .ok()
+        .filter(|n| *n >= 0.0 && n.is_finite())?;
+    let bytes = n * 1024f64.powi(exponent);
+    (bytes <= u64::MAX as f64).then_some(bytes as u64)
+}
+
+/// Validate a `--max-filesize` value ("0" means no cap); the suffix is upper-cased.
+pub fn sanitize_filesize(size: &str) -> Result<String, AppError> {
+    if parse_filesize(size).is_none() {
+        return Err(AppError::Custom(format!(
+            "Invalid file size: '{}'. Use a number with optional K/M/G/T suffix (e.g. 2G, 500M)",
+            size.trim()
+        )));
+    }
+    Ok(size.trim().to_ascii_uppercase())
+}
+
+/// Validate a --recode-video target format.
+pub fn sanitize_recode_format(format: &str) -> Result<String, AppError> {
+    let format = format.trim().to_lowercase();
+    if !VALID_RECODE_FORMATS.contains(&format.as_str()) {
+        return Err(AppError::Custom(format!(
+            "Unsupported recode format: '{}'. Supported: {}",
+            format,
+            VALID_RECODE_FORMATS.join(", ")
+        )));
+    }
+    Ok(format)
+}
+
+/// Validate the global max video height against common resolutions.
+pub fn sanitize_max_height(height: u32) -> Result<u32, AppError> {
+    if !VALID_MAX_HEIGHTS.contains(&height) {
+        let valid: Vec<String> = VALID_MAX_HEIGHTS.iter().map(|h| h.to_string()).collect();
+        return Err(AppError::Custom(format!(
+            "Unsupported max height: {}. Supported: {}",
+            height,
+            valid.join(", ")
+        )));
+    }
+    Ok(height)
+}
+
+/// Validate custom ffmpeg args for the re-encode postprocessor.
+/// They reach ffmpeg as argv (no shell), but must stay a single line of options
+/// and must not redirect output, which yt-dlp controls.
+pub fn sanitize_ffmpeg_args(args: &str) -> Result<String, AppError> {
+    let args = args.trim();
+    if args.is_empty() || args.len() > MAX_FFMPEG_ARGS_LENGTH {
+        return Err(AppError::Custom(format!(
+            "ffmpeg args must be 1-{} characters",
+            MAX_FFMPEG_ARGS_LENGTH
+        )));
+    }
+    if args.contains(['\n', '\r', '\0']) {
+        return Err(AppError::Custom(
+            "ffmpeg args must be a single line".to_string(),
+        ));
+    }
+    if args.split_whitespace().any(|a| a == "-y" || a == "-i") {
+        return Err(AppError::Custom(
+            "ffmpeg args must not set inputs or overwrite outputs (-i, -y)".to_string(),
+        ));
+    }
+    Ok(args.to_string())
 }
 
 /// Clamp max_concurrent to a safe range [1, MAX_CONCURRENT_LIMIT].
@@ -258,6 +483,40 @@ pub fn clamp_max_concurrent(n: u32) -> u32 {
     n.clamp(1, MAX_CONCURRENT_LIMIT)
 }
 
+/// yt-dlp flags whose values are secrets and must never be logged.
+const SECRET_FLAGS: &[&str] = &["--username", "--password", "--video-password", "-u", "-p"];
+
+/// Replace the values of credential flags and auth-bearing headers with "***" for logging.
+pub fn redact_args(args: &[String]) -> Vec<String> {
+    let mut redacted = Vec::with_capacity(args.len());
+    let mut hide_next = false;
+    let mut header_next = false;
+    for arg in args {
+        if hide_next {
+            redacted.push("***".to_string());
+            hide_next = false;
+        } else if header_next {
+            redacted.push(crate::ytdlp::http_headers::redact_header(arg));
+            header_next = false;
+        } else if let Some(header) = arg.strip_prefix("--add-header=") {
+            redacted.push(format!(
+                "--add-header={}",
+                crate::ytdlp::http_headers::redact_header(header)
+            ));
+        } else if let Some((flag, _)) = arg
+            .split_once('=')
+            .filter(|(f, _)| SECRET_FLAGS.contains(f))
+        {
+            redacted.push(format!("{}=***", flag));
+        } else {
+            hide_next = SECRET_FLAGS.contains(&arg.as_str());
+            header_next = arg == "--add-header";
+            redacted.push(arg.clone());
+        }
+    }
+    redacted
+}
+
 /// Sanitize error messages before sending to the frontend.
 /// Removes potentially sensitive system paths.
 pub fn sanitize_error_message(msg: &str) -> String {
@@ -382,10 +641,142 @@ mod tests {
         assert!(sanitize_cookie_browser("malicious_browser").is_err());
         assert!(sanitize_cookie_browser("").is_err());
         assert!(sanitize_cookie_browser("/bin/sh").is_err());
+        assert!(sanitize_cookie_browser("chrome+vault").is_err());
+        assert!(sanitize_cookie_browser("chrome:").is_err());
+        assert!(sanitize_cookie_browser("chrome:Default\n--exec").is_err());
+    }
+
+    #[test]
+    fn test_cookie_browser_profile_and_keyring() {
+        assert_eq!(
+            sanitize_cookie_browser("Chrome+KWallet:Profile 2").unwrap(),
+            "chrome+kwallet:Profile 2"
+        );
+        let spec = parse_cookie_browser(r"chrome:C:\Users\me\Chrome\Profile 1").unwrap();
+        assert_eq!(
+            spec.profile.as_deref(),
+            Some(r"C:\Users\me\Chrome\Profile 1")
+        );
+        let spec = parse_cookie_browser("firefox:abc.default::Work").unwrap();
+        assert_eq!(spec.profile.as_deref(), Some("abc.default"));
+        assert_eq!(spec.container.as_deref(), Some("Work"));
+        assert_eq!(spec.keyring, None);
     }
 
     // === Max concurrent tests ===
 
+    #[test]
+    fn test_url_matches_host() {
+        assert!(url_matches_host(
+            "https://www.youtube.com/watch?v=1",
+            "youtube.com"
+        ));
+        assert!(url_matches_host(
+            "https://YouTube.com/watch?v=1",
+            "www.youtube.com"
+        ));
+        assert!(!url_matches_host("https://notyoutube.com/x", "youtube.com"));
+        assert!(!url_matches_host("https://youtu.be/abc", "youtube.com"));
+        assert!(!url_matches_host("https://youtube.com/x", ""));
+    }
+
+    #[test]
+    fn test_rate_limits() {
+        assert!(sanitize_rate_limit("2M").is_ok());
+        assert!(sanitize_rate_limit("500K").is_ok());
+        assert!(sanitize_rate_limit("1.5m").is_ok());
+        assert!(sanitize_rate_limit("1048576").is_ok());
+        assert!(sanitize_rate_limit("").is_err());
+        assert!(sanitize_rate_limit("M").is_err());
+        assert!(sanitize_rate_limit("2MM").is_err());
+        assert!(sanitize_rate_limit("-1M").is_err());
+        assert!(sanitize_rate_limit("2M --exec rm").is_err());
+    }
+
+    #[test]
+    fn test_languages() {
+        assert_eq!(sanitize_language(" en ").unwrap(), "en");
+        assert!(sanitize_language("pt-BR").is_ok());
+        assert!(sanitize_language("zh-Hant").is_ok());
+        assert!(sanitize_language("").is_err());
+        assert!(sanitize_language("e").is_err());
+        assert!(sanitize_language("en]").is_err());
+        assert!(sanitize_language("en-").is_err());
+        assert!(sanitize_language("en/best").is_err());
+    }
+
+    #[test]
+    fn test_filesizes() {
+        assert_eq!(parse_filesize("2G"), Some(2 * 1024 * 1024 * 1024));
+        assert_eq!(parse_filesize("500m"), Some(500 * 1024 * 1024));
+        assert_eq!(parse_filesize("1.5K"), Some(1536));
+        assert_eq!(parse_filesize("4096"), Some(4096));
+        assert_eq!(parse_filesize("0"), Some(0));
+        assert_eq!(sanitize_filesize(" 2g ").unwrap(), "2G");
+        assert!(sanitize_filesize("").is_err());
+        assert!(sanitize_filesize("G").is_err());
+        assert!(sanitize_filesize("2GB").is_err());
+        assert!(sanitize_filesize("-1M").is_err());
+        assert!(sanitize_filesize("2G --exec rm").is_err());
+    }
+
+    #[test]
+    fn test_recode_formats() {
+        assert_eq!(sanitize_recode_format("MP4").unwrap(), "mp4");
+        assert!(sanitize_recode_format("mkv").is_ok());
+        assert!(sanitize_recode_format("exe").is_err());
+    }
+
+    #[test]
+    fn test_sanitize_max_height() {
+        assert_eq!(sanitize_max_height(1080).unwrap(), 1080);
+        assert!(sanitize_max_height(0).is_err());
+        assert!(sanitize_max_height(1000).is_err());
+        assert!(sanitize_recode_format("").is_err());
+    }
+
+    #[test]
+    fn test_ffmpeg_args() {
+        assert!(sanitize_ffmpeg_args("-c:v libx265 -crf 28").is_ok());
+        assert!(sanitize_ffmpeg_args("").is_err());
+        assert!(sanitize_ffmpeg_args("-c:v libx264\n-y").is_err());
+        assert!(sanitize_ffmpeg_args("-i /etc/passwd").is_err());
+    }
+
+    #[test]
+    fn test_redact_args() {
+        let args: Vec<String> = [
+            "--username",
+            "me",
+            "--password",
+            "hunter2",
+            "--video-password=pw",
+            "--add-header",
+            "Authorization:Bearer abc",
+            "--add-header",
+            "Accept-Language:en",
+            "URL",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        assert_eq!(
+            redact_args(&args),
+            vec![
+                "--username",
+                "***",
+                "--password",
+                "***",
+                "--video-password=***",
+                "--add-header",
+                "Authorization:***",
+                "--add-header",
+                "Accept-Language:en",
+                "URL"
+            ]
+        );
+    }
+
     #[test]
     fn test_clamp_max_concurrent() {
         assert_eq!(clamp_max_concurrent(0), 1);
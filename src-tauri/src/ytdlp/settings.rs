@@ -5,6 +5,9 @@ use tauri_plugin_store::StoreExt;
 
 const STORE_FILE: &str = "settings.json";
 
+/// Maximum number of directories kept in the recents list.
+const MAX_RECENT_DIRECTORIES: usize = 10;
+
 /// Common parsing logic: extract AppSettings from a key-value getter function.
 /// `getter` takes a key name and returns an optional serde_json::Value.
 fn parse_settings(getter: impl Fn(&str) -> Option<serde_json::Value>) -> AppSettings {
@@ -69,6 +72,180 @@ fn parse_settings(getter: impl Fn(&str) -> Option<serde_json::Value>) -> AppSett
         .and_then(|v| v.as_bool())
         .unwrap_or(defaults.setup_completed);
 
+    let prefer_system_binary = getter("preferSystemBinary")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(defaults.prefer_system_binary);
+
+    let use_temp_dir = getter("useTempDir")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(defaults.use_temp_dir);
+
+    let temp_dir = getter("tempDir").and_then(|v| v.as_str().map(String::from));
+
+    let background_throttle = getter("backgroundThrottle")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(defaults.background_throttle);
+
+    let throttle_max_concurrent = getter("throttleMaxConcurrent")
+        .and_then(|v| v.as_u64().map(|n| n as u32))
+        .unwrap_or(defaults.throttle_max_concurrent);
+
+    let throttle_rate_limit = getter("throttleRateLimit")
+        .map(|v| v.as_str().map(String::from))
+        .unwrap_or(defaults.throttle_rate_limit);
+
+    let ignore_config = getter("ignoreConfig")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(defaults.ignore_config);
+
+    let recode_video = getter("recodeVideo")
+        .map(|v| v.as_str().map(String::from))
+        .unwrap_or(defaults.recode_video);
+
+    let recode_ffmpeg_args = getter("recodeFfmpegArgs")
+        .map(|v| v.as_str().map(String::from))
+        .unwrap_or(defaults.recode_ffmpeg_args);
+
+    let start_delay_secs = getter("startDelaySecs")
+        .and_then(|v| v.as_u64().map(|n| n as u32))
+        .unwrap_or(defaults.start_delay_secs);
+
+    let max_height = getter("maxHeight")
+        .map(|v| v.as_u64().map(|n| n as u32))
+        .unwrap_or(defaults.max_height);
+
+    let data_dir_override = getter("dataDirOverride")
+        .map(|v| v.as_str().map(String::from))
+        .unwrap_or(defaults.data_dir_override);
+
+    let single_video_default = getter("singleVideoDefault")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(defaults.single_video_default);
+
+    let ytdlp_binaries = getter("ytdlpBinaries")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or(defaults.ytdlp_binaries);
+
+    let active_ytdlp = getter("activeYtdlp")
+        .map(|v| v.as_str().map(String::from))
+        .unwrap_or(defaults.active_ytdlp);
+
+    let write_xattrs = getter("writeXattrs")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(defaults.write_xattrs);
+
+    let requeue_after_sleep = getter("requeueAfterSleep")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(defaults.requeue_after_sleep);
+
+    let embed_chapters = getter("embedChapters")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(defaults.embed_chapters);
+
+    let write_chapters = getter("writeChapters")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(defaults.write_chapters);
+
+    let auto_container = getter("autoContainer")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(defaults.auto_container);
+
+    let max_filesize = getter("maxFilesize")
+        .map(|v| v.as_str().map(String::from))
+        .unwrap_or(defaults.max_filesize);
+
+    let verify_downloads = getter("verifyDownloads")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(defaults.verify_downloads);
+
+    let retry_suspect_downloads = getter("retrySuspectDownloads")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(defaults.retry_suspect_downloads);
+
+    let output_paths_by_type = getter("outputPathsByType")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or(defaults.output_paths_by_type);
+
+    let log_burst_policy = getter("logBurstPolicy")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or(defaults.log_burst_policy);
+
+    let default_headers = getter("defaultHeaders")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or(defaults.default_headers);
+
+    let startup_network_check = getter("startupNetworkCheck")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(defaults.startup_network_check);
+
+    let log_max_page_size = getter("logMaxPageSize")
+        .and_then(|v| v.as_u64().map(|n| n as u32))
+        .unwrap_or(defaults.log_max_page_size);
+
+    let max_retries = getter("maxRetries")
+        .and_then(|v| v.as_u64().map(|n| n as u32))
+        .unwrap_or(defaults.max_retries);
+
+    let retry_backoff_secs = getter("retryBackoffSecs")
+        .and_then(|v| v.as_u64().map(|n| n as u32))
+        .unwrap_or(defaults.retry_backoff_secs);
+
+    let cleanup_idle_secs = getter("cleanupIdleSecs")
+        .and_then(|v| v.as_u64().map(|n| n as u32))
+        .unwrap_or(defaults.cleanup_idle_secs);
+
+    let queue_watchdog_secs = getter("queueWatchdogSecs")
+        .and_then(|v| v.as_u64().map(|n| n as u32))
+        .unwrap_or(defaults.queue_watchdog_secs);
+
+    let session_download_limit = getter("sessionDownloadLimit")
+        .map(|v| v.as_u64().map(|n| n as u32))
+        .unwrap_or(defaults.session_download_limit);
+
+    let daily_download_limit = getter("dailyDownloadLimit")
+        .map(|v| v.as_u64().map(|n| n as u32))
+        .unwrap_or(defaults.daily_download_limit);
+
+    let host_allowlist = getter("hostAllowlist")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or(defaults.host_allowlist);
+
+    let host_denylist = getter("hostDenylist")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or(defaults.host_denylist);
+
+    let similarity_fingerprints = getter("similarityFingerprints")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(defaults.similarity_fingerprints);
+
+    let binary_backup_count = getter("binaryBackupCount")
+        .and_then(|v| v.as_u64().map(|n| n as u32))
+        .unwrap_or(defaults.binary_backup_count);
+
+    let codec_preference = getter("codecPreference")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or(defaults.codec_preference);
+
+    let pause_below_battery_percent = getter("pauseBelowBatteryPercent")
+        .map(|v| v.as_u64().map(|n| n as u32))
+        .unwrap_or(defaults.pause_below_battery_percent);
+
+    let pause_above_cpu_percent = getter("pauseAboveCpuPercent")
+        .map(|v| v.as_u64().map(|n| n as u32))
+        .unwrap_or(defaults.pause_above_cpu_percent);
+
+    let cpu_sample_secs = getter("cpuSampleSecs")
+        .and_then(|v| v.as_u64().map(|n| n as u32))
+        .unwrap_or(defaults.cpu_sample_secs);
+
+    let cpu_sustain_secs = getter("cpuSustainSecs")
+        .and_then(|v| v.as_u64().map(|n| n as u32))
+        .unwrap_or(defaults.cpu_sustain_secs);
+
+    let monthly_data_cap = getter("monthlyDataCap")
+        .map(|v| v.as_str().map(String::from))
+        .unwrap_or(defaults.monthly_data_cap);
+
     AppSettings {
         download_path,
         default_quality,
@@ -85,6 +262,50 @@ fn parse_settings(getter: impl Fn(&str) -> Option<serde_json::Value>) -> AppSett
         minimize_to_tray,
         dep_mode,
         setup_completed,
+        prefer_system_binary,
+        use_temp_dir,
+        temp_dir,
+        background_throttle,
+        throttle_max_concurrent,
+        throttle_rate_limit,
+        ignore_config,
+        recode_video,
+        recode_ffmpeg_args,
+        start_delay_secs,
+        max_height,
+        data_dir_override,
+        single_video_default,
+        ytdlp_binaries,
+        active_ytdlp,
+        write_xattrs,
+        requeue_after_sleep,
+        embed_chapters,
+        write_chapters,
+        auto_container,
+        max_filesize,
+        verify_downloads,
+        retry_suspect_downloads,
+        output_paths_by_type,
+        log_burst_policy,
+        default_headers,
+        startup_network_check,
+        log_max_page_size,
+        max_retries,
+        retry_backoff_secs,
+        cleanup_idle_secs,
+        queue_watchdog_secs,
+        session_download_limit,
+        daily_download_limit,
+        host_allowlist,
+        host_denylist,
+        similarity_fingerprints,
+        binary_backup_count,
+        codec_preference,
+        pause_below_battery_percent,
+        pause_above_cpu_percent,
+        cpu_sample_secs,
+        cpu_sustain_secs,
+        monthly_data_cap,
     }
 }
 
@@ -93,7 +314,63 @@ pub fn get_settings(app: &AppHandle) -> Result<AppSettings, AppError> {
         .store(STORE_FILE)
         .map_err(|e| AppError::Custom(e.to_string()))?;
 
-    Ok(parse_settings(|key| store.get(key)))
+    let mut settings = parse_settings(|key| store.get(key));
+    // Never chosen (an explicit "none" is stored as null): start on the default browser
+    if !store.has("cookieBrowser") {
+        settings.cookie_browser = crate::ytdlp::default_browser::initial_cookie_browser();
+    }
+    Ok(settings)
+}
+
+/// `settings` safe to paste into a bug report: sensitive header values and
+/// referer credentials/query become `***`, and the home directory in paths
+/// becomes `~`. Login credentials and cookies aren't part of AppSettings.
+pub fn redacted(settings: &AppSettings) -> AppSettings {
+    use crate::ytdlp::http_headers::redact_header;
+    use crate::ytdlp::security::sanitize_error_message as mask_home;
+
+    let mut out = settings.clone();
+    out.default_headers.headers = settings
+        .default_headers
+        .headers
+        .iter()
+        .map(|h| redact_header(h))
+        .collect();
+    out.default_headers.referer = settings.default_headers.referer.as_deref().map(redact_url);
+
+    out.download_path = mask_home(&settings.download_path);
+    for path in [
+        &mut out.temp_dir,
+        &mut out.data_dir_override,
+        &mut out.output_paths_by_type.video,
+        &mut out.output_paths_by_type.audio,
+        &mut out.output_paths_by_type.subtitle,
+        &mut out.output_paths_by_type.thumbnail,
+    ] {
+        *path = path.as_deref().map(mask_home);
+    }
+    for path in out.ytdlp_binaries.values_mut() {
+        *path = mask_home(path);
+    }
+    out
+}
+
+/// URL with any user, password and query string replaced by `***`.
+fn redact_url(url: &str) -> String {
+    let Ok(mut parsed) = reqwest::Url::parse(url) else {
+        // Can't tell which part is sensitive
+        return "***".to_string();
+    };
+    if !parsed.username().is_empty() {
+        let _ = parsed.set_username("***");
+    }
+    if parsed.password().is_some() {
+        let _ = parsed.set_password(Some("***"));
+    }
+    if parsed.query().is_some() {
+        parsed.set_query(Some("***"));
+    }
+    parsed.to_string()
 }
 
 pub fn update_settings(app: &AppHandle, settings: &AppSettings) -> Result<(), AppError> {
@@ -188,11 +465,304 @@ pub fn update_settings(app: &AppHandle, settings: &AppSettings) -> Result<(), Ap
             .map_err(|e| AppError::Custom(e.to_string()))?,
     );
 
+    store.set(
+        "preferSystemBinary",
+        serde_json::to_value(settings.prefer_system_binary)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "useTempDir",
+        serde_json::to_value(settings.use_temp_dir).map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "tempDir",
+        serde_json::to_value(&settings.temp_dir).map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "backgroundThrottle",
+        serde_json::to_value(settings.background_throttle)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "throttleMaxConcurrent",
+        serde_json::to_value(settings.throttle_max_concurrent)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "throttleRateLimit",
+        serde_json::to_value(&settings.throttle_rate_limit)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "ignoreConfig",
+        serde_json::to_value(settings.ignore_config)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "recodeVideo",
+        serde_json::to_value(&settings.recode_video)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "recodeFfmpegArgs",
+        serde_json::to_value(&settings.recode_ffmpeg_args)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "startDelaySecs",
+        serde_json::to_value(settings.start_delay_secs)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "maxHeight",
+        serde_json::to_value(settings.max_height).map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "dataDirOverride",
+        serde_json::to_value(&settings.data_dir_override)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "singleVideoDefault",
+        serde_json::to_value(settings.single_video_default)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "ytdlpBinaries",
+        serde_json::to_value(&settings.ytdlp_binaries)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "activeYtdlp",
+        serde_json::to_value(&settings.active_ytdlp)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "writeXattrs",
+        serde_json::to_value(settings.write_xattrs).map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "requeueAfterSleep",
+        serde_json::to_value(settings.requeue_after_sleep)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "embedChapters",
+        serde_json::to_value(settings.embed_chapters)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "writeChapters",
+        serde_json::to_value(settings.write_chapters)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "autoContainer",
+        serde_json::to_value(settings.auto_container)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "maxFilesize",
+        serde_json::to_value(&settings.max_filesize)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "verifyDownloads",
+        serde_json::to_value(settings.verify_downloads)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "retrySuspectDownloads",
+        serde_json::to_value(settings.retry_suspect_downloads)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "outputPathsByType",
+        serde_json::to_value(&settings.output_paths_by_type)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "logBurstPolicy",
+        serde_json::to_value(settings.log_burst_policy)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "defaultHeaders",
+        serde_json::to_value(&settings.default_headers)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "startupNetworkCheck",
+        serde_json::to_value(settings.startup_network_check)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "logMaxPageSize",
+        serde_json::to_value(settings.log_max_page_size)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "maxRetries",
+        serde_json::to_value(settings.max_retries).map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "retryBackoffSecs",
+        serde_json::to_value(settings.retry_backoff_secs)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "cleanupIdleSecs",
+        serde_json::to_value(settings.cleanup_idle_secs)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "queueWatchdogSecs",
+        serde_json::to_value(settings.queue_watchdog_secs)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "sessionDownloadLimit",
+        serde_json::to_value(settings.session_download_limit)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "dailyDownloadLimit",
+        serde_json::to_value(settings.daily_download_limit)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "hostAllowlist",
+        serde_json::to_value(&settings.host_allowlist)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "hostDenylist",
+        serde_json::to_value(&settings.host_denylist)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "similarityFingerprints",
+        serde_json::to_value(settings.similarity_fingerprints)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "binaryBackupCount",
+        serde_json::to_value(settings.binary_backup_count)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "codecPreference",
+        serde_json::to_value(&settings.codec_preference)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "pauseBelowBatteryPercent",
+        serde_json::to_value(settings.pause_below_battery_percent)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "pauseAboveCpuPercent",
+        serde_json::to_value(settings.pause_above_cpu_percent)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "cpuSampleSecs",
+        serde_json::to_value(settings.cpu_sample_secs)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "cpuSustainSecs",
+        serde_json::to_value(settings.cpu_sustain_secs)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
+    store.set(
+        "monthlyDataCap",
+        serde_json::to_value(&settings.monthly_data_cap)
+            .map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+
     store.save().map_err(|e| AppError::Custom(e.to_string()))?;
 
     Ok(())
 }
 
+/// Move `dir` to the front of the recently used directories list (deduplicated, capped).
+pub fn record_recent_directory(app: &AppHandle, dir: &str) -> Result<(), AppError> {
+    let store = app
+        .store(STORE_FILE)
+        .map_err(|e| AppError::Custom(e.to_string()))?;
+
+    let mut recents: Vec<String> = store
+        .get("recentDirectories")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    recents.retain(|d| d != dir);
+    recents.insert(0, dir.to_string());
+    recents.truncate(MAX_RECENT_DIRECTORIES);
+
+    store.set(
+        "recentDirectories",
+        serde_json::to_value(&recents).map_err(|e| AppError::Custom(e.to_string()))?,
+    );
+    store.save().map_err(|e| AppError::Custom(e.to_string()))?;
+    Ok(())
+}
+
+/// Recently used download directories, most recent first, skipping ones that no longer exist.
+pub fn get_recent_directories(app: &AppHandle) -> Vec<String> {
+    app.store(STORE_FILE)
+        .ok()
+        .and_then(|store| store.get("recentDirectories"))
+        .and_then(|v| serde_json::from_value::<Vec<String>>(v).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|d| std::path::Path::new(d).is_dir())
+        .collect()
+}
+
 pub fn default_download_path() -> String {
     if cfg!(target_os = "windows") {
         if let Ok(profile) = std::env::var("USERPROFILE") {
@@ -220,3 +790,45 @@ pub fn get_settings_from_path(app_data_dir: &std::path::Path) -> Result<AppSetti
 
     Ok(parse_settings(|key| value.get(key).cloned()))
 }
+
+/// Settings from a serialized `AppSettings`, possibly from an older version:
+/// missing keys take their defaults.
+pub fn from_json(value: &serde_json::Value) -> AppSettings {
+    parse_settings(|key| value.get(key).cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacted_hides_every_secret_field() {
+        const SECRET: &str = "s3cr3t-value";
+        let mut settings = AppSettings::default();
+        settings.default_headers.headers = vec![
+            format!("Authorization:Bearer {}", SECRET),
+            format!("Cookie:session={}", SECRET),
+            format!("Proxy-Authorization:Basic {}", SECRET),
+            format!("X-Api-Key:{}", SECRET),
+            format!("X-Upload-Token:{}", SECRET),
+            format!("X-Client-Secret:{}", SECRET),
+            "Accept-Language:en".to_string(),
+        ];
+        settings.default_headers.referer = Some(format!(
+            "https://user:{}@example.com/watch?token={}",
+            SECRET, SECRET
+        ));
+
+        let json = serde_json::to_string(&redacted(&settings)).unwrap();
+        assert!(!json.contains(SECRET), "secret leaked: {}", json);
+        // Harmless headers and the referer's host stay useful for debugging
+        assert!(json.contains("Accept-Language:en"));
+        assert!(json.contains("example.com/watch"));
+    }
+
+    #[test]
+    fn redact_url_masks_unparseable_input() {
+        assert_eq!(redact_url("not a url"), "***");
+        assert_eq!(redact_url("https://example.com/v"), "https://example.com/v");
+    }
+}
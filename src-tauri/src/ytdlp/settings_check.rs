@@ -0,0 +1,224 @@
+//! Whole-payload settings validation (`validate_settings`, `update_settings`).
+//!
+//! Every field is checked and every problem reported, keyed by the camelCase
+//! settings key, so the settings page can mark all bad fields at once.
+//! Folders are never created; an existing one is probed with a temp file, and
+//! one that can't be written to is only a warning, since a drive or share may
+//! be unavailable for the moment.
+
+use crate::modules::data_dir;
+use crate::ytdlp::security;
+use crate::ytdlp::types::{AppSettings, SettingIssue};
+use std::path::Path;
+
+fn issue(field: impl Into<String>, message: impl ToString) -> SettingIssue {
+    SettingIssue {
+        field: field.into(),
+        message: message.to_string(),
+        warning: false,
+    }
+}
+
+fn warning(field: impl Into<String>, message: impl ToString) -> SettingIssue {
+    SettingIssue {
+        warning: true,
+        ..issue(field, message)
+    }
+}
+
+/// A folder setting: absolute, no `..`, and if it exists, a directory. One
+/// that can't be written to right now is a warning.
+fn check_dir(issues: &mut Vec<SettingIssue>, field: &str, dir: &str) {
+    if dir.trim().is_empty() {
+        return;
+    }
+    if let Err(e) = security::sanitize_output_path(dir) {
+        issues.push(issue(field, e));
+        return;
+    }
+    match std::fs::metadata(Path::new(dir)) {
+        Ok(meta) if !meta.is_dir() => issues.push(issue(field, "Not a folder")),
+        Ok(_) => {
+            if let Err(e) = data_dir::probe_writable(Path::new(dir)) {
+                issues.push(warning(field, format!("Folder is not writable: {}", e)));
+            }
+        }
+        // Missing folders are created on first use
+        Err(_) => {}
+    }
+}
+
+/// All problems with `settings`; it can be saved when every one is a warning.
+pub fn validate(settings: &AppSettings) -> Vec<SettingIssue> {
+    let mut issues = Vec::new();
+
+    check_dir(&mut issues, "downloadPath", &settings.download_path);
+    if let Some(dir) = &settings.temp_dir {
+        check_dir(&mut issues, "tempDir", dir);
+    }
+    if let Some(dir) = &settings.data_dir_override {
+        check_dir(&mut issues, "dataDirOverride", dir);
+    }
+    let by_type = &settings.output_paths_by_type;
+    for (kind, dir) in [
+        ("video", &by_type.video),
+        ("audio", &by_type.audio),
+        ("subtitle", &by_type.subtitle),
+        ("thumbnail", &by_type.thumbnail),
+    ] {
+        if let Some(dir) = dir {
+            check_dir(&mut issues, &format!("outputPathsByType.{}", kind), dir);
+        }
+    }
+
+    if let Err(e) = security::sanitize_filename_template(&settings.filename_template) {
+        issues.push(issue("filenameTemplate", e));
+    }
+    if let Some(browser) = &settings.cookie_browser {
+        if let Err(e) = security::sanitize_cookie_browser(browser) {
+            issues.push(issue("cookieBrowser", e));
+        }
+    }
+    if !matches!(settings.dep_mode.as_str(), "external" | "system") {
+        issues.push(issue(
+            "depMode",
+            format!("Unknown dependency mode: {}", settings.dep_mode),
+        ));
+    }
+
+    for (label, path) in &settings.ytdlp_binaries {
+        let field = format!("ytdlpBinaries.{}", label);
+        if label.trim().is_empty() {
+            issues.push(issue(field, "yt-dlp binary label must not be empty"));
+        } else if !Path::new(path).is_absolute() {
+            issues.push(issue(
+                field,
+                format!("yt-dlp binary path must be absolute: {}", path),
+            ));
+        }
+    }
+    if let Some(label) = &settings.active_ytdlp {
+        if !settings.ytdlp_binaries.contains_key(label) {
+            issues.push(issue(
+                "activeYtdlp",
+                format!("Active yt-dlp '{}' is not registered", label),
+            ));
+        }
+    }
+
+    if let Some(rate) = &settings.throttle_rate_limit {
+        if let Err(e) = security::sanitize_rate_limit(rate) {
+            issues.push(issue("throttleRateLimit", e));
+        }
+    }
+    if let Some(height) = settings.max_height {
+        if let Err(e) = security::sanitize_max_height(height) {
+            issues.push(issue("maxHeight", e));
+        }
+    }
+    if let Some(size) = &settings.max_filesize {
+        if let Err(e) = security::sanitize_filesize(size) {
+            issues.push(issue("maxFilesize", e));
+        }
+    }
+    if let Some(cap) = &settings.monthly_data_cap {
+        if let Err(e) = security::sanitize_filesize(cap) {
+            issues.push(issue("monthlyDataCap", e));
+        }
+    }
+    if let Some(format) = &settings.recode_video {
+        if let Err(e) = security::sanitize_recode_format(format) {
+            issues.push(issue("recodeVideo", e));
+        }
+    }
+    if let Some(args) = &settings.recode_ffmpeg_args {
+        if settings.recode_video.is_none() {
+            issues.push(issue(
+                "recodeFfmpegArgs",
+                "Custom ffmpeg args require a recode target format",
+            ));
+        } else if let Err(e) = security::sanitize_ffmpeg_args(args) {
+            issues.push(issue("recodeFfmpegArgs", e));
+        }
+    }
+
+    if let Err(e) = crate::ytdlp::http_headers::sanitize(&settings.default_headers) {
+        issues.push(issue("defaultHeaders", e));
+    }
+    if let Err(e) = crate::ytdlp::host_policy::sanitize_list("allowlist", &settings.host_allowlist)
+    {
+        issues.push(issue("hostAllowlist", e));
+    }
+    if let Err(e) = crate::ytdlp::host_policy::sanitize_list("denylist", &settings.host_denylist) {
+        issues.push(issue("hostDenylist", e));
+    }
+    if let Err(e) = crate::ytdlp::codec_pref::sanitize(&settings.codec_preference) {
+        issues.push(issue("codecPreference", e));
+    }
+    if settings
+        .pause_below_battery_percent
+        .is_some_and(|p| p > 100)
+    {
+        issues.push(issue(
+            "pauseBelowBatteryPercent",
+            "Battery threshold must be a percentage (1-100)",
+        ));
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_are_valid() {
+        assert!(validate(&AppSettings::default()).iter().all(|i| i.warning));
+    }
+
+    #[test]
+    fn writable_folder_passes_and_file_is_an_error() {
+        let dir = std::env::temp_dir().join(format!("ytdlp-settings-check-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("file.txt");
+        std::fs::write(&file, b"x").unwrap();
+
+        let mut issues = Vec::new();
+        check_dir(&mut issues, "downloadPath", &dir.to_string_lossy());
+        assert!(issues.is_empty());
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        check_dir(&mut issues, "tempDir", &file.to_string_lossy());
+        assert_eq!(issues.len(), 1);
+        assert!(!issues[0].warning);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn reports_every_bad_field() {
+        let settings = AppSettings {
+            download_path: "relative/dir".to_string(),
+            throttle_rate_limit: Some("fast".to_string()),
+            max_height: Some(0),
+            recode_ffmpeg_args: Some("-crf 20".to_string()),
+            codec_preference: vec!["mpeg2".to_string()],
+            active_ytdlp: Some("nightly".to_string()),
+            pause_below_battery_percent: Some(150),
+            ..AppSettings::default()
+        };
+        let fields: Vec<String> = validate(&settings).into_iter().map(|i| i.field).collect();
+        assert_eq!(
+            fields,
+            [
+                "downloadPath",
+                "activeYtdlp",
+                "throttleRateLimit",
+                "maxHeight",
+                "recodeFfmpegArgs",
+                "codecPreference",
+                "pauseBelowBatteryPercent",
+            ]
+        );
+    }
+}
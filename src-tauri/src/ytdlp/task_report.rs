@@ -0,0 +1,278 @@
+//! Single-file report of one download for bug reports (`export_task_report`).
+//!
+//! The report holds the task row, the yt-dlp command it was spawned with and
+//! every log line tagged `[download:<id>]`. Credentials, cookie locations and
+//! auth-bearing request headers are masked in all of them, including text
+//! yt-dlp echoed back into the logs.
+
+use super::types::{DownloadTaskInfo, LogEntry, ReportFormat};
+use crate::modules::types::AppError;
+use serde::Serialize;
+use std::fmt::Write as _;
+
+/// Flags whose value is masked entirely
+const MASKED_FLAGS: &[&str] = &[
+    "--username",
+    "--password",
+    "--video-password",
+    "--ap-username",
+    "--ap-password",
+    "--twofactor",
+    "--cookies",
+    "--client-certificate",
+    "--client-certificate-key",
+    "--client-certificate-password",
+    "--netrc-location",
+];
+
+/// `--cookies-from-browser BROWSER[+KEYRING][:PROFILE][::CONTAINER]`: the browser stays visible
+const BROWSER_COOKIES_FLAG: &str = "--cookies-from-browser";
+
+/// `--add-header NAME:VALUE`: masked only for auth-bearing headers
+const HEADER_FLAG: &str = "--add-header";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskReport {
+    pub generated_at: i64,
+    pub app_version: String,
+    pub task: DownloadTaskInfo,
+    /// Redacted yt-dlp invocation from the spawn log line
+    pub command: Option<String>,
+    pub logs: Vec<LogEntry>,
+}
+
+/// Log prefix the executor tags every line of a task with.
+pub fn log_tag(task_id: u64) -> String {
+    format!("[download:{}]", task_id)
+}
+
+/// Mask secret flag values in free text. Handles `--flag value`, `--flag=value`
+/// and the `"--flag", "value"` form of logged argument lists.
+pub fn redact(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find("--") {
+        let (before, from_flag) = rest.split_at(pos);
+        out.push_str(before);
+        let flag_len = from_flag[2..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+            .map_or(from_flag.len(), |n| n + 2);
+        let flag = &from_flag[..flag_len];
+        out.push_str(flag);
+        rest = &from_flag[flag_len..];
+
+        let browser = flag == BROWSER_COOKIES_FLAG;
+        let header = flag == HEADER_FLAG;
+        if !browser && !header && !MASKED_FLAGS.contains(&flag) {
+            continue;
+        }
+        // Separator between flag and value: "=", whitespace, or `", "` in a Debug list
+        let sep_len = if rest.starts_with('=') {
+            1
+        } else if rest.starts_with("\", \"") {
+            4
+        } else if rest.starts_with(' ') {
+            1
+        } else {
+            continue;
+        };
+        out.push_str(&rest[..sep_len]);
+        rest = &rest[sep_len..];
+        let quoted = sep_len == 4;
+        let value_len = if header && !quoted {
+            // Header values may contain spaces ("Bearer abc"); run to the next flag
+            [rest.find(" --"), rest.find('\n')]
+                .into_iter()
+                .flatten()
+                .min()
+                .unwrap_or(rest.len())
+        } else {
+            rest.find(|c: char| {
+                if quoted {
+                    c == '"'
+                } else {
+                    c.is_whitespace() || c == '"'
+                }
+            })
+            .unwrap_or(rest.len())
+        };
+        let value = &rest[..value_len];
+        if header {
+            out.push_str(&crate::ytdlp::http_headers::redact_header(value));
+        } else if browser {
+            match value.split_once(':') {
+                Some((name, _)) => {
+                    out.push_str(name);
+                    out.push_str(":***");
+                }
+                None => out.push_str(value),
+            }
+        } else if value == "***" || value.is_empty() {
+            out.push_str(value);
+        } else {
+            out.push_str("***");
+        }
+        rest = &rest[value_len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn redact_entry(mut entry: LogEntry) -> LogEntry {
+    entry.message = redact(&entry.message);
+    entry.details = entry.details.as_deref().map(redact);
+    entry
+}
+
+/// Assemble the report; everything user- or yt-dlp-supplied is redacted.
+pub fn build(app_version: String, mut task: DownloadTaskInfo, logs: Vec<LogEntry>) -> TaskReport {
+    task.error_message = task.error_message.as_deref().map(redact);
+    if let Some(headers) = &mut task.headers {
+        for header in &mut headers.headers {
+            *header = crate::ytdlp::http_headers::redact_header(header);
+        }
+    }
+    let logs: Vec<LogEntry> = logs.into_iter().map(redact_entry).collect();
+    let spawn_marker = format!("{} spawning: ", log_tag(task.id));
+    // The last spawn wins when the task was retried
+    let command = logs.iter().rev().find_map(|e| {
+        e.message
+            .split_once(&spawn_marker)
+            .map(|(_, c)| c.to_string())
+    });
+    TaskReport {
+        generated_at: chrono::Utc::now().timestamp(),
+        app_version,
+        task,
+        command,
+        logs,
+    }
+}
+
+pub fn render(report: &TaskReport, format: ReportFormat) -> Result<String, AppError> {
+    match format {
+        ReportFormat::Json => serde_json::to_string_pretty(report)
+            .map_err(|e| AppError::Custom(format!("Failed to serialize report: {}", e))),
+        ReportFormat::Text => Ok(render_text(report)),
+    }
+}
+
+fn timestamp(secs: i64) -> String {
+    chrono::DateTime::from_timestamp(secs, 0)
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| secs.to_string())
+}
+
+fn render_text(report: &TaskReport) -> String {
+    let task = &report.task;
+    let mut out = String::new();
+    let _ = writeln!(out, "Download task report #{}", task.id);
+    let _ = writeln!(out, "Generated: {}", timestamp(report.generated_at));
+    let _ = writeln!(out, "App version: {}", report.app_version);
+    let _ = writeln!(out);
+    let _ = writeln!(out, "URL: {}", task.video_url);
+    let _ = writeln!(out, "Title: {}", task.title);
+    let _ = writeln!(out, "Format: {} ({})", task.format_id, task.quality_label);
+    let _ = writeln!(out, "Output: {}", task.output_path);
+    let _ = writeln!(out, "Created: {}", timestamp(task.created_at));
+    if let Some(completed) = task.completed_at {
+        let _ = writeln!(out, "Completed: {}", timestamp(completed));
+    }
+    let _ = writeln!(out, "Status: {:?}", task.status);
+    if let Some(error) = &task.error_message {
+        let _ = writeln!(out, "Error: {}", error);
+    }
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "Command: {}",
+        report.command.as_deref().unwrap_or("(not logged)")
+    );
+    let _ = writeln!(out);
+    let _ = writeln!(out, "Logs ({}):", report.logs.len());
+    for entry in &report.logs {
+        let _ = writeln!(
+            out,
+            "{} [{}] [{}] {}",
+            timestamp(entry.timestamp),
+            entry.level,
+            entry.category,
+            entry.message
+        );
+        if let Some(details) = &entry.details {
+            for line in details.lines() {
+                let _ = writeln!(out, "    {}", line);
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_logged_argument_lists() {
+        let line = r#"[download:7] spawning: yt-dlp ["--cookies", "/home/me/c.txt", "--cookies-from-browser", "firefox:/home/me/.mozilla/p1", "-f", "best"]"#;
+        assert_eq!(
+            redact(line),
+            r#"[download:7] spawning: yt-dlp ["--cookies", "***", "--cookies-from-browser", "firefox:***", "-f", "best"]"#
+        );
+    }
+
+    #[test]
+    fn redacts_plain_and_equals_forms() {
+        assert_eq!(
+            redact(
+                "--password hunter2 --netrc-location=/x/.netrc --cookies-from-browser chrome URL"
+            ),
+            "--password *** --netrc-location=*** --cookies-from-browser chrome URL"
+        );
+    }
+
+    #[test]
+    fn masks_auth_headers_only() {
+        assert_eq!(
+            redact("--add-header Authorization:Bearer abc --add-header Accept:x URL"),
+            "--add-header Authorization:*** --add-header Accept:x URL"
+        );
+        assert_eq!(
+            redact(r#"["--add-header", "Cookie:sid=1; x=2", "URL"]"#),
+            r#"["--add-header", "Cookie:***", "URL"]"#
+        );
+    }
+
+    #[test]
+    fn masks_task_headers() {
+        let task: DownloadTaskInfo = serde_json::from_value(serde_json::json!({
+            "id": 3, "videoUrl": "https://example.com/v", "videoId": "v", "title": "t",
+            "formatId": "best", "qualityLabel": "Best", "outputPath": "/tmp/v.mp4",
+            "status": "failed", "progress": 0.0, "createdAt": 0, "hasCredentials": false,
+            "keepSeparateStreams": false, "replaceExisting": false, "embedMetadata": false,
+            "embedThumbnail": false, "liveFromStart": false, "retryCount": 0,
+            "subtitlesOnly": false, "subtitleLangs": [], "userTags": [], "isFavorite": false,
+            "headers": {
+                "userAgent": null,
+                "referer": null,
+                "headers": ["Cookie: sid=s3cret", "Accept: text/html"]
+            }
+        }))
+        .unwrap();
+        let report = build("1.0.0".to_string(), task, Vec::new());
+        for format in [ReportFormat::Json, ReportFormat::Text] {
+            assert!(!render(&report, format).unwrap().contains("s3cret"));
+        }
+        assert_eq!(
+            report.task.headers.unwrap().headers,
+            ["Cookie:***", "Accept: text/html"]
+        );
+    }
+
+    #[test]
+    fn leaves_other_flags_and_masked_values_alone() {
+        let line = r#"["--username", "***", "--merge-output-format", "mp4", "--"]"#;
+        assert_eq!(redact(line), line);
+    }
+}
@@ -0,0 +1,224 @@
+//! Static checks of an output template (`lint_output_template`).
+//!
+//! yt-dlp only complains about a template once it fills it in, and some
+//! problems never raise an error at all: a field that is empty for most sites
+//! becomes "NA", an unbounded title overruns Windows' 260-character path
+//! limit. These checks read the template alone, so they run without a URL.
+
+use crate::ytdlp::types::{TemplateWarning, TemplateWarningKind};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// `%%` or a `%(spec)` field with its conversion, which may be missing
+static FIELD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"%%|%\(([^)]*)\)([#0\-+ ]*\d*(?:\.\d+)?[diouxXeEfFgGcrsaqjlhDSBU])?").unwrap()
+});
+
+/// Fields many extractors leave unset
+const EMPTY_PRONE: &[&str] = &[
+    "artist",
+    "album",
+    "track",
+    "track_number",
+    "creator",
+    "composer",
+    "genre",
+    "series",
+    "season",
+    "season_number",
+    "episode",
+    "episode_number",
+    "chapter",
+    "chapter_number",
+    "release_date",
+    "release_year",
+    "playlist",
+    "playlist_title",
+    "playlist_index",
+];
+
+/// Fields that can run to hundreds of characters
+const LONG_FIELDS: &[&str] = &[
+    "title",
+    "fulltitle",
+    "alt_title",
+    "description",
+    "playlist",
+    "playlist_title",
+    "album",
+    "track",
+];
+
+/// Literal text past this leaves little room under Windows' 260-character limit
+const MAX_LITERAL_LEN: usize = 120;
+
+/// Warnings for `template`, in the order found. An empty list means nothing stood out.
+pub fn lint(template: &str) -> Vec<TemplateWarning> {
+    let mut warnings = Vec::new();
+    let mut literal_len = template.len();
+
+    for caps in FIELD.captures_iter(template) {
+        let whole = caps.get(0).map_or("", |m| m.as_str());
+        literal_len -= whole.len();
+        let Some(spec) = caps.get(1).map(|m| m.as_str()) else {
+            literal_len += 1;
+            continue;
+        };
+        let name = field_name(spec);
+        let Some(conversion) = caps.get(2).map(|m| m.as_str()) else {
+            warnings.push(warning(
+                TemplateWarningKind::Malformed,
+                Some(name),
+                format!("{} has no conversion type; write it as %({})s", whole, spec),
+            ));
+            continue;
+        };
+        // Alternatives (`a,b`) and defaults (`a|x`) cover a missing value
+        let has_fallback = spec.contains(',') || spec.contains('|');
+
+        if EMPTY_PRONE.contains(&name) && !has_fallback {
+            warnings.push(warning(
+                TemplateWarningKind::EmptyProne,
+                Some(name),
+                format!(
+                    "{} is empty on many sites and becomes \"NA\"; add a default like %({}|Unknown)s",
+                    whole, name
+                ),
+            ));
+        }
+        if LONG_FIELDS.contains(&name) && !conversion.contains('.') {
+            warnings.push(warning(
+                TemplateWarningKind::PathLength,
+                Some(name),
+                format!(
+                    "{} has no length limit and can exceed Windows' path limit; cap it like %({}).150B",
+                    whole, name
+                ),
+            ));
+        }
+    }
+
+    if template
+        .rfind("%(")
+        .is_some_and(|start| !template[start..].contains(')'))
+    {
+        warnings.push(warning(
+            TemplateWarningKind::Malformed,
+            None,
+            "A %( field is never closed with )".to_string(),
+        ));
+    }
+
+    let file_part = template.rsplit(['/', '\\']).next().unwrap_or(template);
+    if !file_part.contains("%(ext") {
+        warnings.push(warning(
+            TemplateWarningKind::MissingExtension,
+            Some("ext"),
+            "The file name doesn't end in %(ext)s, so files may be saved without an extension"
+                .to_string(),
+        ));
+    }
+
+    if template.contains('\\') {
+        warnings.push(warning(
+            TemplateWarningKind::Separator,
+            None,
+            "Backslashes are folder separators only on Windows; use / to work everywhere"
+                .to_string(),
+        ));
+    }
+    if template.starts_with('/') || template.contains("//") || template.ends_with('/') {
+        warnings.push(warning(
+            TemplateWarningKind::Separator,
+            None,
+            "Empty folder name: remove the leading, doubled or trailing /".to_string(),
+        ));
+    }
+
+    if literal_len > MAX_LITERAL_LEN {
+        warnings.push(warning(
+            TemplateWarningKind::PathLength,
+            None,
+            format!(
+                "{} characters of fixed text leave little room under Windows' path limit",
+                literal_len
+            ),
+        ));
+    }
+
+    warnings
+}
+
+/// Base field of a spec: `title` for `title.0`, `upload_date>%Y`, `artist,uploader|NA`.
+fn field_name(spec: &str) -> &str {
+    let end = spec
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(spec.len());
+    &spec[..end]
+}
+
+fn warning(kind: TemplateWarningKind, field: Option<&str>, message: String) -> TemplateWarning {
+    TemplateWarning {
+        kind,
+        field: field.map(String::from),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(template: &str) -> Vec<TemplateWarningKind> {
+        lint(template).into_iter().map(|w| w.kind).collect()
+    }
+
+    #[test]
+    fn bounded_default_template_is_clean() {
+        assert!(lint("%(title).150B [%(id)s].%(ext)s").is_empty());
+        assert!(lint("%(uploader)s/%(upload_date>%Y)s/%(id)s.%(ext)s").is_empty());
+    }
+
+    #[test]
+    fn flags_unbounded_title() {
+        assert_eq!(
+            kinds("%(title)s.%(ext)s"),
+            vec![TemplateWarningKind::PathLength]
+        );
+    }
+
+    #[test]
+    fn flags_missing_extension() {
+        assert_eq!(kinds("%(id)s"), vec![TemplateWarningKind::MissingExtension]);
+        // An extension in a folder name doesn't count
+        assert_eq!(
+            kinds("%(ext)s/%(id)s"),
+            vec![TemplateWarningKind::MissingExtension]
+        );
+    }
+
+    #[test]
+    fn defaults_silence_empty_prone_fields() {
+        assert_eq!(
+            kinds("%(artist)s - %(id)s.%(ext)s"),
+            vec![TemplateWarningKind::EmptyProne]
+        );
+        assert!(lint("%(artist|Unknown)s - %(id)s.%(ext)s").is_empty());
+        assert!(lint("%(artist,uploader)s - %(id)s.%(ext)s").is_empty());
+    }
+
+    #[test]
+    fn flags_malformed_fields_and_separators() {
+        assert_eq!(kinds("%(id).%(ext)s"), vec![TemplateWarningKind::Malformed]);
+        assert!(kinds("%(id)s.%(ext").contains(&TemplateWarningKind::Malformed));
+        assert_eq!(
+            kinds("videos//%(id)s.%(ext)s"),
+            vec![TemplateWarningKind::Separator]
+        );
+    }
+
+    #[test]
+    fn escaped_percent_is_literal() {
+        assert!(lint("100%% %(id)s.%(ext)s").is_empty());
+    }
+}
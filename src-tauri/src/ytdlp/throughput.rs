@@ -0,0 +1,114 @@
+//! Queue throughput over a window of history (`get_throughput_history`).
+//!
+//! Wait is enqueue to first start, run is first start to completion. Both need
+//! the timestamps history has carried since schema v24, so older rows count
+//! toward completions but not toward the averages.
+
+use crate::ytdlp::types::{Throughput, ThroughputBucket};
+
+/// Timestamps of one completed download, in seconds.
+pub struct TimingRow {
+    pub downloaded_at: i64,
+    pub enqueued_at: Option<i64>,
+    pub started_at: Option<i64>,
+}
+
+const HOUR: i64 = 3600;
+
+/// Window used when the caller doesn't give one
+pub const DEFAULT_WINDOW_HOURS: u32 = 24;
+/// 90 days of hourly buckets
+pub const MAX_WINDOW_HOURS: u32 = 2160;
+
+pub fn build(until: i64, window_hours: u32, rows: &[TimingRow]) -> Throughput {
+    let since = until - window_hours as i64 * HOUR;
+    let first_hour = since.div_euclid(HOUR) * HOUR;
+    let mut buckets: Vec<ThroughputBucket> = (first_hour..until)
+        .step_by(HOUR as usize)
+        .map(|hour_start| ThroughputBucket {
+            hour_start,
+            completed: 0,
+        })
+        .collect();
+
+    let mut waits = Vec::new();
+    let mut runs = Vec::new();
+    let mut completed = 0u64;
+    for row in rows
+        .iter()
+        .filter(|r| r.downloaded_at >= since && r.downloaded_at < until)
+    {
+        completed += 1;
+        let i = ((row.downloaded_at - first_hour) / HOUR) as usize;
+        if let Some(bucket) = buckets.get_mut(i) {
+            bucket.completed += 1;
+        }
+        // Clock changes can put a start before its enqueue; skip those rather than go negative
+        if let (Some(enqueued), Some(started)) = (row.enqueued_at, row.started_at) {
+            if started >= enqueued {
+                waits.push(started - enqueued);
+            }
+        }
+        if let Some(started) = row.started_at {
+            if row.downloaded_at >= started {
+                runs.push(row.downloaded_at - started);
+            }
+        }
+    }
+
+    Throughput {
+        since,
+        until,
+        completed,
+        completions_per_hour: completed as f64 / window_hours.max(1) as f64,
+        timed: waits.len() as u64,
+        avg_wait_secs: average(&waits),
+        max_wait_secs: waits.iter().copied().max(),
+        avg_run_secs: average(&runs),
+        buckets,
+    }
+}
+
+fn average(values: &[i64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<i64>() as f64 / values.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(downloaded_at: i64, enqueued_at: Option<i64>, started_at: Option<i64>) -> TimingRow {
+        TimingRow {
+            downloaded_at,
+            enqueued_at,
+            started_at,
+        }
+    }
+
+    #[test]
+    fn averages_only_rows_with_timestamps() {
+        let until = 10 * HOUR;
+        let rows = [
+            row(until - 100, Some(until - 400), Some(until - 300)),
+            row(until - 50, Some(until - 400), Some(until - 100)),
+            row(until - 10, None, None),
+        ];
+        let t = build(until, 2, &rows);
+        assert_eq!(t.completed, 3);
+        assert_eq!(t.completions_per_hour, 1.5);
+        assert_eq!(t.timed, 2);
+        assert_eq!(t.avg_wait_secs, Some(200.0));
+        assert_eq!(t.max_wait_secs, Some(300));
+        assert_eq!(t.avg_run_secs, Some(125.0));
+    }
+
+    #[test]
+    fn fills_empty_hours() {
+        let until = 10 * HOUR + 30;
+        let t = build(until, 3, &[row(8 * HOUR + 5, None, None)]);
+        let counts: Vec<u32> = t.buckets.iter().map(|b| b.completed).collect();
+        assert_eq!(t.buckets[0].hour_start, 7 * HOUR);
+        assert_eq!(counts, vec![0, 1, 0, 0]);
+        assert_eq!(t.avg_wait_secs, None);
+    }
+}
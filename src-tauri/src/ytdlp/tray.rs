@@ -1,3 +1,4 @@
+use crate::modules::logger;
 use crate::modules::types::AppError;
 use std::sync::Arc;
 use tauri::menu::{MenuBuilder, MenuItemBuilder};
@@ -9,8 +10,12 @@ const STORE_FILE: &str = "settings.json";
 
 pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
     let show = MenuItemBuilder::with_id("show", "Show Window").build(app)?;
+    let drain_quit =
+        MenuItemBuilder::with_id("drain_quit", "Quit After Downloads Finish").build(app)?;
     let quit = MenuItemBuilder::with_id("quit", "Quit").build(app)?;
-    let menu = MenuBuilder::new(app).items(&[&show, &quit]).build()?;
+    let menu = MenuBuilder::new(app)
+        .items(&[&show, &drain_quit, &quit])
+        .build()?;
 
     let icon = app
         .default_window_icon()
@@ -22,11 +27,10 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         .tooltip("Modern YT-DLP GUI")
         .menu(&menu)
         .on_menu_event(|app, event| match event.id().as_ref() {
-            "show" => {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.unminimize();
-                    let _ = window.show();
-                    let _ = window.set_focus();
+            "show" => show_main_window(app),
+            "drain_quit" => {
+                if let Err(e) = crate::ytdlp::download::start_drain(app, None) {
+                    logger::warn_cat("app", &e.to_string());
                 }
             }
             "quit" => {
@@ -43,12 +47,7 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
                 ..
             } = event
             {
-                let app = tray.app_handle();
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.unminimize();
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                }
+                show_main_window(tray.app_handle());
             }
         })
         .build(app)?;
@@ -56,6 +55,47 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Restore the main window from the tray and lift the background throttle.
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    apply_background_throttle(app, false);
+}
+
+/// Hide the main window to the tray, throttling downloads if enabled.
+pub fn hide_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.hide();
+    }
+    apply_background_throttle(app, true);
+}
+
+/// Apply (hidden) or lift (shown) the background throttle based on settings.
+/// In-flight downloads keep running either way; only new starts are affected.
+pub fn apply_background_throttle(app: &AppHandle, hidden: bool) {
+    let manager = app.state::<Arc<crate::ytdlp::download::DownloadManager>>();
+    let settings = crate::ytdlp::settings::get_settings(app).unwrap_or_default();
+
+    if hidden && settings.background_throttle {
+        manager.set_throttle(Some(settings.throttle_max_concurrent));
+        logger::info_cat(
+            "download",
+            &format!(
+                "Background throttle on: max {} concurrent",
+                settings.throttle_max_concurrent
+            ),
+        );
+    } else if manager.is_throttled() {
+        manager.set_throttle(None);
+        logger::info_cat("download", "Background throttle off");
+        // Freed capacity: start pending tasks up to the full limit
+        crate::ytdlp::download::process_next_pending_public(app.clone());
+    }
+}
+
 pub fn get_minimize_to_tray_setting(app: &AppHandle) -> Option<bool> {
     let store = app.store(STORE_FILE).ok()?;
     store.get("minimizeToTray").and_then(|v| v.as_bool())
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 // === Video Metadata ===
 
@@ -15,6 +16,12 @@ pub struct VideoInfo {
     pub channel_url: String,
     pub formats: Vec<FormatInfo>,
     pub filesize_approx: Option<u64>,
+    /// Distinct languages of the audio formats, for an audio-track picker
+    pub audio_languages: Vec<String>,
+    /// yt-dlp `live_status`: "is_live", "is_upcoming", "post_live", "was_live", "not_live"
+    pub live_status: Option<String>,
+    /// Live, upcoming or still processing: `live_from_start` applies
+    pub live_event: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
@@ -29,6 +36,90 @@ pub struct FormatInfo {
     pub acodec: Option<String>,
     pub has_video: bool,
     pub has_audio: bool,
+    /// Audio language tag, e.g. "en" (multi-language videos)
+    pub language: Option<String>,
+    /// `filesize` if known, else yt-dlp's `filesize_approx`, else bitrate × duration
+    pub size_bytes: Option<u64>,
+    pub size_accuracy: SizeAccuracy,
+    /// Frame height in pixels; None for audio-only formats
+    pub height: Option<u32>,
+    pub fps: Option<f64>,
+}
+
+/// Where a `FormatInfo::size_bytes` comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum SizeAccuracy {
+    /// Reported by the site
+    Exact,
+    /// yt-dlp's estimate or bitrate × duration
+    Approx,
+    /// Neither a size nor a bitrate is known
+    Unknown,
+}
+
+/// One format in `format_recommendations`, best value first.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FormatRec {
+    /// Selector to download it with; "137+140" when merged with audio
+    pub format_id: String,
+    /// e.g. "1080p" or "1080p60"
+    pub label: String,
+    pub ext: String,
+    pub height: u32,
+    pub fps: Option<f64>,
+    pub vcodec: Option<String>,
+    /// Video plus merged audio
+    pub size_bytes: u64,
+    pub size_accuracy: SizeAccuracy,
+    /// Frame height (fps-weighted) per MiB; higher is better value
+    pub quality_per_mib: f64,
+    pub recommended: bool,
+    /// Smallest download at 480p or better
+    pub smallest_acceptable: bool,
+    /// Why it ranks where it does, for tooltips
+    pub reasons: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum TemplateWarningKind {
+    /// The file name part has no `%(ext)s`
+    MissingExtension,
+    /// A field many sites leave empty, used without a default
+    EmptyProne,
+    /// Unbounded long fields or long fixed text; Windows paths stop at 260 characters
+    PathLength,
+    /// Backslashes or empty folder names
+    Separator,
+    /// A field yt-dlp can't parse
+    Malformed,
+}
+
+/// One finding of `lint_output_template`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateWarning {
+    pub kind: TemplateWarningKind,
+    /// Field the warning is about, e.g. "title"
+    pub field: Option<String>,
+    /// What's wrong and how to fix it
+    pub message: String,
+}
+
+/// Result of `preview_output_filename`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FilenamePreview {
+    /// Name relative to the download folder, subfolders from the template included
+    pub filename: String,
+    pub full_path: String,
+    /// The URL is a playlist or channel and this is its first entry's name;
+    /// the other entries follow the same template
+    pub playlist_example: bool,
+    /// `lint_output_template` findings for the template used
+    pub warnings: Vec<TemplateWarning>,
 }
 
 // === Playlist / Channel ===
@@ -75,6 +166,9 @@ pub struct UrlValidation {
     pub url_type: UrlType,
     pub normalized_url: Option<String>,
     pub video_id: Option<String>,
+    /// `list=` id carried by a single-video URL, so the UI can offer the
+    /// whole playlist instead (or the single video when treated as a playlist)
+    pub playlist_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
@@ -101,6 +195,173 @@ pub struct DownloadRequest {
     pub quality_label: String,
     pub output_dir: Option<String>,
     pub cookie_browser: Option<String>,
+    /// Source playlist id when queued from a playlist
+    pub playlist_id: Option<String>,
+    /// 1-based position in that playlist; fills `%(playlist_index)s` in the filename
+    pub playlist_index: Option<u32>,
+    /// Site login / video password; kept in the OS keychain, never in the DB
+    pub credentials: Option<TaskCredentials>,
+    /// Download best video and best audio as two separate files (`-f bv,ba`) without merging.
+    /// Uses about the same disk space as a merged download, but the video file has no audio;
+    /// merging them later needs room for a third, combined copy.
+    pub keep_separate_streams: bool,
+    /// Size cap for this task (`--max-filesize`, e.g. "2G"; "0" = none); None uses the setting
+    pub max_filesize: Option<String>,
+    /// Preferred audio track language (e.g. "en"); falls back to the default track
+    pub audio_lang: Option<String>,
+    /// Embed title/uploader/date tags into the file (`--embed-metadata`)
+    pub embed_metadata: bool,
+    /// Embed the thumbnail as cover art (`--embed-thumbnail`)
+    pub embed_thumbnail: bool,
+    /// Preset whose options fill the fields left empty here (see `save_preset`)
+    pub preset: Option<String>,
+    /// `--live-from-start`: record a live stream from its beginning rather than from now
+    pub live_from_start: bool,
+    /// HTTP headers layered over the `default_headers` setting
+    pub headers: Option<RequestHeaders>,
+    /// Automatic retries for this task; None uses the `max_retries` setting
+    pub max_retries: Option<u32>,
+    /// First wait before retrying (doubles per attempt); None uses the setting
+    pub retry_backoff_secs: Option<u32>,
+    /// What to do about a file already at the output path (`check_duplicate`);
+    /// None lets yt-dlp skip it as already downloaded
+    pub on_existing_file: Option<ExistingFileAction>,
+    /// Fetch only subtitles (`--skip-download --write-subs`); needs `subtitle_langs`
+    pub subtitles_only: bool,
+    /// Subtitle languages for `subtitles_only` (`--sub-langs`, e.g. "en", "ja.*", "all")
+    pub subtitle_langs: Vec<String>,
+    /// Convert fetched subtitles to this format (`--convert-subs`, e.g. "srt")
+    pub convert_subs: Option<String>,
+    /// User tags for the task; the history row gets them as `user_tags`
+    pub tags: Vec<String>,
+}
+
+/// Choice for a download whose output file already exists on disk.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum ExistingFileAction {
+    /// Write over the existing file
+    Overwrite,
+    /// Save under this name instead, relative to the download folder
+    /// (`DuplicateCheckResult.rename_to`)
+    Rename { filename: String },
+}
+
+/// Extra HTTP headers for yt-dlp requests (see `http_headers`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestHeaders {
+    /// `--user-agent`
+    pub user_agent: Option<String>,
+    /// `--referer`; sites that hotlink-protect their media often need it
+    pub referer: Option<String>,
+    /// `--add-header` entries as `Name: value`
+    pub headers: Vec<String>,
+}
+
+/// Options a preset fills into a `DownloadRequest`. Unset fields leave the request alone.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetOptions {
+    pub format_id: Option<String>,
+    pub quality_label: Option<String>,
+    pub output_dir: Option<String>,
+    pub keep_separate_streams: Option<bool>,
+    pub max_filesize: Option<String>,
+    pub audio_lang: Option<String>,
+    pub embed_metadata: Option<bool>,
+    pub embed_thumbnail: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadPreset {
+    pub name: String,
+    pub options: PresetOptions,
+    /// Shipped with the app; can't be overwritten or deleted
+    pub builtin: bool,
+}
+
+/// Result of `estimate_download_size`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeEstimate {
+    /// Expected size in bytes; None when the site reports neither size nor bitrate
+    pub bytes: Option<u64>,
+    /// Effective `--max-filesize` cap in bytes, if any
+    pub cap_bytes: Option<u64>,
+    /// `bytes` is known and over the cap
+    pub exceeds_cap: bool,
+}
+
+/// Layout of a URL list file for `import_urls_from_file`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum UrlFileFormat {
+    /// One URL per line
+    Lines,
+    /// CSV with a `url`/`link` column
+    Csv,
+    /// JSON array of URLs or of objects with a `url` field
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum UrlImportStatus {
+    Queued,
+    /// Already downloaded and the file is still there
+    AlreadyDownloaded,
+    AlreadyQueued,
+    /// Same video earlier in this file
+    DuplicateInFile,
+    /// The video id is on the blocklist
+    Blocked,
+    Invalid,
+    /// Playlist/channel URLs have to be expanded on the download page
+    Unsupported,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UrlImportLine {
+    /// 1-based line (or JSON array index) in the file
+    pub line: u32,
+    pub url: String,
+    pub status: UrlImportStatus,
+    pub task_id: Option<u64>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UrlImportResult {
+    pub queued: u32,
+    pub skipped: u32,
+    pub failed: u32,
+    pub lines: Vec<UrlImportLine>,
+}
+
+/// Per-task authentication for `--username` / `--password` / `--video-password`.
+#[derive(Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskCredentials {
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub video_password: Option<String>,
+}
+
+// Manual Debug so credentials never end up in logs via {:?}
+impl std::fmt::Debug for TaskCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mask = |v: &Option<String>| v.as_ref().map(|_| "***");
+        f.debug_struct("TaskCredentials")
+            .field("username", &mask(&self.username))
+            .field("password", &mask(&self.password))
+            .field("video_password", &mask(&self.video_password))
+            .finish()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
@@ -164,6 +425,48 @@ pub struct DownloadTaskInfo {
     pub error_message: Option<String>,
     pub created_at: i64,
     pub completed_at: Option<i64>,
+    pub playlist_id: Option<String>,
+    /// Credentials for this task are stored in the keychain
+    pub has_credentials: bool,
+    pub keep_separate_streams: bool,
+    /// History row this task re-downloads (`redownload_with_options`)
+    pub replaces_history_id: Option<u64>,
+    /// Overwrite / remove the file of `replaces_history_id` once this task succeeds
+    pub replace_existing: bool,
+    /// Per-task `--max-filesize` override
+    pub max_filesize: Option<String>,
+    pub audio_lang: Option<String>,
+    pub embed_metadata: bool,
+    pub embed_thumbnail: bool,
+    /// Preset the task was queued with
+    pub preset: Option<String>,
+    pub live_from_start: bool,
+    pub headers: Option<RequestHeaders>,
+    pub playlist_index: Option<u32>,
+    /// Per-task retry overrides as stored
+    pub max_retries: Option<u32>,
+    pub retry_backoff_secs: Option<u32>,
+    /// Automatic retries used so far
+    pub retry_count: u32,
+    pub subtitles_only: bool,
+    pub subtitle_langs: Vec<String>,
+    pub convert_subs: Option<String>,
+    /// Tags from the request (`cancel_matching` by tag); copied to the history row
+    pub user_tags: Vec<String>,
+    /// Policy in effect (overrides over settings); filled by `get_download_queue`
+    pub retry_policy: Option<RetryPolicy>,
+    /// A history row of this video is a favorite; filled by `get_download_queue`
+    pub is_favorite: bool,
+}
+
+/// Automatic retry policy of a task.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub backoff_secs: u32,
+    /// At least one value comes from the task rather than settings
+    pub overridden: bool,
 }
 
 // Global download event for app-wide event emission
@@ -171,13 +474,64 @@ pub struct DownloadTaskInfo {
 #[serde(rename_all = "camelCase")]
 pub struct GlobalDownloadEvent {
     pub task_id: u64,
-    pub event_type: String, // "started", "progress", "completed", "error"
+    pub event_type: String, // "started", "progress", "liveProgress", "completed", "error"
     pub percent: Option<f32>,
     pub speed: Option<String>,
     pub eta: Option<String>,
     pub file_path: Option<String>,
     pub file_size: Option<u64>,
     pub message: Option<String>,
+    /// Bytes written so far; the progress measure of "liveProgress" events
+    pub downloaded_bytes: Option<u64>,
+    pub elapsed_secs: Option<u64>,
+}
+
+/// Coarse queue state transitions for list views (`watch_queue`).
+/// Per-tick progress stays on `download-event`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum QueueEventKind {
+    /// Full queue, sent once on subscribe and again if the subscriber fell behind
+    Snapshot,
+    Added,
+    Started,
+    /// Crossed 25/50/75%
+    Progress,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueEvent {
+    pub kind: QueueEventKind,
+    pub task_id: Option<u64>,
+    pub percent: Option<u32>,
+    pub message: Option<String>,
+    pub tasks: Option<Vec<DownloadTaskInfo>>,
+}
+
+impl QueueEvent {
+    pub fn task(kind: QueueEventKind, task_id: u64) -> Self {
+        Self {
+            kind,
+            task_id: Some(task_id),
+            percent: None,
+            message: None,
+            tasks: None,
+        }
+    }
+
+    pub fn snapshot(tasks: Vec<DownloadTaskInfo>) -> Self {
+        Self {
+            kind: QueueEventKind::Snapshot,
+            task_id: None,
+            percent: None,
+            message: None,
+            tasks: Some(tasks),
+        }
+    }
 }
 
 // === Install ===
@@ -189,6 +543,8 @@ pub struct DependencyStatus {
     pub ytdlp_version: Option<String>,
     pub ffmpeg_installed: bool,
     pub ffmpeg_version: Option<String>,
+    /// Version set by `install_ffmpeg_version`; None follows the latest build
+    pub ffmpeg_pinned: Option<String>,
     /// Diagnostic info when ytdlp check fails (path tried, error reason)
     pub ytdlp_debug: Option<String>,
 }
@@ -207,6 +563,60 @@ pub struct HistoryItem {
     pub file_path: String,
     pub file_size: Option<u64>,
     pub downloaded_at: i64,
+    /// Container/codec the file was re-encoded to (`--recode-video`), if any
+    pub recode_target: Option<String>,
+    /// Global `maxHeight` cap applied to the format selector, if any
+    pub max_height: Option<u32>,
+    /// Media duration in seconds; None until probed (see `backfill_durations`)
+    pub duration_secs: Option<u64>,
+    /// Download task that re-downloaded this item with different options, once it completed
+    pub superseded_by: Option<u64>,
+    /// Chapter markers were embedded (`embedChapters` on and the file has chapters)
+    pub chapters_embedded: bool,
+    /// Container the file was losslessly remuxed into instead of recoded (`autoContainer`)
+    pub remux_target: Option<String>,
+    /// None until backfilled by `normalize_history`
+    pub uploader: Option<String>,
+    /// File container (extension), e.g. "mp4"
+    pub container: Option<String>,
+    pub tags: Vec<String>,
+    /// Audio language of the download: the selected track's tag when yt-dlp reported
+    /// one, else the requested `audio_lang`
+    pub audio_lang: Option<String>,
+    /// "verified" or "suspect" when `verify_downloads` checked the file
+    pub verify_status: Option<String>,
+    /// ffprobe error or duration mismatch behind a "suspect" status
+    pub verify_error: Option<String>,
+    /// Folder each kind of output file went to (None for rows before v18)
+    pub destinations: Option<OutputPathsByType>,
+    /// Preset the download was queued with
+    pub preset: Option<String>,
+    /// Video codec yt-dlp downloaded (e.g. "avc1.640028"); None for audio-only
+    /// files and rows before v27
+    pub vcodec: Option<String>,
+    /// Starred by the user, separate from tags (`toggle_favorite`)
+    pub is_favorite: bool,
+    /// Tags the user added (`bulk_tag`); `tags` are the site's own
+    pub user_tags: Vec<String>,
+}
+
+/// What `get_download_history` orders by. Only these columns can be sorted on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum HistorySortKey {
+    #[default]
+    Date,
+    Size,
+    Duration,
+    Title,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum SortDir {
+    Asc,
+    #[default]
+    Desc,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
@@ -216,188 +626,1496 @@ pub struct HistoryResult {
     pub total_count: u64,
     pub page: u32,
     pub page_size: u32,
+    /// Sort the page was read with
+    pub sort_by: HistorySortKey,
+    pub sort_dir: SortDir,
 }
 
-// === Queue Pagination ===
+/// What `apply_rename` would do with one history item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum RenameStatus {
+    /// Can be renamed
+    Ready,
+    /// Already named as the template says
+    Unchanged,
+    /// The new name is taken on disk or by another item
+    Collision,
+    /// The file isn't there any more
+    Missing,
+    /// The template can't be filled in for this item (see `detail`)
+    Unresolved,
+}
 
-#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+/// One item of a `preview_rename` plan. Pass the ones to rename, unchanged,
+/// to `apply_rename`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
-pub struct QueueResult {
-    pub items: Vec<DownloadTaskInfo>,
-    pub total_count: u64,
-    pub page: u32,
-    pub page_size: u32,
-    pub active_count: u64,
-    pub pending_count: u64,
-    pub completed_count: u64,
-    pub failed_count: u64,
-    pub cancelled_count: u64,
+pub struct RenamePlan {
+    pub history_id: u64,
+    pub title: String,
+    pub from: String,
+    /// New path; None when it couldn't be worked out
+    pub to: Option<String>,
+    pub status: RenameStatus,
+    pub detail: Option<String>,
 }
 
+/// min/avg/max wall time of repeated runs, in milliseconds.
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
-pub struct QueueSummary {
-    pub active_items: Vec<DownloadTaskInfo>,
-    pub recent_completed: Vec<DownloadTaskInfo>,
-    pub active_count: u64,
-    pub pending_count: u64,
-    pub completed_count: u64,
-    pub total_count: u64,
+pub struct LatencyStats {
+    pub runs: u32,
+    pub min_ms: u64,
+    pub avg_ms: u64,
+    pub max_ms: u64,
 }
 
-// === Duplicate Check ===
-
+/// yt-dlp process start-up cost, from `benchmark_ytdlp_spawn`.
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
-pub struct DuplicateCheckResult {
-    pub in_history: bool,
-    pub in_queue: bool,
-    pub history_item: Option<HistoryItem>,
-    pub file_exists: bool,
+pub struct SpawnBench {
+    pub ytdlp_path: String,
+    pub version: Option<String>,
+    /// `yt-dlp --version`: process spawn + Python start-up
+    pub spawn: Option<LatencyStats>,
+    /// `--simulate` on a short public video: start-up plus extraction (network)
+    pub simulate: Option<LatencyStats>,
+    /// Why the `--simulate` runs stopped, e.g. when offline
+    pub simulate_error: Option<String>,
+    pub measured_at: i64,
 }
 
-// === Settings ===
+/// Result of checking a Netscape-format cookies file (`validate_cookies_file`).
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CookiesValidation {
+    pub path: String,
+    /// Parsed without errors and contains at least one cookie
+    pub valid: bool,
+    /// Starts with the "# Netscape HTTP Cookie File" header
+    pub has_header: bool,
+    pub total: u32,
+    /// Cookies without an expiry (expire when the browser closes)
+    pub session: u32,
+    pub expired: u32,
+    pub domains: Vec<CookieDomain>,
+    /// First expired entries, for display
+    pub expired_cookies: Vec<ExpiredCookie>,
+    pub errors: Vec<CookieLineError>,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
-pub struct AppSettings {
-    pub download_path: String,
-    pub default_quality: String,
-    pub max_concurrent: u32,
-    pub filename_template: String,
-    pub cookie_browser: Option<String>,
-    pub auto_update_ytdlp: bool,
-    pub use_advanced_template: bool,
-    pub template_uploader_folder: bool,
-    pub template_upload_date: bool,
-    pub template_video_id: bool,
-    pub language: Option<String>,
-    pub theme: Option<String>,
-    pub minimize_to_tray: Option<bool>,
-    /// Dependency resolution mode: "external" (app-managed) or "system" (system PATH only)
-    pub dep_mode: String,
-    /// Whether the initial setup wizard has been completed
-    pub setup_completed: bool,
+pub struct CookieDomain {
+    pub domain: String,
+    pub count: u32,
+    pub expired: u32,
 }
 
-impl Default for AppSettings {
-    fn default() -> Self {
-        Self {
-            download_path: String::new(),
-            default_quality: "1080p".to_string(),
-            max_concurrent: 3,
-            filename_template: "%(title)s.%(ext)s".to_string(),
-            cookie_browser: None,
-            auto_update_ytdlp: true,
-            use_advanced_template: false,
-            template_uploader_folder: false,
-            template_upload_date: false,
-            template_video_id: false,
-            language: None,
-            theme: None,
-            minimize_to_tray: None,
-            dep_mode: "external".to_string(),
-            setup_completed: false,
-        }
-    }
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpiredCookie {
+    pub domain: String,
+    pub name: String,
+    pub expires_at: i64,
 }
 
-// === Progress ===
+/// A malformed line in a cookies file (1-based line number).
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CookieLineError {
+    pub line: u32,
+    pub message: String,
+}
 
-#[derive(Debug, Clone)]
-pub struct ProgressInfo {
-    pub percent: f32,
-    pub speed: Option<String>,
-    pub eta: Option<String>,
+/// An app-managed yt-dlp/ffmpeg process that no running app instance owns.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanProcess {
+    pub pid: u32,
+    pub name: String,
+    pub exe: String,
+    /// Terminated by `reap_orphaned_processes`
+    pub killed: bool,
 }
 
-// === Dependency Install ===
+/// Sleep/wake handling state, reported by `get_power_status`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerStatus {
+    /// How wake-ups are detected ("clock-gap": wall clock jumped between timer ticks)
+    pub wake_detection: String,
+    pub last_wake_at: Option<i64>,
+    pub last_sleep_secs: Option<u64>,
+    /// Running downloads stopped and re-queued after the last wake
+    pub requeued_on_last_wake: u32,
+}
 
-#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+/// One problem found by `validate_settings`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
-pub struct DepInstallEvent {
-    pub dep_name: String,
-    pub stage: DepInstallStage,
-    pub percent: f32,
-    pub bytes_downloaded: u64,
-    pub bytes_total: Option<u64>,
-    pub message: Option<String>,
+pub struct SettingIssue {
+    /// camelCase settings key; nested values as `outputPathsByType.video`
+    pub field: String,
+    pub message: String,
+    /// Reported but doesn't keep the settings from being saved
+    pub warning: bool,
 }
 
+/// Battery reading and the low-battery hold (`get_battery_state`).
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
-pub enum DepInstallStage {
-    Downloading,
-    Verifying,
-    Extracting,
-    Completing,
-    Failed,
+#[serde(rename_all = "camelCase")]
+pub struct BatteryState {
+    /// False when the charge can't be read (no battery, or unsupported system)
+    pub available: bool,
+    pub percent: Option<u32>,
+    pub on_battery: Option<bool>,
+    /// `pauseBelowBatteryPercent`
+    pub threshold: Option<u32>,
+    /// New downloads are currently held for low battery
+    pub paused: bool,
 }
 
+/// CPU load sampling and the busy-system hold (`get_cpu_load_state`).
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
-pub struct FullDependencyStatus {
-    pub ytdlp: DepInfo,
-    pub ffmpeg: DepInfo,
-    pub deno: DepInfo,
+pub struct CpuLoadState {
+    /// Total CPU usage at the last sample; None before the first one or while off
+    pub percent: Option<f32>,
+    /// `pauseAboveCpuPercent`
+    pub threshold: Option<u32>,
+    pub sample_secs: u32,
+    pub sustain_secs: u32,
+    /// New downloads are currently held for CPU load
+    pub paused: bool,
+    /// How long usage has been on the other side of the threshold, counting
+    /// toward `sustain_secs`; None when the decision is settled
+    pub pending_secs: Option<u32>,
 }
 
+/// Overrides for `redownload_with_options`; unset fields keep the history row's values.
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
-pub struct DepInfo {
-    pub installed: bool,
-    pub version: Option<String>,
-    pub source: DepSource,
-    pub path: Option<String>,
+pub struct RedownloadOptions {
+    pub format_id: Option<String>,
+    pub quality_label: Option<String>,
+    /// Defaults to the folder of the existing file
+    pub output_dir: Option<String>,
+    pub keep_separate_streams: bool,
+    /// Overwrite the existing file, or delete it once the new download succeeds
+    /// if the new file ends up under a different name
+    pub replace_existing: bool,
 }
 
+/// A history item that likely holds the same content (`find_similar`).
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
-pub enum DepSource {
-    AppManaged,
-    SystemPath,
-    NotFound,
+#[serde(rename_all = "camelCase")]
+pub struct SimilarItem {
+    pub item: HistoryItem,
+    /// 0-1; title overlap, weighted with duration agreement when both are known
+    pub confidence: f32,
+    /// None when either duration is unknown
+    pub duration_diff_secs: Option<u64>,
 }
 
+/// Per-row progress of a `normalize_history` batch.
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
-pub struct DepUpdateInfo {
-    pub current_version: Option<String>,
-    pub latest_version: String,
-    pub update_available: bool,
+pub struct NormalizeProgress {
+    pub history_id: u64,
+    /// Rows handled so far in this batch, including this one
+    pub processed: u32,
+    pub batch_size: u32,
+    /// At least one missing field was filled in
+    pub updated: bool,
 }
 
-// === Logs ===
+/// Outcome of one `normalize_history` batch.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizeSummary {
+    pub scanned: u32,
+    pub updated: u32,
+    /// Pass back as `before_id` for the next batch; None when done
+    pub next_before_id: Option<u64>,
+}
 
+/// Outcome of one `backfill_durations` batch.
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
-pub struct LogEntry {
-    pub id: i64,
-    pub timestamp: i64,
-    pub level: String,
-    pub category: String,
-    pub message: String,
-    pub details: Option<String>,
+pub struct DurationBackfill {
+    /// Rows without a duration looked at in this batch
+    pub scanned: u32,
+    /// Rows whose duration was probed and stored
+    pub updated: u32,
+    /// Pass back as `before_id` for the next batch; None when done
+    pub next_before_id: Option<u64>,
 }
 
+// === Watched Sources ===
+
+/// A channel or playlist polled for new uploads.
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
-pub struct LogQueryResult {
-    pub items: Vec<LogEntry>,
-    pub total_count: u64,
-    pub page: u32,
-    pub page_size: u32,
+pub struct WatchedSource {
+    pub id: u64,
+    pub url: String,
+    /// Playlist/channel title from the last successful check
+    pub title: Option<String>,
+    pub interval_secs: u64,
+    pub created_at: i64,
+    /// None until the first check (which only records existing entries)
+    pub last_checked_at: Option<i64>,
+    /// Downloads queued by the last check
+    pub last_new_count: u32,
+    pub last_error: Option<String>,
 }
 
+/// Everything known about one download, for the history detail view.
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
-pub struct LogStats {
-    pub total_count: u64,
-    pub error_count: u64,
-    pub warn_count: u64,
-    pub info_count: u64,
+pub struct HistoryDetail {
+    pub item: HistoryItem,
+    pub file_exists: bool,
+    /// Path of the `.info.json` sidecar when one exists
+    pub info_json_path: Option<String>,
+    /// The sidecar as JSON text, with long arrays/strings summarized
+    pub info_json: Option<String>,
+    /// Queue task that produced this row, while it is still in the queue table
+    pub task_id: Option<u64>,
+    /// Log entries mentioning the task or the video, oldest first
+    pub logs: Vec<LogEntry>,
 }
 
-#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+// === Storage ===
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum CacheKind {
+    /// Partial downloads in the temp dir
+    Partial,
+    /// Per-task codec sidecars yt-dlp prints while downloading
+    Codecs,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheKindStats {
+    pub kind: CacheKind,
+    pub entries: u32,
+    pub total_bytes: u64,
+    /// Entries no running or unfinished download uses (removed by `prune_cache`)
+    pub orphaned: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStats {
+    pub kinds: Vec<CacheKindStats>,
+}
+
+/// State of the idle-gated DB cleanup (`schedule_idle_cleanup`).
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupStatus {
+    /// Waiting for the queue to go idle
+    pub due: bool,
+    pub idle_secs_required: u32,
+    pub active_downloads: u32,
+    /// When the pending run was first held back by active downloads
+    pub deferred_since: Option<i64>,
+    pub last_run_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheClearResult {
+    pub removed: u32,
+    pub freed_bytes: u64,
+}
+
+/// A yt-dlp partial found by `list_leftover_files`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LeftoverFile {
+    /// Also the id passed to `clean_leftover_files`
+    pub path: String,
+    pub size: u64,
+    pub modified_at: Option<i64>,
+    /// Looks like it belongs to a pending, downloading or paused task; never removed
+    pub in_use: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LeftoverCleanup {
+    pub removed: u32,
+    pub reclaimed_bytes: u64,
+    /// Paths not removed: in use, not a partial, outside the download folders, or already gone
+    pub skipped: Vec<String>,
+}
+
+/// Space taken by this app's downloads in one directory.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DirUsage {
+    pub path: String,
+    pub file_count: u32,
+    pub total_bytes: u64,
+    /// Files counted whose size is unknown (not recorded, and not stat-ed or gone)
+    pub unknown_size_count: u32,
+    /// The directory no longer exists
+    pub missing: bool,
+}
+
+// === Queue Pagination ===
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueResult {
+    pub items: Vec<DownloadTaskInfo>,
+    pub total_count: u64,
+    pub page: u32,
+    pub page_size: u32,
+    pub active_count: u64,
+    pub pending_count: u64,
+    pub completed_count: u64,
+    pub failed_count: u64,
+    pub cancelled_count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueSummary {
+    pub active_items: Vec<DownloadTaskInfo>,
+    pub recent_completed: Vec<DownloadTaskInfo>,
+    pub active_count: u64,
+    pub pending_count: u64,
+    pub completed_count: u64,
+    pub total_count: u64,
+}
+
+/// Count and a bounded sample of tasks for one status.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueGroup {
+    pub status: DownloadStatus,
+    pub count: u64,
+    pub items: Vec<DownloadTaskInfo>,
+}
+
+/// Queue grouped by status; every status is present, even with a zero count.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueGrouped {
+    pub groups: Vec<QueueGroup>,
+    pub total_count: u64,
+}
+
+/// Criteria for cancelling a subset of active tasks. Set conditions are ANDed.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelFilter {
+    /// Matches the URL host and its subdomains (e.g. "youtube.com" matches "www.youtube.com")
+    pub host: Option<String>,
+    pub playlist_id: Option<String>,
+    /// Matches tasks carrying this user tag
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelMatchResult {
+    pub count: u32,
+    pub task_ids: Vec<u64>,
+}
+
+/// What the next pending task is waiting for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum NextStartReason {
+    /// A slot is free; it starts on the next queue pass
+    Ready,
+    /// A slot is free but the start delay has not passed
+    StartDelay,
+    /// Every slot is busy
+    WaitingForSlot,
+    /// New starts are paused by hand or while waiting to quit
+    Paused,
+    /// Held on battery below `pauseBelowBatteryPercent`
+    BatteryLow,
+    /// Held while the CPU is above `pauseAboveCpuPercent`
+    CpuBusy,
+    /// `sessionDownloadLimit` reached; waits for a higher limit or a restart
+    SessionLimit,
+    /// `dailyDownloadLimit` reached; the ETA is the reset at local midnight
+    DailyLimit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum NextStartConfidence {
+    /// Known from the slot count and start delay
+    High,
+    /// Based on yt-dlp's ETA of every running task
+    Medium,
+    /// Some or all running tasks report no ETA
+    Low,
+}
+
+/// Estimate from `get_next_start`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NextStart {
+    pub task_id: u64,
+    pub title: String,
+    pub reason: NextStartReason,
+    /// Seconds until the task starts; None when no running task reports an ETA
+    pub eta_secs: Option<u64>,
+    /// Unix time of `eta_secs`
+    pub starts_at: Option<i64>,
+    pub confidence: NextStartConfidence,
+}
+
+/// Outcome of `reset_manager_slots`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SlotReset {
+    /// Slots the manager counted as taken before the reset
+    pub previous: u32,
+    /// Tasks actually downloading according to the DB
+    pub actual: u32,
+    pub max_concurrent: u32,
+    pub corrected: bool,
+}
+
+/// Outcome of one queue watchdog check (`check_queue_stall`).
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueStallCheck {
+    pub pending: u32,
+    /// Tasks downloading according to the DB
+    pub running: u32,
+    /// Pending tasks were waiting with free slots and nothing running
+    pub stalled: bool,
+    /// Tasks the watchdog started to get the queue moving again
+    pub started: u32,
+}
+
+// === Duplicate Check ===
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateCheckResult {
+    pub in_history: bool,
+    pub in_queue: bool,
+    pub history_item: Option<HistoryItem>,
+    pub file_exists: bool,
+    /// A file already sits where this download would be saved, whether or not
+    /// history knows about it. Only checked when a URL is given.
+    pub file_on_disk: bool,
+    pub disk_path: Option<String>,
+    /// Free name for saving alongside it, relative to the download folder
+    pub rename_to: Option<String>,
+    /// The video id is on the blocklist; queueing it will be refused
+    pub blocked: bool,
+}
+
+/// One entry of the video blocklist (`block_video`).
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockedVideo {
+    pub video_id: String,
+    pub blocked_at: i64,
+}
+
+// === Effective Options ===
+
+/// Per-task values that take precedence over global settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskOverrides {
+    pub quality: Option<String>,
+    pub output_dir: Option<String>,
+    pub filename_template: Option<String>,
+    pub cookie_browser: Option<String>,
+    pub max_filesize: Option<String>,
+}
+
+/// Where an effective option value came from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum OptionSource {
+    Default,
+    Settings,
+    Task,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveOption {
+    pub key: String,
+    pub value: Option<String>,
+    pub source: OptionSource,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveOptions {
+    pub url: String,
+    pub options: Vec<EffectiveOption>,
+}
+
+// === Settings ===
+
+/// Download folder per kind of output file. Unset kinds use the download path.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputPathsByType {
+    pub video: Option<String>,
+    /// Audio-only downloads
+    pub audio: Option<String>,
+    pub subtitle: Option<String>,
+    pub thumbnail: Option<String>,
+}
+
+/// How log lines past a burst are handled (`modules::log_burst`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum LogBurstPolicy {
+    /// Write every line
+    Full,
+    /// Keep one in ten lines past the threshold
+    Sampled,
+    /// Drop lines past the threshold and log how many were dropped
+    Summarized,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AppSettings {
+    pub download_path: String,
+    pub default_quality: String,
+    pub max_concurrent: u32,
+    pub filename_template: String,
+    /// `--cookies-from-browser` source: `BROWSER[+KEYRING][:PROFILE][::CONTAINER]`,
+    /// e.g. "chrome:Profile 2" (see `list_browser_profiles`)
+    pub cookie_browser: Option<String>,
+    pub auto_update_ytdlp: bool,
+    pub use_advanced_template: bool,
+    pub template_uploader_folder: bool,
+    pub template_upload_date: bool,
+    pub template_video_id: bool,
+    pub language: Option<String>,
+    pub theme: Option<String>,
+    pub minimize_to_tray: Option<bool>,
+    /// Dependency resolution mode: "external" (app-managed) or "system" (system PATH only)
+    pub dep_mode: String,
+    /// Whether the initial setup wizard has been completed
+    pub setup_completed: bool,
+    /// Resolve yt-dlp from system PATH before the app-managed copy (e.g. pip installs)
+    pub prefer_system_binary: bool,
+    /// Write partial files to a temp dir (`--paths temp:`) and move them on completion
+    pub use_temp_dir: bool,
+    /// Custom temp dir for partial files (None = app cache dir)
+    pub temp_dir: Option<String>,
+    /// Reduce concurrency and bandwidth while the window is hidden to tray
+    pub background_throttle: bool,
+    /// Max concurrent downloads while throttled
+    pub throttle_max_concurrent: u32,
+    /// Rate limit for downloads started while throttled, and for every task but the
+    /// focused one while a task has focus (yt-dlp `--limit-rate`, e.g. "2M")
+    pub throttle_rate_limit: Option<String>,
+    /// Pass `--ignore-config` so yt-dlp config files on the system are not applied
+    pub ignore_config: bool,
+    /// Re-encode downloads to this format via `--recode-video` (requires ffmpeg; slow and lossy)
+    pub recode_video: Option<String>,
+    /// Custom ffmpeg args for the re-encode step (`--postprocessor-args VideoConvertor:...`)
+    pub recode_ffmpeg_args: Option<String>,
+    /// Minimum seconds between starting consecutive downloads (0 = no delay)
+    pub start_delay_secs: u32,
+    /// Cap video height for downloads without an explicit height in their format (e.g. 1080)
+    pub max_height: Option<u32>,
+    /// Store logs, the download DB and binaries here instead of the app data dir (applied on restart)
+    pub data_dir_override: Option<String>,
+    /// Treat watch URLs carrying a `list=` parameter as a single video (yt-dlp `--no-playlist`)
+    pub single_video_default: bool,
+    /// User-registered yt-dlp binaries, label -> absolute path
+    pub ytdlp_binaries: BTreeMap<String, String>,
+    /// Label from `ytdlp_binaries` to use instead of automatic resolution
+    pub active_ytdlp: Option<String>,
+    /// Write source URL and metadata to extended file attributes (`--xattrs`). Needs a filesystem with xattr support (APFS/HFS+, ext4, btrfs, NTFS); elsewhere yt-dlp only warns
+    pub write_xattrs: bool,
+    /// Restart downloads that were running across a system sleep, resuming partial files
+    pub requeue_after_sleep: bool,
+    /// Embed chapter markers into the output (`--embed-chapters`, requires ffmpeg)
+    pub embed_chapters: bool,
+    /// Write chapters to a `<name>.chapters.json` sidecar next to the output
+    pub write_chapters: bool,
+    /// With a recode target set, remux losslessly instead when the source codecs fit that container
+    pub auto_container: bool,
+    /// Default per-download size cap (`--max-filesize`, e.g. "2G"); tasks may override it
+    pub max_filesize: Option<String>,
+    /// Check finished files with ffprobe (valid container, expected duration)
+    pub verify_downloads: bool,
+    /// Re-download once when verification marks a file suspect
+    pub retry_suspect_downloads: bool,
+    /// Per-type download folders (video/audio/subtitle/thumbnail)
+    pub output_paths_by_type: OutputPathsByType,
+    /// What happens to DEBUG/INFO lines past a log burst; WARN/ERROR are always kept
+    pub log_burst_policy: LogBurstPolicy,
+    /// User-Agent, referer and extra headers sent with every download; tasks can override them
+    pub default_headers: RequestHeaders,
+    /// Check on startup that GitHub and a sample site are reachable (opt-in)
+    pub startup_network_check: bool,
+    /// Largest page of log entries served to the log viewer (hard ceiling 2000)
+    pub log_max_page_size: u32,
+    /// Automatic retries of a download that failed for a transient reason (network, rate limit); tasks can override it
+    pub max_retries: u32,
+    /// Wait before the first automatic retry, doubled for each further one
+    pub retry_backoff_secs: u32,
+    /// Seconds the queue must sit idle before VACUUM and large log trims run
+    pub cleanup_idle_secs: u32,
+    /// How often the queue watchdog looks for pending tasks that aren't starting (0 = off)
+    pub queue_watchdog_secs: u32,
+    /// Most tasks started per app run; None = unlimited
+    pub session_download_limit: Option<u32>,
+    /// Most tasks started per local calendar day; None = unlimited
+    pub daily_download_limit: Option<u32>,
+    /// Hosts downloads are limited to (`*.example.com` covers subdomains); empty allows all
+    pub host_allowlist: Vec<String>,
+    /// Hosts downloads are refused from, checked before the allowlist
+    pub host_denylist: Vec<String>,
+    /// Fingerprint history titles so `find_similar` can flag likely duplicates (looking up a new URL probes the site)
+    pub similarity_fingerprints: bool,
+    /// yt-dlp binaries kept for rollback when it is updated or re-downloaded (0 keeps none)
+    pub binary_backup_count: u32,
+    /// Preferred video codecs, best first, as yt-dlp names them (e.g. "av01", "h264")
+    pub codec_preference: Vec<String>,
+    /// Hold new downloads while on battery below this charge (1-100); None = never
+    pub pause_below_battery_percent: Option<u32>,
+    /// Hold new downloads while total CPU usage stays above this (1-100); None = never
+    pub pause_above_cpu_percent: Option<u32>,
+    /// Seconds between CPU usage samples
+    pub cpu_sample_secs: u32,
+    /// Seconds CPU usage must stay above (or back below) the threshold before downloads are held (or resumed)
+    pub cpu_sustain_secs: u32,
+    /// Monthly download budget (e.g. "50G"); a warning is raised as it nears. None = no cap
+    pub monthly_data_cap: Option<String>,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            download_path: String::new(),
+            default_quality: "1080p".to_string(),
+            max_concurrent: 3,
+            filename_template: "%(title)s.%(ext)s".to_string(),
+            cookie_browser: None,
+            auto_update_ytdlp: true,
+            use_advanced_template: false,
+            template_uploader_folder: false,
+            template_upload_date: false,
+            template_video_id: false,
+            language: None,
+            theme: None,
+            minimize_to_tray: None,
+            dep_mode: "external".to_string(),
+            setup_completed: false,
+            prefer_system_binary: false,
+            use_temp_dir: false,
+            temp_dir: None,
+            background_throttle: false,
+            throttle_max_concurrent: 1,
+            throttle_rate_limit: Some("2M".to_string()),
+            ignore_config: false,
+            recode_video: None,
+            recode_ffmpeg_args: None,
+            start_delay_secs: 0,
+            max_height: None,
+            data_dir_override: None,
+            single_video_default: true,
+            ytdlp_binaries: BTreeMap::new(),
+            active_ytdlp: None,
+            write_xattrs: false,
+            requeue_after_sleep: true,
+            embed_chapters: false,
+            write_chapters: false,
+            auto_container: false,
+            max_filesize: None,
+            verify_downloads: false,
+            retry_suspect_downloads: false,
+            output_paths_by_type: OutputPathsByType::default(),
+            log_burst_policy: LogBurstPolicy::Summarized,
+            default_headers: RequestHeaders::default(),
+            startup_network_check: false,
+            log_max_page_size: 200,
+            max_retries: 0,
+            retry_backoff_secs: 30,
+            cleanup_idle_secs: 300,
+            queue_watchdog_secs: 30,
+            session_download_limit: None,
+            daily_download_limit: None,
+            host_allowlist: Vec::new(),
+            host_denylist: Vec::new(),
+            similarity_fingerprints: false,
+            binary_backup_count: 1,
+            codec_preference: Vec::new(),
+            pause_below_battery_percent: None,
+            pause_above_cpu_percent: None,
+            cpu_sample_secs: 5,
+            cpu_sustain_secs: 60,
+            monthly_data_cap: None,
+        }
+    }
+}
+
+// === Progress ===
+
+#[derive(Debug, Clone)]
+pub struct ProgressInfo {
+    /// 0 when `indeterminate`
+    pub percent: f32,
+    /// Total size unknown (live streams); progress is `downloaded_bytes` over `elapsed_secs`
+    pub indeterminate: bool,
+    pub speed: Option<String>,
+    /// `speed` converted to bytes per second
+    pub speed_bytes: Option<u64>,
+    pub eta: Option<String>,
+    pub downloaded_bytes: Option<u64>,
+    pub elapsed_secs: Option<u64>,
+}
+
+/// Sum of the latest speed readings across downloading tasks.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TotalSpeed {
+    pub bytes_per_sec: u64,
+    /// Tasks with a recent speed reading (stale ones count as 0)
+    pub reporting_tasks: u32,
+}
+
+/// What a running task is doing right now.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum TaskStage {
+    /// Process launched, no progress line yet (extracting info)
+    Starting,
+    Downloading,
+    /// Live stream without a known total size
+    Live,
+    /// Merging, converting or embedding after the download
+    Processing,
+}
+
+/// Latest progress of a running task (`get_active_progress`).
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskProgress {
+    pub task_id: u64,
+    pub stage: TaskStage,
+    /// None until the first progress line, and for live streams
+    pub percent: Option<f32>,
+    pub speed: Option<String>,
+    pub eta: Option<String>,
+    pub downloaded_bytes: Option<u64>,
+    /// Unix seconds of the last update
+    pub updated_at: i64,
+}
+
+/// A running task whose download hasn't advanced for a while (`get_stalled_tasks`).
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskStall {
+    pub task_id: u64,
+    pub stage: TaskStage,
+    /// Seconds since downloaded bytes or percent last changed (or since start)
+    pub stalled_secs: u64,
+    pub percent: Option<f32>,
+    pub downloaded_bytes: Option<u64>,
+}
+
+/// A yt-dlp binary kept for rollback (`list_ytdlp_backups`).
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct YtdlpBackup {
+    /// 1 = the binary replaced most recently; pass to `rollback_ytdlp_update`
+    pub index: u32,
+    /// None when the copy no longer runs
+    pub version: Option<String>,
+    pub size: u64,
+    /// Unix seconds
+    pub modified_at: Option<i64>,
+    pub path: String,
+}
+
+/// Header of a full backup archive (`export_full_backup`).
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FullBackupManifest {
+    pub format_version: u32,
+    pub app_version: String,
+    /// ytdlp.db schema the archive was taken at; older ones are migrated on import
+    pub schema_version: u32,
+    /// Unix seconds
+    pub created_at: i64,
+    /// Secrets in settings.json are masked; such settings are not restored
+    pub settings_redacted: bool,
+}
+
+/// How `import_full_backup` treats data already here.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum BackupImportMode {
+    /// Add what isn't here yet; local rows win on conflict
+    Merge,
+    /// Drop the queue, history, watches, presets and logs and take the backup's
+    Replace,
+}
+
+/// Rows brought in by `import_full_backup`, per kind.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupRestoreCounts {
+    pub history: u32,
+    pub queue: u32,
+    pub watched_sources: u32,
+    pub presets: u32,
+    pub logs: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FullBackupImport {
+    pub manifest: FullBackupManifest,
+    pub counts: BackupRestoreCounts,
+    pub settings_restored: bool,
+}
+
+// === Dependency Install ===
+
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct DepInstallEvent {
+    pub dep_name: String,
+    pub stage: DepInstallStage,
+    pub percent: f32,
+    pub bytes_downloaded: u64,
+    pub bytes_total: Option<u64>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub enum DepInstallStage {
+    Downloading,
+    Verifying,
+    Extracting,
+    Completing,
+    Failed,
+}
+
+/// Why a binary failed to start (see `diagnose_ytdlp_binary`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum ExecFailureCause {
+    /// Killed by Gatekeeper / still quarantined (macOS)
+    Quarantine,
+    /// Wrong CPU architecture or OS
+    ExecFormat,
+    /// Not executable, blocked by antivirus or a noexec mount
+    Permission,
+    /// A shared library or runtime is missing
+    MissingLibrary,
+    Timeout,
+    Other,
+}
+
+/// Result of running a binary with `--version`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BinaryDiagnosis {
+    pub path: String,
+    pub runs: bool,
+    pub version: Option<String>,
+    pub cause: Option<ExecFailureCause>,
+    /// Raw exec error or stderr of the failed run
+    pub stderr: Option<String>,
+    pub hint: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FullDependencyStatus {
+    pub ytdlp: DepInfo,
+    pub ffmpeg: DepInfo,
+    pub deno: DepInfo,
+    /// Version set by `install_ffmpeg_version`; None follows the latest build
+    #[serde(default)]
+    pub ffmpeg_pinned: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DepInfo {
+    pub installed: bool,
+    pub version: Option<String>,
+    pub source: DepSource,
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub enum DepSource {
+    AppManaged,
+    SystemPath,
+    /// Chosen from the user's yt-dlp binary registry (`activeYtdlp`)
+    Registered,
+    NotFound,
+}
+
+/// A yt-dlp binary from the user's registry, probed with `--version`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct YtdlpBinary {
+    pub label: String,
+    pub path: String,
+    pub version: Option<String>,
+    /// Why the binary failed to run, when `version` is None
+    pub error: Option<String>,
+    pub active: bool,
+}
+
+/// The yt-dlp binary that downloads will actually use, and why.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BinaryInfo {
+    pub path: Option<String>,
+    pub version: Option<String>,
+    pub source: DepSource,
+    pub prefer_system: bool,
+}
+
+/// App and runtime versions for support, with the yt-dlp range this build was tested against.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AppInfo {
+    pub app_version: String,
+    pub tauri_version: String,
+    /// `<arch>-<os>` the binary was built for
+    pub target: String,
+    pub debug_build: bool,
+    pub ytdlp_tested_min: String,
+    pub ytdlp_tested_max: String,
+    /// Version of the yt-dlp downloads use, if it runs
+    pub ytdlp_version: Option<String>,
+    /// None when the installed version is unknown or unparseable
+    pub ytdlp_in_tested_range: Option<bool>,
+}
+
+/// Extractors gained and lost between two locally available yt-dlp versions.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractorDiff {
+    pub version_a: String,
+    pub version_b: String,
+    /// In version_b but not version_a
+    pub added: Vec<String>,
+    /// In version_a but not version_b
+    pub removed: Vec<String>,
+}
+
+/// Result of trying to create and delete a file in a data directory.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WritableCheck {
+    pub path: String,
+    pub writable: bool,
+    /// OS error message when not writable
+    pub error: Option<String>,
+    /// `std::io::ErrorKind` name, e.g. "PermissionDenied" or "ReadOnlyFilesystem"
+    pub error_kind: Option<String>,
+}
+
+/// Outcome of copying app data from a location earlier builds used.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacyMigration {
+    /// Legacy directory that held data, if one was found
+    pub source: Option<String>,
+    /// Items copied into the current data dir, e.g. "ytdlp.db", "bin"
+    pub migrated: Vec<String>,
+    /// Legacy data was found but the current data dir was already in use
+    pub kept_current: bool,
+    /// Why the copy failed; nothing is kept and the next start retries
+    pub error: Option<String>,
+    /// Unix timestamp of the check
+    pub ran_at: i64,
+}
+
+// === Health Check ===
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum HealthStatus {
+    Ok,
+    Warn,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthCheck {
+    pub name: String,
+    pub status: HealthStatus,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthReport {
+    pub overall: HealthStatus,
+    pub checks: Vec<HealthCheck>,
+    pub generated_at: i64,
+}
+
+/// New position of a task renumbered by `renumber_playlist_tasks`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistIndexChange {
+    pub task_id: u64,
+    pub title: String,
+    pub old_index: Option<u32>,
+    pub new_index: u32,
+}
+
+/// One host probed by `test_network`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkCheck {
+    /// Stable key ("github", "githubApi", "youtube")
+    pub name: String,
+    pub url: String,
+    pub reachable: bool,
+    /// HTTP status of the answer; any status counts as reachable
+    pub status: Option<u16>,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum DrainPhase {
+    /// Downloads still running, no new ones starting
+    Waiting,
+    /// Timed out; the remaining downloads are being cancelled
+    Cancelling,
+    /// Queue empty, the app is about to exit
+    Exiting,
+}
+
+/// Payload of `queue-drain-event`, sent during `drain_and_exit`.
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueDrainEvent {
+    pub phase: DrainPhase,
+    pub active: u32,
+    pub elapsed_secs: u32,
+    pub timeout_secs: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkReport {
+    pub all_reachable: bool,
+    pub checks: Vec<NetworkCheck>,
+    pub checked_at: i64,
+}
+
+/// An option in a yt-dlp config file that overlaps with a flag the app manages.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigConflict {
+    pub file: String,
+    pub line: u32,
+    pub option: String,
+    pub value: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DepUpdateInfo {
+    pub current_version: Option<String>,
+    pub latest_version: String,
+    pub update_available: bool,
+}
+
+// === Logs ===
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEntry {
+    pub id: i64,
+    pub timestamp: i64,
+    pub level: String,
+    pub category: String,
+    pub message: String,
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LogQueryResult {
+    pub items: Vec<LogEntry>,
+    pub total_count: u64,
+    pub page: u32,
+    /// Size actually served, after clamping the requested one
+    pub page_size: u32,
+    /// Limit the request was clamped to (`log_max_page_size`, capped)
+    pub max_page_size: u32,
+}
+
+/// Byte range `[start, end)` of a search match within a log field.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LogSearchHit {
+    pub entry: LogEntry,
+    pub message_matches: Vec<MatchRange>,
+    pub details_matches: Vec<MatchRange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LogSearchResult {
+    pub items: Vec<LogSearchHit>,
+    pub total_count: u64,
+    pub page: u32,
+    pub page_size: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LogStats {
+    pub total_count: u64,
+    pub error_count: u64,
+    pub warn_count: u64,
+    pub info_count: u64,
+}
+
+/// Log entries in one time bucket of `get_log_histogram`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LogBucket {
+    /// Bucket start, ms timestamp (inclusive)
+    pub start: i64,
+    /// Bucket end, ms timestamp (exclusive)
+    pub end: i64,
+    pub total: u64,
+    pub error: u64,
+    pub warn: u64,
+    pub info: u64,
+    pub debug: u64,
+}
+
+/// Cause of a failed download (`error_kind::classify_download_error`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum DownloadErrorKind {
+    Auth,
+    Cookies,
+    AgeRestricted,
+    GeoBlocked,
+    RateLimited,
+    Unavailable,
+    FormatUnavailable,
+    DiskFull,
+    SizeLimit,
+    Encoding,
+    Postprocess,
+    Network,
+    Unknown,
+}
+
+impl DownloadErrorKind {
+    /// Value stored in the logs `classification` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DownloadErrorKind::Auth => "auth",
+            DownloadErrorKind::Cookies => "cookies",
+            DownloadErrorKind::AgeRestricted => "ageRestricted",
+            DownloadErrorKind::GeoBlocked => "geoBlocked",
+            DownloadErrorKind::RateLimited => "rateLimited",
+            DownloadErrorKind::Unavailable => "unavailable",
+            DownloadErrorKind::FormatUnavailable => "formatUnavailable",
+            DownloadErrorKind::DiskFull => "diskFull",
+            DownloadErrorKind::SizeLimit => "sizeLimit",
+            DownloadErrorKind::Encoding => "encoding",
+            DownloadErrorKind::Postprocess => "postprocess",
+            DownloadErrorKind::Network => "network",
+            DownloadErrorKind::Unknown => "unknown",
+        }
+    }
+
+    /// Inverse of `as_str`
+    pub fn parse(value: &str) -> Option<Self> {
+        const ALL: [DownloadErrorKind; 13] = [
+            DownloadErrorKind::Auth,
+            DownloadErrorKind::Cookies,
+            DownloadErrorKind::AgeRestricted,
+            DownloadErrorKind::GeoBlocked,
+            DownloadErrorKind::RateLimited,
+            DownloadErrorKind::Unavailable,
+            DownloadErrorKind::FormatUnavailable,
+            DownloadErrorKind::DiskFull,
+            DownloadErrorKind::SizeLimit,
+            DownloadErrorKind::Encoding,
+            DownloadErrorKind::Postprocess,
+            DownloadErrorKind::Network,
+            DownloadErrorKind::Unknown,
+        ];
+        ALL.into_iter().find(|k| k.as_str() == value)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorKindCount {
+    pub kind: DownloadErrorKind,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReclassifyResult {
+    /// Rows classified by this run; already classified rows are skipped
+    pub classified: u64,
+    pub by_kind: Vec<ErrorKindCount>,
+}
+
+/// One message of an `ErrorGroup`, with the `[download:N]` tag stripped.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageCount {
+    pub message: String,
+    pub count: u64,
+}
+
+/// ERROR/WARN entries sharing a level, category and failure kind.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorGroup {
+    pub level: String,
+    pub category: String,
+    /// Set for failed downloads only; other entries aren't classified
+    pub kind: Option<DownloadErrorKind>,
+    pub count: u64,
+    pub first_at: i64,
+    pub last_at: i64,
+    /// Most frequent messages first
+    pub top_messages: Vec<MessageCount>,
+    /// Tasks named in the group's messages, ascending
+    pub task_ids: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorSummary {
+    pub since: i64,
+    pub until: i64,
+    pub total: u64,
+    /// Earliest and latest entry in the window; None when it's empty
+    pub first_at: Option<i64>,
+    pub last_at: Option<i64>,
+    /// Largest group first
+    pub groups: Vec<ErrorGroup>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LogArchiveResult {
+    pub archived: u64,
+    pub path: String,
+}
+
+/// Layout of `export_task_report` output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum ReportFormat {
+    Json,
+    Text,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskReportResult {
+    pub path: String,
+    pub log_count: u32,
+}
+
+/// Server-side filter for `tail_logs`. Empty `levels` means any level.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LogTailFilter {
+    pub levels: Vec<String>,
+    pub category: Option<String>,
+    /// Case-insensitive substring of message or details
+    pub search: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
 #[serde(rename_all = "camelCase")]
 pub struct NewLogEvent {
     pub entry: LogEntry,
 }
+
+/// Completions in one clock hour, for `Throughput.buckets`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ThroughputBucket {
+    pub hour_start: i64,
+    pub completed: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Throughput {
+    pub since: i64,
+    pub until: i64,
+    pub completed: u64,
+    pub completions_per_hour: f64,
+    /// Completions that have both enqueue and start times (recorded since v24)
+    pub timed: u64,
+    /// Enqueue to first start; None when no completion in the window is timed
+    pub avg_wait_secs: Option<f64>,
+    pub max_wait_secs: Option<i64>,
+    /// First start to completion, retries included
+    pub avg_run_secs: Option<f64>,
+    /// Hourly completions, oldest first, empty hours included
+    pub buckets: Vec<ThroughputBucket>,
+}
+
+/// Which start limit holds back the queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum DownloadLimitKind {
+    Session,
+    Daily,
+}
+
+/// Starts counted against `session_download_limit` / `daily_download_limit`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadLimitUsage {
+    pub session_used: u32,
+    pub session_limit: Option<u32>,
+    pub daily_used: u32,
+    pub daily_limit: Option<u32>,
+    /// Set while a limit keeps pending tasks from starting
+    pub reached: Option<DownloadLimitKind>,
+    /// Next local midnight, when the daily count resets
+    pub daily_resets_at: i64,
+}
+
+/// Window reported by `get_bandwidth_usage`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum BandwidthPeriod {
+    Today,
+    Month,
+    AllTime,
+}
+
+/// Bytes downloaded by completed tasks, against `monthly_data_cap`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BandwidthUsage {
+    pub period: BandwidthPeriod,
+    /// Downloaded in `period`
+    pub bytes: u64,
+    /// Downloaded this local month, which the cap applies to
+    pub month_bytes: u64,
+    pub monthly_cap: Option<u64>,
+    /// Share of the cap used this month; None without a cap
+    pub cap_percent: Option<f64>,
+    /// This month is at or past the warning level of the cap
+    pub near_cap: bool,
+    /// First local day of next month, when the monthly count resets
+    pub month_resets_at: i64,
+}
+
+/// Finishing steps `rerun_postprocessing` applies to a downloaded file.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PostprocessSteps {
+    /// Title/uploader/date tags (`--embed-metadata`)
+    pub embed_metadata: bool,
+    /// Cover art (`--embed-thumbnail`)
+    pub embed_thumbnail: bool,
+    /// Subtitle tracks in `subtitle_langs` (`--embed-subs`)
+    pub embed_subs: bool,
+    /// Chapter markers (`--embed-chapters`)
+    pub embed_chapters: bool,
+    pub subtitle_langs: Vec<String>,
+}
+
+/// File type written by `export_playlist`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum PlaylistFormat {
+    M3u,
+    /// M3U with UTF-8 paths and titles (the `.m3u8` extension tells players so)
+    M3u8,
+    Pls,
+}
+
+/// A history item left out of an exported playlist.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistSkip {
+    pub history_id: u64,
+    /// None when no history item has this id
+    pub title: Option<String>,
+    pub file_path: Option<String>,
+}
+
+/// Result of `export_playlist`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistExport {
+    pub path: String,
+    pub entries: u32,
+    /// Items whose file is gone (or unknown ids), in the order given
+    pub skipped: Vec<PlaylistSkip>,
+}
+
+/// One format yt-dlp would download, from `test_format_selection`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SelectedStream {
+    /// Format id; "137+140" when video and audio are merged
+    pub format_id: String,
+    /// e.g. "1920x1080", or "audio only"
+    pub resolution: Option<String>,
+    pub vcodec: Option<String>,
+    pub acodec: Option<String>,
+}
+
+/// What yt-dlp would download for a request, from `test_format_selection`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SelectedFormat {
+    /// `--format` the download would run with, after `maxHeight`, audio language
+    /// and codec preference
+    pub selector: String,
+    /// Every format downloaded; more than one when streams are kept separate ("bv,ba")
+    pub streams: Vec<SelectedStream>,
+    /// True when this came from a recent identical probe
+    pub cached: bool,
+}
@@ -0,0 +1,234 @@
+use crate::modules::types::AppError;
+use crate::ytdlp::types::UrlFileFormat;
+
+/// Header names recognised as the URL column of a CSV export
+const CSV_URL_COLUMNS: &[&str] = &["url", "link", "webpage_url", "video_url", "href"];
+
+/// Object keys read from JSON entries, in order of preference
+const JSON_URL_KEYS: &[&str] = &["url", "webpage_url", "original_url", "link", "href"];
+
+/// A URL found in an import file and the (1-based) line or array index it came from.
+#[derive(Debug, PartialEq)]
+pub struct ImportedUrl {
+    pub line: u32,
+    pub url: String,
+}
+
+/// Guess the file format from its extension; anything unknown is read as plain lines.
+pub fn detect_format(path: &str) -> UrlFileFormat {
+    let ext = std::path::Path::new(path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase());
+    match ext.as_deref() {
+        Some("csv") => UrlFileFormat::Csv,
+        Some("json") => UrlFileFormat::Json,
+        _ => UrlFileFormat::Lines,
+    }
+}
+
+/// Extract URLs from `text`. A UTF-8 BOM and CRLF/CR line endings are handled.
+pub fn parse_urls(text: &str, format: UrlFileFormat) -> Result<Vec<ImportedUrl>, AppError> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    match format {
+        UrlFileFormat::Lines => Ok(parse_lines(&text)),
+        UrlFileFormat::Csv => Ok(parse_csv(&text)),
+        UrlFileFormat::Json => parse_json(&text),
+    }
+}
+
+/// One URL per line; blank lines and `#` / `;` comments are skipped.
+fn parse_lines(text: &str) -> Vec<ImportedUrl> {
+    text.lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                return None;
+            }
+            Some(ImportedUrl {
+                line: i as u32 + 1,
+                url: line.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// CSV with a header row naming the URL column. Without a recognised header the
+/// first field that looks like a URL is used on every row (header included).
+fn parse_csv(text: &str) -> Vec<ImportedUrl> {
+    let mut rows = text
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty());
+    let Some((first_idx, first)) = rows.next() else {
+        return Vec::new();
+    };
+    let header = split_csv_row(first);
+    let column = header
+        .iter()
+        .position(|h| CSV_URL_COLUMNS.contains(&h.trim().to_ascii_lowercase().as_str()));
+
+    let pick = |fields: &[String]| -> Option<String> {
+        match column {
+            Some(c) => fields.get(c).map(|f| f.trim().to_string()),
+            None => fields
+                .iter()
+                .map(|f| f.trim())
+                .find(|f| looks_like_url(f))
+                .map(String::from),
+        }
+    };
+
+    let data_rows: Box<dyn Iterator<Item = (usize, &str)>> = if column.is_some() {
+        Box::new(rows)
+    } else {
+        Box::new(std::iter::once((first_idx, first)).chain(rows))
+    };
+    data_rows
+        .filter_map(|(i, row)| {
+            let url = pick(&split_csv_row(row)).filter(|u| !u.is_empty())?;
+            Some(ImportedUrl {
+                line: i as u32 + 1,
+                url,
+            })
+        })
+        .collect()
+}
+
+/// Split one CSV row on commas, honouring double-quoted fields and `""` escapes.
+fn split_csv_row(row: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = row.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// JSON array of URL strings or of objects carrying a URL field (e.g. yt-dlp
+/// `--flat-playlist -J` entries). `line` is the 1-based array index.
+fn parse_json(text: &str) -> Result<Vec<ImportedUrl>, AppError> {
+    let value: serde_json::Value =
+        serde_json::from_str(text).map_err(|e| AppError::Custom(format!("Invalid JSON: {}", e)))?;
+    // Accept a bare array or an object wrapping it under "entries" / "urls"
+    let items = value
+        .as_array()
+        .or_else(|| value.get("entries").and_then(|v| v.as_array()))
+        .or_else(|| value.get("urls").and_then(|v| v.as_array()))
+        .ok_or_else(|| AppError::Custom("Expected a JSON array of URLs".to_string()))?;
+
+    Ok(items
+        .iter()
+        .enumerate()
+        .filter_map(|(i, item)| {
+            let url = match item {
+                serde_json::Value::String(s) => Some(s.as_str()),
+                serde_json::Value::Object(obj) => JSON_URL_KEYS
+                    .iter()
+                    .find_map(|k| obj.get(*k).and_then(|v| v.as_str())),
+                _ => None,
+            }?;
+            Some(ImportedUrl {
+                line: i as u32 + 1,
+                url: url.trim().to_string(),
+            })
+        })
+        .collect())
+}
+
+fn looks_like_url(field: &str) -> bool {
+    let lower = field.to_ascii_lowercase();
+    lower.starts_with("http://") || lower.starts_with("https://")
+}
+
+/// yt-dlp format selector for a quality label like "1080p" ("best" = no cap),
+/// matching the download page's default.
+pub fn format_for_quality(quality: &str) -> String {
+    match quality.trim_end_matches('p').parse::<u32>() {
+        Ok(h) => format!("bestvideo[height<={h}]+bestaudio/best[height<={h}]"),
+        Err(_) => "bestvideo+bestaudio/best".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn urls(found: Vec<ImportedUrl>) -> Vec<(u32, String)> {
+        found.into_iter().map(|u| (u.line, u.url)).collect()
+    }
+
+    #[test]
+    fn lines_strip_bom_comments_and_mixed_endings() {
+        let text = "\u{feff}https://a.example/1\r\n# comment\r\n\rhttps://a.example/2\n  \nhttps://a.example/3";
+        assert_eq!(
+            urls(parse_urls(text, UrlFileFormat::Lines).unwrap()),
+            vec![
+                (1, "https://a.example/1".to_string()),
+                (4, "https://a.example/2".to_string()),
+                (6, "https://a.example/3".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_uses_url_column_and_quotes() {
+        let text = "title,URL\r\n\"Hello, world\",https://a.example/1\r\n\"say \"\"hi\"\"\",https://a.example/2\r\n";
+        assert_eq!(
+            urls(parse_urls(text, UrlFileFormat::Csv).unwrap()),
+            vec![
+                (2, "https://a.example/1".to_string()),
+                (3, "https://a.example/2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_without_header_finds_url_fields() {
+        let text = "first,https://a.example/1\nsecond,https://a.example/2\nthird,\n";
+        assert_eq!(
+            urls(parse_urls(text, UrlFileFormat::Csv).unwrap()),
+            vec![
+                (1, "https://a.example/1".to_string()),
+                (2, "https://a.example/2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn json_accepts_strings_objects_and_entries() {
+        let text =
+            r#"["https://a.example/1", {"title": "x", "webpage_url": "https://a.example/2"}, 3]"#;
+        assert_eq!(
+            urls(parse_urls(text, UrlFileFormat::Json).unwrap()),
+            vec![
+                (1, "https://a.example/1".to_string()),
+                (2, "https://a.example/2".to_string()),
+            ]
+        );
+        let wrapped = r#"{"entries": [{"url": "https://a.example/3"}]}"#;
+        assert_eq!(parse_urls(wrapped, UrlFileFormat::Json).unwrap().len(), 1);
+        assert!(parse_urls(r#"{"a": 1}"#, UrlFileFormat::Json).is_err());
+    }
+
+    #[test]
+    fn quality_maps_to_height_cap() {
+        assert_eq!(
+            format_for_quality("1080p"),
+            "bestvideo[height<=1080]+bestaudio/best[height<=1080]"
+        );
+        assert_eq!(format_for_quality("best"), "bestvideo+bestaudio/best");
+    }
+}
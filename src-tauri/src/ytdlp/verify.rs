@@ -0,0 +1,126 @@
+//! Optional check of finished downloads (`verify_downloads`).
+//!
+//! ffprobe must read the file as a media container, and when the source
+//! reports a duration the file's must be close to it. A truncated download
+//! usually fails one of the two. Without ffprobe the check is skipped rather
+//! than flagging every file.
+
+use crate::modules::logger;
+use crate::ytdlp::probe::{self, MediaProbe};
+use std::path::Path;
+use tauri::AppHandle;
+
+pub const VERIFIED: &str = "verified";
+pub const SUSPECT: &str = "suspect";
+
+/// Result recorded on the history row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Verification {
+    pub status: &'static str,
+    pub error: Option<String>,
+}
+
+/// Allowed gap between file and source duration: 2%, at least 2 seconds.
+fn duration_tolerance(expected: u64) -> u64 {
+    (expected / 50).max(2)
+}
+
+/// Judge a probe result against the source duration. None when ffprobe was unavailable.
+fn judge(probe: MediaProbe, expected: Option<u64>) -> Option<Verification> {
+    match probe {
+        MediaProbe::Unavailable => None,
+        MediaProbe::Invalid(error) => Some(Verification {
+            status: SUSPECT,
+            error: Some(error),
+        }),
+        MediaProbe::Valid(actual) => match (actual, expected) {
+            (Some(actual), Some(expected))
+                if actual.abs_diff(expected) > duration_tolerance(expected) =>
+            {
+                Some(Verification {
+                    status: SUSPECT,
+                    error: Some(format!(
+                        "Duration {}s does not match the source ({}s)",
+                        actual, expected
+                    )),
+                })
+            }
+            _ => Some(Verification {
+                status: VERIFIED,
+                error: None,
+            }),
+        },
+    }
+}
+
+/// Verify a finished file against its source URL. Asks yt-dlp for the
+/// source duration only when ffprobe could read the file.
+pub async fn verify_file(app: &AppHandle, url: &str, path: &Path) -> Option<Verification> {
+    let probe = probe::probe_media(app, path).await;
+    let expected = match probe {
+        MediaProbe::Valid(Some(_)) => probe::url_duration(app, url).await,
+        _ => None,
+    };
+    let verification = judge(probe, expected);
+    match &verification {
+        None => logger::warn_cat(
+            "download",
+            &format!(
+                "Skipped verification of {}: ffprobe unavailable",
+                path.display()
+            ),
+        ),
+        Some(v) if v.status == SUSPECT => logger::warn_cat(
+            "download",
+            &format!(
+                "Verification marked {} suspect: {}",
+                path.display(),
+                v.error.as_deref().unwrap_or("")
+            ),
+        ),
+        Some(_) => {}
+    }
+    verification
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matching_duration_is_verified() {
+        let v = judge(MediaProbe::Valid(Some(601)), Some(600)).unwrap();
+        assert_eq!(v.status, VERIFIED);
+        // No source duration to compare against
+        let v = judge(MediaProbe::Valid(Some(30)), None).unwrap();
+        assert_eq!(v.status, VERIFIED);
+    }
+
+    #[test]
+    fn short_file_is_suspect() {
+        let v = judge(MediaProbe::Valid(Some(300)), Some(600)).unwrap();
+        assert_eq!(v.status, SUSPECT);
+        assert!(v.error.unwrap().contains("300s"));
+        // 2% of 600s = 12s
+        assert_eq!(
+            judge(MediaProbe::Valid(Some(588)), Some(600))
+                .unwrap()
+                .status,
+            VERIFIED
+        );
+        assert_eq!(
+            judge(MediaProbe::Valid(Some(587)), Some(600))
+                .unwrap()
+                .status,
+            SUSPECT
+        );
+    }
+
+    #[test]
+    fn unreadable_file_is_suspect_and_missing_ffprobe_skips() {
+        let v = judge(MediaProbe::Invalid("moov atom not found".into()), Some(60)).unwrap();
+        assert_eq!(v.status, SUSPECT);
+        assert_eq!(v.error.as_deref(), Some("moov atom not found"));
+        assert!(judge(MediaProbe::Unavailable, Some(60)).is_none());
+    }
+}
@@ -0,0 +1,259 @@
+//! Background monitoring of channels and playlists for new uploads.
+//!
+//! Each watched source is polled on its own interval with a flat listing of
+//! its newest entries. The first successful check only records what is
+//! already there, so watching a channel doesn't queue its whole back
+//! catalogue; later checks queue the entries that were not seen before.
+//!
+//! Checks against the same host are spaced at least `HOST_GAP` apart, so a
+//! long list of channels on one site is spread over several ticks instead of
+//! hitting it in a burst. Sources live in the database and the poller is
+//! started from setup, so monitoring resumes after a restart.
+
+use crate::modules::logger;
+use crate::modules::types::AppError;
+use crate::ytdlp::types::{DownloadRequest, WatchedSource};
+use crate::ytdlp::url_import;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+const TICK: Duration = Duration::from_secs(30);
+
+/// Minimum spacing between two checks against the same host
+const HOST_GAP: Duration = Duration::from_secs(20);
+
+/// Newest entries looked at per check
+const SCAN_SIZE: u32 = 30;
+
+pub const MIN_INTERVAL_SECS: u64 = 5 * 60;
+pub const MAX_INTERVAL_SECS: u64 = 7 * 24 * 60 * 60;
+
+static THROTTLE: LazyLock<Mutex<HostThrottle>> =
+    LazyLock::new(|| Mutex::new(HostThrottle::default()));
+
+/// Host a source is throttled under; "www." is dropped so both forms share a slot.
+fn host_key(url: &str) -> String {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_ascii_lowercase()))
+        .map(|h| h.strip_prefix("www.").map(str::to_string).unwrap_or(h))
+        .unwrap_or_default()
+}
+
+#[derive(Default)]
+struct HostThrottle {
+    next_allowed: HashMap<String, Instant>,
+}
+
+impl HostThrottle {
+    /// Claim the host's slot, or return how long until it frees up.
+    fn acquire(&mut self, host: &str, now: Instant) -> Result<(), Duration> {
+        if let Some(&next) = self.next_allowed.get(host) {
+            if next > now {
+                return Err(next - now);
+            }
+        }
+        self.next_allowed.insert(host.to_string(), now + HOST_GAP);
+        Ok(())
+    }
+}
+
+/// Start the background poller. Call once from setup.
+pub fn spawn_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(TICK).await;
+            if let Err(e) = run_due(&app).await {
+                logger::warn_cat("download", &format!("Watched source poll failed: {}", e));
+            }
+        }
+    });
+}
+
+async fn run_due(app: &AppHandle) -> Result<(), AppError> {
+    let due = {
+        let db = app.state::<crate::DbState>();
+        db.due_watched_sources(chrono::Utc::now().timestamp())?
+    };
+    for source in due {
+        let claimed = THROTTLE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .acquire(&host_key(&source.url), Instant::now());
+        // Still due next tick
+        if claimed.is_err() {
+            continue;
+        }
+        // Errors are stored on the source and logged by check_source
+        let _ = check_source(app, &source).await;
+    }
+    Ok(())
+}
+
+/// Check a source now, waiting for its host's throttle slot first.
+pub async fn check_now(app: &AppHandle, source: &WatchedSource) -> Result<u32, AppError> {
+    let host = host_key(&source.url);
+    loop {
+        let claimed = THROTTLE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .acquire(&host, Instant::now());
+        match claimed {
+            Ok(()) => break,
+            Err(wait) => tokio::time::sleep(wait).await,
+        }
+    }
+    check_source(app, source).await
+}
+
+/// List the newest entries and queue the unseen ones. Returns how many were queued.
+async fn check_source(app: &AppHandle, source: &WatchedSource) -> Result<u32, AppError> {
+    let listing =
+        crate::ytdlp::metadata::fetch_playlist_info(app.clone(), source.url.clone(), 0, SCAN_SIZE)
+            .await;
+    let db = app.state::<crate::DbState>();
+    let playlist = match listing {
+        Ok(playlist) => playlist,
+        Err(e) => {
+            db.record_watch_check(source.id, None, 0, Some(&e.to_string()))?;
+            logger::warn_cat(
+                "download",
+                &format!("Watched source check failed: {} ({})", source.url, e),
+            );
+            return Err(e);
+        }
+    };
+    let title = (!playlist.title.is_empty()).then_some(playlist.title.as_str());
+
+    let baseline = !db.has_watch_baseline(source.id)?;
+    let ids: Vec<String> = playlist
+        .entries
+        .iter()
+        .map(|e| e.video_id.clone())
+        .collect();
+    let fresh = db.mark_watch_seen(source.id, &ids)?;
+    if baseline {
+        db.set_watch_baseline(source.id)?;
+        db.record_watch_check(source.id, title, 0, None)?;
+        logger::info_cat(
+            "download",
+            &format!(
+                "Watching {}: recorded {} existing entries",
+                source.url,
+                fresh.len()
+            ),
+        );
+        return Ok(0);
+    }
+
+    let settings = crate::ytdlp::settings::get_settings(app)?;
+    let format_id = url_import::format_for_quality(&settings.default_quality);
+    let mut queued = 0;
+    let mut failures = Vec::new();
+    // Listings are newest first; queue oldest first so they download in upload order
+    for entry in playlist
+        .entries
+        .iter()
+        .rev()
+        .filter(|e| fresh.contains(&e.video_id))
+    {
+        if db.check_duplicate_in_queue(&entry.video_id)?
+            || db
+                .check_duplicate(&entry.video_id)?
+                .is_some_and(|h| std::path::Path::new(&h.file_path).exists())
+        {
+            continue;
+        }
+        if db.is_video_blocked(&entry.video_id)? {
+            logger::info_cat(
+                "download",
+                &format!(
+                    "Watched source {}: skipped blocked video {}",
+                    source.url, entry.video_id
+                ),
+            );
+            continue;
+        }
+        let request = DownloadRequest {
+            video_url: entry.url.clone(),
+            video_id: entry.video_id.clone(),
+            title: entry.title.clone().unwrap_or_else(|| entry.url.clone()),
+            format_id: format_id.clone(),
+            quality_label: settings.default_quality.clone(),
+            output_dir: None,
+            cookie_browser: None,
+            playlist_id: None,
+            playlist_index: None,
+            credentials: None,
+            keep_separate_streams: false,
+            max_filesize: None,
+            audio_lang: None,
+            embed_metadata: false,
+            embed_thumbnail: false,
+            preset: None,
+            live_from_start: false,
+            subtitles_only: false,
+            subtitle_langs: Vec::new(),
+            convert_subs: None,
+            tags: Vec::new(),
+            headers: None,
+            max_retries: None,
+            retry_backoff_secs: None,
+            on_existing_file: None,
+        };
+        match crate::ytdlp::download::commands::enqueue(app, request, None).await {
+            Ok(_) => queued += 1,
+            Err(e) => failures.push(format!("{}: {}", entry.video_id, e)),
+        }
+    }
+
+    let error = (!failures.is_empty()).then(|| failures.join("; "));
+    db.record_watch_check(source.id, title, queued, error.as_deref())?;
+    let summary = format!(
+        "Watched source {}: {} new, {} queued",
+        source.url,
+        fresh.len(),
+        queued
+    );
+    if let Some(error) = &error {
+        logger::warn_cat("download", &format!("{} (failed: {})", summary, error));
+    } else if !fresh.is_empty() {
+        logger::info_cat("download", &summary);
+    }
+    Ok(queued)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_key_ignores_www_and_case() {
+        assert_eq!(
+            host_key("https://www.YouTube.com/@someone/videos"),
+            "youtube.com"
+        );
+        assert_eq!(
+            host_key("https://youtube.com/playlist?list=PL1"),
+            "youtube.com"
+        );
+        assert_eq!(host_key("not a url"), "");
+    }
+
+    #[test]
+    fn throttle_spaces_checks_per_host() {
+        let mut throttle = HostThrottle::default();
+        let start = Instant::now();
+        assert!(throttle.acquire("youtube.com", start).is_ok());
+        assert!(throttle.acquire("vimeo.com", start).is_ok());
+
+        let wait = throttle
+            .acquire("youtube.com", start + Duration::from_secs(5))
+            .unwrap_err();
+        assert_eq!(wait, HOST_GAP - Duration::from_secs(5));
+
+        assert!(throttle.acquire("youtube.com", start + HOST_GAP).is_ok());
+    }
+}
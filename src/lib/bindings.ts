@@ -13,9 +13,95 @@ async checkDependencies() : Promise<Result<DependencyStatus, AppError>> {
     else return { status: "error", error: e  as any };
 }
 },
-async updateYtdlp() : Promise<Result<string, AppError>> {
+/**
+ * Drop the cached dependency status and resolve yt-dlp again, for binaries
+ * installed or removed outside the app (e.g. `brew install yt-dlp`).
+ */
+async refreshBinaryResolution() : Promise<Result<DependencyStatus, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("refresh_binary_resolution") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Run `yt-dlp --update`, killing it after `timeout_secs` (default 120).
+ */
+async updateYtdlp(timeoutSecs: number | null) : Promise<Result<string, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_ytdlp", { timeoutSecs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Abort a running `update_ytdlp`. Returns false when no update is running.
+ */
+async cancelUpdate() : Promise<Result<boolean, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_update") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Restore a saved yt-dlp binary: `index` from `list_ytdlp_backups`, default 1
+ * (the one replaced last). Returns its version.
+ */
+async rollbackYtdlpUpdate(index: number | null) : Promise<Result<string, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("rollback_ytdlp_update", { index }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * yt-dlp binaries kept for rollback, newest first.
+ */
+async listYtdlpBackups() : Promise<Result<YtdlpBackup[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_ytdlp_backups") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Install a specific ffmpeg build and keep it there: a release branch ("7.1")
+ * resolves to its newest dated BtbN release, which the pin then names
+ * ("7.1@autobuild-…"); a release tag on macOS. The binaries it replaces are
+ * kept for `rollback_ffmpeg`.
+ */
+async installFfmpegVersion(version: string) : Promise<Result<string, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("install_ffmpeg_version", { version }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stop following the pinned ffmpeg build; installs and updates get the latest
+ * one again. The installed binaries are left as they are.
+ */
+async unpinFfmpeg() : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("unpin_ffmpeg") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Restore the ffmpeg replaced by the last `install_ffmpeg_version`. Returns its version.
+ */
+async rollbackFfmpeg() : Promise<Result<string, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_ytdlp") };
+    return { status: "ok", data: await TAURI_INVOKE("rollback_ffmpeg") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -37,6 +123,18 @@ async clearCompleted() : Promise<Result<number, AppError>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Close the gaps left in a playlist's `%(playlist_index)s` numbering after
+ * cherry-picking: queued tasks get consecutive positions, downloaded ones keep theirs.
+ */
+async renumberPlaylistTasks(playlistId: string) : Promise<Result<PlaylistIndexChange[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("renumber_playlist_tasks", { playlistId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async retryDownload(taskId: number) : Promise<Result<null, AppError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("retry_download", { taskId }) };
@@ -45,343 +143,2934 @@ async retryDownload(taskId: number) : Promise<Result<null, AppError>> {
     else return { status: "error", error: e  as any };
 }
 },
-async getSettings() : Promise<Result<AppSettings, AppError>> {
+/**
+ * Override the automatic retry policy of a task that hasn't finished.
+ * None for a value goes back to the setting.
+ */
+async setTaskRetryPolicy(taskId: number, maxRetries: number | null, retryBackoffSecs: number | null) : Promise<Result<null, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_settings") };
+    return { status: "ok", data: await TAURI_INVOKE("set_task_retry_policy", { taskId, maxRetries, retryBackoffSecs }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateSettings(settings: AppSettings) : Promise<Result<null, AppError>> {
+/**
+ * Stop starting downloads, wait up to `timeout_secs` (default one hour) for the
+ * running ones, then exit; whatever is still running then is cancelled.
+ * Progress is sent as `queue-drain-event`.
+ */
+async drainAndExit(timeoutSecs: number | null) : Promise<Result<null, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_settings", { settings }) };
+    return { status: "ok", data: await TAURI_INVOKE("drain_and_exit", { timeoutSecs }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async selectDownloadDirectory() : Promise<Result<string | null, AppError>> {
+/**
+ * Tasks started this session and today against their configured limits.
+ */
+async getDownloadLimits() : Promise<Result<DownloadLimitUsage, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("select_download_directory") };
+    return { status: "ok", data: await TAURI_INVOKE("get_download_limits") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getAvailableBrowsers() : Promise<string[]> {
-    return await TAURI_INVOKE("get_available_browsers");
+/**
+ * Bytes downloaded today, this month or in total, and this month's share of
+ * `monthly_data_cap`.
+ */
+async getBandwidthUsage(period: BandwidthPeriod) : Promise<Result<BandwidthUsage, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_bandwidth_usage", { period }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
 },
-async getDownloadHistory(page: number, pageSize: number, search: string | null) : Promise<Result<HistoryResult, AppError>> {
+async getSettings() : Promise<Result<AppSettings, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_download_history", { page, pageSize, search }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_settings") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async checkDuplicate(videoId: string) : Promise<Result<DuplicateCheckResult, AppError>> {
+/**
+ * Current settings with secrets masked, for sharing in bug reports.
+ */
+async exportSettingsRedacted() : Promise<Result<AppSettings, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("check_duplicate", { videoId }) };
+    return { status: "ok", data: await TAURI_INVOKE("export_settings_redacted") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteHistoryItem(id: number) : Promise<Result<null, AppError>> {
+/**
+ * Test `url` against the saved host allow/deny lists without queueing anything.
+ */
+async checkHostPolicy(url: string) : Promise<Result<null, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_history_item", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("check_host_policy", { url }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getActiveDownloads() : Promise<Result<DownloadTaskInfo[], AppError>> {
+/**
+ * Every problem with `settings`, keyed by settings field, without saving anything.
+ */
+async validateSettings(settings: AppSettings) : Promise<Result<SettingIssue[], AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_active_downloads") };
+    return { status: "ok", data: await TAURI_INVOKE("validate_settings", { settings }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getDownloadQueuePaginated(page: number, pageSize: number, statusFilter: string | null) : Promise<Result<QueueResult, AppError>> {
+async updateSettings(settings: AppSettings) : Promise<Result<null, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_download_queue_paginated", { page, pageSize, statusFilter }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_settings", { settings }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getQueueSummary() : Promise<Result<QueueSummary, AppError>> {
+/**
+ * Show which options a download of `url` would use and where each value came from.
+ */
+async resolveEffectiveOptions(url: string, taskOverrides: TaskOverrides | null) : Promise<Result<EffectiveOptions, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_queue_summary") };
+    return { status: "ok", data: await TAURI_INVOKE("resolve_effective_options", { url, taskOverrides }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async selectDownloadDirectory() : Promise<Result<string | null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("select_download_directory") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getRecentDirectories() : Promise<Result<string[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_recent_directories") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
+async getAvailableBrowsers() : Promise<string[]> {
+    return await TAURI_INVOKE("get_available_browsers");
+},
 /**
- * Validate if a URL is a valid YouTube URL
+ * Profile names of `browser` for a `browser:profile` cookie source, e.g.
+ * ["Default", "Profile 2"]. Empty when the browser has none on this machine.
  */
-async validateUrl(url: string) : Promise<Result<UrlValidation, AppError>> {
+async listBrowserProfiles(browser: string) : Promise<Result<string[], AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("validate_url", { url }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_browser_profiles", { browser }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Fetch video metadata using yt-dlp --dump-json
+ * The system's default browser under a `get_available_browsers` name, or None.
  */
-async fetchVideoInfo(url: string) : Promise<Result<VideoInfo, AppError>> {
+async getDefaultBrowser() : Promise<string | null> {
+    return await TAURI_INVOKE("get_default_browser");
+},
+/**
+ * Check an output template for likely trouble (missing extension, fields that
+ * are often empty, Windows path length, separators) without downloading.
+ * `preview_output_filename` reports the same warnings next to a real name.
+ */
+async lintOutputTemplate(template: string) : Promise<TemplateWarning[]> {
+    return await TAURI_INVOKE("lint_output_template", { template });
+},
+async getDownloadHistory(page: number, pageSize: number, search: string | null, favoritesOnly: boolean | null, sortBy: HistorySortKey | null, sortDir: SortDir | null) : Promise<Result<HistoryResult, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_video_info", { url }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_download_history", { page, pageSize, search, favoritesOnly, sortBy, sortDir }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Fetch playlist metadata and entries using yt-dlp --flat-playlist
+ * `url` adds a check for a file already at the would-be output path, which
+ * catches files kept after history was cleared. It costs a yt-dlp run, so
+ * batch adds leave it out.
  */
-async fetchPlaylistInfo(url: string, page: number, pageSize: number) : Promise<Result<PlaylistResult, AppError>> {
+async checkDuplicate(videoId: string, url: string | null) : Promise<Result<DuplicateCheckResult, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_playlist_info", { url, page, pageSize }) };
+    return { status: "ok", data: await TAURI_INVOKE("check_duplicate", { videoId, url }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Fetch quick metadata via YouTube oEmbed API (~200ms vs ~12s for yt-dlp)
+ * Never queue `video_id` again, from any source (manual adds, imports, watched
+ * channels). Returns false if it was already blocked.
  */
-async fetchQuickMetadata(url: string) : Promise<Result<QuickMetadata, AppError>> {
+async blockVideo(videoId: string) : Promise<Result<boolean, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_quick_metadata", { url }) };
+    return { status: "ok", data: await TAURI_INVOKE("block_video", { videoId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async startDownload(request: DownloadRequest) : Promise<Result<number, AppError>> {
+/**
+ * Take `video_id` off the blocklist. Returns false if it wasn't blocked.
+ */
+async unblockVideo(videoId: string) : Promise<Result<boolean, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("start_download", { request }) };
+    return { status: "ok", data: await TAURI_INVOKE("unblock_video", { videoId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async addToQueue(request: DownloadRequest) : Promise<Result<number, AppError>> {
+/**
+ * Every blocked video id, most recently blocked first.
+ */
+async listBlocked() : Promise<Result<BlockedVideo[], AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("add_to_queue", { request }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_blocked") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async cancelDownload(taskId: number) : Promise<Result<null, AppError>> {
+async deleteHistoryItem(id: number) : Promise<Result<null, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("cancel_download", { taskId }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_history_item", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async cancelAllDownloads() : Promise<Result<number, AppError>> {
+/**
+ * Space used by downloads in each output directory, largest first. Sizes come
+ * from history; `stat_files` measures files whose size was never recorded
+ * (slower on large libraries, off by default).
+ */
+async getStorageByDirectory(statFiles: boolean | null) : Promise<Result<DirUsage[], AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("cancel_all_downloads") };
+    return { status: "ok", data: await TAURI_INVOKE("get_storage_by_directory", { statFiles }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async pauseDownload(taskId: number) : Promise<Result<null, AppError>> {
+/**
+ * All columns of a history row plus its info-json sidecar (summarized) and the
+ * log lines of the download that produced it.
+ */
+async getHistoryDetail(id: number) : Promise<Result<HistoryDetail, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("pause_download", { taskId }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_history_detail", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async resumeDownload(taskId: number) : Promise<Result<null, AppError>> {
+/**
+ * Completions per hour and average queue wait over the last `window_hours`
+ * (default 24, at most 90 days).
+ */
+async getThroughputHistory(windowHours: number | null) : Promise<Result<Throughput, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("resume_download", { taskId }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_throughput_history", { windowHours }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setMinimizeToTray(minimize: boolean, remember: boolean) : Promise<Result<null, AppError>> {
+/**
+ * Add user tag `tag` to every history item matching `search` (the Library search;
+ * None or empty means all). Returns how many items gained the tag.
+ */
+async bulkTag(search: string | null, tag: string) : Promise<Result<number, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_minimize_to_tray", { minimize, remember }) };
+    return { status: "ok", data: await TAURI_INVOKE("bulk_tag", { search, tag }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getRecentLogs() : Promise<string> {
-    return await TAURI_INVOKE("get_recent_logs");
+/**
+ * Remove user tag `tag` from every history item matching `search`. Returns how many items lost it.
+ */
+async bulkUntag(search: string | null, tag: string) : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("bulk_untag", { search, tag }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
 },
-async getCachedDepStatus() : Promise<Result<FullDependencyStatus | null, AppError>> {
+/**
+ * Star or unstar a history item. Returns whether it is a favorite now.
+ */
+async toggleFavorite(id: number) : Promise<Result<boolean, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_cached_dep_status") };
+    return { status: "ok", data: await TAURI_INVOKE("toggle_favorite", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async checkFullDependencies(force: boolean | null) : Promise<Result<FullDependencyStatus, AppError>> {
+/**
+ * What renaming the current history files to `template` would do, without
+ * touching anything. Items whose file is missing are listed as such.
+ */
+async previewRename(template: string) : Promise<Result<RenamePlan[], AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("check_full_dependencies", { force }) };
+    return { status: "ok", data: await TAURI_INVOKE("preview_rename", { template }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async installDependency(depName: string) : Promise<Result<string, AppError>> {
+/**
+ * Rename the files of `plans`, ready items of the latest `preview_rename`,
+ * and update their history rows. Nothing changes if any of them fails.
+ * Returns how many files were renamed.
+ */
+async applyRename(plans: RenamePlan[]) : Promise<Result<number, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("install_dependency", { depName }) };
+    return { status: "ok", data: await TAURI_INVOKE("apply_rename", { plans }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async installAllDependencies() : Promise<Result<string[], AppError>> {
+/**
+ * Write a playlist of the given history items, in that order, to `path` (a save
+ * dialog asks when None; None is returned if it's cancelled). Items whose file
+ * is missing are left out and listed. With `relative_paths` the entries point
+ * at the files relative to the playlist's folder, so the folder tree can move
+ * as a whole; files on another drive stay absolute.
+ */
+async exportPlaylist(historyIds: number[], path: string | null, format: PlaylistFormat, relativePaths: boolean) : Promise<Result<PlaylistExport | null, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("install_all_dependencies") };
+    return { status: "ok", data: await TAURI_INVOKE("export_playlist", { historyIds, path, format, relativePaths }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async checkDependencyUpdate(depName: string) : Promise<Result<DepUpdateInfo, AppError>> {
+/**
+ * History items that likely hold the same content as `video_id_or_url`, best
+ * match first. A history entry is looked up by id or URL; a URL not in history
+ * is probed with yt-dlp for its title and duration. Needs `similarity_fingerprints`.
+ */
+async findSimilar(videoIdOrUrl: string) : Promise<Result<SimilarItem[], AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("check_dependency_update", { depName }) };
+    return { status: "ok", data: await TAURI_INVOKE("find_similar", { videoIdOrUrl }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateDependency(depName: string) : Promise<Result<string, AppError>> {
+/**
+ * Check a downloaded file with ffprobe now and store the outcome on its history row.
+ */
+async verifyHistoryItem(id: number) : Promise<Result<HistoryItem, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_dependency", { depName }) };
+    return { status: "ok", data: await TAURI_INVOKE("verify_history_item", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Delete an app-managed dependency binary from app_data_dir/bin/.
+ * Apply finishing steps (tags, cover art, subtitles, chapters) to a downloaded
+ * file without downloading it again, and store the result on its history row.
  */
-async deleteAppManagedDep(depName: string) : Promise<Result<string, AppError>> {
+async rerunPostprocessing(historyId: number, steps: PostprocessSteps) : Promise<Result<HistoryItem, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_app_managed_dep", { depName }) };
+    return { status: "ok", data: await TAURI_INVOKE("rerun_postprocessing", { historyId, steps }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Full factory reset: clear settings, databases (via live connections), binaries, and caches.
- * 
- * Databases are cleared through their live connections rather than deleting files,
- * which would leave orphaned in-memory connections and cause silent data loss.
+ * Probe durations for one bounded batch of history rows that predate the
+ * `duration_secs` column. The file on disk is probed with ffprobe, falling
+ * back to asking yt-dlp about the URL. Every row is marked probed, found or
+ * not, so rows that can't be probed are not tried again. Call again with
+ * `next_before_id` until it comes back None.
  */
-async resetAllData() : Promise<Result<string[], AppError>> {
+async backfillDurations(batchSize: number | null, beforeId: number | null) : Promise<Result<DurationBackfill, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("reset_all_data") };
+    return { status: "ok", data: await TAURI_INVOKE("backfill_durations", { batchSize, beforeId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getLogs(page: number, pageSize: number, level: string | null, category: string | null, search: string | null, since: number | null) : Promise<Result<LogQueryResult, AppError>> {
+/**
+ * Backfill uploader, container, tags and duration on history rows that predate
+ * those columns, one batch per call. Sources per row: an info-json sidecar, the
+ * file itself, then a yt-dlp probe of the URL. Progress goes out per row on
+ * `on_progress`. Pages like `backfill_durations`: each row is marked visited
+ * as it finishes, so an interrupted batch loses nothing; call again with
+ * `next_before_id` until it comes back None.
+ */
+async normalizeHistory(batchSize: number | null, beforeId: number | null, onProgress: TAURI_CHANNEL<NormalizeProgress>) : Promise<Result<NormalizeSummary, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_logs", { page, pageSize, level, category, search, since }) };
+    return { status: "ok", data: await TAURI_INVOKE("normalize_history", { batchSize, beforeId, onProgress }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getLogStats() : Promise<Result<LogStats, AppError>> {
+/**
+ * Queue a fresh download of a history item with different options. The history
+ * row is kept and linked to the new task through `superseded_by` once it
+ * completes; with `replace_existing` the new download is written aside and
+ * replaces the old file only when it succeeds. Returns the new task id.
+ */
+async redownloadWithOptions(historyId: number, newOptions: RedownloadOptions) : Promise<Result<number, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_log_stats") };
+    return { status: "ok", data: await TAURI_INVOKE("redownload_with_options", { historyId, newOptions }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async clearLogs(beforeTimestamp: number | null) : Promise<Result<number, AppError>> {
+async getActiveDownloads() : Promise<Result<DownloadTaskInfo[], AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("clear_logs", { beforeTimestamp }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_active_downloads") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
+},
+async getDownloadQueuePaginated(page: number, pageSize: number, statusFilter: string | null) : Promise<Result<QueueResult, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_download_queue_paginated", { page, pageSize, statusFilter }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
 }
+},
+async getQueueSummary() : Promise<Result<QueueSummary, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_queue_summary") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
 }
-
-/** user-defined events **/
-
-
-export const events = __makeEvents__<{
-depInstallEvent: DepInstallEvent,
-globalDownloadEvent: GlobalDownloadEvent,
-newLogEvent: NewLogEvent
-}>({
-depInstallEvent: "dep-install-event",
-globalDownloadEvent: "global-download-event",
-newLogEvent: "new-log-event"
-})
-
-/** user-defined constants **/
-
-
-
-/** user-defined types **/
-
-export type AppError = { FileError: string } | { Custom: string } | { BinaryNotFound: string } | { DownloadError: string } | { MetadataError: string } | { DatabaseError: string } | { NetworkError: string } | { InvalidUrl: string } | { DependencyInstallError: string } | { ChecksumError: string } | { NotImplemented: string }
-export type AppSettings = { downloadPath: string; defaultQuality: string; maxConcurrent: number; filenameTemplate: string; cookieBrowser: string | null; autoUpdateYtdlp: boolean; useAdvancedTemplate: boolean; templateUploaderFolder: boolean; templateUploadDate: boolean; templateVideoId: boolean; language: string | null; theme: string | null; minimizeToTray: boolean | null; 
+},
 /**
- * Dependency resolution mode: "external" (app-managed) or "system" (system PATH only)
+ * Counts and the first few tasks for each status in one round trip.
  */
-depMode: string; 
+async getQueueGrouped(sampleSize: number | null) : Promise<Result<QueueGrouped, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_queue_grouped", { sampleSize }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stream coarse queue state changes (add/start/milestone/complete/fail/cancel).
+ * Sends a full snapshot first, and again if the subscriber falls behind.
+ */
+async watchQueue(onEvent: TAURI_CHANNEL<QueueEvent>) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("watch_queue", { onEvent }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Combined download speed of all running tasks, for a "total down" display.
+ */
+async getTotalSpeed() : Promise<Result<TotalSpeed, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_total_speed") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Latest progress of every running task in one call, for live tables that poll
+ * instead of following `download-event`.
+ */
+async getActiveProgress() : Promise<Result<TaskProgress[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_active_progress") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Running tasks whose download hasn't moved for `threshold_secs` or more, so
+ * they can be flagged before the download timeout kills them.
+ */
+async getStalledTasks(thresholdSecs: number) : Promise<Result<TaskStall[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_stalled_tasks", { thresholdSecs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Give `task_id` the full bandwidth: it runs uncapped while every other task
+ * is held to `throttleRateLimit`. Running tasks whose cap changes restart and
+ * resume. The focus is dropped when the task finishes.
+ */
+async setFocusedTask(taskId: number) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_focused_task", { taskId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lift the focus set by `set_focused_task`; the other tasks go back to their normal rate.
+ */
+async clearFocusedTask() : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clear_focused_task") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Queue every video URL in a text, CSV or JSON file (`format` is guessed from the
+ * extension when omitted). URLs already queued, already downloaded with the file
+ * still present, or repeated within the file are skipped. Downloads use
+ * `format_id`/`quality_label` when given, else the default quality setting.
+ */
+async importUrlsFromFile(path: string, format: UrlFileFormat | null, formatId: string | null, qualityLabel: string | null) : Promise<Result<UrlImportResult, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_urls_from_file", { path, format, formatId, qualityLabel }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Probe the expected size of `format_id` for `url` and compare it with the size cap
+ * (`max_filesize` if given, else the setting) before queueing.
+ */
+async estimateDownloadSize(url: string, formatId: string, maxFilesize: string | null) : Promise<Result<SizeEstimate, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("estimate_download_size", { url, formatId, maxFilesize }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Validate if a URL is a valid YouTube URL.
+ * 
+ * A watch URL that also carries `list=` is treated as a single video when
+ * `single_video` (or the `single_video_default` setting when omitted) is on,
+ * and as the playlist otherwise. Either way the list id is returned so the
+ * caller can switch for this paste.
+ */
+async validateUrl(url: string, singleVideo: boolean | null) : Promise<Result<UrlValidation, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("validate_url", { url, singleVideo }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetch video metadata using yt-dlp --dump-json
+ */
+async fetchVideoInfo(url: string) : Promise<Result<VideoInfo, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_video_info", { url }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * `url`'s formats ranked by quality per byte, with a recommended and a
+ * smallest acceptable option flagged (see `format_rank`).
+ */
+async formatRecommendations(url: string) : Promise<Result<FormatRec[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("format_recommendations", { url }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetch playlist metadata and entries using yt-dlp --flat-playlist
+ */
+async fetchPlaylistInfo(url: string, page: number, pageSize: number) : Promise<Result<PlaylistResult, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_playlist_info", { url, page, pageSize }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetch quick metadata via YouTube oEmbed API (~200ms vs ~12s for yt-dlp)
+ */
+async fetchQuickMetadata(url: string) : Promise<Result<QuickMetadata, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_quick_metadata", { url }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Resolve the filename `template` (default: the filename template setting)
+ * produces for `url`, without downloading. For a playlist or channel the
+ * first entry stands in for the rest.
+ */
+async previewOutputFilename(url: string, template: string | null) : Promise<Result<FilenamePreview, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("preview_output_filename", { url, template }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async startDownload(request: DownloadRequest) : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_download", { request }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async addToQueue(request: DownloadRequest) : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_to_queue", { request }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Features of `options` (a request as it would be queued, preset applied) that
+ * need ffmpeg, so the UI can warn before queueing when ffmpeg is missing.
+ */
+async featuresRequiringFfmpeg(options: DownloadRequest) : Promise<Result<string[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("features_requiring_ffmpeg", { options }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * What yt-dlp would download for `options` under the current settings
+ * (`maxHeight`, codec preference, audio language), without downloading.
+ */
+async testFormatSelection(options: DownloadRequest) : Promise<Result<SelectedFormat, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("test_format_selection", { options }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async cancelDownload(taskId: number) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_download", { taskId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async cancelAllDownloads() : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_all_downloads") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Recount running tasks from the DB and correct the manager's slot counter,
+ * for when a leaked slot keeps queued downloads from starting.
+ */
+async resetManagerSlots() : Promise<Result<SlotReset, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reset_manager_slots") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Run the queue watchdog's check now: restart the queue if pending tasks are
+ * waiting with free slots and nothing running.
+ */
+async checkQueueStall() : Promise<Result<QueueStallCheck, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_queue_stall") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * When the next pending task should start, or None when nothing is pending.
+ */
+async getNextStart() : Promise<Result<NextStart | null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_next_start") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Cancel only the active tasks matching `filter` (e.g. one playlist or tag), leaving the rest queued.
+ */
+async cancelMatching(filter: CancelFilter) : Promise<Result<CancelMatchResult, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_matching", { filter }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async pauseDownload(taskId: number) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("pause_download", { taskId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async resumeDownload(taskId: number) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("resume_download", { taskId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setMinimizeToTray(minimize: boolean, remember: boolean) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_minimize_to_tray", { minimize, remember }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getRecentLogs() : Promise<string> {
+    return await TAURI_INVOKE("get_recent_logs");
+},
+async getCachedDepStatus() : Promise<Result<FullDependencyStatus | null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_cached_dep_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async checkFullDependencies(force: boolean | null) : Promise<Result<FullDependencyStatus, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_full_dependencies", { force }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getBinaryInfo() : Promise<Result<BinaryInfo, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_binary_info") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Find yt-dlp config files that set options the app also manages.
+ */
+async checkYtdlpConfigConflicts() : Promise<Result<ConfigConflict[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_ytdlp_config_conflicts") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Aggregate app state (dependencies, databases, manager, disk, config) for triage.
+ */
+async healthCheck() : Promise<Result<HealthReport, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("health_check") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Run the active yt-dlp with `--version` and explain why it fails to start, if it does.
+ */
+async diagnoseYtdlpBinary() : Promise<Result<BinaryDiagnosis, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("diagnose_ytdlp_binary") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check that GitHub (dependency downloads) and a sample extractor host answer.
+ */
+async testNetwork() : Promise<Result<NetworkReport, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("test_network") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Result of the last network check (e.g. the startup one), if any has finished.
+ */
+async getNetworkReport() : Promise<Result<NetworkReport | null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_network_report") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * App, Tauri and build versions plus the yt-dlp range this build was tested with.
+ */
+async getAppInfo() : Promise<Result<AppInfo, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_app_info") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Compare extractor support between two locally available yt-dlp versions.
+ */
+async diffExtractors(versionA: string, versionB: string) : Promise<Result<ExtractorDiff, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("diff_extractors", { versionA, versionB }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * List the user-registered yt-dlp binaries with their probed versions.
+ */
+async listYtdlpBinaries() : Promise<Result<YtdlpBinary[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_ytdlp_binaries") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Switch downloads to a registered yt-dlp (or back to automatic with `null`).
+ */
+async setActiveYtdlp(label: string | null) : Promise<Result<BinaryInfo, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_active_ytdlp", { label }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Time yt-dlp start-up (`--version`) and a `--simulate` run; `runs` defaults to 3 (max 10).
+ */
+async benchmarkYtdlpSpawn(runs: number | null) : Promise<Result<SpawnBench, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("benchmark_ytdlp_spawn", { runs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Watch a channel or playlist and queue its new uploads every `interval_secs`
+ * (default 1 hour, clamped to 5 minutes – 7 days). Watching an already watched
+ * URL updates its interval. The first check runs immediately and only records
+ * the entries that already exist.
+ */
+async watchSource(url: string, intervalSecs: number | null) : Promise<Result<WatchedSource, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("watch_source", { url, intervalSecs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listWatchedSources() : Promise<Result<WatchedSource[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_watched_sources") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stop watching a source. Downloads it already queued are left alone.
+ */
+async unwatchSource(id: number) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("unwatch_source", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Save `options` under `name`, replacing a saved preset of the same name.
+ * Built-in presets can't be overwritten.
+ */
+async savePreset(name: string, options: PresetOptions) : Promise<Result<DownloadPreset, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_preset", { name, options }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Built-in presets first, then saved ones by name.
+ */
+async listPresets() : Promise<Result<DownloadPreset[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_presets") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Delete a saved preset. Returns false when no such preset exists.
+ */
+async deletePreset(name: string) : Promise<Result<boolean, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_preset", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async installDependency(depName: string) : Promise<Result<string, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("install_dependency", { depName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async installAllDependencies() : Promise<Result<string[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("install_all_dependencies") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async checkDependencyUpdate(depName: string) : Promise<Result<DepUpdateInfo, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_dependency_update", { depName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateDependency(depName: string) : Promise<Result<string, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_dependency", { depName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Delete an app-managed dependency binary from app_data_dir/bin/.
+ */
+async deleteAppManagedDep(depName: string) : Promise<Result<string, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_app_managed_dep", { depName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Full factory reset: clear settings, databases (via live connections), binaries, and caches.
+ * 
+ * Databases are cleared through their live connections rather than deleting files,
+ * which would leave orphaned in-memory connections and cause silent data loss.
+ */
+async resetAllData() : Promise<Result<string[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reset_all_data") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * yt-dlp partial files under `dir`, or under the download folders and temp dir.
+ */
+async listLeftoverFiles(dir: string | null) : Promise<Result<LeftoverFile[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_leftover_files", { dir }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Delete partial files listed by `list_leftover_files`, identified by path.
+ * Pass the same `dir` the list was made with.
+ */
+async cleanLeftoverFiles(dir: string | null, paths: string[]) : Promise<Result<LeftoverCleanup, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clean_leftover_files", { dir, paths }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Entry counts and sizes of the partial-download and codec caches.
+ */
+async getCacheStats() : Promise<Result<CacheStats, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_cache_stats") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Empty one cache kind, or all of them when `kind` is None. Entries of
+ * running or unfinished downloads are kept.
+ */
+async clearCache(kind: CacheKind | null) : Promise<Result<CacheClearResult, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clear_cache", { kind }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Remove cache entries no running or unfinished download uses.
+ */
+async pruneCache() : Promise<Result<CacheClearResult, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("prune_cache") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Run log trimming and VACUUM as soon as no download has been active for
+ * `cleanup_idle_secs`, instead of waiting for the daily pass.
+ */
+async scheduleIdleCleanup() : Promise<Result<CleanupStatus, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("schedule_idle_cleanup") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Write settings, queue, history, logs and the dependency list to one zip
+ * archive at `path`. With `redact_settings` secrets in the settings are masked
+ * (such an archive can't restore settings).
+ */
+async exportFullBackup(path: string, redactSettings: boolean) : Promise<Result<FullBackupManifest, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_full_backup", { path, redactSettings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Write a zip for bug reports to `path`: app info, a fresh health check, the
+ * redacted settings and the most recent log entries. No databases.
+ */
+async exportDiagnostics(path: string) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_diagnostics", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Restore an `export_full_backup` archive. Older databases are migrated
+ * first. `Replace` needs an idle queue; `Merge` keeps local rows on conflict.
+ * Settings are restored only when asked and the archive has them unredacted.
+ */
+async importFullBackup(path: string, mode: BackupImportMode, restoreSettings: boolean) : Promise<Result<FullBackupImport, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_full_backup", { path, mode, restoreSettings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check that the data dir (logs DB, download DB, binaries) can be written to.
+ */
+async checkAppDataWritable() : Promise<Result<WritableCheck, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_app_data_writable") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Copy data from a location earlier builds used into the current data dir.
+ * This runs once at startup; afterwards the recorded outcome is returned.
+ */
+async migrateLegacyData() : Promise<Result<LegacyMigration, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("migrate_legacy_data") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Whether `dir` tells apart file names that differ only by case. When it
+ * doesn't, templates without a unique field like `%(id)s` can overwrite files.
+ */
+async checkCaseSensitivity(dir: string) : Promise<Result<boolean, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_case_sensitivity", { dir }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Report how system sleep is handled and what happened on the last wake.
+ */
+async getPowerStatus() : Promise<Result<PowerStatus, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_power_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Battery charge and whether new downloads are held for low battery.
+ * `available` is false when the charge can't be read on this system.
+ */
+async getBatteryState() : Promise<Result<BatteryState, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_battery_state") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Latest CPU usage sample and whether new downloads are held for system load.
+ */
+async getCpuLoadState() : Promise<Result<CpuLoadState, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_cpu_load_state") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * List yt-dlp/ffmpeg processes from the app's bin dir that no running app instance owns.
+ */
+async listOrphanedProcesses() : Promise<Result<OrphanProcess[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_orphaned_processes") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Terminate the processes reported by `list_orphaned_processes`.
+ */
+async reapOrphanedProcesses() : Promise<Result<OrphanProcess[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reap_orphaned_processes") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check a Netscape-format cookies.txt: cookie and domain counts, expired
+ * entries and malformed lines.
+ */
+async validateCookiesFile(path: string) : Promise<Result<CookiesValidation, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("validate_cookies_file", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getLogs(page: number, pageSize: number, level: string | null, category: string | null, search: string | null, since: number | null) : Promise<Result<LogQueryResult, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_logs", { page, pageSize, level, category, search, since }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Same as `get_logs`, plus byte ranges of `search` matches in each entry's
+ * message and details so the log viewer can highlight them.
+ */
+async searchLogs(page: number, pageSize: number, level: string | null, category: string | null, search: string | null, since: number | null) : Promise<Result<LogSearchResult, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("search_logs", { page, pageSize, level, category, search, since }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getLogStats() : Promise<Result<LogStats, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_log_stats") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async clearLogs(beforeTimestamp: number | null) : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clear_logs", { beforeTimestamp }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Move logs at or before `before_timestamp` into a gzip-compressed JSONL file.
+ */
+async archiveLogs(path: string, beforeTimestamp: number) : Promise<Result<LogArchiveResult, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("archive_logs", { path, beforeTimestamp }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Write one download's row, redacted command and tagged logs to `path` for a
+ * bug report. `format` defaults to JSON for a `.json` path, plain text otherwise.
+ */
+async exportTaskReport(taskId: number, path: string, format: ReportFormat | null) : Promise<Result<TaskReportResult, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_task_report", { taskId, path, format }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Classify failed-download logs written before classification existed.
+ * Already classified rows are left alone, so it is safe to run repeatedly.
+ */
+async reclassifyLogs() : Promise<Result<ReclassifyResult, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reclassify_logs") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Digest of ERROR/WARN logs between `since` and `until` (ms timestamps,
+ * `until` defaults to now): counts per kind and category, top messages and
+ * the tasks involved.
+ */
+async summarizeErrors(since: number, until: number | null) : Promise<Result<ErrorSummary, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("summarize_errors", { since, until }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Log counts per `bucket_secs` between `since` and `until` (ms timestamps,
+ * `until` defaults to now), split by level, for an activity chart. At most
+ * 500 buckets come back: longer ranges get wider buckets. `level` counts
+ * only that level.
+ */
+async getLogHistogram(bucketSecs: number, since: number, until: number | null, level: string | null) : Promise<Result<LogBucket[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_log_histogram", { bucketSecs, since, until, level }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stream new log entries matching `filter` as they are written. The filter is
+ * compiled once and evaluated on this subscription's task, never in the logger.
+ * Entries a slow subscriber falls behind on are skipped.
+ */
+async tailLogs(filter: LogTailFilter | null, onEntry: TAURI_CHANNEL<LogEntry>) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("tail_logs", { filter, onEntry }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+}
+}
+
+/** user-defined events **/
+
+
+export const events = __makeEvents__<{
+depInstallEvent: DepInstallEvent,
+globalDownloadEvent: GlobalDownloadEvent,
+newLogEvent: NewLogEvent,
+queueDrainEvent: QueueDrainEvent
+}>({
+depInstallEvent: "dep-install-event",
+globalDownloadEvent: "global-download-event",
+newLogEvent: "new-log-event",
+queueDrainEvent: "queue-drain-event"
+})
+
+/** user-defined constants **/
+
+
+
+/** user-defined types **/
+
+export type AppError = { FileError: string } | { Custom: string } | { BinaryNotFound: string } | { DownloadError: string } | { MetadataError: string } | { DatabaseError: string } | { NetworkError: string } | { InvalidUrl: string } | { DependencyInstallError: string } | { ChecksumError: string } | { NotImplemented: string }
+/**
+ * App and runtime versions for support, with the yt-dlp range this build was tested against.
+ */
+export type AppInfo = { appVersion: string; tauriVersion: string; 
+/**
+ * `<arch>-<os>` the binary was built for
+ */
+target: string; debugBuild: boolean; ytdlpTestedMin: string; ytdlpTestedMax: string; 
+/**
+ * Version of the yt-dlp downloads use, if it runs
+ */
+ytdlpVersion: string | null; 
+/**
+ * None when the installed version is unknown or unparseable
+ */
+ytdlpInTestedRange: boolean | null }
+export type AppSettings = { downloadPath: string; defaultQuality: string; maxConcurrent: number; filenameTemplate: string; cookieBrowser: string | null; autoUpdateYtdlp: boolean; useAdvancedTemplate: boolean; templateUploaderFolder: boolean; templateUploadDate: boolean; templateVideoId: boolean; language: string | null; theme: string | null; minimizeToTray: boolean | null; 
+/**
+ * Dependency resolution mode: "external" (app-managed) or "system" (system PATH only)
+ */
+depMode: string; 
+/**
+ * Whether the initial setup wizard has been completed
+ */
+setupCompleted: boolean; 
+/**
+ * Resolve yt-dlp from system PATH before the app-managed copy (e.g. pip installs)
+ */
+preferSystemBinary: boolean; 
+/**
+ * Write partial files to a temp dir (`--paths temp:`) and move them on completion
+ */
+useTempDir: boolean; 
+/**
+ * Custom temp dir for partial files (None = app cache dir)
+ */
+tempDir: string | null; 
+/**
+ * Reduce concurrency and bandwidth while the window is hidden to tray
+ */
+backgroundThrottle: boolean; 
+/**
+ * Max concurrent downloads while throttled
+ */
+throttleMaxConcurrent: number; 
+/**
+ * Rate limit for downloads started while throttled (yt-dlp `--limit-rate`, e.g. "2M")
+ */
+throttleRateLimit: string | null; 
+/**
+ * Pass `--ignore-config` so yt-dlp config files on the system are not applied
+ */
+ignoreConfig: boolean; 
+/**
+ * Re-encode downloads to this format via `--recode-video` (requires ffmpeg; slow and lossy)
+ */
+recodeVideo: string | null; 
+/**
+ * Custom ffmpeg args for the re-encode step (`--postprocessor-args VideoConvertor:...`)
+ */
+recodeFfmpegArgs: string | null; 
+/**
+ * Minimum seconds between starting consecutive downloads (0 = no delay)
+ */
+startDelaySecs: number; 
+/**
+ * Cap video height for downloads without an explicit height in their format (e.g. 1080)
+ */
+maxHeight: number | null; 
+/**
+ * Store logs, the download DB and binaries here instead of the app data dir (applied on restart)
+ */
+dataDirOverride: string | null; 
+/**
+ * Treat watch URLs carrying a `list=` parameter as a single video (yt-dlp `--no-playlist`)
+ */
+singleVideoDefault: boolean; 
+/**
+ * User-registered yt-dlp binaries, label -> absolute path
+ */
+ytdlpBinaries: Partial<{ [key in string]: string }>; 
+/**
+ * Label from `ytdlp_binaries` to use instead of automatic resolution
+ */
+activeYtdlp: string | null; 
+/**
+ * Write source URL and metadata to extended file attributes (`--xattrs`). Needs a filesystem with xattr support (APFS/HFS+, ext4, btrfs, NTFS); elsewhere yt-dlp only warns
+ */
+writeXattrs: boolean; 
+/**
+ * Restart downloads that were running across a system sleep, resuming partial files
+ */
+requeueAfterSleep: boolean; 
+/**
+ * Embed chapter markers into the output (`--embed-chapters`, requires ffmpeg)
+ */
+embedChapters: boolean; 
+/**
+ * Write chapters to a `<name>.chapters.json` sidecar next to the output
+ */
+writeChapters: boolean; 
+/**
+ * With a recode target set, remux losslessly instead when the source codecs fit that container
+ */
+autoContainer: boolean; 
+/**
+ * Default per-download size cap (`--max-filesize`, e.g. "2G"); tasks may override it
+ */
+maxFilesize: string | null; 
+/**
+ * Check finished files with ffprobe (valid container, expected duration)
+ */
+verifyDownloads: boolean; 
+/**
+ * Re-download once when verification marks a file suspect
+ */
+retrySuspectDownloads: boolean; 
+/**
+ * Per-type download folders (video/audio/subtitle/thumbnail)
+ */
+outputPathsByType: OutputPathsByType; 
+/**
+ * What happens to DEBUG/INFO lines past a log burst; WARN/ERROR are always kept
+ */
+logBurstPolicy: LogBurstPolicy; 
+/**
+ * User-Agent, referer and extra headers sent with every download; tasks can override them
+ */
+defaultHeaders: RequestHeaders; 
+/**
+ * Check on startup that GitHub and a sample site are reachable (opt-in)
+ */
+startupNetworkCheck: boolean; 
+/**
+ * Largest page of log entries served to the log viewer (hard ceiling 2000)
+ */
+logMaxPageSize: number; 
+/**
+ * Automatic retries of a download that failed for a transient reason (network, rate limit); tasks can override it
+ */
+maxRetries: number; 
+/**
+ * Wait before the first automatic retry, doubled for each further one
+ */
+retryBackoffSecs: number; 
+/**
+ * Seconds the queue must sit idle before VACUUM and large log trims run
+ */
+cleanupIdleSecs: number; 
+/**
+ * How often the queue watchdog looks for pending tasks that aren't starting (0 = off)
+ */
+queueWatchdogSecs: number; 
+/**
+ * Most tasks started per app run; None = unlimited
+ */
+sessionDownloadLimit: number | null; 
+/**
+ * Most tasks started per local calendar day; None = unlimited
+ */
+dailyDownloadLimit: number | null; 
+/**
+ * Hosts downloads are limited to (`*.example.com` covers subdomains); empty allows all
+ */
+hostAllowlist: string[]; 
+/**
+ * Hosts downloads are refused from, checked before the allowlist
+ */
+hostDenylist: string[]; 
+/**
+ * Fingerprint history titles so `find_similar` can flag likely duplicates (looking up a new URL probes the site)
+ */
+similarityFingerprints: boolean; 
+/**
+ * yt-dlp binaries kept for rollback when it is updated or re-downloaded (0 keeps none)
+ */
+binaryBackupCount: number; 
+/**
+ * Preferred video codecs, best first, as yt-dlp names them (e.g. "av01", "h264")
+ */
+codecPreference: string[]; 
+/**
+ * Hold new downloads while on battery below this charge (1-100); None = never
+ */
+pauseBelowBatteryPercent: number | null; 
+/**
+ * Hold new downloads while total CPU usage stays above this (1-100); None = never
+ */
+pauseAboveCpuPercent: number | null; 
+/**
+ * Seconds between CPU usage samples
+ */
+cpuSampleSecs: number; 
+/**
+ * Seconds CPU usage must stay above (or back below) the threshold before downloads are held (or resumed)
+ */
+cpuSustainSecs: number; 
+/**
+ * Monthly download budget (e.g. "50G"); a warning is raised as it nears. None = no cap
+ */
+monthlyDataCap: string | null }
+/**
+ * How `import_full_backup` treats data already here.
+ */
+export type BackupImportMode = 
+/**
+ * Add what isn't here yet; local rows win on conflict
+ */
+"merge" | 
+/**
+ * Drop the queue, history, watches, presets and logs and take the backup's
+ */
+"replace"
+/**
+ * Rows brought in by `import_full_backup`, per kind.
+ */
+export type BackupRestoreCounts = { history: number; queue: number; watchedSources: number; presets: number; logs: number }
+/**
+ * Window reported by `get_bandwidth_usage`.
+ */
+export type BandwidthPeriod = "today" | "month" | "allTime"
+/**
+ * Bytes downloaded by completed tasks, against `monthly_data_cap`.
+ */
+export type BandwidthUsage = { period: BandwidthPeriod; 
+/**
+ * Downloaded in `period`
+ */
+bytes: number; 
+/**
+ * Downloaded this local month, which the cap applies to
+ */
+monthBytes: number; monthlyCap: number | null; 
+/**
+ * Share of the cap used this month; None without a cap
+ */
+capPercent: number | null; 
+/**
+ * This month is at or past the warning level of the cap
+ */
+nearCap: boolean; 
+/**
+ * First local day of next month, when the monthly count resets
+ */
+monthResetsAt: number }
+/**
+ * Battery reading and the low-battery hold (`get_battery_state`).
+ */
+export type BatteryState = { 
+/**
+ * False when the charge can't be read (no battery, or unsupported system)
+ */
+available: boolean; percent: number | null; onBattery: boolean | null; 
+/**
+ * `pauseBelowBatteryPercent`
+ */
+threshold: number | null; 
+/**
+ * New downloads are currently held for low battery
+ */
+paused: boolean }
+/**
+ * Result of running a binary with `--version`.
+ */
+export type BinaryDiagnosis = { path: string; runs: boolean; version: string | null; cause: ExecFailureCause | null; 
+/**
+ * Raw exec error or stderr of the failed run
+ */
+stderr: string | null; hint: string | null }
+/**
+ * The yt-dlp binary that downloads will actually use, and why.
+ */
+export type BinaryInfo = { path: string | null; version: string | null; source: DepSource; preferSystem: boolean }
+/**
+ * One entry of the video blocklist (`block_video`).
+ */
+export type BlockedVideo = { videoId: string; blockedAt: number }
+export type CacheClearResult = { removed: number; freedBytes: number }
+export type CacheKind = 
+/**
+ * Partial downloads in the temp dir
+ */
+"partial" | 
+/**
+ * Per-task codec sidecars yt-dlp prints while downloading
+ */
+"codecs"
+export type CacheKindStats = { kind: CacheKind; entries: number; totalBytes: number; 
+/**
+ * Entries no running or unfinished download uses (removed by `prune_cache`)
+ */
+orphaned: number }
+export type CacheStats = { kinds: CacheKindStats[] }
+/**
+ * Criteria for cancelling a subset of active tasks. Set conditions are ANDed.
+ */
+export type CancelFilter = { 
+/**
+ * Matches the URL host and its subdomains (e.g. "youtube.com" matches "www.youtube.com")
+ */
+host: string | null; playlistId: string | null; 
+/**
+ * Matches tasks carrying this user tag
+ */
+tag: string | null }
+export type CancelMatchResult = { count: number; taskIds: number[] }
+/**
+ * State of the idle-gated DB cleanup (`schedule_idle_cleanup`).
+ */
+export type CleanupStatus = { 
+/**
+ * Waiting for the queue to go idle
+ */
+due: boolean; idleSecsRequired: number; activeDownloads: number; 
+/**
+ * When the pending run was first held back by active downloads
+ */
+deferredSince: number | null; lastRunAt: number | null }
+/**
+ * An option in a yt-dlp config file that overlaps with a flag the app manages.
+ */
+export type ConfigConflict = { file: string; line: number; option: string; value: string | null; reason: string }
+export type CookieDomain = { domain: string; count: number; expired: number }
+/**
+ * A malformed line in a cookies file (1-based line number).
+ */
+export type CookieLineError = { line: number; message: string }
+/**
+ * Result of checking a Netscape-format cookies file (`validate_cookies_file`).
+ */
+export type CookiesValidation = { path: string; 
+/**
+ * Parsed without errors and contains at least one cookie
+ */
+valid: boolean; 
+/**
+ * Starts with the "# Netscape HTTP Cookie File" header
+ */
+hasHeader: boolean; total: number; 
+/**
+ * Cookies without an expiry (expire when the browser closes)
+ */
+session: number; expired: number; domains: CookieDomain[]; 
+/**
+ * First expired entries, for display
+ */
+expiredCookies: ExpiredCookie[]; errors: CookieLineError[] }
+/**
+ * CPU load sampling and the busy-system hold (`get_cpu_load_state`).
+ */
+export type CpuLoadState = { 
+/**
+ * Total CPU usage at the last sample; None before the first one or while off
+ */
+percent: number | null; 
+/**
+ * `pauseAboveCpuPercent`
+ */
+threshold: number | null; sampleSecs: number; sustainSecs: number; 
+/**
+ * New downloads are currently held for CPU load
+ */
+paused: boolean; 
+/**
+ * How long usage has been on the other side of the threshold, counting
+ * toward `sustain_secs`; None when the decision is settled
+ */
+pendingSecs: number | null }
+export type DepInfo = { installed: boolean; version: string | null; source: DepSource; path: string | null }
+export type DepInstallEvent = { depName: string; stage: DepInstallStage; percent: number; bytesDownloaded: number; bytesTotal: number | null; message: string | null }
+export type DepInstallStage = "Downloading" | "Verifying" | "Extracting" | "Completing" | "Failed"
+export type DepSource = "AppManaged" | "SystemPath" | 
+/**
+ * Chosen from the user's yt-dlp binary registry (`activeYtdlp`)
+ */
+"Registered" | "NotFound"
+export type DepUpdateInfo = { currentVersion: string | null; latestVersion: string; updateAvailable: boolean }
+export type DependencyStatus = { ytdlpInstalled: boolean; ytdlpVersion: string | null; ffmpegInstalled: boolean; ffmpegVersion: string | null; 
+/**
+ * Version set by `install_ffmpeg_version`; None follows the latest build
+ */
+ffmpegPinned: string | null; 
+/**
+ * Diagnostic info when ytdlp check fails (path tried, error reason)
+ */
+ytdlpDebug: string | null }
+/**
+ * Space taken by this app's downloads in one directory.
+ */
+export type DirUsage = { path: string; fileCount: number; totalBytes: number; 
+/**
+ * Files counted whose size is unknown (not recorded, and not stat-ed or gone)
+ */
+unknownSizeCount: number; 
+/**
+ * The directory no longer exists
+ */
+missing: boolean }
+/**
+ * Cause of a failed download (`error_kind::classify_download_error`).
+ */
+export type DownloadErrorKind = "auth" | "cookies" | "ageRestricted" | "geoBlocked" | "rateLimited" | "unavailable" | "formatUnavailable" | "diskFull" | "sizeLimit" | "encoding" | "postprocess" | "network" | "unknown"
+/**
+ * Which start limit holds back the queue.
+ */
+export type DownloadLimitKind = "session" | "daily"
+/**
+ * Starts counted against `session_download_limit` / `daily_download_limit`.
+ */
+export type DownloadLimitUsage = { sessionUsed: number; sessionLimit: number | null; dailyUsed: number; dailyLimit: number | null; 
+/**
+ * Set while a limit keeps pending tasks from starting
+ */
+reached: DownloadLimitKind | null; 
+/**
+ * Next local midnight, when the daily count resets
+ */
+dailyResetsAt: number }
+export type DownloadPreset = { name: string; options: PresetOptions; 
+/**
+ * Shipped with the app; can't be overwritten or deleted
+ */
+builtin: boolean }
+export type DownloadRequest = { videoUrl: string; videoId: string; title: string; formatId: string; qualityLabel: string; outputDir: string | null; cookieBrowser: string | null; 
+/**
+ * Source playlist id when queued from a playlist
+ */
+playlistId: string | null; 
+/**
+ * 1-based position in that playlist; fills `%(playlist_index)s` in the filename
+ */
+playlistIndex: number | null; 
+/**
+ * Site login / video password; kept in the OS keychain, never in the DB
+ */
+credentials: TaskCredentials | null; 
+/**
+ * Download best video and best audio as two separate files (`-f bv,ba`) without merging.
+ * Uses about the same disk space as a merged download, but the video file has no audio;
+ * merging them later needs room for a third, combined copy.
+ */
+keepSeparateStreams: boolean; 
+/**
+ * Size cap for this task (`--max-filesize`, e.g. "2G"; "0" = none); None uses the setting
+ */
+maxFilesize: string | null; 
+/**
+ * Preferred audio track language (e.g. "en"); falls back to the default track
+ */
+audioLang: string | null; 
+/**
+ * Embed title/uploader/date tags into the file (`--embed-metadata`)
+ */
+embedMetadata: boolean; 
+/**
+ * Embed the thumbnail as cover art (`--embed-thumbnail`)
+ */
+embedThumbnail: boolean; 
+/**
+ * Preset whose options fill the fields left empty here (see `save_preset`)
+ */
+preset: string | null; 
+/**
+ * `--live-from-start`: record a live stream from its beginning rather than from now
+ */
+liveFromStart: boolean; 
+/**
+ * HTTP headers layered over the `default_headers` setting
+ */
+headers: RequestHeaders | null; 
+/**
+ * Automatic retries for this task; None uses the `max_retries` setting
+ */
+maxRetries: number | null; 
+/**
+ * First wait before retrying (doubles per attempt); None uses the setting
+ */
+retryBackoffSecs: number | null; 
+/**
+ * What to do about a file already at the output path (`check_duplicate`);
+ * None lets yt-dlp skip it as already downloaded
+ */
+onExistingFile: ExistingFileAction | null; 
+/**
+ * Fetch only subtitles (`--skip-download --write-subs`); needs `subtitle_langs`
+ */
+subtitlesOnly: boolean; 
+/**
+ * Subtitle languages for `subtitles_only` (`--sub-langs`, e.g. "en", "ja.*", "all")
+ */
+subtitleLangs: string[]; 
+/**
+ * Convert fetched subtitles to this format (`--convert-subs`, e.g. "srt")
+ */
+convertSubs: string | null; 
+/**
+ * User tags for the task; the history row gets them as `user_tags`
+ */
+tags: string[] }
+export type DownloadStatus = "pending" | "downloading" | "paused" | "completed" | "failed" | "cancelled"
+export type DownloadTaskInfo = { id: number; videoUrl: string; videoId: string; title: string; formatId: string; qualityLabel: string; outputPath: string; status: DownloadStatus; progress: number; speed: string | null; eta: string | null; errorMessage: string | null; createdAt: number; completedAt: number | null; playlistId: string | null; 
+/**
+ * Credentials for this task are stored in the keychain
+ */
+hasCredentials: boolean; keepSeparateStreams: boolean; 
+/**
+ * History row this task re-downloads (`redownload_with_options`)
+ */
+replacesHistoryId: number | null; 
+/**
+ * Overwrite / remove the file of `replaces_history_id` once this task succeeds
+ */
+replaceExisting: boolean; 
+/**
+ * Per-task `--max-filesize` override
+ */
+maxFilesize: string | null; audioLang: string | null; embedMetadata: boolean; embedThumbnail: boolean; 
+/**
+ * Preset the task was queued with
+ */
+preset: string | null; liveFromStart: boolean; headers: RequestHeaders | null; playlistIndex: number | null; 
+/**
+ * Per-task retry overrides as stored
+ */
+maxRetries: number | null; retryBackoffSecs: number | null; 
+/**
+ * Automatic retries used so far
+ */
+retryCount: number; subtitlesOnly: boolean; subtitleLangs: string[]; convertSubs: string | null; 
+/**
+ * Tags from the request (`cancel_matching` by tag); copied to the history row
+ */
+userTags: string[]; 
+/**
+ * Policy in effect (overrides over settings); filled by `get_download_queue`
+ */
+retryPolicy: RetryPolicy | null; 
+/**
+ * A history row of this video is a favorite; filled by `get_download_queue`
+ */
+isFavorite: boolean }
+export type DrainPhase = 
+/**
+ * Downloads still running, no new ones starting
+ */
+"waiting" | 
+/**
+ * Timed out; the remaining downloads are being cancelled
+ */
+"cancelling" | 
+/**
+ * Queue empty, the app is about to exit
+ */
+"exiting"
+export type DuplicateCheckResult = { inHistory: boolean; inQueue: boolean; historyItem: HistoryItem | null; fileExists: boolean; 
+/**
+ * A file already sits where this download would be saved, whether or not
+ * history knows about it. Only checked when a URL is given.
+ */
+fileOnDisk: boolean; diskPath: string | null; 
+/**
+ * Free name for saving alongside it, relative to the download folder
+ */
+renameTo: string | null; 
+/**
+ * The video id is on the blocklist; queueing it will be refused
+ */
+blocked: boolean }
+/**
+ * Outcome of one `backfill_durations` batch.
+ */
+export type DurationBackfill = { 
+/**
+ * Rows without a duration looked at in this batch
+ */
+scanned: number; 
+/**
+ * Rows whose duration was probed and stored
+ */
+updated: number; 
+/**
+ * Pass back as `before_id` for the next batch; None when done
+ */
+nextBeforeId: number | null }
+export type EffectiveOption = { key: string; value: string | null; source: OptionSource }
+export type EffectiveOptions = { url: string; options: EffectiveOption[] }
+/**
+ * ERROR/WARN entries sharing a level, category and failure kind.
+ */
+export type ErrorGroup = { level: string; category: string; 
+/**
+ * Set for failed downloads only; other entries aren't classified
+ */
+kind: DownloadErrorKind | null; count: number; firstAt: number; lastAt: number; 
+/**
+ * Most frequent messages first
+ */
+topMessages: MessageCount[]; 
+/**
+ * Tasks named in the group's messages, ascending
+ */
+taskIds: number[] }
+export type ErrorKindCount = { kind: DownloadErrorKind; count: number }
+export type ErrorSummary = { since: number; until: number; total: number; 
+/**
+ * Earliest and latest entry in the window; None when it's empty
+ */
+firstAt: number | null; lastAt: number | null; 
+/**
+ * Largest group first
+ */
+groups: ErrorGroup[] }
+/**
+ * Why a binary failed to start (see `diagnose_ytdlp_binary`).
+ */
+export type ExecFailureCause = 
+/**
+ * Killed by Gatekeeper / still quarantined (macOS)
+ */
+"quarantine" | 
+/**
+ * Wrong CPU architecture or OS
+ */
+"execFormat" | 
+/**
+ * Not executable, blocked by antivirus or a noexec mount
+ */
+"permission" | 
+/**
+ * A shared library or runtime is missing
+ */
+"missingLibrary" | "timeout" | "other"
+/**
+ * Choice for a download whose output file already exists on disk.
+ */
+export type ExistingFileAction = 
+/**
+ * Write over the existing file
+ */
+"overwrite" | 
+/**
+ * Save under this name instead, relative to the download folder
+ * (`DuplicateCheckResult.rename_to`)
+ */
+{ rename: { filename: string } }
+export type ExpiredCookie = { domain: string; name: string; expiresAt: number }
+/**
+ * Extractors gained and lost between two locally available yt-dlp versions.
+ */
+export type ExtractorDiff = { versionA: string; versionB: string; 
+/**
+ * In version_b but not version_a
+ */
+added: string[]; 
+/**
+ * In version_a but not version_b
+ */
+removed: string[] }
+/**
+ * Result of `preview_output_filename`.
+ */
+export type FilenamePreview = { 
+/**
+ * Name relative to the download folder, subfolders from the template included
+ */
+filename: string; fullPath: string; 
+/**
+ * The URL is a playlist or channel and this is its first entry's name;
+ * the other entries follow the same template
+ */
+playlistExample: boolean; 
+/**
+ * `lint_output_template` findings for the template used
+ */
+warnings: TemplateWarning[] }
+export type FormatInfo = { formatId: string; ext: string; resolution: string | null; qualityLabel: string | null; filesize: number | null; vcodec: string | null; acodec: string | null; hasVideo: boolean; hasAudio: boolean; 
+/**
+ * Audio language tag, e.g. "en" (multi-language videos)
+ */
+language: string | null; 
+/**
+ * `filesize` if known, else yt-dlp's `filesize_approx`, else bitrate × duration
+ */
+sizeBytes: number | null; sizeAccuracy: SizeAccuracy; 
+/**
+ * Frame height in pixels; None for audio-only formats
+ */
+height: number | null; fps: number | null }
+/**
+ * One format in `format_recommendations`, best value first.
+ */
+export type FormatRec = { 
+/**
+ * Selector to download it with; "137+140" when merged with audio
+ */
+formatId: string; 
+/**
+ * e.g. "1080p" or "1080p60"
+ */
+label: string; ext: string; height: number; fps: number | null; vcodec: string | null; 
+/**
+ * Video plus merged audio
+ */
+sizeBytes: number; sizeAccuracy: SizeAccuracy; 
+/**
+ * Frame height (fps-weighted) per MiB; higher is better value
+ */
+qualityPerMib: number; recommended: boolean; 
+/**
+ * Smallest download at 480p or better
+ */
+smallestAcceptable: boolean; 
+/**
+ * Why it ranks where it does, for tooltips
+ */
+reasons: string[] }
+export type FullBackupImport = { manifest: FullBackupManifest; counts: BackupRestoreCounts; settingsRestored: boolean }
+/**
+ * Header of a full backup archive (`export_full_backup`).
+ */
+export type FullBackupManifest = { formatVersion: number; appVersion: string; 
+/**
+ * ytdlp.db schema the archive was taken at; older ones are migrated on import
+ */
+schemaVersion: number; 
+/**
+ * Unix seconds
+ */
+createdAt: number; 
+/**
+ * Secrets in settings.json are masked; such settings are not restored
+ */
+settingsRedacted: boolean }
+export type FullDependencyStatus = { ytdlp: DepInfo; ffmpeg: DepInfo; deno: DepInfo; 
+/**
+ * Version set by `install_ffmpeg_version`; None follows the latest build
+ */
+ffmpegPinned?: string | null }
+export type GlobalDownloadEvent = { taskId: number; eventType: string; percent: number | null; speed: string | null; eta: string | null; filePath: string | null; fileSize: number | null; message: string | null; 
+/**
+ * Bytes written so far; the progress measure of "liveProgress" events
+ */
+downloadedBytes: number | null; elapsedSecs: number | null }
+export type HealthCheck = { name: string; status: HealthStatus; message: string }
+export type HealthReport = { overall: HealthStatus; checks: HealthCheck[]; generatedAt: number }
+export type HealthStatus = "ok" | "warn" | "error"
+/**
+ * Everything known about one download, for the history detail view.
+ */
+export type HistoryDetail = { item: HistoryItem; fileExists: boolean; 
+/**
+ * Path of the `.info.json` sidecar when one exists
+ */
+infoJsonPath: string | null; 
+/**
+ * The sidecar as JSON text, with long arrays/strings summarized
+ */
+infoJson: string | null; 
+/**
+ * Queue task that produced this row, while it is still in the queue table
+ */
+taskId: number | null; 
+/**
+ * Log entries mentioning the task or the video, oldest first
+ */
+logs: LogEntry[] }
+export type HistoryItem = { id: number; videoUrl: string; videoId: string; title: string; qualityLabel: string; format: string; filePath: string; fileSize: number | null; downloadedAt: number; 
+/**
+ * Container/codec the file was re-encoded to (`--recode-video`), if any
+ */
+recodeTarget: string | null; 
+/**
+ * Global `maxHeight` cap applied to the format selector, if any
+ */
+maxHeight: number | null; 
+/**
+ * Media duration in seconds; None until probed (see `backfill_durations`)
+ */
+durationSecs: number | null; 
+/**
+ * Download task that re-downloaded this item with different options, once it completed
+ */
+supersededBy: number | null; 
+/**
+ * Chapter markers were embedded (`embedChapters` on and the file has chapters)
+ */
+chaptersEmbedded: boolean; 
+/**
+ * Container the file was losslessly remuxed into instead of recoded (`autoContainer`)
+ */
+remuxTarget: string | null; 
+/**
+ * None until backfilled by `normalize_history`
+ */
+uploader: string | null; 
+/**
+ * File container (extension), e.g. "mp4"
+ */
+container: string | null; tags: string[]; 
+/**
+ * Audio language of the download: the selected track's tag when yt-dlp reported
+ * one, else the requested `audio_lang`
+ */
+audioLang: string | null; 
+/**
+ * "verified" or "suspect" when `verify_downloads` checked the file
+ */
+verifyStatus: string | null; 
+/**
+ * ffprobe error or duration mismatch behind a "suspect" status
+ */
+verifyError: string | null; 
+/**
+ * Folder each kind of output file went to (None for rows before v18)
+ */
+destinations: OutputPathsByType | null; 
+/**
+ * Preset the download was queued with
+ */
+preset: string | null; 
+/**
+ * Video codec yt-dlp downloaded (e.g. "avc1.640028"); None for audio-only
+ * files and rows before v27
+ */
+vcodec: string | null; 
+/**
+ * Starred by the user, separate from tags (`toggle_favorite`)
+ */
+isFavorite: boolean; 
+/**
+ * Tags the user added (`bulk_tag`); `tags` are the site's own
+ */
+userTags: string[] }
+export type HistoryResult = { items: HistoryItem[]; totalCount: number; page: number; pageSize: number; 
+/**
+ * Sort the page was read with
+ */
+sortBy: HistorySortKey; sortDir: SortDir }
+/**
+ * What `get_download_history` orders by. Only these columns can be sorted on.
+ */
+export type HistorySortKey = "date" | "size" | "duration" | "title"
+/**
+ * min/avg/max wall time of repeated runs, in milliseconds.
+ */
+export type LatencyStats = { runs: number; minMs: number; avgMs: number; maxMs: number }
+export type LeftoverCleanup = { removed: number; reclaimedBytes: number; 
+/**
+ * Paths not removed: in use, not a partial, outside the download folders, or already gone
+ */
+skipped: string[] }
+/**
+ * A yt-dlp partial found by `list_leftover_files`.
+ */
+export type LeftoverFile = { 
+/**
+ * Also the id passed to `clean_leftover_files`
+ */
+path: string; size: number; modifiedAt: number | null; 
+/**
+ * Looks like it belongs to a pending, downloading or paused task; never removed
+ */
+inUse: boolean }
+/**
+ * Outcome of copying app data from a location earlier builds used.
+ */
+export type LegacyMigration = { 
+/**
+ * Legacy directory that held data, if one was found
+ */
+source: string | null; 
+/**
+ * Items copied into the current data dir, e.g. "ytdlp.db", "bin"
+ */
+migrated: string[]; 
+/**
+ * Legacy data was found but the current data dir was already in use
+ */
+keptCurrent: boolean; 
+/**
+ * Why the copy failed; nothing is kept and the next start retries
+ */
+error: string | null; 
+/**
+ * Unix timestamp of the check
+ */
+ranAt: number }
+export type LogArchiveResult = { archived: number; path: string }
+/**
+ * Log entries in one time bucket of `get_log_histogram`.
+ */
+export type LogBucket = { 
+/**
+ * Bucket start, ms timestamp (inclusive)
+ */
+start: number; 
+/**
+ * Bucket end, ms timestamp (exclusive)
+ */
+end: number; total: number; error: number; warn: number; info: number; debug: number }
+/**
+ * How log lines past a burst are handled (`modules::log_burst`).
+ */
+export type LogBurstPolicy = 
+/**
+ * Write every line
+ */
+"full" | 
+/**
+ * Keep one in ten lines past the threshold
+ */
+"sampled" | 
+/**
+ * Drop lines past the threshold and log how many were dropped
+ */
+"summarized"
+export type LogEntry = { id: number; timestamp: number; level: string; category: string; message: string; details: string | null }
+export type LogQueryResult = { items: LogEntry[]; totalCount: number; page: number; 
+/**
+ * Size actually served, after clamping the requested one
+ */
+pageSize: number; 
+/**
+ * Limit the request was clamped to (`log_max_page_size`, capped)
+ */
+maxPageSize: number }
+export type LogSearchHit = { entry: LogEntry; messageMatches: MatchRange[]; detailsMatches: MatchRange[] }
+export type LogSearchResult = { items: LogSearchHit[]; totalCount: number; page: number; pageSize: number }
+export type LogStats = { totalCount: number; errorCount: number; warnCount: number; infoCount: number }
+/**
+ * Server-side filter for `tail_logs`. Empty `levels` means any level.
+ */
+export type LogTailFilter = { levels: string[]; category: string | null; 
+/**
+ * Case-insensitive substring of message or details
+ */
+search: string | null }
+/**
+ * Byte range `[start, end)` of a search match within a log field.
+ */
+export type MatchRange = { start: number; end: number }
+/**
+ * One message of an `ErrorGroup`, with the `[download:N]` tag stripped.
+ */
+export type MessageCount = { message: string; count: number }
+/**
+ * One host probed by `test_network`.
+ */
+export type NetworkCheck = { 
+/**
+ * Stable key ("github", "githubApi", "youtube")
+ */
+name: string; url: string; reachable: boolean; 
+/**
+ * HTTP status of the answer; any status counts as reachable
+ */
+status: number | null; latencyMs: number | null; error: string | null }
+export type NetworkReport = { allReachable: boolean; checks: NetworkCheck[]; checkedAt: number }
+export type NewLogEvent = { entry: LogEntry }
+/**
+ * Estimate from `get_next_start`.
+ */
+export type NextStart = { taskId: number; title: string; reason: NextStartReason; 
+/**
+ * Seconds until the task starts; None when no running task reports an ETA
+ */
+etaSecs: number | null; 
+/**
+ * Unix time of `eta_secs`
+ */
+startsAt: number | null; confidence: NextStartConfidence }
+export type NextStartConfidence = 
+/**
+ * Known from the slot count and start delay
+ */
+"high" | 
+/**
+ * Based on yt-dlp's ETA of every running task
+ */
+"medium" | 
+/**
+ * Some or all running tasks report no ETA
+ */
+"low"
+/**
+ * What the next pending task is waiting for.
+ */
+export type NextStartReason = 
+/**
+ * A slot is free; it starts on the next queue pass
+ */
+"ready" | 
+/**
+ * A slot is free but the start delay has not passed
+ */
+"startDelay" | 
+/**
+ * Every slot is busy
+ */
+"waitingForSlot" | 
+/**
+ * New starts are paused by hand or while waiting to quit
+ */
+"paused" | 
+/**
+ * Held on battery below `pauseBelowBatteryPercent`
+ */
+"batteryLow" | 
+/**
+ * Held while the CPU is above `pauseAboveCpuPercent`
+ */
+"cpuBusy" | 
+/**
+ * `sessionDownloadLimit` reached; waits for a higher limit or a restart
+ */
+"sessionLimit" | 
+/**
+ * `dailyDownloadLimit` reached; the ETA is the reset at local midnight
+ */
+"dailyLimit"
+/**
+ * Per-row progress of a `normalize_history` batch.
+ */
+export type NormalizeProgress = { historyId: number; 
+/**
+ * Rows handled so far in this batch, including this one
+ */
+processed: number; batchSize: number; 
+/**
+ * At least one missing field was filled in
+ */
+updated: boolean }
+/**
+ * Outcome of one `normalize_history` batch.
+ */
+export type NormalizeSummary = { scanned: number; updated: number; 
+/**
+ * Pass back as `before_id` for the next batch; None when done
+ */
+nextBeforeId: number | null }
+/**
+ * Where an effective option value came from.
+ */
+export type OptionSource = "default" | "settings" | "task"
+/**
+ * An app-managed yt-dlp/ffmpeg process that no running app instance owns.
+ */
+export type OrphanProcess = { pid: number; name: string; exe: string; 
+/**
+ * Terminated by `reap_orphaned_processes`
+ */
+killed: boolean }
+/**
+ * Download folder per kind of output file. Unset kinds use the download path.
+ */
+export type OutputPathsByType = { video: string | null; 
+/**
+ * Audio-only downloads
+ */
+audio: string | null; subtitle: string | null; thumbnail: string | null }
+export type PlaylistEntry = { url: string; videoId: string; title: string | null; duration: number | null; thumbnail: string | null }
+/**
+ * Result of `export_playlist`.
+ */
+export type PlaylistExport = { path: string; entries: number; 
+/**
+ * Items whose file is gone (or unknown ids), in the order given
+ */
+skipped: PlaylistSkip[] }
 /**
- * Whether the initial setup wizard has been completed
+ * File type written by `export_playlist`.
  */
-setupCompleted: boolean }
-export type DepInfo = { installed: boolean; version: string | null; source: DepSource; path: string | null }
-export type DepInstallEvent = { depName: string; stage: DepInstallStage; percent: number; bytesDownloaded: number; bytesTotal: number | null; message: string | null }
-export type DepInstallStage = "Downloading" | "Verifying" | "Extracting" | "Completing" | "Failed"
-export type DepSource = "AppManaged" | "SystemPath" | "NotFound"
-export type DepUpdateInfo = { currentVersion: string | null; latestVersion: string; updateAvailable: boolean }
-export type DependencyStatus = { ytdlpInstalled: boolean; ytdlpVersion: string | null; ffmpegInstalled: boolean; ffmpegVersion: string | null; 
+export type PlaylistFormat = "m3u" | 
 /**
- * Diagnostic info when ytdlp check fails (path tried, error reason)
+ * M3U with UTF-8 paths and titles (the `.m3u8` extension tells players so)
  */
-ytdlpDebug: string | null }
-export type DownloadRequest = { videoUrl: string; videoId: string; title: string; formatId: string; qualityLabel: string; outputDir: string | null; cookieBrowser: string | null }
-export type DownloadStatus = "pending" | "downloading" | "paused" | "completed" | "failed" | "cancelled"
-export type DownloadTaskInfo = { id: number; videoUrl: string; videoId: string; title: string; formatId: string; qualityLabel: string; outputPath: string; status: DownloadStatus; progress: number; speed: string | null; eta: string | null; errorMessage: string | null; createdAt: number; completedAt: number | null }
-export type DuplicateCheckResult = { inHistory: boolean; inQueue: boolean; historyItem: HistoryItem | null; fileExists: boolean }
-export type FormatInfo = { formatId: string; ext: string; resolution: string | null; qualityLabel: string | null; filesize: number | null; vcodec: string | null; acodec: string | null; hasVideo: boolean; hasAudio: boolean }
-export type FullDependencyStatus = { ytdlp: DepInfo; ffmpeg: DepInfo; deno: DepInfo }
-export type GlobalDownloadEvent = { taskId: number; eventType: string; percent: number | null; speed: string | null; eta: string | null; filePath: string | null; fileSize: number | null; message: string | null }
-export type HistoryItem = { id: number; videoUrl: string; videoId: string; title: string; qualityLabel: string; format: string; filePath: string; fileSize: number | null; downloadedAt: number }
-export type HistoryResult = { items: HistoryItem[]; totalCount: number; page: number; pageSize: number }
-export type LogEntry = { id: number; timestamp: number; level: string; category: string; message: string; details: string | null }
-export type LogQueryResult = { items: LogEntry[]; totalCount: number; page: number; pageSize: number }
-export type LogStats = { totalCount: number; errorCount: number; warnCount: number; infoCount: number }
-export type NewLogEvent = { entry: LogEntry }
-export type PlaylistEntry = { url: string; videoId: string; title: string | null; duration: number | null; thumbnail: string | null }
+"m3u8" | "pls"
+/**
+ * New position of a task renumbered by `renumber_playlist_tasks`.
+ */
+export type PlaylistIndexChange = { taskId: number; title: string; oldIndex: number | null; newIndex: number }
 export type PlaylistResult = { playlistId: string; title: string; url: string; videoCount: number | null; channelName: string | null; entries: PlaylistEntry[] }
+/**
+ * A history item left out of an exported playlist.
+ */
+export type PlaylistSkip = { historyId: number; 
+/**
+ * None when no history item has this id
+ */
+title: string | null; filePath: string | null }
+/**
+ * Finishing steps `rerun_postprocessing` applies to a downloaded file.
+ */
+export type PostprocessSteps = { 
+/**
+ * Title/uploader/date tags (`--embed-metadata`)
+ */
+embedMetadata: boolean; 
+/**
+ * Cover art (`--embed-thumbnail`)
+ */
+embedThumbnail: boolean; 
+/**
+ * Subtitle tracks in `subtitle_langs` (`--embed-subs`)
+ */
+embedSubs: boolean; 
+/**
+ * Chapter markers (`--embed-chapters`)
+ */
+embedChapters: boolean; subtitleLangs: string[] }
+/**
+ * Sleep/wake handling state, reported by `get_power_status`.
+ */
+export type PowerStatus = { 
+/**
+ * How wake-ups are detected ("clock-gap": wall clock jumped between timer ticks)
+ */
+wakeDetection: string; lastWakeAt: number | null; lastSleepSecs: number | null; 
+/**
+ * Running downloads stopped and re-queued after the last wake
+ */
+requeuedOnLastWake: number }
+/**
+ * Options a preset fills into a `DownloadRequest`. Unset fields leave the request alone.
+ */
+export type PresetOptions = { formatId: string | null; qualityLabel: string | null; outputDir: string | null; keepSeparateStreams: boolean | null; maxFilesize: string | null; audioLang: string | null; embedMetadata: boolean | null; embedThumbnail: boolean | null }
+/**
+ * Payload of `queue-drain-event`, sent during `drain_and_exit`.
+ */
+export type QueueDrainEvent = { phase: DrainPhase; active: number; elapsedSecs: number; timeoutSecs: number }
+export type QueueEvent = { kind: QueueEventKind; taskId: number | null; percent: number | null; message: string | null; tasks: DownloadTaskInfo[] | null }
+/**
+ * Coarse queue state transitions for list views (`watch_queue`).
+ * Per-tick progress stays on `download-event`.
+ */
+export type QueueEventKind = 
+/**
+ * Full queue, sent once on subscribe and again if the subscriber fell behind
+ */
+"snapshot" | "added" | "started" | 
+/**
+ * Crossed 25/50/75%
+ */
+"progress" | "completed" | "failed" | "cancelled"
+/**
+ * Count and a bounded sample of tasks for one status.
+ */
+export type QueueGroup = { status: DownloadStatus; count: number; items: DownloadTaskInfo[] }
+/**
+ * Queue grouped by status; every status is present, even with a zero count.
+ */
+export type QueueGrouped = { groups: QueueGroup[]; totalCount: number }
 export type QueueResult = { items: DownloadTaskInfo[]; totalCount: number; page: number; pageSize: number; activeCount: number; pendingCount: number; completedCount: number; failedCount: number; cancelledCount: number }
+/**
+ * Outcome of one queue watchdog check (`check_queue_stall`).
+ */
+export type QueueStallCheck = { pending: number; 
+/**
+ * Tasks downloading according to the DB
+ */
+running: number; 
+/**
+ * Pending tasks were waiting with free slots and nothing running
+ */
+stalled: boolean; 
+/**
+ * Tasks the watchdog started to get the queue moving again
+ */
+started: number }
 export type QueueSummary = { activeItems: DownloadTaskInfo[]; recentCompleted: DownloadTaskInfo[]; activeCount: number; pendingCount: number; completedCount: number; totalCount: number }
 export type QuickMetadata = { videoId: string; title: string; channel: string; channelUrl: string; thumbnail: string }
+export type ReclassifyResult = { 
+/**
+ * Rows classified by this run; already classified rows are skipped
+ */
+classified: number; byKind: ErrorKindCount[] }
+/**
+ * Overrides for `redownload_with_options`; unset fields keep the history row's values.
+ */
+export type RedownloadOptions = { formatId: string | null; qualityLabel: string | null; 
+/**
+ * Defaults to the folder of the existing file
+ */
+outputDir: string | null; keepSeparateStreams: boolean; 
+/**
+ * Overwrite the existing file, or delete it once the new download succeeds
+ * if the new file ends up under a different name
+ */
+replaceExisting: boolean }
+/**
+ * One item of a `preview_rename` plan. Pass the ones to rename, unchanged,
+ * to `apply_rename`.
+ */
+export type RenamePlan = { historyId: number; title: string; from: string; 
+/**
+ * New path; None when it couldn't be worked out
+ */
+to: string | null; status: RenameStatus; detail: string | null }
+/**
+ * What `apply_rename` would do with one history item.
+ */
+export type RenameStatus = 
+/**
+ * Can be renamed
+ */
+"ready" | 
+/**
+ * Already named as the template says
+ */
+"unchanged" | 
+/**
+ * The new name is taken on disk or by another item
+ */
+"collision" | 
+/**
+ * The file isn't there any more
+ */
+"missing" | 
+/**
+ * The template can't be filled in for this item (see `detail`)
+ */
+"unresolved"
+/**
+ * Layout of `export_task_report` output.
+ */
+export type ReportFormat = "json" | "text"
+/**
+ * Extra HTTP headers for yt-dlp requests (see `http_headers`).
+ */
+export type RequestHeaders = { 
+/**
+ * `--user-agent`
+ */
+userAgent: string | null; 
+/**
+ * `--referer`; sites that hotlink-protect their media often need it
+ */
+referer: string | null; 
+/**
+ * `--add-header` entries as `Name: value`
+ */
+headers: string[] }
+/**
+ * Automatic retry policy of a task.
+ */
+export type RetryPolicy = { maxRetries: number; backoffSecs: number; 
+/**
+ * At least one value comes from the task rather than settings
+ */
+overridden: boolean }
+/**
+ * What yt-dlp would download for a request, from `test_format_selection`.
+ */
+export type SelectedFormat = { 
+/**
+ * `--format` the download would run with, after `maxHeight`, audio language
+ * and codec preference
+ */
+selector: string; 
+/**
+ * Every format downloaded; more than one when streams are kept separate ("bv,ba")
+ */
+streams: SelectedStream[]; 
+/**
+ * True when this came from a recent identical probe
+ */
+cached: boolean }
+/**
+ * One format yt-dlp would download, from `test_format_selection`.
+ */
+export type SelectedStream = { 
+/**
+ * Format id; "137+140" when video and audio are merged
+ */
+formatId: string; 
+/**
+ * e.g. "1920x1080", or "audio only"
+ */
+resolution: string | null; vcodec: string | null; acodec: string | null }
+/**
+ * One problem found by `validate_settings`.
+ */
+export type SettingIssue = { 
+/**
+ * camelCase settings key; nested values as `outputPathsByType.video`
+ */
+field: string; message: string; 
+/**
+ * Reported but doesn't keep the settings from being saved
+ */
+warning: boolean }
+/**
+ * A history item that likely holds the same content (`find_similar`).
+ */
+export type SimilarItem = { item: HistoryItem; 
+/**
+ * 0-1; title overlap, weighted with duration agreement when both are known
+ */
+confidence: number; 
+/**
+ * None when either duration is unknown
+ */
+durationDiffSecs: number | null }
+/**
+ * Where a `FormatInfo::size_bytes` comes from.
+ */
+export type SizeAccuracy = 
+/**
+ * Reported by the site
+ */
+"exact" | 
+/**
+ * yt-dlp's estimate or bitrate × duration
+ */
+"approx" | 
+/**
+ * Neither a size nor a bitrate is known
+ */
+"unknown"
+/**
+ * Result of `estimate_download_size`.
+ */
+export type SizeEstimate = { 
+/**
+ * Expected size in bytes; None when the site reports neither size nor bitrate
+ */
+bytes: number | null; 
+/**
+ * Effective `--max-filesize` cap in bytes, if any
+ */
+capBytes: number | null; 
+/**
+ * `bytes` is known and over the cap
+ */
+exceedsCap: boolean }
+/**
+ * Outcome of `reset_manager_slots`.
+ */
+export type SlotReset = { 
+/**
+ * Slots the manager counted as taken before the reset
+ */
+previous: number; 
+/**
+ * Tasks actually downloading according to the DB
+ */
+actual: number; maxConcurrent: number; corrected: boolean }
+/**
+ * yt-dlp process start-up cost, from `benchmark_ytdlp_spawn`.
+ */
+export type SortDir = "asc" | "desc"
+export type SpawnBench = { ytdlpPath: string; version: string | null; 
+/**
+ * `yt-dlp --version`: process spawn + Python start-up
+ */
+spawn: LatencyStats | null; 
+/**
+ * `--simulate` on a short public video: start-up plus extraction (network)
+ */
+simulate: LatencyStats | null; 
+/**
+ * Why the `--simulate` runs stopped, e.g. when offline
+ */
+simulateError: string | null; measuredAt: number }
+/**
+ * Per-task authentication for `--username` / `--password` / `--video-password`.
+ */
+export type TaskCredentials = { username: string | null; password: string | null; videoPassword: string | null }
+/**
+ * Per-task values that take precedence over global settings.
+ */
+export type TaskOverrides = { quality: string | null; outputDir: string | null; filenameTemplate: string | null; cookieBrowser: string | null; maxFilesize: string | null }
+/**
+ * Latest progress of a running task (`get_active_progress`).
+ */
+export type TaskProgress = { taskId: number; stage: TaskStage; 
+/**
+ * None until the first progress line, and for live streams
+ */
+percent: number | null; speed: string | null; eta: string | null; downloadedBytes: number | null; 
+/**
+ * Unix seconds of the last update
+ */
+updatedAt: number }
+export type TaskReportResult = { path: string; logCount: number }
+/**
+ * What a running task is doing right now.
+ */
+export type TaskStage = 
+/**
+ * Process launched, no progress line yet (extracting info)
+ */
+"starting" | "downloading" | 
+/**
+ * Live stream without a known total size
+ */
+"live" | 
+/**
+ * Merging, converting or embedding after the download
+ */
+"processing"
+/**
+ * A running task whose download hasn't advanced for a while (`get_stalled_tasks`).
+ */
+export type TaskStall = { taskId: number; stage: TaskStage; 
+/**
+ * Seconds since downloaded bytes or percent last changed (or since start)
+ */
+stalledSecs: number; percent: number | null; downloadedBytes: number | null }
+/**
+ * One finding of `lint_output_template`.
+ */
+export type TemplateWarning = { kind: TemplateWarningKind; 
+/**
+ * Field the warning is about, e.g. "title"
+ */
+field: string | null; 
+/**
+ * What's wrong and how to fix it
+ */
+message: string }
+export type TemplateWarningKind = 
+/**
+ * The file name part has no `%(ext)s`
+ */
+"missingExtension" | 
+/**
+ * A field many sites leave empty, used without a default
+ */
+"emptyProne" | 
+/**
+ * Unbounded long fields or long fixed text; Windows paths stop at 260 characters
+ */
+"pathLength" | 
+/**
+ * Backslashes or empty folder names
+ */
+"separator" | 
+/**
+ * A field yt-dlp can't parse
+ */
+"malformed"
+export type Throughput = { since: number; until: number; completed: number; completionsPerHour: number; 
+/**
+ * Completions that have both enqueue and start times (recorded since v24)
+ */
+timed: number; 
+/**
+ * Enqueue to first start; None when no completion in the window is timed
+ */
+avgWaitSecs: number | null; maxWaitSecs: number | null; 
+/**
+ * First start to completion, retries included
+ */
+avgRunSecs: number | null; 
+/**
+ * Hourly completions, oldest first, empty hours included
+ */
+buckets: ThroughputBucket[] }
+/**
+ * Completions in one clock hour, for `Throughput.buckets`.
+ */
+export type ThroughputBucket = { hourStart: number; completed: number }
+/**
+ * Sum of the latest speed readings across downloading tasks.
+ */
+export type TotalSpeed = { bytesPerSec: number; 
+/**
+ * Tasks with a recent speed reading (stale ones count as 0)
+ */
+reportingTasks: number }
+/**
+ * Layout of a URL list file for `import_urls_from_file`.
+ */
+export type UrlFileFormat = 
+/**
+ * One URL per line
+ */
+"lines" | 
+/**
+ * CSV with a `url`/`link` column
+ */
+"csv" | 
+/**
+ * JSON array of URLs or of objects with a `url` field
+ */
+"json"
+export type UrlImportLine = { 
+/**
+ * 1-based line (or JSON array index) in the file
+ */
+line: number; url: string; status: UrlImportStatus; taskId: number | null; message: string | null }
+export type UrlImportResult = { queued: number; skipped: number; failed: number; lines: UrlImportLine[] }
+export type UrlImportStatus = "queued" | 
+/**
+ * Already downloaded and the file is still there
+ */
+"alreadyDownloaded" | "alreadyQueued" | 
+/**
+ * Same video earlier in this file
+ */
+"duplicateInFile" | 
+/**
+ * The video id is on the blocklist
+ */
+"blocked" | "invalid" | 
+/**
+ * Playlist/channel URLs have to be expanded on the download page
+ */
+"unsupported" | "failed"
 export type UrlType = "video" | "channel" | "playlist" | "unknown"
-export type UrlValidation = { valid: boolean; urlType: UrlType; normalizedUrl: string | null; videoId: string | null }
-export type VideoInfo = { url: string; videoId: string; title: string; thumbnail: string; duration: number; uploadDate: string; channel: string; channelUrl: string; formats: FormatInfo[]; filesizeApprox: number | null }
+export type UrlValidation = { valid: boolean; urlType: UrlType; normalizedUrl: string | null; videoId: string | null; 
+/**
+ * `list=` id carried by a single-video URL, so the UI can offer the
+ * whole playlist instead (or the single video when treated as a playlist)
+ */
+playlistId: string | null }
+export type VideoInfo = { url: string; videoId: string; title: string; thumbnail: string; duration: number; uploadDate: string; channel: string; channelUrl: string; formats: FormatInfo[]; filesizeApprox: number | null; 
+/**
+ * Distinct languages of the audio formats, for an audio-track picker
+ */
+audioLanguages: string[]; 
+/**
+ * yt-dlp `live_status`: "is_live", "is_upcoming", "post_live", "was_live", "not_live"
+ */
+liveStatus: string | null; 
+/**
+ * Live, upcoming or still processing: `live_from_start` applies
+ */
+liveEvent: boolean }
+/**
+ * A channel or playlist polled for new uploads.
+ */
+export type WatchedSource = { id: number; url: string; 
+/**
+ * Playlist/channel title from the last successful check
+ */
+title: string | null; intervalSecs: number; createdAt: number; 
+/**
+ * None until the first check (which only records existing entries)
+ */
+lastCheckedAt: number | null; 
+/**
+ * Downloads queued by the last check
+ */
+lastNewCount: number; lastError: string | null }
+/**
+ * Result of trying to create and delete a file in a data directory.
+ */
+export type WritableCheck = { path: string; writable: boolean; 
+/**
+ * OS error message when not writable
+ */
+error: string | null; 
+/**
+ * `std::io::ErrorKind` name, e.g. "PermissionDenied" or "ReadOnlyFilesystem"
+ */
+errorKind: string | null }
+/**
+ * A yt-dlp binary kept for rollback (`list_ytdlp_backups`).
+ */
+export type YtdlpBackup = { 
+/**
+ * 1 = the binary replaced most recently; pass to `rollback_ytdlp_update`
+ */
+index: number; 
+/**
+ * None when the copy no longer runs
+ */
+version: string | null; size: number; 
+/**
+ * Unix seconds
+ */
+modifiedAt: number | null; path: string }
+/**
+ * A yt-dlp binary from the user's registry, probed with `--version`.
+ */
+export type YtdlpBinary = { label: string; path: string; version: string | null; 
+/**
+ * Why the binary failed to run, when `version` is None
+ */
+error: string | null; active: boolean }
 
 /** tauri-specta globals **/
 
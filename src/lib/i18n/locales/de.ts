@@ -18,9 +18,17 @@ const de: Record<string, string> = {
   "layout.notInstalled": "Nicht installiert",
   "layout.ffmpegNote": "Die Installation von ffmpeg ermöglicht das Zusammenführen von Videos in bester Qualität.",
   "layout.noActiveDownloads": "Keine aktiven Downloads",
+  "layout.live": "Live",
+  "layout.networkUnreachable": "Einige Hosts sind nicht erreichbar. Installationen oder Downloads können fehlschlagen; prüfe Firewall oder Proxy",
+  "layout.networkRetest": "Erneut prüfen",
+  "layout.networkDismiss": "Schließen",
+  "layout.drainWaiting": "Beenden, sobald {count} Download(s) fertig sind...",
+  "layout.drain.cancelling": "Zeitlimit erreicht, verbleibende Downloads werden abgebrochen...",
+  "layout.drain.exiting": "Downloads fertig, wird beendet...",
   "layout.cancelAll": "Alle abbrechen",
   "layout.queueAdded": "{count} Video(s) zur Warteschlange hinzugefügt.",
   "layout.downloadComplete": "Download von {title} abgeschlossen.",
+  "layout.dataCapWarning": "Die Downloads dieses Monats liegen bei {percent}% deines Datenlimits",
   "layout.queued": "Wartend",
   "layout.recent": "Kürzlich",
   "layout.viewAll": "Alle anzeigen",
@@ -64,7 +72,12 @@ const de: Record<string, string> = {
   "debug.resetDone": "Zurücksetzen abgeschlossen. Neustart...",
 
   "download.invalidUrl": "Ungültige URL",
+  "download.partOfPlaylist": "Dieses Video ist Teil einer Playlist. Nur dieses Video wird heruntergeladen.",
+  "download.openedAsPlaylist": "Dieser Link wurde als Playlist geöffnet.",
+  "download.wholePlaylistInstead": "Stattdessen ganze Playlist herunterladen",
+  "download.singleVideoInstead": "Nur dieses Video",
   "download.alreadyInQueue": "Dieses Video befindet sich bereits in der Warteschlange.",
+  "download.videoBlocked": "Dieses Video steht auf deiner Sperrliste und wird nicht heruntergeladen.",
   "download.analyzing": "Metadaten werden abgerufen...",
   "download.loadingFormats": "Videoformate werden geladen...",
   "download.urlPlaceholder": "Video-URL einfügen (YouTube, TikTok, Twitter, Vimeo...)",
@@ -92,6 +105,9 @@ const de: Record<string, string> = {
   "download.uploaderFolder": "Uploader-Ordner",
   "download.uploadDate": "Upload-Datum",
   "download.videoId": "Video-ID",
+  "download.templateWarnings": "{count} Vorlagenwarnung(en)",
+  "download.caseCollision": "Ordner ohne Groß-/Kleinschreibung",
+  "download.caseCollisionHint": "Dieser Ordner behandelt 'Title' und 'title' als dieselbe Datei, daher überschreiben sich Videos, deren Titel sich nur in der Schreibweise unterscheiden. Nimm die Video-ID in den Dateinamen auf.",
   "download.advancedTooltip": "yt-dlp-Vorlagensyntax direkt eingeben",
   "download.uploaderFolderTooltip": "Unterordner nach Kanal-/Uploader-Name erstellen",
   "download.uploadDateTooltip": "Upload-Datum vor dem Dateinamen hinzufügen",
@@ -101,13 +117,43 @@ const de: Record<string, string> = {
   "download.downloadFailed": "Download fehlgeschlagen",
   "download.skippedQueue": "{count} Video(s) übersprungen (bereits in der Warteschlange).",
   "download.skippedExists": "{count} Video(s) übersprungen (bereits heruntergeladen).",
+  "download.skippedBlocked": "{count} Video(s) übersprungen (gesperrt).",
+  "download.importFromFile": "URLs aus Datei importieren",
+  "download.urlListFile": "URL-Liste",
+  "download.importSummary": "{queued} Video(s) importiert; {skipped} übersprungen, {failed} ungültig oder fehlgeschlagen.",
+  "download.audioLanguage": "Audiosprache",
+  "download.audioLanguageDefault": "Originalton",
+  "download.liveFromStart": "Von Anfang an",
+  "download.liveFromStartHint": "Den Livestream von Beginn an statt ab jetzt aufnehmen",
+  "download.subtitlesOnly": "Nur Untertitel",
+  "download.subtitlesOnlyHint": "Nur die Untertitel als SRT-Dateien speichern, ohne das Video",
+  "download.subtitleLangs": "Untertitelsprachen, durch Kommas getrennt (z. B. en, ja.*, all)",
+  "download.testFormat": "Auswahl testen",
+  "download.testFormatHint": "Zeigt, welches Format yt-dlp mit den aktuellen Einstellungen wählen würde, ohne herunterzuladen",
+  "download.testFormatFailed": "Test fehlgeschlagen: {error}",
+  "download.suggestFormat": "Format vorschlagen",
+  "download.suggestFormatHint": "Die Formate dieses Videos nach Qualität im Verhältnis zur Größe ordnen",
+  "download.noFormatRecs": "Keine Formate mit bekannter Größe zum Vergleichen",
+  "download.recommended": "Empfohlen",
+  "download.smallestAcceptable": "Kleinste brauchbare",
+  "download.ffmpegMissing": "Diese Optionen benötigen ffmpeg, das nicht installiert ist. Sie schlagen fehl oder werden übersprungen:",
+  "download.ffmpegFeature.merge": "Video und Audio zusammenführen",
+  "download.ffmpegFeature.recode": "Neu kodieren",
+  "download.ffmpegFeature.embedMetadata": "Metadaten einbetten",
+  "download.ffmpegFeature.embedThumbnail": "Vorschaubild einbetten",
+  "download.ffmpegFeature.embedChapters": "Kapitel einbetten",
+  "download.ffmpegFeature.convertSubs": "Untertitel umwandeln",
   "download.redownload": "Erneut herunterladen",
   "download.cancel": "Abbrechen",
   "download.alreadyDownloaded": "\u201E{title}\u201C wurde bereits heruntergeladen.",
+  "download.fileOnDisk": "Bereits auf dem Datenträger: {path}",
+  "download.overwrite": "Überschreiben",
+  "download.saveAsCopy": "Als Kopie speichern",
   "download.error": "Fehler",
   "download.duplicateFound": "Duplikat gefunden",
   "download.include": "Einschließen:",
   "download.cookie": "Cookie:",
+  "download.defaultBrowser": "{browser} (Standard)",
   "download.concurrent": "Gleichzeitig:",
   "download.emptyState": "URL einfügen, um den Download zu starten",
 
@@ -132,6 +178,37 @@ const de: Record<string, string> = {
   "history.searchPlaceholder": "Nach Titel suchen...",
   "history.empty": "Kein Download-Verlauf",
   "history.deleteConfirm": "Diesen Eintrag löschen?",
+  "history.superseded": "Erneut heruntergeladen",
+  "history.suspect": "Verdächtig",
+  "history.bulkTagPlaceholder": "Tag",
+  "history.bulkTag": "Alle {count} Treffer taggen",
+  "history.bulkUntag": "Tag von Treffern entfernen",
+  "history.bulkTagResult": "{count} Einträge geändert",
+  "history.favoritesOnly": "Nur Favoriten",
+  "history.favorite": "Zu Favoriten hinzufügen",
+  "history.unfavorite": "Aus Favoriten entfernen",
+  "history.sortBy": "Sortieren nach",
+  "history.sortDate": "Datum",
+  "history.sortSize": "Größe",
+  "history.sortDuration": "Dauer",
+  "history.sortTitle": "Titel",
+  "history.sortAsc": "Aufsteigend",
+  "history.sortDesc": "Absteigend",
+  "history.renameFiles": "Dateien umbenennen",
+  "history.renameHint": "Heruntergeladene Dateien nach einer neuen Ausgabevorlage umbenennen. Dateien bleiben in ihrem Ordner; erst Vorschau, dann anwenden.",
+  "history.renameTemplate": "Ausgabevorlage",
+  "history.renamePreview": "Vorschau",
+  "history.renameApply": "{count} umbenennen",
+  "history.renameConfirm": "{count} Datei(en) auf dem Datenträger umbenennen?",
+  "history.renameDone": "{count} Datei(en) umbenannt",
+  "history.renameNothing": "Alle Dateien entsprechen bereits dieser Vorlage",
+  "history.renameStatus.ready": "Umbenennen",
+  "history.renameStatus.collision": "Name belegt",
+  "history.renameStatus.missing": "Datei fehlt",
+  "history.renameStatus.unresolved": "Nicht ausfüllbar",
+  "history.renameStatus.unchanged": "Unverändert",
+  "history.exportPlaylist": "Seite als Playlist exportieren",
+  "history.playlistExported": "Playlist mit {count} Eintrag/Einträgen gespeichert; {skipped} übersprungen (Datei fehlt).",
 
   "settings.title": "Einstellungen",
   "settings.subtitle": "Download-Einstellungen konfigurieren",
@@ -150,10 +227,21 @@ const de: Record<string, string> = {
   "settings.theme": "Design",
   "settings.general": "Allgemein",
   "settings.appearance": "Erscheinungsbild",
+  "settings.issuesTitle": "Die Einstellungen werden erst gespeichert, wenn diese Probleme behoben sind",
+  "settings.warningsTitle": "Gespeichert, aber bitte diese Einstellungen prüfen",
   "settings.dependencies": "Abhängigkeiten",
   "settings.notInstalled": "Nicht installiert",
   "settings.appManaged": "App-verwaltet",
+  "settings.ffmpegPinned": "Fixiert auf {version}",
+  "settings.ffmpegPin": "Version fixieren",
+  "settings.ffmpegRollback": "Zurücksetzen",
+  "settings.ffmpegUnpin": "Fixierung aufheben",
+  "settings.ffmpegUnpinned": "Fixierung aufgehoben; Updates folgen dem neuesten Build",
+  "settings.ytdlpBackupCount": "Gespeicherte Backups",
+  "settings.ytdlpBackupBroken": "startet nicht",
+  "settings.ytdlpRollback": "Zurücksetzen",
   "settings.systemPath": "System-PATH",
+  "settings.registeredBinary": "Registriert",
   "settings.install": "Installieren",
   "settings.update": "Aktualisieren",
   "settings.updating": "Wird aktualisiert...",
@@ -164,6 +252,8 @@ const de: Record<string, string> = {
   "settings.depModeSystemDesc": "Verwendet auf dem System PATH installierte Binärdateien. Manuelle Installation erforderlich.",
   "settings.depModeLabel": "Wie sollen Abhängigkeiten verwaltet werden?",
   "settings.installAll": "Alle fehlenden installieren",
+  "settings.rescanDeps": "Erneut suchen",
+  "settings.rescanDepsDesc": "yt-dlp und ffmpeg erneut suchen, z. B. nach der Installation über einen Paketmanager",
   "settings.reinstall": "Neu installieren",
 
   "theme.dark": "Dunkel",
@@ -177,6 +267,94 @@ const de: Record<string, string> = {
   "tray.quit": "Beenden",
   "settings.minimizeToTray": "In den Tray minimieren",
   "settings.minimizeToTrayDesc": "Beim Schließen in den System-Tray minimieren",
+  "settings.singleVideoDefault": "Einzelnes Video aus Playlist-Links",
+  "settings.singleVideoDefaultDesc": "Nur das verlinkte Video herunterladen, wenn die URL auch eine Playlist enthält",
+  "settings.autoContainer": "Intelligente Container-Konvertierung",
+  "settings.autoContainerDesc": "Beim Konvertieren in einen anderen Container remuxen (schnell, verlustfrei), wenn die Codecs passen, und nur sonst neu kodieren (langsam, verlustbehaftet). Fügt vor jedem Download eine kurze Formatprüfung hinzu",
+  "settings.verifyDownloads": "Downloads prüfen",
+  "settings.startupNetworkCheck": "Netzwerkprüfung beim Start",
+  "settings.startupNetworkCheckDesc": "Prüft im Hintergrund, ob GitHub und YouTube erreichbar sind, und warnt, wenn nicht",
+  "settings.verifyDownloadsDesc": "Jede fertige Datei mit ffprobe prüfen und ihre Dauer mit der Quelle vergleichen. Fehlerhafte Dateien werden im Verlauf als verdächtig markiert. Kostet pro Download einige Sekunden",
+  "settings.similarityFingerprints": "Ähnliche Downloads finden",
+  "settings.similarityFingerprintsDesc": "Verlaufseinträge nach Titel und Dauer abgleichen, um dasselbe Video in anderer Qualität zu erkennen. Für neue URLs werden die Details bei der Seite abgefragt",
+  "settings.retrySuspectDownloads": "Verdächtige Dateien neu laden",
+  "settings.retrySuspectDownloadsDesc": "Eine als verdächtig markierte Datei einmal erneut herunterladen",
+  "settings.maxFilesize": "Maximale Dateigröße",
+  "settings.maxFilesizeDesc": "Downloads über dieser Größe überspringen (z. B. 2G, 500M). Leer lassen für kein Limit",
+  "settings.maxFilesizeInvalid": "Ungültige Größe. Zahl mit K, M, G oder T verwenden (z. B. 2G)",
+  "settings.monthlyDataCap": "Monatliches Datenlimit",
+  "settings.monthlyDataCapDesc": "Warnen, wenn die Downloads dieses Monats diese Menge erreichen (z. B. 50G). Leer lassen für kein Limit",
+  "settings.dataUsage": "Heute {today} · Dieser Monat {month} · Insgesamt {total}",
+  "settings.dataCapUsed": "{percent}% des Limits",
+  "settings.headers": "HTTP-Header",
+  "settings.headersDesc": "Werden mit jedem Download gesendet. Manche Seiten benötigen einen Referer (die Seite, in die das Video eingebettet ist) oder einen Browser-User-Agent. Zusätzliche Header einer pro Zeile als 'Name: Wert'",
+  "settings.hostLists": "Erlaubte und gesperrte Seiten",
+  "settings.hostListsDesc": "Ein Host pro Zeile. *.example.com umfasst auch Subdomains. Sperren haben Vorrang; enthält die Erlaubt-Liste Einträge, sind nur diese Seiten erlaubt.",
+  "settings.hostAllowlist": "Nur erlauben",
+  "settings.hostDenylist": "Sperren",
+  "settings.codecPreference": "Bevorzugte Video-Codecs",
+  "settings.codecPreferenceDesc": "Kommagetrennt, wichtigster zuerst (av01, vp9, h265, h264, ...). Gilt, wenn ein Video sie anbietet; der tatsächlich geladene Codec steht im Verlauf.",
+  "settings.userAgent": "User-Agent",
+  "settings.referer": "Referer-URL",
+  "settings.extraHeaders": "Zusätzliche Header",
+  "settings.outputPathsByType": "Ordner nach Dateityp",
+  "settings.outputPathsByTypeDesc": "Videos, reine Audio-Downloads, Untertitel und Vorschaubilder in getrennten Ordnern speichern. Typen ohne Ordner nutzen den Download-Ordner",
+  "settings.outputType.video": "Video",
+  "settings.outputType.audio": "Audio",
+  "settings.outputType.subtitle": "Untertitel",
+  "settings.outputType.thumbnail": "Vorschaubilder",
+  "settings.outputTypeDefault": "Download-Ordner",
+  "settings.outputTypeClear": "Entfernen",
+  "settings.logBurstPolicy": "Log-Spitzen",
+  "settings.logBurstPolicyDesc": "Umgang mit mehr als ~200 Logzeilen pro Sekunde. Warnungen und Fehler werden immer behalten.",
+  "settings.logBurstPolicy.full": "Alles behalten",
+  "settings.logBurstPolicy.sampled": "Jede 10. Zeile",
+  "settings.logBurstPolicy.summarized": "Zusammenfassen",
+  "settings.logMaxPageSize": "Seitengröße der Log-Ansicht",
+  "settings.logMaxPageSizeDesc": "Höchstzahl der Log-Einträge pro Seite. Auf Geräten mit wenig Speicher verringern (max. 2000)",
+  "settings.cleanupIdleSecs": "Leerlaufzeit vor Bereinigung (Sekunden)",
+  "settings.cleanupIdleSecsDesc": "Log-Kürzung und Datenbankkomprimierung warten, bis so lange kein Download lief",
+  "settings.cleanupNow": "Im Leerlauf bereinigen",
+  "settings.cleanupScheduled": "Die Bereinigung läuft, sobald die Warteschlange im Leerlauf ist",
+  "settings.queueWatchdogSecs": "Intervall der Warteschlangen-Überwachung (Sekunden)",
+  "settings.queueWatchdogSecsDesc": "Wenn wartende Downloads nicht starten, obwohl nichts läuft, wird die Warteschlange neu gestartet. So oft wird geprüft; 0 schaltet es aus",
+  "settings.queueWatchdogCheck": "Jetzt prüfen",
+  "settings.queueWatchdogRestarted": "Warteschlange hing; neu gestartet ({count} gestartet)",
+  "settings.queueWatchdogOk": "Warteschlange läuft normal",
+  "settings.backup": "Sicherung",
+  "settings.backupDesc": "Einstellungen, Warteschlange, Verlauf und Protokolle in einem Archiv",
+  "settings.backupRedact": "Geheimnisse in exportierten Einstellungen maskieren",
+  "settings.backupExport": "Exportieren",
+  "settings.backupReplace": "Vorhandene Daten beim Import ersetzen",
+  "settings.backupImport": "Importieren",
+  "settings.backupExported": "Sicherung gespeichert",
+  "settings.backupImported": "{history} Verlaufseinträge, {queue} Aufgaben und {logs} Protokolleinträge importiert",
+  "settings.backupSettingsRestored": "Einstellungen wurden wiederhergestellt",
+  "settings.diagnostics": "Diagnose",
+  "settings.diagnosticsDesc": "App-Infos, Statusprüfung, maskierte Einstellungen und aktuelle Logs für einen Fehlerbericht",
+  "settings.diagnosticsExported": "Diagnose gespeichert",
+  "settings.maxRetries": "Automatische Wiederholungen",
+  "settings.maxRetriesDesc": "Wiederholt nach Netzwerk- oder Rate-Limit-Fehlern mit jeweils längerer Wartezeit (0 schaltet es aus). Zweites Feld: erste Wartezeit in Sekunden",
+  "settings.retryBackoff": "Erste Wartezeit (Sekunden)",
+  "settings.downloadLimits": "Download-Limits",
+  "settings.downloadLimitsDesc": "Höchstzahl gestarteter Aufgaben pro Sitzung und pro Tag. Darüber hinaus bleiben Aufgaben ausstehend. Leer lassen für kein Limit",
+  "settings.pauseBelowBattery": "Bei niedrigem Akku pausieren",
+  "settings.pauseBelowBatteryDesc": "Im Akkubetrieb unter diesem Ladestand (%) keine neuen Downloads starten. Leer lassen, um nie zu pausieren",
+  "settings.batteryUnavailable": "Der Akkustand ist auf diesem System nicht verfügbar",
+  "settings.batteryPaused": "Akku bei {percent}%: neue Downloads pausiert",
+  "settings.batteryOnBattery": "Akkubetrieb bei {percent}%",
+  "settings.batteryCharging": "Am Netz ({percent}%)",
+  "settings.pauseAboveCpu": "Bei hoher CPU-Last pausieren",
+  "settings.pauseAboveCpuDesc": "Keine neuen Downloads starten, solange die CPU-Auslastung über diesem Wert (%) bleibt, z. B. beim Spielen oder Rendern. Leer lassen, um nie zu pausieren",
+  "settings.cpuSampling": "CPU-Auslastung wird gemessen...",
+  "settings.cpuPaused": "CPU bei {percent}%: neue Downloads sind pausiert",
+  "settings.cpuUsage": "CPU bei {percent}%",
+  "settings.cpuSampleSecs": "Sekunden zwischen Messungen",
+  "settings.cpuSustainSecs": "Sekunden, die die Last anhalten muss, bevor pausiert oder fortgesetzt wird",
+  "settings.sessionLimit": "Pro Sitzung",
+  "settings.dailyLimit": "Pro Tag",
+  "settings.limitUsed": "{used}/{limit} genutzt",
+  "settings.limitUsedUnlimited": "{used} genutzt",
 
   // Update
   "update.checkUpdate": "Nach Updates suchen",
@@ -198,6 +376,7 @@ const de: Record<string, string> = {
   "logs.searchPlaceholder": "Protokoll durchsuchen...",
   "logs.empty": "Keine Protokolle vorhanden",
   "logs.live": "Live",
+  "logs.activity": "Aktivität der letzten 24 Stunden",
   "logs.clearLogs": "Löschen",
   "logs.clearConfirm": "Alle Protokolle löschen?",
   "logs.cleared": "Protokolle gelöscht",
@@ -20,9 +20,17 @@ const en: Record<string, string> = {
   "layout.notInstalled": "Not installed",
   "layout.ffmpegNote": "Installing ffmpeg together enables best quality video merging.",
   "layout.noActiveDownloads": "No active downloads",
+  "layout.live": "Live",
+  "layout.networkUnreachable": "Some hosts are unreachable. Installing dependencies or downloading may fail; check your firewall or proxy",
+  "layout.networkRetest": "Retest",
+  "layout.networkDismiss": "Dismiss",
+  "layout.drainWaiting": "Quitting after {count} download(s) finish...",
+  "layout.drain.cancelling": "Time limit reached, cancelling remaining downloads...",
+  "layout.drain.exiting": "Downloads finished, quitting...",
   "layout.cancelAll": "Cancel All",
   "layout.queueAdded": "{count} video(s) added to queue.",
   "layout.downloadComplete": "{title} download complete.",
+  "layout.dataCapWarning": "This month's downloads are at {percent}% of your data cap",
   "layout.queued": "Queued",
   "layout.recent": "Recent",
   "layout.viewAll": "View All",
@@ -67,7 +75,12 @@ const en: Record<string, string> = {
 
   // Download page
   "download.invalidUrl": "Invalid URL",
+  "download.partOfPlaylist": "This video is part of a playlist. Only this video will be downloaded.",
+  "download.openedAsPlaylist": "This link was opened as a playlist.",
+  "download.wholePlaylistInstead": "Download whole playlist instead",
+  "download.singleVideoInstead": "Just this video",
   "download.alreadyInQueue": "This video is already in the download queue.",
+  "download.videoBlocked": "This video is on your blocklist and won't be downloaded.",
   "download.analyzing": "Fetching metadata...",
   "download.loadingFormats": "Loading video formats...",
   "download.urlPlaceholder": "Paste any video URL (YouTube, TikTok, Twitter, Vimeo...)",
@@ -95,6 +108,9 @@ const en: Record<string, string> = {
   "download.uploaderFolder": "Uploader Folder",
   "download.uploadDate": "Upload Date",
   "download.videoId": "Video ID",
+  "download.templateWarnings": "{count} template warning(s)",
+  "download.caseCollision": "Case-insensitive folder",
+  "download.caseCollisionHint": "This folder treats 'Title' and 'title' as the same file, so videos whose titles differ only by case overwrite each other. Include the video ID in the file name.",
   "download.advancedTooltip": "Enter yt-dlp template syntax directly",
   "download.uploaderFolderTooltip": "Create subfolder by channel/uploader name",
   "download.uploadDateTooltip": "Add upload date before filename",
@@ -104,13 +120,43 @@ const en: Record<string, string> = {
   "download.downloadFailed": "Download failed",
   "download.skippedQueue": "{count} video(s) skipped (already in queue).",
   "download.skippedExists": "{count} video(s) skipped (already downloaded).",
+  "download.skippedBlocked": "{count} video(s) skipped (blocked).",
+  "download.importFromFile": "Import URLs from file",
+  "download.urlListFile": "URL list",
+  "download.importSummary": "Imported {queued} video(s); {skipped} skipped, {failed} invalid or failed.",
+  "download.audioLanguage": "Audio language",
+  "download.audioLanguageDefault": "Original audio",
+  "download.liveFromStart": "From start",
+  "download.liveFromStartHint": "Record the live stream from its beginning instead of from now",
+  "download.subtitlesOnly": "Subtitles only",
+  "download.subtitlesOnlyHint": "Save just the subtitles as SRT files, without the video",
+  "download.subtitleLangs": "Subtitle languages, comma separated (e.g. en, ja.*, all)",
+  "download.testFormat": "Test selection",
+  "download.testFormatHint": "Show which format yt-dlp would pick with the current settings, without downloading",
+  "download.testFormatFailed": "Test failed: {error}",
+  "download.suggestFormat": "Suggest a format",
+  "download.suggestFormatHint": "Rank this video's formats by quality for their size",
+  "download.noFormatRecs": "No formats with a known size to compare",
+  "download.recommended": "Recommended",
+  "download.smallestAcceptable": "Smallest OK",
+  "download.ffmpegMissing": "These options need ffmpeg, which isn't installed. They will fail or be skipped:",
+  "download.ffmpegFeature.merge": "Merging video and audio",
+  "download.ffmpegFeature.recode": "Re-encoding",
+  "download.ffmpegFeature.embedMetadata": "Embedding metadata",
+  "download.ffmpegFeature.embedThumbnail": "Embedding the thumbnail",
+  "download.ffmpegFeature.embedChapters": "Embedding chapters",
+  "download.ffmpegFeature.convertSubs": "Converting subtitles",
   "download.redownload": "Re-download",
   "download.cancel": "Cancel",
   "download.alreadyDownloaded": "\"{title}\" has already been downloaded.",
+  "download.fileOnDisk": "Already on disk: {path}",
+  "download.overwrite": "Overwrite",
+  "download.saveAsCopy": "Save as copy",
   "download.error": "Error",
   "download.duplicateFound": "Duplicate Found",
   "download.include": "Include:",
   "download.cookie": "Cookie:",
+  "download.defaultBrowser": "{browser} (default)",
   "download.concurrent": "Concurrent:",
   "download.emptyState": "Paste a URL to start downloading",
 
@@ -137,6 +183,37 @@ const en: Record<string, string> = {
   "history.searchPlaceholder": "Search by title...",
   "history.empty": "No download history",
   "history.deleteConfirm": "Delete this item?",
+  "history.superseded": "Re-downloaded",
+  "history.suspect": "Suspect file",
+  "history.bulkTagPlaceholder": "Tag",
+  "history.bulkTag": "Tag all {count} matches",
+  "history.bulkUntag": "Remove tag from matches",
+  "history.bulkTagResult": "{count} item(s) updated",
+  "history.favoritesOnly": "Favorites only",
+  "history.favorite": "Add to favorites",
+  "history.unfavorite": "Remove from favorites",
+  "history.sortBy": "Sort by",
+  "history.sortDate": "Date",
+  "history.sortSize": "Size",
+  "history.sortDuration": "Duration",
+  "history.sortTitle": "Title",
+  "history.sortAsc": "Ascending",
+  "history.sortDesc": "Descending",
+  "history.renameFiles": "Rename files",
+  "history.renameHint": "Rename downloaded files to a new output template. Files stay in their folder; preview first, then apply.",
+  "history.renameTemplate": "Output template",
+  "history.renamePreview": "Preview",
+  "history.renameApply": "Rename {count}",
+  "history.renameConfirm": "Rename {count} file(s) on disk?",
+  "history.renameDone": "Renamed {count} file(s)",
+  "history.renameNothing": "Every file already matches this template",
+  "history.renameStatus.ready": "Rename",
+  "history.renameStatus.collision": "Name taken",
+  "history.renameStatus.missing": "File missing",
+  "history.renameStatus.unresolved": "Can't fill in",
+  "history.renameStatus.unchanged": "Unchanged",
+  "history.exportPlaylist": "Export page as playlist",
+  "history.playlistExported": "Playlist saved with {count} item(s); {skipped} skipped (file missing).",
 
   // Settings page
   "settings.title": "Settings",
@@ -156,10 +233,21 @@ const en: Record<string, string> = {
   "settings.theme": "Theme",
   "settings.general": "General",
   "settings.appearance": "Appearance",
+  "settings.issuesTitle": "Settings aren't saved until these problems are fixed",
+  "settings.warningsTitle": "Saved, but check these settings",
   "settings.dependencies": "Dependencies",
   "settings.notInstalled": "Not installed",
   "settings.appManaged": "App managed",
+  "settings.ffmpegPinned": "Pinned {version}",
+  "settings.ffmpegPin": "Pin version",
+  "settings.ffmpegRollback": "Roll back",
+  "settings.ffmpegUnpin": "Unpin",
+  "settings.ffmpegUnpinned": "Unpinned; updates follow the latest build",
+  "settings.ytdlpBackupCount": "Backups kept",
+  "settings.ytdlpBackupBroken": "does not run",
+  "settings.ytdlpRollback": "Roll back",
   "settings.systemPath": "System PATH",
+  "settings.registeredBinary": "Registered",
   "settings.install": "Install",
   "settings.update": "Update",
   "settings.updating": "Updating...",
@@ -170,6 +258,8 @@ const en: Record<string, string> = {
   "settings.depModeSystemDesc": "Use binaries already installed on your system PATH. You need to install them manually.",
   "settings.depModeLabel": "How should dependencies be resolved?",
   "settings.installAll": "Install All Missing",
+  "settings.rescanDeps": "Re-scan",
+  "settings.rescanDepsDesc": "Look for yt-dlp and ffmpeg again, e.g. after installing them with a package manager",
   "settings.reinstall": "Reinstall",
 
   // Theme names
@@ -185,6 +275,94 @@ const en: Record<string, string> = {
   "tray.quit": "Quit",
   "settings.minimizeToTray": "Minimize to Tray",
   "settings.minimizeToTrayDesc": "Minimize to system tray when closing the window",
+  "settings.singleVideoDefault": "Single Video from Playlist Links",
+  "settings.singleVideoDefaultDesc": "Download only the linked video when a video URL also contains a playlist",
+  "settings.autoContainer": "Smart Container Conversion",
+  "settings.autoContainerDesc": "When converting to another container, remux (fast, lossless) if the codecs fit and re-encode (slow, lossy) only when they don't. Adds a short format check before each download",
+  "settings.verifyDownloads": "Verify Downloads",
+  "settings.startupNetworkCheck": "Network Check on Startup",
+  "settings.startupNetworkCheckDesc": "Check in the background that GitHub and YouTube are reachable and warn when they are not",
+  "settings.verifyDownloadsDesc": "Check each finished file with ffprobe and compare its duration with the source. Files that fail are marked suspect in history. Adds a few seconds per download",
+  "settings.similarityFingerprints": "Find Similar Downloads",
+  "settings.similarityFingerprintsDesc": "Match history items by title and duration to spot the same video saved at another quality. Checking a new URL asks the site for its details",
+  "settings.retrySuspectDownloads": "Re-download Suspect Files",
+  "settings.retrySuspectDownloadsDesc": "Download a file once more when verification marks it suspect",
+  "settings.maxFilesize": "Max File Size",
+  "settings.maxFilesizeDesc": "Skip downloads larger than this (e.g. 2G, 500M). Leave empty for no limit",
+  "settings.maxFilesizeInvalid": "Invalid size. Use a number with K, M, G or T (e.g. 2G)",
+  "settings.monthlyDataCap": "Monthly data cap",
+  "settings.monthlyDataCapDesc": "Warn when downloads this month near this amount (e.g. 50G). Leave empty for no cap",
+  "settings.dataUsage": "Today {today} · This month {month} · All time {total}",
+  "settings.dataCapUsed": "{percent}% of cap",
+  "settings.headers": "HTTP Headers",
+  "settings.headersDesc": "Sent with every download. Some sites need a referer (the page the video is embedded in) or a browser User-Agent. Extra headers go one per line as 'Name: value'",
+  "settings.hostLists": "Allowed and blocked sites",
+  "settings.hostListsDesc": "One host per line. *.example.com also covers its subdomains. Blocked hosts win; when the allow list has entries, only those sites can be downloaded.",
+  "settings.hostAllowlist": "Allow only",
+  "settings.hostDenylist": "Block",
+  "settings.codecPreference": "Preferred video codecs",
+  "settings.codecPreferenceDesc": "Comma-separated, best first (av01, vp9, h265, h264, ...). Used when a video offers them; the codec actually downloaded is kept in history.",
+  "settings.userAgent": "User-Agent",
+  "settings.referer": "Referer URL",
+  "settings.extraHeaders": "Extra headers",
+  "settings.outputPathsByType": "Folders by File Type",
+  "settings.outputPathsByTypeDesc": "Save videos, audio-only downloads, subtitles and thumbnails to separate folders. Types without a folder use the download folder",
+  "settings.outputType.video": "Video",
+  "settings.outputType.audio": "Audio",
+  "settings.outputType.subtitle": "Subtitles",
+  "settings.outputType.thumbnail": "Thumbnails",
+  "settings.outputTypeDefault": "Download folder",
+  "settings.outputTypeClear": "Clear",
+  "settings.logBurstPolicy": "Log bursts",
+  "settings.logBurstPolicyDesc": "What to do with log lines past ~200 per second. Warnings and errors are always kept.",
+  "settings.logBurstPolicy.full": "Keep everything",
+  "settings.logBurstPolicy.sampled": "Sample 1 in 10",
+  "settings.logBurstPolicy.summarized": "Summarize",
+  "settings.logMaxPageSize": "Log viewer page size limit",
+  "settings.logMaxPageSizeDesc": "Most log entries loaded per page. Lower it on low-memory devices (max 2000)",
+  "settings.cleanupIdleSecs": "Cleanup idle time (seconds)",
+  "settings.cleanupIdleSecsDesc": "Log trimming and database compaction wait until no download has run for this long",
+  "settings.cleanupNow": "Clean up when idle",
+  "settings.cleanupScheduled": "Cleanup will run once the queue is idle",
+  "settings.queueWatchdogSecs": "Queue watchdog interval (seconds)",
+  "settings.queueWatchdogSecsDesc": "If queued downloads stop starting while nothing is running, restart the queue. Checked this often; 0 turns it off",
+  "settings.queueWatchdogCheck": "Check now",
+  "settings.queueWatchdogRestarted": "Queue was stuck; restarted it ({count} started)",
+  "settings.queueWatchdogOk": "Queue is moving normally",
+  "settings.backup": "Backup",
+  "settings.backupDesc": "Settings, queue, history and logs in one archive",
+  "settings.backupRedact": "Mask secrets in exported settings",
+  "settings.backupExport": "Export",
+  "settings.backupReplace": "Replace existing data on import",
+  "settings.backupImport": "Import",
+  "settings.backupExported": "Backup saved",
+  "settings.backupImported": "Imported {history} history items, {queue} queued tasks and {logs} log entries",
+  "settings.backupSettingsRestored": "Settings were restored",
+  "settings.diagnostics": "Diagnostics",
+  "settings.diagnosticsDesc": "App info, health check, masked settings and recent logs for a bug report",
+  "settings.diagnosticsExported": "Diagnostics saved",
+  "settings.maxRetries": "Automatic retries",
+  "settings.maxRetriesDesc": "Retries after network or rate-limit failures, waiting longer each time (0 turns it off). Second box: first wait in seconds",
+  "settings.retryBackoff": "First retry wait (seconds)",
+  "settings.downloadLimits": "Download limits",
+  "settings.downloadLimitsDesc": "Most tasks started per session and per day. Past the limit, tasks stay pending. Leave empty for no limit",
+  "settings.pauseBelowBattery": "Pause on low battery",
+  "settings.pauseBelowBatteryDesc": "Don't start new downloads while on battery below this charge (%). Leave empty to never pause",
+  "settings.batteryUnavailable": "Battery status isn't available on this system",
+  "settings.batteryPaused": "On battery at {percent}%: new downloads are paused",
+  "settings.batteryOnBattery": "On battery at {percent}%",
+  "settings.batteryCharging": "Plugged in ({percent}%)",
+  "settings.pauseAboveCpu": "Pause when the CPU is busy",
+  "settings.pauseAboveCpuDesc": "Don't start new downloads while total CPU usage stays above this (%), e.g. while gaming or rendering. Leave empty to never pause",
+  "settings.cpuSampling": "Measuring CPU usage...",
+  "settings.cpuPaused": "CPU at {percent}%: new downloads are paused",
+  "settings.cpuUsage": "CPU at {percent}%",
+  "settings.cpuSampleSecs": "Seconds between samples",
+  "settings.cpuSustainSecs": "Seconds the load must last before pausing or resuming",
+  "settings.sessionLimit": "Per session",
+  "settings.dailyLimit": "Per day",
+  "settings.limitUsed": "{used}/{limit} used",
+  "settings.limitUsedUnlimited": "{used} used",
 
   // Update
   "update.checkUpdate": "Check for Updates",
@@ -206,6 +384,7 @@ const en: Record<string, string> = {
   "logs.searchPlaceholder": "Search logs...",
   "logs.empty": "No logs yet",
   "logs.live": "Live",
+  "logs.activity": "Activity, last 24 hours",
   "logs.clearLogs": "Clear",
   "logs.clearConfirm": "Clear all logs?",
   "logs.cleared": "Logs cleared",
@@ -18,9 +18,17 @@ const fr: Record<string, string> = {
   "layout.notInstalled": "Non installé",
   "layout.ffmpegNote": "Installer ffmpeg permet la fusion vidéo en meilleure qualité.",
   "layout.noActiveDownloads": "Aucun téléchargement actif",
+  "layout.live": "En direct",
+  "layout.networkUnreachable": "Certains hôtes sont injoignables. L'installation des dépendances ou les téléchargements peuvent échouer ; vérifiez votre pare-feu ou proxy",
+  "layout.networkRetest": "Retester",
+  "layout.networkDismiss": "Fermer",
+  "layout.drainWaiting": "Fermeture après la fin de {count} téléchargement(s)...",
+  "layout.drain.cancelling": "Délai atteint, annulation des téléchargements restants...",
+  "layout.drain.exiting": "Téléchargements terminés, fermeture...",
   "layout.cancelAll": "Tout annuler",
   "layout.queueAdded": "{count} vidéo(s) ajoutée(s) à la file d'attente.",
   "layout.downloadComplete": "Téléchargement de {title} terminé.",
+  "layout.dataCapWarning": "Les téléchargements du mois atteignent {percent}% de votre plafond de données",
   "layout.queued": "En attente",
   "layout.recent": "Récents",
   "layout.viewAll": "Tout voir",
@@ -64,7 +72,12 @@ const fr: Record<string, string> = {
   "debug.resetDone": "Réinitialisation terminée. Redémarrage...",
 
   "download.invalidUrl": "URL invalide",
+  "download.partOfPlaylist": "Cette vidéo fait partie d'une playlist. Seule cette vidéo sera téléchargée.",
+  "download.openedAsPlaylist": "Ce lien a été ouvert comme playlist.",
+  "download.wholePlaylistInstead": "Télécharger toute la playlist",
+  "download.singleVideoInstead": "Cette vidéo uniquement",
   "download.alreadyInQueue": "Cette vidéo est déjà dans la file d'attente.",
+  "download.videoBlocked": "Cette vidéo est dans votre liste de blocage et ne sera pas téléchargée.",
   "download.analyzing": "Récupération des métadonnées...",
   "download.loadingFormats": "Chargement des formats vidéo...",
   "download.urlPlaceholder": "Collez une URL vidéo (YouTube, TikTok, Twitter, Vimeo...)",
@@ -92,6 +105,9 @@ const fr: Record<string, string> = {
   "download.uploaderFolder": "Dossier par auteur",
   "download.uploadDate": "Date de mise en ligne",
   "download.videoId": "ID vidéo",
+  "download.templateWarnings": "{count} avertissement(s) de modèle",
+  "download.caseCollision": "Dossier insensible à la casse",
+  "download.caseCollisionHint": "Ce dossier traite 'Title' et 'title' comme le même fichier : les vidéos dont les titres ne diffèrent que par la casse s'écrasent. Ajoutez l'ID de la vidéo au nom de fichier.",
   "download.advancedTooltip": "Entrez directement la syntaxe de modèle yt-dlp",
   "download.uploaderFolderTooltip": "Créer un sous-dossier par nom de chaîne",
   "download.uploadDateTooltip": "Ajouter la date de mise en ligne au nom de fichier",
@@ -101,13 +117,43 @@ const fr: Record<string, string> = {
   "download.downloadFailed": "Échec du téléchargement",
   "download.skippedQueue": "{count} vidéo(s) ignorée(s) (déjà dans la file d'attente).",
   "download.skippedExists": "{count} vidéo(s) ignorée(s) (déjà téléchargée(s)).",
+  "download.skippedBlocked": "{count} vidéo(s) ignorée(s) (bloquée(s)).",
+  "download.importFromFile": "Importer des URL depuis un fichier",
+  "download.urlListFile": "Liste d'URL",
+  "download.importSummary": "{queued} vidéo(s) importée(s) ; {skipped} ignorée(s), {failed} invalide(s) ou en échec.",
+  "download.audioLanguage": "Langue audio",
+  "download.audioLanguageDefault": "Audio d'origine",
+  "download.liveFromStart": "Depuis le début",
+  "download.liveFromStartHint": "Enregistrer le direct depuis son début plutôt qu'à partir de maintenant",
+  "download.subtitlesOnly": "Sous-titres seuls",
+  "download.subtitlesOnlyHint": "Enregistrer uniquement les sous-titres en fichiers SRT, sans la vidéo",
+  "download.subtitleLangs": "Langues des sous-titres, séparées par des virgules (ex. en, ja.*, all)",
+  "download.testFormat": "Tester la sélection",
+  "download.testFormatHint": "Afficher le format que yt-dlp choisirait avec les réglages actuels, sans télécharger",
+  "download.testFormatFailed": "Échec du test : {error}",
+  "download.suggestFormat": "Suggérer un format",
+  "download.suggestFormatHint": "Classer les formats de cette vidéo selon leur qualité par rapport à leur taille",
+  "download.noFormatRecs": "Aucun format de taille connue à comparer",
+  "download.recommended": "Recommandé",
+  "download.smallestAcceptable": "Le plus petit correct",
+  "download.ffmpegMissing": "Ces options nécessitent ffmpeg, qui n'est pas installé. Elles échoueront ou seront ignorées :",
+  "download.ffmpegFeature.merge": "Fusion vidéo et audio",
+  "download.ffmpegFeature.recode": "Réencodage",
+  "download.ffmpegFeature.embedMetadata": "Intégration des métadonnées",
+  "download.ffmpegFeature.embedThumbnail": "Intégration de la miniature",
+  "download.ffmpegFeature.embedChapters": "Intégration des chapitres",
+  "download.ffmpegFeature.convertSubs": "Conversion des sous-titres",
   "download.redownload": "Retélécharger",
   "download.cancel": "Annuler",
   "download.alreadyDownloaded": "« {title} » a déjà été téléchargée.",
+  "download.fileOnDisk": "Déjà sur le disque : {path}",
+  "download.overwrite": "Écraser",
+  "download.saveAsCopy": "Enregistrer une copie",
   "download.error": "Erreur",
   "download.duplicateFound": "Doublon trouvé",
   "download.include": "Inclure :",
   "download.cookie": "Cookie :",
+  "download.defaultBrowser": "{browser} (par défaut)",
   "download.concurrent": "Simultanés :",
   "download.emptyState": "Collez une URL pour commencer le téléchargement",
 
@@ -132,6 +178,37 @@ const fr: Record<string, string> = {
   "history.searchPlaceholder": "Rechercher par titre...",
   "history.empty": "Aucun historique de téléchargement",
   "history.deleteConfirm": "Supprimer cet élément ?",
+  "history.superseded": "Retéléchargé",
+  "history.suspect": "Fichier suspect",
+  "history.bulkTagPlaceholder": "Tag",
+  "history.bulkTag": "Étiqueter les {count} résultats",
+  "history.bulkUntag": "Retirer le tag des résultats",
+  "history.bulkTagResult": "{count} élément(s) modifié(s)",
+  "history.favoritesOnly": "Favoris uniquement",
+  "history.favorite": "Ajouter aux favoris",
+  "history.unfavorite": "Retirer des favoris",
+  "history.sortBy": "Trier par",
+  "history.sortDate": "Date",
+  "history.sortSize": "Taille",
+  "history.sortDuration": "Durée",
+  "history.sortTitle": "Titre",
+  "history.sortAsc": "Croissant",
+  "history.sortDesc": "Décroissant",
+  "history.renameFiles": "Renommer les fichiers",
+  "history.renameHint": "Renommer les fichiers téléchargés selon un nouveau modèle de sortie. Les fichiers restent dans leur dossier ; prévisualisez puis appliquez.",
+  "history.renameTemplate": "Modèle de sortie",
+  "history.renamePreview": "Aperçu",
+  "history.renameApply": "Renommer {count}",
+  "history.renameConfirm": "Renommer {count} fichier(s) sur le disque ?",
+  "history.renameDone": "{count} fichier(s) renommé(s)",
+  "history.renameNothing": "Tous les fichiers correspondent déjà à ce modèle",
+  "history.renameStatus.ready": "Renommer",
+  "history.renameStatus.collision": "Nom déjà pris",
+  "history.renameStatus.missing": "Fichier absent",
+  "history.renameStatus.unresolved": "Impossible à remplir",
+  "history.renameStatus.unchanged": "Inchangé",
+  "history.exportPlaylist": "Exporter la page en playlist",
+  "history.playlistExported": "Playlist enregistrée avec {count} élément(s) ; {skipped} ignoré(s) (fichier manquant).",
 
   "settings.title": "Paramètres",
   "settings.subtitle": "Configurer les préférences de téléchargement",
@@ -150,10 +227,21 @@ const fr: Record<string, string> = {
   "settings.theme": "Thème",
   "settings.general": "Général",
   "settings.appearance": "Apparence",
+  "settings.issuesTitle": "Les paramètres ne seront pas enregistrés tant que ces problèmes ne sont pas corrigés",
+  "settings.warningsTitle": "Enregistré, mais vérifiez ces paramètres",
   "settings.dependencies": "Dépendances",
   "settings.notInstalled": "Non installé",
   "settings.appManaged": "Géré par l'app",
+  "settings.ffmpegPinned": "Épinglé à {version}",
+  "settings.ffmpegPin": "Épingler la version",
+  "settings.ffmpegRollback": "Revenir en arrière",
+  "settings.ffmpegUnpin": "Désépingler",
+  "settings.ffmpegUnpinned": "Désépinglé ; les mises à jour suivent la dernière version",
+  "settings.ytdlpBackupCount": "Sauvegardes conservées",
+  "settings.ytdlpBackupBroken": "ne démarre pas",
+  "settings.ytdlpRollback": "Restaurer",
   "settings.systemPath": "PATH système",
+  "settings.registeredBinary": "Enregistré",
   "settings.install": "Installer",
   "settings.update": "Mettre à jour",
   "settings.updating": "Mise à jour...",
@@ -164,6 +252,8 @@ const fr: Record<string, string> = {
   "settings.depModeSystemDesc": "Utilise les binaires installés dans le PATH système. Installation manuelle requise.",
   "settings.depModeLabel": "Comment gérer les dépendances ?",
   "settings.installAll": "Installer tous les manquants",
+  "settings.rescanDeps": "Relancer la détection",
+  "settings.rescanDepsDesc": "Rechercher à nouveau yt-dlp et ffmpeg, par ex. après une installation via un gestionnaire de paquets",
   "settings.reinstall": "Réinstaller",
 
   "theme.dark": "Sombre",
@@ -177,6 +267,94 @@ const fr: Record<string, string> = {
   "tray.quit": "Quitter",
   "settings.minimizeToTray": "Minimiser dans la barre",
   "settings.minimizeToTrayDesc": "Minimiser dans la barre système lors de la fermeture",
+  "settings.singleVideoDefault": "Vidéo seule depuis un lien de playlist",
+  "settings.singleVideoDefaultDesc": "Télécharger uniquement la vidéo liée lorsque l'URL contient aussi une playlist",
+  "settings.autoContainer": "Conversion de conteneur intelligente",
+  "settings.autoContainerDesc": "Lors d'une conversion vers un autre conteneur, remuxer (rapide, sans perte) si les codecs sont compatibles et ne réencoder (lent, avec perte) que sinon. Ajoute une courte vérification du format avant chaque téléchargement",
+  "settings.verifyDownloads": "Vérifier les téléchargements",
+  "settings.startupNetworkCheck": "Vérification réseau au démarrage",
+  "settings.startupNetworkCheckDesc": "Vérifie en arrière-plan que GitHub et YouTube sont joignables et avertit sinon",
+  "settings.verifyDownloadsDesc": "Contrôle chaque fichier terminé avec ffprobe et compare sa durée à la source. Les fichiers en échec sont marqués suspects dans l'historique. Ajoute quelques secondes par téléchargement",
+  "settings.similarityFingerprints": "Trouver les téléchargements similaires",
+  "settings.similarityFingerprintsDesc": "Compare l'historique par titre et durée pour repérer la même vidéo dans une autre qualité. Vérifier une nouvelle URL interroge le site",
+  "settings.retrySuspectDownloads": "Retélécharger les fichiers suspects",
+  "settings.retrySuspectDownloadsDesc": "Télécharge une nouvelle fois un fichier marqué suspect par la vérification",
+  "settings.maxFilesize": "Taille maximale de fichier",
+  "settings.maxFilesizeDesc": "Ignorer les téléchargements plus gros (ex. 2G, 500M). Laisser vide pour aucune limite",
+  "settings.maxFilesizeInvalid": "Taille invalide. Utilisez un nombre avec K, M, G ou T (ex. 2G)",
+  "settings.monthlyDataCap": "Plafond de données mensuel",
+  "settings.monthlyDataCapDesc": "Avertir quand les téléchargements du mois approchent cette quantité (ex. 50G). Laisser vide pour aucun plafond",
+  "settings.dataUsage": "Aujourd'hui {today} · Ce mois-ci {month} · Total {total}",
+  "settings.dataCapUsed": "{percent}% du plafond",
+  "settings.headers": "En-têtes HTTP",
+  "settings.headersDesc": "Envoyés avec chaque téléchargement. Certains sites exigent un referer (la page où la vidéo est intégrée) ou un User-Agent de navigateur. En-têtes supplémentaires : un par ligne, au format 'Nom: valeur'",
+  "settings.hostLists": "Sites autorisés et bloqués",
+  "settings.hostListsDesc": "Un hôte par ligne. *.example.com couvre aussi ses sous-domaines. Les blocages priment ; si la liste d'autorisation n'est pas vide, seuls ces sites sont téléchargeables.",
+  "settings.hostAllowlist": "Autoriser uniquement",
+  "settings.hostDenylist": "Bloquer",
+  "settings.codecPreference": "Codecs vidéo préférés",
+  "settings.codecPreferenceDesc": "Séparés par des virgules, le préféré en premier (av01, vp9, h265, h264, ...). Utilisés quand la vidéo les propose ; le codec réellement téléchargé est noté dans l'historique.",
+  "settings.userAgent": "User-Agent",
+  "settings.referer": "URL du referer",
+  "settings.extraHeaders": "En-têtes supplémentaires",
+  "settings.outputPathsByType": "Dossiers par type de fichier",
+  "settings.outputPathsByTypeDesc": "Enregistre les vidéos, les téléchargements audio seul, les sous-titres et les miniatures dans des dossiers séparés. Les types sans dossier utilisent le dossier de téléchargement",
+  "settings.outputType.video": "Vidéo",
+  "settings.outputType.audio": "Audio",
+  "settings.outputType.subtitle": "Sous-titres",
+  "settings.outputType.thumbnail": "Miniatures",
+  "settings.outputTypeDefault": "Dossier de téléchargement",
+  "settings.outputTypeClear": "Effacer",
+  "settings.logBurstPolicy": "Rafales de logs",
+  "settings.logBurstPolicyDesc": "Que faire des lignes de log au-delà d'environ 200 par seconde. Les avertissements et erreurs sont toujours conservés.",
+  "settings.logBurstPolicy.full": "Tout garder",
+  "settings.logBurstPolicy.sampled": "1 ligne sur 10",
+  "settings.logBurstPolicy.summarized": "Résumer",
+  "settings.logMaxPageSize": "Taille maximale des pages de journaux",
+  "settings.logMaxPageSizeDesc": "Nombre maximal d'entrées chargées par page. Réduisez-le sur les appareils avec peu de mémoire (max 2000)",
+  "settings.cleanupIdleSecs": "Délai d'inactivité avant nettoyage (secondes)",
+  "settings.cleanupIdleSecsDesc": "Le tri des journaux et le compactage de la base attendent qu'aucun téléchargement n'ait tourné pendant cette durée",
+  "settings.cleanupNow": "Nettoyer en cas d'inactivité",
+  "settings.cleanupScheduled": "Le nettoyage s'exécutera dès que la file sera inactive",
+  "settings.queueWatchdogSecs": "Intervalle de surveillance de la file (secondes)",
+  "settings.queueWatchdogSecsDesc": "Si les téléchargements en attente ne démarrent plus alors que rien ne tourne, la file est relancée. Vérifié à cet intervalle ; 0 la désactive",
+  "settings.queueWatchdogCheck": "Vérifier maintenant",
+  "settings.queueWatchdogRestarted": "La file était bloquée ; relancée ({count} démarré(s))",
+  "settings.queueWatchdogOk": "La file avance normalement",
+  "settings.backup": "Sauvegarde",
+  "settings.backupDesc": "Paramètres, file, historique et journaux dans une seule archive",
+  "settings.backupRedact": "Masquer les secrets dans les paramètres exportés",
+  "settings.backupExport": "Exporter",
+  "settings.backupReplace": "Remplacer les données existantes à l'import",
+  "settings.backupImport": "Importer",
+  "settings.backupExported": "Sauvegarde enregistrée",
+  "settings.backupImported": "{history} éléments d'historique, {queue} tâches et {logs} entrées de journal importés",
+  "settings.backupSettingsRestored": "Les paramètres ont été restaurés",
+  "settings.diagnostics": "Diagnostic",
+  "settings.diagnosticsDesc": "Infos de l'app, bilan de santé, paramètres masqués et journaux récents pour un rapport de bug",
+  "settings.diagnosticsExported": "Diagnostic enregistré",
+  "settings.maxRetries": "Nouvelles tentatives automatiques",
+  "settings.maxRetriesDesc": "Réessaie après une erreur réseau ou de limitation, en attendant plus longtemps à chaque fois (0 pour désactiver). Second champ : première attente en secondes",
+  "settings.retryBackoff": "Première attente (secondes)",
+  "settings.downloadLimits": "Limites de téléchargement",
+  "settings.downloadLimitsDesc": "Nombre maximal de tâches démarrées par session et par jour. Au-delà, les tâches restent en attente. Laisser vide pour aucune limite",
+  "settings.pauseBelowBattery": "Pause sur batterie faible",
+  "settings.pauseBelowBatteryDesc": "Ne pas lancer de nouveaux téléchargements sur batterie sous ce niveau (%). Laisser vide pour ne jamais mettre en pause",
+  "settings.batteryUnavailable": "L'état de la batterie n'est pas disponible sur ce système",
+  "settings.batteryPaused": "Batterie à {percent}% : nouveaux téléchargements en pause",
+  "settings.batteryOnBattery": "Sur batterie à {percent}%",
+  "settings.batteryCharging": "Sur secteur ({percent}%)",
+  "settings.pauseAboveCpu": "Mettre en pause quand le processeur est occupé",
+  "settings.pauseAboveCpuDesc": "Ne pas démarrer de nouveaux téléchargements tant que l'utilisation du processeur reste au-dessus de ce seuil (%), par ex. en jeu ou pendant un rendu. Laisser vide pour ne jamais mettre en pause",
+  "settings.cpuSampling": "Mesure de l'utilisation du processeur...",
+  "settings.cpuPaused": "Processeur à {percent}% : les nouveaux téléchargements sont en pause",
+  "settings.cpuUsage": "Processeur à {percent}%",
+  "settings.cpuSampleSecs": "Secondes entre les mesures",
+  "settings.cpuSustainSecs": "Secondes pendant lesquelles la charge doit durer avant la pause ou la reprise",
+  "settings.sessionLimit": "Par session",
+  "settings.dailyLimit": "Par jour",
+  "settings.limitUsed": "{used}/{limit} utilisés",
+  "settings.limitUsedUnlimited": "{used} utilisés",
 
   // Update
   "update.checkUpdate": "Vérifier les mises à jour",
@@ -198,6 +376,7 @@ const fr: Record<string, string> = {
   "logs.searchPlaceholder": "Rechercher dans les journaux...",
   "logs.empty": "Aucun journal",
   "logs.live": "En direct",
+  "logs.activity": "Activité des dernières 24 heures",
   "logs.clearLogs": "Effacer",
   "logs.clearConfirm": "Effacer tous les journaux ?",
   "logs.cleared": "Journaux effacés",
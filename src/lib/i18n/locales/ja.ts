@@ -18,9 +18,17 @@ const ja: Record<string, string> = {
   "layout.notInstalled": "未インストール",
   "layout.ffmpegNote": "ffmpegも一緒にインストールすると最高画質の動画結合が可能になります。",
   "layout.noActiveDownloads": "アクティブなダウンロードはありません",
+  "layout.live": "ライブ",
+  "layout.networkUnreachable": "一部のホストに接続できません。依存関係のインストールやダウンロードが失敗する可能性があります。ファイアウォールやプロキシを確認してください",
+  "layout.networkRetest": "再確認",
+  "layout.networkDismiss": "閉じる",
+  "layout.drainWaiting": "{count}件のダウンロード完了後に終了します...",
+  "layout.drain.cancelling": "制限時間に達したため残りのダウンロードをキャンセルしています...",
+  "layout.drain.exiting": "ダウンロードが完了しました。終了しています...",
   "layout.cancelAll": "すべてキャンセル",
   "layout.queueAdded": "{count}件の動画がキューに追加されました。",
   "layout.downloadComplete": "{title}のダウンロードが完了しました。",
+  "layout.dataCapWarning": "今月のダウンロード量がデータ上限の {percent}% に達しました",
   "layout.queued": "待機中",
   "layout.recent": "最近の完了",
   "layout.viewAll": "すべて表示",
@@ -64,7 +72,12 @@ const ja: Record<string, string> = {
   "debug.resetDone": "リセット完了。再起動中...",
 
   "download.invalidUrl": "無効なURLです",
+  "download.partOfPlaylist": "この動画はプレイリストの一部です。この動画のみダウンロードします。",
+  "download.openedAsPlaylist": "このリンクをプレイリストとして開きました。",
+  "download.wholePlaylistInstead": "代わりにプレイリスト全体をダウンロード",
+  "download.singleVideoInstead": "この動画のみ",
   "download.alreadyInQueue": "この動画はすでにダウンロードキューにあります。",
+  "download.videoBlocked": "この動画はブロックリストにあるため、ダウンロードされません。",
   "download.analyzing": "メタデータを取得中...",
   "download.loadingFormats": "動画形式を読み込み中...",
   "download.urlPlaceholder": "動画URLを貼り付けてください（YouTube、TikTok、Twitter、Vimeo...）",
@@ -92,6 +105,9 @@ const ja: Record<string, string> = {
   "download.uploaderFolder": "投稿者フォルダ",
   "download.uploadDate": "アップロード日",
   "download.videoId": "動画ID",
+  "download.templateWarnings": "テンプレートの警告 {count}件",
+  "download.caseCollision": "大文字小文字を区別しないフォルダー",
+  "download.caseCollisionHint": "このフォルダーは 'Title' と 'title' を同じファイルとして扱うため、大文字小文字だけが異なるタイトルの動画は上書きされます。ファイル名に動画 ID を含めてください。",
   "download.advancedTooltip": "yt-dlpテンプレート構文を直接入力",
   "download.uploaderFolderTooltip": "チャンネル/投稿者名でサブフォルダを作成",
   "download.uploadDateTooltip": "ファイル名の前にアップロード日を追加",
@@ -101,13 +117,43 @@ const ja: Record<string, string> = {
   "download.downloadFailed": "ダウンロード失敗",
   "download.skippedQueue": "{count}件の動画がスキップされました（すでにキューにあります）。",
   "download.skippedExists": "{count}件の動画がスキップされました（すでにダウンロード済み）。",
+  "download.skippedBlocked": "{count}件の動画がスキップされました（ブロック済み）。",
+  "download.importFromFile": "ファイルからURLをインポート",
+  "download.urlListFile": "URLリスト",
+  "download.importSummary": "{queued}件の動画を追加しました。{skipped}件スキップ、{failed}件が無効または失敗しました。",
+  "download.audioLanguage": "音声言語",
+  "download.audioLanguageDefault": "オリジナル音声",
+  "download.liveFromStart": "最初から",
+  "download.liveFromStartHint": "現在からではなく、ライブ配信の最初から録画します",
+  "download.subtitlesOnly": "字幕のみ",
+  "download.subtitlesOnlyHint": "動画なしで字幕だけを SRT ファイルとして保存します",
+  "download.subtitleLangs": "字幕の言語、カンマ区切り (例: en, ja.*, all)",
+  "download.testFormat": "選択をテスト",
+  "download.testFormatHint": "現在の設定で yt-dlp が選ぶフォーマットを、ダウンロードせずに表示します",
+  "download.testFormatFailed": "テストに失敗しました: {error}",
+  "download.suggestFormat": "フォーマットを提案",
+  "download.suggestFormatHint": "この動画のフォーマットをサイズに対する画質で順位付けします",
+  "download.noFormatRecs": "比較できるサイズの分かるフォーマットがありません",
+  "download.recommended": "おすすめ",
+  "download.smallestAcceptable": "最小で十分",
+  "download.ffmpegMissing": "次のオプションには ffmpeg が必要ですが、インストールされていません。失敗するかスキップされます:",
+  "download.ffmpegFeature.merge": "映像と音声の結合",
+  "download.ffmpegFeature.recode": "再エンコード",
+  "download.ffmpegFeature.embedMetadata": "メタデータの埋め込み",
+  "download.ffmpegFeature.embedThumbnail": "サムネイルの埋め込み",
+  "download.ffmpegFeature.embedChapters": "チャプターの埋め込み",
+  "download.ffmpegFeature.convertSubs": "字幕の変換",
   "download.redownload": "再ダウンロード",
   "download.cancel": "キャンセル",
   "download.alreadyDownloaded": "「{title}」はすでにダウンロード済みです。",
+  "download.fileOnDisk": "ディスクに既にあります: {path}",
+  "download.overwrite": "上書き",
+  "download.saveAsCopy": "コピーとして保存",
   "download.error": "エラー",
   "download.duplicateFound": "重複を検出",
   "download.include": "含む:",
   "download.cookie": "Cookie:",
+  "download.defaultBrowser": "{browser}（既定）",
   "download.concurrent": "同時:",
   "download.emptyState": "URLを貼り付けてダウンロードを開始",
 
@@ -132,6 +178,37 @@ const ja: Record<string, string> = {
   "history.searchPlaceholder": "タイトルで検索...",
   "history.empty": "ダウンロード履歴がありません",
   "history.deleteConfirm": "この項目を削除しますか？",
+  "history.superseded": "再ダウンロード済み",
+  "history.suspect": "要確認",
+  "history.bulkTagPlaceholder": "タグ",
+  "history.bulkTag": "一致する {count} 件すべてにタグ付け",
+  "history.bulkUntag": "一致した項目からタグを削除",
+  "history.bulkTagResult": "{count} 件を更新しました",
+  "history.favoritesOnly": "お気に入りのみ",
+  "history.favorite": "お気に入りに追加",
+  "history.unfavorite": "お気に入りから削除",
+  "history.sortBy": "並べ替え",
+  "history.sortDate": "日付",
+  "history.sortSize": "サイズ",
+  "history.sortDuration": "再生時間",
+  "history.sortTitle": "タイトル",
+  "history.sortAsc": "昇順",
+  "history.sortDesc": "降順",
+  "history.renameFiles": "ファイル名を変更",
+  "history.renameHint": "ダウンロード済みファイルを新しい出力テンプレートに合わせて名前変更します。フォルダーは変わりません。プレビューしてから適用してください。",
+  "history.renameTemplate": "出力テンプレート",
+  "history.renamePreview": "プレビュー",
+  "history.renameApply": "{count} 件を変更",
+  "history.renameConfirm": "ディスク上の {count} 件のファイル名を変更しますか？",
+  "history.renameDone": "{count} 件のファイル名を変更しました",
+  "history.renameNothing": "すべてのファイルがこのテンプレートに一致しています",
+  "history.renameStatus.ready": "変更",
+  "history.renameStatus.collision": "名前が重複",
+  "history.renameStatus.missing": "ファイルなし",
+  "history.renameStatus.unresolved": "埋められない",
+  "history.renameStatus.unchanged": "変更なし",
+  "history.exportPlaylist": "このページをプレイリストとして書き出す",
+  "history.playlistExported": "{count}件のプレイリストを保存しました。{skipped}件はファイルがないためスキップしました。",
 
   "settings.title": "設定",
   "settings.subtitle": "ダウンロード設定",
@@ -150,10 +227,21 @@ const ja: Record<string, string> = {
   "settings.theme": "テーマ",
   "settings.general": "一般",
   "settings.appearance": "外観",
+  "settings.issuesTitle": "以下の問題を直すまで設定は保存されません",
+  "settings.warningsTitle": "保存しましたが、以下の設定を確認してください",
   "settings.dependencies": "依存関係",
   "settings.notInstalled": "未インストール",
   "settings.appManaged": "アプリ管理",
+  "settings.ffmpegPinned": "{version} に固定",
+  "settings.ffmpegPin": "バージョンを固定",
+  "settings.ffmpegRollback": "元に戻す",
+  "settings.ffmpegUnpin": "固定を解除",
+  "settings.ffmpegUnpinned": "固定を解除しました。更新は最新ビルドに従います",
+  "settings.ytdlpBackupCount": "保持するバックアップ数",
+  "settings.ytdlpBackupBroken": "実行できません",
+  "settings.ytdlpRollback": "ロールバック",
   "settings.systemPath": "システムPATH",
+  "settings.registeredBinary": "登録済み",
   "settings.install": "インストール",
   "settings.update": "更新",
   "settings.updating": "更新中...",
@@ -164,6 +252,8 @@ const ja: Record<string, string> = {
   "settings.depModeSystemDesc": "システムPATHにインストール済みのバイナリを使用します。手動インストールが必要です。",
   "settings.depModeLabel": "依存関係の管理方法は？",
   "settings.installAll": "未インストール項目を一括インストール",
+  "settings.rescanDeps": "再スキャン",
+  "settings.rescanDepsDesc": "パッケージマネージャーでインストールした後などに yt-dlp と ffmpeg を再検出します",
   "settings.reinstall": "再インストール",
 
   "theme.dark": "ダーク",
@@ -177,6 +267,94 @@ const ja: Record<string, string> = {
   "tray.quit": "終了",
   "settings.minimizeToTray": "トレイに最小化",
   "settings.minimizeToTrayDesc": "ウィンドウを閉じる時にシステムトレイに最小化",
+  "settings.singleVideoDefault": "プレイリストリンクから単一動画",
+  "settings.singleVideoDefaultDesc": "動画URLにプレイリストが含まれていても、その動画のみダウンロードします",
+  "settings.autoContainer": "スマートコンテナ変換",
+  "settings.autoContainerDesc": "別のコンテナに変換する際、コーデックが対応していればリマックス（高速・無劣化）し、対応していない場合のみ再エンコード（低速・劣化あり）します。各ダウンロード前に短いフォーマット確認が入ります",
+  "settings.verifyDownloads": "ダウンロードを検証",
+  "settings.startupNetworkCheck": "起動時のネットワーク確認",
+  "settings.startupNetworkCheckDesc": "GitHubとYouTubeに接続できるかをバックグラウンドで確認し、できない場合は通知します",
+  "settings.verifyDownloadsDesc": "完了したファイルを ffprobe で検査し、元の長さと比較します。失敗したファイルは履歴で要確認として表示されます。ダウンロードごとに数秒かかります",
+  "settings.similarityFingerprints": "類似ダウンロードの検出",
+  "settings.similarityFingerprintsDesc": "タイトルと長さで履歴を照合し、別の画質で保存した同じ動画を見つけます。新しいURLを調べるときはサイトに情報を問い合わせます",
+  "settings.retrySuspectDownloads": "要確認ファイルを再ダウンロード",
+  "settings.retrySuspectDownloadsDesc": "検証で要確認とされたファイルをもう一度ダウンロードします",
+  "settings.maxFilesize": "最大ファイルサイズ",
+  "settings.maxFilesizeDesc": "これより大きいダウンロードはスキップします（例: 2G、500M）。空欄で無制限",
+  "settings.maxFilesizeInvalid": "無効なサイズです。数値とK、M、G、Tを使用してください（例: 2G）",
+  "settings.monthlyDataCap": "月間データ上限",
+  "settings.monthlyDataCapDesc": "今月のダウンロード量がこの値に近づくと警告します (例: 50G)。空欄で上限なし",
+  "settings.dataUsage": "今日 {today} · 今月 {month} · 累計 {total}",
+  "settings.dataCapUsed": "上限の {percent}%",
+  "settings.headers": "HTTPヘッダー",
+  "settings.headersDesc": "すべてのダウンロードで送信されます。一部のサイトではReferer（動画が埋め込まれたページ）やブラウザのUser-Agentが必要です。追加ヘッダーは1行に1つ「名前: 値」の形式で入力してください",
+  "settings.hostLists": "許可・ブロックするサイト",
+  "settings.hostListsDesc": "1行に1ホスト。*.example.com はサブドメインも含みます。ブロックが優先され、許可リストに項目があるとそのサイトだけダウンロードできます。",
+  "settings.hostAllowlist": "許可リスト",
+  "settings.hostDenylist": "ブロックリスト",
+  "settings.codecPreference": "優先する動画コーデック",
+  "settings.codecPreferenceDesc": "カンマ区切りで優先順に (av01, vp9, h265, h264 など)。動画が提供していれば使われ、実際のコーデックは履歴に残ります。",
+  "settings.userAgent": "User-Agent",
+  "settings.referer": "Referer URL",
+  "settings.extraHeaders": "追加ヘッダー",
+  "settings.outputPathsByType": "ファイル種別ごとのフォルダ",
+  "settings.outputPathsByTypeDesc": "動画、音声のみのダウンロード、字幕、サムネイルを別々のフォルダに保存します。未指定の種別はダウンロードフォルダを使います",
+  "settings.outputType.video": "動画",
+  "settings.outputType.audio": "音声",
+  "settings.outputType.subtitle": "字幕",
+  "settings.outputType.thumbnail": "サムネイル",
+  "settings.outputTypeDefault": "ダウンロードフォルダ",
+  "settings.outputTypeClear": "クリア",
+  "settings.logBurstPolicy": "ログの急増",
+  "settings.logBurstPolicyDesc": "毎秒約200行を超えるログの扱い方です。警告とエラーは常に記録されます。",
+  "settings.logBurstPolicy.full": "すべて記録",
+  "settings.logBurstPolicy.sampled": "10行に1行",
+  "settings.logBurstPolicy.summarized": "要約",
+  "settings.logMaxPageSize": "ログビューアのページサイズ上限",
+  "settings.logMaxPageSizeDesc": "1ページに読み込むログの最大件数です。メモリの少ない端末では下げてください（最大2000）",
+  "settings.cleanupIdleSecs": "クリーンアップ待機時間（秒）",
+  "settings.cleanupIdleSecsDesc": "ログの整理とデータベースの圧縮は、この時間ダウンロードがない状態になってから実行されます",
+  "settings.cleanupNow": "アイドル時にクリーンアップ",
+  "settings.cleanupScheduled": "キューがアイドルになったらクリーンアップを実行します",
+  "settings.queueWatchdogSecs": "キュー監視の間隔（秒）",
+  "settings.queueWatchdogSecsDesc": "何も実行されていないのに待機中のダウンロードが始まらない場合、キューを再開します。この間隔で確認し、0 で無効になります",
+  "settings.queueWatchdogCheck": "今すぐ確認",
+  "settings.queueWatchdogRestarted": "キューが止まっていたため再開しました（{count} 件開始）",
+  "settings.queueWatchdogOk": "キューは正常に動いています",
+  "settings.backup": "バックアップ",
+  "settings.backupDesc": "設定・キュー・履歴・ログを1つのアーカイブに",
+  "settings.backupRedact": "エクスポートする設定の秘密情報を伏せる",
+  "settings.backupExport": "エクスポート",
+  "settings.backupReplace": "インポート時に既存データを置き換える",
+  "settings.backupImport": "インポート",
+  "settings.backupExported": "バックアップを保存しました",
+  "settings.backupImported": "履歴{history}件、キュー{queue}件、ログ{logs}件をインポートしました",
+  "settings.backupSettingsRestored": "設定を復元しました",
+  "settings.diagnostics": "診断情報",
+  "settings.diagnosticsDesc": "不具合報告用のアプリ情報、ヘルスチェック、マスク済み設定、最近のログ",
+  "settings.diagnosticsExported": "診断情報を保存しました",
+  "settings.maxRetries": "自動リトライ",
+  "settings.maxRetriesDesc": "ネットワークやレート制限のエラー後、待ち時間を延ばしながら再試行します（0で無効）。2つ目の欄：最初の待ち時間（秒）",
+  "settings.retryBackoff": "最初のリトライまでの待ち時間（秒）",
+  "settings.downloadLimits": "ダウンロード上限",
+  "settings.downloadLimitsDesc": "セッションごと・1日ごとに開始できるタスクの上限です。上限を超えたタスクは待機のままになります。空欄で無制限",
+  "settings.pauseBelowBattery": "バッテリー低下時に一時停止",
+  "settings.pauseBelowBatteryDesc": "バッテリー駆動中に残量がこの値 (%) を下回ると新しいダウンロードを開始しません。空欄なら停止しません",
+  "settings.batteryUnavailable": "このシステムではバッテリー状態を取得できません",
+  "settings.batteryPaused": "バッテリー {percent}%: 新しいダウンロードを一時停止中",
+  "settings.batteryOnBattery": "バッテリー駆動中 {percent}%",
+  "settings.batteryCharging": "電源接続中 ({percent}%)",
+  "settings.pauseAboveCpu": "CPU 負荷が高いときに一時停止",
+  "settings.pauseAboveCpuDesc": "CPU 使用率がこの値 (%) を超えている間は新しいダウンロードを開始しません（ゲームやレンダリング中など）。空欄にすると一時停止しません",
+  "settings.cpuSampling": "CPU 使用率を測定中...",
+  "settings.cpuPaused": "CPU {percent}%: 新しいダウンロードは一時停止中です",
+  "settings.cpuUsage": "CPU {percent}%",
+  "settings.cpuSampleSecs": "測定の間隔（秒）",
+  "settings.cpuSustainSecs": "一時停止・再開までに負荷が続く必要がある秒数",
+  "settings.sessionLimit": "セッションごと",
+  "settings.dailyLimit": "1日ごと",
+  "settings.limitUsed": "{used}/{limit} 使用",
+  "settings.limitUsedUnlimited": "{used} 使用",
 
   // Update
   "update.checkUpdate": "アップデートを確認",
@@ -198,6 +376,7 @@ const ja: Record<string, string> = {
   "logs.searchPlaceholder": "ログを検索...",
   "logs.empty": "ログがありません",
   "logs.live": "リアルタイム",
+  "logs.activity": "過去 24 時間のアクティビティ",
   "logs.clearLogs": "削除",
   "logs.clearConfirm": "すべてのログを削除しますか？",
   "logs.cleared": "ログが削除されました",
@@ -18,9 +18,17 @@ const ko: Record<string, string> = {
   "layout.notInstalled": "미설치",
   "layout.ffmpegNote": "ffmpeg도 함께 설치하면 최고 화질 영상 병합을 지원합니다.",
   "layout.noActiveDownloads": "활성 다운로드가 없습니다",
+  "layout.live": "라이브",
+  "layout.networkUnreachable": "일부 호스트에 연결할 수 없습니다. 의존성 설치나 다운로드가 실패할 수 있으니 방화벽 또는 프록시를 확인하세요",
+  "layout.networkRetest": "다시 확인",
+  "layout.networkDismiss": "닫기",
+  "layout.drainWaiting": "다운로드 {count}개가 끝나면 종료합니다...",
+  "layout.drain.cancelling": "제한 시간이 지나 남은 다운로드를 취소하는 중...",
+  "layout.drain.exiting": "다운로드가 끝나 종료하는 중...",
   "layout.cancelAll": "모두 취소",
   "layout.queueAdded": "{count}개 영상이 대기열에 등록되었습니다.",
   "layout.downloadComplete": "{title}의 다운로드가 완료되었습니다.",
+  "layout.dataCapWarning": "이번 달 다운로드량이 데이터 한도의 {percent}%에 도달했습니다",
   "layout.queued": "대기 중",
   "layout.recent": "최근 완료",
   "layout.viewAll": "전체 보기",
@@ -64,7 +72,12 @@ const ko: Record<string, string> = {
   "debug.resetDone": "초기화 완료. 재시작 중...",
 
   "download.invalidUrl": "유효하지 않은 URL입니다",
+  "download.partOfPlaylist": "이 영상은 재생목록에 포함되어 있습니다. 이 영상만 다운로드합니다.",
+  "download.openedAsPlaylist": "이 링크를 재생목록으로 열었습니다.",
+  "download.wholePlaylistInstead": "대신 재생목록 전체 다운로드",
+  "download.singleVideoInstead": "이 영상만",
   "download.alreadyInQueue": "이미 다운로드 큐에 있는 영상입니다.",
+  "download.videoBlocked": "차단 목록에 있는 영상이라 다운로드하지 않습니다.",
   "download.analyzing": "메타데이터를 조회하는 중...",
   "download.loadingFormats": "비디오 형식을 불러오는 중...",
   "download.urlPlaceholder": "동영상 URL을 붙여넣으세요 (YouTube, TikTok, Twitter, Vimeo...)",
@@ -92,6 +105,9 @@ const ko: Record<string, string> = {
   "download.uploaderFolder": "업로더 폴더",
   "download.uploadDate": "업로드 날짜",
   "download.videoId": "영상 ID",
+  "download.templateWarnings": "템플릿 경고 {count}개",
+  "download.caseCollision": "대소문자 구분 없는 폴더",
+  "download.caseCollisionHint": "이 폴더는 'Title'과 'title'을 같은 파일로 취급하므로 제목이 대소문자만 다른 동영상은 서로 덮어씁니다. 파일 이름에 동영상 ID를 포함하세요.",
   "download.advancedTooltip": "yt-dlp 템플릿 문법으로 직접 입력",
   "download.uploaderFolderTooltip": "채널/업로더 이름으로 하위 폴더 생성",
   "download.uploadDateTooltip": "파일명 앞에 업로드 날짜 추가",
@@ -101,13 +117,43 @@ const ko: Record<string, string> = {
   "download.downloadFailed": "다운로드 실패",
   "download.skippedQueue": "{count}개 영상이 이미 큐에 있어 건너뛰었습니다.",
   "download.skippedExists": "{count}개 영상이 이미 다운로드되어 건너뛰었습니다.",
+  "download.skippedBlocked": "{count}개 영상이 차단되어 건너뛰었습니다.",
+  "download.importFromFile": "파일에서 URL 가져오기",
+  "download.urlListFile": "URL 목록",
+  "download.importSummary": "{queued}개 영상을 추가했습니다. {skipped}개 건너뜀, {failed}개 잘못되었거나 실패했습니다.",
+  "download.audioLanguage": "오디오 언어",
+  "download.audioLanguageDefault": "원본 오디오",
+  "download.liveFromStart": "처음부터",
+  "download.liveFromStartHint": "지금부터가 아니라 라이브 방송의 처음부터 녹화합니다",
+  "download.subtitlesOnly": "자막만",
+  "download.subtitlesOnlyHint": "동영상 없이 자막만 SRT 파일로 저장합니다",
+  "download.subtitleLangs": "자막 언어, 쉼표로 구분 (예: en, ja.*, all)",
+  "download.testFormat": "선택 테스트",
+  "download.testFormatHint": "다운로드하지 않고 현재 설정에서 yt-dlp가 고를 포맷을 보여줍니다",
+  "download.testFormatFailed": "테스트 실패: {error}",
+  "download.suggestFormat": "포맷 추천",
+  "download.suggestFormatHint": "이 영상의 포맷을 크기 대비 화질 순으로 정렬합니다",
+  "download.noFormatRecs": "크기를 알 수 있는 비교할 포맷이 없습니다",
+  "download.recommended": "추천",
+  "download.smallestAcceptable": "최소 적정",
+  "download.ffmpegMissing": "다음 옵션에는 ffmpeg가 필요하지만 설치되어 있지 않습니다. 실패하거나 건너뜁니다:",
+  "download.ffmpegFeature.merge": "비디오/오디오 병합",
+  "download.ffmpegFeature.recode": "재인코딩",
+  "download.ffmpegFeature.embedMetadata": "메타데이터 삽입",
+  "download.ffmpegFeature.embedThumbnail": "썸네일 삽입",
+  "download.ffmpegFeature.embedChapters": "챕터 삽입",
+  "download.ffmpegFeature.convertSubs": "자막 변환",
   "download.redownload": "다시 다운로드",
   "download.cancel": "취소",
   "download.alreadyDownloaded": "\"{title}\"은(는) 이미 다운로드한 적이 있습니다.",
+  "download.fileOnDisk": "디스크에 이미 있음: {path}",
+  "download.overwrite": "덮어쓰기",
+  "download.saveAsCopy": "사본으로 저장",
   "download.error": "오류",
   "download.duplicateFound": "중복 발견",
   "download.include": "포함:",
   "download.cookie": "쿠키:",
+  "download.defaultBrowser": "{browser} (기본)",
   "download.concurrent": "동시:",
   "download.emptyState": "URL을 붙여넣어 다운로드를 시작하세요",
 
@@ -132,6 +178,37 @@ const ko: Record<string, string> = {
   "history.searchPlaceholder": "제목으로 검색...",
   "history.empty": "다운로드 이력이 없습니다",
   "history.deleteConfirm": "이 항목을 삭제하시겠습니까?",
+  "history.superseded": "다시 다운로드됨",
+  "history.suspect": "의심 파일",
+  "history.bulkTagPlaceholder": "태그",
+  "history.bulkTag": "일치하는 {count}개 모두 태그",
+  "history.bulkUntag": "일치 항목에서 태그 제거",
+  "history.bulkTagResult": "{count}개 항목 변경됨",
+  "history.favoritesOnly": "즐겨찾기만",
+  "history.favorite": "즐겨찾기에 추가",
+  "history.unfavorite": "즐겨찾기에서 제거",
+  "history.sortBy": "정렬 기준",
+  "history.sortDate": "날짜",
+  "history.sortSize": "크기",
+  "history.sortDuration": "길이",
+  "history.sortTitle": "제목",
+  "history.sortAsc": "오름차순",
+  "history.sortDesc": "내림차순",
+  "history.renameFiles": "파일 이름 변경",
+  "history.renameHint": "다운로드한 파일 이름을 새 출력 템플릿에 맞게 바꿉니다. 파일은 원래 폴더에 남습니다. 미리 보기 후 적용하세요.",
+  "history.renameTemplate": "출력 템플릿",
+  "history.renamePreview": "미리 보기",
+  "history.renameApply": "{count}개 변경",
+  "history.renameConfirm": "디스크의 파일 {count}개 이름을 바꿀까요?",
+  "history.renameDone": "파일 {count}개 이름 변경됨",
+  "history.renameNothing": "모든 파일이 이미 이 템플릿과 일치합니다",
+  "history.renameStatus.ready": "변경",
+  "history.renameStatus.collision": "이름 중복",
+  "history.renameStatus.missing": "파일 없음",
+  "history.renameStatus.unresolved": "채울 수 없음",
+  "history.renameStatus.unchanged": "변경 없음",
+  "history.exportPlaylist": "이 페이지를 재생목록으로 내보내기",
+  "history.playlistExported": "{count}개 항목으로 재생목록을 저장했습니다. {skipped}개는 파일이 없어 건너뛰었습니다.",
 
   "settings.title": "설정",
   "settings.subtitle": "다운로드 환경 설정",
@@ -150,10 +227,21 @@ const ko: Record<string, string> = {
   "settings.theme": "테마",
   "settings.general": "일반",
   "settings.appearance": "외관",
+  "settings.issuesTitle": "아래 문제를 고칠 때까지 설정이 저장되지 않습니다",
+  "settings.warningsTitle": "저장했지만 아래 설정을 확인하세요",
   "settings.dependencies": "의존성",
   "settings.notInstalled": "미설치",
   "settings.appManaged": "앱 관리",
+  "settings.ffmpegPinned": "{version} 고정",
+  "settings.ffmpegPin": "버전 고정",
+  "settings.ffmpegRollback": "되돌리기",
+  "settings.ffmpegUnpin": "고정 해제",
+  "settings.ffmpegUnpinned": "고정을 해제했습니다. 업데이트는 최신 빌드를 따릅니다",
+  "settings.ytdlpBackupCount": "보관할 백업 수",
+  "settings.ytdlpBackupBroken": "실행 불가",
+  "settings.ytdlpRollback": "롤백",
   "settings.systemPath": "시스템 PATH",
+  "settings.registeredBinary": "등록된 바이너리",
   "settings.install": "설치",
   "settings.update": "업데이트",
   "settings.updating": "업데이트 중...",
@@ -164,6 +252,8 @@ const ko: Record<string, string> = {
   "settings.depModeSystemDesc": "시스템 PATH에 설치된 바이너리를 사용합니다. 수동 설치가 필요합니다.",
   "settings.depModeLabel": "의존성을 어떻게 관리할까요?",
   "settings.installAll": "누락 항목 전체 설치",
+  "settings.rescanDeps": "다시 검색",
+  "settings.rescanDepsDesc": "패키지 관리자로 설치한 뒤 등 yt-dlp와 ffmpeg를 다시 찾습니다",
   "settings.reinstall": "재설치",
 
   "theme.dark": "다크",
@@ -177,6 +267,94 @@ const ko: Record<string, string> = {
   "tray.quit": "종료",
   "settings.minimizeToTray": "트레이로 최소화",
   "settings.minimizeToTrayDesc": "창을 닫을 때 시스템 트레이로 최소화",
+  "settings.singleVideoDefault": "재생목록 링크에서 단일 영상",
+  "settings.singleVideoDefaultDesc": "영상 URL에 재생목록이 포함되어 있어도 해당 영상만 다운로드합니다",
+  "settings.autoContainer": "스마트 컨테이너 변환",
+  "settings.autoContainerDesc": "다른 컨테이너로 변환할 때 코덱이 호환되면 리먹스(빠르고 무손실)하고, 호환되지 않을 때만 재인코딩(느리고 손실)합니다. 다운로드 전 짧은 포맷 확인이 추가됩니다",
+  "settings.verifyDownloads": "다운로드 검증",
+  "settings.startupNetworkCheck": "시작 시 네트워크 확인",
+  "settings.startupNetworkCheckDesc": "백그라운드에서 GitHub와 YouTube 연결을 확인하고 연결할 수 없으면 알립니다",
+  "settings.verifyDownloadsDesc": "완료된 파일을 ffprobe로 검사하고 원본 길이와 비교합니다. 실패한 파일은 기록에 의심으로 표시됩니다. 다운로드마다 몇 초가 추가됩니다",
+  "settings.similarityFingerprints": "비슷한 다운로드 찾기",
+  "settings.similarityFingerprintsDesc": "제목과 길이로 기록을 비교해 다른 화질로 받은 같은 영상을 찾습니다. 새 URL을 확인할 때는 사이트에 정보를 요청합니다",
+  "settings.retrySuspectDownloads": "의심 파일 다시 받기",
+  "settings.retrySuspectDownloadsDesc": "검증에서 의심으로 표시된 파일을 한 번 더 다운로드합니다",
+  "settings.maxFilesize": "최대 파일 크기",
+  "settings.maxFilesizeDesc": "이보다 큰 다운로드는 건너뜁니다 (예: 2G, 500M). 비워 두면 제한 없음",
+  "settings.maxFilesizeInvalid": "잘못된 크기입니다. 숫자와 K, M, G, T를 사용하세요 (예: 2G)",
+  "settings.monthlyDataCap": "월간 데이터 한도",
+  "settings.monthlyDataCapDesc": "이번 달 다운로드량이 이 값에 가까워지면 경고합니다 (예: 50G). 비워 두면 한도 없음",
+  "settings.dataUsage": "오늘 {today} · 이번 달 {month} · 전체 {total}",
+  "settings.dataCapUsed": "한도의 {percent}%",
+  "settings.headers": "HTTP 헤더",
+  "settings.headersDesc": "모든 다운로드에 함께 전송됩니다. 일부 사이트는 Referer(동영상이 삽입된 페이지 주소)나 브라우저 User-Agent가 필요합니다. 추가 헤더는 한 줄에 하나씩 '이름: 값' 형식으로 입력하세요",
+  "settings.hostLists": "허용 및 차단 사이트",
+  "settings.hostListsDesc": "한 줄에 호스트 하나. *.example.com은 하위 도메인도 포함합니다. 차단 목록이 우선하며, 허용 목록에 항목이 있으면 그 사이트만 다운로드할 수 있습니다.",
+  "settings.hostAllowlist": "허용 목록",
+  "settings.hostDenylist": "차단 목록",
+  "settings.codecPreference": "선호 비디오 코덱",
+  "settings.codecPreferenceDesc": "쉼표로 구분, 우선순위 순 (av01, vp9, h265, h264 등). 영상이 해당 코덱을 제공할 때 사용되며, 실제로 받은 코덱은 기록에 남습니다.",
+  "settings.userAgent": "User-Agent",
+  "settings.referer": "Referer URL",
+  "settings.extraHeaders": "추가 헤더",
+  "settings.outputPathsByType": "파일 유형별 폴더",
+  "settings.outputPathsByTypeDesc": "동영상, 오디오 전용 다운로드, 자막, 썸네일을 각각 다른 폴더에 저장합니다. 폴더를 지정하지 않은 유형은 다운로드 폴더를 사용합니다",
+  "settings.outputType.video": "동영상",
+  "settings.outputType.audio": "오디오",
+  "settings.outputType.subtitle": "자막",
+  "settings.outputType.thumbnail": "썸네일",
+  "settings.outputTypeDefault": "다운로드 폴더",
+  "settings.outputTypeClear": "지우기",
+  "settings.logBurstPolicy": "로그 폭주",
+  "settings.logBurstPolicyDesc": "초당 약 200줄을 넘는 로그를 처리하는 방법입니다. 경고와 오류는 항상 기록됩니다.",
+  "settings.logBurstPolicy.full": "모두 기록",
+  "settings.logBurstPolicy.sampled": "10줄 중 1줄만",
+  "settings.logBurstPolicy.summarized": "요약",
+  "settings.logMaxPageSize": "로그 뷰어 페이지 크기 제한",
+  "settings.logMaxPageSizeDesc": "한 페이지에 불러오는 최대 로그 수입니다. 메모리가 적은 기기에서는 낮추세요 (최대 2000)",
+  "settings.cleanupIdleSecs": "정리 대기 시간(초)",
+  "settings.cleanupIdleSecsDesc": "로그 정리와 데이터베이스 압축은 이 시간 동안 다운로드가 없을 때 실행됩니다",
+  "settings.cleanupNow": "유휴 시 정리",
+  "settings.cleanupScheduled": "대기열이 유휴 상태가 되면 정리가 실행됩니다",
+  "settings.queueWatchdogSecs": "대기열 감시 간격(초)",
+  "settings.queueWatchdogSecsDesc": "실행 중인 작업이 없는데 대기 중인 다운로드가 시작되지 않으면 대기열을 다시 시작합니다. 이 간격으로 확인하며 0이면 꺼집니다",
+  "settings.queueWatchdogCheck": "지금 확인",
+  "settings.queueWatchdogRestarted": "대기열이 멈춰 있어 다시 시작했습니다 ({count}개 시작)",
+  "settings.queueWatchdogOk": "대기열이 정상적으로 진행 중입니다",
+  "settings.backup": "백업",
+  "settings.backupDesc": "설정, 대기열, 기록, 로그를 하나의 압축 파일로",
+  "settings.backupRedact": "내보낸 설정의 비밀 값 가리기",
+  "settings.backupExport": "내보내기",
+  "settings.backupReplace": "가져올 때 기존 데이터 교체",
+  "settings.backupImport": "가져오기",
+  "settings.backupExported": "백업을 저장했습니다",
+  "settings.backupImported": "기록 {history}개, 대기 작업 {queue}개, 로그 {logs}개를 가져왔습니다",
+  "settings.backupSettingsRestored": "설정을 복원했습니다",
+  "settings.diagnostics": "진단 정보",
+  "settings.diagnosticsDesc": "버그 신고용 앱 정보, 상태 점검, 가려진 설정, 최근 로그",
+  "settings.diagnosticsExported": "진단 정보를 저장했습니다",
+  "settings.maxRetries": "자동 재시도",
+  "settings.maxRetriesDesc": "네트워크 또는 요청 제한 오류 후 점점 더 오래 기다리며 재시도합니다 (0이면 끔). 두 번째 칸: 첫 대기 시간(초)",
+  "settings.retryBackoff": "첫 재시도 대기 시간(초)",
+  "settings.downloadLimits": "다운로드 한도",
+  "settings.downloadLimitsDesc": "세션당, 하루당 시작할 수 있는 최대 작업 수입니다. 한도를 넘으면 작업은 대기 상태로 남습니다. 비워 두면 제한 없음",
+  "settings.pauseBelowBattery": "배터리 부족 시 일시 중지",
+  "settings.pauseBelowBatteryDesc": "배터리 사용 중 잔량이 이 값(%) 미만이면 새 다운로드를 시작하지 않습니다. 비워 두면 중지하지 않습니다",
+  "settings.batteryUnavailable": "이 시스템에서는 배터리 상태를 확인할 수 없습니다",
+  "settings.batteryPaused": "배터리 {percent}%: 새 다운로드가 일시 중지되었습니다",
+  "settings.batteryOnBattery": "배터리 사용 중 {percent}%",
+  "settings.batteryCharging": "전원 연결됨 ({percent}%)",
+  "settings.pauseAboveCpu": "CPU 사용량이 높을 때 일시정지",
+  "settings.pauseAboveCpuDesc": "전체 CPU 사용량이 이 값(%)을 넘는 동안 새 다운로드를 시작하지 않습니다(게임, 렌더링 중 등). 비워 두면 일시정지하지 않습니다",
+  "settings.cpuSampling": "CPU 사용량 측정 중...",
+  "settings.cpuPaused": "CPU {percent}%: 새 다운로드가 일시정지되었습니다",
+  "settings.cpuUsage": "CPU {percent}%",
+  "settings.cpuSampleSecs": "측정 간격(초)",
+  "settings.cpuSustainSecs": "일시정지 또는 재개 전에 부하가 지속되어야 하는 시간(초)",
+  "settings.sessionLimit": "세션당",
+  "settings.dailyLimit": "하루당",
+  "settings.limitUsed": "{used}/{limit} 사용",
+  "settings.limitUsedUnlimited": "{used} 사용",
 
   // Update
   "update.checkUpdate": "업데이트 확인",
@@ -198,6 +376,7 @@ const ko: Record<string, string> = {
   "logs.searchPlaceholder": "로그 검색...",
   "logs.empty": "로그가 없습니다",
   "logs.live": "실시간",
+  "logs.activity": "최근 24시간 활동",
   "logs.clearLogs": "삭제",
   "logs.clearConfirm": "모든 로그를 삭제하시겠습니까?",
   "logs.cleared": "로그가 삭제되었습니다",
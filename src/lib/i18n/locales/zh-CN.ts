@@ -18,9 +18,17 @@ const zhCN: Record<string, string> = {
   "layout.notInstalled": "未安装",
   "layout.ffmpegNote": "同时安装ffmpeg可支持最佳画质视频合并。",
   "layout.noActiveDownloads": "没有正在进行的下载",
+  "layout.live": "直播",
+  "layout.networkUnreachable": "部分主机无法访问。安装依赖或下载可能失败，请检查防火墙或代理",
+  "layout.networkRetest": "重新检测",
+  "layout.networkDismiss": "关闭",
+  "layout.drainWaiting": "{count} 个下载完成后退出...",
+  "layout.drain.cancelling": "已达时间上限，正在取消剩余下载...",
+  "layout.drain.exiting": "下载已完成，正在退出...",
   "layout.cancelAll": "全部取消",
   "layout.queueAdded": "{count}个视频已添加到队列。",
   "layout.downloadComplete": "{title}下载完成。",
+  "layout.dataCapWarning": "本月下载量已达流量上限的 {percent}%",
   "layout.queued": "排队中",
   "layout.recent": "最近完成",
   "layout.viewAll": "查看全部",
@@ -64,7 +72,12 @@ const zhCN: Record<string, string> = {
   "debug.resetDone": "重置完成。正在重启...",
 
   "download.invalidUrl": "无效的URL",
+  "download.partOfPlaylist": "此视频属于一个播放列表。仅下载此视频。",
+  "download.openedAsPlaylist": "此链接已作为播放列表打开。",
+  "download.wholePlaylistInstead": "改为下载整个播放列表",
+  "download.singleVideoInstead": "仅此视频",
   "download.alreadyInQueue": "该视频已在下载队列中。",
+  "download.videoBlocked": "该视频在屏蔽列表中，不会被下载。",
   "download.analyzing": "正在获取元数据...",
   "download.loadingFormats": "正在加载视频格式...",
   "download.urlPlaceholder": "粘贴任意视频URL（YouTube、TikTok、Twitter、Vimeo...）",
@@ -92,6 +105,9 @@ const zhCN: Record<string, string> = {
   "download.uploaderFolder": "上传者文件夹",
   "download.uploadDate": "上传日期",
   "download.videoId": "视频ID",
+  "download.templateWarnings": "{count} 个模板警告",
+  "download.caseCollision": "不区分大小写的文件夹",
+  "download.caseCollisionHint": "此文件夹将 'Title' 和 'title' 视为同一文件，标题仅大小写不同的视频会相互覆盖。请在文件名中加入视频 ID。",
   "download.advancedTooltip": "直接输入yt-dlp模板语法",
   "download.uploaderFolderTooltip": "按频道/上传者名称创建子文件夹",
   "download.uploadDateTooltip": "在文件名前添加上传日期",
@@ -101,13 +117,43 @@ const zhCN: Record<string, string> = {
   "download.downloadFailed": "下载失败",
   "download.skippedQueue": "{count}个视频已跳过（已在队列中）。",
   "download.skippedExists": "{count}个视频已跳过（已下载）。",
+  "download.skippedBlocked": "{count}个视频已跳过（已屏蔽）。",
+  "download.importFromFile": "从文件导入 URL",
+  "download.urlListFile": "URL 列表",
+  "download.importSummary": "已导入 {queued} 个视频；跳过 {skipped} 个，{failed} 个无效或失败。",
+  "download.audioLanguage": "音频语言",
+  "download.audioLanguageDefault": "原始音频",
+  "download.liveFromStart": "从头开始",
+  "download.liveFromStartHint": "从直播开头录制，而不是从现在开始",
+  "download.subtitlesOnly": "仅字幕",
+  "download.subtitlesOnlyHint": "只将字幕保存为 SRT 文件，不下载视频",
+  "download.subtitleLangs": "字幕语言，用逗号分隔（例如 en, ja.*, all）",
+  "download.testFormat": "测试选择",
+  "download.testFormatHint": "不下载，显示 yt-dlp 在当前设置下会选择的格式",
+  "download.testFormatFailed": "测试失败：{error}",
+  "download.suggestFormat": "推荐格式",
+  "download.suggestFormatHint": "按画质与大小之比为此视频的格式排序",
+  "download.noFormatRecs": "没有已知大小的格式可供比较",
+  "download.recommended": "推荐",
+  "download.smallestAcceptable": "最小可接受",
+  "download.ffmpegMissing": "以下选项需要 ffmpeg，但尚未安装。它们将失败或被跳过：",
+  "download.ffmpegFeature.merge": "合并视频和音频",
+  "download.ffmpegFeature.recode": "重新编码",
+  "download.ffmpegFeature.embedMetadata": "嵌入元数据",
+  "download.ffmpegFeature.embedThumbnail": "嵌入缩略图",
+  "download.ffmpegFeature.embedChapters": "嵌入章节",
+  "download.ffmpegFeature.convertSubs": "转换字幕",
   "download.redownload": "重新下载",
   "download.cancel": "取消",
   "download.alreadyDownloaded": "「{title}」已经下载过。",
+  "download.fileOnDisk": "磁盘上已存在：{path}",
+  "download.overwrite": "覆盖",
+  "download.saveAsCopy": "另存为副本",
   "download.error": "错误",
   "download.duplicateFound": "发现重复",
   "download.include": "包含:",
   "download.cookie": "Cookie:",
+  "download.defaultBrowser": "{browser}（默认）",
   "download.concurrent": "并发:",
   "download.emptyState": "粘贴URL开始下载",
 
@@ -132,6 +178,37 @@ const zhCN: Record<string, string> = {
   "history.searchPlaceholder": "按标题搜索...",
   "history.empty": "暂无下载历史",
   "history.deleteConfirm": "确定要删除此项吗？",
+  "history.superseded": "已重新下载",
+  "history.suspect": "可疑文件",
+  "history.bulkTagPlaceholder": "标签",
+  "history.bulkTag": "为全部 {count} 个匹配项添加标签",
+  "history.bulkUntag": "从匹配项移除标签",
+  "history.bulkTagResult": "已更新 {count} 项",
+  "history.favoritesOnly": "仅收藏",
+  "history.favorite": "加入收藏",
+  "history.unfavorite": "取消收藏",
+  "history.sortBy": "排序方式",
+  "history.sortDate": "日期",
+  "history.sortSize": "大小",
+  "history.sortDuration": "时长",
+  "history.sortTitle": "标题",
+  "history.sortAsc": "升序",
+  "history.sortDesc": "降序",
+  "history.renameFiles": "重命名文件",
+  "history.renameHint": "按新的输出模板重命名已下载的文件。文件保留在原文件夹中；请先预览再应用。",
+  "history.renameTemplate": "输出模板",
+  "history.renamePreview": "预览",
+  "history.renameApply": "重命名 {count} 个",
+  "history.renameConfirm": "要重命名磁盘上的 {count} 个文件吗？",
+  "history.renameDone": "已重命名 {count} 个文件",
+  "history.renameNothing": "所有文件已符合此模板",
+  "history.renameStatus.ready": "重命名",
+  "history.renameStatus.collision": "名称冲突",
+  "history.renameStatus.missing": "文件缺失",
+  "history.renameStatus.unresolved": "无法填充",
+  "history.renameStatus.unchanged": "未变化",
+  "history.exportPlaylist": "将本页导出为播放列表",
+  "history.playlistExported": "已保存包含 {count} 项的播放列表；{skipped} 项因文件缺失已跳过。",
 
   "settings.title": "设置",
   "settings.subtitle": "配置下载偏好",
@@ -150,10 +227,21 @@ const zhCN: Record<string, string> = {
   "settings.theme": "主题",
   "settings.general": "常规",
   "settings.appearance": "外观",
+  "settings.issuesTitle": "修复以下问题之前，设置不会被保存",
+  "settings.warningsTitle": "已保存，但请检查以下设置",
   "settings.dependencies": "依赖项",
   "settings.notInstalled": "未安装",
   "settings.appManaged": "应用管理",
+  "settings.ffmpegPinned": "已固定 {version}",
+  "settings.ffmpegPin": "固定版本",
+  "settings.ffmpegRollback": "回滚",
+  "settings.ffmpegUnpin": "取消固定",
+  "settings.ffmpegUnpinned": "已取消固定；更新将跟随最新版本",
+  "settings.ytdlpBackupCount": "保留的备份数",
+  "settings.ytdlpBackupBroken": "无法运行",
+  "settings.ytdlpRollback": "回滚",
   "settings.systemPath": "系统PATH",
+  "settings.registeredBinary": "已注册",
   "settings.install": "安装",
   "settings.update": "更新",
   "settings.updating": "更新中...",
@@ -164,6 +252,8 @@ const zhCN: Record<string, string> = {
   "settings.depModeSystemDesc": "使用系统 PATH 中已安装的二进制文件。需要手动安装。",
   "settings.depModeLabel": "如何管理依赖？",
   "settings.installAll": "安装所有缺失项",
+  "settings.rescanDeps": "重新扫描",
+  "settings.rescanDepsDesc": "重新查找 yt-dlp 和 ffmpeg，例如在用包管理器安装之后",
   "settings.reinstall": "重新安装",
 
   "theme.dark": "深色",
@@ -177,6 +267,94 @@ const zhCN: Record<string, string> = {
   "tray.quit": "退出",
   "settings.minimizeToTray": "最小化到托盘",
   "settings.minimizeToTrayDesc": "关闭窗口时最小化到系统托盘",
+  "settings.singleVideoDefault": "播放列表链接仅下载单个视频",
+  "settings.singleVideoDefaultDesc": "视频链接包含播放列表时，仅下载该视频",
+  "settings.autoContainer": "智能容器转换",
+  "settings.autoContainerDesc": "转换为其他容器时，编解码器兼容则重新封装（快速、无损），仅在不兼容时重新编码（较慢、有损）。每次下载前会增加一次简短的格式检查",
+  "settings.verifyDownloads": "校验下载",
+  "settings.startupNetworkCheck": "启动时检查网络",
+  "settings.startupNetworkCheckDesc": "在后台检查 GitHub 和 YouTube 是否可访问，无法访问时提示",
+  "settings.verifyDownloadsDesc": "使用 ffprobe 检查每个完成的文件，并与源时长比较。未通过的文件会在历史记录中标记为可疑。每次下载会多花几秒",
+  "settings.similarityFingerprints": "查找相似下载",
+  "settings.similarityFingerprintsDesc": "按标题和时长比对历史记录，找出以其他画质保存的同一视频。检查新网址时会向网站查询信息",
+  "settings.retrySuspectDownloads": "重新下载可疑文件",
+  "settings.retrySuspectDownloadsDesc": "校验标记为可疑的文件会再下载一次",
+  "settings.maxFilesize": "最大文件大小",
+  "settings.maxFilesizeDesc": "跳过超过此大小的下载（例如 2G、500M）。留空表示不限制",
+  "settings.maxFilesizeInvalid": "大小无效。请使用数字加 K、M、G 或 T（例如 2G）",
+  "settings.monthlyDataCap": "每月流量上限",
+  "settings.monthlyDataCapDesc": "本月下载量接近此值时发出警告（例如 50G）。留空表示不限制",
+  "settings.dataUsage": "今天 {today} · 本月 {month} · 累计 {total}",
+  "settings.dataCapUsed": "已用上限的 {percent}%",
+  "settings.headers": "HTTP 请求头",
+  "settings.headersDesc": "随每次下载发送。部分网站需要 Referer（嵌入视频的页面）或浏览器 User-Agent。额外请求头每行一个，格式为“名称: 值”",
+  "settings.hostLists": "允许和屏蔽的网站",
+  "settings.hostListsDesc": "每行一个主机。*.example.com 也包括其子域名。屏蔽优先；允许列表不为空时，只能从其中的网站下载。",
+  "settings.hostAllowlist": "仅允许",
+  "settings.hostDenylist": "屏蔽",
+  "settings.codecPreference": "首选视频编码",
+  "settings.codecPreferenceDesc": "以逗号分隔，优先的在前 (av01、vp9、h265、h264 等)。视频提供时使用，实际下载的编码会记录在历史中。",
+  "settings.userAgent": "User-Agent",
+  "settings.referer": "Referer 网址",
+  "settings.extraHeaders": "额外请求头",
+  "settings.outputPathsByType": "按文件类型分文件夹",
+  "settings.outputPathsByTypeDesc": "将视频、纯音频下载、字幕和缩略图保存到不同文件夹。未指定文件夹的类型使用下载文件夹",
+  "settings.outputType.video": "视频",
+  "settings.outputType.audio": "音频",
+  "settings.outputType.subtitle": "字幕",
+  "settings.outputType.thumbnail": "缩略图",
+  "settings.outputTypeDefault": "下载文件夹",
+  "settings.outputTypeClear": "清除",
+  "settings.logBurstPolicy": "日志突发",
+  "settings.logBurstPolicyDesc": "每秒超过约 200 行日志时的处理方式。警告和错误始终保留。",
+  "settings.logBurstPolicy.full": "全部保留",
+  "settings.logBurstPolicy.sampled": "每 10 行取 1 行",
+  "settings.logBurstPolicy.summarized": "汇总",
+  "settings.logMaxPageSize": "日志查看器每页上限",
+  "settings.logMaxPageSizeDesc": "每页加载的最大日志条数。内存较小的设备上请调低（最大 2000）",
+  "settings.cleanupIdleSecs": "清理前空闲时间（秒）",
+  "settings.cleanupIdleSecsDesc": "日志裁剪和数据库压缩会等到这段时间内没有下载时再运行",
+  "settings.cleanupNow": "空闲时清理",
+  "settings.cleanupScheduled": "队列空闲后将执行清理",
+  "settings.queueWatchdogSecs": "队列监视间隔（秒）",
+  "settings.queueWatchdogSecsDesc": "如果没有任务在运行而排队的下载却不开始，就重新启动队列。按此间隔检查；0 表示关闭",
+  "settings.queueWatchdogCheck": "立即检查",
+  "settings.queueWatchdogRestarted": "队列卡住了，已重新启动（已开始 {count} 个）",
+  "settings.queueWatchdogOk": "队列运行正常",
+  "settings.backup": "备份",
+  "settings.backupDesc": "将设置、队列、历史和日志保存到一个压缩包",
+  "settings.backupRedact": "在导出的设置中隐藏敏感信息",
+  "settings.backupExport": "导出",
+  "settings.backupReplace": "导入时替换现有数据",
+  "settings.backupImport": "导入",
+  "settings.backupExported": "备份已保存",
+  "settings.backupImported": "已导入 {history} 条历史、{queue} 个任务和 {logs} 条日志",
+  "settings.backupSettingsRestored": "设置已恢复",
+  "settings.diagnostics": "诊断信息",
+  "settings.diagnosticsDesc": "用于问题报告的应用信息、健康检查、已遮蔽的设置和最近日志",
+  "settings.diagnosticsExported": "诊断信息已保存",
+  "settings.maxRetries": "自动重试",
+  "settings.maxRetriesDesc": "网络或限流错误后重试，每次等待更久（0 为关闭）。第二个框：首次等待秒数",
+  "settings.retryBackoff": "首次重试等待（秒）",
+  "settings.downloadLimits": "下载限制",
+  "settings.downloadLimitsDesc": "每次会话和每天最多启动的任务数。超过限制后任务保持等待。留空表示不限制",
+  "settings.pauseBelowBattery": "电量低时暂停",
+  "settings.pauseBelowBatteryDesc": "使用电池且电量低于此值 (%) 时不开始新的下载。留空则从不暂停",
+  "settings.batteryUnavailable": "此系统无法获取电池状态",
+  "settings.batteryPaused": "电池 {percent}%：新的下载已暂停",
+  "settings.batteryOnBattery": "使用电池 {percent}%",
+  "settings.batteryCharging": "已接通电源 ({percent}%)",
+  "settings.pauseAboveCpu": "CPU 繁忙时暂停",
+  "settings.pauseAboveCpuDesc": "当 CPU 总使用率持续高于此值（%）时不开始新的下载，例如游戏或渲染时。留空表示从不暂停",
+  "settings.cpuSampling": "正在测量 CPU 使用率...",
+  "settings.cpuPaused": "CPU 为 {percent}%：新的下载已暂停",
+  "settings.cpuUsage": "CPU 为 {percent}%",
+  "settings.cpuSampleSecs": "采样间隔（秒）",
+  "settings.cpuSustainSecs": "暂停或恢复前负载需持续的秒数",
+  "settings.sessionLimit": "每次会话",
+  "settings.dailyLimit": "每天",
+  "settings.limitUsed": "已用 {used}/{limit}",
+  "settings.limitUsedUnlimited": "已用 {used}",
 
   // Update
   "update.checkUpdate": "检查更新",
@@ -198,6 +376,7 @@ const zhCN: Record<string, string> = {
   "logs.searchPlaceholder": "搜索日志...",
   "logs.empty": "暂无日志",
   "logs.live": "实时",
+  "logs.activity": "最近 24 小时的活动",
   "logs.clearLogs": "清除",
   "logs.clearConfirm": "清除所有日志？",
   "logs.cleared": "日志已清除",
@@ -18,9 +18,17 @@ const zhTW: Record<string, string> = {
   "layout.notInstalled": "未安裝",
   "layout.ffmpegNote": "同時安裝ffmpeg可支援最佳畫質影片合併。",
   "layout.noActiveDownloads": "沒有進行中的下載",
+  "layout.live": "直播",
+  "layout.networkUnreachable": "部分主機無法連線。安裝相依元件或下載可能失敗，請檢查防火牆或 Proxy",
+  "layout.networkRetest": "重新檢測",
+  "layout.networkDismiss": "關閉",
+  "layout.drainWaiting": "{count} 個下載完成後結束...",
+  "layout.drain.cancelling": "已達時間上限，正在取消剩餘下載...",
+  "layout.drain.exiting": "下載已完成，正在結束...",
   "layout.cancelAll": "全部取消",
   "layout.queueAdded": "{count}部影片已加入佇列。",
   "layout.downloadComplete": "{title}下載完成。",
+  "layout.dataCapWarning": "本月下載量已達流量上限的 {percent}%",
   "layout.queued": "排隊中",
   "layout.recent": "最近完成",
   "layout.viewAll": "檢視全部",
@@ -64,7 +72,12 @@ const zhTW: Record<string, string> = {
   "debug.resetDone": "重置完成。正在重新啟動...",
 
   "download.invalidUrl": "無效的URL",
+  "download.partOfPlaylist": "此影片屬於一個播放清單。僅下載此影片。",
+  "download.openedAsPlaylist": "此連結已作為播放清單開啟。",
+  "download.wholePlaylistInstead": "改為下載整個播放清單",
+  "download.singleVideoInstead": "僅此影片",
   "download.alreadyInQueue": "此影片已在下載佇列中。",
+  "download.videoBlocked": "此影片在封鎖清單中，不會被下載。",
   "download.analyzing": "正在取得中繼資料...",
   "download.loadingFormats": "正在載入影片格式...",
   "download.urlPlaceholder": "貼上任意影片URL（YouTube、TikTok、Twitter、Vimeo...）",
@@ -92,6 +105,9 @@ const zhTW: Record<string, string> = {
   "download.uploaderFolder": "上傳者資料夾",
   "download.uploadDate": "上傳日期",
   "download.videoId": "影片ID",
+  "download.templateWarnings": "{count} 個範本警告",
+  "download.caseCollision": "不區分大小寫的資料夾",
+  "download.caseCollisionHint": "此資料夾將 'Title' 和 'title' 視為同一檔案，標題僅大小寫不同的影片會互相覆寫。請在檔名中加入影片 ID。",
   "download.advancedTooltip": "直接輸入yt-dlp範本語法",
   "download.uploaderFolderTooltip": "依頻道/上傳者名稱建立子資料夾",
   "download.uploadDateTooltip": "在檔案名稱前加上上傳日期",
@@ -101,13 +117,43 @@ const zhTW: Record<string, string> = {
   "download.downloadFailed": "下載失敗",
   "download.skippedQueue": "{count}部影片已略過（已在佇列中）。",
   "download.skippedExists": "{count}部影片已略過（已下載）。",
+  "download.skippedBlocked": "{count}部影片已略過（已封鎖）。",
+  "download.importFromFile": "從檔案匯入 URL",
+  "download.urlListFile": "URL 清單",
+  "download.importSummary": "已匯入 {queued} 部影片；略過 {skipped} 部，{failed} 部無效或失敗。",
+  "download.audioLanguage": "音訊語言",
+  "download.audioLanguageDefault": "原始音訊",
+  "download.liveFromStart": "從頭開始",
+  "download.liveFromStartHint": "從直播開頭錄製，而不是從現在開始",
+  "download.subtitlesOnly": "僅字幕",
+  "download.subtitlesOnlyHint": "只將字幕儲存為 SRT 檔案，不下載影片",
+  "download.subtitleLangs": "字幕語言，以逗號分隔（例如 en, ja.*, all）",
+  "download.testFormat": "測試選擇",
+  "download.testFormatHint": "不下載，顯示 yt-dlp 在目前設定下會選擇的格式",
+  "download.testFormatFailed": "測試失敗：{error}",
+  "download.suggestFormat": "推薦格式",
+  "download.suggestFormatHint": "依畫質與大小之比為此影片的格式排序",
+  "download.noFormatRecs": "沒有已知大小的格式可供比較",
+  "download.recommended": "推薦",
+  "download.smallestAcceptable": "最小可接受",
+  "download.ffmpegMissing": "以下選項需要 ffmpeg，但尚未安裝。它們將失敗或被略過：",
+  "download.ffmpegFeature.merge": "合併影片和音訊",
+  "download.ffmpegFeature.recode": "重新編碼",
+  "download.ffmpegFeature.embedMetadata": "嵌入中繼資料",
+  "download.ffmpegFeature.embedThumbnail": "嵌入縮圖",
+  "download.ffmpegFeature.embedChapters": "嵌入章節",
+  "download.ffmpegFeature.convertSubs": "轉換字幕",
   "download.redownload": "重新下載",
   "download.cancel": "取消",
   "download.alreadyDownloaded": "「{title}」已經下載過。",
+  "download.fileOnDisk": "磁碟上已存在：{path}",
+  "download.overwrite": "覆寫",
+  "download.saveAsCopy": "另存為副本",
   "download.error": "錯誤",
   "download.duplicateFound": "發現重複",
   "download.include": "包含:",
   "download.cookie": "Cookie:",
+  "download.defaultBrowser": "{browser}（預設）",
   "download.concurrent": "並行:",
   "download.emptyState": "貼上URL開始下載",
 
@@ -132,6 +178,37 @@ const zhTW: Record<string, string> = {
   "history.searchPlaceholder": "依標題搜尋...",
   "history.empty": "尚無下載紀錄",
   "history.deleteConfirm": "確定要刪除此項目嗎？",
+  "history.superseded": "已重新下載",
+  "history.suspect": "可疑檔案",
+  "history.bulkTagPlaceholder": "標籤",
+  "history.bulkTag": "為全部 {count} 個符合項目加上標籤",
+  "history.bulkUntag": "從符合項目移除標籤",
+  "history.bulkTagResult": "已更新 {count} 項",
+  "history.favoritesOnly": "僅顯示收藏",
+  "history.favorite": "加入收藏",
+  "history.unfavorite": "取消收藏",
+  "history.sortBy": "排序方式",
+  "history.sortDate": "日期",
+  "history.sortSize": "大小",
+  "history.sortDuration": "長度",
+  "history.sortTitle": "標題",
+  "history.sortAsc": "遞增",
+  "history.sortDesc": "遞減",
+  "history.renameFiles": "重新命名檔案",
+  "history.renameHint": "依新的輸出範本重新命名已下載的檔案。檔案保留在原資料夾；請先預覽再套用。",
+  "history.renameTemplate": "輸出範本",
+  "history.renamePreview": "預覽",
+  "history.renameApply": "重新命名 {count} 個",
+  "history.renameConfirm": "要重新命名磁碟上的 {count} 個檔案嗎？",
+  "history.renameDone": "已重新命名 {count} 個檔案",
+  "history.renameNothing": "所有檔案已符合此範本",
+  "history.renameStatus.ready": "重新命名",
+  "history.renameStatus.collision": "名稱衝突",
+  "history.renameStatus.missing": "檔案遺失",
+  "history.renameStatus.unresolved": "無法填入",
+  "history.renameStatus.unchanged": "未變更",
+  "history.exportPlaylist": "將本頁匯出為播放清單",
+  "history.playlistExported": "已儲存包含 {count} 項的播放清單；{skipped} 項因檔案遺失已略過。",
 
   "settings.title": "設定",
   "settings.subtitle": "設定下載偏好",
@@ -150,10 +227,21 @@ const zhTW: Record<string, string> = {
   "settings.theme": "佈景主題",
   "settings.general": "一般",
   "settings.appearance": "外觀",
+  "settings.issuesTitle": "修正以下問題之前，設定不會被儲存",
+  "settings.warningsTitle": "已儲存，但請檢查以下設定",
   "settings.dependencies": "依賴項",
   "settings.notInstalled": "未安裝",
   "settings.appManaged": "應用程式管理",
+  "settings.ffmpegPinned": "已固定 {version}",
+  "settings.ffmpegPin": "固定版本",
+  "settings.ffmpegRollback": "復原",
+  "settings.ffmpegUnpin": "取消固定",
+  "settings.ffmpegUnpinned": "已取消固定；更新將跟隨最新版本",
+  "settings.ytdlpBackupCount": "保留的備份數",
+  "settings.ytdlpBackupBroken": "無法執行",
+  "settings.ytdlpRollback": "回復",
   "settings.systemPath": "系統PATH",
+  "settings.registeredBinary": "已註冊",
   "settings.install": "安裝",
   "settings.update": "更新",
   "settings.updating": "更新中...",
@@ -164,6 +252,8 @@ const zhTW: Record<string, string> = {
   "settings.depModeSystemDesc": "使用系統 PATH 中已安裝的二進位檔案。需要手動安裝。",
   "settings.depModeLabel": "如何管理依賴？",
   "settings.installAll": "安裝所有缺失項",
+  "settings.rescanDeps": "重新掃描",
+  "settings.rescanDepsDesc": "重新尋找 yt-dlp 和 ffmpeg，例如在用套件管理器安裝之後",
   "settings.reinstall": "重新安裝",
 
   "theme.dark": "深色",
@@ -177,6 +267,94 @@ const zhTW: Record<string, string> = {
   "tray.quit": "退出",
   "settings.minimizeToTray": "最小化到系統匣",
   "settings.minimizeToTrayDesc": "關閉視窗時最小化到系統匣",
+  "settings.singleVideoDefault": "播放清單連結僅下載單一影片",
+  "settings.singleVideoDefaultDesc": "影片連結包含播放清單時，僅下載該影片",
+  "settings.autoContainer": "智慧容器轉換",
+  "settings.autoContainerDesc": "轉換為其他容器時，編解碼器相容則重新封裝（快速、無損），僅在不相容時重新編碼（較慢、有損）。每次下載前會增加一次簡短的格式檢查",
+  "settings.verifyDownloads": "驗證下載",
+  "settings.startupNetworkCheck": "啟動時檢查網路",
+  "settings.startupNetworkCheckDesc": "在背景檢查 GitHub 與 YouTube 是否可連線，無法連線時提示",
+  "settings.verifyDownloadsDesc": "使用 ffprobe 檢查每個完成的檔案，並與來源長度比較。未通過的檔案會在歷史紀錄中標記為可疑。每次下載會多花幾秒",
+  "settings.similarityFingerprints": "尋找相似下載",
+  "settings.similarityFingerprintsDesc": "依標題與長度比對歷史紀錄，找出以其他畫質儲存的同一影片。檢查新網址時會向網站查詢資訊",
+  "settings.retrySuspectDownloads": "重新下載可疑檔案",
+  "settings.retrySuspectDownloadsDesc": "驗證標記為可疑的檔案會再下載一次",
+  "settings.maxFilesize": "最大檔案大小",
+  "settings.maxFilesizeDesc": "略過超過此大小的下載（例如 2G、500M）。留空表示不限制",
+  "settings.maxFilesizeInvalid": "大小無效。請使用數字加 K、M、G 或 T（例如 2G）",
+  "settings.monthlyDataCap": "每月流量上限",
+  "settings.monthlyDataCapDesc": "本月下載量接近此值時發出警告（例如 50G）。留空表示不限制",
+  "settings.dataUsage": "今天 {today} · 本月 {month} · 累計 {total}",
+  "settings.dataCapUsed": "已用上限的 {percent}%",
+  "settings.headers": "HTTP 標頭",
+  "settings.headersDesc": "隨每次下載傳送。部分網站需要 Referer（嵌入影片的頁面）或瀏覽器 User-Agent。額外標頭每行一個，格式為「名稱: 值」",
+  "settings.hostLists": "允許與封鎖的網站",
+  "settings.hostListsDesc": "每行一個主機。*.example.com 也包含其子網域。封鎖優先；允許清單不為空時，只能從其中的網站下載。",
+  "settings.hostAllowlist": "僅允許",
+  "settings.hostDenylist": "封鎖",
+  "settings.codecPreference": "偏好的影片編碼",
+  "settings.codecPreferenceDesc": "以逗號分隔，優先的在前 (av01、vp9、h265、h264 等)。影片提供時使用，實際下載的編碼會記錄在歷史中。",
+  "settings.userAgent": "User-Agent",
+  "settings.referer": "Referer 網址",
+  "settings.extraHeaders": "額外標頭",
+  "settings.outputPathsByType": "依檔案類型分資料夾",
+  "settings.outputPathsByTypeDesc": "將影片、純音訊下載、字幕和縮圖儲存到不同資料夾。未指定資料夾的類型使用下載資料夾",
+  "settings.outputType.video": "影片",
+  "settings.outputType.audio": "音訊",
+  "settings.outputType.subtitle": "字幕",
+  "settings.outputType.thumbnail": "縮圖",
+  "settings.outputTypeDefault": "下載資料夾",
+  "settings.outputTypeClear": "清除",
+  "settings.logBurstPolicy": "日誌突發",
+  "settings.logBurstPolicyDesc": "每秒超過約 200 行日誌時的處理方式。警告與錯誤一律保留。",
+  "settings.logBurstPolicy.full": "全部保留",
+  "settings.logBurstPolicy.sampled": "每 10 行取 1 行",
+  "settings.logBurstPolicy.summarized": "彙總",
+  "settings.logMaxPageSize": "日誌檢視器每頁上限",
+  "settings.logMaxPageSizeDesc": "每頁載入的最大日誌筆數。記憶體較少的裝置請調低（最多 2000）",
+  "settings.cleanupIdleSecs": "清理前閒置時間（秒）",
+  "settings.cleanupIdleSecsDesc": "日誌裁剪和資料庫壓縮會等到這段時間內沒有下載時再執行",
+  "settings.cleanupNow": "閒置時清理",
+  "settings.cleanupScheduled": "佇列閒置後將執行清理",
+  "settings.queueWatchdogSecs": "佇列監視間隔（秒）",
+  "settings.queueWatchdogSecsDesc": "如果沒有任務在執行而排隊的下載卻不開始，就重新啟動佇列。依此間隔檢查；0 表示關閉",
+  "settings.queueWatchdogCheck": "立即檢查",
+  "settings.queueWatchdogRestarted": "佇列卡住了，已重新啟動（已開始 {count} 個）",
+  "settings.queueWatchdogOk": "佇列運作正常",
+  "settings.backup": "備份",
+  "settings.backupDesc": "將設定、佇列、歷史與記錄儲存到一個壓縮檔",
+  "settings.backupRedact": "在匯出的設定中隱藏機密資訊",
+  "settings.backupExport": "匯出",
+  "settings.backupReplace": "匯入時取代現有資料",
+  "settings.backupImport": "匯入",
+  "settings.backupExported": "備份已儲存",
+  "settings.backupImported": "已匯入 {history} 筆歷史、{queue} 個工作和 {logs} 筆記錄",
+  "settings.backupSettingsRestored": "設定已還原",
+  "settings.diagnostics": "診斷資訊",
+  "settings.diagnosticsDesc": "用於問題回報的應用程式資訊、健康檢查、已遮蔽的設定和最近日誌",
+  "settings.diagnosticsExported": "診斷資訊已儲存",
+  "settings.maxRetries": "自動重試",
+  "settings.maxRetriesDesc": "網路或限流錯誤後重試，每次等待更久（0 為關閉）。第二個欄位：首次等待秒數",
+  "settings.retryBackoff": "首次重試等待（秒）",
+  "settings.downloadLimits": "下載限制",
+  "settings.downloadLimitsDesc": "每次工作階段和每天最多啟動的任務數。超過限制後任務保持等待。留空表示不限制",
+  "settings.pauseBelowBattery": "電量低時暫停",
+  "settings.pauseBelowBatteryDesc": "使用電池且電量低於此值 (%) 時不開始新的下載。留空則永不暫停",
+  "settings.batteryUnavailable": "此系統無法取得電池狀態",
+  "settings.batteryPaused": "電池 {percent}%：新的下載已暫停",
+  "settings.batteryOnBattery": "使用電池 {percent}%",
+  "settings.batteryCharging": "已接上電源 ({percent}%)",
+  "settings.pauseAboveCpu": "CPU 忙碌時暫停",
+  "settings.pauseAboveCpuDesc": "當 CPU 總使用率持續高於此值（%）時不開始新的下載，例如遊戲或算圖時。留空表示永不暫停",
+  "settings.cpuSampling": "正在測量 CPU 使用率...",
+  "settings.cpuPaused": "CPU 為 {percent}%：新的下載已暫停",
+  "settings.cpuUsage": "CPU 為 {percent}%",
+  "settings.cpuSampleSecs": "取樣間隔（秒）",
+  "settings.cpuSustainSecs": "暫停或恢復前負載需持續的秒數",
+  "settings.sessionLimit": "每次工作階段",
+  "settings.dailyLimit": "每天",
+  "settings.limitUsed": "已用 {used}/{limit}",
+  "settings.limitUsedUnlimited": "已用 {used}",
 
   // Update
   "update.checkUpdate": "檢查更新",
@@ -198,6 +376,7 @@ const zhTW: Record<string, string> = {
   "logs.searchPlaceholder": "搜尋日誌...",
   "logs.empty": "尚無日誌",
   "logs.live": "即時",
+  "logs.activity": "最近 24 小時的活動",
   "logs.clearLogs": "清除",
   "logs.clearConfirm": "清除所有日誌？",
   "logs.cleared": "日誌已清除",
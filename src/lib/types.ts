@@ -13,10 +13,13 @@ export interface ActiveDownload {
   errorMessage: string | null
   createdAt: number
   completedAt: number | null
+  liveBytes?: number | null
 }
 
 export interface ProgressCacheEntry {
   progress: number
   speed: string | null
   eta: string | null
+  /** Set for live downloads, which grow without a known total */
+  liveBytes?: number | null
 }